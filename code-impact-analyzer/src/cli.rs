@@ -1,5 +1,12 @@
 use clap::{Parser, ValueEnum};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::str::FromStr;
+use crate::impact_tracer::{Direction, LabelStyle, Risk};
+use crate::git_diff::GitDiffMode;
+use crate::code_index::TableNormalization;
+use crate::language_parser::{LanguageOverride, Visibility};
+use crate::types::{HttpEndpoint, ResourceId};
 
 /// 代码影响分析工具 - 分析 Git patch 文件对代码库的影响
 #[derive(Parser, Debug)]
@@ -12,13 +19,34 @@ pub struct CliArgs {
     pub workspace_path: PathBuf,
 
     /// Git diff 补丁文件目录路径，包含以项目命名的多个 patch 文件
-    #[arg(short = 'd', long = "diff", value_name = "PATH")]
-    pub diff_path: PathBuf,
+    #[arg(short = 'd', long = "diff", value_name = "PATH", required_unless_present_any = ["trace_endpoint", "trace_resource", "list_endpoints", "report_unresolved", "dump_index", "dry_run", "staged", "working"])]
+    pub diff_path: Option<PathBuf>,
 
-    /// 输出格式：dot, json, 或 mermaid
+    /// 分析工作空间 Git 仓库中已暂存的变更（HEAD 与暂存区之间），代替 --diff；需要启用 git 特性
+    #[arg(long = "staged", conflicts_with_all = ["diff_path", "working"])]
+    pub staged: bool,
+
+    /// 分析工作空间 Git 仓库中未暂存的变更（暂存区与工作区之间），代替 --diff；需要启用 git 特性
+    #[arg(long = "working", conflicts_with = "diff_path")]
+    pub working: bool,
+
+    /// 输出格式：dot, json, mermaid, 或 sarif
     #[arg(short = 'o', long = "output-format", value_enum, default_value = "dot")]
     pub output_format: OutputFormat,
 
+    /// 输出文件路径，默认输出到标准输出
+    #[arg(long = "output", value_name = "PATH")]
+    pub output_path: Option<PathBuf>,
+    
+    /// 以 JSON Lines 事件流输出影响图：每行一个 `{"event":"node",...}` 或 `{"event":"edge",...}` 对象，忽略 --output-format
+    #[arg(long = "stream-jsonl")]
+    pub stream_jsonl: bool,
+    
+    /// 方法节点标签的样式：full（完整全限定名）, simple（只保留类名和方法名）,
+    /// package-initials（包名取首字母、类名取大写字母、方法名截断，如 `c.e.s.OSI::create...`）；节点 ID 始终为完整名称
+    #[arg(long = "label-style", value_enum, default_value = "full")]
+    pub label_style: LabelStyleOption,
+
     /// 追溯的最大深度，防止无限递归
     #[arg(short = 'm', long = "max-depth", default_value = "10")]
     pub max_depth: usize,
@@ -27,10 +55,31 @@ pub struct CliArgs {
     #[arg(short = 'l', long = "log-level", value_enum, default_value = "info")]
     pub log_level: LogLevel,
     
+    /// 记录影响追溯的每个决策（找到的调用者/被调用者、跳过的外部方法、接口解析），
+    /// 用于排查预期节点缺失的原因；以 trace 级别输出，与 --log-level 无关
+    #[arg(long = "trace-debug")]
+    pub trace_debug: bool,
+    
+    /// 运行结束时摘要的着色方式：auto（输出到终端时着色）, always, never
+    #[arg(long = "color", value_enum, default_value = "auto")]
+    pub color: ColorMode,
+    
+    /// 静默模式：不输出分析各步骤的进度日志，只输出最终结果、摘要以及警告和错误
+    #[arg(short = 'q', long = "quiet")]
+    pub quiet: bool,
+    
     /// 强制重建索引，忽略缓存
     #[arg(long = "rebuild-index")]
     pub rebuild_index: bool,
     
+    /// 不将索引写入磁盘（只读工作空间），已有索引仍可加载
+    #[arg(long = "no-save-index")]
+    pub no_save_index: bool,
+    
+    /// 索引目录，默认为工作空间下的 .code-impact-analyzer；与 --no-save-index 一起使用时从该目录加载预先构建的索引
+    #[arg(long = "index-dir", value_name = "PATH", conflicts_with = "index_url")]
+    pub index_dir: Option<PathBuf>,
+    
     /// 清除索引缓存并退出
     #[arg(long = "clear-index")]
     pub clear_index: bool,
@@ -42,6 +91,255 @@ pub struct CliArgs {
     /// 验证索引有效性并退出
     #[arg(long = "verify-index")]
     pub verify_index: bool,
+
+    /// 从该 HTTP 地址获取集中构建的索引（与本地 index.json 格式相同），不在本地构建索引；需要启用 remote-index 特性
+    #[arg(long = "index-url", value_name = "URL")]
+    pub index_url: Option<String>,
+    
+    /// 输出的边方向：downstream, upstream, 或 both
+    #[arg(long = "direction", value_enum, default_value = "both")]
+    pub direction: DirectionFilter,
+    
+    /// 输出的数据库边的最低风险等级：low（读取和写入）或 high（只保留写入）；其他类型的边不受影响
+    #[arg(long = "min-risk", value_enum, value_name = "RISK")]
+    pub min_risk: Option<RiskFilter>,
+    
+    /// 只保留从入口点（HTTP 接口、Kafka 消费者、定时任务、GraphQL 解析器等）经方法调用可达的受影响方法，剔除只被死代码调用的影响
+    #[arg(long = "reachable-only")]
+    pub reachable_only: bool,
+    
+    /// 收缩纯委托方法（方法体只转发一次调用，如 `return delegate.foo();`），调用方直接连接到被委托的方法
+    #[arg(long = "contract-delegations")]
+    pub contract_delegations: bool,
+    
+    /// 作为追溯起点的方法的最低可见性：public, package, 或 all；
+    /// 可见性更低的变更方法（如私有辅助方法）由调用它的、满足可见性的方法代替作为起点
+    #[arg(long = "visibility", value_enum, default_value = "all")]
+    pub visibility: VisibilityFilter,
+    
+    /// 同时从影响图中收缩低于 `--visibility` 的方法，调用方直接连接到其调用的方法
+    #[arg(long = "hide-low-visibility")]
+    pub hide_low_visibility: bool,
+    
+    /// 受影响方法数的上限，超过时在输出结果后以非零状态退出（用于 CI 门禁）
+    #[arg(long = "max-impact", value_name = "N")]
+    pub max_impact: Option<usize>,
+    
+    /// 严格模式：有 patch、源文件或配置文件无法解析时在输出结果后以退出码 2 结束
+    #[arg(long = "strict")]
+    pub strict: bool,
+    
+    /// 最多输出的警告条数；同类警告总是合并为一条并附带次数，超出部分汇总为 `... and N more warnings`
+    #[arg(long = "max-warnings", value_name = "N")]
+    pub max_warnings: Option<usize>,
+    
+    /// 只在指定的服务（工作空间下的顶层目录）内追溯，可重复指定；不指定则不限制
+    #[arg(long = "service", value_name = "NAME")]
+    pub services: Vec<String>,
+    
+    /// Feign 服务名到提供者 application.name 的别名，格式为 SERVICE=APP，可重复指定；
+    /// 服务名与应用名不一致时使 Feign 调用能匹配到提供者
+    #[arg(long = "service-alias", value_name = "SERVICE=APP")]
+    pub service_aliases: Vec<ServiceAlias>,
+    
+    /// 单个 HTTP 端点最多展开的提供者/消费者数量，超出部分合并为一个汇总节点
+    #[arg(long = "max-bridge-fanout", value_name = "N")]
+    pub max_bridge_fanout: Option<usize>,
+    
+    /// 启发式匹配 HTTP 客户端调用时，端点路径至少需要的非参数段数
+    /// 
+    /// 例如 `/` 和 `/api` 几乎能匹配任何调用，设为 2 可避免产生虚假的跨服务边；
+    /// Feign 等精确匹配不受影响。默认为 0，不限制
+    #[arg(long = "min-http-segments", value_name = "N", default_value = "0")]
+    pub min_http_segments: usize,
+    
+    /// 按前 N 个冒号分隔段合并 Redis 键节点，例如 1 表示 `user:1`、`user:2` 都合并为 `user`；N 至少为 1
+    #[arg(
+        long = "redis-namespace-depth",
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub redis_namespace_depth: Option<usize>,
+    
+    /// 匹配 Kafka Topic 前去掉的环境前缀，可重复指定，例如 `prod.` 使监听 `prod.order-events`
+    /// 的消费者与写入 `order-events` 的生产者匹配
+    #[arg(long = "kafka-topic-prefix-strip", value_name = "PREFIX")]
+    pub kafka_topic_prefix_strip: Vec<String>,
+    
+    /// 数据库表名规范化规则（逗号分隔，可选 strip-prefix、singularize、lowercase），
+    /// 使 `t_users`、`user` 等不同命名约定的同一张表合并为一个节点
+    #[arg(long = "table-normalize", value_name = "RULES", value_delimiter = ',')]
+    pub table_normalize: Vec<TableNormalizeRule>,
+    
+    /// YAML 配置中声明 Kafka Topic 的键路径（`.` 分隔），可重复指定；
+    /// 用于键名不含 topic/queue 的 Topic 列表，如 `app.messaging.destinations`
+    #[arg(long = "config-topic-path", value_name = "PATH")]
+    pub config_topic_paths: Vec<String>,
+    
+    /// 按路径 glob 强制指定文件的语言解析器，格式为 GLOB=LANGUAGE，可重复指定；
+    /// 如 `legacy/*.txt=java` 将 `.txt` 按 Java 解析，`vendor/**/*.rs=none` 跳过匹配的文件
+    #[arg(long = "lang-override", value_name = "GLOB=LANGUAGE")]
+    pub lang_overrides: Vec<LanguageOverride>,
+    
+    /// 额外索引的源码根目录（如 `libs-src/` 下的第三方依赖源码），可重复指定；
+    /// 调用其中的方法时继续追溯而不是视为外部调用，工作空间中的同名方法优先
+    #[arg(long = "extra-source-root", value_name = "DIR")]
+    pub extra_source_roots: Vec<PathBuf>,
+    
+    /// 只有非平凡变更（非空行、非纯注释）落在方法内时才将该方法视为变更
+    #[arg(long = "significant-changes-only")]
+    pub significant_changes_only: bool,
+    
+    /// 只有新增行（hunk 中的 `+` 行）落在方法内时才将该方法视为变更，忽略只删除了代码的方法
+    #[arg(long = "added-only")]
+    pub added_only: bool,
+    
+    /// 不规范化 patch 与索引中的文件路径，按原样比较
+    /// 
+    /// 默认比较前先规范化（统一 `/` 分隔符、去掉 `./` 前缀、绝对路径转为相对于工作空间），
+    /// 以兼容索引与 patch 在不同操作系统上生成的情况
+    #[arg(long = "no-normalize-paths", overrides_with = "normalize_paths")]
+    pub no_normalize_paths: bool,
+    
+    /// 规范化 patch 与索引中的文件路径（默认行为，保留以兼容已有脚本）
+    #[arg(long = "normalize-paths", overrides_with = "no_normalize_paths")]
+    pub normalize_paths: bool,
+    
+    /// 按改动量（重叠的 hunk 数、变更行数）对变更方法排序，并在统计摘要后输出排名
+    #[arg(long = "sort-by-churn")]
+    pub sort_by_churn: bool,
+    
+    /// 提取调用时忽略 getter/setter 等访问器调用（方法名匹配 `^(get|set|is)[A-Z]`），减少图中的噪声边
+    #[arg(long = "ignore-accessors")]
+    pub ignore_accessors: bool,
+    
+    /// 通过 git blame 为方法节点附加最后修改的作者和提交（`last_author`、`last_commit`），需要 `git` 特性
+    #[arg(long = "blame")]
+    pub blame: bool,
+    
+    /// 不使用 diff，以提供该 HTTP 端点的方法为起点追溯，格式为 METHOD:PATH，如 "GET:/api/orders/{id}"
+    #[arg(long = "trace-endpoint", value_name = "METHOD:PATH")]
+    pub trace_endpoint: Option<HttpEndpoint>,
+    
+    /// 不使用 diff，以读写该资源的方法为起点追溯，格式与节点 ID 相同，
+    /// 如 "db:orders"、"kafka:order-created"、"redis:user:"、"http:GET:/api/orders/{id}"
+    #[arg(long = "trace-resource", value_name = "KIND:NAME", conflicts_with = "trace_endpoint")]
+    pub trace_resource: Option<ResourceId>,
+    
+    /// 基线影响图（之前以 JSON 格式输出的结果），只输出相对基线新增的节点和边
+    #[arg(long = "baseline", value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+    
+    /// 仅列出 patch 涉及的变更方法（按全限定名排序）并退出，不追溯影响
+    #[arg(long = "list-changed")]
+    pub list_changed: bool,
+    
+    /// 列出工作空间提供的所有 HTTP 端点（方法、路径、提供者）并退出，不需要 patch
+    #[arg(long = "list-endpoints")]
+    pub list_endpoints: bool,
+    
+    /// 列出无法解析到已索引方法的调用目标及调用次数并退出，不需要 patch，用于评估解析覆盖率
+    #[arg(long = "report-unresolved")]
+    pub report_unresolved: bool,
+    
+    /// 构建或加载索引后将完整索引（方法、调用映射、提供者/消费者映射）以 JSON 输出并退出，不需要 patch
+    #[arg(long = "dump-index")]
+    pub dump_index: bool,
+    
+    /// 试运行：报告各语言将被索引的文件数和没有匹配解析器的文件后退出，不解析文件也不构建索引，不需要 patch
+    #[arg(long = "dry-run")]
+    pub dry_run: bool,
+}
+
+impl CliArgs {
+    /// 从 Git 仓库读取变更的方式，未指定 `--staged` / `--working` 时为 `None`
+    pub fn git_diff_mode(&self) -> Option<GitDiffMode> {
+        if self.staged {
+            Some(GitDiffMode::Staged)
+        } else if self.working {
+            Some(GitDiffMode::Working)
+        } else {
+            None
+        }
+    }
+    
+    /// 由 `--service-alias` 构造服务名到应用名的映射，同一服务名指定多次时以最后一次为准
+    pub fn service_alias_map(&self) -> HashMap<String, String> {
+        self.service_aliases.iter()
+            .map(|alias| (alias.service.clone(), alias.application.clone()))
+            .collect()
+    }
+    
+    /// 由 `--table-normalize` 指定的规则构造表名规范化配置
+    pub fn table_normalization(&self) -> TableNormalization {
+        TableNormalization {
+            strip_prefix: self.table_normalize.contains(&TableNormalizeRule::StripPrefix),
+            singularize: self.table_normalize.contains(&TableNormalizeRule::Singularize),
+            lowercase: self.table_normalize.contains(&TableNormalizeRule::Lowercase),
+        }
+    }
+}
+
+impl Default for CliArgs {
+    /// 与命令行只指定必需参数时各选项的默认值一致，工作空间路径为空
+    fn default() -> Self {
+        Self {
+            workspace_path: PathBuf::new(),
+            diff_path: None,
+            staged: false,
+            working: false,
+            output_format: OutputFormat::Dot,
+            output_path: None,
+            stream_jsonl: false,
+            label_style: LabelStyleOption::Full,
+            max_depth: 10,
+            log_level: LogLevel::Info,
+            trace_debug: false,
+            color: ColorMode::Auto,
+            quiet: false,
+            rebuild_index: false,
+            no_save_index: false,
+            index_dir: None,
+            clear_index: false,
+            index_info: false,
+            verify_index: false,
+            index_url: None,
+            list_changed: false,
+            list_endpoints: false,
+            report_unresolved: false,
+            dump_index: false,
+            dry_run: false,
+            direction: DirectionFilter::Both,
+            min_risk: None,
+            reachable_only: false,
+            contract_delegations: false,
+            visibility: VisibilityFilter::All,
+            hide_low_visibility: false,
+            max_impact: None,
+            strict: false,
+            max_warnings: None,
+            services: Vec::new(),
+            service_aliases: Vec::new(),
+            max_bridge_fanout: None,
+            min_http_segments: 0,
+            redis_namespace_depth: None,
+            kafka_topic_prefix_strip: Vec::new(),
+            table_normalize: Vec::new(),
+            config_topic_paths: Vec::new(),
+            lang_overrides: Vec::new(),
+            extra_source_roots: Vec::new(),
+            significant_changes_only: false,
+            added_only: false,
+            no_normalize_paths: false,
+            normalize_paths: false,
+            sort_by_churn: false,
+            ignore_accessors: false,
+            blame: false,
+            trace_endpoint: None,
+            trace_resource: None,
+            baseline: None,
+        }
+    }
 }
 
 /// 输出格式枚举
@@ -53,6 +351,154 @@ pub enum OutputFormat {
     Json,
     /// Mermaid 图表格式
     Mermaid,
+    /// SARIF 2.1.0 格式（用于 GitHub code scanning）
+    Sarif,
+}
+
+/// 边方向过滤枚举
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DirectionFilter {
+    /// 仅下游（被变更影响的调用链）
+    Downstream,
+    /// 仅上游（调用变更方法的调用链）
+    Upstream,
+    /// 上下游都输出
+    Both,
+}
+
+impl DirectionFilter {
+    /// 转换为需要保留的边方向，`Both` 表示不过滤
+    pub fn to_direction(&self) -> Option<Direction> {
+        match self {
+            DirectionFilter::Downstream => Some(Direction::Downstream),
+            DirectionFilter::Upstream => Some(Direction::Upstream),
+            DirectionFilter::Both => None,
+        }
+    }
+}
+
+/// 方法节点标签样式枚举
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LabelStyleOption {
+    /// 完整的全限定名
+    Full,
+    /// 只保留类名和方法名
+    Simple,
+    /// 包名取首字母、类名取大写字母、方法名截断
+    PackageInitials,
+}
+
+impl LabelStyleOption {
+    /// 转换为影响图使用的标签样式
+    pub fn to_label_style(&self) -> LabelStyle {
+        match self {
+            LabelStyleOption::Full => LabelStyle::Full,
+            LabelStyleOption::Simple => LabelStyle::Simple,
+            LabelStyleOption::PackageInitials => LabelStyle::PackageInitials,
+        }
+    }
+}
+
+/// 边风险等级过滤枚举
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RiskFilter {
+    /// 低风险及以上（读取和写入）
+    Low,
+    /// 只有高风险（写入）
+    High,
+}
+
+impl RiskFilter {
+    /// 转换为需要保留的最低风险等级
+    pub fn min_risk(&self) -> Risk {
+        match self {
+            RiskFilter::Low => Risk::Low,
+            RiskFilter::High => Risk::High,
+        }
+    }
+}
+
+/// 方法可见性过滤枚举
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum VisibilityFilter {
+    /// 只有公开方法
+    Public,
+    /// 公开和包级可见的方法
+    Package,
+    /// 所有方法，不过滤
+    All,
+}
+
+impl VisibilityFilter {
+    /// 转换为最低可见性，`All` 表示不过滤
+    pub fn min_visibility(&self) -> Option<Visibility> {
+        match self {
+            VisibilityFilter::Public => Some(Visibility::Public),
+            VisibilityFilter::Package => Some(Visibility::Package),
+            VisibilityFilter::All => None,
+        }
+    }
+}
+
+/// Feign 服务名到提供者 application.name 的别名
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceAlias {
+    /// Feign 客户端使用的服务名
+    pub service: String,
+    /// 提供者的 application.name
+    pub application: String,
+}
+
+impl FromStr for ServiceAlias {
+    type Err = String;
+    
+    /// 解析 `SERVICE=APP` 形式的别名
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((service, application)) if !service.trim().is_empty() && !application.trim().is_empty() => {
+                Ok(ServiceAlias {
+                    service: service.trim().to_string(),
+                    application: application.trim().to_string(),
+                })
+            }
+            _ => Err(format!("expected SERVICE=APP, got '{}'", s)),
+        }
+    }
+}
+
+/// 数据库表名规范化规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TableNormalizeRule {
+    /// 去除 `t_` 前缀
+    StripPrefix,
+    /// 复数表名转换为单数
+    Singularize,
+    /// 转换为小写
+    Lowercase,
+}
+
+/// 摘要着色方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorMode {
+    /// 标准错误输出是终端且未设置 `NO_COLOR` 时着色
+    Auto,
+    /// 总是着色
+    Always,
+    /// 从不着色
+    Never,
+}
+
+impl ColorMode {
+    /// 判断是否对标准错误输出着色
+    pub fn enabled(&self) -> bool {
+        use std::io::IsTerminal;
+        
+        match self {
+            ColorMode::Auto => std::env::var_os("NO_COLOR").is_none() && std::io::stderr().is_terminal(),
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        }
+    }
 }
 
 /// 日志级别枚举
@@ -91,23 +537,39 @@ mod tests {
     #[test]
     fn test_cli_args_parsing() {
         // 测试基本参数解析
-        let args = CliArgs::parse_from(&[
+        let args = CliArgs::parse_from([
             "code-impact-analyzer",
             "--workspace", "/path/to/workspace",
             "--diff", "/path/to/patch.diff",
         ]);
 
         assert_eq!(args.workspace_path, PathBuf::from("/path/to/workspace"));
-        assert_eq!(args.diff_path, PathBuf::from("/path/to/patch.diff"));
+        assert_eq!(args.diff_path, Some(PathBuf::from("/path/to/patch.diff")));
         assert!(matches!(args.output_format, OutputFormat::Dot));
         assert_eq!(args.max_depth, 10);
         assert!(matches!(args.log_level, LogLevel::Info));
+        assert!(args.output_path.is_none());
+    }
+
+    #[test]
+    fn test_default_matches_parsed_defaults() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "--workspace", "/path/to/workspace",
+            "--diff", "/path/to/patch.diff",
+        ]);
+        let expected = CliArgs {
+            workspace_path: PathBuf::from("/path/to/workspace"),
+            diff_path: Some(PathBuf::from("/path/to/patch.diff")),
+            ..Default::default()
+        };
+        assert_eq!(format!("{:?}", args), format!("{:?}", expected));
     }
 
     #[test]
     fn test_cli_args_with_all_options() {
         // 测试所有参数
-        let args = CliArgs::parse_from(&[
+        let args = CliArgs::parse_from([
             "code-impact-analyzer",
             "-w", "/workspace",
             "-d", "/patch.diff",
@@ -117,7 +579,7 @@ mod tests {
         ]);
 
         assert_eq!(args.workspace_path, PathBuf::from("/workspace"));
-        assert_eq!(args.diff_path, PathBuf::from("/patch.diff"));
+        assert_eq!(args.diff_path, Some(PathBuf::from("/patch.diff")));
         assert!(matches!(args.output_format, OutputFormat::Json));
         assert_eq!(args.max_depth, 5);
         assert!(matches!(args.log_level, LogLevel::Debug));
@@ -126,7 +588,7 @@ mod tests {
     #[test]
     fn test_output_format_variants() {
         // 测试 DOT 格式
-        let args = CliArgs::parse_from(&[
+        let args = CliArgs::parse_from([
             "code-impact-analyzer",
             "-w", "/workspace",
             "-d", "/patch.diff",
@@ -135,7 +597,7 @@ mod tests {
         assert!(matches!(args.output_format, OutputFormat::Dot));
 
         // 测试 JSON 格式
-        let args = CliArgs::parse_from(&[
+        let args = CliArgs::parse_from([
             "code-impact-analyzer",
             "-w", "/workspace",
             "-d", "/patch.diff",
@@ -144,13 +606,22 @@ mod tests {
         assert!(matches!(args.output_format, OutputFormat::Json));
 
         // 测试 Mermaid 格式
-        let args = CliArgs::parse_from(&[
+        let args = CliArgs::parse_from([
             "code-impact-analyzer",
             "-w", "/workspace",
             "-d", "/patch.diff",
             "-o", "mermaid",
         ]);
         assert!(matches!(args.output_format, OutputFormat::Mermaid));
+
+        // 测试 SARIF 格式
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "-o", "sarif",
+        ]);
+        assert!(matches!(args.output_format, OutputFormat::Sarif));
     }
 
     #[test]
@@ -165,7 +636,7 @@ mod tests {
         ];
 
         for (level_str, _expected) in levels {
-            let args = CliArgs::parse_from(&[
+            let args = CliArgs::parse_from([
                 "code-impact-analyzer",
                 "-w", "/workspace",
                 "-d", "/patch.diff",
@@ -186,10 +657,28 @@ mod tests {
         assert_eq!(LogLevel::Error.to_filter_str(), "error");
     }
 
+    #[test]
+    fn test_trace_debug_flag() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(!args.trace_debug);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--trace-debug",
+        ]);
+        assert!(args.trace_debug);
+    }
+
     #[test]
     fn test_max_depth_parsing() {
         // 测试自定义深度
-        let args = CliArgs::parse_from(&[
+        let args = CliArgs::parse_from([
             "code-impact-analyzer",
             "-w", "/workspace",
             "-d", "/patch.diff",
@@ -199,23 +688,761 @@ mod tests {
     }
 
     #[test]
-    fn test_cli_help_generation() {
-        // 确保帮助信息可以生成（不会 panic）
-        let mut cmd = CliArgs::command();
-        let _ = cmd.render_help();
+    fn test_output_path_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--output", "/tmp/impact.json",
+        ]);
+        assert_eq!(args.output_path, Some(PathBuf::from("/tmp/impact.json")));
+    }
+
+    #[test]
+    fn test_direction_parsing() {
+        // 默认输出两个方向
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert_eq!(args.direction, DirectionFilter::Both);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--direction", "downstream",
+        ]);
+        assert_eq!(args.direction, DirectionFilter::Downstream);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--direction", "upstream",
+        ]);
+        assert_eq!(args.direction, DirectionFilter::Upstream);
+    }
+
+    #[test]
+    fn test_max_impact_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert_eq!(args.max_impact, None);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--max-impact", "50",
+        ]);
+        assert_eq!(args.max_impact, Some(50));
+        assert!(!args.strict);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--strict",
+        ]);
+        assert!(args.strict);
+    }
+
+    #[test]
+    fn test_max_warnings_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert_eq!(args.max_warnings, None);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--max-warnings", "20",
+        ]);
+        assert_eq!(args.max_warnings, Some(20));
+    }
+
+    #[test]
+    fn test_max_bridge_fanout_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert_eq!(args.max_bridge_fanout, None);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--max-bridge-fanout", "20",
+        ]);
+        assert_eq!(args.max_bridge_fanout, Some(20));
+    }
+
+    #[test]
+    fn test_min_http_segments_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert_eq!(args.min_http_segments, 0);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--min-http-segments", "2",
+        ]);
+        assert_eq!(args.min_http_segments, 2);
+    }
+
+    #[test]
+    fn test_config_topic_path_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--config-topic-path", "app.messaging.destinations",
+            "--config-topic-path", "app.events",
+        ]);
+        assert_eq!(args.config_topic_paths, vec!["app.messaging.destinations", "app.events"]);
+    }
+
+    #[test]
+    fn test_redis_namespace_depth_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert_eq!(args.redis_namespace_depth, None);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--redis-namespace-depth", "1",
+        ]);
+        assert_eq!(args.redis_namespace_depth, Some(1));
+
+        // 深度为 0 没有意义，直接拒绝而不是静默改为 1
+        let result = CliArgs::try_parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--redis-namespace-depth", "0",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_kafka_topic_prefix_strip_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--kafka-topic-prefix-strip", "prod.",
+            "--kafka-topic-prefix-strip", "staging.",
+        ]);
+        assert_eq!(args.kafka_topic_prefix_strip, vec!["prod.", "staging."]);
+    }
+
+    #[test]
+    fn test_service_alias_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--service-alias", "user-service=hll-user-app",
+            "--service-alias", "order-service = hll-order-app",
+        ]);
+        let aliases = args.service_alias_map();
+        assert_eq!(aliases.len(), 2);
+        assert_eq!(aliases["user-service"], "hll-user-app");
+        assert_eq!(aliases["order-service"], "hll-order-app");
+
+        for invalid in ["user-service", "=app", "user-service="] {
+            let result = CliArgs::try_parse_from([
+                "code-impact-analyzer",
+                "-w", "/workspace",
+                "-d", "/patch.diff",
+                "--service-alias", invalid,
+            ]);
+            assert!(result.is_err(), "{} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn test_table_normalize_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(args.table_normalize.is_empty());
+        assert!(!args.table_normalization().is_enabled());
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--table-normalize", "strip-prefix,singularize",
+        ]);
+        assert_eq!(args.table_normalize, vec![TableNormalizeRule::StripPrefix, TableNormalizeRule::Singularize]);
+        assert_eq!(args.table_normalization(), TableNormalization {
+            strip_prefix: true,
+            singularize: true,
+            lowercase: false,
+        });
+    }
+
+    #[test]
+    fn test_service_allowlist_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(args.services.is_empty());
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--service", "order-service",
+            "--service", "user-service",
+        ]);
+        assert_eq!(args.services, vec!["order-service", "user-service"]);
+    }
+
+    #[test]
+    fn test_significant_changes_only_flag() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(!args.significant_changes_only);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--significant-changes-only",
+        ]);
+        assert!(args.significant_changes_only);
+    }
+
+    #[test]
+    fn test_added_only_flag() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(!args.added_only);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--added-only",
+        ]);
+        assert!(args.added_only);
+        assert!(!args.no_normalize_paths);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--no-normalize-paths",
+        ]);
+        assert!(args.no_normalize_paths);
+
+        // --normalize-paths 是默认行为，仍然接受；两者同时指定时以最后一个为准
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--normalize-paths",
+        ]);
+        assert!(!args.no_normalize_paths);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--no-normalize-paths",
+            "--normalize-paths",
+        ]);
+        assert!(!args.no_normalize_paths);
+    }
+
+    #[test]
+    fn test_ignore_accessors_flag() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(!args.ignore_accessors);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--ignore-accessors",
+        ]);
+        assert!(args.ignore_accessors);
+        assert!(!args.blame);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--blame",
+        ]);
+        assert!(args.blame);
+    }
+
+    #[test]
+    fn test_list_changed_flag() {
+        // 默认不启用
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(!args.list_changed);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--list-changed",
+        ]);
+        assert!(args.list_changed);
+    }
+
+    #[test]
+    fn test_list_endpoints_flag_does_not_require_diff() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "--list-endpoints",
+        ]);
+        assert!(args.list_endpoints);
+        assert!(args.diff_path.is_none());
+    }
+
+    #[test]
+    fn test_report_unresolved_flag_does_not_require_diff() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "--report-unresolved",
+        ]);
+        assert!(args.report_unresolved);
+        assert!(args.diff_path.is_none());
+    }
+
+    #[test]
+    fn test_sort_by_churn_flag() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--sort-by-churn",
+        ]);
+        assert!(args.sort_by_churn);
+    }
+
+    #[test]
+    fn test_dump_index_flag_does_not_require_diff() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "--dump-index",
+        ]);
+        assert!(args.dump_index);
+        assert!(args.diff_path.is_none());
+    }
+
+    #[test]
+    fn test_dry_run_flag_does_not_require_diff() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "--dry-run",
+        ]);
+        assert!(args.dry_run);
+        assert!(args.diff_path.is_none());
+    }
+
+    #[test]
+    fn test_stream_jsonl_flag() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(!args.stream_jsonl);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--stream-jsonl",
+        ]);
+        assert!(args.stream_jsonl);
+    }
+
+    #[test]
+    fn test_git_diff_mode_flags() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "--staged",
+        ]);
+        assert_eq!(args.git_diff_mode(), Some(GitDiffMode::Staged));
+        assert!(args.diff_path.is_none());
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "--working",
+        ]);
+        assert_eq!(args.git_diff_mode(), Some(GitDiffMode::Working));
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert_eq!(args.git_diff_mode(), None);
+
+        // 两种模式互斥，也不能与 --diff 同时使用
+        for conflicting in [["--staged", "--working"], ["--staged", "-d=/patch.diff"], ["--working", "-d=/patch.diff"]] {
+            let result = CliArgs::try_parse_from(
+                ["code-impact-analyzer", "-w", "/workspace"].into_iter().chain(conflicting),
+            );
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_reachable_only_flag() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(!args.reachable_only);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--reachable-only",
+        ]);
+        assert!(args.reachable_only);
+    }
+
+    #[test]
+    fn test_contract_delegations_flag() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(!args.contract_delegations);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--contract-delegations",
+        ]);
+        assert!(args.contract_delegations);
+    }
+
+    #[test]
+    fn test_visibility_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert_eq!(args.visibility, VisibilityFilter::All);
+        assert_eq!(args.visibility.min_visibility(), None);
+        assert!(!args.hide_low_visibility);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--visibility", "public",
+            "--hide-low-visibility",
+        ]);
+        assert_eq!(args.visibility.min_visibility(), Some(Visibility::Public));
+        assert!(args.hide_low_visibility);
+    }
+
+    #[test]
+    fn test_lang_override_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--lang-override", "legacy/*.txt=java",
+            "--lang-override", "vendor/**=none",
+        ]);
+        assert_eq!(args.lang_overrides.len(), 2);
+        assert_eq!(args.lang_overrides[0].pattern, "legacy/*.txt");
+        assert_eq!(args.lang_overrides[0].language.as_deref(), Some("java"));
+        assert_eq!(args.lang_overrides[1].language, None);
+
+        let result = CliArgs::try_parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--lang-override", "java",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_extra_source_root_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--extra-source-root", "/workspace/libs-src",
+            "--extra-source-root", "/opt/vendor-src",
+        ]);
+        assert_eq!(
+            args.extra_source_roots,
+            vec![PathBuf::from("/workspace/libs-src"), PathBuf::from("/opt/vendor-src")]
+        );
+    }
+
+    #[test]
+    fn test_min_risk_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert_eq!(args.min_risk, None);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--min-risk", "high",
+        ]);
+        assert_eq!(args.min_risk.map(|risk| risk.min_risk()), Some(Risk::High));
+    }
+
+    #[test]
+    fn test_label_style_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert_eq!(args.label_style.to_label_style(), LabelStyle::Full);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--label-style", "package-initials",
+        ]);
+        assert_eq!(args.label_style.to_label_style(), LabelStyle::PackageInitials);
+    }
+
+    #[test]
+    fn test_index_dir_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--no-save-index",
+            "--index-dir", "/prebuilt/index",
+        ]);
+        assert!(args.no_save_index);
+        assert_eq!(args.index_dir, Some(PathBuf::from("/prebuilt/index")));
+        
+        // 本地索引目录和远程索引不能同时指定
+        let result = CliArgs::try_parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--index-dir", "/prebuilt/index",
+            "--index-url", "http://index.internal/index.json",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_index_url_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--index-url", "http://index.internal/index.json",
+        ]);
+        assert_eq!(args.index_url.as_deref(), Some("http://index.internal/index.json"));
+    }
+
+    #[test]
+    fn test_color_mode_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert_eq!(args.color, ColorMode::Auto);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--color", "never",
+        ]);
+        assert_eq!(args.color, ColorMode::Never);
+        assert!(!args.color.enabled());
+        assert!(ColorMode::Always.enabled());
+    }
+
+    #[test]
+    fn test_quiet_flag_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(!args.quiet);
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--quiet",
+            "--log-level", "info",
+        ]);
+        assert!(args.quiet);
+        assert!(matches!(args.log_level, LogLevel::Info));
+    }
+
+    #[test]
+    fn test_cli_help_generation() {
+        // 确保帮助信息可以生成（不会 panic）
+        let mut cmd = CliArgs::command();
+        let _ = cmd.render_help();
+    }
+
+    #[test]
+    fn test_trace_resource_parsing() {
+        // 指定 --trace-resource 时不需要 --diff
+        let args = CliArgs::try_parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "--trace-resource", "db:orders",
+        ]).unwrap();
+        assert!(args.diff_path.is_none());
+        assert_eq!(args.trace_resource, Some(ResourceId::Database("orders".to_string())));
+
+        let args = CliArgs::try_parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "--trace-resource", "http:GET:/api/orders/{id}",
+        ]).unwrap();
+        assert_eq!(args.trace_resource.unwrap().to_string(), "http:GET:/api/orders/{id}");
+
+        // 未知类型、缺少名称或与 --trace-endpoint 同时指定时报错
+        for invalid in ["orders", "queue:orders", "kafka:", "http:FETCH:/x"] {
+            let result = CliArgs::try_parse_from([
+                "code-impact-analyzer",
+                "-w", "/workspace",
+                "--trace-resource", invalid,
+            ]);
+            assert!(result.is_err(), "{} should be rejected", invalid);
+        }
+        let result = CliArgs::try_parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "--trace-resource", "db:orders",
+            "--trace-endpoint", "GET:/api/orders",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_trace_endpoint_parsing() {
+        // 指定 --trace-endpoint 时不需要 --diff
+        let args = CliArgs::try_parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "--trace-endpoint", "get:/api/orders/{id}",
+        ]).unwrap();
+        assert!(args.diff_path.is_none());
+        let endpoint = args.trace_endpoint.unwrap();
+        assert_eq!(endpoint.method, crate::types::HttpMethod::GET);
+        assert_eq!(endpoint.path_pattern, "/api/orders/{id}");
+        assert_eq!(endpoint.to_string(), "GET:/api/orders/{id}");
+
+        // 格式错误或未知方法时报错
+        for invalid in ["/api/orders", "FETCH:/api/orders", "POST:"] {
+            let result = CliArgs::try_parse_from([
+                "code-impact-analyzer",
+                "-w", "/workspace",
+                "--trace-endpoint", invalid,
+            ]);
+            assert!(result.is_err(), "{} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn test_baseline_parsing() {
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+        ]);
+        assert!(args.baseline.is_none());
+
+        let args = CliArgs::parse_from([
+            "code-impact-analyzer",
+            "-w", "/workspace",
+            "-d", "/patch.diff",
+            "--baseline", "/reviews/last.json",
+        ]);
+        assert_eq!(args.baseline, Some(PathBuf::from("/reviews/last.json")));
     }
 
     #[test]
     fn test_required_arguments() {
         // 测试缺少必需参数时的行为
-        let result = CliArgs::try_parse_from(&[
+        let result = CliArgs::try_parse_from([
             "code-impact-analyzer",
             "-w", "/workspace",
             // 缺少 --diff 参数
         ]);
         assert!(result.is_err());
 
-        let result = CliArgs::try_parse_from(&[
+        let result = CliArgs::try_parse_from([
             "code-impact-analyzer",
             "-d", "/patch.diff",
             // 缺少 --workspace 参数
@@ -1,19 +1,24 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::sync::{Arc, Mutex};
 use rayon::prelude::*;
 use indicatif::{ProgressBar, ProgressStyle, ParallelProgressIterator};
-use rustc_hash::FxHashMap;
-use crate::errors::IndexError;
-use crate::language_parser::{LanguageParser, LanguageDetector, ParsedFile, MethodInfo, FunctionInfo};
-use crate::types::{HttpAnnotation, HttpEndpoint};
+use rustc_hash::{FxHashMap, FxHashSet};
+use crate::errors::{IndexError, ParseError};
+use crate::language_parser::{LanguageParser, LanguageDetector, LanguageOverride, ParsedFile, MethodInfo, FunctionInfo};
+use crate::types::{FeignClientConfig, HttpAnnotation, HttpEndpoint, HttpMethod};
 use crate::parse_cache::ParseCache;
 
+/// 一类资源两侧（读/写、生产/消费）的索引: key -> [methods]
+type OperationMaps<'a> = (&'a mut FxHashMap<String, Vec<String>>, &'a mut FxHashMap<String, Vec<String>>);
+
 /// 代码索引
 /// 
 /// 构建全局代码索引，支持快速查询方法调用关系和跨服务资源
 /// 
 /// 使用 FxHashMap 替代标准 HashMap 以提升性能（约 20-30% 提升）
+#[derive(Clone)]
 pub struct CodeIndex {
     /// 方法信息映射: qualified_name -> MethodInfo
     methods: FxHashMap<String, MethodInfo>,
@@ -48,15 +53,79 @@ pub struct CodeIndex {
     /// Redis 读取者映射: key_prefix -> [reader_methods]
     redis_readers: FxHashMap<String, Vec<String>>,
     
+    /// RPC 提供者映射: interface::method -> [provider_methods]
+    rpc_providers: FxHashMap<String, Vec<String>>,
+    
+    /// RPC 消费者映射: interface::method -> [consumer_methods]
+    rpc_consumers: FxHashMap<String, Vec<String>>,
+    
+    /// Thrift 提供者映射: service::method -> [provider_methods]
+    thrift_providers: FxHashMap<String, Vec<String>>,
+    
+    /// Thrift 消费者映射: service::method -> [consumer_methods]
+    thrift_consumers: FxHashMap<String, Vec<String>>,
+    
+    /// Thrift IDL 中定义的服务: service -> [methods]
+    thrift_services: FxHashMap<String, Vec<String>>,
+    
+    /// GraphQL 解析器映射: Type.field -> [resolver_methods]
+    graphql_resolvers: FxHashMap<String, Vec<String>>,
+    
+    /// Spring Batch XML 中定义的作业: 步骤组件方法 -> [job_names]
+    batch_jobs: FxHashMap<String, Vec<String>>,
+    
     /// 配置关联映射: 配置值 -> 使用该配置的方法列表
     /// 用于追踪从配置文件中读取的值在代码中的使用
     config_associations: FxHashMap<String, Vec<String>>,
     
+    /// Feign 客户端配置映射: 模块 -> client_name -> 超时/重试配置
+    /// 工作空间根目录下（不属于任何模块）的配置登记在空模块名下，对所有模块生效
+    feign_client_configs: FxHashMap<String, FxHashMap<String, FeignClientConfig>>,
+    
+    /// 配置文件（如 SQL 迁移脚本）中声明的数据库表，统一为小写
+    declared_db_tables: FxHashSet<String>,
+    
     /// 接口到实现类的映射: interface_name -> [implementation_class_names]
     interface_implementations: FxHashMap<String, Vec<String>>,
     
     /// 实现类到接口的映射: implementation_class_name -> [interface_names]
     class_interfaces: FxHashMap<String, Vec<String>>,
+    
+    /// 子类到父类的映射: class_name -> superclass_name
+    class_superclasses: FxHashMap<String, String>,
+    
+    /// 父类到直接子类的映射: superclass_name -> [subclass_names]
+    subclasses: FxHashMap<String, Vec<String>>,
+    
+    /// Redis 键按命名空间合并时保留的段数，为空表示不合并
+    redis_namespace_depth: Option<usize>,
+    
+    /// 匹配 Kafka Topic 前去掉的环境前缀（如 `prod.`）
+    kafka_topic_prefixes: Vec<String>,
+    
+    /// 数据库表名的规范化规则
+    table_normalization: TableNormalization,
+    
+    /// 启发式匹配 HTTP 客户端调用时端点路径至少需要的非参数段数
+    min_http_segments: usize,
+    
+    /// 按路径模式强制指定或禁用语言解析器的规则
+    language_overrides: Vec<LanguageOverride>,
+    
+    /// 额外索引的源码根目录（如第三方依赖源码），索引工作空间时跳过这些目录
+    extra_source_roots: Vec<PathBuf>,
+    
+    /// 从额外源码根目录索引的方法
+    third_party_methods: FxHashSet<String>,
+    
+    /// 构建索引时解析失败的源文件
+    parse_failures: Vec<PathBuf>,
+    
+    /// 存在语法错误的源文件中各错误节点的位置: file -> [(line, column)]
+    syntax_errors: FxHashMap<PathBuf, Vec<(usize, usize)>>,
+    
+    /// 静默模式，启用后不输出构建索引各步骤的进度日志
+    quiet: bool,
 }
 
 impl CodeIndex {
@@ -74,12 +143,40 @@ impl CodeIndex {
             db_readers: FxHashMap::default(),
             redis_writers: FxHashMap::default(),
             redis_readers: FxHashMap::default(),
+            rpc_providers: FxHashMap::default(),
+            rpc_consumers: FxHashMap::default(),
+            thrift_providers: FxHashMap::default(),
+            thrift_consumers: FxHashMap::default(),
+            thrift_services: FxHashMap::default(),
+            graphql_resolvers: FxHashMap::default(),
+            batch_jobs: FxHashMap::default(),
             config_associations: FxHashMap::default(),
+            feign_client_configs: FxHashMap::default(),
+            declared_db_tables: FxHashSet::default(),
             interface_implementations: FxHashMap::default(),
             class_interfaces: FxHashMap::default(),
+            class_superclasses: FxHashMap::default(),
+            subclasses: FxHashMap::default(),
+            redis_namespace_depth: None,
+            kafka_topic_prefixes: Vec::new(),
+            table_normalization: TableNormalization::default(),
+            min_http_segments: 0,
+            language_overrides: Vec::new(),
+            extra_source_roots: Vec::new(),
+            third_party_methods: FxHashSet::default(),
+            parse_failures: Vec::new(),
+            syntax_errors: FxHashMap::default(),
+            quiet: false,
         }
     }
     
+    /// 设置静默模式
+    /// 
+    /// 启用后构建索引时不输出各步骤的进度日志，警告仍照常输出
+    pub fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+    
     /// 索引整个工作空间
     /// 
     /// # Arguments
@@ -94,13 +191,21 @@ impl CodeIndex {
         workspace_path: &Path,
         parsers: &[Box<dyn LanguageParser>],
     ) -> Result<(), IndexError> {
-        log::info!("开始收集源文件...");
+        // 额外源码根目录不存在时在解析工作空间之前报错
+        if let Some(root) = self.extra_source_roots.iter().find(|root| !root.is_dir()) {
+            return Err(IndexError::IoError {
+                path: root.clone(),
+                error: "extra source root is not a directory".to_string(),
+            });
+        }
         
-        // 遍历工作空间中的所有文件
-        let source_files = self.collect_source_files(workspace_path)?;
+        progress!(self.quiet, "开始收集源文件...");
+        
+        // 遍历工作空间中的所有文件，额外源码根目录在工作空间之后以较低优先级索引
+        let source_files = self.collect_source_files(workspace_path, parsers, &self.extra_source_roots)?;
         let total_files = source_files.len();
         
-        log::info!("找到 {} 个源文件，开始并行解析...", total_files);
+        progress!(self.quiet, "找到 {} 个源文件，开始并行解析...", total_files);
         
         // 创建进度条
         let pb = ProgressBar::new(total_files as u64);
@@ -116,20 +221,9 @@ impl CodeIndex {
         let cache = Arc::new(Mutex::new(ParseCache::new()));
         
         // 使用 rayon 并行解析所有源文件，并显示进度
-        let parsed_files: Vec<ParsedFile> = source_files
-            .par_iter()
-            .progress_with(pb.clone())
-            .filter_map(|file_path| {
-                match self.parse_file_with_cache(file_path, parsers, &cache) {
-                    Ok(parsed) => Some(parsed),
-                    Err(e) => {
-                        // 记录错误但继续处理其他文件
-                        log::warn!("解析失败 {}: {}", file_path.display(), e);
-                        None
-                    }
-                }
-            })
-            .collect();
+        let parsed_files = self.parse_and_resolve(&source_files, &pb, |index, file_path| {
+            Some(index.parse_file_with_cache(file_path, parsers, &cache).map_err(|e| (file_path.clone(), e)))
+        });
         
         pb.finish_with_message(format!("解析完成：{}/{} 个文件", parsed_files.len(), total_files));
         
@@ -143,7 +237,7 @@ impl CodeIndex {
         );
         index_pb.set_message("构建索引");
         
-        log::info!("开始构建索引，处理 {} 个已解析文件...", parsed_files.len());
+        progress!(self.quiet, "开始构建索引，处理 {} 个已解析文件...", parsed_files.len());
         
         // 串行构建索引（确保线程安全）
         for parsed_file in parsed_files {
@@ -155,18 +249,154 @@ impl CodeIndex {
         
         index_pb.finish_with_message("索引构建完成");
         
-        log::info!("索引构建完成：");
-        log::info!("  - 方法总数: {}", self.methods.len());
-        log::info!("  - 方法调用关系: {}", self.method_calls.len());
-        log::info!("  - HTTP 提供者: {}", self.http_providers.len());
-        log::info!("  - HTTP 消费者: {}", self.http_consumers.len());
-        log::info!("  - Kafka 生产者: {}", self.kafka_producers.len());
-        log::info!("  - Kafka 消费者: {}", self.kafka_consumers.len());
-        log::info!("  - 接口实现关系: {}", self.interface_implementations.len());
+        for root in self.extra_source_roots.clone() {
+            self.index_extra_source_root(&root, parsers)?;
+        }
+        
+        progress!(self.quiet, "索引构建完成：");
+        progress!(self.quiet, "  - 方法总数: {}", self.methods.len());
+        progress!(self.quiet, "  - 方法调用关系: {}", self.method_calls.len());
+        progress!(self.quiet, "  - HTTP 提供者: {}", self.http_providers.len());
+        progress!(self.quiet, "  - HTTP 消费者: {}", self.http_consumers.len());
+        progress!(self.quiet, "  - Kafka 生产者: {}", self.kafka_producers.len());
+        progress!(self.quiet, "  - Kafka 消费者: {}", self.kafka_consumers.len());
+        progress!(self.quiet, "  - 接口实现关系: {}", self.interface_implementations.len());
+        
+        Ok(())
+    }
+    
+    /// 索引内存中的源文件，不读取磁盘
+    /// 
+    /// 用于嵌入长期运行的服务或单元测试：直接解析给定的内容，不遍历工作空间也不使用解析缓存。
+    /// 没有匹配解析器的文件被忽略，解析失败的文件记录到 `parse_failures`
+    /// 
+    /// # Arguments
+    /// * `sources` - 文件路径（用于选择解析器和记录方法所在文件）与文件内容
+    /// * `parsers` - 语言解析器列表
+    /// 
+    /// # Returns
+    /// * `Ok(())` - 索引构建成功
+    /// * `Err(IndexError)` - 索引构建失败
+    pub fn index_sources(
+        &mut self,
+        sources: &[(PathBuf, String)],
+        parsers: &[Box<dyn LanguageParser>],
+    ) -> Result<(), IndexError> {
+        let parsed_files = self.parse_and_resolve(sources, &ProgressBar::hidden(), |index, (path, content)| {
+            let parser = LanguageDetector::select_parser(path, parsers, &index.language_overrides)?;
+            Some(parser.parse_file(content, path).map_err(|e| (path.clone(), e)))
+        });
+        
+        for parsed_file in parsed_files {
+            if let Err(e) = self.index_parsed_file(parsed_file) {
+                log::warn!("索引文件失败: {}", e);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// 设置额外索引的源码根目录（如 `libs-src/` 下的第三方依赖源码）
+    /// 
+    /// `index_workspace` 遍历工作空间时跳过这些目录（即使位于工作空间内），在工作空间之后再索引它们：
+    /// 与工作空间方法同名的方法被忽略，其余方法参与调用解析并记录为第三方方法
+    /// 
+    /// # Arguments
+    /// * `roots` - 源码根目录列表
+    pub fn set_extra_source_roots(&mut self, roots: Vec<PathBuf>) {
+        self.extra_source_roots = roots;
+    }
+    
+    /// 索引一个额外源码根目录，目录已由 `index_workspace` 检查存在
+    fn index_extra_source_root(
+        &mut self,
+        root: &Path,
+        parsers: &[Box<dyn LanguageParser>],
+    ) -> Result<(), IndexError> {
+        let source_files = self.collect_source_files(root, parsers, &[])?;
+        progress!(self.quiet, "索引额外源码目录 {}：{} 个源文件", root.display(), source_files.len());
+        
+        let cache = Arc::new(Mutex::new(ParseCache::new()));
+        let parsed_files = self.parse_and_resolve(&source_files, &ProgressBar::hidden(), |index, file_path| {
+            Some(index.parse_file_with_cache(file_path, parsers, &cache).map_err(|e| (file_path.clone(), e)))
+        });
+        
+        for mut parsed_file in parsed_files {
+            // 工作空间中已有的方法优先
+            for class in &mut parsed_file.classes {
+                class.methods.retain(|method| !self.methods.contains_key(&method.full_qualified_name));
+            }
+            parsed_file.functions.retain(|function| !self.methods.contains_key(&function.full_qualified_name));
+            
+            let names: Vec<String> = parsed_file.classes.iter()
+                .flat_map(|class| class.methods.iter().map(|method| method.full_qualified_name.clone()))
+                .chain(parsed_file.functions.iter().map(|function| function.full_qualified_name.clone()))
+                .collect();
+            if let Err(e) = self.index_parsed_file(parsed_file) {
+                log::warn!("索引文件失败: {}", e);
+            }
+            self.third_party_methods.extend(names);
+        }
         
         Ok(())
     }
     
+    /// 并行解析一组源文件，记录解析失败的文件，再解析文件之间的引用
+    /// 
+    /// 解析失败的文件记录警告和 `parse_failures` 后跳过，不中断其他文件的解析
+    /// 
+    /// # Arguments
+    /// * `inputs` - 待解析的文件（路径或内存中的路径与内容）
+    /// * `pb` - 解析进度条，不显示进度时传入 `ProgressBar::hidden()`
+    /// * `parse` - 解析单个文件，没有匹配的解析器时返回 `None`
+    /// 
+    /// # Returns
+    /// * 解析成功并完成跨文件解析的文件
+    fn parse_and_resolve<T: Sync>(
+        &mut self,
+        inputs: &[T],
+        pb: &ProgressBar,
+        parse: impl Fn(&Self, &T) -> Option<Result<ParsedFile, (PathBuf, ParseError)>> + Sync + Send,
+    ) -> Vec<ParsedFile> {
+        let index = &*self;
+        let results: Vec<Result<ParsedFile, (PathBuf, ParseError)>> = inputs
+            .par_iter()
+            .progress_with(pb.clone())
+            .filter_map(|input| parse(index, input))
+            .collect();
+        
+        let mut parsed_files = Vec::with_capacity(results.len());
+        for result in results {
+            match result {
+                Ok(parsed) => parsed_files.push(parsed),
+                Err((file_path, e)) => {
+                    // 记录错误但继续处理其他文件
+                    log::warn!("解析失败 {}: {}", file_path.display(), e);
+                    self.parse_failures.push(file_path);
+                }
+            }
+        }
+        
+        // 第二遍：收集所有文件的字符串常量后，解析以常量引用的 Kafka topic
+        resolve_topic_constants(&mut parsed_files);
+        
+        // 将 WebFlux 函数式路由附加到其他文件中的处理方法
+        resolve_functional_routes(&mut parsed_files);
+        
+        // 按命名空间和 using 指令补全 C# 文件中引用其他文件的类型名
+        resolve_csharp_types(&mut parsed_files);
+        
+        parsed_files
+    }
+    
+    /// 方法是否来自额外源码根目录
+    /// 
+    /// # Arguments
+    /// * `qualified_name` - 方法的全限定名
+    pub fn is_third_party(&self, qualified_name: &str) -> bool {
+        self.third_party_methods.contains(qualified_name)
+    }
+    
     /// 使用缓存解析单个文件
     /// 
     /// 此方法设计为线程安全，可以在多个线程中并行调用
@@ -175,95 +405,58 @@ impl CodeIndex {
         file_path: &Path,
         parsers: &[Box<dyn LanguageParser>],
         cache: &Arc<Mutex<ParseCache>>,
-    ) -> Result<ParsedFile, IndexError> {
+    ) -> Result<ParsedFile, ParseError> {
         // 尝试从缓存获取或解析
         let mut cache_guard = cache.lock().unwrap();
         
         cache_guard.get_or_parse(file_path, |path| {
             // 读取文件内容
             let content = fs::read_to_string(path)
-                .map_err(|e| crate::errors::ParseError::IoError {
+                .map_err(|e| ParseError::IoError {
                     path: path.to_path_buf(),
                     error: e.to_string(),
                 })?;
             
             // 选择合适的解析器
-            let parser = self.select_parser(path, parsers)
-                .ok_or_else(|| crate::errors::ParseError::UnsupportedLanguage {
+            let parser = LanguageDetector::select_parser(path, parsers, &self.language_overrides)
+                .ok_or_else(|| ParseError::UnsupportedLanguage {
                     language: format!("{:?}", path.extension()),
                 })?;
             
             // 解析文件
             parser.parse_file(&content, path)
-        })
-        .map(|parsed| parsed.clone())
-        .map_err(|e| IndexError::ParseError {
-            file: file_path.to_path_buf(),
-            error: format!("{:?}", e),
-        })
+        }).cloned()
     }
     
-    /// 收集工作空间中的所有源文件
-    fn collect_source_files(&self, workspace_path: &Path) -> Result<Vec<PathBuf>, IndexError> {
+    /// 收集目录中的所有源文件
+    /// 
+    /// # Arguments
+    /// * `workspace_path` - 遍历的根目录
+    /// * `parsers` - 语言解析器列表
+    /// * `excluded_dirs` - 跳过的子目录
+    fn collect_source_files(
+        &self,
+        workspace_path: &Path,
+        parsers: &[Box<dyn LanguageParser>],
+        excluded_dirs: &[PathBuf],
+    ) -> Result<Vec<PathBuf>, IndexError> {
+        let excluded_dirs: Vec<PathBuf> = excluded_dirs.iter()
+            .map(|dir| fs::canonicalize(dir).unwrap_or_else(|_| dir.clone()))
+            .collect();
         let mut source_files = Vec::new();
-        self.collect_files_recursive(workspace_path, &mut source_files)?;
+        collect_files_recursive(workspace_path, &excluded_dirs, &mut source_files)?;
+        source_files.retain(|path| LanguageDetector::select_parser(path, parsers, &self.language_overrides).is_some());
         Ok(source_files)
     }
     
-    /// 递归收集文件
-    fn collect_files_recursive(
-        &self,
-        dir: &Path,
-        files: &mut Vec<PathBuf>,
-    ) -> Result<(), IndexError> {
-        if !dir.is_dir() {
-            return Ok(());
-        }
-        
-        let entries = fs::read_dir(dir)
-            .map_err(|e| IndexError::IoError {
-                path: dir.to_path_buf(),
-                error: e.to_string(),
-            })?;
-        
-        for entry in entries {
-            let entry = entry.map_err(|e| IndexError::IoError {
-                path: dir.to_path_buf(),
-                error: e.to_string(),
-            })?;
-            
-            let path = entry.path();
-            
-            // 跳过隐藏目录和常见的构建目录
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.starts_with('.') || name == "target" || name == "build" || name == "node_modules" {
-                    continue;
-                }
-            }
-            
-            if path.is_dir() {
-                self.collect_files_recursive(&path, files)?;
-            } else if LanguageDetector::is_supported(&path) {
-                files.push(path);
-            }
-        }
-        
-        Ok(())
-    }
-    
-    
-    /// 选择合适的语言解析器
-    fn select_parser<'a>(
-        &self,
-        file_path: &Path,
-        parsers: &'a [Box<dyn LanguageParser>],
-    ) -> Option<&'a Box<dyn LanguageParser>> {
-        let language = LanguageDetector::detect_language(file_path)?;
-        parsers.iter().find(|p| p.language_name() == language)
-    }
     
     /// 索引解析后的文件
     fn index_parsed_file(&mut self, parsed_file: ParsedFile) -> Result<(), IndexError> {
+        // 语法错误不影响从恢复后的语法树中提取的内容，只记录位置用于警告
+        if !parsed_file.syntax_errors.is_empty() {
+            self.syntax_errors.insert(parsed_file.file_path.clone(), parsed_file.syntax_errors.clone());
+        }
+        
         // 索引类中的方法
         for class in &parsed_file.classes {
             // 索引接口实现关系
@@ -272,17 +465,22 @@ impl CodeIndex {
                     // 正向映射：接口 -> 实现类
                     self.interface_implementations
                         .entry(interface_name.clone())
-                        .or_insert_with(Vec::new)
+                        .or_default()
                         .push(class.name.clone());
                     
                     // 反向映射：实现类 -> 接口
                     self.class_interfaces
                         .entry(class.name.clone())
-                        .or_insert_with(Vec::new)
+                        .or_default()
                         .push(interface_name.clone());
                 }
             }
             
+            // 索引继承关系
+            if let Some(superclass) = &class.extends {
+                self.index_superclass(&class.name, superclass);
+            }
+            
             for method in &class.methods {
                 self.index_method(method)?;
             }
@@ -308,18 +506,18 @@ impl CodeIndex {
             // 正向调用: caller -> callee
             self.method_calls
                 .entry(qualified_name.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(call.target.clone());
             
             // 反向调用: callee -> caller
             self.reverse_calls
                 .entry(call.target.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(qualified_name.clone());
         }
         
         // 索引 HTTP 注解
-        if let Some(http_annotation) = &method.http_annotations {
+        for http_annotation in method.all_http_annotations() {
             self.index_http_annotation(&qualified_name, http_annotation);
         }
         
@@ -338,6 +536,24 @@ impl CodeIndex {
             self.index_redis_operation(&qualified_name, redis_op);
         }
         
+        // 索引 RPC 操作
+        for rpc_op in &method.rpc_operations {
+            self.index_rpc_operation(&qualified_name, rpc_op);
+        }
+        
+        // 索引 Thrift 操作
+        for thrift_op in &method.thrift_operations {
+            self.index_thrift_operation(&qualified_name, thrift_op);
+        }
+        
+        // 索引 GraphQL 解析器
+        if let Some(resolver) = &method.graphql_resolver {
+            let methods = self.graphql_resolvers.entry(resolver.key()).or_default();
+            if !methods.contains(&qualified_name) {
+                methods.push(qualified_name.clone());
+            }
+        }
+        
         Ok(())
     }
     
@@ -354,6 +570,12 @@ impl CodeIndex {
             kafka_operations: function.kafka_operations.clone(),
             db_operations: function.db_operations.clone(),
             redis_operations: function.redis_operations.clone(),
+            rpc_operations: function.rpc_operations.clone(),
+            thrift_operations: function.thrift_operations.clone(),
+            graphql_resolver: function.graphql_resolver.clone(),
+            column_range: function.column_range,
+            visibility: function.visibility,
+            ..Default::default()
         };
         
         self.index_method(&method_info)
@@ -371,7 +593,7 @@ impl CodeIndex {
             // Feign 消费者
             self.http_consumers
                 .entry(endpoint)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(method_name.to_string());
         } else {
             // HTTP 接口提供者
@@ -386,14 +608,14 @@ impl CodeIndex {
         match operation.operation_type {
             KafkaOpType::Produce => {
                 self.kafka_producers
-                    .entry(operation.topic.clone())
-                    .or_insert_with(Vec::new)
+                    .entry(self.normalize_kafka_topic(&operation.topic))
+                    .or_default()
                     .push(method_name.to_string());
             }
             KafkaOpType::Consume => {
                 self.kafka_consumers
-                    .entry(operation.topic.clone())
-                    .or_insert_with(Vec::new)
+                    .entry(self.normalize_kafka_topic(&operation.topic))
+                    .or_default()
                     .push(method_name.to_string());
             }
         }
@@ -406,14 +628,14 @@ impl CodeIndex {
         match operation.operation_type {
             DbOpType::Select => {
                 self.db_readers
-                    .entry(operation.table.clone())
-                    .or_insert_with(Vec::new)
+                    .entry(self.table_normalization.normalize(&operation.table))
+                    .or_default()
                     .push(method_name.to_string());
             }
             DbOpType::Insert | DbOpType::Update | DbOpType::Delete => {
                 self.db_writers
-                    .entry(operation.table.clone())
-                    .or_insert_with(Vec::new)
+                    .entry(self.table_normalization.normalize(&operation.table))
+                    .or_default()
                     .push(method_name.to_string());
             }
         }
@@ -426,150 +648,703 @@ impl CodeIndex {
         match operation.operation_type {
             RedisOpType::Get => {
                 self.redis_readers
-                    .entry(operation.key_pattern.clone())
-                    .or_insert_with(Vec::new)
+                    .entry(redis_namespace_key(&operation.key_pattern, self.redis_namespace_depth))
+                    .or_default()
                     .push(method_name.to_string());
             }
             RedisOpType::Set | RedisOpType::Delete => {
                 self.redis_writers
-                    .entry(operation.key_pattern.clone())
-                    .or_insert_with(Vec::new)
+                    .entry(redis_namespace_key(&operation.key_pattern, self.redis_namespace_depth))
+                    .or_default()
                     .push(method_name.to_string());
             }
         }
     }
     
+    /// 索引 RPC 操作
+    fn index_rpc_operation(&mut self, method_name: &str, operation: &crate::types::RpcOperation) {
+        use crate::types::RpcOpType;
+        
+        let target = match operation.operation_type {
+            RpcOpType::Provide => &mut self.rpc_providers,
+            RpcOpType::Consume => &mut self.rpc_consumers,
+        };
+        
+        let methods = target.entry(operation.key()).or_default();
+        if !methods.iter().any(|m| m == method_name) {
+            methods.push(method_name.to_string());
+        }
+    }
+    
+    /// 索引 Thrift 操作
+    fn index_thrift_operation(&mut self, method_name: &str, operation: &crate::types::ThriftOperation) {
+        use crate::types::RpcOpType;
+        
+        let target = match operation.operation_type {
+            RpcOpType::Provide => &mut self.thrift_providers,
+            RpcOpType::Consume => &mut self.thrift_consumers,
+        };
+        
+        let methods = target.entry(operation.key()).or_default();
+        if !methods.iter().any(|m| m == method_name) {
+            methods.push(method_name.to_string());
+        }
+    }
+    
     /// 查找方法信息
     pub fn find_method(&self, qualified_name: &str) -> Option<&MethodInfo> {
         self.methods.get(qualified_name)
     }
     
     /// 查找调用指定方法的所有方法（上游）
+    /// 
+    /// 与其他 `find_*` 查询一样，结果按名称排序，不受索引顺序影响
     pub fn find_callers(&self, method: &str) -> Vec<&str> {
-        self.reverse_calls
-            .get(method)
-            .map(|callers| callers.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+        sorted_refs(self.reverse_calls.get(method))
     }
     
-    /// 查找指定方法调用的所有方法（下游）
+    /// 查找指定方法调用的所有方法（下游），按名称排序
     pub fn find_callees(&self, method: &str) -> Vec<&str> {
-        self.method_calls
-            .get(method)
-            .map(|callees| callees.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+        sorted_refs(self.method_calls.get(method))
     }
     
     /// 查找 HTTP 端点的提供者
+    /// 
+    /// 同一路径上方法为 `Any` 的提供者也会匹配；查找 `Any` 端点时返回该路径上所有方法的提供者
     pub fn find_http_providers(&self, endpoint: &HttpEndpoint) -> Vec<&str> {
-        self.http_providers
-            .get(endpoint)
-            .map(|provider| vec![provider.as_str()])
-            .unwrap_or_default()
+        let mut providers: Vec<&str> = matching_http_entries(&self.http_providers, endpoint)
+            .into_iter()
+            .map(String::as_str)
+            .collect();
+        providers.sort_unstable();
+        providers.dedup();
+        providers
+    }
+    
+    /// 获取所有 HTTP 端点及其提供者
+    /// 
+    /// # Returns
+    /// 按路径和 HTTP 方法排序的 (端点, 提供者方法) 列表
+    pub fn all_http_providers(&self) -> Vec<(HttpEndpoint, &str)> {
+        let mut providers: Vec<(HttpEndpoint, &str)> = self.http_providers
+            .iter()
+            .map(|(endpoint, provider)| (endpoint.clone(), provider.as_str()))
+            .collect();
+        providers.sort_by(|(a, _), (b, _)| {
+            a.path_pattern.cmp(&b.path_pattern).then_with(|| a.method_str().cmp(b.method_str()))
+        });
+        providers
     }
     
     /// 查找 HTTP 端点的消费者
+    /// 
+    /// 与 `find_http_providers` 相同，`Any` 端点与同一路径上任意方法的消费者匹配
     pub fn find_http_consumers(&self, endpoint: &HttpEndpoint) -> Vec<&str> {
-        self.http_consumers
-            .get(endpoint)
-            .map(|consumers| consumers.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+        let mut consumers: Vec<&str> = matching_http_entries(&self.http_consumers, endpoint)
+            .into_iter()
+            .flat_map(|methods| methods.iter().map(String::as_str))
+            .collect();
+        consumers.sort_unstable();
+        consumers.dedup();
+        consumers
     }
     
     /// 查找 Kafka Topic 的消费者
+    /// 
+    /// Topic 会先去掉设置的环境前缀
     pub fn find_kafka_consumers(&self, topic: &str) -> Vec<&str> {
-        self.kafka_consumers
-            .get(topic)
-            .map(|consumers| consumers.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+        sorted_refs(self.kafka_consumers.get(&self.normalize_kafka_topic(topic)))
     }
     
     /// 查找 Kafka Topic 的生产者
+    /// 
+    /// Topic 会先去掉设置的环境前缀
     pub fn find_kafka_producers(&self, topic: &str) -> Vec<&str> {
-        self.kafka_producers
-            .get(topic)
-            .map(|producers| producers.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+        sorted_refs(self.kafka_producers.get(&self.normalize_kafka_topic(topic)))
     }
     
     /// 查找数据库表的读取者
+    /// 
+    /// 表名会先按设置的规范化规则处理
     pub fn find_db_readers(&self, table: &str) -> Vec<&str> {
-        self.db_readers
-            .get(table)
-            .map(|readers| readers.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+        sorted_refs(self.db_readers.get(&self.table_normalization.normalize(table)))
     }
     
-    /// 查找数据库表的写入者
-    pub fn find_db_writers(&self, table: &str) -> Vec<&str> {
-        self.db_writers
-            .get(table)
-            .map(|writers| writers.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+    /// 获取配置文件中声明的数据库表（小写）
+    pub fn declared_db_tables(&self) -> impl Iterator<Item = &String> {
+        self.declared_db_tables.iter()
     }
     
-    /// 查找 Redis 键前缀的读取者
-    pub fn find_redis_readers(&self, prefix: &str) -> Vec<&str> {
-        self.redis_readers
-            .get(prefix)
-            .map(|readers| readers.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+    /// 查找代码中访问但配置中未声明的数据库表
+    /// 
+    /// 没有任何声明的表（如工作空间中没有迁移脚本）时不做检查，返回空列表；
+    /// 表名比较不区分大小写
+    /// 
+    /// # Returns
+    /// * `Vec<&str>` - 按名称排序的未声明表名
+    pub fn undeclared_db_tables(&self) -> Vec<&str> {
+        if self.declared_db_tables.is_empty() {
+            return Vec::new();
+        }
+        
+        let declared: FxHashSet<String> = self.declared_db_tables.iter()
+            .map(|table| self.table_normalization.normalize(table).to_lowercase())
+            .collect();
+        let mut tables: Vec<&str> = self.db_readers.keys()
+            .chain(self.db_writers.keys())
+            .filter(|table| !declared.contains(&table.to_lowercase()))
+            .map(|table| table.as_str())
+            .collect();
+        tables.sort_unstable();
+        tables.dedup();
+        tables
     }
     
-    /// 查找 Redis 键前缀的写入者
-    pub fn find_redis_writers(&self, prefix: &str) -> Vec<&str> {
-        self.redis_writers
-            .get(prefix)
-            .map(|writers| writers.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+    /// 查找无法解析到已索引方法的调用目标
+    /// 
+    /// 经过接口和继承解析后仍不是已索引方法的调用目标，通常是外部库调用或解析器
+    /// 未能推断接收者类型的调用（如裸方法名），可用于评估分析覆盖率。
+    /// Rust 宏调用（`name!`）和 HTTP 客户端调用描述（如 `RestTemplate.get(/api)`）不是方法调用，不计入
+    /// 
+    /// # Returns
+    /// * `Vec<&str>` - 按名称排序的未解析调用目标
+    pub fn unresolved_targets(&self) -> Vec<&str> {
+        let mut targets: Vec<&str> = self.reverse_calls.keys()
+            .filter(|target| !target.ends_with('!') && !target.contains('('))
+            .filter(|target| {
+                let resolved = self.resolve_inherited_call(&self.resolve_interface_call(target));
+                !self.methods.contains_key(&resolved)
+            })
+            .map(|target| target.as_str())
+            .collect();
+        targets.sort_unstable();
+        targets
     }
     
-    /// 获取所有方法的迭代器
+    /// 获取入口点方法
+    /// 
+    /// 入口点是由框架或外部调用触发的方法：HTTP 接口提供者、Kafka 消费者、`@Scheduled` 定时任务、
+    /// GraphQL 解析器、Spring Batch 作业（Java 配置的作业 `@Bean` 方法和 XML 作业的步骤组件方法）
+    /// 以及 RPC/Thrift 服务提供者
     /// 
     /// # Returns
-    /// 返回一个迭代器，遍历所有 (方法名, 方法信息) 对
-    pub fn methods(&self) -> impl Iterator<Item = (&String, &MethodInfo)> {
-        self.methods.iter()
+    /// * `Vec<&str>` - 按名称排序的入口点方法
+    pub fn entry_points(&self) -> Vec<&str> {
+        let mut entry_points: Vec<&str> = self.methods.iter()
+            .filter(|(name, method)| is_entry_point(method) || self.batch_jobs.contains_key(name.as_str()))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        entry_points.sort_unstable();
+        entry_points
     }
     
-    /// 关联配置数据到代码
+    /// 查找从入口点沿方法调用可达的所有方法（包含入口点本身）
     /// 
-    /// 此方法将配置文件中的值与代码中的引用关联起来
+    /// 调用目标与影响追溯一样经过接口和继承解析，未索引的调用目标忽略
     /// 
-    /// # Arguments
-    /// * `config_data` - 从配置文件解析的配置数据
-    pub fn associate_config_data(&mut self, config_data: &crate::config_parser::ConfigData) {
-        // 关联 HTTP 端点配置
-        for endpoint in &config_data.http_endpoints {
-            self.associate_http_endpoint(endpoint);
-        }
+    /// # Returns
+    /// * `FxHashSet<&str>` - 可达方法的全限定名
+    pub fn reachable_from_entry_points(&self) -> FxHashSet<&str> {
+        let mut reachable = FxHashSet::default();
+        let mut stack = self.entry_points();
         
-        // 关联 Kafka Topic 配置
-        for topic in &config_data.kafka_topics {
-            self.associate_kafka_topic(topic);
+        while let Some(method) = stack.pop() {
+            if !reachable.insert(method) {
+                continue;
+            }
+            for callee in self.find_callees(method) {
+                let resolved = self.resolve_inherited_call(&self.resolve_interface_call(callee));
+                if let Some((name, _)) = self.methods.get_key_value(resolved.as_str())
+                    && !reachable.contains(name.as_str())
+                {
+                    stack.push(name.as_str());
+                }
+            }
         }
         
-        // 关联数据库表配置
-        for table in &config_data.db_tables {
-            self.associate_db_table(table);
+        reachable
+    }
+    
+    /// 查找数据库表的写入者
+    /// 
+    /// 表名会先按设置的规范化规则处理
+    pub fn find_db_writers(&self, table: &str) -> Vec<&str> {
+        sorted_refs(self.db_writers.get(&self.table_normalization.normalize(table)))
+    }
+    
+    /// 设置数据库表名的规范化规则，并按新的表名重建数据库读写索引
+    /// 
+    /// 例如同时启用去前缀、单数化和小写时 `t_users`、`User` 都合并为 `user`
+    /// 
+    /// # Arguments
+    /// * `normalization` - 表名规范化规则
+    pub fn set_table_normalization(&mut self, normalization: TableNormalization) {
+        self.table_normalization = normalization;
+        self.rebuild_operation_index(
+            |index| (&mut index.db_readers, &mut index.db_writers),
+            |method| &method.db_operations,
+            Self::index_db_operation,
+        );
+    }
+    
+    /// 获取数据库表名的规范化规则
+    pub fn table_normalization(&self) -> TableNormalization {
+        self.table_normalization
+    }
+    
+    /// 设置 Redis 键按命名空间合并时保留的段数，并按新的键重建 Redis 读写索引
+    /// 
+    /// 例如深度为 1 时 `user:1`、`user:{id}:profile` 都合并为 `user`
+    /// 
+    /// # Arguments
+    /// * `depth` - 保留的冒号分隔段数（至少为 1），`None` 表示不合并
+    pub fn set_redis_namespace_depth(&mut self, depth: Option<usize>) {
+        self.redis_namespace_depth = depth;
+        self.rebuild_operation_index(
+            |index| (&mut index.redis_readers, &mut index.redis_writers),
+            |method| &method.redis_operations,
+            Self::index_redis_operation,
+        );
+    }
+    
+    /// 设置匹配 Kafka Topic 前去掉的环境前缀，并按新的 Topic 重建 Kafka 生产者/消费者索引
+    /// 
+    /// 例如前缀为 `prod.` 时，监听 `prod.order-events` 的消费者与写入 `order-events` 的生产者匹配
+    /// 
+    /// # Arguments
+    /// * `prefixes` - 环境前缀列表，Topic 以其中某个前缀开头时去掉第一个匹配的前缀
+    pub fn set_kafka_topic_prefixes(&mut self, prefixes: Vec<String>) {
+        self.kafka_topic_prefixes = prefixes;
+        self.rebuild_operation_index(
+            |index| (&mut index.kafka_producers, &mut index.kafka_consumers),
+            |method| &method.kafka_operations,
+            Self::index_kafka_operation,
+        );
+    }
+    
+    /// 按当前的键规范化设置，从所有方法的操作重建一类资源两侧（读/写、生产/消费）的索引
+    /// 
+    /// # Arguments
+    /// * `maps` - 取出该资源两侧的索引
+    /// * `operations` - 取出方法中该资源的操作
+    /// * `index_operation` - 索引单个操作
+    fn rebuild_operation_index<Op: Clone>(
+        &mut self,
+        maps: fn(&mut Self) -> OperationMaps<'_>,
+        operations: fn(&MethodInfo) -> &[Op],
+        index_operation: fn(&mut Self, &str, &Op),
+    ) {
+        let (first, second) = maps(self);
+        first.clear();
+        second.clear();
+        
+        let collected: Vec<(String, Op)> = self.methods.iter()
+            .flat_map(|(name, method)| operations(method).iter().map(move |op| (name.clone(), op.clone())))
+            .collect();
+        for (method_name, operation) in &collected {
+            index_operation(self, method_name, operation);
         }
         
-        // 关联 Redis 键前缀配置
-        for prefix in &config_data.redis_prefixes {
-            self.associate_redis_prefix(prefix);
+        // 方法映射无序，排序保证两侧的方法列表稳定
+        let (first, second) = maps(self);
+        for methods in first.values_mut().chain(second.values_mut()) {
+            methods.sort();
         }
     }
     
-    /// 关联 HTTP 端点配置到代码
+    /// 去掉 Kafka Topic 的环境前缀，未设置前缀或不匹配时原样返回
+    pub fn normalize_kafka_topic(&self, topic: &str) -> String {
+        self.kafka_topic_prefixes.iter()
+            .find_map(|prefix| topic.strip_prefix(prefix.as_str()).filter(|rest| !rest.is_empty()))
+            .unwrap_or(topic)
+            .to_string()
+    }
+    
+    /// 获取构建索引时解析失败的源文件
     /// 
-    /// 查找所有可能调用该端点的方法，并建立关联
-    fn associate_http_endpoint(&mut self, endpoint: &HttpEndpoint) {
-        // 查找所有方法，检查是否有 HTTP 客户端调用匹配该端点
-        let mut consumers = Vec::new();
-        
-        for (method_name, method_info) in &self.methods {
-            // 检查方法调用中是否包含 HTTP 客户端调用
-            if self.method_contains_http_call(method_info, endpoint) {
+    /// 从磁盘缓存加载的索引返回保存索引时记录的文件
+    pub fn parse_failures(&self) -> &[PathBuf] {
+        &self.parse_failures
+    }
+    
+    /// 恢复保存索引时记录的解析诊断，用于从磁盘加载索引
+    /// 
+    /// # Arguments
+    /// * `parse_failures` - 解析失败的源文件
+    /// * `syntax_errors` - 存在语法错误的源文件及各错误节点的位置
+    pub fn restore_parse_diagnostics(
+        &mut self,
+        parse_failures: Vec<PathBuf>,
+        syntax_errors: impl IntoIterator<Item = (PathBuf, Vec<(usize, usize)>)>,
+    ) {
+        self.parse_failures = parse_failures;
+        self.syntax_errors = syntax_errors.into_iter().collect();
+    }
+    
+    /// 获取存在语法错误的源文件中各错误节点的位置（行号、列号，均从 1 开始）
+    /// 
+    /// 这些文件仍按 tree-sitter 恢复后的语法树索引；文件没有语法错误时返回空切片
+    pub fn syntax_error_locations(&self, file_path: &Path) -> &[(usize, usize)] {
+        self.syntax_errors.get(file_path).map(Vec::as_slice).unwrap_or_default()
+    }
+    
+    /// 获取构建索引时存在语法错误的源文件，按路径排序
+    pub fn syntax_error_files(&self) -> Vec<&Path> {
+        let mut files: Vec<&Path> = self.syntax_errors.keys().map(PathBuf::as_path).collect();
+        files.sort_unstable();
+        files
+    }
+    
+    /// 获取构建索引时各源文件的语法错误，按路径排序
+    /// 
+    /// # Returns
+    /// * 每个存在语法错误的源文件对应一个 `ParseError::SyntaxErrors`
+    pub fn syntax_errors(&self) -> Vec<ParseError> {
+        self.syntax_error_files().into_iter()
+            .map(|file| ParseError::SyntaxErrors {
+                path: file.to_path_buf(),
+                locations: self.syntax_error_locations(file).to_vec(),
+            })
+            .collect()
+    }
+    
+    /// 设置启发式匹配 HTTP 客户端调用时端点路径至少需要的非参数段数
+    /// 
+    /// 关联配置中的 HTTP 端点时，非参数段少于该值的端点（如 `/`、`/api`）不再按路径
+    /// 启发式匹配消费者；Feign 等由注解精确记录的消费者不受影响。需要在关联配置前设置
+    /// 
+    /// # Arguments
+    /// * `min_segments` - 最少非参数段数，0 表示不限制
+    pub fn set_min_http_segments(&mut self, min_segments: usize) {
+        self.min_http_segments = min_segments;
+    }
+    
+    /// 设置按路径模式强制指定或禁用语言解析器的规则
+    /// 
+    /// 需要在索引前设置；匹配 `none` 规则的文件不参与索引
+    /// 
+    /// # Arguments
+    /// * `overrides` - 语言覆盖规则，第一条匹配路径的规则生效
+    pub fn set_language_overrides(&mut self, overrides: Vec<LanguageOverride>) {
+        self.language_overrides = overrides;
+    }
+    
+    /// 应用 Feign 服务名到提供者 application.name 的别名
+    /// 
+    /// Feign 调用的路径以服务名开头，提供者路径以 application.name 开头，两者不一致时无法匹配。
+    /// 将消费者路径的第一段按别名替换并重建 HTTP 消费者索引；该服务名的 Feign 客户端配置
+    /// 同时登记到应用名下，追溯时仍能附加到端点节点
+    /// 
+    /// # Arguments
+    /// * `aliases` - 服务名 -> 应用名
+    pub fn set_service_aliases(&mut self, aliases: &HashMap<String, String>) {
+        let mut renamed = Vec::new();
+        for (method_name, method) in self.methods.iter_mut() {
+            let annotations = method.http_annotations.iter_mut()
+                .chain(method.extra_http_annotations.iter_mut())
+                .filter(|a| a.is_feign_client);
+            for annotation in annotations {
+                let (service, rest) = annotation.path.split_once('/').unwrap_or((annotation.path.as_str(), ""));
+                let Some(application) = aliases.get(service) else {
+                    continue;
+                };
+                let old_endpoint = HttpEndpoint {
+                    method: annotation.method.clone(),
+                    path_pattern: annotation.path.clone(),
+                };
+                annotation.path = if rest.is_empty() {
+                    application.clone()
+                } else {
+                    format!("{}/{}", application, rest)
+                };
+                let new_endpoint = HttpEndpoint {
+                    method: annotation.method.clone(),
+                    path_pattern: annotation.path.clone(),
+                };
+                renamed.push((method_name.clone(), old_endpoint, new_endpoint));
+            }
+        }
+        
+        for (method_name, old_endpoint, new_endpoint) in renamed {
+            if let Some(consumers) = self.http_consumers.get_mut(&old_endpoint) {
+                consumers.retain(|consumer| *consumer != method_name);
+                if consumers.is_empty() {
+                    self.http_consumers.remove(&old_endpoint);
+                }
+            }
+            let consumers = self.http_consumers.entry(new_endpoint).or_default();
+            consumers.push(method_name);
+            consumers.sort();
+        }
+        
+        for configs in self.feign_client_configs.values_mut() {
+            for (service, application) in aliases {
+                if let Some(config) = configs.get(service).cloned() {
+                    configs.entry(application.clone()).or_insert(config);
+                }
+            }
+        }
+    }
+    
+    /// 获取 Redis 键按命名空间合并时保留的段数
+    pub fn redis_namespace_depth(&self) -> Option<usize> {
+        self.redis_namespace_depth
+    }
+    
+    /// 查找 Redis 键前缀的读取者
+    /// 
+    /// 键会先经过规范化，`user:{id}` 与 `user:*` 视为同一个键
+    pub fn find_redis_readers(&self, prefix: &str) -> Vec<&str> {
+        sorted_refs(self.redis_readers.get(&redis_namespace_key(prefix, self.redis_namespace_depth)))
+    }
+    
+    /// 查找 Redis 键前缀的写入者
+    /// 
+    /// 键会先经过规范化，`user:{id}` 与 `user:*` 视为同一个键
+    pub fn find_redis_writers(&self, prefix: &str) -> Vec<&str> {
+        sorted_refs(self.redis_writers.get(&redis_namespace_key(prefix, self.redis_namespace_depth)))
+    }
+    
+    /// 查找 RPC 方法的提供者
+    /// 
+    /// # Arguments
+    /// * `rpc_method` - RPC 方法标识：接口完整类名::方法名
+    pub fn find_rpc_providers(&self, rpc_method: &str) -> Vec<&str> {
+        sorted_refs(self.rpc_providers.get(rpc_method))
+    }
+    
+    /// 查找 GraphQL 字段的解析器方法
+    /// 
+    /// # Arguments
+    /// * `field` - GraphQL 字段标识：类型名.字段名
+    pub fn find_graphql_resolvers(&self, field: &str) -> Vec<&str> {
+        sorted_refs(self.graphql_resolvers.get(field))
+    }
+    
+    /// 查找 Spring Batch XML 中以该方法为步骤组件的作业
+    /// 
+    /// # Arguments
+    /// * `method` - tasklet/reader/processor/writer 方法的全限定名
+    pub fn find_batch_jobs(&self, method: &str) -> Vec<&str> {
+        sorted_refs(self.batch_jobs.get(method))
+    }
+    
+    /// 获取 Spring Batch XML 中定义的作业的迭代器: 步骤组件方法 -> 作业名
+    pub fn batch_jobs(&self) -> impl Iterator<Item = (&String, &Vec<String>)> {
+        self.batch_jobs.iter()
+    }
+    
+    /// 查找 Feign 客户端的超时/重试配置
+    /// 
+    /// 优先使用调用方所在模块的配置，模块中没有该客户端时使用工作空间根目录下的配置；
+    /// 客户端自身未设置的配置项使用 `default` 客户端的配置补全
+    /// 
+    /// # Arguments
+    /// * `module` - 调用方所在模块（工作空间下第一级目录名）
+    /// * `client_name` - `@FeignClient` 的服务名
+    pub fn find_feign_client_config(&self, module: &str, client_name: &str) -> Option<FeignClientConfig> {
+        let lookup = |name: &str| {
+            self.feign_client_configs.get(module).and_then(|configs| configs.get(name))
+                .or_else(|| self.feign_client_configs.get("").and_then(|configs| configs.get(name)))
+        };
+        match (lookup(client_name), lookup("default")) {
+            (Some(config), Some(default)) => Some(config.clone().or(default)),
+            (Some(config), None) => Some(config.clone()),
+            (None, default) => default.cloned(),
+        }
+    }
+    
+    /// 获取所有 Feign 客户端配置的迭代器
+    /// 
+    /// # Returns
+    /// * 迭代器，元素为（模块, 客户端名, 配置）
+    pub fn feign_client_configs(&self) -> impl Iterator<Item = (&String, &String, &FeignClientConfig)> {
+        self.feign_client_configs.iter()
+            .flat_map(|(module, configs)| configs.iter().map(move |(name, config)| (module, name, config)))
+    }
+    
+    /// 查找 RPC 方法的消费者
+    /// 
+    /// # Arguments
+    /// * `rpc_method` - RPC 方法标识：接口完整类名::方法名
+    pub fn find_rpc_consumers(&self, rpc_method: &str) -> Vec<&str> {
+        sorted_refs(self.rpc_consumers.get(rpc_method))
+    }
+    
+    /// 查找 Thrift 方法的提供者
+    /// 
+    /// # Arguments
+    /// * `thrift_method` - Thrift 方法标识：服务完整类名::方法名
+    pub fn find_thrift_providers(&self, thrift_method: &str) -> Vec<&str> {
+        sorted_refs(self.thrift_providers.get(thrift_method))
+    }
+    
+    /// 查找 Thrift 方法的消费者
+    /// 
+    /// # Arguments
+    /// * `thrift_method` - Thrift 方法标识：服务完整类名::方法名
+    pub fn find_thrift_consumers(&self, thrift_method: &str) -> Vec<&str> {
+        sorted_refs(self.thrift_consumers.get(thrift_method))
+    }
+    
+    /// 判断方法是否为 Thrift IDL 中声明的服务方法
+    /// 
+    /// 生成的 `Client` 类还包含 `send_xxx`/`recv_xxx` 等辅助方法，只有 IDL 中
+    /// 声明的方法才是跨服务调用。IDL 中没有该服务时无法判断，视为已声明
+    /// 
+    /// # Arguments
+    /// * `service` - 服务完整类名
+    /// * `method` - 方法名
+    pub fn is_thrift_method_declared(&self, service: &str, method: &str) -> bool {
+        self.thrift_services
+            .get(service)
+            .is_none_or(|methods| methods.iter().any(|m| m == method))
+    }
+    
+    /// 获取 Thrift IDL 中定义的所有服务的迭代器
+    pub fn thrift_services(&self) -> impl Iterator<Item = (&String, &Vec<String>)> {
+        self.thrift_services.iter()
+    }
+    
+    /// 获取所有方法的迭代器
+    /// 
+    /// # Returns
+    /// 返回一个迭代器，遍历所有 (方法名, 方法信息) 对
+    pub fn methods(&self) -> impl Iterator<Item = (&String, &MethodInfo)> {
+        self.methods.iter()
+    }
+    
+    /// 将完整索引导出为 JSON，供调试和外部分析工具使用
+    /// 
+    /// 包含方法信息、调用映射、各类资源的提供者/消费者映射和类型层次。各映射按键排序，
+    /// HTTP 端点的键为 `METHOD:path`。与磁盘索引格式无关，不能用于重新加载
+    /// 
+    /// # Returns
+    /// * `Ok(String)` - 格式化的 JSON
+    /// * `Err(serde_json::Error)` - 序列化错误
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        use std::collections::BTreeMap;
+        
+        fn sorted<V: serde::Serialize>(map: &FxHashMap<String, V>) -> BTreeMap<&String, &V> {
+            map.iter().collect()
+        }
+        fn sorted_endpoints<V: serde::Serialize>(map: &FxHashMap<HttpEndpoint, V>) -> BTreeMap<String, &V> {
+            map.iter().map(|(endpoint, value)| (endpoint.to_string(), value)).collect()
+        }
+        
+        let mut declared_db_tables: Vec<&String> = self.declared_db_tables.iter().collect();
+        declared_db_tables.sort();
+        
+        let value = serde_json::json!({
+            "methods": sorted(&self.methods),
+            "method_calls": sorted(&self.method_calls),
+            "reverse_calls": sorted(&self.reverse_calls),
+            "http_providers": sorted_endpoints(&self.http_providers),
+            "http_consumers": sorted_endpoints(&self.http_consumers),
+            "kafka_producers": sorted(&self.kafka_producers),
+            "kafka_consumers": sorted(&self.kafka_consumers),
+            "db_writers": sorted(&self.db_writers),
+            "db_readers": sorted(&self.db_readers),
+            "redis_writers": sorted(&self.redis_writers),
+            "redis_readers": sorted(&self.redis_readers),
+            "rpc_providers": sorted(&self.rpc_providers),
+            "rpc_consumers": sorted(&self.rpc_consumers),
+            "thrift_providers": sorted(&self.thrift_providers),
+            "thrift_consumers": sorted(&self.thrift_consumers),
+            "thrift_services": sorted(&self.thrift_services),
+            "graphql_resolvers": sorted(&self.graphql_resolvers),
+            "batch_jobs": sorted(&self.batch_jobs),
+            "config_associations": sorted(&self.config_associations),
+            "feign_client_configs": self.feign_client_configs.iter()
+                .map(|(module, configs)| (module, sorted(configs)))
+                .collect::<BTreeMap<_, _>>(),
+            "declared_db_tables": declared_db_tables,
+            "interface_implementations": sorted(&self.interface_implementations),
+            "class_superclasses": sorted(&self.class_superclasses),
+        });
+        serde_json::to_string_pretty(&value)
+    }
+    
+    /// 关联配置数据到代码
+    /// 
+    /// 此方法将配置文件中的值与代码中的引用关联起来，其中的 Feign 客户端配置对所有模块生效
+    /// 
+    /// # Arguments
+    /// * `config_data` - 从配置文件解析的配置数据
+    pub fn associate_config_data(&mut self, config_data: &crate::config_parser::ConfigData) {
+        self.associate_module_config_data("", config_data);
+    }
+    
+    /// 关联某个模块的配置数据到代码
+    /// 
+    /// 与 `associate_config_data` 相同，但 Feign 客户端配置只对 `module` 中的调用方生效
+    /// 
+    /// # Arguments
+    /// * `module` - 配置文件所在模块（工作空间下第一级目录名），空字符串表示工作空间根目录
+    /// * `config_data` - 从配置文件解析的配置数据
+    pub fn associate_module_config_data(&mut self, module: &str, config_data: &crate::config_parser::ConfigData) {
+        // 关联 HTTP 端点配置
+        for endpoint in &config_data.http_endpoints {
+            self.associate_http_endpoint(endpoint);
+        }
+        
+        // 关联 Kafka Topic 配置
+        for topic in &config_data.kafka_topics {
+            self.associate_kafka_topic(topic);
+        }
+        
+        // 关联数据库表配置
+        for table in &config_data.db_tables {
+            self.associate_db_table(table);
+            self.declared_db_tables.insert(table.to_lowercase());
+        }
+        
+        // 关联 Redis 键前缀配置
+        for prefix in &config_data.redis_prefixes {
+            self.associate_redis_prefix(prefix);
+        }
+        
+        // 记录 Feign 客户端配置，追溯时附加到 Feign 调用的 HTTP 端点节点
+        for (client_name, config) in &config_data.feign_client_configs {
+            self.feign_client_configs.entry(module.to_string()).or_default()
+                .insert(client_name.clone(), config.clone());
+        }
+        
+        // 记录 Thrift IDL 中的服务定义，追溯时用于识别生成的客户端上的服务方法
+        for service in &config_data.thrift_services {
+            self.thrift_services.insert(service.name.clone(), service.methods.clone());
+        }
+        
+        // 记录 Spring Batch XML 中定义的作业，步骤组件方法作为作业入口点
+        for job in &config_data.batch_jobs {
+            for method in &job.methods {
+                let jobs = self.batch_jobs.entry(method.clone()).or_default();
+                if !jobs.contains(&job.name) {
+                    jobs.push(job.name.clone());
+                }
+            }
+        }
+    }
+    
+    /// 关联 HTTP 端点配置到代码
+    /// 
+    /// 查找所有可能调用该端点的方法，并建立关联
+    fn associate_http_endpoint(&mut self, endpoint: &HttpEndpoint) {
+        // 过短的路径几乎匹配所有调用，不参与启发式匹配
+        let segments = endpoint.path_pattern.split('/')
+            .filter(|part| !part.is_empty() && !part.starts_with('{'))
+            .count();
+        if segments < self.min_http_segments {
+            log::debug!("Skipping heuristic consumer matching for short endpoint {}", endpoint.path_pattern);
+            return;
+        }
+        
+        // 查找所有方法，检查是否有 HTTP 客户端调用匹配该端点
+        let mut consumers = Vec::new();
+        
+        for (method_name, method_info) in &self.methods {
+            // 检查方法调用中是否包含 HTTP 客户端调用
+            if self.method_contains_http_call(method_info, endpoint) {
                 consumers.push(method_name.clone());
             }
         }
@@ -578,14 +1353,14 @@ impl CodeIndex {
         if !consumers.is_empty() {
             self.http_consumers
                 .entry(endpoint.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .extend(consumers.clone());
             
             // 记录配置关联
             let config_key = format!("http:{}:{}", endpoint.method_str(), endpoint.path_pattern);
             self.config_associations
                 .entry(config_key)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .extend(consumers);
         }
     }
@@ -601,6 +1376,7 @@ impl CodeIndex {
             if target_lower.contains("httpclient") 
                 || target_lower.contains("resttemplate")
                 || target_lower.contains("webclient")
+                || target_lower.contains("restclient")
                 || target_lower.contains("http::get")
                 || target_lower.contains("http::post")
                 || target_lower.contains("reqwest")
@@ -645,12 +1421,13 @@ impl CodeIndex {
     fn associate_kafka_topic(&mut self, topic: &str) {
         // 查找所有使用该 topic 的生产者和消费者
         let mut associated_methods = Vec::new();
+        let normalized = self.normalize_kafka_topic(topic);
         
-        if let Some(producers) = self.kafka_producers.get(topic) {
+        if let Some(producers) = self.kafka_producers.get(&normalized) {
             associated_methods.extend(producers.clone());
         }
         
-        if let Some(consumers) = self.kafka_consumers.get(topic) {
+        if let Some(consumers) = self.kafka_consumers.get(&normalized) {
             associated_methods.extend(consumers.clone());
         }
         
@@ -659,7 +1436,7 @@ impl CodeIndex {
             let config_key = format!("kafka:topic:{}", topic);
             self.config_associations
                 .entry(config_key)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .extend(associated_methods);
         }
     }
@@ -669,11 +1446,12 @@ impl CodeIndex {
         // 查找所有访问该表的读取者和写入者
         let mut associated_methods = Vec::new();
         
-        if let Some(readers) = self.db_readers.get(table) {
+        let key = self.table_normalization.normalize(table);
+        if let Some(readers) = self.db_readers.get(&key) {
             associated_methods.extend(readers.clone());
         }
         
-        if let Some(writers) = self.db_writers.get(table) {
+        if let Some(writers) = self.db_writers.get(&key) {
             associated_methods.extend(writers.clone());
         }
         
@@ -682,7 +1460,7 @@ impl CodeIndex {
             let config_key = format!("db:table:{}", table);
             self.config_associations
                 .entry(config_key)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .extend(associated_methods);
         }
     }
@@ -727,31 +1505,27 @@ impl CodeIndex {
             let config_key = format!("redis:key:{}", prefix);
             self.config_associations
                 .entry(config_key)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .extend(associated_methods);
         }
     }
     
     /// 检查 Redis 键是否匹配
     /// 
-    /// 支持通配符匹配，例如 user:* 匹配 user:123
+    /// 键按 `:` 分段逐位比较，`*` 和 `{...}` 占位符视为匹配任意单段，
+    /// 例如 `user:{id}:profile` 匹配 `user:*:profile` 和 `user:123:profile`；
+    /// 末尾带 `*` 的段按前缀匹配并匹配其后任意段，例如 user:* 匹配 user:123
     fn redis_key_matches(&self, pattern: &str, key: &str) -> bool {
         if pattern == key {
             return true;
         }
         
-        // 处理通配符
-        if pattern.contains('*') {
-            let prefix = pattern.trim_end_matches('*');
-            return key.starts_with(prefix);
-        }
-        
-        if key.contains('*') {
-            let prefix = key.trim_end_matches('*');
-            return pattern.starts_with(prefix);
-        }
+        let pattern_segments: Vec<&str> = pattern.split(':').collect();
+        let key_segments: Vec<&str> = key.split(':').collect();
         
-        false
+        // 双向匹配：任一侧都可能带通配符
+        redis_segments_match(&pattern_segments, &key_segments)
+            || redis_segments_match(&key_segments, &pattern_segments)
     }
     
     /// 查找与配置关联的方法
@@ -762,10 +1536,7 @@ impl CodeIndex {
     /// # Returns
     /// 与该配置关联的方法列表
     pub fn find_config_associations(&self, config_key: &str) -> Vec<&str> {
-        self.config_associations
-            .get(config_key)
-            .map(|methods| methods.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+        sorted_refs(self.config_associations.get(config_key))
     }
     
     /// 查找接口的所有实现类
@@ -776,10 +1547,7 @@ impl CodeIndex {
     /// # Returns
     /// 实现该接口的所有类的完整类名列表
     pub fn find_interface_implementations(&self, interface_name: &str) -> Vec<&str> {
-        self.interface_implementations
-            .get(interface_name)
-            .map(|impls| impls.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+        sorted_refs(self.interface_implementations.get(interface_name))
     }
     
     /// 查找类实现的所有接口
@@ -790,10 +1558,95 @@ impl CodeIndex {
     /// # Returns
     /// 该类实现的所有接口的完整类名列表
     pub fn find_class_interfaces(&self, class_name: &str) -> Vec<&str> {
-        self.class_interfaces
-            .get(class_name)
-            .map(|interfaces| interfaces.iter().map(|s| s.as_str()).collect())
-            .unwrap_or_default()
+        sorted_refs(self.class_interfaces.get(class_name))
+    }
+    
+    /// 索引类的继承关系
+    pub(crate) fn index_superclass(&mut self, class_name: &str, superclass: &str) {
+        self.class_superclasses.insert(class_name.to_string(), superclass.to_string());
+        let subclasses = self.subclasses.entry(superclass.to_string()).or_default();
+        if !subclasses.iter().any(|name| name == class_name) {
+            subclasses.push(class_name.to_string());
+        }
+    }
+    
+    /// 查找类继承的父类
+    /// 
+    /// # Arguments
+    /// * `class_name` - 类的完整类名
+    /// 
+    /// # Returns
+    /// 父类的完整类名，未声明 `extends` 时返回 `None`
+    pub fn find_superclass(&self, class_name: &str) -> Option<&str> {
+        self.class_superclasses.get(class_name).map(|s| s.as_str())
+    }
+    
+    /// 获取所有类及其父类
+    pub fn class_superclasses(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.class_superclasses.iter()
+    }
+    
+    /// 解析继承的方法调用：被调用类未定义该方法时，沿父类链查找定义该方法的类
+    /// 
+    /// # Arguments
+    /// * `method_call_target` - 方法调用目标（格式：ClassName::methodName）
+    /// 
+    /// # Returns
+    /// 定义该方法的最近父类中的方法；目标已在索引中或父类链中都未定义时返回原始目标
+    pub fn resolve_inherited_call(&self, method_call_target: &str) -> String {
+        if self.methods.contains_key(method_call_target) {
+            return method_call_target.to_string();
+        }
+        
+        if let Some((class_name, method_name)) = method_call_target.rsplit_once("::") {
+            let mut seen = FxHashSet::default();
+            let mut current = class_name;
+            while let Some(superclass) = self.find_superclass(current) {
+                // 防止错误的继承声明造成循环
+                if !seen.insert(superclass) {
+                    break;
+                }
+                let inherited = format!("{}::{}", superclass, method_name);
+                if self.methods.contains_key(&inherited) {
+                    return inherited;
+                }
+                current = superclass;
+            }
+        }
+        
+        method_call_target.to_string()
+    }
+    
+    /// 查找通过子类调用时会解析到该方法的调用目标
+    /// 
+    /// 未重写该方法的子类（包括间接子类）继承父类的实现，对 `Sub::method` 的调用
+    /// 实际执行的是父类的方法
+    /// 
+    /// # Arguments
+    /// * `method` - 方法的完整限定名（格式：ClassName::methodName）
+    /// 
+    /// # Returns
+    /// 继承该方法的子类中的同名方法限定名列表
+    pub fn find_inherited_call_targets(&self, method: &str) -> Vec<String> {
+        let Some((class_name, method_name)) = method.rsplit_once("::") else {
+            return Vec::new();
+        };
+        
+        let mut targets = Vec::new();
+        let mut seen = FxHashSet::default();
+        let mut pending = vec![class_name];
+        while let Some(class_name) = pending.pop() {
+            for subclass in self.subclasses.get(class_name).into_iter().flatten() {
+                let target = format!("{}::{}", subclass, method_name);
+                // 重写了该方法的子类及其子类不再继承父类实现
+                if self.methods.contains_key(&target) || !seen.insert(subclass.as_str()) {
+                    continue;
+                }
+                targets.push(target);
+                pending.push(subclass);
+            }
+        }
+        targets
     }
     
     /// 解析方法调用目标，如果是接口且只有一个实现类，则返回实现类的方法
@@ -840,6 +1693,378 @@ impl CodeIndex {
     }
 }
 
+/// 规范化 Redis 键
+/// 
+/// 将 `{...}` 形式的占位符段统一替换为 `*`，使 `user:{id}` 与 `user:*`
+/// 归并为同一个键
+/// 
+/// # Arguments
+/// * `key` - 原始键模式
+/// 
+/// # Returns
+/// * `String` - 规范化后的键模式
+pub fn normalize_redis_key(key: &str) -> String {
+    key.split(':')
+        .map(|segment| if is_redis_wildcard_segment(segment) { "*" } else { segment })
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// 计算 Redis 键的命名空间标识
+/// 
+/// 先规范化键，再只保留前 `depth` 个冒号分隔段，使细粒度的键（如 `user:1`、`user:2`）
+/// 合并为同一个命名空间
+/// 
+/// # Arguments
+/// * `key` - 原始键模式
+/// * `depth` - 保留的段数（应至少为 1，命令行会拒绝 0），`None` 表示不截断
+/// 
+/// # Returns
+/// * `String` - 命名空间标识
+pub fn redis_namespace_key(key: &str, depth: Option<usize>) -> String {
+    let normalized = normalize_redis_key(key);
+    match depth {
+        Some(depth) => normalized
+            .split(':')
+            .take(depth)
+            .collect::<Vec<_>>()
+            .join(":"),
+        None => normalized,
+    }
+}
+
+/// 将名称列表转换为按字典序排序的引用列表
+/// 
+/// 索引中列表的插入顺序取决于并行解析的完成顺序，排序后查询结果与索引顺序无关
+fn sorted_refs(names: Option<&Vec<String>>) -> Vec<&str> {
+    let mut refs: Vec<&str> = names
+        .map(|names| names.iter().map(|s| s.as_str()).collect())
+        .unwrap_or_default();
+    refs.sort_unstable();
+    refs
+}
+
+/// 查找与端点匹配（见 `HttpEndpoint::matches`）的映射项
+/// 
+/// 具体方法的端点只需查找自身和同路径的 `Any` 端点，`Any` 端点需要遍历所有端点
+fn matching_http_entries<'a, V>(map: &'a FxHashMap<HttpEndpoint, V>, endpoint: &HttpEndpoint) -> Vec<&'a V> {
+    if endpoint.method == HttpMethod::Any {
+        return map.iter()
+            .filter(|(candidate, _)| candidate.matches(endpoint))
+            .map(|(_, value)| value)
+            .collect();
+    }
+    let any = HttpEndpoint {
+        method: HttpMethod::Any,
+        path_pattern: endpoint.path_pattern.clone(),
+    };
+    map.get(endpoint).into_iter().chain(map.get(&any)).collect()
+}
+
+/// 递归收集工作空间中的所有文件
+/// 
+/// 跳过隐藏目录（`.` 开头）和常见的构建目录（`target`、`build`、`node_modules`）
+/// 
+/// # Arguments
+/// * `dir` - 起始目录
+/// 
+/// # Returns
+/// * `Ok(Vec<PathBuf>)` - 目录中的文件路径
+/// * `Err(IndexError)` - 读取目录失败
+pub fn collect_workspace_files(dir: &Path) -> Result<Vec<PathBuf>, IndexError> {
+    let mut files = Vec::new();
+    collect_files_recursive(dir, &[], &mut files)?;
+    Ok(files)
+}
+
+/// 递归收集文件，跳过 `excluded_dirs` 中的目录（规范化后的绝对路径）
+fn collect_files_recursive(dir: &Path, excluded_dirs: &[PathBuf], files: &mut Vec<PathBuf>) -> Result<(), IndexError> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    
+    let entries = fs::read_dir(dir)
+        .map_err(|e| IndexError::IoError {
+            path: dir.to_path_buf(),
+            error: e.to_string(),
+        })?;
+    
+    for entry in entries {
+        let entry = entry.map_err(|e| IndexError::IoError {
+            path: dir.to_path_buf(),
+            error: e.to_string(),
+        })?;
+        
+        let path = entry.path();
+        
+        // 跳过隐藏目录和常见的构建目录
+        if let Some(name) = path.file_name().and_then(|n| n.to_str())
+            && (name.starts_with('.') || name == "target" || name == "build" || name == "node_modules")
+        {
+            continue;
+        }
+        
+        if path.is_dir() {
+            if !excluded_dirs.is_empty()
+                && fs::canonicalize(&path).is_ok_and(|canonical| excluded_dirs.contains(&canonical))
+            {
+                continue;
+            }
+            collect_files_recursive(&path, excluded_dirs, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    
+    Ok(())
+}
+
+/// 数据库表名规范化规则
+/// 
+/// SQL 与实体类的表名约定常常不一致（如 `user`、`users`、`t_user`），
+/// 规范化后同一张表的读写者归并到同一个表节点。所有规则关闭时表名保持不变
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TableNormalization {
+    /// 去除 `t_` 前缀
+    pub strip_prefix: bool,
+    /// 将英文复数形式的表名转换为单数
+    pub singularize: bool,
+    /// 转换为小写
+    pub lowercase: bool,
+}
+
+impl TableNormalization {
+    /// 是否启用了任一规则
+    pub fn is_enabled(&self) -> bool {
+        self.strip_prefix || self.singularize || self.lowercase
+    }
+    
+    /// 按规则规范化表名
+    /// 
+    /// 带 schema 的表名（`schema.table`）只处理表名部分
+    /// 
+    /// # Arguments
+    /// * `table` - 原始表名
+    /// 
+    /// # Returns
+    /// * 规范化后的表名
+    pub fn normalize(&self, table: &str) -> String {
+        if !self.is_enabled() {
+            return table.to_string();
+        }
+        
+        let (schema, name) = match table.rsplit_once('.') {
+            Some((schema, name)) => (Some(schema), name),
+            None => (None, table),
+        };
+        
+        let mut name = name.to_string();
+        if self.strip_prefix && name.len() > 2 && name.get(..2).is_some_and(|prefix| prefix.eq_ignore_ascii_case("t_")) {
+            name = name[2..].to_string();
+        }
+        if self.singularize {
+            name = singularize(&name);
+        }
+        
+        let normalized = match schema {
+            Some(schema) => format!("{}.{}", schema, name),
+            None => name,
+        };
+        if self.lowercase {
+            normalized.to_lowercase()
+        } else {
+            normalized
+        }
+    }
+}
+
+/// 将英文复数单词转换为单数（简单的后缀规则，保留原大小写）
+/// 
+/// - `categories` -> `category`
+/// - `addresses`、`boxes`、`matches` -> `address`、`box`、`match`
+/// - `users` -> `user`；以 `ss`、`us`、`is` 结尾的单词（如 `status`）保持不变
+fn singularize(word: &str) -> String {
+    let lower = word.to_lowercase();
+    if lower.len() != word.len() {
+        return word.to_string();
+    }
+    
+    if lower.len() > 3 && lower.ends_with("ies") {
+        let stem = &word[..word.len() - 3];
+        let y = if word.ends_with("IES") { "Y" } else { "y" };
+        return format!("{}{}", stem, y);
+    }
+    if ["sses", "shes", "ches", "xes", "zes"].iter().any(|suffix| lower.ends_with(suffix)) {
+        return word[..word.len() - 2].to_string();
+    }
+    if lower.len() > 1 && lower.ends_with('s') && !["ss", "us", "is"].iter().any(|suffix| lower.ends_with(suffix)) {
+        return word[..word.len() - 1].to_string();
+    }
+    word.to_string()
+}
+
+/// 将以字符串常量引用的 Kafka topic 解析为常量值
+/// 
+/// 常量可能声明在其他文件中，因此需要在所有文件解析完成后统一解析；
+/// 找不到对应常量的保留源码中的引用文本
+/// 
+/// # Arguments
+/// * `parsed_files` - 工作空间中所有已解析的文件
+pub fn resolve_topic_constants(parsed_files: &mut [ParsedFile]) {
+    let constants: FxHashMap<String, String> = parsed_files.iter()
+        .flat_map(|file| &file.string_constants)
+        .map(|constant| (constant.name.clone(), constant.value.clone()))
+        .collect();
+    
+    let operations = parsed_files.iter_mut().flat_map(|file| {
+        let method_ops = file.classes.iter_mut()
+            .flat_map(|class| class.methods.iter_mut())
+            .flat_map(|method| method.kafka_operations.iter_mut());
+        let function_ops = file.functions.iter_mut()
+            .flat_map(|function| function.kafka_operations.iter_mut());
+        method_ops.chain(function_ops)
+    });
+    
+    for operation in operations {
+        let Some(constant) = &operation.topic_constant else {
+            continue;
+        };
+        match constants.get(constant) {
+            Some(value) => {
+                operation.topic = value.clone();
+                operation.topic_constant = None;
+            }
+            None => log::debug!("未找到 Kafka topic 常量: {}", constant),
+        }
+    }
+}
+
+/// 将 WebFlux 函数式路由的 HTTP 接口信息附加到处理方法
+/// 
+/// 路由通常声明在配置类中，而处理方法位于其他文件，因此在收集整个工作空间的解析结果后处理。
+/// 已有 HTTP 注解的方法保持不变；同一处理方法对应多条路由时只使用第一条
+pub fn resolve_functional_routes(parsed_files: &mut [ParsedFile]) {
+    // 处理方法 -> 第一条指向它的路由
+    let mut routes: FxHashMap<String, HttpAnnotation> = FxHashMap::default();
+    for route in parsed_files.iter().flat_map(|file| &file.functional_routes) {
+        routes.entry(route.handler.clone()).or_insert_with(|| route.annotation.clone());
+    }
+    if routes.is_empty() {
+        return;
+    }
+    
+    let methods = parsed_files.iter_mut()
+        .flat_map(|file| file.classes.iter_mut())
+        .flat_map(|class| class.methods.iter_mut());
+    for method in methods {
+        if method.http_annotations.is_none()
+            && let Some(annotation) = routes.remove(&method.full_qualified_name)
+        {
+            method.http_annotations = Some(annotation);
+        }
+    }
+    
+    for handler in routes.keys() {
+        log::debug!("未找到函数式路由的处理方法: {}", handler);
+    }
+}
+
+/// 将 C# 文件中按源码写法记录的类型名补全为工作空间中的完整类型名
+/// 
+/// 按 C# 的查找顺序，依次尝试所在类型及外层命名空间（由内向外）、文件中 `using` 引入的命名空间，
+/// 取第一个在工作空间中声明过的类型；都找不到的（如 `ControllerBase`、`IDisposable` 等框架类型）
+/// 保持原样。处理基类、接口和调用目标 `T::method` 中的 `T`
+/// 
+/// # Arguments
+/// * `parsed_files` - 工作空间中所有已解析的文件
+pub fn resolve_csharp_types(parsed_files: &mut [ParsedFile]) {
+    let types: FxHashSet<String> = parsed_files.iter()
+        .filter(|file| file.language == "csharp")
+        .flat_map(|file| file.classes.iter().map(|class| class.name.clone()))
+        .collect();
+    if types.is_empty() {
+        return;
+    }
+    
+    for file in parsed_files.iter_mut().filter(|file| file.language == "csharp") {
+        let namespaces: Vec<&str> = file.imports.iter()
+            .filter(|import| import.items.is_empty())
+            .map(|import| import.module.as_str())
+            .collect();
+        
+        for class in &mut file.classes {
+            // 所在类型自身及各层外层名称，由内向外
+            let scopes: Vec<&str> = std::iter::once(class.name.as_str())
+                .chain(class.name.match_indices('.').rev().map(|(i, _)| &class.name[..i]))
+                .collect();
+            let resolve = |name: &str| -> String {
+                if types.contains(name) {
+                    return name.to_string();
+                }
+                scopes.iter().chain(&namespaces)
+                    .map(|scope| format!("{}.{}", scope, name))
+                    .find(|candidate| types.contains(candidate))
+                    .unwrap_or_else(|| name.to_string())
+            };
+            
+            let extends = class.extends.as_deref().map(resolve);
+            let implements = class.implements.iter().map(|interface| resolve(interface)).collect();
+            for call in class.methods.iter_mut().flat_map(|method| method.calls.iter_mut()) {
+                if let Some((type_name, method)) = call.target.split_once("::") {
+                    call.target = format!("{}::{}", resolve(type_name), method);
+                }
+            }
+            class.extends = extends;
+            class.implements = implements;
+        }
+    }
+}
+
+/// 判断方法是否为入口点，见 `CodeIndex::entry_points`
+fn is_entry_point(method: &MethodInfo) -> bool {
+    use crate::types::{KafkaOpType, RpcOpType};
+    
+    method.http_annotations.as_ref().is_some_and(|annotation| !annotation.is_feign_client)
+        || method.kafka_operations.iter().any(|op| op.operation_type == KafkaOpType::Consume)
+        || method.scheduled
+        || method.graphql_resolver.is_some()
+        || method.batch_job.is_some()
+        || method.rpc_operations.iter().any(|op| op.operation_type == RpcOpType::Provide)
+        || method.thrift_operations.iter().any(|op| op.operation_type == RpcOpType::Provide)
+}
+
+/// 判断 Redis 键段是否为通配段（`*` 或 `{...}` 占位符）
+fn is_redis_wildcard_segment(segment: &str) -> bool {
+    segment == "*" || (segment.len() >= 2 && segment.starts_with('{') && segment.ends_with('}'))
+}
+
+/// 按段匹配 Redis 键，`pattern` 末尾的 `*` 段可以匹配剩余的任意段
+fn redis_segments_match(pattern: &[&str], key: &[&str]) -> bool {
+    for (i, pattern_segment) in pattern.iter().enumerate() {
+        let is_last = i + 1 == pattern.len();
+        
+        // 末尾通配：段内前缀匹配，其后的段不再比较
+        if is_last && pattern_segment.ends_with('*') {
+            let prefix = pattern_segment.trim_end_matches('*');
+            return key.get(i)
+                .is_some_and(|s| is_redis_wildcard_segment(s) || s.starts_with(prefix));
+        }
+        
+        let Some(key_segment) = key.get(i) else {
+            return false;
+        };
+        
+        if !(is_redis_wildcard_segment(pattern_segment)
+            || is_redis_wildcard_segment(key_segment)
+            || pattern_segment == key_segment)
+        {
+            return false;
+        }
+    }
+    
+    pattern.len() == key.len()
+}
+
 impl Default for CodeIndex {
     fn default() -> Self {
         Self::new()
@@ -859,6 +2084,166 @@ mod tests {
         assert_eq!(index.method_calls.len(), 0);
     }
     
+    #[test]
+    fn test_index_in_memory_sources() {
+        let parsers: Vec<Box<dyn LanguageParser>> = vec![
+            Box::new(crate::java_parser::JavaParser::new().unwrap()),
+        ];
+        let sources = vec![
+            (PathBuf::from("order/src/OrderService.java"), r#"
+                package com.example.order;
+                
+                public class OrderService {
+                    private PaymentClient paymentClient;
+                    
+                    public void createOrder() {
+                        paymentClient.charge();
+                    }
+                }
+            "#.to_string()),
+            (PathBuf::from("order/src/PaymentClient.java"), r#"
+                package com.example.order;
+                
+                public class PaymentClient {
+                    public void charge() {}
+                }
+            "#.to_string()),
+            (PathBuf::from("order/README.md"), "# Order".to_string()),
+        ];
+        
+        let mut index = CodeIndex::new();
+        index.index_sources(&sources, &parsers).unwrap();
+        
+        assert_eq!(index.methods.len(), 2);
+        assert!(index.parse_failures().is_empty());
+        assert_eq!(
+            index.find_callers("com.example.order.PaymentClient::charge"),
+            vec!["com.example.order.OrderService::createOrder"]
+        );
+        let charge = index.find_method("com.example.order.PaymentClient::charge").unwrap();
+        assert_eq!(charge.file_path, PathBuf::from("order/src/PaymentClient.java"));
+        
+        // 存在语法错误的文件仍按恢复后的语法树索引，并记录错误位置
+        let broken = PathBuf::from("order/src/Broken.java");
+        let source = "package com.example.order;\n\npublic class Broken {\n    void run() {\n        int x = ;\n        charge();\n    }\n    void charge() {}\n}\n";
+        index.index_sources(&[(broken.clone(), source.to_string())], &parsers).unwrap();
+        assert!(index.parse_failures().is_empty());
+        assert!(index.find_method("com.example.order.Broken::run").is_some());
+        assert_eq!(index.syntax_error_locations(&broken), [(5, 17)]);
+        assert_eq!(index.syntax_error_files(), vec![broken.as_path()]);
+        assert!(matches!(
+            &index.syntax_errors()[..],
+            [ParseError::SyntaxErrors { path, locations }] if *path == broken && *locations == [(5, 17)]
+        ));
+        assert!(index.syntax_error_locations(Path::new("order/src/OrderService.java")).is_empty());
+    }
+    
+    #[test]
+    fn test_extra_source_root_resolves_external_calls() {
+        let parsers: Vec<Box<dyn LanguageParser>> = vec![
+            Box::new(crate::java_parser::JavaParser::new().unwrap()),
+        ];
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("workspace");
+        let libs_src = temp_dir.path().join("libs-src");
+        fs::create_dir_all(&workspace).unwrap();
+        fs::create_dir_all(&libs_src).unwrap();
+        fs::write(workspace.join("OrderService.java"), r#"
+            package com.example.order;
+            
+            import org.thirdparty.Mailer;
+            
+            public class OrderService {
+                private Mailer mailer;
+                
+                public void createOrder() {
+                    mailer.send();
+                }
+            }
+        "#).unwrap();
+        fs::write(libs_src.join("Mailer.java"), r#"
+            package org.thirdparty;
+            
+            public class Mailer {
+                public void send() {}
+            }
+        "#).unwrap();
+        // 与工作空间同名的类，不应覆盖工作空间中的方法
+        fs::write(libs_src.join("OrderService.java"), r#"
+            package com.example.order;
+            
+            public class OrderService {
+                public void createOrder() {}
+            }
+        "#).unwrap();
+        
+        let mut index = CodeIndex::new();
+        index.index_workspace(&workspace, &parsers).unwrap();
+        assert!(index.find_method("org.thirdparty.Mailer::send").is_none());
+        
+        let mut index = CodeIndex::new();
+        index.set_extra_source_roots(vec![libs_src.clone()]);
+        index.index_workspace(&workspace, &parsers).unwrap();
+        let send = index.find_method("org.thirdparty.Mailer::send").unwrap();
+        assert_eq!(send.file_path, libs_src.join("Mailer.java"));
+        assert!(index.is_third_party("org.thirdparty.Mailer::send"));
+        assert_eq!(
+            index.find_callers("org.thirdparty.Mailer::send"),
+            vec!["com.example.order.OrderService::createOrder"]
+        );
+        
+        let create_order = index.find_method("com.example.order.OrderService::createOrder").unwrap();
+        assert_eq!(create_order.file_path, workspace.join("OrderService.java"));
+        assert!(!index.is_third_party("com.example.order.OrderService::createOrder"));
+        
+        // 追溯继续进入第三方方法，并标记其节点
+        let tracer = crate::impact_tracer::ImpactTracer::new(&index, crate::impact_tracer::TraceConfig::default());
+        let graph = tracer.trace_impact(&["com.example.order.OrderService::createOrder".to_string()]).unwrap();
+        let send_node = graph.get_node("method:org.thirdparty.Mailer::send").unwrap();
+        assert_eq!(send_node.metadata.properties.get("third_party").map(String::as_str), Some("true"));
+        
+        // 不存在的目录报错，而不是静默忽略；在解析工作空间之前检查
+        let mut index = CodeIndex::new();
+        index.set_extra_source_roots(vec![libs_src.clone(), temp_dir.path().join("lib-src")]);
+        assert!(index.index_workspace(&workspace, &parsers).is_err());
+        assert_eq!(index.methods.len(), 0);
+    }
+    
+    #[test]
+    fn test_index_sources_with_language_overrides() {
+        let parsers: Vec<Box<dyn LanguageParser>> = vec![
+            Box::new(crate::java_parser::JavaParser::new().unwrap()),
+        ];
+        let sources = vec![
+            (PathBuf::from("legacy/OrderService.txt"), r#"
+                package com.example.legacy;
+                
+                public class OrderService {
+                    public void createOrder() {}
+                }
+            "#.to_string()),
+            (PathBuf::from("generated/OrderDto.java"), r#"
+                package com.example.generated;
+                
+                public class OrderDto {
+                    public void copy() {}
+                }
+            "#.to_string()),
+        ];
+        
+        let mut index = CodeIndex::new();
+        index.set_language_overrides(vec![
+            "legacy/*.txt=java".parse().unwrap(),
+            "generated/**=none".parse().unwrap(),
+        ]);
+        index.index_sources(&sources, &parsers).unwrap();
+        
+        // .txt 文件按 Java 解析，generated 目录被跳过
+        assert!(index.find_method("com.example.legacy.OrderService::createOrder").is_some());
+        assert!(index.find_method("com.example.generated.OrderDto::copy").is_none());
+        assert!(index.parse_failures().is_empty());
+    }
+    
     #[test]
     fn test_index_method_with_calls() {
         let mut index = CodeIndex::new();
@@ -872,12 +2257,11 @@ mod tests {
                 MethodCall {
                     target: "com.example.Bar::bar".to_string(),
                     line: 15,
+                    condition: None,
+                    low_confidence: false,
                 },
             ],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&method).unwrap();
@@ -894,6 +2278,40 @@ mod tests {
         assert_eq!(callers, vec!["com.example.Foo::foo"]);
     }
     
+    #[test]
+    fn test_to_json_dumps_methods_and_calls() {
+        let mut index = CodeIndex::new();
+        
+        let method = MethodInfo {
+            name: "foo".to_string(),
+            full_qualified_name: "com.example.Foo::foo".to_string(),
+            file_path: std::path::PathBuf::from("Foo.java"),
+            line_range: (10, 20),
+            calls: vec![
+                MethodCall {
+                    target: "com.example.Bar::bar".to_string(),
+                    line: 15,
+                    condition: None,
+                    low_confidence: false,
+                },
+            ],
+            http_annotations: Some(HttpAnnotation {
+                method: crate::types::HttpMethod::GET,
+                path: "/api/foo".to_string(),
+                path_params: vec![],
+                is_feign_client: false,
+            }),
+            ..Default::default()
+        };
+        index.index_method(&method).unwrap();
+        
+        let dump: serde_json::Value = serde_json::from_str(&index.to_json().unwrap()).unwrap();
+        assert_eq!(dump["methods"]["com.example.Foo::foo"]["line_range"], serde_json::json!([10, 20]));
+        assert_eq!(dump["method_calls"]["com.example.Foo::foo"], serde_json::json!(["com.example.Bar::bar"]));
+        assert_eq!(dump["reverse_calls"]["com.example.Bar::bar"], serde_json::json!(["com.example.Foo::foo"]));
+        assert_eq!(dump["http_providers"]["GET:/api/foo"], "com.example.Foo::foo");
+    }
+    
     #[test]
     fn test_index_kafka_operations() {
         let mut index = CodeIndex::new();
@@ -903,17 +2321,16 @@ mod tests {
             full_qualified_name: "com.example.Producer::sendMessage".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (10, 20),
-            calls: vec![],
-            http_annotations: None,
             kafka_operations: vec![
                 crate::types::KafkaOperation {
                     operation_type: KafkaOpType::Produce,
                     topic: "user-events".to_string(),
                     line: 15,
+                    topic_constant: None,
+                    value_type: None,
                 },
             ],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&producer_method).unwrap();
@@ -922,6 +2339,34 @@ mod tests {
         assert_eq!(producers, vec!["com.example.Producer::sendMessage"]);
     }
     
+    #[test]
+    fn test_kafka_topic_prefix_stripping() {
+        let mut index = CodeIndex::new();
+        
+        let kafka_method = |qualified_name: &str, operation_type: KafkaOpType, topic: &str| MethodInfo {
+            line_range: (10, 20),
+            kafka_operations: vec![crate::types::KafkaOperation {
+                operation_type,
+                topic: topic.to_string(),
+                line: 15,
+                topic_constant: None,
+                value_type: None,
+            }],
+            ..MethodInfo::new(qualified_name, "OrderEvents.java")
+        };
+        index.index_method(&kafka_method("com.example.OrderService::publish", KafkaOpType::Produce, "order-events")).unwrap();
+        index.index_method(&kafka_method("com.example.BillingListener::onOrder", KafkaOpType::Consume, "prod.order-events")).unwrap();
+        
+        // 默认不去前缀，带环境前缀的消费者与生产者不匹配
+        assert_eq!(index.find_kafka_consumers("order-events"), Vec::<&str>::new());
+        
+        index.set_kafka_topic_prefixes(vec!["staging.".to_string(), "prod.".to_string()]);
+        assert_eq!(index.find_kafka_consumers("order-events"), vec!["com.example.BillingListener::onOrder"]);
+        assert_eq!(index.find_kafka_producers("prod.order-events"), vec!["com.example.OrderService::publish"]);
+        assert_eq!(index.normalize_kafka_topic("prod."), "prod.");
+        assert_eq!(index.normalize_kafka_topic("dev.order-events"), "dev.order-events");
+    }
+    
     #[test]
     fn test_index_db_operations() {
         let mut index = CodeIndex::new();
@@ -931,9 +2376,6 @@ mod tests {
             full_qualified_name: "com.example.UserDao::getUser".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (10, 20),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
             db_operations: vec![
                 crate::types::DbOperation {
                     operation_type: DbOpType::Select,
@@ -941,7 +2383,7 @@ mod tests {
                     line: 15,
                 },
             ],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&method).unwrap();
@@ -959,10 +2401,6 @@ mod tests {
             full_qualified_name: "com.example.UserCache::cacheUser".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (10, 20),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
             redis_operations: vec![
                 crate::types::RedisOperation {
                     operation_type: RedisOpType::Set,
@@ -970,6 +2408,7 @@ mod tests {
                     line: 15,
                 },
             ],
+            ..Default::default()
         };
         
         index.index_method(&method).unwrap();
@@ -978,6 +2417,118 @@ mod tests {
         assert_eq!(writers, vec!["com.example.UserCache::cacheUser"]);
     }
     
+    #[test]
+    fn test_redis_namespace_depth_collapses_keys() {
+        let mut index = CodeIndex::new();
+        
+        let redis_method = |qualified_name: &str, operation_type: RedisOpType, key_pattern: &str| MethodInfo {
+            line_range: (10, 20),
+            redis_operations: vec![crate::types::RedisOperation {
+                operation_type,
+                key_pattern: key_pattern.to_string(),
+                line: 15,
+            }],
+            ..MethodInfo::new(qualified_name, "UserCache.java")
+        };
+        index.index_method(&redis_method("com.example.UserCache::cacheUser", RedisOpType::Set, "user:1")).unwrap();
+        index.index_method(&redis_method("com.example.UserCache::cacheProfile", RedisOpType::Set, "user:{id}:profile")).unwrap();
+        index.index_method(&redis_method("com.example.UserCache::loadUser", RedisOpType::Get, "user:2")).unwrap();
+        index.index_method(&redis_method("com.example.OrderCache::cacheOrder", RedisOpType::Set, "order:1")).unwrap();
+        
+        // 默认不合并，不同的键互不相关
+        assert_eq!(index.find_redis_writers("user:2"), Vec::<&str>::new());
+        
+        // 深度 1：所有 user:* 键合并为 user 命名空间
+        index.set_redis_namespace_depth(Some(1));
+        assert_eq!(index.redis_namespace_depth(), Some(1));
+        assert_eq!(index.find_redis_writers("user:2"), vec![
+            "com.example.UserCache::cacheProfile",
+            "com.example.UserCache::cacheUser",
+        ]);
+        assert_eq!(index.find_redis_readers("user"), vec!["com.example.UserCache::loadUser"]);
+        assert_eq!(index.find_redis_writers("order:99"), vec!["com.example.OrderCache::cacheOrder"]);
+        
+        // 深度 2：user:{id}:profile 与 user:1 不再合并
+        index.set_redis_namespace_depth(Some(2));
+        assert_eq!(index.find_redis_writers("user:*"), vec!["com.example.UserCache::cacheProfile"]);
+        assert_eq!(index.find_redis_writers("user:1"), vec!["com.example.UserCache::cacheUser"]);
+        
+        assert_eq!(redis_namespace_key("user:{id}:profile", Some(1)), "user");
+        assert_eq!(redis_namespace_key("user:{id}:profile", Some(2)), "user:*");
+        assert_eq!(redis_namespace_key("user:{id}:profile", None), "user:*:profile");
+    }
+    
+    #[test]
+    fn test_finder_results_independent_of_index_order() {
+        use crate::types::{DbOperation, KafkaOperation, RedisOperation};
+        
+        let method = |name: &str| MethodInfo {
+            line_range: (1, 10),
+            calls: vec![MethodCall { target: "com.example.Shared::run".to_string(), line: 5, condition: None, low_confidence: false }],
+            kafka_operations: vec![KafkaOperation {
+                operation_type: KafkaOpType::Produce,
+                topic: "events".to_string(),
+                line: 6,
+                topic_constant: None,
+                value_type: None,
+            }],
+            db_operations: vec![DbOperation {
+                operation_type: DbOpType::Select,
+                table: "orders".to_string(),
+                line: 7,
+            }],
+            redis_operations: vec![RedisOperation {
+                operation_type: RedisOpType::Set,
+                key_pattern: "order:1".to_string(),
+                line: 8,
+            }],
+            ..MethodInfo::new(name, "test.java")
+        };
+        let names = ["com.example.C::c", "com.example.A::a", "com.example.B::b"];
+        
+        let mut forward = CodeIndex::new();
+        for name in names {
+            forward.index_method(&method(name)).unwrap();
+        }
+        let mut backward = CodeIndex::new();
+        for name in names.iter().rev() {
+            backward.index_method(&method(name)).unwrap();
+        }
+        
+        let expected = vec!["com.example.A::a", "com.example.B::b", "com.example.C::c"];
+        for index in [&forward, &backward] {
+            assert_eq!(index.find_callers("com.example.Shared::run"), expected);
+            assert_eq!(index.find_kafka_producers("events"), expected);
+            assert_eq!(index.find_db_readers("orders"), expected);
+            assert_eq!(index.find_redis_writers("order:1"), expected);
+        }
+    }
+    
+    #[test]
+    fn test_table_normalization() {
+        let all = TableNormalization {
+            strip_prefix: true,
+            singularize: true,
+            lowercase: true,
+        };
+        assert_eq!(all.normalize("t_users"), "user");
+        assert_eq!(all.normalize("T_USERS"), "user");
+        assert_eq!(all.normalize("user"), "user");
+        assert_eq!(all.normalize("order_categories"), "order_category");
+        assert_eq!(all.normalize("addresses"), "address");
+        assert_eq!(all.normalize("status"), "status");
+        assert_eq!(all.normalize("shop.t_orders"), "shop.order");
+        // 非 ASCII 表名不在字符中间截断
+        assert_eq!(all.normalize("订单表"), "订单表");
+        assert_eq!(all.normalize("t_订单"), "订单");
+        
+        // 未启用任何规则时保持原样
+        assert_eq!(TableNormalization::default().normalize("t_Users"), "t_Users");
+        
+        let lowercase_only = TableNormalization { lowercase: true, ..TableNormalization::default() };
+        assert_eq!(lowercase_only.normalize("T_Users"), "t_users");
+    }
+    
     #[test]
     fn test_find_method_query() {
         let mut index = CodeIndex::new();
@@ -987,11 +2538,7 @@ mod tests {
             full_qualified_name: "com.example.Test::testMethod".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (5, 15),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&method).unwrap();
@@ -1020,12 +2567,11 @@ mod tests {
                 MethodCall {
                     target: "com.example.B::methodB".to_string(),
                     line: 5,
+                    condition: None,
+                    low_confidence: false,
                 },
             ],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         let method_b = MethodInfo {
@@ -1037,12 +2583,11 @@ mod tests {
                 MethodCall {
                     target: "com.example.C::methodC".to_string(),
                     line: 5,
+                    condition: None,
+                    low_confidence: false,
                 },
             ],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         let method_c = MethodInfo {
@@ -1050,11 +2595,7 @@ mod tests {
             full_qualified_name: "com.example.C::methodC".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (1, 10),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&method_a).unwrap();
@@ -1093,16 +2634,13 @@ mod tests {
             full_qualified_name: "com.example.UserController::getUser".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (10, 20),
-            calls: vec![],
             http_annotations: Some(HttpAnnotation {
                 method: HttpMethod::GET,
                 path: "/api/users/{id}".to_string(),
                 path_params: vec!["id".to_string()],
                 is_feign_client: false,
             }),
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&provider).unwrap();
@@ -1120,6 +2658,159 @@ mod tests {
         assert!(index.http_providers.contains_key(&endpoint));
     }
     
+    #[test]
+    fn test_any_method_provider_matches_specific_consumer() {
+        let mut index = CodeIndex::new();
+        
+        let http_method = |qualified_name: &str, method: HttpMethod, is_feign_client: bool| MethodInfo {
+            line_range: (10, 20),
+            http_annotations: Some(HttpAnnotation {
+                method,
+                path: "/api/orders/sync".to_string(),
+                path_params: vec![],
+                is_feign_client,
+            }),
+            ..MethodInfo::new(qualified_name, "OrderController.java")
+        };
+        index.index_method(&http_method("com.example.OrderController::sync", HttpMethod::Any, false)).unwrap();
+        index.index_method(&http_method("com.example.OrderClient::syncOrders", HttpMethod::POST, true)).unwrap();
+        
+        let endpoint = |method: HttpMethod| HttpEndpoint {
+            method,
+            path_pattern: "/api/orders/sync".to_string(),
+        };
+        // POST 消费者匹配到未限定方法的提供者，反向同样匹配
+        assert_eq!(index.find_http_providers(&endpoint(HttpMethod::POST)), vec!["com.example.OrderController::sync"]);
+        assert_eq!(index.find_http_consumers(&endpoint(HttpMethod::Any)), vec!["com.example.OrderClient::syncOrders"]);
+        // 具体方法之间仍需一致
+        assert!(index.find_http_consumers(&endpoint(HttpMethod::GET)).is_empty());
+        assert!(endpoint(HttpMethod::Any).matches(&endpoint(HttpMethod::DELETE)));
+        assert!(!endpoint(HttpMethod::GET).matches(&endpoint(HttpMethod::POST)));
+    }
+    
+    #[test]
+    fn test_resolve_csharp_types() {
+        use crate::csharp_parser::CSharpParser;
+        
+        let parser = CSharpParser::new().unwrap();
+        let controller = r#"
+using Microsoft.AspNetCore.Mvc;
+using Shop.Data;
+
+namespace Shop.Orders
+{
+    public class OrdersController : ControllerBase
+    {
+        private readonly IOrderService _orderService;
+
+        public void Get(int id)
+        {
+            _orderService.Find(id);
+            var repository = new OrderRepository();
+            repository.Remove(id);
+        }
+    }
+}
+"#;
+        let service = "namespace Shop.Orders;\npublic interface IOrderService { void Find(int id); }\n";
+        let repository = "namespace Shop.Data;\npublic class OrderRepository { public void Remove(int id) { } }\n";
+        let mut parsed_files = vec![
+            parser.parse_file(controller, Path::new("OrdersController.cs")).unwrap(),
+            parser.parse_file(service, Path::new("IOrderService.cs")).unwrap(),
+            parser.parse_file(repository, Path::new("OrderRepository.cs")).unwrap(),
+        ];
+        
+        resolve_csharp_types(&mut parsed_files);
+        
+        let class = &parsed_files[0].classes[0];
+        // 工作空间中找不到的框架类型保持原样
+        assert_eq!(class.extends.as_deref(), Some("ControllerBase"));
+        let targets: Vec<&str> = class.methods[0].calls.iter().map(|call| call.target.as_str()).collect();
+        // 同一命名空间中的类型优先，其次是 using 引入的命名空间
+        assert_eq!(targets, vec![
+            "Shop.Orders.IOrderService::Find",
+            "Shop.Data.OrderRepository::<init>",
+            "Shop.Data.OrderRepository::Remove",
+        ]);
+    }
+    
+    #[test]
+    fn test_resolve_topic_constants() {
+        use crate::language_parser::StringConstant;
+        
+        let producer = MethodInfo {
+            name: "publish".to_string(),
+            full_qualified_name: "com.example.OrderProducer::publish".to_string(),
+            file_path: PathBuf::from("OrderProducer.java"),
+            line_range: (1, 5),
+            kafka_operations: vec![
+                crate::types::KafkaOperation {
+                    operation_type: KafkaOpType::Produce,
+                    topic: "Topics.ORDER_CREATED".to_string(),
+                    line: 3,
+                    topic_constant: Some("com.example.Topics.ORDER_CREATED".to_string()),
+                    value_type: None,
+                },
+                crate::types::KafkaOperation {
+                    operation_type: KafkaOpType::Produce,
+                    topic: "Topics.UNKNOWN".to_string(),
+                    line: 4,
+                    topic_constant: Some("com.example.Topics.UNKNOWN".to_string()),
+                    value_type: None,
+                },
+            ],
+            ..Default::default()
+        };
+        
+        let mut parsed_files = vec![
+            ParsedFile {
+                file_path: PathBuf::from("Topics.java"),
+                language: "java".to_string(),
+                classes: vec![],
+                functions: vec![],
+                imports: vec![],
+                string_constants: vec![StringConstant {
+                    name: "com.example.Topics.ORDER_CREATED".to_string(),
+                    value: "order-created".to_string(),
+                }],
+                functional_routes: vec![],
+                syntax_errors: Vec::new(),
+            },
+            ParsedFile {
+                file_path: PathBuf::from("OrderProducer.java"),
+                language: "java".to_string(),
+                classes: vec![crate::language_parser::ClassInfo {
+                    name: "com.example.OrderProducer".to_string(),
+                    methods: vec![producer],
+                    line_range: (1, 6),
+                    is_interface: false,
+                    implements: vec![],
+                    extends: None,
+                }],
+                functions: vec![],
+                imports: vec![],
+                string_constants: vec![],
+                functional_routes: vec![],
+                syntax_errors: Vec::new(),
+            },
+        ];
+        
+        resolve_topic_constants(&mut parsed_files);
+        
+        // 已知常量解析为常量值，未知常量保留引用文本
+        let operations = &parsed_files[1].classes[0].methods[0].kafka_operations;
+        assert_eq!(operations[0].topic, "order-created");
+        assert_eq!(operations[0].topic_constant, None);
+        assert_eq!(operations[1].topic, "Topics.UNKNOWN");
+        assert!(operations[1].topic_constant.is_some());
+        
+        let mut index = CodeIndex::new();
+        for parsed_file in parsed_files {
+            index.index_parsed_file(parsed_file).unwrap();
+        }
+        assert_eq!(index.find_kafka_producers("order-created"), vec!["com.example.OrderProducer::publish"]);
+    }
+    
     #[test]
     fn test_kafka_producer_and_consumer_queries() {
         let mut index = CodeIndex::new();
@@ -1129,17 +2820,16 @@ mod tests {
             full_qualified_name: "com.example.EventProducer::sendEvent".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (10, 20),
-            calls: vec![],
-            http_annotations: None,
             kafka_operations: vec![
                 crate::types::KafkaOperation {
                     operation_type: KafkaOpType::Produce,
                     topic: "order-events".to_string(),
                     line: 15,
+                    topic_constant: None,
+                    value_type: None,
                 },
             ],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         let consumer = MethodInfo {
@@ -1147,17 +2837,16 @@ mod tests {
             full_qualified_name: "com.example.EventConsumer::handleEvent".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (30, 40),
-            calls: vec![],
-            http_annotations: None,
             kafka_operations: vec![
                 crate::types::KafkaOperation {
                     operation_type: KafkaOpType::Consume,
                     topic: "order-events".to_string(),
                     line: 35,
+                    topic_constant: None,
+                    value_type: None,
                 },
             ],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&producer).unwrap();
@@ -1185,9 +2874,6 @@ mod tests {
             full_qualified_name: "com.example.UserRepository::findUser".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (10, 20),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
             db_operations: vec![
                 crate::types::DbOperation {
                     operation_type: DbOpType::Select,
@@ -1195,7 +2881,7 @@ mod tests {
                     line: 15,
                 },
             ],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         let writer = MethodInfo {
@@ -1203,9 +2889,6 @@ mod tests {
             full_qualified_name: "com.example.UserRepository::saveUser".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (30, 40),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
             db_operations: vec![
                 crate::types::DbOperation {
                     operation_type: DbOpType::Insert,
@@ -1213,7 +2896,7 @@ mod tests {
                     line: 35,
                 },
             ],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&reader).unwrap();
@@ -1232,6 +2915,136 @@ mod tests {
         assert!(no_readers.is_empty());
     }
     
+    #[test]
+    fn test_resolve_inherited_call() {
+        let mut index = CodeIndex::new();
+        
+        let method = |qualified_name: &str| MethodInfo {
+            line_range: (1, 5),
+            ..MethodInfo::new(qualified_name, "Repository.java")
+        };
+        
+        // BaseRepository <- OrderRepository <- CachedOrderRepository，
+        // 只有 CachedOrderRepository 重写了 delete
+        index.index_method(&method("com.example.BaseRepository::save")).unwrap();
+        index.index_method(&method("com.example.BaseRepository::delete")).unwrap();
+        index.index_method(&method("com.example.CachedOrderRepository::delete")).unwrap();
+        index.index_superclass("com.example.OrderRepository", "com.example.BaseRepository");
+        index.index_superclass("com.example.CachedOrderRepository", "com.example.OrderRepository");
+        
+        assert_eq!(index.find_superclass("com.example.OrderRepository"), Some("com.example.BaseRepository"));
+        assert_eq!(index.find_superclass("com.example.BaseRepository"), None);
+        
+        // 未重写的方法沿父类链解析到定义它的类
+        assert_eq!(
+            index.resolve_inherited_call("com.example.CachedOrderRepository::save"),
+            "com.example.BaseRepository::save"
+        );
+        assert_eq!(
+            index.resolve_inherited_call("com.example.CachedOrderRepository::delete"),
+            "com.example.CachedOrderRepository::delete"
+        );
+        assert_eq!(
+            index.resolve_inherited_call("com.example.OrderRepository::flush"),
+            "com.example.OrderRepository::flush"
+        );
+        
+        // 反向：继承 save 的子类都可能是调用目标，delete 在重写处截止
+        let mut targets = index.find_inherited_call_targets("com.example.BaseRepository::save");
+        targets.sort();
+        assert_eq!(targets, vec![
+            "com.example.CachedOrderRepository::save".to_string(),
+            "com.example.OrderRepository::save".to_string(),
+        ]);
+        assert_eq!(
+            index.find_inherited_call_targets("com.example.BaseRepository::delete"),
+            vec!["com.example.OrderRepository::delete".to_string()]
+        );
+    }
+    
+    #[test]
+    fn test_unresolved_targets() {
+        let mut index = CodeIndex::new();
+        let method = |name: &str, calls: &[&str]| MethodInfo {
+            line_range: (1, 10),
+            calls: calls.iter()
+                .map(|target| MethodCall { target: target.to_string(), line: 5, condition: None, low_confidence: false })
+                .collect(),
+            ..MethodInfo::new(name, "test.java")
+        };
+        
+        index.index_method(&method("com.example.A::run", &["com.example.B::work", "process", "process"])).unwrap();
+        index.index_method(&method("com.example.B::work", &[])).unwrap();
+        
+        assert_eq!(index.unresolved_targets(), vec!["process"]);
+        assert_eq!(index.find_callers("process").len(), 2);
+    }
+    
+    #[test]
+    fn test_reachable_from_entry_points() {
+        let mut index = CodeIndex::new();
+        let method = |name: &str, calls: &[&str]| MethodInfo {
+            line_range: (1, 10),
+            calls: calls.iter()
+                .map(|target| MethodCall { target: target.to_string(), line: 5, condition: None, low_confidence: false })
+                .collect(),
+            ..MethodInfo::new(name, "test.java")
+        };
+        
+        let mut job = method("com.example.Cleanup::run", &["com.example.Repo::purge"]);
+        job.scheduled = true;
+        index.index_method(&job).unwrap();
+        index.index_method(&method("com.example.Repo::purge", &["com.example.Repo::log", "java.util.List::clear"])).unwrap();
+        index.index_method(&method("com.example.Repo::log", &[])).unwrap();
+        index.index_method(&method("com.example.Legacy::unused", &["com.example.Repo::log"])).unwrap();
+        
+        assert_eq!(index.entry_points(), vec!["com.example.Cleanup::run"]);
+        
+        let mut reachable: Vec<&str> = index.reachable_from_entry_points().into_iter().collect();
+        reachable.sort_unstable();
+        assert_eq!(reachable, vec!["com.example.Cleanup::run", "com.example.Repo::log", "com.example.Repo::purge"]);
+    }
+    
+    #[test]
+    fn test_undeclared_db_tables() {
+        use crate::config_parser::ConfigParser;
+        
+        let mut index = CodeIndex::new();
+        
+        let repository = MethodInfo {
+            name: "save".to_string(),
+            full_qualified_name: "com.example.OrderRepository::save".to_string(),
+            file_path: std::path::PathBuf::from("OrderRepository.java"),
+            line_range: (10, 20),
+            db_operations: vec![
+                crate::types::DbOperation {
+                    operation_type: DbOpType::Insert,
+                    table: "ORDERS".to_string(),
+                    line: 12,
+                },
+                crate::types::DbOperation {
+                    operation_type: DbOpType::Select,
+                    table: "ordr_items".to_string(),
+                    line: 15,
+                },
+            ],
+            ..Default::default()
+        };
+        index.index_method(&repository).unwrap();
+        
+        // 没有声明任何表时不检查
+        assert!(index.undeclared_db_tables().is_empty());
+        
+        // 迁移脚本声明了 orders 和 order_items，表名比较不区分大小写
+        let migration = crate::sql_parser::SqlMigrationParser
+            .parse("CREATE TABLE orders (id BIGINT);\nCREATE TABLE order_items (id BIGINT);")
+            .unwrap();
+        index.associate_config_data(&migration);
+        
+        assert!(index.declared_db_tables().any(|table| table == "orders"));
+        assert_eq!(index.undeclared_db_tables(), vec!["ordr_items"]);
+    }
+    
     #[test]
     fn test_redis_reader_and_writer_queries() {
         let mut index = CodeIndex::new();
@@ -1241,10 +3054,6 @@ mod tests {
             full_qualified_name: "com.example.CacheService::getCache".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (10, 20),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
             redis_operations: vec![
                 crate::types::RedisOperation {
                     operation_type: RedisOpType::Get,
@@ -1252,6 +3061,7 @@ mod tests {
                     line: 15,
                 },
             ],
+            ..Default::default()
         };
         
         let writer = MethodInfo {
@@ -1259,10 +3069,6 @@ mod tests {
             full_qualified_name: "com.example.CacheService::setCache".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (30, 40),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
             redis_operations: vec![
                 crate::types::RedisOperation {
                     operation_type: RedisOpType::Set,
@@ -1270,6 +3076,7 @@ mod tests {
                     line: 35,
                 },
             ],
+            ..Default::default()
         };
         
         index.index_method(&reader).unwrap();
@@ -1288,6 +3095,52 @@ mod tests {
         assert!(no_readers.is_empty());
     }
     
+    #[test]
+    fn test_rpc_provider_and_consumer_queries() {
+        use crate::types::{RpcOperation, RpcOpType};
+        
+        let mut index = CodeIndex::new();
+        
+        let provider = MethodInfo {
+            name: "queryOrder".to_string(),
+            full_qualified_name: "com.example.OrderFacadeImpl::queryOrder".to_string(),
+            file_path: std::path::PathBuf::from("test.java"),
+            line_range: (10, 20),
+            rpc_operations: vec![RpcOperation {
+                operation_type: RpcOpType::Provide,
+                interface: "com.example.OrderFacade".to_string(),
+                method: "queryOrder".to_string(),
+                line: 10,
+            }],
+            ..Default::default()
+        };
+        
+        let consumer = MethodInfo {
+            name: "checkout".to_string(),
+            full_qualified_name: "com.example.ShopService::checkout".to_string(),
+            file_path: std::path::PathBuf::from("test.java"),
+            line_range: (30, 40),
+            rpc_operations: vec![RpcOperation {
+                operation_type: RpcOpType::Consume,
+                interface: "com.example.OrderFacade".to_string(),
+                method: "queryOrder".to_string(),
+                line: 35,
+            }],
+            ..Default::default()
+        };
+        
+        index.index_method(&provider).unwrap();
+        index.index_method(&consumer).unwrap();
+        
+        let providers = index.find_rpc_providers("com.example.OrderFacade::queryOrder");
+        assert_eq!(providers, vec!["com.example.OrderFacadeImpl::queryOrder"]);
+        
+        let consumers = index.find_rpc_consumers("com.example.OrderFacade::queryOrder");
+        assert_eq!(consumers, vec!["com.example.ShopService::checkout"]);
+        
+        assert!(index.find_rpc_providers("com.example.OrderFacade::unknown").is_empty());
+    }
+    
     #[test]
     fn test_multiple_callers_and_callees() {
         let mut index = CodeIndex::new();
@@ -1302,12 +3155,11 @@ mod tests {
                 MethodCall {
                     target: "com.example.Common::shared".to_string(),
                     line: 5,
+                    condition: None,
+                    low_confidence: false,
                 },
             ],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         let method_b = MethodInfo {
@@ -1319,12 +3171,11 @@ mod tests {
                 MethodCall {
                     target: "com.example.Common::shared".to_string(),
                     line: 5,
+                    condition: None,
+                    low_confidence: false,
                 },
             ],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&method_a).unwrap();
@@ -1347,17 +3198,16 @@ mod tests {
             full_qualified_name: "com.example.Producer::sendEvent".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (10, 20),
-            calls: vec![],
-            http_annotations: None,
             kafka_operations: vec![
                 crate::types::KafkaOperation {
                     operation_type: KafkaOpType::Produce,
                     topic: "user-events".to_string(),
                     line: 15,
+                    topic_constant: None,
+                    value_type: None,
                 },
             ],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         let consumer = MethodInfo {
@@ -1365,17 +3215,16 @@ mod tests {
             full_qualified_name: "com.example.Consumer::handleEvent".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (30, 40),
-            calls: vec![],
-            http_annotations: None,
             kafka_operations: vec![
                 crate::types::KafkaOperation {
                     operation_type: KafkaOpType::Consume,
                     topic: "user-events".to_string(),
                     line: 35,
+                    topic_constant: None,
+                    value_type: None,
                 },
             ],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&producer).unwrap();
@@ -1395,6 +3244,57 @@ mod tests {
         assert!(associated.contains(&"com.example.Consumer::handleEvent"));
     }
     
+    #[test]
+    fn test_find_feign_client_config_falls_back_to_default() {
+        let mut index = CodeIndex::new();
+        assert!(index.find_feign_client_config("", "user-service").is_none());
+        
+        let mut config_data = crate::config_parser::ConfigData::default();
+        config_data.feign_client_configs.insert("default".to_string(), FeignClientConfig {
+            connect_timeout: Some(2000),
+            read_timeout: Some(6000),
+            retryer: None,
+        });
+        config_data.feign_client_configs.insert("user-service".to_string(), FeignClientConfig {
+            connect_timeout: Some(5000),
+            ..FeignClientConfig::default()
+        });
+        index.associate_config_data(&config_data);
+        
+        // 客户端自身的配置优先，缺失项使用 default 补全
+        let config = index.find_feign_client_config("", "user-service").unwrap();
+        assert_eq!(config.connect_timeout, Some(5000));
+        assert_eq!(config.read_timeout, Some(6000));
+        
+        // 未单独配置的客户端使用 default
+        let config = index.find_feign_client_config("", "order-service").unwrap();
+        assert_eq!(config.connect_timeout, Some(2000));
+    }
+    
+    #[test]
+    fn test_feign_client_configs_are_scoped_to_modules() {
+        let mut index = CodeIndex::new();
+        let config_of = |connect_timeout: u64| {
+            let mut config_data = crate::config_parser::ConfigData::default();
+            config_data.feign_client_configs.insert("user-service".to_string(), FeignClientConfig {
+                connect_timeout: Some(connect_timeout),
+                ..FeignClientConfig::default()
+            });
+            config_data
+        };
+        index.associate_module_config_data("order-service", &config_of(1000));
+        index.associate_module_config_data("payment-service", &config_of(3000));
+        index.associate_config_data(&config_of(9000));
+        
+        // 两个服务对同一客户端的配置互不覆盖
+        assert_eq!(index.find_feign_client_config("order-service", "user-service").unwrap().connect_timeout, Some(1000));
+        assert_eq!(index.find_feign_client_config("payment-service", "user-service").unwrap().connect_timeout, Some(3000));
+        
+        // 模块中没有该客户端时使用工作空间根目录下的配置
+        assert_eq!(index.find_feign_client_config("audit-service", "user-service").unwrap().connect_timeout, Some(9000));
+        assert_eq!(index.feign_client_configs().count(), 3);
+    }
+    
     #[test]
     fn test_associate_db_table_config() {
         let mut index = CodeIndex::new();
@@ -1405,9 +3305,6 @@ mod tests {
             full_qualified_name: "com.example.UserDao::getUser".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (10, 20),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
             db_operations: vec![
                 crate::types::DbOperation {
                     operation_type: DbOpType::Select,
@@ -1415,7 +3312,7 @@ mod tests {
                     line: 15,
                 },
             ],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         let writer = MethodInfo {
@@ -1423,9 +3320,6 @@ mod tests {
             full_qualified_name: "com.example.UserDao::saveUser".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (30, 40),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
             db_operations: vec![
                 crate::types::DbOperation {
                     operation_type: DbOpType::Insert,
@@ -1433,7 +3327,7 @@ mod tests {
                     line: 35,
                 },
             ],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&reader).unwrap();
@@ -1463,10 +3357,6 @@ mod tests {
             full_qualified_name: "com.example.Cache::getCache".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (10, 20),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
             redis_operations: vec![
                 crate::types::RedisOperation {
                     operation_type: RedisOpType::Get,
@@ -1474,6 +3364,7 @@ mod tests {
                     line: 15,
                 },
             ],
+            ..Default::default()
         };
         
         let writer = MethodInfo {
@@ -1481,10 +3372,6 @@ mod tests {
             full_qualified_name: "com.example.Cache::setCache".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (30, 40),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
             redis_operations: vec![
                 crate::types::RedisOperation {
                     operation_type: RedisOpType::Set,
@@ -1492,6 +3379,7 @@ mod tests {
                     line: 35,
                 },
             ],
+            ..Default::default()
         };
         
         index.index_method(&reader).unwrap();
@@ -1528,6 +3416,57 @@ mod tests {
         assert!(!index.redis_key_matches("session:*", "user:123"));
     }
     
+    #[test]
+    fn test_redis_key_embedded_wildcard_matching() {
+        let index = CodeIndex::new();
+        
+        // 中间段的占位符和通配符按位置匹配
+        assert!(index.redis_key_matches("user:{id}:profile", "user:*:profile"));
+        assert!(index.redis_key_matches("user:*:profile", "user:123:profile"));
+        assert!(index.redis_key_matches("user:123:profile", "user:{userId}:profile"));
+        assert!(index.redis_key_matches("order:{shopId}:{orderId}", "order:1:2"));
+        
+        // 末尾通配仍然按前缀匹配
+        assert!(index.redis_key_matches("user:*", "user:{id}:profile"));
+        
+        // 段数或固定段不一致时不匹配
+        assert!(!index.redis_key_matches("user:{id}:profile", "user:123:settings"));
+        assert!(!index.redis_key_matches("user:{id}:profile", "user:123"));
+        assert!(!index.redis_key_matches("user:{id}", "order:123"));
+    }
+    
+    #[test]
+    fn test_normalize_redis_key() {
+        assert_eq!(normalize_redis_key("user:{id}"), "user:*");
+        assert_eq!(normalize_redis_key("user:*"), "user:*");
+        assert_eq!(normalize_redis_key("user:{id}:profile"), "user:*:profile");
+        assert_eq!(normalize_redis_key("session:abc"), "session:abc");
+        
+        // 规范化后的读写者归并到同一个键
+        let mut index = CodeIndex::new();
+        let mut reader = MethodInfo {
+            name: "getProfile".to_string(),
+            full_qualified_name: "com.example.UserCache::getProfile".to_string(),
+            file_path: std::path::PathBuf::from("test.java"),
+            line_range: (10, 20),
+            redis_operations: vec![crate::types::RedisOperation {
+                operation_type: RedisOpType::Get,
+                key_pattern: "user:{id}:profile".to_string(),
+                line: 15,
+            }],
+            ..Default::default()
+        };
+        index.index_method(&reader).unwrap();
+        
+        reader.full_qualified_name = "com.example.UserCache::putProfile".to_string();
+        reader.redis_operations[0].operation_type = RedisOpType::Set;
+        reader.redis_operations[0].key_pattern = "user:*:profile".to_string();
+        index.index_method(&reader).unwrap();
+        
+        assert_eq!(index.find_redis_readers("user:*:profile"), vec!["com.example.UserCache::getProfile"]);
+        assert_eq!(index.find_redis_writers("user:{uid}:profile"), vec!["com.example.UserCache::putProfile"]);
+    }
+    
     #[test]
     fn test_http_path_matching() {
         let index = CodeIndex::new();
@@ -1558,16 +3497,13 @@ mod tests {
             full_qualified_name: "com.example.UserController::getUser".to_string(),
             file_path: std::path::PathBuf::from("test.java"),
             line_range: (10, 20),
-            calls: vec![],
             http_annotations: Some(HttpAnnotation {
                 method: HttpMethod::GET,
                 path: "/api/users/{id}".to_string(),
                 path_params: vec!["id".to_string()],
                 is_feign_client: false,
             }),
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         // 添加 HTTP 消费者（调用该接口的方法）
@@ -1580,12 +3516,11 @@ mod tests {
                 MethodCall {
                     target: "RestTemplate.get(/api/users)".to_string(),
                     line: 35,
+                    condition: None,
+                    low_confidence: false,
                 },
             ],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         index.index_method(&provider).unwrap();
@@ -1615,4 +3550,99 @@ mod tests {
         assert_eq!(associated.len(), 1);
         assert!(associated.contains(&"com.example.UserClient::fetchUser"));
     }
+    
+    #[test]
+    fn test_service_aliases_rename_feign_consumers() {
+        use crate::types::HttpMethod;
+        
+        let consumer = MethodInfo {
+            name: "getUser".to_string(),
+            full_qualified_name: "com.example.UserClient::getUser".to_string(),
+            file_path: std::path::PathBuf::from("UserClient.java"),
+            line_range: (10, 12),
+            http_annotations: Some(HttpAnnotation {
+                method: HttpMethod::GET,
+                path: "user-service/api/users/{id}".to_string(),
+                path_params: vec!["id".to_string()],
+                is_feign_client: true,
+            }),
+            ..Default::default()
+        };
+        let mut index = CodeIndex::new();
+        index.index_method(&consumer).unwrap();
+        
+        let mut config_data = crate::config_parser::ConfigData::default();
+        config_data.feign_client_configs.insert("user-service".to_string(), FeignClientConfig {
+            connect_timeout: Some(5000),
+            ..FeignClientConfig::default()
+        });
+        index.associate_config_data(&config_data);
+        
+        let aliases: HashMap<String, String> =
+            [("user-service".to_string(), "hll-user-app".to_string())].into_iter().collect();
+        index.set_service_aliases(&aliases);
+        
+        let old = HttpEndpoint {
+            method: HttpMethod::GET,
+            path_pattern: "user-service/api/users/{id}".to_string(),
+        };
+        let new = HttpEndpoint {
+            method: HttpMethod::GET,
+            path_pattern: "hll-user-app/api/users/{id}".to_string(),
+        };
+        assert!(index.find_http_consumers(&old).is_empty());
+        assert_eq!(index.find_http_consumers(&new), vec!["com.example.UserClient::getUser"]);
+        assert_eq!(
+            index.find_method("com.example.UserClient::getUser").unwrap().http_annotations.as_ref().unwrap().path,
+            "hll-user-app/api/users/{id}"
+        );
+        
+        // 服务名的 Feign 配置同时登记到应用名下
+        assert_eq!(index.find_feign_client_config("", "hll-user-app").unwrap().connect_timeout, Some(5000));
+    }
+    
+    #[test]
+    fn test_min_http_segments_skips_short_endpoints() {
+        use crate::types::HttpMethod;
+        
+        let consumer = MethodInfo {
+            name: "fetchOrder".to_string(),
+            full_qualified_name: "com.example.OrderClient::fetchOrder".to_string(),
+            file_path: std::path::PathBuf::from("test.java"),
+            line_range: (30, 40),
+            calls: vec![
+                MethodCall {
+                    target: "RestTemplate.get(/api/orders/123)".to_string(),
+                    line: 35,
+                    condition: None,
+                    low_confidence: false,
+                },
+            ],
+            ..Default::default()
+        };
+        let root = HttpEndpoint {
+            method: HttpMethod::GET,
+            path_pattern: "/".to_string(),
+        };
+        let order = HttpEndpoint {
+            method: HttpMethod::GET,
+            path_pattern: "/api/orders/{id}".to_string(),
+        };
+        let mut config_data = crate::config_parser::ConfigData::default();
+        config_data.http_endpoints.push(root.clone());
+        config_data.http_endpoints.push(order.clone());
+        
+        // 不限制时 `/` 匹配任意 HTTP 客户端调用
+        let mut index = CodeIndex::new();
+        index.index_method(&consumer).unwrap();
+        index.associate_config_data(&config_data);
+        assert_eq!(index.find_http_consumers(&root), vec!["com.example.OrderClient::fetchOrder"]);
+        
+        let mut index = CodeIndex::new();
+        index.set_min_http_segments(2);
+        index.index_method(&consumer).unwrap();
+        index.associate_config_data(&config_data);
+        assert!(index.find_http_consumers(&root).is_empty());
+        assert_eq!(index.find_http_consumers(&order), vec!["com.example.OrderClient::fetchOrder"]);
+    }
 }
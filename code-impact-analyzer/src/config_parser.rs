@@ -1,9 +1,9 @@
 use crate::errors::ParseError;
-use crate::types::{HttpMethod, HttpEndpoint};
-use quick_xml::events::Event;
+use crate::types::{BatchJobDefinition, FeignClientConfig, HttpMethod, HttpEndpoint, ThriftService};
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
 use serde_yaml::Value as YamlValue;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// 配置数据结构
 #[derive(Debug, Clone, Default)]
@@ -12,6 +12,12 @@ pub struct ConfigData {
     pub kafka_topics: Vec<String>,
     pub db_tables: Vec<String>,
     pub redis_prefixes: Vec<String>,
+    /// Feign 客户端配置: 客户端名（`@FeignClient` 的服务名或 `default`）-> 超时/重试配置
+    pub feign_client_configs: HashMap<String, FeignClientConfig>,
+    /// Thrift IDL（`.thrift` 文件）中定义的服务
+    pub thrift_services: Vec<ThriftService>,
+    /// Spring Batch XML 中定义的作业
+    pub batch_jobs: Vec<BatchJobDefinition>,
 }
 
 /// 配置解析器 trait
@@ -20,6 +26,13 @@ pub trait ConfigParser: Send + Sync {
     
     /// 检查是否支持指定的配置格式
     fn supports_format(&self, format: &str) -> bool;
+    
+    /// 返回处理的文件扩展名列表，这些文件计入磁盘索引的校验和
+    /// 
+    /// 默认为空；注册了自定义配置解析器时编排器不使用磁盘索引，因此无需覆盖
+    fn file_extensions(&self) -> &[&str] {
+        &[]
+    }
 }
 
 /// XML 配置解析器
@@ -102,6 +115,8 @@ impl ConfigParser for XmlConfigParser {
             buf.clear();
         }
 
+        config_data.batch_jobs = extract_batch_jobs(content)?;
+        
         // 去重
         deduplicate_config_data(&mut config_data);
         Ok(config_data)
@@ -110,11 +125,138 @@ impl ConfigParser for XmlConfigParser {
     fn supports_format(&self, format: &str) -> bool {
         format == "xml"
     }
+    
+    fn file_extensions(&self) -> &[&str] {
+        &["xml"]
+    }
+}
+
+/// 从 Spring Batch XML 中提取作业定义
+/// 
+/// 识别 `<batch:job id="...">`（或默认命名空间下的 `<job>`）中各步骤的 `<tasklet ref="...">` 和
+/// `<chunk reader="..." processor="..." writer="...">`，分别对应 `execute`（tasklet 指定 `method`
+/// 属性时为该方法）/ `read` / `process` / `write` 方法。引用的 bean 按同一文件中
+/// `<bean id="..." class="...">` 的声明解析为类名；在其他文件或通过注解声明的 bean 无法解析，忽略该组件
+fn extract_batch_jobs(content: &str) -> Result<Vec<BatchJobDefinition>, ParseError> {
+    let mut reader = Reader::from_str(content);
+    let mut beans: HashMap<String, String> = HashMap::new();
+    let mut jobs: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    let mut current_job: Option<(String, Vec<(String, String)>)> = None;
+    
+    loop {
+        let event = reader.read_event().map_err(|e| ParseError::InvalidFormat {
+            message: format!("XML parse error: {}", e),
+        })?;
+        let (element, is_start) = match &event {
+            Event::Start(e) => (e, true),
+            Event::Empty(e) => (e, false),
+            Event::End(e) => {
+                if e.local_name().as_ref() == b"job" && let Some(job) = current_job.take() {
+                    jobs.push(job);
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+        
+        match element.local_name().as_ref() {
+            b"bean" => {
+                if let (Some(id), Some(class)) = (xml_attribute(element, "id"), xml_attribute(element, "class")) {
+                    beans.insert(id, class);
+                }
+            }
+            b"job" if is_start => {
+                current_job = xml_attribute(element, "id").map(|id| (id, Vec::new()));
+            }
+            b"tasklet" => {
+                if let Some((_, components)) = &mut current_job
+                    && let Some(bean) = xml_attribute(element, "ref")
+                {
+                    let method = xml_attribute(element, "method").unwrap_or_else(|| "execute".to_string());
+                    components.push((bean, method));
+                }
+            }
+            b"chunk" => {
+                if let Some((_, components)) = &mut current_job {
+                    for (attribute, method) in [("reader", "read"), ("processor", "process"), ("writer", "write")] {
+                        if let Some(bean) = xml_attribute(element, attribute) {
+                            components.push((bean, method.to_string()));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    Ok(jobs.into_iter()
+        .map(|(name, components)| {
+            let methods = components.into_iter()
+                .filter_map(|(bean, method)| match beans.get(&bean) {
+                    Some(class) => Some(format!("{}::{}", class, method)),
+                    None => {
+                        log::debug!("Bean {} referenced by batch job {} is not declared in the same file", bean, name);
+                        None
+                    }
+                })
+                .collect();
+            BatchJobDefinition { name, methods }
+        })
+        .collect())
+}
+
+/// 读取 XML 元素的属性值
+fn xml_attribute(element: &BytesStart, name: &str) -> Option<String> {
+    element.try_get_attribute(name).ok()??
+        .unescape_value().ok()
+        .map(|value| value.into_owned())
 }
 
 /// YAML 配置解析器
+/// 
+/// 递归扫描整个文档：键名包含 `topic`/`queue` 的值作为 Kafka Topic，包含 `table` 的值作为数据库表，
+/// 值为嵌套的列表或映射时取其中所有的字符串。键名不符合约定的 Topic 列表可以用
+/// [`yaml_topics_at_paths`] 按键路径提取
+#[derive(Debug, Clone, Copy, Default)]
 pub struct YamlConfigParser;
 
+impl YamlConfigParser {
+    /// 创建 YAML 配置解析器
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// 从 YAML 配置的指定键路径提取 Kafka Topic
+/// 
+/// 用于键名不符合 `topic`/`queue` 约定的 Topic 列表。键路径的各段可以是嵌套的映射，
+/// 也可以是 Spring 风格的带点号的键（如 `messaging.destinations:`）
+/// 
+/// # Arguments
+/// * `content` - YAML 文件内容
+/// * `topic_paths` - `.` 分隔的键路径，路径下的所有字符串值都作为 Topic
+/// 
+/// # Returns
+/// * 按路径顺序去重后的 Topic 列表，YAML 格式错误时返回错误
+pub fn yaml_topics_at_paths(content: &str, topic_paths: &[String]) -> Result<Vec<String>, ParseError> {
+    let yaml: YamlValue = serde_yaml::from_str(content)
+        .map_err(|e| ParseError::InvalidFormat {
+            message: format!("YAML parse error: {}", e),
+        })?;
+    
+    let mut topics = Vec::new();
+    for path in topic_paths {
+        let segments: Vec<&str> = path.split('.').collect();
+        if let Some(value) = yaml_at_path(&yaml, &segments) {
+            collect_yaml_strings(value, &mut topics);
+        }
+    }
+    let mut seen = HashSet::new();
+    topics.retain(|topic| seen.insert(topic.clone()));
+    Ok(topics)
+}
+
 impl ConfigParser for YamlConfigParser {
     fn parse(&self, content: &str) -> Result<ConfigData, ParseError> {
         let yaml: YamlValue = serde_yaml::from_str(content)
@@ -124,6 +266,7 @@ impl ConfigParser for YamlConfigParser {
 
         let mut config_data = ConfigData::default();
         extract_from_yaml(&yaml, &mut config_data);
+        extract_feign_client_configs(&yaml, &mut config_data);
         
         // 去重
         deduplicate_config_data(&mut config_data);
@@ -133,6 +276,10 @@ impl ConfigParser for YamlConfigParser {
     fn supports_format(&self, format: &str) -> bool {
         format == "yaml"
     }
+    
+    fn file_extensions(&self) -> &[&str] {
+        &["yaml", "yml"]
+    }
 }
 
 /// 从 YAML 值中递归提取配置信息
@@ -150,24 +297,16 @@ fn extract_from_yaml(value: &YamlValue, config_data: &mut ConfigData) {
                             extract_http_endpoint(text, config_data);
                         }
                     }
-                    // 检测 Kafka 相关键
-                    else if key_lower.contains("topic") {
-                        // 处理字符串值
-                        if let Some(text) = val.as_str() {
-                            config_data.kafka_topics.push(text.to_string());
-                        }
-                        // 处理数组值
-                        else if let Some(seq) = val.as_sequence() {
-                            for item in seq {
-                                if let Some(text) = item.as_str() {
-                                    config_data.kafka_topics.push(text.to_string());
-                                }
-                            }
-                        }
+                    // 检测 Kafka 相关键，值可以是嵌套的列表或映射
+                    else if key_lower.contains("topic") || key_lower.contains("queue") {
+                        collect_yaml_strings(val, &mut config_data.kafka_topics);
+                    }
+                    // 检测数据库表键，值可以是嵌套的列表或映射
+                    else if key_lower.contains("table") {
+                        collect_yaml_strings(val, &mut config_data.db_tables);
                     }
-                    // 检测数据库相关键
-                    else if key_lower.contains("table") || key_lower.contains("entity") 
-                        || key_lower.contains("database") {
+                    // 检测其他数据库相关键
+                    else if key_lower.contains("entity") || key_lower.contains("database") {
                         // 处理字符串值
                         if let Some(text) = val.as_str() {
                             config_data.db_tables.push(text.to_string());
@@ -211,6 +350,87 @@ fn extract_from_yaml(value: &YamlValue, config_data: &mut ConfigData) {
     }
 }
 
+/// 收集 YAML 值（包括嵌套的列表和映射的值）中的所有字符串
+/// 
+/// 跳过序列化器等类名配置：键名包含 `serializer`/`class` 的项，以及形如 Java 全限定类名的值
+fn collect_yaml_strings(value: &YamlValue, out: &mut Vec<String>) {
+    match value {
+        YamlValue::String(text) if !is_java_class_name(text) => out.push(text.clone()),
+        YamlValue::Sequence(seq) => {
+            for item in seq {
+                collect_yaml_strings(item, out);
+            }
+        }
+        YamlValue::Mapping(map) => {
+            for (key, item) in map {
+                let key_lower = key.as_str().unwrap_or_default().to_lowercase();
+                if key_lower.contains("serializer") || key_lower.contains("class") {
+                    continue;
+                }
+                collect_yaml_strings(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 判断文本是否形如 Java 全限定类名（如 `org.apache.kafka.common.serialization.StringSerializer`）
+fn is_java_class_name(text: &str) -> bool {
+    let segments: Vec<&str> = text.split('.').collect();
+    segments.len() > 1
+        && segments.iter().all(|segment| {
+            segment.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+                && segment.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        })
+        && segments.last().and_then(|last| last.chars().next()).is_some_and(char::is_uppercase)
+        && segments[..segments.len() - 1].iter().all(|segment| !segment.chars().any(char::is_uppercase))
+}
+
+/// 按键路径查找 YAML 值
+/// 
+/// 每一层依次尝试由剩余各段的前缀拼成的键，使嵌套映射和带点号的键都能匹配
+fn yaml_at_path<'a>(value: &'a YamlValue, segments: &[&str]) -> Option<&'a YamlValue> {
+    if segments.is_empty() {
+        return Some(value);
+    }
+    let map = value.as_mapping()?;
+    (1..=segments.len()).find_map(|n| {
+        let key = segments[..n].join(".");
+        map.get(key.as_str()).and_then(|child| yaml_at_path(child, &segments[n..]))
+    })
+}
+
+/// 从 YAML 中提取 `feign.client.config.<name>` 下的超时与重试配置
+fn extract_feign_client_configs(yaml: &YamlValue, config_data: &mut ConfigData) {
+    let Some(clients) = yaml
+        .get("feign")
+        .and_then(|v| v.get("client"))
+        .and_then(|v| v.get("config"))
+        .and_then(|v| v.as_mapping())
+    else {
+        return;
+    };
+    
+    for (name, settings) in clients {
+        let Some(name) = name.as_str() else {
+            continue;
+        };
+        let config = FeignClientConfig {
+            connect_timeout: settings.get("connectTimeout").and_then(yaml_as_u64),
+            read_timeout: settings.get("readTimeout").and_then(yaml_as_u64),
+            retryer: settings.get("retryer").and_then(|v| v.as_str()).map(str::to_string),
+        };
+        if config != FeignClientConfig::default() {
+            config_data.feign_client_configs.insert(name.to_string(), config);
+        }
+    }
+}
+
+/// 读取数值配置，兼容写成字符串的数字
+fn yaml_as_u64(value: &YamlValue) -> Option<u64> {
+    value.as_u64().or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+}
+
 /// 从文本中提取 HTTP 端点信息
 fn extract_http_endpoint(text: &str, config_data: &mut ConfigData) {
     // 尝试从 URL 中提取路径
@@ -299,7 +519,7 @@ mod tests {
               key: "user:*"
         "#;
 
-        let parser = YamlConfigParser;
+        let parser = YamlConfigParser::new();
         let result = parser.parse(yaml).unwrap();
 
         assert_eq!(result.http_endpoints.len(), 1);
@@ -309,6 +529,39 @@ mod tests {
         assert_eq!(result.redis_prefixes, vec!["user:*"]);
     }
 
+    #[test]
+    fn test_yaml_parser_feign_client_config() {
+        let yaml = r#"
+            feign:
+              client:
+                config:
+                  default:
+                    connectTimeout: 2000
+                  user-service:
+                    connectTimeout: 5000
+                    readTimeout: "10000"
+                    retryer: feign.Retryer.Default
+                  order-service:
+                    loggerLevel: full
+        "#;
+
+        let parser = YamlConfigParser::new();
+        let result = parser.parse(yaml).unwrap();
+
+        assert_eq!(result.feign_client_configs.len(), 2);
+        assert_eq!(
+            result.feign_client_configs["user-service"],
+            FeignClientConfig {
+                connect_timeout: Some(5000),
+                read_timeout: Some(10000),
+                retryer: Some("feign.Retryer.Default".to_string()),
+            }
+        );
+        assert_eq!(result.feign_client_configs["default"].connect_timeout, Some(2000));
+        // 没有超时/重试配置的客户端不记录
+        assert!(!result.feign_client_configs.contains_key("order-service"));
+    }
+
     #[test]
     fn test_xml_parser_nested() {
         let xml = r#"
@@ -328,6 +581,52 @@ mod tests {
         assert_eq!(result.http_endpoints[0].path_pattern, "/api/users/{id}");
     }
 
+    #[test]
+    fn test_xml_parser_spring_batch_jobs() {
+        let xml = r#"
+            <beans xmlns:batch="http://www.springframework.org/schema/batch">
+                <batch:job id="importOrders">
+                    <batch:step id="load" next="cleanup">
+                        <batch:tasklet>
+                            <batch:chunk reader="orderReader" processor="orderProcessor" writer="orderWriter" commit-interval="10"/>
+                        </batch:tasklet>
+                    </batch:step>
+                    <batch:step id="cleanup">
+                        <batch:tasklet ref="cleanupTasklet"/>
+                    </batch:step>
+                </batch:job>
+                <batch:job id="archive">
+                    <batch:step id="archiveStep">
+                        <batch:tasklet ref="archiver" method="archiveAll"/>
+                    </batch:step>
+                </batch:job>
+                <bean id="orderReader" class="com.example.batch.OrderReader"/>
+                <bean id="orderProcessor" class="com.example.batch.OrderProcessor"/>
+                <bean id="orderWriter" class="com.example.batch.OrderWriter"/>
+                <bean id="cleanupTasklet" class="com.example.batch.CleanupTasklet"/>
+                <bean id="archiver" class="com.example.batch.Archiver"/>
+            </beans>
+        "#;
+
+        let result = XmlConfigParser.parse(xml).unwrap();
+
+        assert_eq!(result.batch_jobs, vec![
+            BatchJobDefinition {
+                name: "importOrders".to_string(),
+                methods: vec![
+                    "com.example.batch.OrderReader::read".to_string(),
+                    "com.example.batch.OrderProcessor::process".to_string(),
+                    "com.example.batch.OrderWriter::write".to_string(),
+                    "com.example.batch.CleanupTasklet::execute".to_string(),
+                ],
+            },
+            BatchJobDefinition {
+                name: "archive".to_string(),
+                methods: vec!["com.example.batch.Archiver::archiveAll".to_string()],
+            },
+        ]);
+    }
+
     #[test]
     fn test_yaml_parser_nested() {
         let yaml = r#"
@@ -341,7 +640,7 @@ mod tests {
                     - user-updated
         "#;
 
-        let parser = YamlConfigParser;
+        let parser = YamlConfigParser::new();
         let result = parser.parse(yaml).unwrap();
 
         assert_eq!(result.http_endpoints.len(), 1);
@@ -349,6 +648,68 @@ mod tests {
         assert!(result.kafka_topics.contains(&"user-updated".to_string()));
     }
 
+    #[test]
+    fn test_yaml_parser_nested_topic_and_queue_lists() {
+        let yaml = r#"
+            app:
+              messaging:
+                topics:
+                  orders:
+                    name: order-events
+                    partitions: 3
+                  payments: [payment-events]
+                retry-queue: order-retry
+              archive-tables:
+                - order_archive
+        "#;
+
+        let result = YamlConfigParser::new().parse(yaml).unwrap();
+
+        assert_eq!(result.kafka_topics, vec!["order-events", "payment-events", "order-retry"]);
+        assert_eq!(result.db_tables, vec!["order_archive"]);
+    }
+
+    #[test]
+    fn test_yaml_parser_custom_topic_path() {
+        let yaml = r#"
+            app:
+              messaging.destinations:
+                - order-events
+                - payment-events
+              notifications:
+                channels: [email-outbox]
+        "#;
+
+        // 键名不含 topic，默认不提取
+        let result = YamlConfigParser.parse(yaml).unwrap();
+        assert!(result.kafka_topics.is_empty());
+
+        // 指定键路径后提取，路径段可以对应带点号的键
+        let topics = yaml_topics_at_paths(yaml, &[
+            "app.messaging.destinations".to_string(),
+            "app.notifications.channels".to_string(),
+            "app.missing".to_string(),
+        ]).unwrap();
+        assert_eq!(topics, vec!["order-events", "payment-events", "email-outbox"]);
+    }
+
+    #[test]
+    fn test_yaml_topic_values_skip_serializer_classes() {
+        let yaml = r#"
+            app:
+              topics:
+                orders:
+                  name: order-events
+                  value-serializer: org.springframework.kafka.support.serializer.JsonSerializer
+                payments:
+                  - payment-events
+                  - com.example.kafka.PaymentSerializer
+        "#;
+
+        let result = YamlConfigParser::new().parse(yaml).unwrap();
+        assert_eq!(result.kafka_topics, vec!["order-events", "payment-events"]);
+    }
+
     #[test]
     fn test_xml_parser_invalid_format() {
         // Test with mismatched tags which will cause an error
@@ -368,7 +729,7 @@ mod tests {
     fn test_yaml_parser_invalid_format() {
         let yaml = "invalid: yaml: content: [unclosed";
 
-        let parser = YamlConfigParser;
+        let parser = YamlConfigParser::new();
         let result = parser.parse(yaml);
 
         assert!(result.is_err());
@@ -0,0 +1,802 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use tree_sitter::Parser;
+use regex::Regex;
+use crate::errors::ParseError;
+use crate::language_parser::{collect_syntax_errors, walk_tree, CallFilter, ClassInfo, LanguageParser, MethodCall, MethodInfo, ParsedFile, Visibility};
+use crate::types::*;
+
+/// C# 语言解析器
+///
+/// 使用 tree-sitter-c-sharp 解析 C# 源代码，识别 ASP.NET Core 控制器的路由特性
+pub struct CSharpParser {
+    parser: Mutex<Parser>,
+    /// 提取调用时使用的过滤器
+    call_filter: CallFilter,
+}
+
+/// 类型声明的上下文：所在命名空间和外层类型
+///
+/// 基类型、字段类型和调用接收者的类型按源码中的写法记录，由 `resolve_file_types` 和
+/// `code_index::resolve_csharp_types` 再按 `using` 指令和命名空间补全为完整名称
+struct TypeScope<'a> {
+    /// 命名空间，如 `Shop.Orders`
+    namespace: Option<&'a str>,
+    /// 外层类型的完整名称（嵌套类型）
+    outer: Option<&'a str>,
+}
+
+impl CSharpParser {
+    /// 创建新的 CSharpParser 实例
+    pub fn new() -> Result<Self, ParseError> {
+        let mut parser = Parser::new();
+        let language = tree_sitter_c_sharp::LANGUAGE;
+        parser
+            .set_language(&language.into())
+            .map_err(|e| ParseError::InvalidFormat {
+                message: format!("Failed to set C# language: {}", e),
+            })?;
+
+        Ok(CSharpParser {
+            parser: Mutex::new(parser),
+            call_filter: CallFilter::default(),
+        })
+    }
+
+    /// 提取文件中的类型声明（类、接口、record、struct）
+    ///
+    /// 块命名空间（`namespace A { ... }`）可以嵌套；文件范围命名空间（`namespace A;`）作用于整个文件
+    fn extract_classes(&self, source: &str, file_path: &Path, tree: &tree_sitter::Tree) -> Vec<ClassInfo> {
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let file_namespace = root.children(&mut cursor)
+            .find(|child| child.kind() == "file_scoped_namespace_declaration")
+            .and_then(|node| node.child_by_field_name("name"))
+            .and_then(|name| source.get(name.byte_range()));
+
+        let mut classes = Vec::new();
+        self.walk_declarations(source, file_path, root, file_namespace, &mut classes);
+        classes
+    }
+
+    /// 遍历声明列表，收集类型声明
+    fn walk_declarations(
+        &self,
+        source: &str,
+        file_path: &Path,
+        node: tree_sitter::Node,
+        namespace: Option<&str>,
+        classes: &mut Vec<ClassInfo>,
+    ) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "namespace_declaration" => {
+                    let Some(name) = child.child_by_field_name("name").and_then(|n| source.get(n.byte_range())) else {
+                        continue;
+                    };
+                    let nested = match namespace {
+                        Some(parent) => format!("{}.{}", parent, name),
+                        None => name.to_string(),
+                    };
+                    if let Some(body) = child.child_by_field_name("body") {
+                        self.walk_declarations(source, file_path, body, Some(&nested), classes);
+                    }
+                }
+                "class_declaration" | "interface_declaration" | "record_declaration" | "struct_declaration" => {
+                    let scope = TypeScope { namespace, outer: None };
+                    self.extract_type(source, file_path, child, &scope, classes);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// 提取一个类型声明及其嵌套类型
+    fn extract_type(
+        &self,
+        source: &str,
+        file_path: &Path,
+        type_node: tree_sitter::Node,
+        scope: &TypeScope,
+        classes: &mut Vec<ClassInfo>,
+    ) {
+        let Some(name) = type_node.child_by_field_name("name").and_then(|n| source.get(n.byte_range())) else {
+            return;
+        };
+        let full_name = match (scope.outer, scope.namespace) {
+            (Some(outer), _) => format!("{}.{}", outer, name),
+            (None, Some(namespace)) => format!("{}.{}", namespace, name),
+            (None, None) => name.to_string(),
+        };
+        let is_interface = type_node.kind() == "interface_declaration";
+
+        // 基类型列表不区分基类和接口，按 `I` + 大写字母的命名约定识别接口
+        let mut implements = Vec::new();
+        let mut extends = None;
+        let mut cursor = type_node.walk();
+        if let Some(base_list) = type_node.children(&mut cursor).find(|child| child.kind() == "base_list") {
+            let mut base_cursor = base_list.walk();
+            for base in base_list.named_children(&mut base_cursor) {
+                let Some(base_name) = type_name(source, base) else {
+                    continue;
+                };
+                if is_interface || is_interface_name(&base_name) || extends.is_some() {
+                    implements.push(base_name);
+                } else {
+                    extends = Some(base_name);
+                }
+            }
+        }
+
+        // 控制器级别的路由模板
+        let controller_route = self.attributes(source, type_node).into_iter()
+            .find(|(name, _)| matches!(name.as_str(), "Route" | "RoutePrefix"))
+            .and_then(|(_, template)| template);
+
+        let mut methods = Vec::new();
+        if let Some(body) = type_node.child_by_field_name("body") {
+            let field_types = self.member_types(source, body);
+            let nested_scope = TypeScope { namespace: scope.namespace, outer: Some(&full_name) };
+            let mut body_cursor = body.walk();
+            for member in body.children(&mut body_cursor) {
+                match member.kind() {
+                    "method_declaration" | "constructor_declaration" => {
+                        let context = MethodContext {
+                            class_name: &full_name,
+                            simple_class_name: name,
+                            superclass: extends.as_deref(),
+                            controller_route: controller_route.as_deref(),
+                            field_types: &field_types,
+                        };
+                        if let Some(method) = self.extract_method_info(source, file_path, member, &context) {
+                            methods.push(method);
+                        }
+                    }
+                    "class_declaration" | "interface_declaration" | "record_declaration" | "struct_declaration" => {
+                        self.extract_type(source, file_path, member, &nested_scope, classes);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        classes.push(ClassInfo {
+            name: full_name.clone(),
+            methods,
+            line_range: (type_node.start_position().row + 1, type_node.end_position().row + 1),
+            is_interface,
+            implements,
+            extends,
+        });
+    }
+
+    /// 提取类型中字段和属性的类型：成员名 -> 完整类型名
+    fn member_types(&self, source: &str, body: tree_sitter::Node) -> HashMap<String, String> {
+        let mut types = HashMap::new();
+        let mut cursor = body.walk();
+        for member in body.children(&mut cursor) {
+            match member.kind() {
+                "field_declaration" => {
+                    let mut member_cursor = member.walk();
+                    if let Some(declaration) = member.children(&mut member_cursor).find(|c| c.kind() == "variable_declaration") {
+                        self.record_variable_declaration(source, declaration, &mut types);
+                    }
+                }
+                "property_declaration" => {
+                    let type_name = member.child_by_field_name("type").and_then(|t| type_name(source, t));
+                    let name = member.child_by_field_name("name").and_then(|n| source.get(n.byte_range()));
+                    if let (Some(type_name), Some(name)) = (type_name, name) {
+                        types.insert(name.to_string(), type_name);
+                    }
+                }
+                _ => {}
+            }
+        }
+        types
+    }
+
+    /// 记录变量声明中各变量的类型，`var` 声明只在初始化为 `new T(...)` 时推断为 `T`
+    fn record_variable_declaration(
+        &self,
+        source: &str,
+        declaration: tree_sitter::Node,
+        types: &mut HashMap<String, String>,
+    ) {
+        let declared = declaration.child_by_field_name("type")
+            .filter(|t| t.kind() != "implicit_type")
+            .and_then(|t| type_name(source, t));
+
+        let mut cursor = declaration.walk();
+        for declarator in declaration.children(&mut cursor).filter(|c| c.kind() == "variable_declarator") {
+            let Some(name) = declarator.child_by_field_name("name").and_then(|n| source.get(n.byte_range())) else {
+                continue;
+            };
+            let inferred = declared.clone().or_else(|| {
+                let mut declarator_cursor = declarator.walk();
+                let creation = declarator.named_children(&mut declarator_cursor)
+                    .find(|c| c.kind() == "object_creation_expression");
+                creation.and_then(|c| c.child_by_field_name("type")).and_then(|t| type_name(source, t))
+            });
+            if let Some(type_name) = inferred {
+                types.insert(name.to_string(), type_name);
+            }
+        }
+    }
+
+    /// 从方法或构造器声明节点提取方法信息
+    ///
+    /// 构造器的方法名记为 `<init>`，与 `new T(...)` 产生的调用目标对应
+    fn extract_method_info(
+        &self,
+        source: &str,
+        file_path: &Path,
+        method_node: tree_sitter::Node,
+        context: &MethodContext,
+    ) -> Option<MethodInfo> {
+        let name = if method_node.kind() == "constructor_declaration" {
+            "<init>".to_string()
+        } else {
+            source.get(method_node.child_by_field_name("name")?.byte_range())?.to_string()
+        };
+        let full_qualified_name = format!("{}::{}", context.class_name, name);
+        let line_start = method_node.start_position().row + 1;
+        let line_end = method_node.end_position().row + 1;
+
+        // 参数和局部变量遮蔽同名的字段和属性
+        let mut variable_types = context.field_types.clone();
+        if let Some(parameters) = method_node.child_by_field_name("parameters") {
+            let mut cursor = parameters.walk();
+            for parameter in parameters.children(&mut cursor).filter(|p| p.kind() == "parameter") {
+                let type_name = parameter.child_by_field_name("type").and_then(|t| type_name(source, t));
+                let param_name = parameter.child_by_field_name("name").and_then(|n| source.get(n.byte_range()));
+                if let (Some(type_name), Some(param_name)) = (type_name, param_name) {
+                    variable_types.insert(param_name.to_string(), type_name);
+                }
+            }
+        }
+        walk_tree(method_node, |node| {
+            if node.kind() == "local_declaration_statement" {
+                let mut cursor = node.walk();
+                if let Some(declaration) = node.children(&mut cursor).find(|c| c.kind() == "variable_declaration") {
+                    self.record_variable_declaration(source, declaration, &mut variable_types);
+                }
+            }
+            true
+        });
+
+        let calls = self.extract_method_calls(source, method_node, context, &variable_types);
+        let http_annotations = self.extract_http_annotation(source, method_node, &name, context);
+
+        Some(MethodInfo {
+            name,
+            full_qualified_name,
+            file_path: file_path.to_path_buf(),
+            line_range: (line_start, line_end),
+            calls,
+            http_annotations,
+            kafka_operations: Vec::new(),
+            db_operations: Vec::new(),
+            redis_operations: Vec::new(),
+            rpc_operations: Vec::new(),
+            thrift_operations: Vec::new(),
+            is_bean: false,
+            conditional: None,
+            graphql_resolver: None,
+            batch_job: None,
+            api_operation: None,
+            scheduled: false,
+            is_delegation: false,
+            resilience: None,
+            column_range: Some((method_node.start_position().column, method_node.end_position().column)),
+            visibility: method_visibility(source, method_node),
+            extra_line_ranges: Vec::new(),
+            extra_http_annotations: Vec::new(),
+        })
+    }
+
+    /// 提取方法体中的调用
+    ///
+    /// - `Foo()` / `this.Foo()` 解析到当前类，`base.Foo()` 解析到基类
+    /// - `_service.Foo()` 按字段、属性、参数或局部变量的类型解析
+    /// - `Helper.Foo()` 接收者不是已知变量且以大写字母开头时视为静态调用
+    /// - `new T(...)` 解析为 `T::<init>`
+    /// - 其他调用保留调用表达式原文
+    fn extract_method_calls(
+        &self,
+        source: &str,
+        method_node: tree_sitter::Node,
+        context: &MethodContext,
+        variable_types: &HashMap<String, String>,
+    ) -> Vec<MethodCall> {
+        let mut calls = Vec::new();
+
+        walk_tree(method_node, |node| {
+            if node.kind() == "object_creation_expression" {
+                if let Some(type_name) = node.child_by_field_name("type").and_then(|t| type_name(source, t)) {
+                    calls.push(MethodCall {
+                        target: format!("{}::<init>", type_name),
+                        line: node.start_position().row + 1,
+                        condition: None,
+                        low_confidence: false,
+                    });
+                }
+                return true;
+            }
+            if node.kind() != "invocation_expression" {
+                return true;
+            }
+            let Some(function) = node.child_by_field_name("function") else {
+                return true;
+            };
+
+            let target = match function.kind() {
+                "identifier" | "generic_name" => {
+                    type_name(source, function).map(|name| format!("{}::{}", context.class_name, name))
+                }
+                "member_access_expression" => {
+                    let receiver = function.child_by_field_name("expression");
+                    let member = function.child_by_field_name("name").and_then(|n| type_name(source, n));
+                    match (receiver, member) {
+                        (Some(receiver), Some(member)) => {
+                            let receiver_text = source.get(receiver.byte_range()).unwrap_or_default();
+                            let receiver_type = match receiver.kind() {
+                                "this_expression" | "this" => Some(context.class_name.to_string()),
+                                "base_expression" | "base" => context.superclass.map(str::to_string),
+                                "identifier" => variable_types.get(receiver_text).cloned().or_else(|| {
+                                    receiver_text.starts_with(char::is_uppercase)
+                                        .then(|| receiver_text.to_string())
+                                }),
+                                _ => None,
+                            };
+                            Some(match receiver_type {
+                                Some(receiver_type) => format!("{}::{}", receiver_type, member),
+                                None => format!("{}.{}", receiver_text, member),
+                            })
+                        }
+                        _ => None,
+                    }
+                }
+                _ => source.get(function.byte_range()).map(str::to_string),
+            };
+
+            if let Some(target) = target {
+                calls.push(MethodCall {
+                    target,
+                    line: node.start_position().row + 1,
+                    condition: None,
+                    low_confidence: false,
+                });
+            }
+            true
+        });
+
+        calls.retain(|call| !self.call_filter.is_ignored(&call.target));
+        calls
+    }
+
+    /// 提取方法上的 ASP.NET 路由特性
+    ///
+    /// `[HttpGet("{id}")]` 等 HTTP 方法特性的模板（或方法上 `[Route]` 的模板）与控制器的 `[Route]` 模板组合，
+    /// 以 `/` 或 `~/` 开头的方法模板覆盖控制器模板。`[controller]`、`[action]` 替换为小写的控制器名（去掉
+    /// `Controller` 后缀）和方法名，路由约束（`{id:int}`、`{id?}`、`{*path}`）统一为 `{id}` 形式
+    fn extract_http_annotation(
+        &self,
+        source: &str,
+        method_node: tree_sitter::Node,
+        method_name: &str,
+        context: &MethodContext,
+    ) -> Option<HttpAnnotation> {
+        let attributes = self.attributes(source, method_node);
+        let (method, verb_template) = attributes.iter().find_map(|(name, template)| {
+            let verb = name.strip_prefix("Http")?;
+            let method = match verb {
+                "Get" => HttpMethod::GET,
+                "Post" => HttpMethod::POST,
+                "Put" => HttpMethod::PUT,
+                "Delete" => HttpMethod::DELETE,
+                "Patch" => HttpMethod::PATCH,
+                _ => return None,
+            };
+            Some((method, template.clone()))
+        })?;
+        let method_template = verb_template.or_else(|| {
+            attributes.iter()
+                .find(|(name, _)| name == "Route")
+                .and_then(|(_, template)| template.clone())
+        });
+
+        let template = match (context.controller_route, method_template.as_deref()) {
+            (_, Some(template)) if template.starts_with('/') || template.starts_with("~/") => template.to_string(),
+            (Some(controller), Some(template)) if !template.is_empty() => {
+                format!("{}/{}", controller.trim_end_matches('/'), template)
+            }
+            (Some(controller), _) => controller.to_string(),
+            (None, template) => template.unwrap_or_default().to_string(),
+        };
+
+        let controller = context.simple_class_name.strip_suffix("Controller").unwrap_or(context.simple_class_name);
+        let path = template.trim_start_matches('~').trim_start_matches('/')
+            .replace("[controller]", &controller.to_lowercase())
+            .replace("[action]", &method_name.to_lowercase());
+        let path = route_parameter_pattern().replace_all(&path, "{$name}").into_owned();
+        let path_params = route_parameter_pattern().captures_iter(&path)
+            .map(|cap| cap["name"].to_string())
+            .collect();
+
+        Some(HttpAnnotation {
+            method,
+            path,
+            path_params,
+            is_feign_client: false,
+        })
+    }
+
+    /// 提取声明上的特性，返回 (特性名, 第一个字符串参数) 列表
+    ///
+    /// 特性名去掉命名空间前缀和 `Attribute` 后缀，如 `[Microsoft.AspNetCore.Mvc.HttpGetAttribute]` 记为 `HttpGet`
+    fn attributes(&self, source: &str, node: tree_sitter::Node) -> Vec<(String, Option<String>)> {
+        let mut attributes = Vec::new();
+        let mut cursor = node.walk();
+        for list in node.children(&mut cursor).filter(|c| c.kind() == "attribute_list") {
+            let mut list_cursor = list.walk();
+            for attribute in list.children(&mut list_cursor).filter(|c| c.kind() == "attribute") {
+                let Some(name) = attribute.child_by_field_name("name").and_then(|n| source.get(n.byte_range())) else {
+                    continue;
+                };
+                let name = name.rsplit('.').next().unwrap_or(name);
+                let name = name.strip_suffix("Attribute").unwrap_or(name);
+
+                let mut template = None;
+                walk_tree(attribute, |child| {
+                    if template.is_none() && child.kind() == "string_literal" {
+                        template = source.get(child.byte_range()).map(|text| text.trim_matches('"').to_string());
+                    }
+                    template.is_none()
+                });
+                attributes.push((name.to_string(), template));
+            }
+        }
+        attributes
+    }
+
+    /// 提取 `using` 指令
+    ///
+    /// `using Ns;` 记为 `items` 为空的导入；别名 `using Alias = Ns.Type;` 的 `items` 为别名
+    fn extract_imports(&self, source: &str, tree: &tree_sitter::Tree) -> Vec<Import> {
+        let mut imports = Vec::new();
+        walk_tree(tree.root_node(), |node| {
+            if node.kind() == "using_directive" {
+                let alias = node.child_by_field_name("name");
+                let mut cursor = node.walk();
+                if let Some(text) = node.named_children(&mut cursor)
+                    .filter(|c| Some(*c) != alias && matches!(c.kind(), "identifier" | "qualified_name" | "generic_name"))
+                    .last()
+                    .and_then(|c| source.get(c.byte_range()))
+                {
+                    imports.push(Import {
+                        module: text.to_string(),
+                        items: alias.and_then(|a| source.get(a.byte_range())).map(str::to_string).into_iter().collect(),
+                    });
+                }
+                return false;
+            }
+            !matches!(node.kind(), "class_declaration" | "interface_declaration" | "record_declaration" | "struct_declaration")
+        });
+        imports
+    }
+}
+
+/// 解析方法时使用的所在类型信息
+struct MethodContext<'a> {
+    /// 所在类型的完整名称
+    class_name: &'a str,
+    /// 所在类型的简单名称，用于替换路由中的 `[controller]`
+    simple_class_name: &'a str,
+    /// 基类的名称
+    superclass: Option<&'a str>,
+    /// 控制器级别的路由模板
+    controller_route: Option<&'a str>,
+    /// 字段和属性的类型
+    field_types: &'a HashMap<String, String>,
+}
+
+/// 路由参数模式：`{id}`、`{id:int}`、`{id?}`、`{*path}`、`{**path}`
+fn route_parameter_pattern() -> &'static Regex {
+    static PATTERN: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\{\**(?P<name>\w+)[^}]*\}").unwrap())
+}
+
+/// 取类型节点的名称：泛型类型去掉类型参数，可空类型去掉 `?`
+fn type_name(source: &str, node: tree_sitter::Node) -> Option<String> {
+    match node.kind() {
+        "generic_name" => {
+            let mut cursor = node.walk();
+            let identifier = node.named_children(&mut cursor).find(|c| c.kind() == "identifier")?;
+            source.get(identifier.byte_range()).map(str::to_string)
+        }
+        "nullable_type" => type_name(source, node.named_child(0)?),
+        "primary_constructor_base_type" => type_name(source, node.named_child(0)?),
+        _ => source.get(node.byte_range()).map(|text| text.trim_end_matches('?').to_string()),
+    }
+}
+
+/// 按 `using` 别名和本文件声明的类型补全类型名
+///
+/// 基类型和调用目标中的类型名（`T::method` 的 `T`）首段是别名或本文件中声明的类型时替换为完整名称，
+/// 其余类型名可能来自同一命名空间的其他文件或 `using` 引入的命名空间，留给索引阶段的
+/// `code_index::resolve_csharp_types` 处理
+fn resolve_file_types(classes: &mut [ClassInfo], imports: &[Import]) {
+    let mut known: HashMap<String, String> = HashMap::new();
+    for class in classes.iter() {
+        let simple = class.name.rsplit('.').next().unwrap_or(&class.name);
+        known.entry(simple.to_string()).or_insert_with(|| class.name.clone());
+    }
+    // 别名优先于同名的类型声明
+    for import in imports {
+        for alias in &import.items {
+            known.insert(alias.clone(), import.module.clone());
+        }
+    }
+
+    let resolve = |name: &str| -> String {
+        let (head, rest) = match name.split_once('.') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (name, None),
+        };
+        match (known.get(head), rest) {
+            (Some(full), Some(rest)) => format!("{}.{}", full, rest),
+            (Some(full), None) => full.clone(),
+            (None, _) => name.to_string(),
+        }
+    };
+
+    for class in classes.iter_mut() {
+        class.extends = class.extends.as_deref().map(resolve);
+        for interface in &mut class.implements {
+            *interface = resolve(interface);
+        }
+        for call in class.methods.iter_mut().flat_map(|method| method.calls.iter_mut()) {
+            if let Some((type_name, method)) = call.target.split_once("::") {
+                call.target = format!("{}::{}", resolve(type_name), method);
+            }
+        }
+    }
+}
+
+/// 取方法的可见性
+///
+/// 接口成员默认为 public；`internal`、`protected` 按包级可见处理，没有访问修饰符的类成员为私有
+fn method_visibility(source: &str, method_node: tree_sitter::Node) -> Visibility {
+    let in_interface = method_node.parent()
+        .and_then(|body| body.parent())
+        .is_some_and(|declaration| declaration.kind() == "interface_declaration");
+
+    let mut cursor = method_node.walk();
+    let modifiers: Vec<&str> = method_node.children(&mut cursor)
+        .filter(|child| child.kind() == "modifier")
+        .filter_map(|child| source.get(child.byte_range()))
+        .collect();
+    if modifiers.contains(&"public") || in_interface {
+        Visibility::Public
+    } else if modifiers.iter().any(|m| matches!(*m, "internal" | "protected")) {
+        Visibility::Package
+    } else {
+        Visibility::Private
+    }
+}
+
+/// 是否符合接口的命名约定（`I` 后跟大写字母，如 `IOrderService`）
+fn is_interface_name(type_name: &str) -> bool {
+    let simple = type_name.rsplit('.').next().unwrap_or(type_name);
+    let mut chars = simple.chars();
+    chars.next() == Some('I') && chars.next().is_some_and(char::is_uppercase)
+}
+
+impl LanguageParser for CSharpParser {
+    fn language_name(&self) -> &str {
+        "csharp"
+    }
+
+    fn file_extensions(&self) -> &[&str] {
+        &["cs"]
+    }
+
+    fn set_call_filter(&mut self, filter: CallFilter) {
+        self.call_filter = filter;
+    }
+
+    fn parse_file(&self, content: &str, file_path: &Path) -> Result<ParsedFile, ParseError> {
+        let tree = self.parser.lock().unwrap().parse(content, None)
+            .ok_or_else(|| ParseError::InvalidFormat {
+                message: "Failed to parse C# file".to_string(),
+            })?;
+        let syntax_errors = collect_syntax_errors(&tree);
+
+        let mut classes = self.extract_classes(content, file_path, &tree);
+        let imports = self.extract_imports(content, &tree);
+        resolve_file_types(&mut classes, &imports);
+
+        Ok(ParsedFile {
+            file_path: file_path.to_path_buf(),
+            language: "csharp".to_string(),
+            classes,
+            functions: vec![], // C# 使用类和方法，不使用顶层函数
+            imports,
+            string_constants: vec![],
+            functional_routes: vec![],
+            syntax_errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTROLLER: &str = r#"
+using Microsoft.AspNetCore.Mvc;
+
+namespace Shop.Orders
+{
+    [ApiController]
+    [Route("api/[controller]")]
+    public class OrdersController : ControllerBase
+    {
+        private readonly IOrderService _orderService;
+
+        public OrdersController(IOrderService orderService)
+        {
+            _orderService = orderService;
+        }
+
+        [HttpGet("{id}")]
+        public ActionResult<Order> Get(int id)
+        {
+            var order = _orderService.Find(id);
+            Audit(order);
+            return Ok(order);
+        }
+
+        [HttpPost]
+        public void Create([FromBody] Order order) => _orderService.Save(order);
+
+        [HttpDelete("/v2/orders/{id:int}")]
+        public void Delete(int id)
+        {
+            var repository = new OrderRepository();
+            repository.Remove(id);
+        }
+
+        private void Audit(Order order)
+        {
+        }
+    }
+}
+"#;
+
+    #[test]
+    fn test_parse_aspnet_controller() {
+        let parser = CSharpParser::new().unwrap();
+        let result = parser.parse_file(CONTROLLER, Path::new("OrdersController.cs")).unwrap();
+
+        assert_eq!(result.language, "csharp");
+        assert_eq!(result.imports[0].module, "Microsoft.AspNetCore.Mvc");
+        assert_eq!(result.classes.len(), 1);
+        let class = &result.classes[0];
+        assert_eq!(class.name, "Shop.Orders.OrdersController");
+        // 框架类型不在本文件中声明，保持源码中的写法
+        assert_eq!(class.extends.as_deref(), Some("ControllerBase"));
+
+        let method = |name: &str| class.methods.iter().find(|m| m.name == name).unwrap();
+
+        // 构造器记为 <init>
+        assert_eq!(method("<init>").full_qualified_name, "Shop.Orders.OrdersController::<init>");
+        assert!(method("<init>").http_annotations.is_none());
+
+        // 控制器的 [Route] 与 [HttpGet("{id}")] 组合，[controller] 替换为控制器名
+        let get = method("Get").http_annotations.as_ref().unwrap();
+        assert_eq!(get.method, HttpMethod::GET);
+        assert_eq!(get.path, "api/orders/{id}");
+        assert_eq!(get.path_params, vec!["id".to_string()]);
+        assert!(!get.is_feign_client);
+
+        // 没有模板的 HTTP 方法特性使用控制器路由
+        let create = method("Create").http_annotations.as_ref().unwrap();
+        assert_eq!(create.method, HttpMethod::POST);
+        assert_eq!(create.path, "api/orders");
+
+        // 以 / 开头的模板覆盖控制器路由，路由约束被去掉
+        let delete = method("Delete").http_annotations.as_ref().unwrap();
+        assert_eq!(delete.path, "v2/orders/{id}");
+
+        assert!(method("Audit").http_annotations.is_none());
+    }
+
+    #[test]
+    fn test_resolve_method_calls() {
+        let parser = CSharpParser::new().unwrap();
+        let result = parser.parse_file(CONTROLLER, Path::new("OrdersController.cs")).unwrap();
+        let class = &result.classes[0];
+        let targets = |name: &str| -> Vec<String> {
+            class.methods.iter().find(|m| m.name == name).unwrap()
+                .calls.iter().map(|c| c.target.clone()).collect()
+        };
+
+        // 字段按声明类型解析，不带接收者的调用解析到当前类；
+        // 其他文件中的类型由索引阶段按命名空间补全
+        assert_eq!(targets("Get"), vec![
+            "IOrderService::Find".to_string(),
+            "Shop.Orders.OrdersController::Audit".to_string(),
+            "Shop.Orders.OrdersController::Ok".to_string(),
+        ]);
+        assert_eq!(targets("Create"), vec!["IOrderService::Save".to_string()]);
+        // `var x = new T()` 推断为 T，并产生构造器调用
+        assert_eq!(targets("Delete"), vec![
+            "OrderRepository::<init>".to_string(),
+            "OrderRepository::Remove".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_file_scoped_namespace_and_interfaces() {
+        let parser = CSharpParser::new().unwrap();
+        let source = r#"
+namespace Shop.Orders;
+
+public interface IOrderService
+{
+    Order Find(int id);
+}
+
+public class OrderService : BaseService, IOrderService, IDisposable
+{
+    public Order Find(int id) => base.Load(id);
+
+    public class Nested
+    {
+        public void Run() { }
+    }
+}
+"#;
+        let result = parser.parse_file(source, Path::new("OrderService.cs")).unwrap();
+        let class = |name: &str| result.classes.iter().find(|c| c.name == name).unwrap();
+
+        assert!(class("Shop.Orders.IOrderService").is_interface);
+        let service = class("Shop.Orders.OrderService");
+        // 本文件中声明的类型补全为完整名称，其余保持原样
+        assert_eq!(service.extends.as_deref(), Some("BaseService"));
+        assert_eq!(service.implements, vec!["Shop.Orders.IOrderService".to_string(), "IDisposable".to_string()]);
+        assert_eq!(service.methods[0].calls[0].target, "BaseService::Load");
+        assert_eq!(class("Shop.Orders.OrderService.Nested").methods[0].full_qualified_name, "Shop.Orders.OrderService.Nested::Run");
+    }
+
+    #[test]
+    fn test_using_alias_resolves_types() {
+        let parser = CSharpParser::new().unwrap();
+        let source = r#"
+using System;
+using Repo = Shop.Data.OrderRepository;
+
+namespace Shop.Orders;
+
+public class OrderService : Repo
+{
+    private readonly Repo _repository;
+
+    public void Delete(int id) => _repository.Remove(id);
+}
+"#;
+        let result = parser.parse_file(source, Path::new("OrderService.cs")).unwrap();
+
+        assert_eq!(result.imports[0].module, "System");
+        assert!(result.imports[0].items.is_empty());
+        assert_eq!(result.imports[1].module, "Shop.Data.OrderRepository");
+        assert_eq!(result.imports[1].items, vec!["Repo".to_string()]);
+
+        let service = &result.classes[0];
+        assert_eq!(service.extends.as_deref(), Some("Shop.Data.OrderRepository"));
+        assert_eq!(service.methods[0].calls[0].target, "Shop.Data.OrderRepository::Remove");
+    }
+}
@@ -10,6 +10,10 @@ pub enum AnalysisError {
     IndexBuildError(IndexError),
     TraceError(TraceError),
     IoError(std::io::Error),
+    /// 影响范围超过阈值（用于 CI 门禁）
+    ImpactThresholdExceeded { impact: usize, threshold: usize },
+    /// 严格模式（`--strict`）下有 patch、源文件或配置文件无法解析
+    ParseFailures { count: usize },
 }
 
 impl fmt::Display for AnalysisError {
@@ -25,12 +29,59 @@ impl fmt::Display for AnalysisError {
             AnalysisError::IndexBuildError(e) => write!(f, "Index build error: {}", e),
             AnalysisError::TraceError(e) => write!(f, "Trace error: {}", e),
             AnalysisError::IoError(e) => write!(f, "IO error: {}", e),
+            AnalysisError::ImpactThresholdExceeded { impact, threshold } => {
+                write!(f, "impact of {} exceeds threshold {}", impact, threshold)
+            }
+            AnalysisError::ParseFailures { count } => write!(f, "{} files could not be parsed", count),
+        }
+    }
+}
+
+impl AnalysisError {
+    /// 获取以该错误结束时的进程退出码
+    /// 
+    /// 严格模式下的解析失败和超过影响阈值使用 `ExitStatus` 中对应的退出码，其他错误为 `ExitStatus::FAILURE_CODE`
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AnalysisError::ParseFailures { .. } => ExitStatus::ParseErrors.code(),
+            AnalysisError::ImpactThresholdExceeded { .. } => ExitStatus::ImpactThresholdExceeded.code(),
+            _ => ExitStatus::FAILURE_CODE,
         }
     }
 }
 
 impl std::error::Error for AnalysisError {}
 
+/// 分析完成后的退出状态，供 CI 区分不同的结果
+/// 
+/// 分析失败（返回 `AnalysisError`）时以 `AnalysisError::exit_code` 退出
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// 分析成功完成（退出码 0）
+    Success,
+    /// 严格模式（`--strict`）下有 patch、源文件或配置文件无法解析（退出码 2）
+    ParseErrors,
+    /// 影响范围超过 `--max-impact` 阈值（退出码 3）
+    ImpactThresholdExceeded,
+    /// 变更没有落在任何方法内（退出码 4）
+    NoMethodsMatched,
+}
+
+impl ExitStatus {
+    /// 分析失败时的退出码
+    pub const FAILURE_CODE: i32 = 1;
+    
+    /// 获取进程退出码
+    pub fn code(self) -> i32 {
+        match self {
+            ExitStatus::Success => 0,
+            ExitStatus::ParseErrors => 2,
+            ExitStatus::ImpactThresholdExceeded => 3,
+            ExitStatus::NoMethodsMatched => 4,
+        }
+    }
+}
+
 impl From<std::io::Error> for AnalysisError {
     fn from(error: std::io::Error) -> Self {
         AnalysisError::IoError(error)
@@ -42,6 +93,8 @@ impl From<std::io::Error> for AnalysisError {
 pub enum ParseError {
     InvalidFormat { message: String },
     SyntaxError { line: usize, column: usize, message: String },
+    /// 语法树中存在错误节点，`locations` 为各错误节点的起始位置（行号、列号，均从 1 开始）
+    SyntaxErrors { path: PathBuf, locations: Vec<(usize, usize)> },
     UnsupportedLanguage { language: String },
     BinaryFile { path: PathBuf },
     IoError { path: PathBuf, error: String },
@@ -54,6 +107,12 @@ impl fmt::Display for ParseError {
             ParseError::SyntaxError { line, column, message } => {
                 write!(f, "Syntax error at {}:{}: {}", line, column, message)
             }
+            ParseError::SyntaxErrors { path, locations } => {
+                let locations: Vec<String> = locations.iter()
+                    .map(|(line, column)| format!("{}:{}", line, column))
+                    .collect();
+                write!(f, "Syntax errors in {:?} at {}", path, locations.join(", "))
+            }
             ParseError::UnsupportedLanguage { language } => {
                 write!(f, "Unsupported language: {}", language)
             }
@@ -105,20 +164,35 @@ impl std::error::Error for IndexError {}
 #[derive(Debug, Clone)]
 pub enum TraceError {
     MethodNotFound { method: String },
+    /// 没有找到提供该 HTTP 端点的方法
+    EndpointNotFound { endpoint: String },
+    /// 没有找到访问该资源（表、Topic、Redis 键、HTTP 端点）的方法
+    ResourceNotFound { resource: String },
     MaxDepthExceeded { depth: usize },
     CyclicDependency { cycle: Vec<String> },
+    /// 追溯配置无效（如未启用任何追溯方向）
+    InvalidConfig { message: String },
 }
 
 impl fmt::Display for TraceError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TraceError::MethodNotFound { method } => write!(f, "Method not found: {}", method),
+            TraceError::EndpointNotFound { endpoint } => {
+                write!(f, "No provider found for endpoint: {}", endpoint)
+            }
+            TraceError::ResourceNotFound { resource } => {
+                write!(f, "No method accesses resource: {}", resource)
+            }
             TraceError::MaxDepthExceeded { depth } => {
                 write!(f, "Max depth exceeded: {}", depth)
             }
             TraceError::CyclicDependency { cycle } => {
                 write!(f, "Cyclic dependency: {}", cycle.join(" -> "))
             }
+            TraceError::InvalidConfig { message } => {
+                write!(f, "Invalid trace config: {}", message)
+            }
         }
     }
 }
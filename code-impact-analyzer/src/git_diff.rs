@@ -0,0 +1,214 @@
+#[cfg(feature = "git")]
+use std::collections::{hash_map::Entry, HashMap};
+#[cfg(feature = "git")]
+use std::path::PathBuf;
+use std::path::Path;
+
+/// 从 Git 仓库读取的变更范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitDiffMode {
+    /// 已暂存的变更：HEAD 与暂存区之间的差异（`git diff --cached`）
+    Staged,
+    /// 未暂存的变更：暂存区与工作区之间的差异（`git diff`），不包含未跟踪的文件
+    Working,
+}
+
+/// 读取仓库中的变更并输出为 unified diff 文本
+/// 
+/// 输出格式与 `git diff` 相同，可直接交给 `PatchParser::parse_patch_str` 解析；
+/// 文件路径相对于仓库根目录
+/// 
+/// # Arguments
+/// * `repo_path` - 仓库根目录（即工作空间根目录）
+/// * `mode` - 读取已暂存还是未暂存的变更
+/// 
+/// # Returns
+/// * `Ok(String)` - diff 文本，没有变更时为空字符串
+/// * `Err(std::io::Error)` - 打开仓库或生成 diff 失败
+#[cfg(feature = "git")]
+pub fn read_git_diff(repo_path: &Path, mode: GitDiffMode) -> Result<String, std::io::Error> {
+    let to_io_error = |e: git2::Error| std::io::Error::other(format!("Git diff failed in {:?}: {}", repo_path, e));
+    
+    let repo = git2::Repository::open(repo_path).map_err(to_io_error)?;
+    
+    // 不输出上下文行：hunk 的行范围只覆盖实际修改的行，避免把相邻方法误判为变更
+    let mut options = git2::DiffOptions::new();
+    options.context_lines(0);
+    
+    let diff = match mode {
+        GitDiffMode::Staged => {
+            // 还没有提交时以空树为基准，暂存的文件都视为新增
+            let head_tree = match repo.head() {
+                Ok(head) => Some(head.peel_to_tree().map_err(to_io_error)?),
+                Err(_) => None,
+            };
+            repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut options))
+        }
+        GitDiffMode::Working => repo.diff_index_to_workdir(None, Some(&mut options)),
+    }
+    .map_err(to_io_error)?;
+    
+    let mut patch = String::new();
+    diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
+        // 文件头和 hunk 头的内容已是完整的行，正文行需要补上 +/-/空格 前缀
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            patch.push(line.origin());
+        }
+        patch.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })
+    .map_err(to_io_error)?;
+    
+    Ok(patch)
+}
+
+/// 读取仓库中的变更（未启用 `git` 特性时总是返回错误）
+#[cfg(not(feature = "git"))]
+pub fn read_git_diff(repo_path: &Path, _mode: GitDiffMode) -> Result<String, std::io::Error> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("Reading changes from {:?} requires the `git` feature", repo_path),
+    ))
+}
+
+/// 一段代码最后一次修改所在的提交
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastCommit {
+    /// 提交作者
+    pub author: String,
+    /// 提交 ID
+    pub commit: String,
+}
+
+/// 文件的 blame 结果：各段的起止行号（1 起始，闭区间）、提交时间和提交
+#[cfg(feature = "git")]
+type BlameHunks = Vec<((usize, usize), i64, LastCommit)>;
+
+/// 按文件缓存 git blame 结果，查询行范围最后一次修改所在的提交
+/// 
+/// 每个文件只 blame 一次；blame 以工作区中的文件内容为准，尚未提交的行不计入
+#[cfg(feature = "git")]
+pub struct BlameCache {
+    repo: git2::Repository,
+    /// 文件路径 -> blame 的各段，不在仓库中或未跟踪的文件为空
+    files: HashMap<PathBuf, BlameHunks>,
+}
+
+#[cfg(feature = "git")]
+impl BlameCache {
+    /// 打开包含指定路径的 Git 仓库
+    /// 
+    /// # Arguments
+    /// * `path` - 仓库中的任意路径（如工作空间根目录）
+    pub fn open(path: &Path) -> Result<Self, std::io::Error> {
+        let repo = git2::Repository::discover(path)
+            .map_err(|e| std::io::Error::other(format!("Failed to open git repository at {:?}: {}", path, e)))?;
+        Ok(Self { repo, files: HashMap::new() })
+    }
+    
+    /// 查询文件中一段行范围最后一次修改所在的提交
+    /// 
+    /// # Arguments
+    /// * `file` - 文件路径（绝对路径或相对于仓库根目录）
+    /// * `line_range` - 行范围（1 起始，闭区间）
+    /// 
+    /// # Returns
+    /// * 范围内提交时间最晚的提交；文件不在仓库中或范围内的行都未提交时为 `None`
+    pub fn last_commit(&mut self, file: &Path, line_range: (usize, usize)) -> Option<LastCommit> {
+        if !self.files.contains_key(file) {
+            let hunks = self.blame(file).unwrap_or_else(|e| {
+                log::debug!("Failed to blame {:?}: {}", file, e);
+                Vec::new()
+            });
+            self.files.insert(file.to_path_buf(), hunks);
+        }
+        
+        let overlapping = self.files[file].iter()
+            .filter(|((start, end), _, _)| *start <= line_range.1 && *end >= line_range.0);
+        let mut latest: Option<(i64, &LastCommit)> = None;
+        for (_, time, commit) in overlapping {
+            let later = match latest {
+                None => true,
+                Some((latest_time, _)) if *time != latest_time => *time > latest_time,
+                // 提交时间相同（精确到秒）时按提交历史判断先后
+                Some((_, latest_commit)) => self.is_descendant(&commit.commit, &latest_commit.commit),
+            };
+            if later {
+                latest = Some((*time, commit));
+            }
+        }
+        latest.map(|(_, commit)| commit.clone())
+    }
+    
+    /// 判断提交 `commit` 是否是 `ancestor` 的后代
+    fn is_descendant(&self, commit: &str, ancestor: &str) -> bool {
+        match (git2::Oid::from_str(commit), git2::Oid::from_str(ancestor)) {
+            (Ok(commit), Ok(ancestor)) => self.repo.graph_descendant_of(commit, ancestor).unwrap_or(false),
+            _ => false,
+        }
+    }
+    
+    /// 对工作区中的文件执行 blame
+    fn blame(&self, file: &Path) -> Result<BlameHunks, git2::Error> {
+        let workdir = self.repo.workdir()
+            .ok_or_else(|| git2::Error::from_str("bare repository has no working directory"))?;
+        let relative = match file.strip_prefix(workdir) {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) if file.is_relative() => file.to_path_buf(),
+            // 工作空间路径可能经过符号链接，规范化后再比较
+            Err(_) => {
+                let canonical = file.canonicalize().map_err(|e| git2::Error::from_str(&e.to_string()))?;
+                let workdir = workdir.canonicalize().map_err(|e| git2::Error::from_str(&e.to_string()))?;
+                canonical.strip_prefix(&workdir)
+                    .map_err(|_| git2::Error::from_str("file is outside the repository"))?
+                    .to_path_buf()
+            }
+        };
+        
+        let committed = self.repo.blame_file(&relative, None)?;
+        // 以工作区内容为准，使行号与索引中的方法行范围一致
+        let blame = match std::fs::read(workdir.join(&relative)) {
+            Ok(content) => committed.blame_buffer(&content)?,
+            Err(_) => committed,
+        };
+        
+        // 按缓冲区 blame 的段不一定带有签名，作者和时间从提交中读取
+        let mut authors: HashMap<git2::Oid, (String, i64)> = HashMap::new();
+        let mut hunks = Vec::new();
+        for hunk in blame.iter().filter(|hunk| !hunk.final_commit_id().is_zero()) {
+            let oid = hunk.final_commit_id();
+            let (author, time) = match authors.entry(oid) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let commit = self.repo.find_commit(oid)?;
+                    let author = commit.author();
+                    entry.insert((author.name().unwrap_or_default().to_string(), author.when().seconds()))
+                }
+            };
+            let start = hunk.final_start_line();
+            let end = start + hunk.lines_in_hunk().saturating_sub(1);
+            hunks.push(((start, end), *time, LastCommit { author: author.clone(), commit: oid.to_string() }));
+        }
+        Ok(hunks)
+    }
+}
+
+/// 按文件缓存 git blame 结果（未启用 `git` 特性时无法打开仓库）
+#[cfg(not(feature = "git"))]
+pub struct BlameCache;
+
+#[cfg(not(feature = "git"))]
+impl BlameCache {
+    /// 打开包含指定路径的 Git 仓库（未启用 `git` 特性时总是返回错误）
+    pub fn open(path: &Path) -> Result<Self, std::io::Error> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!("Blaming files in {:?} requires the `git` feature", path),
+        ))
+    }
+    
+    /// 查询文件中一段行范围最后一次修改所在的提交（总是返回 `None`）
+    pub fn last_commit(&mut self, _file: &Path, _line_range: (usize, usize)) -> Option<LastCommit> {
+        None
+    }
+}
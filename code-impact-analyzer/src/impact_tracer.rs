@@ -1,10 +1,22 @@
-use std::collections::{HashSet, HashMap};
+use std::collections::{BTreeMap, HashSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use crate::code_index::CodeIndex;
+use crate::language_parser::MethodCall;
+use crate::resource_matcher::{DatabaseMatcher, KafkaMatcher, RedisMatcher, ResourceAccess, ResourceMatcher};
 use crate::errors::TraceError;
 use crate::types::HttpMethod;
 use serde::{Deserialize, Serialize};
 use petgraph::graph::{DiGraph, NodeIndex};
 
+/// 追溯决策日志的目标名
+/// 
+/// 追溯过程中每个方法找到的调用者/被调用者、跳过的外部方法和接口解析以 `trace` 级别
+/// 记录到该目标下，默认不输出；命令行 `--trace-debug` 为该目标开启 `trace` 级别，
+/// [`TraceConfig::diagnostic_filter`] 可以把日志限定在特定方法上
+pub const TRACE_DEBUG_TARGET: &str = "trace_debug";
+
 /// 追溯配置
 #[derive(Debug, Clone)]
 pub struct TraceConfig {
@@ -16,6 +28,18 @@ pub struct TraceConfig {
     pub trace_downstream: bool,
     /// 是否追溯跨服务边界
     pub trace_cross_service: bool,
+    /// 允许追溯的服务列表（工作空间下的顶层目录名），为空表示不限制
+    /// 
+    /// 指向其他服务的跨服务边只保留 HTTP/Kafka 等中间节点，作为叶子节点
+    pub allowed_services: Vec<String>,
+    /// 单个 HTTP 端点桥接最多展开的提供者/消费者数量，为空表示不限制
+    /// 
+    /// 按方法名排序后取前 N 个，其余以一个汇总节点代替
+    pub max_bridge_fanout: Option<usize>,
+    /// 诊断过滤器：只为全限定名包含该子串的方法记录追溯决策日志（见 [`TRACE_DEBUG_TARGET`]）
+    /// 
+    /// 用于排查特定方法的追溯结果，为空表示记录所有方法
+    pub diagnostic_filter: Option<String>,
 }
 
 impl Default for TraceConfig {
@@ -25,12 +49,118 @@ impl Default for TraceConfig {
             trace_upstream: true,
             trace_downstream: true,
             trace_cross_service: true,
+            allowed_services: Vec::new(),
+            max_bridge_fanout: None,
+            diagnostic_filter: None,
+        }
+    }
+}
+
+impl TraceConfig {
+    /// 创建追溯配置构建器，未设置的选项使用默认值
+    pub fn builder() -> TraceConfigBuilder {
+        TraceConfigBuilder::default()
+    }
+    
+    /// 影响追溯结果的配置项的规范化文本，用作追溯缓存键
+    /// 
+    /// 服务列表排序后拼接；只影响日志的诊断过滤器不参与
+    fn cache_key(&self) -> String {
+        let mut allowed_services = self.allowed_services.clone();
+        allowed_services.sort_unstable();
+        allowed_services.dedup();
+        format!(
+            "max_depth={};upstream={};downstream={};cross_service={};allowed_services={};max_bridge_fanout={}",
+            self.max_depth,
+            self.trace_upstream,
+            self.trace_downstream,
+            self.trace_cross_service,
+            allowed_services.join(","),
+            self.max_bridge_fanout.map(|n| n.to_string()).unwrap_or_default(),
+        )
+    }
+}
+
+/// 追溯配置构建器
+/// 
+/// 与直接构造 `TraceConfig` 不同，`build` 会拒绝只会产生空图的配置组合
+#[derive(Debug, Clone, Default)]
+pub struct TraceConfigBuilder {
+    config: TraceConfig,
+}
+
+impl TraceConfigBuilder {
+    /// 设置最大追溯深度
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.config.max_depth = max_depth;
+        self
+    }
+    
+    /// 设置是否追溯上游
+    pub fn upstream(mut self, enabled: bool) -> Self {
+        self.config.trace_upstream = enabled;
+        self
+    }
+    
+    /// 设置是否追溯下游
+    pub fn downstream(mut self, enabled: bool) -> Self {
+        self.config.trace_downstream = enabled;
+        self
+    }
+    
+    /// 设置是否追溯跨服务边界
+    pub fn cross_service(mut self, enabled: bool) -> Self {
+        self.config.trace_cross_service = enabled;
+        self
+    }
+    
+    /// 设置允许追溯的服务列表，为空表示不限制
+    pub fn allowed_services(mut self, services: Vec<String>) -> Self {
+        self.config.allowed_services = services;
+        self
+    }
+    
+    /// 设置单个 HTTP 端点桥接最多展开的提供者/消费者数量
+    pub fn max_bridge_fanout(mut self, max_fanout: Option<usize>) -> Self {
+        self.config.max_bridge_fanout = max_fanout;
+        self
+    }
+    
+    /// 设置诊断过滤器，只为全限定名包含该子串的方法记录追溯决策日志
+    pub fn diagnostic_filter(mut self, filter: Option<String>) -> Self {
+        self.config.diagnostic_filter = filter;
+        self
+    }
+    
+    /// 校验并构建追溯配置
+    /// 
+    /// # Returns
+    /// * `Ok(TraceConfig)` - 追溯配置
+    /// * `Err(TraceError::InvalidConfig)` - 未启用任何追溯方向，或启用跨服务追溯时最大深度为 0
+    pub fn build(self) -> Result<TraceConfig, TraceError> {
+        let config = self.config;
+        
+        if !config.trace_upstream && !config.trace_downstream {
+            return Err(TraceError::InvalidConfig {
+                message: "at least one of upstream or downstream tracing must be enabled".to_string(),
+            });
+        }
+        
+        if config.max_depth == 0 && config.trace_cross_service {
+            return Err(TraceError::InvalidConfig {
+                message: "max_depth must be at least 1 when cross-service tracing is enabled".to_string(),
+            });
         }
+        
+        Ok(config)
     }
 }
 
 /// 节点类型
+/// 
+/// 序列化形式即 JSON 输出中节点的 `type` 字段：以 `kind` 标识变体，其余为变体字段
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum NodeType {
     /// 方法节点
     Method { qualified_name: String },
@@ -42,6 +172,19 @@ pub enum NodeType {
     DatabaseTable { name: String },
     /// Redis 键前缀节点
     RedisPrefix { prefix: String },
+    /// RPC 接口方法节点（如 Dubbo）
+    RpcMethod { interface: String, method: String },
+    /// Thrift 服务方法节点
+    ThriftMethod { service: String, method: String },
+    /// GraphQL 字段节点（解析器入口点）
+    #[serde(rename = "graphql_field")]
+    GraphQLField { type_name: String, field: String },
+    /// Spring Batch 作业节点（批处理入口点）
+    BatchJob { name: String },
+    /// 桥接汇总节点，代表端点上超出展开上限而未追溯的方法
+    BridgeSummary { endpoint: String, omitted: usize },
+    /// 自定义资源匹配器产生的资源节点
+    Resource { category: String, name: String },
 }
 
 /// 节点元数据
@@ -80,7 +223,7 @@ impl ImpactNode {
     
     /// 创建 HTTP 端点节点
     pub fn http_endpoint(method: HttpMethod, path: String) -> Self {
-        let method_str = format!("{:?}", method);
+        let method_str = method.as_str().to_string();
         let id = format!("http:{}:{}", method_str, path);
         Self {
             id: id.clone(),
@@ -122,7 +265,19 @@ impl ImpactNode {
     }
     
     /// 创建 Redis 键前缀节点
+    /// 
+    /// 键前缀会先经过规范化，`user:{id}` 与 `user:*` 归并为同一个节点
     pub fn redis_prefix(prefix: String) -> Self {
+        Self::redis_namespace(prefix, None)
+    }
+    
+    /// 创建按命名空间合并的 Redis 键节点
+    /// 
+    /// # Arguments
+    /// * `prefix` - 原始键模式
+    /// * `depth` - 节点标识保留的冒号分隔段数，`None` 表示不合并
+    pub fn redis_namespace(prefix: String, depth: Option<usize>) -> Self {
+        let prefix = crate::code_index::redis_namespace_key(&prefix, depth);
         let id = format!("redis:{}", prefix);
         Self {
             id: id.clone(),
@@ -133,10 +288,126 @@ impl ImpactNode {
             },
         }
     }
+    
+    /// 创建 RPC 接口方法节点
+    pub fn rpc_method(interface: String, method: String) -> Self {
+        let id = format!("rpc:{}::{}", interface, method);
+        Self {
+            id: id.clone(),
+            node_type: NodeType::RpcMethod { 
+                interface: interface.clone(), 
+                method: method.clone() 
+            },
+            metadata: NodeMetadata {
+                label: format!("RPC: {}::{}", interface, method),
+                properties: HashMap::new(),
+            },
+        }
+    }
+    
+    /// 创建 Thrift 服务方法节点
+    pub fn thrift_method(service: String, method: String) -> Self {
+        let id = format!("thrift:{}::{}", service, method);
+        Self {
+            id: id.clone(),
+            node_type: NodeType::ThriftMethod {
+                service: service.clone(),
+                method: method.clone(),
+            },
+            metadata: NodeMetadata {
+                label: format!("Thrift: {}::{}", service, method),
+                properties: HashMap::new(),
+            },
+        }
+    }
+    
+    /// 创建 GraphQL 字段节点
+    /// 
+    /// GraphQL 解析器是服务的入口点，节点属性中标记 `entry_point = true`
+    pub fn graphql_field(type_name: String, field: String) -> Self {
+        let id = format!("graphql:{}.{}", type_name, field);
+        let mut properties = HashMap::new();
+        properties.insert("entry_point".to_string(), "true".to_string());
+        Self {
+            id: id.clone(),
+            node_type: NodeType::GraphQLField {
+                type_name: type_name.clone(),
+                field: field.clone(),
+            },
+            metadata: NodeMetadata {
+                label: format!("GraphQL: {}.{}", type_name, field),
+                properties,
+            },
+        }
+    }
+    
+    /// 创建 Spring Batch 作业节点
+    /// 
+    /// 批处理作业由调度器或启动器触发，是服务的入口点，节点属性中标记 `entry_point = true`
+    pub fn batch_job(name: String) -> Self {
+        let id = format!("batch:{}", name);
+        let mut properties = HashMap::new();
+        properties.insert("entry_point".to_string(), "true".to_string());
+        Self {
+            id: id.clone(),
+            node_type: NodeType::BatchJob { name: name.clone() },
+            metadata: NodeMetadata {
+                label: format!("Batch Job: {}", name),
+                properties,
+            },
+        }
+    }
+    
+    /// 创建自定义资源节点
+    /// 
+    /// # Arguments
+    /// * `category` - 资源类别（如 `queue`、`feature-store`），用于区分不同匹配器的资源
+    /// * `name` - 资源名
+    pub fn resource(category: String, name: String) -> Self {
+        let id = format!("resource:{}:{}", category, name);
+        Self {
+            id: id.clone(),
+            node_type: NodeType::Resource { category: category.clone(), name: name.clone() },
+            metadata: NodeMetadata {
+                label: format!("{}: {}", category, name),
+                properties: HashMap::new(),
+            },
+        }
+    }
+    
+    /// 创建桥接汇总节点
+    /// 
+    /// 节点 ID 包含追溯方向，同一端点的提供者侧（下游）和消费者侧（上游）汇总互不覆盖
+    /// 
+    /// # Arguments
+    /// * `endpoint_id` - 被截断的端点节点 ID
+    /// * `direction` - 被截断的追溯方向
+    /// * `omitted` - 未展开的提供者/消费者数量
+    pub fn bridge_summary(endpoint_id: String, direction: &Direction, omitted: usize) -> Self {
+        let direction = match direction {
+            Direction::Upstream => "upstream",
+            Direction::Downstream => "downstream",
+        };
+        let id = format!("summary:{}:{}", direction, endpoint_id);
+        Self {
+            id: id.clone(),
+            node_type: NodeType::BridgeSummary {
+                endpoint: endpoint_id,
+                omitted,
+            },
+            metadata: NodeMetadata {
+                label: format!("... {} more", omitted),
+                properties: HashMap::new(),
+            },
+        }
+    }
 }
 
 /// 边类型
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// 
+/// 序列化形式即 JSON 输出中边的 `type` 字段
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum EdgeType {
     /// 方法调用
     MethodCall,
@@ -148,10 +419,22 @@ pub enum EdgeType {
     DatabaseReadWrite,
     /// Redis 读写
     RedisReadWrite,
+    /// RPC 调用
+    RpcCall,
+    /// Thrift 调用
+    ThriftCall,
+    /// GraphQL 字段解析
+    #[serde(rename = "graphql_resolve")]
+    GraphQLResolve,
+    /// 批处理作业启动
+    BatchLaunch,
+    /// 自定义资源读写
+    ResourceAccess,
 }
 
 /// 边方向
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Direction {
     /// 上游（调用者）
     Upstream,
@@ -159,6 +442,200 @@ pub enum Direction {
     Downstream,
 }
 
+/// 边的风险等级
+/// 
+/// 目前只用于数据库读写边：写入共享表（INSERT/UPDATE/DELETE）比读取（SELECT）风险更高
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Risk {
+    /// 低风险（读取）
+    Low,
+    /// 高风险（写入）
+    High,
+}
+
+/// 方法节点标签的生成方式，节点 ID 始终保留完整的全限定名
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LabelStyle {
+    /// 完整的全限定名，如 `com.example.service.OrderServiceImpl::createOrder`
+    #[default]
+    Full,
+    /// 只保留类名和方法名，如 `OrderServiceImpl::createOrder`
+    Simple,
+    /// 包名取首字母、类名取大写字母、方法名只保留第一个单词，如 `c.e.s.OSI::create...`
+    PackageInitials,
+}
+
+impl LabelStyle {
+    /// 生成方法节点的标签
+    /// 
+    /// 类型路径中首字母小写的段视为包名，首字母大写的段视为类名（嵌套类保留各层）；
+    /// 不含 `::` 的名称原样返回
+    /// 
+    /// # Arguments
+    /// * `qualified_name` - 方法全限定名
+    pub fn method_label(&self, qualified_name: &str) -> String {
+        let Some((type_path, method)) = qualified_name.rsplit_once("::") else {
+            return qualified_name.to_string();
+        };
+        let segments: Vec<&str> = type_path.split(['.', ':']).filter(|s| !s.is_empty()).collect();
+        let is_class = |segment: &&str| segment.starts_with(char::is_uppercase);
+        let classes: Vec<&str> = match segments.iter().copied().filter(is_class).collect::<Vec<_>>() {
+            classes if classes.is_empty() => segments.last().copied().into_iter().collect(),
+            classes => classes,
+        };
+        
+        match self {
+            LabelStyle::Full => qualified_name.to_string(),
+            LabelStyle::Simple => format!("{}::{}", classes.join("."), method),
+            LabelStyle::PackageInitials => {
+                let packages = segments.iter()
+                    .filter(|segment| !is_class(segment) && !classes.contains(segment))
+                    .filter_map(|segment| segment.chars().next());
+                let classes = classes.iter().map(|class| {
+                    let initials: String = class.chars().filter(|c| c.is_uppercase()).collect();
+                    if initials.is_empty() { class.to_string() } else { initials }
+                });
+                let type_label: Vec<String> = packages.map(String::from).chain(classes).collect();
+                // 方法名在第二个驼峰单词处截断
+                let method = match method.char_indices().skip(1).find(|(_, c)| c.is_uppercase()) {
+                    Some((end, _)) => format!("{}...", &method[..end]),
+                    None => method.to_string(),
+                };
+                format!("{}::{}", type_label.join("."), method)
+            }
+        }
+    }
+}
+
+/// 节点的渲染提示
+/// 
+/// 输出格式只通过该 trait 获取节点的类型标识和样式，新增节点类型时只需补充 `NodeType::dot_style`
+/// 和 `NodeType` 的 `sarif_rule`
+pub trait NodeRenderHints {
+    /// 节点类型标识，对应 DOT 输出的 `type` 属性
+    fn type_tag(&self) -> &'static str;
+    /// DOT 输出中的节点形状
+    fn dot_shape(&self) -> &'static str;
+    /// SARIF 输出中节点对应的规则和结果描述，方法节点和桥接汇总节点返回 `None`
+    fn sarif_rule(&self) -> Option<SarifRule>;
+}
+
+/// 资源节点在 SARIF 输出中对应的规则
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SarifRule {
+    /// 规则 ID，如 `impact/database-table`
+    pub id: &'static str,
+    /// 规则描述，输出到 `tool.driver.rules`
+    pub description: &'static str,
+    /// 结果描述，包含具体的资源名
+    pub message: String,
+}
+
+/// 边的渲染提示
+pub trait EdgeRenderHints {
+    /// 边类型标识，对应 DOT 输出的边标签
+    fn type_tag(&self) -> &'static str;
+}
+
+impl NodeType {
+    /// 节点类型标识和 DOT 形状
+    fn dot_style(&self) -> (&'static str, &'static str) {
+        match self {
+            NodeType::Method { .. } => ("method", "box"),
+            NodeType::HttpEndpoint { .. } => ("http", "ellipse"),
+            NodeType::KafkaTopic { .. } => ("kafka", "diamond"),
+            NodeType::DatabaseTable { .. } => ("database", "cylinder"),
+            NodeType::RedisPrefix { .. } => ("redis", "hexagon"),
+            NodeType::RpcMethod { .. } => ("rpc", "parallelogram"),
+            NodeType::ThriftMethod { .. } => ("thrift", "trapezium"),
+            NodeType::GraphQLField { .. } => ("graphql", "invhouse"),
+            NodeType::BatchJob { .. } => ("batch", "component"),
+            NodeType::BridgeSummary { .. } => ("summary", "note"),
+            NodeType::Resource { .. } => ("resource", "folder"),
+        }
+    }
+}
+
+impl NodeRenderHints for NodeType {
+    fn type_tag(&self) -> &'static str {
+        self.dot_style().0
+    }
+    
+    fn dot_shape(&self) -> &'static str {
+        self.dot_style().1
+    }
+    
+    fn sarif_rule(&self) -> Option<SarifRule> {
+        let (id, description, message) = match self {
+            NodeType::Method { .. } | NodeType::BridgeSummary { .. } => return None,
+            NodeType::HttpEndpoint { path, method } => (
+                "impact/http-endpoint",
+                "Change impacts an HTTP endpoint",
+                format!("change impacts HTTP endpoint {} {}", method, path),
+            ),
+            NodeType::KafkaTopic { name } => (
+                "impact/kafka-topic",
+                "Change impacts a Kafka topic",
+                format!("change impacts Kafka topic {}", name),
+            ),
+            NodeType::DatabaseTable { name } => (
+                "impact/database-table",
+                "Change impacts a database table",
+                format!("change impacts table {}", name),
+            ),
+            NodeType::RedisPrefix { prefix } => (
+                "impact/redis-prefix",
+                "Change impacts a Redis key prefix",
+                format!("change impacts Redis key prefix {}", prefix),
+            ),
+            NodeType::RpcMethod { interface, method } => (
+                "impact/rpc-method",
+                "Change impacts an RPC method",
+                format!("change impacts RPC method {}::{}", interface, method),
+            ),
+            NodeType::ThriftMethod { service, method } => (
+                "impact/thrift-method",
+                "Change impacts a Thrift service method",
+                format!("change impacts Thrift method {}::{}", service, method),
+            ),
+            NodeType::GraphQLField { type_name, field } => (
+                "impact/graphql-field",
+                "Change impacts a GraphQL field",
+                format!("change impacts GraphQL field {}.{}", type_name, field),
+            ),
+            NodeType::BatchJob { name } => (
+                "impact/batch-job",
+                "Change impacts a Spring Batch job",
+                format!("change impacts batch job {}", name),
+            ),
+            NodeType::Resource { category, name } => (
+                "impact/resource",
+                "Change impacts a custom resource",
+                format!("change impacts {} {}", category, name),
+            ),
+        };
+        Some(SarifRule { id, description, message })
+    }
+}
+
+impl EdgeRenderHints for EdgeType {
+    fn type_tag(&self) -> &'static str {
+        match self {
+            EdgeType::MethodCall => "method_call",
+            EdgeType::HttpCall => "http_call",
+            EdgeType::KafkaProduceConsume => "kafka",
+            EdgeType::DatabaseReadWrite => "database",
+            EdgeType::RedisReadWrite => "redis",
+            EdgeType::RpcCall => "rpc_call",
+            EdgeType::ThriftCall => "thrift_call",
+            EdgeType::GraphQLResolve => "graphql",
+            EdgeType::BatchLaunch => "batch",
+            EdgeType::ResourceAccess => "resource",
+        }
+    }
+}
+
 /// 影响边
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImpactEdge {
@@ -170,6 +647,38 @@ pub struct ImpactEdge {
     pub edge_type: EdgeType,
     /// 边方向
     pub direction: Direction,
+    /// 方法调用边在调用方源码中的调用行号
+    pub source_line: Option<u32>,
+    /// 控制该调用的特性开关名，调用不受开关控制时为 `None`
+    pub conditional: Option<String>,
+    /// 边的风险等级，只有数据库读写边有值
+    #[serde(default)]
+    pub risk: Option<Risk>,
+}
+
+/// 无法推导所属模块时使用的模块名
+pub const UNKNOWN_MODULE: &str = "(unknown)";
+
+/// 单个模块（服务）的影响汇总
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModuleImpact {
+    /// 模块名，即方法文件路径的第一级目录名
+    pub module: String,
+    /// 受影响的方法数
+    pub methods: usize,
+    /// 受影响的资源数，按资源类型（`http`、`kafka`、`database` 等）统计
+    pub resources: BTreeMap<String, usize>,
+}
+
+/// 影响图的结构指标
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphMetrics {
+    /// 弱连通分量数（忽略边方向后互不相连的子图数量）
+    pub components: usize,
+    /// 平均度数（每个节点的入边与出边之和的平均值）
+    pub average_degree: f64,
+    /// 最大深度：从根节点（没有入边的节点）出发的最长调用链的边数
+    pub max_depth: usize,
 }
 
 /// 影响图（使用 petgraph 的 DiGraph 实现）
@@ -179,6 +688,8 @@ pub struct ImpactGraph {
     graph: DiGraph<ImpactNode, ImpactEdge>,
     /// 节点 ID 到 NodeIndex 的映射
     node_map: HashMap<String, NodeIndex>,
+    /// 种子节点 ID（追溯起点，即变更方法），用于计算影响半径
+    seeds: Vec<String>,
 }
 
 impl Default for ImpactGraph {
@@ -193,9 +704,18 @@ impl ImpactGraph {
         Self {
             graph: DiGraph::new(),
             node_map: HashMap::new(),
+            seeds: Vec::new(),
         }
     }
     
+    /// 记录追溯起点（种子节点）
+    /// 
+    /// # Arguments
+    /// * `seed_ids` - 种子节点 ID 列表
+    pub fn set_seeds(&mut self, seed_ids: &[String]) {
+        self.seeds = seed_ids.to_vec();
+    }
+    
     /// 添加节点
     /// 
     /// # Arguments
@@ -225,6 +745,25 @@ impl ImpactGraph {
     /// * `edge_type` - 边类型
     /// * `direction` - 边方向
     pub fn add_edge(&mut self, from: &str, to: &str, edge_type: EdgeType, direction: Direction) {
+        self.add_edge_with_line(from, to, edge_type, direction, None);
+    }
+    
+    /// 添加带调用行号的边
+    /// 
+    /// # Arguments
+    /// * `from` - 起始节点 ID
+    /// * `to` - 目标节点 ID
+    /// * `edge_type` - 边类型
+    /// * `direction` - 边方向
+    /// * `source_line` - 调用发生在调用方源码中的行号
+    pub fn add_edge_with_line(
+        &mut self,
+        from: &str,
+        to: &str,
+        edge_type: EdgeType,
+        direction: Direction,
+        source_line: Option<u32>,
+    ) {
         // 获取节点索引
         let from_index = match self.node_map.get(from) {
             Some(&index) => index,
@@ -242,17 +781,111 @@ impl ImpactGraph {
             to: to.to_string(),
             edge_type,
             direction,
+            source_line,
+            conditional: None,
+            risk: None,
         };
         
         // 添加边到图中
         self.graph.add_edge(from_index, to_index, edge);
     }
     
+    /// 复制另一个图中的边，保留其调用行号和特性开关，端点不存在时跳过
+    fn copy_edge(&mut self, edge: &ImpactEdge) {
+        if let (Some(&from_index), Some(&to_index)) = (self.node_map.get(&edge.from), self.node_map.get(&edge.to)) {
+            self.graph.add_edge(from_index, to_index, edge.clone());
+        }
+    }
+    
+    /// 合并另一个图的节点和边
+    /// 
+    /// 已存在的节点保持不变；起点、终点、类型和方向都相同的边只保留一条
+    /// 
+    /// # Arguments
+    /// * `other` - 要合并的影响图
+    pub fn merge(&mut self, other: &ImpactGraph) {
+        let existing: HashSet<(String, String, EdgeType, Direction)> = self.edges()
+            .map(|edge| (edge.from.clone(), edge.to.clone(), edge.edge_type.clone(), edge.direction.clone()))
+            .collect();
+        
+        for node in other.nodes() {
+            self.add_node(node.clone());
+        }
+        for seed in &other.seeds {
+            if !self.seeds.contains(seed) {
+                self.seeds.push(seed.clone());
+            }
+        }
+        for edge in other.edges() {
+            let key = (edge.from.clone(), edge.to.clone(), edge.edge_type.clone(), edge.direction.clone());
+            if !existing.contains(&key) {
+                self.copy_edge(edge);
+            }
+        }
+    }
+    
+    /// 将两个节点之间的方法调用边标记为受特性开关控制
+    /// 
+    /// # Arguments
+    /// * `from` - 起始节点 ID
+    /// * `to` - 目标节点 ID
+    /// * `flag` - 特性开关名
+    pub fn mark_edge_conditional(&mut self, from: &str, to: &str, flag: &str) {
+        use petgraph::visit::EdgeRef;
+        
+        let (Some(&from_index), Some(&to_index)) = (self.node_map.get(from), self.node_map.get(to)) else {
+            return;
+        };
+        
+        let edge_indices: Vec<_> = self.graph.edges_connecting(from_index, to_index)
+            .filter(|edge| edge.weight().edge_type == EdgeType::MethodCall)
+            .map(|edge| edge.id())
+            .collect();
+        for edge_index in edge_indices {
+            self.graph[edge_index].conditional = Some(flag.to_string());
+        }
+    }
+    
+    /// 设置两个节点之间所有边的风险等级
+    /// 
+    /// # Arguments
+    /// * `from` - 起始节点 ID
+    /// * `to` - 目标节点 ID
+    /// * `risk` - 风险等级
+    pub fn mark_edge_risk(&mut self, from: &str, to: &str, risk: Risk) {
+        use petgraph::visit::EdgeRef;
+        
+        let (Some(&from_index), Some(&to_index)) = (self.node_map.get(from), self.node_map.get(to)) else {
+            return;
+        };
+        
+        let edge_indices: Vec<_> = self.graph.edges_connecting(from_index, to_index)
+            .map(|edge| edge.id())
+            .collect();
+        for edge_index in edge_indices {
+            self.graph[edge_index].risk = Some(risk);
+        }
+    }
+    
     /// 获取所有节点
     pub fn nodes(&self) -> impl Iterator<Item = &ImpactNode> {
         self.graph.node_weights()
     }
     
+    /// 获取所有节点的可变引用
+    pub fn nodes_mut(&mut self) -> impl Iterator<Item = &mut ImpactNode> {
+        self.graph.node_weights_mut()
+    }
+    
+    /// 按指定方式重新生成所有方法节点的标签，节点 ID 不变
+    pub fn set_label_style(&mut self, style: LabelStyle) {
+        for node in self.graph.node_weights_mut() {
+            if let NodeType::Method { qualified_name } = &node.node_type {
+                node.metadata.label = style.method_label(qualified_name);
+            }
+        }
+    }
+    
     /// 获取所有边
     pub fn edges(&self) -> impl Iterator<Item = &ImpactEdge> {
         self.graph.edge_weights()
@@ -263,6 +896,154 @@ impl ImpactGraph {
         self.graph.node_count()
     }
     
+    /// 计算影响半径：从种子方法可达的不同方法节点数量
+    /// 
+    /// 忽略边方向，从种子节点出发遍历图，统计经过的方法节点（包括种子本身）；
+    /// 过滤后与种子断开的节点不计入。与 `node_count` 不同，不统计
+    /// HTTP/Kafka/数据库/Redis/RPC 等基础设施节点。
+    /// 没有记录种子的图（如从 JSON 加载或基线差异图）统计全部方法节点
+    pub fn blast_radius(&self) -> usize {
+        let is_method = |index: &NodeIndex| matches!(self.graph[*index].node_type, NodeType::Method { .. });
+        if self.seeds.is_empty() {
+            return self.graph.node_indices().filter(is_method).count();
+        }
+        
+        self.reachable_from_seeds().iter().filter(|index| is_method(index)).count()
+    }
+    
+    /// 忽略边方向，收集从种子节点可达的节点（包括种子本身）
+    fn reachable_from_seeds(&self) -> HashSet<NodeIndex> {
+        let mut visited = HashSet::new();
+        let mut stack: Vec<NodeIndex> = self.seeds.iter()
+            .filter_map(|id| self.node_map.get(id).copied())
+            .collect();
+        while let Some(index) = stack.pop() {
+            if visited.insert(index) {
+                stack.extend(self.graph.neighbors_undirected(index));
+            }
+        }
+        visited
+    }
+    
+    /// 移除与种子节点断开的节点及其边，没有记录种子时原样返回
+    fn retain_reachable_from_seeds(self) -> ImpactGraph {
+        if self.seeds.is_empty() {
+            return self;
+        }
+        
+        let reachable = self.reachable_from_seeds();
+        let mut pruned = ImpactGraph::new();
+        for index in self.graph.node_indices().filter(|index| reachable.contains(index)) {
+            pruned.add_node(self.graph[index].clone());
+        }
+        for edge in self.graph.edge_weights() {
+            pruned.copy_edge(edge);
+        }
+        pruned.set_seeds(&self.seeds);
+        
+        pruned
+    }
+    
+    /// 按模块汇总受影响的方法和资源
+    /// 
+    /// 方法按 `file` 属性推导所属模块（与 `ImpactTracer::service_of` 相同，取第一级目录名）；
+    /// 资源节点计入与其直接相连的方法所在的每个模块，没有相连方法时计入 `(unknown)`。
+    /// 结果按模块名排序
+    pub fn module_rollup(&self) -> Vec<ModuleImpact> {
+        let module_of = |index: NodeIndex| -> Option<String> {
+            let node = &self.graph[index];
+            if !matches!(node.node_type, NodeType::Method { .. }) {
+                return None;
+            }
+            Some(node.metadata.properties.get("file")
+                .and_then(|file| service_from_path(Path::new(file)))
+                .unwrap_or_else(|| UNKNOWN_MODULE.to_string()))
+        };
+        
+        let mut modules: BTreeMap<String, ModuleImpact> = BTreeMap::new();
+        let empty = |module: &String| ModuleImpact {
+            module: module.clone(),
+            methods: 0,
+            resources: BTreeMap::new(),
+        };
+        
+        for index in self.graph.node_indices() {
+            let node_type = &self.graph[index].node_type;
+            match node_type {
+                NodeType::Method { .. } => {
+                    if let Some(module) = module_of(index) {
+                        modules.entry(module).or_insert_with_key(empty).methods += 1;
+                    }
+                }
+                NodeType::BridgeSummary { .. } => {}
+                _ => {
+                    let mut owners: Vec<String> = self.graph.neighbors_undirected(index)
+                        .filter_map(module_of)
+                        .collect();
+                    owners.sort();
+                    owners.dedup();
+                    if owners.is_empty() {
+                        owners.push(UNKNOWN_MODULE.to_string());
+                    }
+                    for module in owners {
+                        let impact = modules.entry(module).or_insert_with_key(empty);
+                        *impact.resources.entry(node_type.type_tag().to_string()).or_default() += 1;
+                    }
+                }
+            }
+        }
+        
+        modules.into_values().collect()
+    }
+    
+    /// 计算图的结构指标
+    /// 
+    /// 最大深度为从根节点出发的最长路径的边数，按拓扑序逐个节点取前驱深度加一的最大值；
+    /// 环上的节点及只能经过环到达的节点不计入
+    pub fn metrics(&self) -> GraphMetrics {
+        use petgraph::Direction as EdgeDirection;
+        use petgraph::visit::EdgeRef;
+        use std::collections::VecDeque;
+        
+        let node_count = self.graph.node_count();
+        let average_degree = if node_count == 0 {
+            0.0
+        } else {
+            2.0 * self.graph.edge_count() as f64 / node_count as f64
+        };
+        
+        // 按入度为 0 的顺序（Kahn 拓扑排序）处理节点，处理时所有前驱的深度都已确定
+        let mut in_degrees: HashMap<NodeIndex, usize> = self.graph.node_indices()
+            .map(|index| (index, self.graph.edges_directed(index, EdgeDirection::Incoming).count()))
+            .collect();
+        let mut depths: HashMap<NodeIndex, usize> = HashMap::new();
+        let mut queue: VecDeque<NodeIndex> = in_degrees.iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(index, _)| *index)
+            .collect();
+        let mut max_depth = 0;
+        while let Some(index) = queue.pop_front() {
+            let depth = depths.get(&index).copied().unwrap_or_default();
+            max_depth = max_depth.max(depth);
+            for edge in self.graph.edges_directed(index, EdgeDirection::Outgoing) {
+                let next = edge.target();
+                let next_depth = depths.entry(next).or_default();
+                *next_depth = (*next_depth).max(depth + 1);
+                let degree = in_degrees.get_mut(&next).expect("node has an in-degree entry");
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+        
+        GraphMetrics {
+            components: petgraph::algo::connected_components(&self.graph),
+            average_degree,
+            max_depth,
+        }
+    }
+    
     /// 获取边数量
     pub fn edge_count(&self) -> usize {
         self.graph.edge_count()
@@ -279,6 +1060,12 @@ impl ImpactGraph {
             .and_then(|&index| self.graph.node_weight(index))
     }
     
+    /// 获取节点的可变引用
+    pub fn get_node_mut(&mut self, node_id: &str) -> Option<&mut ImpactNode> {
+        self.node_map.get(node_id)
+            .and_then(|&index| self.graph.node_weight_mut(index))
+    }
+    
     /// 获取底层 petgraph DiGraph 的引用
     pub fn graph(&self) -> &DiGraph<ImpactNode, ImpactEdge> {
         &self.graph
@@ -290,47 +1077,46 @@ impl ImpactGraph {
     /// * `String` - DOT 格式的图描述
     pub fn to_dot(&self) -> String {
         use petgraph::dot::{Dot, Config};
+        use petgraph::visit::EdgeRef;
         
-        // 使用 petgraph 的 Dot 格式化器
-        let dot = Dot::with_attr_getters(
+        // 使用 petgraph 的 Dot 格式化器（边属性引用节点数据，格式化需在同一表达式内完成）
+        format!("{:?}", Dot::with_attr_getters(
             &self.graph,
             &[Config::EdgeNoLabel, Config::NodeNoLabel],
             &|_, edge| {
                 let edge_data = edge.weight();
-                let edge_type_str = match edge_data.edge_type {
-                    EdgeType::MethodCall => "method_call",
-                    EdgeType::HttpCall => "http_call",
-                    EdgeType::KafkaProduceConsume => "kafka",
-                    EdgeType::DatabaseReadWrite => "database",
-                    EdgeType::RedisReadWrite => "redis",
-                };
+                let edge_type_str = edge_data.edge_type.type_tag();
                 let direction_str = match edge_data.direction {
                     Direction::Upstream => "upstream",
                     Direction::Downstream => "downstream",
                 };
-                format!("label=\"{}\" dir=\"{}\"", edge_type_str, direction_str)
+                let mut attrs = match &edge_data.conditional {
+                    // 受特性开关控制的调用以虚线表示
+                    Some(flag) => format!("label=\"{}\" dir=\"{}\" style=\"dashed\" conditional=\"{}\"", edge_type_str, direction_str, flag),
+                    None => format!("label=\"{}\" dir=\"{}\"", edge_type_str, direction_str),
+                };
+                // 指向带容错注解方法的边以橙色表示
+                if let Some(resilience) = self.graph[edge.target()].metadata.properties.get("resilience") {
+                    attrs.push_str(&format!(" color=\"orange\" resilience=\"{}\"", resilience));
+                }
+                // 高风险的数据库写入边以红色粗线表示
+                match edge_data.risk {
+                    Some(Risk::High) => attrs.push_str(" color=\"red\" style=\"bold\" risk=\"high\""),
+                    Some(Risk::Low) => attrs.push_str(" risk=\"low\""),
+                    None => {}
+                }
+                attrs
             },
             &|_, (_, node)| {
-                let node_type_str = match &node.node_type {
-                    NodeType::Method { .. } => "method",
-                    NodeType::HttpEndpoint { .. } => "http",
-                    NodeType::KafkaTopic { .. } => "kafka",
-                    NodeType::DatabaseTable { .. } => "database",
-                    NodeType::RedisPrefix { .. } => "redis",
-                };
-                let shape = match &node.node_type {
-                    NodeType::Method { .. } => "box",
-                    NodeType::HttpEndpoint { .. } => "ellipse",
-                    NodeType::KafkaTopic { .. } => "diamond",
-                    NodeType::DatabaseTable { .. } => "cylinder",
-                    NodeType::RedisPrefix { .. } => "hexagon",
-                };
-                format!("label=\"{}\" shape=\"{}\" type=\"{}\"", 
-                    node.metadata.label, shape, node_type_str)
+                let mut attrs = format!("label=\"{}\" shape=\"{}\" type=\"{}\"", 
+                    node.metadata.label, node.node_type.dot_shape(), node.node_type.type_tag());
+                // 按改动量排序后的变更方法排名
+                if let Some(rank) = node.metadata.properties.get("churn_rank") {
+                    attrs.push_str(&format!(" churn_rank=\"{}\"", rank));
+                }
+                attrs
             },
-        );
-        
-        format!("{:?}", dot)
+        ))
     }
     
     /// 输出为 JSON 格式
@@ -343,57 +1129,12 @@ impl ImpactGraph {
         
         // 收集所有节点
         let nodes: Vec<_> = self.graph.node_weights()
-            .map(|node| {
-                json!({
-                    "id": node.id,
-                    "type": match &node.node_type {
-                        NodeType::Method { qualified_name } => json!({
-                            "kind": "method",
-                            "qualified_name": qualified_name
-                        }),
-                        NodeType::HttpEndpoint { path, method } => json!({
-                            "kind": "http_endpoint",
-                            "path": path,
-                            "method": method
-                        }),
-                        NodeType::KafkaTopic { name } => json!({
-                            "kind": "kafka_topic",
-                            "name": name
-                        }),
-                        NodeType::DatabaseTable { name } => json!({
-                            "kind": "database_table",
-                            "name": name
-                        }),
-                        NodeType::RedisPrefix { prefix } => json!({
-                            "kind": "redis_prefix",
-                            "prefix": prefix
-                        }),
-                    },
-                    "label": node.metadata.label,
-                    "properties": node.metadata.properties
-                })
-            })
+            .map(node_to_json)
             .collect();
         
         // 收集所有边
         let edges: Vec<_> = self.graph.edge_weights()
-            .map(|edge| {
-                json!({
-                    "from": edge.from,
-                    "to": edge.to,
-                    "type": match edge.edge_type {
-                        EdgeType::MethodCall => "method_call",
-                        EdgeType::HttpCall => "http_call",
-                        EdgeType::KafkaProduceConsume => "kafka_produce_consume",
-                        EdgeType::DatabaseReadWrite => "database_read_write",
-                        EdgeType::RedisReadWrite => "redis_read_write",
-                    },
-                    "direction": match edge.direction {
-                        Direction::Upstream => "upstream",
-                        Direction::Downstream => "downstream",
-                    }
-                })
-            })
+            .map(edge_to_json)
             .collect();
         
         // 构建完整的 JSON 对象
@@ -401,12 +1142,432 @@ impl ImpactGraph {
             "nodes": nodes,
             "edges": edges,
             "node_count": self.node_count(),
-            "edge_count": self.edge_count()
+            "edge_count": self.edge_count(),
+            "blast_radius": self.blast_radius(),
+            "by_module": self.module_rollup(),
+            "metrics": self.metrics()
         });
         
         serde_json::to_string_pretty(&graph_json)
     }
     
+    /// 以流式方式输出 JSON 格式
+    /// 
+    /// 与 `to_json` 输出相同的结构，但逐个序列化节点和边并直接写入 `writer`，
+    /// 不会在内存中构建完整的 JSON 字符串，适合超大图
+    /// 
+    /// # Arguments
+    /// * `writer` - 输出目标
+    /// 
+    /// # Returns
+    /// * `Ok(())` - 写入成功
+    /// * `Err(serde_json::Error)` - 序列化或 IO 错误
+    pub fn write_json<W: std::io::Write>(&self, writer: W) -> Result<(), serde_json::Error> {
+        use serde::Serializer as _;
+        
+        let mut serializer = serde_json::Serializer::pretty(writer);
+        serializer.collect_map([
+            ("nodes", JsonField::Nodes(self)),
+            ("edges", JsonField::Edges(self)),
+            ("node_count", JsonField::Count(self.node_count())),
+            ("edge_count", JsonField::Count(self.edge_count())),
+            ("blast_radius", JsonField::Count(self.blast_radius())),
+            ("by_module", JsonField::ByModule(self)),
+            ("metrics", JsonField::Metrics(self)),
+        ])
+    }
+    
+    /// 以 JSON Lines 事件流输出
+    /// 
+    /// 每行一个 JSON 对象：先是所有节点（`"event": "node"`），然后是所有边（`"event": "edge"`），
+    /// 其余字段与 `to_json` 中的节点和边相同，便于其他工具逐行消费
+    /// 
+    /// # Arguments
+    /// * `writer` - 输出目标
+    /// 
+    /// # Returns
+    /// * `Ok(())` - 写入成功
+    /// * `Err(serde_json::Error)` - 序列化或 IO 错误
+    pub fn write_jsonl<W: std::io::Write>(&self, mut writer: W) -> Result<(), serde_json::Error> {
+        let nodes = self.graph.node_weights().map(|node| ("node", node_to_json(node)));
+        let edges = self.graph.edge_weights().map(|edge| ("edge", edge_to_json(edge)));
+        
+        for (event, mut value) in nodes.chain(edges) {
+            value["event"] = serde_json::json!(event);
+            serde_json::to_writer(&mut writer, &value)?;
+            writer.write_all(b"\n").map_err(serde_json::Error::io)?;
+        }
+        Ok(())
+    }
+    
+    /// 输出为 SARIF 2.1.0 格式（用于 GitHub code scanning）
+    /// 
+    /// 每个受影响的资源（HTTP 端点、Kafka Topic、数据库表等非方法节点）生成一条结果，
+    /// 位置取自图中与该资源相连的种子方法的源码位置（`file`/`line_start`/`line_end` 属性）；
+    /// 规则取自节点的 `NodeRenderHints::sarif_rule`，`tool.driver.rules` 声明结果中出现的全部规则
+    /// 
+    /// # Arguments
+    /// * `seed_methods` - 种子方法（变更方法）的全限定名列表
+    /// 
+    /// # Returns
+    /// * `Ok(String)` - SARIF 格式的 JSON 文档
+    /// * `Err(serde_json::Error)` - 序列化错误
+    pub fn to_sarif(&self, seed_methods: &[String]) -> Result<String, serde_json::Error> {
+        use serde_json::json;
+        
+        // 资源节点 -> 与其相连的种子方法节点
+        let mut resource_seeds: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+        for seed in seed_methods {
+            let Some(seed_index) = self.get_node_index(&format!("method:{}", seed)) else {
+                continue;
+            };
+            let mut visited = HashSet::from([seed_index]);
+            let mut stack = vec![seed_index];
+            while let Some(index) = stack.pop() {
+                for neighbor in self.graph.neighbors_undirected(index) {
+                    if visited.insert(neighbor) {
+                        stack.push(neighbor);
+                    }
+                }
+                if !matches!(self.graph[index].node_type, NodeType::Method { .. }) {
+                    resource_seeds.entry(index).or_default().push(seed_index);
+                }
+            }
+        }
+        
+        // 按原图顺序输出，保证结果稳定；规则按首次出现的顺序声明
+        let mut rules = Vec::new();
+        let mut rule_ids = HashSet::new();
+        let mut results = Vec::new();
+        for index in self.graph.node_indices() {
+            let Some(seeds) = resource_seeds.get(&index) else {
+                continue;
+            };
+            let Some(rule) = self.graph[index].node_type.sarif_rule() else {
+                continue;
+            };
+            if rule_ids.insert(rule.id) {
+                rules.push(json!({
+                    "id": rule.id,
+                    "shortDescription": { "text": rule.description }
+                }));
+            }
+            let locations: Vec<_> = seeds.iter()
+                .filter_map(|&seed| sarif_location(&self.graph[seed]))
+                .collect();
+            results.push(json!({
+                "ruleId": rule.id,
+                "level": "note",
+                "message": { "text": rule.message },
+                "locations": locations
+            }));
+        }
+        
+        let sarif = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "code-impact-analyzer",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules
+                    }
+                },
+                "results": results
+            }]
+        });
+        
+        serde_json::to_string_pretty(&sarif)
+    }
+    
+    /// 按边方向过滤影响图
+    /// 
+    /// 只保留方向与 `direction` 一致的边，并移除过滤后变为孤立的节点，
+    /// 种子节点（变更方法）即使孤立也会保留
+    /// 
+    /// # Arguments
+    /// * `direction` - 需要保留的边方向
+    /// * `seed_ids` - 种子节点 ID 列表
+    /// 
+    /// # Returns
+    /// * `ImpactGraph` - 过滤后的新影响图
+    pub fn filter_by_direction(&self, direction: &Direction, seed_ids: &[String]) -> ImpactGraph {
+        let kept_edges: Vec<&ImpactEdge> = self.graph.edge_weights()
+            .filter(|edge| edge.direction == *direction)
+            .collect();
+        
+        // 收集需要保留的节点：种子节点 + 保留边的端点
+        let mut kept_node_ids: HashSet<&str> = seed_ids.iter()
+            .map(|id| id.as_str())
+            .collect();
+        for edge in &kept_edges {
+            kept_node_ids.insert(edge.from.as_str());
+            kept_node_ids.insert(edge.to.as_str());
+        }
+        
+        let mut filtered = ImpactGraph::new();
+        
+        // 按原图顺序添加节点，保证输出稳定
+        for node in self.graph.node_weights() {
+            if kept_node_ids.contains(node.id.as_str()) {
+                filtered.add_node(node.clone());
+            }
+        }
+        
+        for edge in kept_edges {
+            filtered.copy_edge(edge);
+        }
+        filtered.set_seeds(seed_ids);
+        
+        filtered
+    }
+    
+    /// 按风险等级过滤影响图
+    /// 
+    /// 移除风险等级低于 `min_risk` 的边（没有风险等级的边保留），并移除过滤后与种子节点
+    /// 断开的节点（忽略边方向判断连通性），种子节点（变更方法）即使孤立也会保留
+    /// 
+    /// # Arguments
+    /// * `min_risk` - 最低风险等级
+    /// * `seed_ids` - 种子节点 ID 列表
+    /// 
+    /// # Returns
+    /// * `ImpactGraph` - 过滤后的新影响图
+    pub fn filter_by_risk(&self, min_risk: Risk, seed_ids: &[String]) -> ImpactGraph {
+        let kept_edges: Vec<&ImpactEdge> = self.graph.edge_weights()
+            .filter(|edge| edge.risk.is_none_or(|risk| risk >= min_risk))
+            .collect();
+        
+        let mut kept_node_ids: HashSet<&str> = seed_ids.iter()
+            .map(|id| id.as_str())
+            .collect();
+        for edge in &kept_edges {
+            kept_node_ids.insert(edge.from.as_str());
+            kept_node_ids.insert(edge.to.as_str());
+        }
+        
+        let mut filtered = ImpactGraph::new();
+        for node in self.graph.node_weights() {
+            if kept_node_ids.contains(node.id.as_str()) {
+                filtered.add_node(node.clone());
+            }
+        }
+        for edge in kept_edges {
+            filtered.copy_edge(edge);
+        }
+        filtered.set_seeds(seed_ids);
+        
+        filtered.retain_reachable_from_seeds()
+    }
+    
+    /// 按方法过滤影响图
+    /// 
+    /// 移除不满足 `keep` 的方法节点（种子节点始终保留）及其相连的边，
+    /// 并移除因此与种子节点断开的节点（如只经由被移除方法连接到种子的调用方和 HTTP 端点）
+    /// 
+    /// # Arguments
+    /// * `keep` - 根据方法全限定名判断是否保留
+    /// * `seed_ids` - 种子节点 ID 列表
+    /// 
+    /// # Returns
+    /// * `ImpactGraph` - 过滤后的新影响图
+    pub fn filter_methods(&self, keep: impl Fn(&str) -> bool, seed_ids: &[String]) -> ImpactGraph {
+        let candidate_ids: HashSet<&str> = self.graph.node_weights()
+            .filter(|node| match &node.node_type {
+                NodeType::Method { qualified_name } => keep(qualified_name) || seed_ids.contains(&node.id),
+                _ => true,
+            })
+            .map(|node| node.id.as_str())
+            .collect();
+        let kept_edges: Vec<&ImpactEdge> = self.graph.edge_weights()
+            .filter(|edge| candidate_ids.contains(edge.from.as_str()) && candidate_ids.contains(edge.to.as_str()))
+            .collect();
+        
+        // 收集需要保留的节点：种子节点 + 保留边的端点
+        let mut kept_node_ids: HashSet<&str> = seed_ids.iter()
+            .map(|id| id.as_str())
+            .collect();
+        for edge in &kept_edges {
+            kept_node_ids.insert(edge.from.as_str());
+            kept_node_ids.insert(edge.to.as_str());
+        }
+        
+        let mut filtered = ImpactGraph::new();
+        for node in self.graph.node_weights() {
+            if kept_node_ids.contains(node.id.as_str()) {
+                filtered.add_node(node.clone());
+            }
+        }
+        for edge in kept_edges {
+            filtered.copy_edge(edge);
+        }
+        filtered.set_seeds(seed_ids);
+        
+        filtered.retain_reachable_from_seeds()
+    }
+    
+    /// 收缩满足 `contract` 的方法节点
+    /// 
+    /// 被收缩的方法从图中移除，其调用方直接连接到它调用的方法，新边沿用调用方
+    /// 原调用边的类型、方向、调用行号和特性开关。只收缩所有相连边都是方法调用边、
+    /// 且既有调用方又有被调用方的方法，种子节点始终保留；连续的多个委托会一并收缩
+    /// 
+    /// # Arguments
+    /// * `contract` - 根据方法全限定名判断是否收缩
+    /// * `seed_ids` - 种子节点 ID 列表
+    /// 
+    /// # Returns
+    /// * `ImpactGraph` - 收缩后的新影响图
+    pub fn contract_methods(&self, contract: impl Fn(&str) -> bool, seed_ids: &[String]) -> ImpactGraph {
+        use petgraph::Direction as EdgeDirection;
+        use petgraph::visit::EdgeRef;
+        
+        let contracted: HashSet<NodeIndex> = self.graph.node_indices()
+            .filter(|&index| {
+                let node = &self.graph[index];
+                let NodeType::Method { qualified_name } = &node.node_type else {
+                    return false;
+                };
+                let mut edges = self.graph.edges_directed(index, EdgeDirection::Incoming)
+                    .chain(self.graph.edges_directed(index, EdgeDirection::Outgoing));
+                contract(qualified_name)
+                    && !seed_ids.contains(&node.id)
+                    && self.graph.edges_directed(index, EdgeDirection::Incoming).next().is_some()
+                    && self.graph.edges_directed(index, EdgeDirection::Outgoing).next().is_some()
+                    && edges.all(|edge| edge.weight().edge_type == EdgeType::MethodCall)
+            })
+            .collect();
+        
+        let mut result = ImpactGraph::new();
+        for index in self.graph.node_indices().filter(|index| !contracted.contains(index)) {
+            result.add_node(self.graph[index].clone());
+        }
+        
+        for edge in self.graph.edge_references() {
+            if contracted.contains(&edge.source()) {
+                continue;
+            }
+            if !contracted.contains(&edge.target()) {
+                result.copy_edge(edge.weight());
+                continue;
+            }
+            
+            // 调用方 -> 委托方法：沿委托链找到第一个未被收缩的被调用方
+            let mut visited = HashSet::new();
+            let mut stack = vec![edge.target()];
+            while let Some(current) = stack.pop() {
+                if !visited.insert(current) {
+                    continue;
+                }
+                for next in self.graph.neighbors_directed(current, EdgeDirection::Outgoing) {
+                    if contracted.contains(&next) {
+                        stack.push(next);
+                        continue;
+                    }
+                    let to = &self.graph[next].id;
+                    if *to == edge.weight().from || result.has_edge(&edge.weight().from, to) {
+                        continue;
+                    }
+                    result.copy_edge(&ImpactEdge {
+                        to: to.clone(),
+                        ..edge.weight().clone()
+                    });
+                }
+            }
+        }
+        result.set_seeds(seed_ids);
+        
+        result
+    }
+    
+    /// 判断两个节点之间是否已有边
+    fn has_edge(&self, from: &str, to: &str) -> bool {
+        match (self.node_map.get(from), self.node_map.get(to)) {
+            (Some(&from_index), Some(&to_index)) => self.graph.find_edge(from_index, to_index).is_some(),
+            _ => false,
+        }
+    }
+    
+    /// 从 `to_json`/`write_json` 输出的 JSON 重新加载影响图
+    /// 
+    /// # Arguments
+    /// * `json` - JSON 格式的图描述
+    /// 
+    /// # Returns
+    /// * `Ok(ImpactGraph)` - 加载的影响图
+    /// * `Err(serde_json::Error)` - JSON 格式错误
+    pub fn from_json(json: &str) -> Result<ImpactGraph, serde_json::Error> {
+        let data: JsonGraph = serde_json::from_str(json)?;
+        
+        let mut graph = ImpactGraph::new();
+        for node in data.nodes {
+            graph.add_node(ImpactNode {
+                id: node.id,
+                node_type: node.node_type,
+                metadata: NodeMetadata {
+                    label: node.label,
+                    properties: node.properties,
+                },
+            });
+        }
+        for edge in data.edges {
+            graph.add_edge_with_line(&edge.from, &edge.to, edge.edge_type, edge.direction, edge.source_line);
+            if let Some(flag) = &edge.conditional {
+                graph.mark_edge_conditional(&edge.from, &edge.to, flag);
+            }
+            if let Some(risk) = edge.risk {
+                graph.mark_edge_risk(&edge.from, &edge.to, risk);
+            }
+        }
+        
+        Ok(graph)
+    }
+    
+    /// 计算相对于基线图新增的影响
+    /// 
+    /// 保留基线中不存在的边（按起点、终点、类型和方向比较）和节点；
+    /// 新增边连接的已有节点也会保留，保证输出的每条边都有对应的端点
+    /// 
+    /// # Arguments
+    /// * `baseline` - 基线影响图（如上次评审时的输出）
+    /// 
+    /// # Returns
+    /// * `ImpactGraph` - 只包含新增影响的图
+    pub fn difference(&self, baseline: &ImpactGraph) -> ImpactGraph {
+        let baseline_edges: HashSet<(&str, &str, &EdgeType, &Direction)> = baseline.edges()
+            .map(|edge| (edge.from.as_str(), edge.to.as_str(), &edge.edge_type, &edge.direction))
+            .collect();
+        
+        let new_edges: Vec<&ImpactEdge> = self.edges()
+            .filter(|edge| !baseline_edges.contains(&(edge.from.as_str(), edge.to.as_str(), &edge.edge_type, &edge.direction)))
+            .collect();
+        
+        let mut kept_node_ids: HashSet<&str> = self.nodes()
+            .filter(|node| baseline.get_node(&node.id).is_none())
+            .map(|node| node.id.as_str())
+            .collect();
+        for edge in &new_edges {
+            kept_node_ids.insert(edge.from.as_str());
+            kept_node_ids.insert(edge.to.as_str());
+        }
+        
+        let mut delta = ImpactGraph::new();
+        
+        // 按原图顺序添加节点，保证输出稳定
+        for node in self.nodes() {
+            if kept_node_ids.contains(node.id.as_str()) {
+                delta.add_node(node.clone());
+            }
+        }
+        
+        for edge in new_edges {
+            delta.copy_edge(edge);
+        }
+        
+        delta
+    }
+    
     /// 检测图中的循环依赖
     /// 
     /// # Returns
@@ -436,18 +1597,381 @@ impl ImpactGraph {
     }
 }
 
+/// 根据方法节点的源码位置属性构建 SARIF 位置，缺少文件路径时返回 `None`
+fn sarif_location(node: &ImpactNode) -> Option<serde_json::Value> {
+    use serde_json::json;
+    
+    let properties = &node.metadata.properties;
+    let uri = properties.get("file")?.replace('\\', "/");
+    let mut region = serde_json::Map::new();
+    if let Some(line) = properties.get("line_start").and_then(|l| l.parse::<usize>().ok()) {
+        region.insert("startLine".to_string(), json!(line));
+    }
+    if let Some(line) = properties.get("line_end").and_then(|l| l.parse::<usize>().ok()) {
+        region.insert("endLine".to_string(), json!(line));
+    }
+    
+    let mut physical_location = json!({ "artifactLocation": { "uri": uri } });
+    if !region.is_empty() {
+        physical_location["region"] = serde_json::Value::Object(region);
+    }
+    Some(json!({ "physicalLocation": physical_location }))
+}
+
+/// 将节点转换为 JSON 值
+fn node_to_json(node: &ImpactNode) -> serde_json::Value {
+    use serde_json::json;
+    
+    json!({
+        "id": node.id,
+        "type": node.node_type,
+        "label": node.metadata.label,
+        "properties": node.metadata.properties
+    })
+}
+
+/// 将边转换为 JSON 值
+/// 
+/// 方法调用边有调用行号时附加 `source_line` 字段
+fn edge_to_json(edge: &ImpactEdge) -> serde_json::Value {
+    use serde_json::json;
+    
+    let mut value = json!({
+        "from": edge.from,
+        "to": edge.to,
+        "type": edge.edge_type,
+        "direction": edge.direction
+    });
+    if let Some(line) = edge.source_line {
+        value["source_line"] = json!(line);
+    }
+    if let Some(flag) = &edge.conditional {
+        value["conditional"] = json!(flag);
+    }
+    if let Some(risk) = edge.risk {
+        value["risk"] = json!(risk);
+    }
+    value
+}
+
+/// 重新加载 JSON 输出时使用的图结构
+#[derive(Deserialize)]
+struct JsonGraph {
+    nodes: Vec<JsonNode>,
+    edges: Vec<JsonEdge>,
+}
+
+/// JSON 输出中的节点
+#[derive(Deserialize)]
+struct JsonNode {
+    id: String,
+    #[serde(rename = "type")]
+    node_type: NodeType,
+    label: String,
+    #[serde(default)]
+    properties: HashMap<String, String>,
+}
+
+/// JSON 输出中的边
+#[derive(Deserialize)]
+struct JsonEdge {
+    from: String,
+    to: String,
+    #[serde(rename = "type")]
+    edge_type: EdgeType,
+    direction: Direction,
+    #[serde(default)]
+    source_line: Option<u32>,
+    #[serde(default)]
+    conditional: Option<String>,
+    #[serde(default)]
+    risk: Option<Risk>,
+}
+
+/// 从相对工作空间根目录的文件路径推导所属服务（模块）名
+/// 
+/// 取第一级目录名；路径中没有目录时返回 `None`
+pub(crate) fn service_from_path(relative: &Path) -> Option<String> {
+    // 至少需要一级目录加文件名
+    let mut components = relative.components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        });
+    let service = components.next()?;
+    components.next()?;
+    Some(service.to_string())
+}
+
+/// 流式 JSON 输出的顶层字段
+/// 
+/// 节点和边在序列化时逐个转换，避免一次性物化全部 JSON 值
+enum JsonField<'g> {
+    Nodes(&'g ImpactGraph),
+    Edges(&'g ImpactGraph),
+    Count(usize),
+    ByModule(&'g ImpactGraph),
+    Metrics(&'g ImpactGraph),
+}
+
+impl Serialize for JsonField<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            JsonField::Nodes(graph) => {
+                serializer.collect_seq(graph.graph.node_weights().map(node_to_json))
+            }
+            JsonField::Edges(graph) => {
+                serializer.collect_seq(graph.graph.edge_weights().map(edge_to_json))
+            }
+            JsonField::Count(count) => serializer.serialize_u64(*count as u64),
+            JsonField::ByModule(graph) => serializer.collect_seq(graph.module_rollup()),
+            JsonField::Metrics(graph) => graph.metrics().serialize(serializer),
+        }
+    }
+}
+
+/// 追溯子图的磁盘缓存
+/// 
+/// 以（索引标识、工作空间根目录、追溯配置、种子方法）的 SHA-256 摘要为键，把单个种子方法
+/// 追溯出的子图以 JSON 保存在缓存目录中。同一索引上分析不同的 diff 时，已追溯过的种子方法
+/// 直接读取缓存，只有新的种子方法需要重新追溯。缓存项超过上限时删除最久未使用的项
+pub struct TraceCache {
+    /// 缓存目录
+    dir: PathBuf,
+    /// 最多保留的缓存项数量
+    max_entries: usize,
+    /// 从缓存读取的子图数量
+    hits: AtomicUsize,
+    /// 重新追溯并写入缓存的子图数量
+    misses: AtomicUsize,
+}
+
+impl TraceCache {
+    /// 默认最多保留的缓存项数量
+    pub const DEFAULT_MAX_ENTRIES: usize = 10_000;
+    
+    /// 创建追溯缓存，缓存目录在首次写入时创建
+    /// 
+    /// # Arguments
+    /// * `dir` - 缓存目录
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            max_entries: Self::DEFAULT_MAX_ENTRIES,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+    
+    /// 设置最多保留的缓存项数量，写入新项后超出的部分按最近使用时间淘汰
+    /// 
+    /// # Arguments
+    /// * `max_entries` - 缓存项数量上限
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+    
+    /// 从缓存读取的子图数量
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+    
+    /// 缓存未命中、重新追溯的子图数量
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+    
+    /// 计算缓存键对应的缓存文件路径
+    /// 
+    /// # Arguments
+    /// * `key` - 各组成部分以 `\0` 分隔的规范化文本，见 `ImpactTracer::trace_cache_key`
+    fn entry_path(&self, key: &str) -> PathBuf {
+        use sha2::{Digest, Sha256};
+        
+        let digest = Sha256::digest(key.as_bytes());
+        let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+        self.dir.join(format!("{}.json", hex))
+    }
+    
+    /// 读取缓存的子图，不存在或无法解析时返回 None；命中时刷新文件的修改时间，用于淘汰
+    fn load(&self, key: &str) -> Option<ImpactGraph> {
+        let path = self.entry_path(key);
+        let content = std::fs::read_to_string(&path).ok()?;
+        let graph = ImpactGraph::from_json(&content).ok()?;
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        if let Ok(file) = std::fs::File::options().write(true).open(&path) {
+            let _ = file.set_modified(std::time::SystemTime::now());
+        }
+        Some(graph)
+    }
+    
+    /// 写入子图，失败时只记录警告
+    fn store(&self, key: &str, graph: &ImpactGraph) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let path = self.entry_path(key);
+        let result = std::fs::create_dir_all(&self.dir)
+            .map_err(|e| e.to_string())
+            .and_then(|_| graph.to_json().map_err(|e| e.to_string()))
+            .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            log::warn!("Failed to write trace cache entry {}: {}", path.display(), e);
+        }
+    }
+    
+    /// 缓存项超过上限时删除最久未使用（修改时间最早）的项
+    fn evict(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut entries: Vec<(std::time::SystemTime, PathBuf)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .collect();
+        if entries.len() <= self.max_entries {
+            return;
+        }
+        entries.sort();
+        let excess = entries.len() - self.max_entries;
+        for (_, path) in entries.into_iter().take(excess) {
+            if let Err(e) = std::fs::remove_file(&path) {
+                log::warn!("Failed to evict trace cache entry {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
 /// 影响追溯器
 pub struct ImpactTracer<'a> {
     /// 代码索引引用
     index: &'a CodeIndex,
     /// 追溯配置
     config: TraceConfig,
+    /// 工作空间根目录，用于从文件路径推导方法所属服务
+    workspace_root: Option<PathBuf>,
+    /// 追溯子图缓存及当前索引的标识
+    trace_cache: Option<(&'a TraceCache, String)>,
+    /// 共享资源匹配器，内置 Kafka、数据库和 Redis 匹配器
+    resource_matchers: Vec<Arc<dyn ResourceMatcher>>,
+    /// 是否注册了自定义资源匹配器；自定义匹配器的行为无法纳入缓存键，注册后不使用追溯缓存
+    custom_resource_matchers: bool,
 }
 
 impl<'a> ImpactTracer<'a> {
     /// 创建新的影响追溯器
     pub fn new(index: &'a CodeIndex, config: TraceConfig) -> Self {
-        Self { index, config }
+        Self {
+            index,
+            config,
+            workspace_root: None,
+            trace_cache: None,
+            resource_matchers: vec![Arc::new(KafkaMatcher), Arc::new(DatabaseMatcher), Arc::new(RedisMatcher)],
+            custom_resource_matchers: false,
+        }
+    }
+    
+    /// 添加自定义资源匹配器
+    /// 
+    /// 自定义匹配器在内置的 Kafka、数据库和 Redis 匹配器之后执行；注册了自定义匹配器时不使用追溯缓存
+    /// 
+    /// # Arguments
+    /// * `matchers` - 自定义资源匹配器
+    pub fn with_resource_matchers(mut self, matchers: impl IntoIterator<Item = Arc<dyn ResourceMatcher>>) -> Self {
+        let builtin = self.resource_matchers.len();
+        self.resource_matchers.extend(matchers);
+        self.custom_resource_matchers |= self.resource_matchers.len() > builtin;
+        self
+    }
+    
+    /// 设置追溯子图缓存
+    /// 
+    /// 设置后每个种子方法单独追溯，子图按索引标识、工作空间根目录和追溯配置缓存，再合并为完整的影响图
+    /// 
+    /// # Arguments
+    /// * `cache` - 追溯缓存
+    /// * `index_key` - 当前索引的标识，应包含磁盘索引校验和以及加载后改变索引内容的所有设置，
+    ///   其中任何一项变化后旧的缓存项都不再命中
+    pub fn with_trace_cache(mut self, cache: &'a TraceCache, index_key: String) -> Self {
+        self.trace_cache = Some((cache, index_key));
+        self
+    }
+    
+    /// 种子方法子图的缓存键
+    fn trace_cache_key(&self, index_key: &str, seed: &str) -> String {
+        let workspace_root = self.workspace_root.as_deref()
+            .map(|root| root.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        ["trace-cache-v2", index_key, &workspace_root, &self.config.cache_key(), seed].join("\0")
+    }
+    
+    /// 设置工作空间根目录
+    /// 
+    /// 方法所属服务取其文件路径相对于该目录的第一级目录名；
+    /// 未设置时直接取文件路径的第一级目录名
+    pub fn with_workspace_root(mut self, workspace_root: PathBuf) -> Self {
+        self.workspace_root = Some(workspace_root);
+        self
+    }
+    
+    /// 将文件路径转换为相对工作空间根目录的路径，不在根目录下时原样返回
+    fn relative_path<'p>(&self, file_path: &'p Path) -> &'p Path {
+        self.workspace_root.as_deref()
+            .and_then(|root| file_path.strip_prefix(root).ok())
+            .unwrap_or(file_path)
+    }
+    
+    /// 推导方法所属的服务名
+    /// 
+    /// # Arguments
+    /// * `method` - 方法全限定名
+    /// 
+    /// # Returns
+    /// * `Some(service)` - 方法文件路径的第一级目录名
+    /// * `None` - 方法不在索引中或路径中没有目录
+    pub fn service_of(&self, method: &str) -> Option<String> {
+        service_from_path(self.relative_path(&self.index.find_method(method)?.file_path))
+    }
+    
+    /// 判断方法是否属于允许追溯的服务
+    fn is_service_allowed(&self, method: &str) -> bool {
+        if self.config.allowed_services.is_empty() {
+            return true;
+        }
+        
+        self.service_of(method)
+            .map(|service| self.config.allowed_services.contains(&service))
+            .unwrap_or(false)
+    }
+    
+    /// 从端点的提供者/消费者中选出需要展开的方法
+    /// 
+    /// 过滤掉已访问和不允许追溯的方法后按方法名排序，保证截断结果稳定；
+    /// 超过 `max_bridge_fanout` 时只保留前 N 个
+    /// 
+    /// # Returns
+    /// (需要展开的方法, 被省略的方法数量)
+    fn select_bridge_methods<'b>(
+        &self,
+        candidates: Vec<&'b str>,
+        visited: &HashSet<String>,
+    ) -> (Vec<&'b str>, usize) {
+        let mut selected: Vec<&str> = candidates
+            .into_iter()
+            .filter(|method| !visited.contains(*method) && self.is_service_allowed(method))
+            .collect();
+        selected.sort_unstable();
+        selected.dedup();
+        
+        let omitted = match self.config.max_bridge_fanout {
+            Some(max_fanout) if selected.len() > max_fanout => {
+                let omitted = selected.len() - max_fanout;
+                selected.truncate(max_fanout);
+                omitted
+            }
+            _ => 0,
+        };
+        (selected, omitted)
     }
     
     /// 追溯影响
@@ -462,27 +1986,99 @@ impl<'a> ImpactTracer<'a> {
         let mut graph = ImpactGraph::new();
         let mut visited = HashSet::new();
         
+        let trace_cache = self.trace_cache.as_ref().filter(|_| !self.custom_resource_matchers);
         for method in changed_methods {
-            // 添加变更方法节点
-            let node = ImpactNode::method(method.clone());
-            graph.add_node(node);
-            
-            // 追溯上游
-            if self.config.trace_upstream {
-                self.trace_method_upstream(method, 0, &mut visited, &mut graph);
-            }
-            
-            // 追溯下游
-            if self.config.trace_downstream {
-                // 为下游追溯创建新的 visited 集合
-                let mut downstream_visited = HashSet::new();
-                self.trace_method_downstream(method, 0, &mut downstream_visited, &mut graph);
+            match trace_cache {
+                Some((cache, index_key)) => {
+                    // 每个种子方法的子图独立追溯，便于按种子缓存和复用
+                    let key = self.trace_cache_key(index_key, method);
+                    let subgraph = match cache.load(&key) {
+                        Some(subgraph) => subgraph,
+                        None => {
+                            let mut subgraph = ImpactGraph::new();
+                            self.trace_seed(method, &mut HashSet::new(), &mut subgraph);
+                            cache.store(&key, &subgraph);
+                            subgraph
+                        }
+                    };
+                    graph.merge(&subgraph);
+                }
+                None => self.trace_seed(method, &mut visited, &mut graph),
             }
         }
+        if let Some((cache, _)) = trace_cache {
+            cache.evict();
+        }
+        
+        self.annotate_method_nodes(&mut graph);
+        let seed_ids: Vec<String> = changed_methods.iter()
+            .map(|method| format!("method:{}", method))
+            .collect();
+        graph.set_seeds(&seed_ids);
         
         Ok(graph)
     }
     
+    /// 追溯单个变更方法的上下游
+    /// 
+    /// # Arguments
+    /// * `method` - 变更方法名
+    /// * `visited` - 上游追溯的已访问节点集合，可在多个种子方法间共享
+    /// * `graph` - 影响图
+    fn trace_seed(&self, method: &str, visited: &mut HashSet<String>, graph: &mut ImpactGraph) {
+        // 添加变更方法节点
+        let node = ImpactNode::method(method.to_string());
+        graph.add_node(node);
+        
+        // 追溯上游
+        if self.config.trace_upstream {
+            self.trace_method_upstream(method, 0, visited, graph);
+        }
+        
+        // 追溯下游
+        if self.config.trace_downstream {
+            // 为下游追溯创建新的 visited 集合
+            let mut downstream_visited = HashSet::new();
+            self.trace_method_downstream(method, 0, &mut downstream_visited, graph);
+        }
+    }
+    
+    /// 为方法节点附加索引中的属性
+    /// 
+    /// - 源码位置：`file`（相对工作空间根目录）、`line_start`、`line_end`
+    /// - Spring `@Bean` 工厂方法：`bean = true`
+    /// - 受特性开关控制的方法（如 `@ConditionalOnProperty`）：`conditional = <开关名>`
+    /// - 带容错注解的方法：`resilience = retryable` 或 `resilience = circuit_breaker`
+    /// - 来自额外源码根目录（第三方依赖源码）的方法：`third_party = true`
+    fn annotate_method_nodes(&self, graph: &mut ImpactGraph) {
+        for node in graph.nodes_mut() {
+            let NodeType::Method { qualified_name } = &node.node_type else {
+                continue;
+            };
+            let Some(method_info) = self.index.find_method(qualified_name) else {
+                continue;
+            };
+            let file = self.relative_path(&method_info.file_path).to_string_lossy().into_owned();
+            let (line_start, line_end) = method_info.line_range;
+            let properties = &mut node.metadata.properties;
+            properties.insert("file".to_string(), file);
+            properties.insert("line_start".to_string(), line_start.to_string());
+            properties.insert("line_end".to_string(), line_end.to_string());
+            if method_info.is_bean {
+                properties.insert("bean".to_string(), "true".to_string());
+            }
+            if let Some(flag) = &method_info.conditional {
+                properties.insert("conditional".to_string(), flag.clone());
+            }
+            if let Some(resilience) = &method_info.resilience {
+                properties.insert("resilience".to_string(), resilience.clone());
+            }
+            if self.index.is_third_party(qualified_name) {
+                properties.insert("third_party".to_string(), "true".to_string());
+            }
+        }
+    }
+    
     /// 追溯方法的上游调用链（DFS）
     /// 
     /// # Arguments
@@ -499,6 +2095,7 @@ impl<'a> ImpactTracer<'a> {
     ) {
         // 深度限制检查
         if depth >= self.config.max_depth {
+            self.trace_decision(method, format_args!("stopped at {}: max depth {} reached", method, self.config.max_depth));
             return;
         }
         
@@ -532,17 +2129,30 @@ impl<'a> ImpactTracer<'a> {
             }
         }
         
-        if method.contains("sendCoupon") {
-            println!("method = {}, all_callers = {:?}", method, all_callers);
+        // 通过未重写该方法的子类发起的调用实际执行的是当前方法
+        for inherited_target in self.index.find_inherited_call_targets(method) {
+            all_callers.extend(self.index.find_callers(&inherited_target));
         }
         
+        self.trace_decision(method, format_args!("upstream {} (depth {}): callers {:?}", method, depth, all_callers));
+        
         for caller in all_callers {
             // 解析接口调用：如果调用者调用的是接口方法，且接口只有一个实现类，
             // 则将调用目标替换为实现类的方法
             let resolved_caller = self.index.resolve_interface_call(caller);
+            if resolved_caller != caller {
+                self.trace_decision(method, format_args!("  resolved interface call {} -> {}", caller, resolved_caller));
+            }
             
             // 检查调用者是否在索引中（忽略外部库）
             if self.index.find_method(&resolved_caller).is_none() {
+                self.trace_decision(method, format_args!("  skipped external caller {}", resolved_caller));
+                continue;
+            }
+            
+            // 不进入未在白名单中的服务
+            if !self.is_service_allowed(&resolved_caller) {
+                self.trace_decision(method, format_args!("  skipped caller {} outside allowed services", resolved_caller));
                 continue;
             }
             
@@ -550,17 +2160,8 @@ impl<'a> ImpactTracer<'a> {
             let caller_node = ImpactNode::method(resolved_caller.clone());
             graph.add_node(caller_node);
             
-            // 构建节点 ID
-            let caller_id = format!("method:{}", resolved_caller);
-            let method_id = format!("method:{}", method);
-            
-            // 添加边：caller -> method
-            graph.add_edge(
-                &caller_id,
-                &method_id,
-                EdgeType::MethodCall,
-                Direction::Upstream,
-            );
+            // 添加边：caller -> method，记录调用方中的调用行号和控制调用的特性开关
+            self.add_call_edge(&resolved_caller, method, method, Direction::Upstream, graph);
             
             // 递归追溯上游
             self.trace_method_upstream(&resolved_caller, depth + 1, visited, graph);
@@ -588,6 +2189,7 @@ impl<'a> ImpactTracer<'a> {
     ) {
         // 深度限制检查
         if depth >= self.config.max_depth {
+            self.trace_decision(method, format_args!("stopped at {}: max depth {} reached", method, self.config.max_depth));
             return;
         }
         
@@ -600,14 +2202,27 @@ impl<'a> ImpactTracer<'a> {
         
         // 查找当前方法调用的所有方法（下游）
         let callees = self.index.find_callees(method);
+        self.trace_decision(method, format_args!("downstream {} (depth {}): callees {:?}", method, depth, callees));
         
         for callee in callees {
             // 解析接口调用：如果被调用的是接口方法，且接口只有一个实现类，
-            // 则将调用目标替换为实现类的方法
-            let resolved_callee = self.index.resolve_interface_call(callee);
+            // 则将调用目标替换为实现类的方法；被调用类未重写的方法解析到父类的定义
+            let resolved_callee = self.index.resolve_inherited_call(
+                &self.index.resolve_interface_call(callee),
+            );
+            if resolved_callee != callee {
+                self.trace_decision(method, format_args!("  resolved call {} -> {}", callee, resolved_callee));
+            }
             
             // 检查被调用者是否在索引中（忽略外部库）
             if self.index.find_method(&resolved_callee).is_none() {
+                self.trace_decision(method, format_args!("  skipped external callee {}", resolved_callee));
+                continue;
+            }
+            
+            // 不进入未在白名单中的服务
+            if !self.is_service_allowed(&resolved_callee) {
+                self.trace_decision(method, format_args!("  skipped callee {} outside allowed services", resolved_callee));
                 continue;
             }
             
@@ -615,57 +2230,288 @@ impl<'a> ImpactTracer<'a> {
             let callee_node = ImpactNode::method(resolved_callee.clone());
             graph.add_node(callee_node);
             
-            // 构建节点 ID
-            let method_id = format!("method:{}", method);
-            let callee_id = format!("method:{}", resolved_callee);
-            
-            // 添加边：method -> callee
-            graph.add_edge(
-                &method_id,
-                &callee_id,
-                EdgeType::MethodCall,
-                Direction::Downstream,
-            );
+            // 添加边：method -> callee，记录当前方法中的调用行号和控制调用的特性开关
+            self.add_call_edge(method, &resolved_callee, callee, Direction::Downstream, graph);
             
             // 递归追溯下游
             self.trace_method_downstream(&resolved_callee, depth + 1, visited, graph);
         }
         
-        // 跨服务追溯
-        if self.config.trace_cross_service {
-            self.trace_cross_service(method, visited, graph);
+        // 跨服务追溯
+        if self.config.trace_cross_service {
+            self.trace_cross_service(method, visited, graph);
+        }
+    }
+    
+    /// 记录追溯决策日志
+    /// 
+    /// 设置了诊断过滤器时只记录全限定名包含该子串的方法的决策
+    /// 
+    /// # Arguments
+    /// * `method` - 当前追溯的方法
+    /// * `message` - 决策内容
+    fn trace_decision(&self, method: &str, message: std::fmt::Arguments) {
+        if self.config.diagnostic_filter.as_deref().is_none_or(|filter| method.contains(filter)) {
+            log::trace!(target: TRACE_DEBUG_TARGET, "{}", message);
+        }
+    }
+    
+    /// 查找调用方中调用目标方法的所有调用点
+    /// 
+    /// 调用方可能通过目标方法所在类实现的接口，或未重写该方法的子类发起调用，
+    /// 此时按接口方法或子类方法匹配
+    /// 
+    /// # Arguments
+    /// * `caller` - 调用方方法名
+    /// * `callee` - 被调用方法名
+    /// 
+    /// # Returns
+    /// * `Vec<&MethodCall>` - 按源码顺序排列的匹配调用（含行号和控制调用的特性开关）
+    fn find_call_sites(&self, caller: &str, callee: &str) -> Vec<&MethodCall> {
+        let Some(caller_info) = self.index.find_method(caller) else {
+            return Vec::new();
+        };
+        let mut aliases = callee.rsplit_once("::")
+            .map(|(class_name, method_name)| {
+                self.index.find_class_interfaces(class_name).into_iter()
+                    .map(|interface| format!("{}::{}", interface, method_name))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        aliases.extend(self.index.find_inherited_call_targets(callee));
+        
+        caller_info.calls.iter()
+            .filter(|call| call.target == callee || aliases.contains(&call.target))
+            .collect()
+    }
+    
+    /// 在两个方法之间添加调用边
+    /// 
+    /// 边上记录第一个调用点的行号；只有所有调用点都受特性开关控制时才将边标记为
+    /// 条件调用（使用第一个调用点的开关名），任一调用点不受控制时调用总会发生
+    /// 
+    /// # Arguments
+    /// * `caller` - 调用方方法名
+    /// * `callee` - 被调用方法名（已解析到实现类或父类）
+    /// * `call_target` - 调用方中记录的调用目标，用于查找调用点
+    /// * `direction` - 边方向
+    /// * `graph` - 影响图
+    fn add_call_edge(&self, caller: &str, callee: &str, call_target: &str, direction: Direction, graph: &mut ImpactGraph) {
+        let caller_id = format!("method:{}", caller);
+        let callee_id = format!("method:{}", callee);
+        let call_sites = self.find_call_sites(caller, call_target);
+        graph.add_edge_with_line(
+            &caller_id,
+            &callee_id,
+            EdgeType::MethodCall,
+            direction,
+            call_sites.first().and_then(|call| u32::try_from(call.line).ok()),
+        );
+        let conditions: Option<Vec<&str>> = call_sites.iter()
+            .map(|call| call.condition.as_deref())
+            .collect();
+        if let Some(flag) = conditions.and_then(|conditions| conditions.first().copied()) {
+            graph.mark_edge_conditional(&caller_id, &callee_id, flag);
+        }
+    }
+    
+    /// 追溯跨服务边界的调用关系
+    /// 
+    /// # Arguments
+    /// * `method` - 方法名
+    /// * `visited` - 已访问节点集合（用于循环检测）
+    /// * `graph` - 影响图
+    fn trace_cross_service(
+        &self,
+        method: &str,
+        visited: &mut HashSet<String>,
+        graph: &mut ImpactGraph,
+    ) {
+        // 获取方法信息
+        let method_info = match self.index.find_method(method) {
+            Some(info) => info,
+            None => return,
+        };
+        
+        // 1. HTTP 接口追溯
+        self.trace_http_interface(method, method_info, visited, graph);
+        
+        // 2. 共享资源追溯：Kafka Topic、数据库表、Redis 键及自定义资源
+        self.trace_resources(method, method_info, visited, graph);
+        
+        // 3. RPC 接口追溯
+        self.trace_rpc_interface(method, method_info, visited, graph);
+        
+        // 4. Thrift 服务追溯
+        self.trace_thrift_service(method, method_info, visited, graph);
+        
+        // 5. GraphQL 解析器入口
+        self.trace_graphql_resolver(method, method_info, graph);
+        
+        // 6. Spring Batch 作业入口
+        self.trace_batch_job(method, method_info, graph);
+    }
+    
+    /// 记录 GraphQL 解析器入口
+    /// 
+    /// GraphQL 客户端难以静态匹配，因此只添加字段节点作为方法的上游入口点
+    fn trace_graphql_resolver(
+        &self,
+        method: &str,
+        method_info: &crate::language_parser::MethodInfo,
+        graph: &mut ImpactGraph,
+    ) {
+        let Some(resolver) = &method_info.graphql_resolver else {
+            return;
+        };
+        
+        let field_node = ImpactNode::graphql_field(resolver.type_name.clone(), resolver.field.clone());
+        let field_id = field_node.id.clone();
+        graph.add_node(field_node);
+        
+        // 添加边：graphql -> method (GraphQL 字段 -> 解析器方法)
+        graph.add_edge(
+            &field_id,
+            &format!("method:{}", method),
+            EdgeType::GraphQLResolve,
+            Direction::Upstream,
+        );
+    }
+    
+    /// 记录 Spring Batch 作业入口
+    /// 
+    /// Java 配置的作业 `@Bean` 方法经步骤依赖调用到 tasklet/reader/processor/writer，
+    /// 因此只需在作业方法上添加作业节点作为上游入口点；XML 中定义的作业没有对应的方法，
+    /// 作业节点直接连接到步骤的 tasklet/reader/processor/writer 方法
+    fn trace_batch_job(
+        &self,
+        method: &str,
+        method_info: &crate::language_parser::MethodInfo,
+        graph: &mut ImpactGraph,
+    ) {
+        let xml_jobs = self.index.find_batch_jobs(method);
+        for job_name in method_info.batch_job.iter().map(String::as_str).chain(xml_jobs) {
+            let job_node = ImpactNode::batch_job(job_name.to_string());
+            let job_id = job_node.id.clone();
+            graph.add_node(job_node);
+            
+            // 添加边：batch -> method (作业 -> 作业定义方法或步骤组件方法)
+            graph.add_edge(
+                &job_id,
+                &format!("method:{}", method),
+                EdgeType::BatchLaunch,
+                Direction::Upstream,
+            );
+        }
+    }
+    
+    /// 追溯 RPC 接口（如 Dubbo）的双向关系
+    fn trace_rpc_interface(
+        &self,
+        method: &str,
+        method_info: &crate::language_parser::MethodInfo,
+        visited: &mut HashSet<String>,
+        graph: &mut ImpactGraph,
+    ) {
+        use crate::types::RpcOpType;
+        
+        for rpc_op in &method_info.rpc_operations {
+            let rpc_key = rpc_op.key();
+            let peers = match rpc_op.operation_type {
+                RpcOpType::Consume => self.index.find_rpc_providers(&rpc_key),
+                RpcOpType::Provide => self.index.find_rpc_consumers(&rpc_key),
+            };
+            let rpc_node = ImpactNode::rpc_method(rpc_op.interface.clone(), rpc_op.method.clone());
+            self.bridge_remote_call(method, rpc_node, &rpc_op.operation_type, EdgeType::RpcCall, peers, visited, graph);
+        }
+    }
+    
+    /// 追溯 Thrift 服务的双向关系
+    /// 
+    /// 通过生成的 `Client` 发起的调用中，只有 IDL 中声明的方法才作为跨服务调用，
+    /// `send_xxx`/`recv_xxx` 等客户端辅助方法被忽略
+    fn trace_thrift_service(
+        &self,
+        method: &str,
+        method_info: &crate::language_parser::MethodInfo,
+        visited: &mut HashSet<String>,
+        graph: &mut ImpactGraph,
+    ) {
+        use crate::types::RpcOpType;
+        
+        for thrift_op in &method_info.thrift_operations {
+            if !self.index.is_thrift_method_declared(&thrift_op.service, &thrift_op.method) {
+                continue;
+            }
+            
+            let thrift_key = thrift_op.key();
+            let peers = match thrift_op.operation_type {
+                RpcOpType::Consume => self.index.find_thrift_providers(&thrift_key),
+                RpcOpType::Provide => self.index.find_thrift_consumers(&thrift_key),
+            };
+            let thrift_node = ImpactNode::thrift_method(thrift_op.service.clone(), thrift_op.method.clone());
+            self.bridge_remote_call(method, thrift_node, &thrift_op.operation_type, EdgeType::ThriftCall, peers, visited, graph);
         }
     }
     
-    /// 追溯跨服务边界的调用关系
+    /// 通过远程方法节点（Dubbo/Thrift 等）桥接调用方和提供者
+    /// 
+    /// - 当前方法是消费者时添加 `method -> 远程方法 -> 提供者` 的下游边，并继续追溯提供者的下游
+    /// - 当前方法是提供者时添加 `消费者 -> 远程方法 -> method` 的上游边，并继续追溯消费者的上游
     /// 
     /// # Arguments
-    /// * `method` - 方法名
-    /// * `visited` - 已访问节点集合（用于循环检测）
-    /// * `graph` - 影响图
-    fn trace_cross_service(
+    /// * `method` - 当前方法
+    /// * `remote_node` - 远程方法节点
+    /// * `operation_type` - 当前方法在远程调用中的角色
+    /// * `edge_type` - 桥接边的类型
+    /// * `peers` - 另一侧的方法：消费者对应提供者，提供者对应消费者
+    #[allow(clippy::too_many_arguments)]
+    fn bridge_remote_call(
         &self,
         method: &str,
+        remote_node: ImpactNode,
+        operation_type: &crate::types::RpcOpType,
+        edge_type: EdgeType,
+        peers: Vec<&str>,
         visited: &mut HashSet<String>,
         graph: &mut ImpactGraph,
     ) {
-        // 获取方法信息
-        let method_info = match self.index.find_method(method) {
-            Some(info) => info,
-            None => return,
-        };
-        
-        // 1. HTTP 接口追溯
-        self.trace_http_interface(method, method_info, visited, graph);
+        use crate::types::RpcOpType;
         
-        // 2. Kafka Topic 追溯
-        self.trace_kafka_topic(method, method_info, visited, graph);
-        
-        // 3. 数据库表追溯
-        self.trace_database_table(method, method_info, visited, graph);
+        let method_id = format!("method:{}", method);
+        let remote_id = remote_node.id.clone();
+        graph.add_node(remote_node);
+        
+        match operation_type {
+            // 添加边：method -> 远程方法
+            RpcOpType::Consume => graph.add_edge(&method_id, &remote_id, edge_type.clone(), Direction::Downstream),
+            // 添加边：远程方法 -> method
+            RpcOpType::Provide => graph.add_edge(&remote_id, &method_id, edge_type.clone(), Direction::Upstream),
+        }
         
-        // 4. Redis 键追溯
-        self.trace_redis_key(method, method_info, visited, graph);
+        for peer in peers {
+            if visited.contains(peer) || !self.is_service_allowed(peer) {
+                continue;
+            }
+            
+            let peer_node = ImpactNode::method(peer.to_string());
+            let peer_id = peer_node.id.clone();
+            graph.add_node(peer_node);
+            
+            let mut peer_visited = visited.clone();
+            match operation_type {
+                RpcOpType::Consume => {
+                    // 添加边：远程方法 -> provider，继续追溯提供者的下游
+                    graph.add_edge(&remote_id, &peer_id, edge_type.clone(), Direction::Downstream);
+                    self.trace_method_downstream(peer, 0, &mut peer_visited, graph);
+                }
+                RpcOpType::Provide => {
+                    // 添加边：consumer -> 远程方法，继续追溯消费者的上游
+                    graph.add_edge(&peer_id, &remote_id, edge_type.clone(), Direction::Upstream);
+                    self.trace_method_upstream(peer, 0, &mut peer_visited, graph);
+                }
+            }
+        }
     }
     
     /// 追溯 HTTP 接口的双向关系
@@ -678,21 +2524,38 @@ impl<'a> ImpactTracer<'a> {
     ) {
         use crate::types::HttpEndpoint;
         
-        // 如果当前方法有 HTTP 注解
-        if let Some(http_annotation) = &method_info.http_annotations {
+        // 方法上的每个 HTTP 注解或客户端调用
+        for http_annotation in method_info.all_http_annotations() {
             let endpoint = HttpEndpoint {
                 method: http_annotation.method.clone(),
                 path_pattern: http_annotation.path.clone(),
             };
             
             // 创建 HTTP 端点节点
-            let endpoint_node = ImpactNode::http_endpoint(
+            let mut endpoint_node = ImpactNode::http_endpoint(
                 http_annotation.method.clone(),
                 http_annotation.path.clone(),
             );
+            
+            // Feign 调用的路径以服务名开头，附加该客户端的超时/重试配置
+            if http_annotation.is_feign_client {
+                let client_name = http_annotation.path.split('/').next().unwrap_or_default();
+                let module = self.service_of(method).unwrap_or_default();
+                if let Some(config) = self.index.find_feign_client_config(&module, client_name) {
+                    endpoint_node.metadata.properties.extend(config.to_properties());
+                }
+            }
             let endpoint_id = endpoint_node.id.clone();
             graph.add_node(endpoint_node);
             
+            // 提供者方法上的 OpenAPI/Swagger 文档作为端点说明（端点节点可能已由调用方创建）
+            if !http_annotation.is_feign_client
+                && let Some(operation) = &method_info.api_operation
+                && let Some(node) = graph.get_node_mut(&endpoint_id)
+            {
+                node.metadata.properties.extend(operation.to_properties());
+            }
+            
             // 方法节点 ID
             let method_id = format!("method:{}", method);
             
@@ -708,26 +2571,40 @@ impl<'a> ImpactTracer<'a> {
                 );
                 
                 // 查找提供该接口的方法（其他服务）
-                let providers = self.index.find_http_providers(&endpoint);
+                let (providers, omitted) = self.select_bridge_methods(
+                    self.index.find_http_providers(&endpoint),
+                    visited,
+                );
                 for provider in providers {
-                    if !visited.contains(provider) {
-                        // 添加提供者节点
-                        let provider_node = ImpactNode::method(provider.to_string());
-                        let provider_id = provider_node.id.clone();
-                        graph.add_node(provider_node);
-                        
-                        // 添加边：endpoint -> provider (HTTP接口 -> 提供者方法)
-                        graph.add_edge(
-                            &endpoint_id,
-                            &provider_id,
-                            EdgeType::HttpCall,
-                            Direction::Downstream,
-                        );
-                        
-                        // 继续追溯提供者的下游
-                        let mut provider_visited = visited.clone();
-                        self.trace_method_downstream(provider, 0, &mut provider_visited, graph);
-                    }
+                    // 添加提供者节点
+                    let provider_node = ImpactNode::method(provider.to_string());
+                    let provider_id = provider_node.id.clone();
+                    graph.add_node(provider_node);
+                    
+                    // 添加边：endpoint -> provider (HTTP接口 -> 提供者方法)
+                    graph.add_edge(
+                        &endpoint_id,
+                        &provider_id,
+                        EdgeType::HttpCall,
+                        Direction::Downstream,
+                    );
+                    
+                    // 继续追溯提供者的下游
+                    let mut provider_visited = visited.clone();
+                    self.trace_method_downstream(provider, 0, &mut provider_visited, graph);
+                }
+                
+                // 超出展开上限的提供者合并为一个汇总节点
+                if omitted > 0 {
+                    let summary_node = ImpactNode::bridge_summary(endpoint_id.clone(), &Direction::Downstream, omitted);
+                    let summary_id = summary_node.id.clone();
+                    graph.add_node(summary_node);
+                    graph.add_edge(
+                        &endpoint_id,
+                        &summary_id,
+                        EdgeType::HttpCall,
+                        Direction::Downstream,
+                    );
                 }
             } else {
                 // HTTP 接口声明：HTTP 节点是方法的上游
@@ -740,288 +2617,121 @@ impl<'a> ImpactTracer<'a> {
                 );
                 
                 // 查找所有调用该接口的消费者（Feign 客户端）
-                let consumers = self.index.find_http_consumers(&endpoint);
+                let (consumers, omitted) = self.select_bridge_methods(
+                    self.index.find_http_consumers(&endpoint),
+                    visited,
+                );
                 for consumer in consumers {
-                    if !visited.contains(consumer) {
-                        // 添加消费者节点
-                        let consumer_node = ImpactNode::method(consumer.to_string());
-                        let consumer_id = consumer_node.id.clone();
-                        graph.add_node(consumer_node);
-                        
-                        // 添加边：consumer -> endpoint (消费者方法 -> HTTP接口)
-                        graph.add_edge(
-                            &consumer_id,
-                            &endpoint_id,
-                            EdgeType::HttpCall,
-                            Direction::Upstream,
-                        );
-                        
-                        // 继续追溯消费者的上游
-                        let mut consumer_visited = visited.clone();
-                        self.trace_method_upstream(consumer, 0, &mut consumer_visited, graph);
-                    }
-                }
-            }
-        }
-    }
-    
-    /// 追溯 Kafka Topic 的双向关系
-    fn trace_kafka_topic(
-        &self,
-        method: &str,
-        method_info: &crate::language_parser::MethodInfo,
-        visited: &mut HashSet<String>,
-        graph: &mut ImpactGraph,
-    ) {
-        use crate::types::KafkaOpType;
-        
-        let method_id = format!("method:{}", method);
-        
-        for kafka_op in &method_info.kafka_operations {
-            let topic_node = ImpactNode::kafka_topic(kafka_op.topic.clone());
-            let topic_id = topic_node.id.clone();
-            graph.add_node(topic_node);
-            
-            match kafka_op.operation_type {
-                KafkaOpType::Produce => {
-                    // 当前方法是生产者
-                    // 添加边：method -> topic
-                    graph.add_edge(
-                        &method_id,
-                        &topic_id,
-                        EdgeType::KafkaProduceConsume,
-                        Direction::Downstream,
-                    );
+                    // 添加消费者节点
+                    let consumer_node = ImpactNode::method(consumer.to_string());
+                    let consumer_id = consumer_node.id.clone();
+                    graph.add_node(consumer_node);
                     
-                    // 查找所有消费该 Topic 的消费者
-                    let consumers = self.index.find_kafka_consumers(&kafka_op.topic);
-                    for consumer in consumers {
-                        if !visited.contains(consumer) {
-                            // 添加消费者节点
-                            let consumer_node = ImpactNode::method(consumer.to_string());
-                            let consumer_id = consumer_node.id.clone();
-                            graph.add_node(consumer_node);
-                            
-                            // 添加边：topic -> consumer
-                            graph.add_edge(
-                                &topic_id,
-                                &consumer_id,
-                                EdgeType::KafkaProduceConsume,
-                                Direction::Downstream,
-                            );
-                            
-                            // 继续追溯消费者的下游
-                            let mut consumer_visited = visited.clone();
-                            self.trace_method_downstream(consumer, 0, &mut consumer_visited, graph);
-                        }
-                    }
-                }
-                KafkaOpType::Consume => {
-                    // 当前方法是消费者
-                    // 添加边：topic -> method
+                    // 添加边：consumer -> endpoint (消费者方法 -> HTTP接口)
                     graph.add_edge(
-                        &topic_id,
-                        &method_id,
-                        EdgeType::KafkaProduceConsume,
+                        &consumer_id,
+                        &endpoint_id,
+                        EdgeType::HttpCall,
                         Direction::Upstream,
                     );
                     
-                    // 查找所有生产该 Topic 的生产者
-                    let producers = self.index.find_kafka_producers(&kafka_op.topic);
-                    for producer in producers {
-                        if !visited.contains(producer) {
-                            // 添加生产者节点
-                            let producer_node = ImpactNode::method(producer.to_string());
-                            let producer_id = producer_node.id.clone();
-                            graph.add_node(producer_node);
-                            
-                            // 添加边：producer -> topic
-                            graph.add_edge(
-                                &producer_id,
-                                &topic_id,
-                                EdgeType::KafkaProduceConsume,
-                                Direction::Upstream,
-                            );
-                            
-                            // 继续追溯生产者的上游
-                            let mut producer_visited = visited.clone();
-                            self.trace_method_upstream(producer, 0, &mut producer_visited, graph);
-                        }
-                    }
+                    // 继续追溯消费者的上游
+                    let mut consumer_visited = visited.clone();
+                    self.trace_method_upstream(consumer, 0, &mut consumer_visited, graph);
                 }
-            }
-        }
-    }
-    
-    /// 追溯数据库表的双向关系
-    fn trace_database_table(
-        &self,
-        method: &str,
-        method_info: &crate::language_parser::MethodInfo,
-        visited: &mut HashSet<String>,
-        graph: &mut ImpactGraph,
-    ) {
-        use crate::types::DbOpType;
-        
-        let method_id = format!("method:{}", method);
-        
-        for db_op in &method_info.db_operations {
-            let table_node = ImpactNode::database_table(db_op.table.clone());
-            let table_id = table_node.id.clone();
-            graph.add_node(table_node);
-            
-            match db_op.operation_type {
-                DbOpType::Select => {
-                    // 当前方法读取表
-                    // 添加边：table -> method
+                
+                // 超出展开上限的消费者合并为一个汇总节点
+                if omitted > 0 {
+                    let summary_node = ImpactNode::bridge_summary(endpoint_id.clone(), &Direction::Upstream, omitted);
+                    let summary_id = summary_node.id.clone();
+                    graph.add_node(summary_node);
                     graph.add_edge(
-                        &table_id,
-                        &method_id,
-                        EdgeType::DatabaseReadWrite,
+                        &summary_id,
+                        &endpoint_id,
+                        EdgeType::HttpCall,
                         Direction::Upstream,
                     );
-                    
-                    // 查找所有写入该表的方法
-                    let writers = self.index.find_db_writers(&db_op.table);
-                    for writer in writers {
-                        if !visited.contains(writer) {
-                            // 添加写入者节点
-                            let writer_node = ImpactNode::method(writer.to_string());
-                            let writer_id = writer_node.id.clone();
-                            graph.add_node(writer_node);
-                            
-                            // 添加边：writer -> table
-                            graph.add_edge(
-                                &writer_id,
-                                &table_id,
-                                EdgeType::DatabaseReadWrite,
-                                Direction::Upstream,
-                            );
-                            
-                            // 继续追溯写入者的上游
-                            let mut writer_visited = visited.clone();
-                            self.trace_method_upstream(writer, 0, &mut writer_visited, graph);
-                        }
-                    }
-                }
-                DbOpType::Insert | DbOpType::Update | DbOpType::Delete => {
-                    // 当前方法写入表
-                    // 添加边：method -> table
-                    graph.add_edge(
-                        &method_id,
-                        &table_id,
-                        EdgeType::DatabaseReadWrite,
-                        Direction::Downstream,
-                    );
-                    
-                    // 查找所有读取该表的方法
-                    let readers = self.index.find_db_readers(&db_op.table);
-                    for reader in readers {
-                        if !visited.contains(reader) {
-                            // 添加读取者节点
-                            let reader_node = ImpactNode::method(reader.to_string());
-                            let reader_id = reader_node.id.clone();
-                            graph.add_node(reader_node);
-                            
-                            // 添加边：table -> reader
-                            graph.add_edge(
-                                &table_id,
-                                &reader_id,
-                                EdgeType::DatabaseReadWrite,
-                                Direction::Downstream,
-                            );
-                            
-                            // 继续追溯读取者的下游
-                            let mut reader_visited = visited.clone();
-                            self.trace_method_downstream(reader, 0, &mut reader_visited, graph);
-                        }
-                    }
                 }
             }
         }
     }
     
-    /// 追溯 Redis 键的双向关系
-    fn trace_redis_key(
+    /// 通过资源匹配器追溯共享资源两端的方法
+    /// 
+    /// 方法写入资源时添加 方法 -> 资源 -> 读取者 的下游边并继续追溯读取者的下游；
+    /// 方法读取资源时添加 写入者 -> 资源 -> 方法 的上游边并继续追溯写入者的上游
+    fn trace_resources(
         &self,
         method: &str,
         method_info: &crate::language_parser::MethodInfo,
         visited: &mut HashSet<String>,
         graph: &mut ImpactGraph,
     ) {
-        use crate::types::RedisOpType;
-        
         let method_id = format!("method:{}", method);
         
-        for redis_op in &method_info.redis_operations {
-            let redis_node = ImpactNode::redis_prefix(redis_op.key_pattern.clone());
-            let redis_id = redis_node.id.clone();
-            graph.add_node(redis_node);
-            
-            match redis_op.operation_type {
-                RedisOpType::Get => {
-                    // 当前方法读取 Redis 键
-                    // 添加边：redis -> method
-                    graph.add_edge(
-                        &redis_id,
-                        &method_id,
-                        EdgeType::RedisReadWrite,
-                        Direction::Upstream,
-                    );
-                    
-                    // 查找所有写入该键的方法
-                    let writers = self.index.find_redis_writers(&redis_op.key_pattern);
-                    for writer in writers {
-                        if !visited.contains(writer) {
-                            // 添加写入者节点
-                            let writer_node = ImpactNode::method(writer.to_string());
-                            let writer_id = writer_node.id.clone();
-                            graph.add_node(writer_node);
-                            
-                            // 添加边：writer -> redis
-                            graph.add_edge(
-                                &writer_id,
-                                &redis_id,
-                                EdgeType::RedisReadWrite,
-                                Direction::Upstream,
-                            );
-                            
-                            // 继续追溯写入者的上游
-                            let mut writer_visited = visited.clone();
-                            self.trace_method_upstream(writer, 0, &mut writer_visited, graph);
-                        }
+        // 数据库写入边为高风险，读取边为低风险
+        let risk_of = |edge_type: &EdgeType, access: ResourceAccess| {
+            (*edge_type == EdgeType::DatabaseReadWrite).then_some(match access {
+                ResourceAccess::Write => Risk::High,
+                ResourceAccess::Read => Risk::Low,
+            })
+        };
+        let mark_risk = |graph: &mut ImpactGraph, from: &str, to: &str, risk: Option<Risk>| {
+            if let Some(risk) = risk {
+                graph.mark_edge_risk(from, to, risk);
+            }
+        };
+        
+        for matcher in &self.resource_matchers {
+            for op in matcher.extract(method_info, self.index) {
+                let write_risk = risk_of(&op.edge_type, ResourceAccess::Write);
+                let read_risk = risk_of(&op.edge_type, ResourceAccess::Read);
+                let resource_id = op.node.id.clone();
+                let properties = op.node.metadata.properties.clone();
+                graph.add_node(op.node.clone());
+                
+                // 资源节点可能已由另一端创建，补充本端提供的属性
+                if let Some(node) = graph.get_node_mut(&resource_id) {
+                    for (key, value) in properties {
+                        node.metadata.properties.entry(key).or_insert(value);
                     }
                 }
-                RedisOpType::Set | RedisOpType::Delete => {
-                    // 当前方法写入 Redis 键
-                    // 添加边：method -> redis
-                    graph.add_edge(
-                        &method_id,
-                        &redis_id,
-                        EdgeType::RedisReadWrite,
-                        Direction::Downstream,
-                    );
-                    
-                    // 查找所有读取该键的方法
-                    let readers = self.index.find_redis_readers(&redis_op.key_pattern);
-                    for reader in readers {
-                        if !visited.contains(reader) {
-                            // 添加读取者节点
-                            let reader_node = ImpactNode::method(reader.to_string());
-                            let reader_id = reader_node.id.clone();
-                            graph.add_node(reader_node);
-                            
-                            // 添加边：redis -> reader
-                            graph.add_edge(
-                                &redis_id,
-                                &reader_id,
-                                EdgeType::RedisReadWrite,
-                                Direction::Downstream,
-                            );
-                            
-                            // 继续追溯读取者的下游
-                            let mut reader_visited = visited.clone();
-                            self.trace_method_downstream(reader, 0, &mut reader_visited, graph);
+                
+                match op.access {
+                    ResourceAccess::Write => {
+                        graph.add_edge(&method_id, &resource_id, op.edge_type.clone(), Direction::Downstream);
+                        mark_risk(graph, &method_id, &resource_id, write_risk);
+                        
+                        for reader in matcher.bridge(&op, self.index) {
+                            if !visited.contains(&reader) && self.is_service_allowed(&reader) {
+                                let reader_node = ImpactNode::method(reader.clone());
+                                let reader_id = reader_node.id.clone();
+                                graph.add_node(reader_node);
+                                graph.add_edge(&resource_id, &reader_id, op.edge_type.clone(), Direction::Downstream);
+                                mark_risk(graph, &resource_id, &reader_id, read_risk);
+                                
+                                // 继续追溯读取者的下游
+                                let mut reader_visited = visited.clone();
+                                self.trace_method_downstream(&reader, 0, &mut reader_visited, graph);
+                            }
+                        }
+                    }
+                    ResourceAccess::Read => {
+                        graph.add_edge(&resource_id, &method_id, op.edge_type.clone(), Direction::Upstream);
+                        mark_risk(graph, &resource_id, &method_id, read_risk);
+                        
+                        for writer in matcher.bridge(&op, self.index) {
+                            if !visited.contains(&writer) && self.is_service_allowed(&writer) {
+                                let writer_node = ImpactNode::method(writer.clone());
+                                let writer_id = writer_node.id.clone();
+                                graph.add_node(writer_node);
+                                graph.add_edge(&writer_id, &resource_id, op.edge_type.clone(), Direction::Upstream);
+                                mark_risk(graph, &writer_id, &resource_id, write_risk);
+                                
+                                // 继续追溯写入者的上游
+                                let mut writer_visited = visited.clone();
+                                self.trace_method_upstream(&writer, 0, &mut writer_visited, graph);
+                            }
                         }
                     }
                 }
@@ -1043,6 +2753,43 @@ mod tests {
         assert!(config.trace_cross_service);
     }
     
+    #[test]
+    fn test_trace_config_builder_valid() {
+        let config = TraceConfig::builder()
+            .max_depth(3)
+            .upstream(false)
+            .allowed_services(vec!["order-service".to_string()])
+            .build()
+            .unwrap();
+        assert_eq!(config.max_depth, 3);
+        assert!(!config.trace_upstream);
+        assert!(config.trace_downstream);
+        assert!(config.trace_cross_service);
+        assert_eq!(config.allowed_services, vec!["order-service".to_string()]);
+        
+        // 不追溯跨服务时允许深度为 0（只包含起点）
+        let config = TraceConfig::builder()
+            .max_depth(0)
+            .cross_service(false)
+            .build()
+            .unwrap();
+        assert_eq!(config.max_depth, 0);
+    }
+    
+    #[test]
+    fn test_trace_config_builder_invalid() {
+        let result = TraceConfig::builder()
+            .upstream(false)
+            .downstream(false)
+            .build();
+        assert!(matches!(result, Err(TraceError::InvalidConfig { .. })));
+        
+        let result = TraceConfig::builder()
+            .max_depth(0)
+            .build();
+        assert!(matches!(result, Err(TraceError::InvalidConfig { .. })));
+    }
+    
     #[test]
     fn test_impact_node_creation() {
         let method_node = ImpactNode::method("com.example.Test::test".to_string());
@@ -1064,6 +2811,31 @@ mod tests {
         let redis_node = ImpactNode::redis_prefix("user:*".to_string());
         assert_eq!(redis_node.id, "redis:user:*");
         assert!(matches!(redis_node.node_type, NodeType::RedisPrefix { .. }));
+        
+        let namespace_node = ImpactNode::redis_namespace("user:{id}:profile".to_string(), Some(1));
+        assert_eq!(namespace_node.id, "redis:user");
+        assert_eq!(namespace_node.metadata.label, "Redis: user");
+    }
+    
+    #[test]
+    fn test_label_styles() {
+        let name = "com.hualala.shop.domain.service.impl.VeryLongNameServiceImpl::doSomethingComplicated";
+        assert_eq!(LabelStyle::Full.method_label(name), name);
+        assert_eq!(LabelStyle::Simple.method_label(name), "VeryLongNameServiceImpl::doSomethingComplicated");
+        assert_eq!(LabelStyle::PackageInitials.method_label(name), "c.h.s.d.s.i.VLNSI::do...");
+        assert_eq!(LabelStyle::Simple.method_label("com.example.Outer.Inner::run"), "Outer.Inner::run");
+        assert_eq!(LabelStyle::Simple.method_label("service::order::create"), "order::create");
+        
+        let mut graph = ImpactGraph::new();
+        graph.add_node(ImpactNode::method(name.to_string()));
+        graph.add_node(ImpactNode::kafka_topic("orders".to_string()));
+        graph.set_label_style(LabelStyle::Simple);
+        
+        // 标签去掉包名，ID 仍为完整名称；非方法节点不受影响
+        let node = graph.get_node(&format!("method:{}", name)).unwrap();
+        assert_eq!(node.metadata.label, "VeryLongNameServiceImpl::doSomethingComplicated");
+        assert_eq!(node.id, format!("method:{}", name));
+        assert_eq!(graph.get_node("kafka:orders").unwrap().metadata.label, "Kafka: orders");
     }
     
     #[test]
@@ -1107,6 +2879,431 @@ mod tests {
         assert_eq!(edge.direction, Direction::Downstream);
     }
     
+    #[test]
+    fn test_write_json_streaming() {
+        let mut graph = ImpactGraph::new();
+        
+        graph.add_node(ImpactNode::method("A".to_string()));
+        graph.add_node(ImpactNode::http_endpoint(HttpMethod::GET, "/api/users".to_string()));
+        graph.add_node(ImpactNode::kafka_topic("user-events".to_string()));
+        graph.add_edge("method:A", "http:GET:/api/users", EdgeType::HttpCall, Direction::Downstream);
+        graph.add_edge("method:A", "kafka:user-events", EdgeType::KafkaProduceConsume, Direction::Upstream);
+        
+        // 写入内存缓冲区
+        let mut buffer: Vec<u8> = Vec::new();
+        graph.write_json(&mut buffer).unwrap();
+        
+        // 解析回来，应与 to_json 的结果一致
+        let streamed: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+        assert_eq!(streamed, expected);
+        
+        assert_eq!(streamed["node_count"], 3);
+        assert_eq!(streamed["edge_count"], 2);
+        assert_eq!(streamed["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(streamed["edges"][0]["type"], "http_call");
+    }
+    
+    #[test]
+    fn test_blast_radius_counts_only_method_nodes() {
+        let mut graph = ImpactGraph::new();
+        
+        graph.add_node(ImpactNode::method("Seed::change".to_string()));
+        graph.add_node(ImpactNode::method("Caller::call".to_string()));
+        graph.add_node(ImpactNode::method("Consumer::onEvent".to_string()));
+        graph.add_node(ImpactNode::http_endpoint(HttpMethod::GET, "/api/users".to_string()));
+        graph.add_node(ImpactNode::kafka_topic("user-events".to_string()));
+        graph.add_node(ImpactNode::database_table("users".to_string()));
+        graph.add_node(ImpactNode::redis_prefix("user:*".to_string()));
+        graph.add_edge("method:Caller::call", "method:Seed::change", EdgeType::MethodCall, Direction::Upstream);
+        graph.add_edge("method:Seed::change", "http:GET:/api/users", EdgeType::HttpCall, Direction::Upstream);
+        graph.add_edge("method:Seed::change", "kafka:user-events", EdgeType::KafkaProduceConsume, Direction::Downstream);
+        graph.add_edge("kafka:user-events", "method:Consumer::onEvent", EdgeType::KafkaProduceConsume, Direction::Downstream);
+        graph.add_edge("method:Seed::change", "db:users", EdgeType::DatabaseReadWrite, Direction::Downstream);
+        graph.add_edge("method:Seed::change", "redis:user:*", EdgeType::RedisReadWrite, Direction::Downstream);
+        
+        assert_eq!(graph.node_count(), 7);
+        assert_eq!(graph.blast_radius(), 3);
+        
+        // JSON 输出中包含影响半径
+        let parsed: serde_json::Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+        assert_eq!(parsed["blast_radius"], 3);
+        
+        let mut buffer: Vec<u8> = Vec::new();
+        graph.write_json(&mut buffer).unwrap();
+        let streamed: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(streamed["blast_radius"], 3);
+    }
+    
+    #[test]
+    fn test_blast_radius_counts_methods_reachable_from_seeds() {
+        let mut graph = ImpactGraph::new();
+        
+        graph.add_node(ImpactNode::method("Seed::change".to_string()));
+        graph.add_node(ImpactNode::method("Caller::call".to_string()));
+        graph.add_node(ImpactNode::method("Consumer::onEvent".to_string()));
+        graph.add_node(ImpactNode::method("Other::run".to_string()));
+        graph.add_node(ImpactNode::method("Other::helper".to_string()));
+        graph.add_node(ImpactNode::kafka_topic("user-events".to_string()));
+        graph.add_edge("method:Caller::call", "method:Seed::change", EdgeType::MethodCall, Direction::Upstream);
+        graph.add_edge("method:Seed::change", "kafka:user-events", EdgeType::KafkaProduceConsume, Direction::Downstream);
+        graph.add_edge("kafka:user-events", "method:Consumer::onEvent", EdgeType::KafkaProduceConsume, Direction::Downstream);
+        graph.add_edge("method:Other::run", "method:Other::helper", EdgeType::MethodCall, Direction::Downstream);
+        
+        // 未记录种子时统计全部方法节点
+        assert_eq!(graph.blast_radius(), 5);
+        
+        // 与种子不相连的方法不计入
+        let seed_ids = vec!["method:Seed::change".to_string()];
+        graph.set_seeds(&seed_ids);
+        assert_eq!(graph.blast_radius(), 3);
+        
+        // 过滤后的图沿用种子，只统计仍与种子相连的方法
+        let downstream = graph.filter_by_direction(&Direction::Downstream, &seed_ids);
+        assert_eq!(downstream.node_count(), 5);
+        assert_eq!(downstream.blast_radius(), 2);
+        
+        let parsed: serde_json::Value = serde_json::from_str(&downstream.to_json().unwrap()).unwrap();
+        assert_eq!(parsed["blast_radius"], 2);
+    }
+    
+    #[test]
+    fn test_filter_by_risk_drops_components_disconnected_from_seeds() {
+        let mut graph = ImpactGraph::new();
+        
+        graph.add_node(ImpactNode::method("OrderRepository::update".to_string()));
+        graph.add_node(ImpactNode::method("ReportService::load".to_string()));
+        graph.add_node(ImpactNode::method("ReportController::show".to_string()));
+        graph.add_node(ImpactNode::database_table("orders".to_string()));
+        graph.add_edge("method:OrderRepository::update", "db:orders", EdgeType::DatabaseReadWrite, Direction::Downstream);
+        graph.add_edge("db:orders", "method:ReportService::load", EdgeType::DatabaseReadWrite, Direction::Downstream);
+        graph.add_edge("method:ReportController::show", "method:ReportService::load", EdgeType::MethodCall, Direction::Upstream);
+        graph.mark_edge_risk("method:OrderRepository::update", "db:orders", Risk::High);
+        graph.mark_edge_risk("db:orders", "method:ReportService::load", Risk::Low);
+        
+        let seed_ids = vec!["method:OrderRepository::update".to_string()];
+        let filtered = graph.filter_by_risk(Risk::High, &seed_ids);
+        
+        // 低风险读取者与其调用方之间的边仍满足条件，但已与种子断开，整体移除
+        assert!(filtered.get_node("db:orders").is_some());
+        assert!(filtered.get_node("method:ReportService::load").is_none());
+        assert!(filtered.get_node("method:ReportController::show").is_none());
+        assert_eq!(filtered.node_count(), 2);
+        assert_eq!(filtered.edge_count(), 1);
+    }
+    
+    #[test]
+    fn test_filter_methods_drops_islands_left_by_removed_methods() {
+        let mut graph = ImpactGraph::new();
+        
+        graph.add_node(ImpactNode::method("Seed::change".to_string()));
+        graph.add_node(ImpactNode::method("Dead::call".to_string()));
+        graph.add_node(ImpactNode::method("DeadCaller::run".to_string()));
+        graph.add_node(ImpactNode::method("Live::call".to_string()));
+        graph.add_node(ImpactNode::http_endpoint(HttpMethod::GET, "/dead".to_string()));
+        graph.add_edge("method:Dead::call", "method:Seed::change", EdgeType::MethodCall, Direction::Upstream);
+        graph.add_edge("method:DeadCaller::run", "method:Dead::call", EdgeType::MethodCall, Direction::Upstream);
+        graph.add_edge("http:GET:/dead", "method:DeadCaller::run", EdgeType::HttpCall, Direction::Upstream);
+        graph.add_edge("method:Live::call", "method:Seed::change", EdgeType::MethodCall, Direction::Upstream);
+        
+        let seed_ids = vec!["method:Seed::change".to_string()];
+        let filtered = graph.filter_methods(|method| method != "Dead::call", &seed_ids);
+        
+        // 只经由被移除方法连接到种子的调用方和端点不再保留
+        assert!(filtered.get_node("method:Dead::call").is_none());
+        assert!(filtered.get_node("method:DeadCaller::run").is_none());
+        assert!(filtered.get_node("http:GET:/dead").is_none());
+        assert!(filtered.get_node("method:Live::call").is_some());
+        assert_eq!(filtered.node_count(), 2);
+        assert_eq!(filtered.edge_count(), 1);
+    }
+    
+    #[test]
+    fn test_module_rollup_counts_per_module() {
+        let mut graph = ImpactGraph::new();
+        
+        let mut method = |name: &str, file: &str| {
+            let mut node = ImpactNode::method(name.to_string());
+            node.metadata.properties.insert("file".to_string(), file.to_string());
+            graph.add_node(node);
+        };
+        method("OrderService::create", "order-service/src/OrderService.java");
+        method("OrderController::create", "order-service/src/OrderController.java");
+        method("UserClient::notify", "user-service/src/UserClient.java");
+        graph.add_node(ImpactNode::database_table("orders".to_string()));
+        graph.add_node(ImpactNode::database_table("order_items".to_string()));
+        graph.add_node(ImpactNode::kafka_topic("order-created".to_string()));
+        graph.add_edge("method:OrderController::create", "method:OrderService::create", EdgeType::MethodCall, Direction::Upstream);
+        graph.add_edge("method:OrderService::create", "db:orders", EdgeType::DatabaseReadWrite, Direction::Downstream);
+        graph.add_edge("method:OrderService::create", "db:order_items", EdgeType::DatabaseReadWrite, Direction::Downstream);
+        // topic 由两个模块共享，分别计入
+        graph.add_edge("method:OrderService::create", "kafka:order-created", EdgeType::KafkaProduceConsume, Direction::Downstream);
+        graph.add_edge("kafka:order-created", "method:UserClient::notify", EdgeType::KafkaProduceConsume, Direction::Downstream);
+        
+        let rollup = graph.module_rollup();
+        assert_eq!(rollup, vec![
+            ModuleImpact {
+                module: "order-service".to_string(),
+                methods: 2,
+                resources: BTreeMap::from([("database".to_string(), 2), ("kafka".to_string(), 1)]),
+            },
+            ModuleImpact {
+                module: "user-service".to_string(),
+                methods: 1,
+                resources: BTreeMap::from([("kafka".to_string(), 1)]),
+            },
+        ]);
+        
+        // JSON 输出中的 by_module 与流式输出一致
+        let parsed: serde_json::Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+        assert_eq!(parsed["by_module"][0]["module"], "order-service");
+        assert_eq!(parsed["by_module"][0]["methods"], 2);
+        assert_eq!(parsed["by_module"][0]["resources"]["database"], 2);
+        
+        let mut buffer: Vec<u8> = Vec::new();
+        graph.write_json(&mut buffer).unwrap();
+        let streamed: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(streamed["by_module"], parsed["by_module"]);
+    }
+    
+    #[test]
+    fn test_metrics_on_two_component_graph() {
+        let mut graph = ImpactGraph::new();
+        for name in ["A::a", "B::b", "C::c", "D::d", "E::e"] {
+            graph.add_node(ImpactNode::method(name.to_string()));
+        }
+        // 第一个分量：a -> b -> c 以及捷径 a -> c；第二个分量：d -> e
+        graph.add_edge("method:A::a", "method:B::b", EdgeType::MethodCall, Direction::Downstream);
+        graph.add_edge("method:B::b", "method:C::c", EdgeType::MethodCall, Direction::Downstream);
+        graph.add_edge("method:A::a", "method:C::c", EdgeType::MethodCall, Direction::Downstream);
+        graph.add_edge("method:D::d", "method:E::e", EdgeType::MethodCall, Direction::Downstream);
+        
+        // 最大深度取最长链 a -> b -> c，而不是 c 离根节点的最短距离
+        let metrics = graph.metrics();
+        assert_eq!(metrics.components, 2);
+        assert_eq!(metrics.max_depth, 2);
+        assert!((metrics.average_degree - 1.6).abs() < 1e-9);
+        
+        let parsed: serde_json::Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+        assert_eq!(parsed["metrics"]["components"], 2);
+        
+        let mut buffer: Vec<u8> = Vec::new();
+        graph.write_json(&mut buffer).unwrap();
+        let streamed: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(streamed["metrics"], parsed["metrics"]);
+        
+        // 空图
+        assert_eq!(ImpactGraph::new().metrics(), GraphMetrics { components: 0, average_degree: 0.0, max_depth: 0 });
+    }
+    
+    #[test]
+    fn test_write_jsonl_events() {
+        let mut graph = ImpactGraph::new();
+        
+        graph.add_node(ImpactNode::method("A".to_string()));
+        graph.add_node(ImpactNode::method("B".to_string()));
+        graph.add_node(ImpactNode::kafka_topic("user-events".to_string()));
+        graph.add_edge("method:A", "method:B", EdgeType::MethodCall, Direction::Downstream);
+        graph.add_edge("method:B", "kafka:user-events", EdgeType::KafkaProduceConsume, Direction::Downstream);
+        
+        let mut buffer: Vec<u8> = Vec::new();
+        graph.write_jsonl(&mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        
+        // 每行都是独立的 JSON 对象，行数等于节点数加边数
+        let events: Vec<serde_json::Value> = output.lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), graph.node_count() + graph.edge_count());
+        
+        let count = |event: &str| events.iter().filter(|e| e["event"] == event).count();
+        assert_eq!(count("node"), 3);
+        assert_eq!(count("edge"), 2);
+        assert_eq!(events[0]["id"], "method:A");
+        assert_eq!(events[4]["from"], "method:B");
+        assert_eq!(events[4]["type"], "kafka_produce_consume");
+    }
+    
+    #[test]
+    fn test_contract_methods() {
+        let mut graph = ImpactGraph::new();
+        for name in ["A::caller", "B::delegate", "C::delegate", "D::target", "E::changed"] {
+            graph.add_node(ImpactNode::method(name.to_string()));
+        }
+        graph.add_node(ImpactNode::database_table("orders".to_string()));
+        graph.add_edge_with_line("method:A::caller", "method:B::delegate", EdgeType::MethodCall, Direction::Upstream, Some(12));
+        graph.add_edge("method:B::delegate", "method:C::delegate", EdgeType::MethodCall, Direction::Upstream);
+        graph.add_edge("method:C::delegate", "method:D::target", EdgeType::MethodCall, Direction::Upstream);
+        graph.add_edge("method:D::target", "method:E::changed", EdgeType::MethodCall, Direction::Upstream);
+        graph.add_edge("method:E::changed", "db:orders", EdgeType::DatabaseReadWrite, Direction::Downstream);
+        
+        let seeds = vec!["method:E::changed".to_string()];
+        let contracted = graph.contract_methods(|name| name.ends_with("::delegate") || name == "E::changed", &seeds);
+        
+        // 连续的委托一并收缩，调用方直接连接到被委托的方法并保留调用行号
+        assert_eq!(contracted.node_count(), 4);
+        assert!(contracted.get_node("method:B::delegate").is_none());
+        assert!(contracted.get_node("method:C::delegate").is_none());
+        let edge = contracted.edges().find(|e| e.from == "method:A::caller").unwrap();
+        assert_eq!(edge.to, "method:D::target");
+        assert_eq!(edge.source_line, Some(12));
+        
+        // 种子节点和有非调用边的节点不收缩
+        assert!(contracted.get_node("method:E::changed").is_some());
+        assert_eq!(contracted.edge_count(), 3);
+    }
+    
+    #[test]
+    fn test_trace_cache_reuses_seed_subgraph() {
+        let parser = crate::java_parser::JavaParser::new().unwrap();
+        let source = r#"
+package com.example;
+
+public class OrderService {
+    public void a() {
+        shared();
+    }
+    
+    public void b() {
+        shared();
+    }
+    
+    public void shared() {
+    }
+    
+    public void entry() {
+        a();
+        b();
+    }
+}
+"#;
+        let parsed = crate::language_parser::LanguageParser::parse_file(&parser, source, Path::new("OrderService.java")).unwrap();
+        let mut index = CodeIndex::new();
+        for method in &parsed.classes[0].methods {
+            index.index_method(method).unwrap();
+        }
+        
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = TraceCache::new(temp_dir.path().join("trace-cache"));
+        let seed_a = "com.example.OrderService::a".to_string();
+        let seed_b = "com.example.OrderService::b".to_string();
+        
+        let tracer = ImpactTracer::new(&index, TraceConfig::default())
+            .with_trace_cache(&cache, "checksum-1".to_string());
+        tracer.trace_impact(std::slice::from_ref(&seed_a)).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+        
+        // A 的子图来自缓存，只有 B 需要重新追溯
+        let cached = tracer.trace_impact(&[seed_a.clone(), seed_b.clone()]).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+        
+        let uncached = ImpactTracer::new(&index, TraceConfig::default())
+            .trace_impact(&[seed_a.clone(), seed_b.clone()])
+            .unwrap();
+        let node_ids = |graph: &ImpactGraph| graph.nodes().map(|node| node.id.clone()).collect::<HashSet<_>>();
+        assert_eq!(node_ids(&cached), node_ids(&uncached));
+        assert_eq!(cached.edge_count(), uncached.edge_count());
+        
+        // 索引变化后缓存项不再命中
+        let tracer = ImpactTracer::new(&index, TraceConfig::default())
+            .with_trace_cache(&cache, "checksum-2".to_string());
+        tracer.trace_impact(std::slice::from_ref(&seed_a)).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 3));
+        
+        // 追溯配置变化后缓存项不再命中
+        let tracer = ImpactTracer::new(&index, TraceConfig { max_depth: 3, ..TraceConfig::default() })
+            .with_trace_cache(&cache, "checksum-2".to_string());
+        tracer.trace_impact(std::slice::from_ref(&seed_a)).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 4));
+        
+        // 注册了自定义资源匹配器时不使用缓存
+        let tracer = ImpactTracer::new(&index, TraceConfig::default())
+            .with_resource_matchers([Arc::new(KafkaMatcher) as Arc<dyn ResourceMatcher>])
+            .with_trace_cache(&cache, "checksum-2".to_string());
+        tracer.trace_impact(std::slice::from_ref(&seed_a)).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 4));
+    }
+    
+    #[test]
+    fn test_trace_cache_evicts_least_recently_used_entries() {
+        let mut index = CodeIndex::new();
+        for name in ["A::a", "B::b", "C::c"] {
+            index.index_method(&crate::language_parser::MethodInfo::new(name, "App.java")).unwrap();
+        }
+        
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = TraceCache::new(temp_dir.path().join("trace-cache")).with_max_entries(2);
+        let tracer = ImpactTracer::new(&index, TraceConfig::default())
+            .with_trace_cache(&cache, "checksum".to_string());
+        let entry_count = || std::fs::read_dir(temp_dir.path().join("trace-cache")).unwrap().count();
+        
+        tracer.trace_impact(&["A::a".to_string(), "B::b".to_string()]).unwrap();
+        assert_eq!(entry_count(), 2);
+        tracer.trace_impact(&["C::c".to_string()]).unwrap();
+        assert_eq!(entry_count(), 2);
+        assert_eq!(cache.misses(), 3);
+    }
+    
+    #[test]
+    fn test_write_json_empty_graph() {
+        let graph = ImpactGraph::new();
+        
+        let mut buffer: Vec<u8> = Vec::new();
+        graph.write_json(&mut buffer).unwrap();
+        
+        let streamed: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(streamed["nodes"].as_array().unwrap().len(), 0);
+        assert_eq!(streamed["edges"].as_array().unwrap().len(), 0);
+        assert_eq!(streamed["node_count"], 0);
+    }
+    
+    #[test]
+    fn test_filter_by_direction() {
+        let mut graph = ImpactGraph::new();
+        
+        // 种子方法 B，上游调用者 A，下游被调用者 C
+        graph.add_node(ImpactNode::method("A".to_string()));
+        graph.add_node(ImpactNode::method("B".to_string()));
+        graph.add_node(ImpactNode::method("C".to_string()));
+        
+        graph.add_edge("method:A", "method:B", EdgeType::MethodCall, Direction::Upstream);
+        graph.add_edge("method:B", "method:C", EdgeType::MethodCall, Direction::Downstream);
+        
+        let seeds = vec!["method:B".to_string()];
+        
+        // 仅保留下游
+        let downstream = graph.filter_by_direction(&Direction::Downstream, &seeds);
+        assert_eq!(downstream.edge_count(), 1);
+        assert!(downstream.edges().all(|e| e.direction == Direction::Downstream));
+        assert!(downstream.get_node("method:A").is_none());
+        assert!(downstream.get_node("method:B").is_some());
+        assert!(downstream.get_node("method:C").is_some());
+        
+        // 仅保留上游
+        let upstream = graph.filter_by_direction(&Direction::Upstream, &seeds);
+        assert_eq!(upstream.edge_count(), 1);
+        assert!(upstream.get_node("method:A").is_some());
+        assert!(upstream.get_node("method:C").is_none());
+    }
+    
+    #[test]
+    fn test_filter_by_direction_keeps_isolated_seeds() {
+        let mut graph = ImpactGraph::new();
+        
+        // 种子 A 只有上游调用者 X
+        graph.add_node(ImpactNode::method("A".to_string()));
+        graph.add_node(ImpactNode::method("X".to_string()));
+        graph.add_edge("method:X", "method:A", EdgeType::MethodCall, Direction::Upstream);
+        
+        let seeds = vec!["method:A".to_string()];
+        let downstream = graph.filter_by_direction(&Direction::Downstream, &seeds);
+        
+        // 种子节点即使孤立也保留，其他孤立节点被移除
+        assert_eq!(downstream.node_count(), 1);
+        assert_eq!(downstream.edge_count(), 0);
+        assert!(downstream.get_node("method:A").is_some());
+    }
+    
     #[test]
     fn test_impact_tracer_creation() {
         let index = CodeIndex::new();
@@ -1155,6 +3352,9 @@ mod tests {
             trace_upstream: true,
             trace_downstream: true,
             trace_cross_service: false,
+            allowed_services: Vec::new(),
+            max_bridge_fanout: None,
+            diagnostic_filter: None,
         };
         let tracer = ImpactTracer::new(&index, config);
         
@@ -1186,6 +3386,9 @@ mod tests {
             trace_upstream: true,
             trace_downstream: false,
             trace_cross_service: false,
+            allowed_services: Vec::new(),
+            max_bridge_fanout: None,
+            diagnostic_filter: None,
         };
         let tracer = ImpactTracer::new(&index, config);
         
@@ -1201,6 +3404,9 @@ mod tests {
             trace_upstream: false,
             trace_downstream: true,
             trace_cross_service: false,
+            allowed_services: Vec::new(),
+            max_bridge_fanout: None,
+            diagnostic_filter: None,
         };
         let tracer = ImpactTracer::new(&index, config);
         
@@ -1216,6 +3422,9 @@ mod tests {
             trace_upstream: true,
             trace_downstream: true,
             trace_cross_service: false,
+            allowed_services: Vec::new(),
+            max_bridge_fanout: None,
+            diagnostic_filter: None,
         };
         let tracer = ImpactTracer::new(&index, config);
         
@@ -1445,4 +3654,93 @@ mod tests {
         // 空图也应该生成有效的 DOT 格式
         assert!(dot.contains("digraph"));
     }
+    
+    #[test]
+    fn test_bridge_summaries_for_both_directions_are_distinct() {
+        let mut graph = ImpactGraph::new();
+        let endpoint_id = "http:GET:/api/users".to_string();
+        graph.add_node(ImpactNode::http_endpoint(HttpMethod::GET, "/api/users".to_string()));
+        
+        let providers = ImpactNode::bridge_summary(endpoint_id.clone(), &Direction::Downstream, 2);
+        let consumers = ImpactNode::bridge_summary(endpoint_id.clone(), &Direction::Upstream, 5);
+        assert_eq!(providers.id, "summary:downstream:http:GET:/api/users");
+        assert_eq!(consumers.id, "summary:upstream:http:GET:/api/users");
+        
+        graph.add_node(providers);
+        graph.add_node(consumers);
+        graph.add_edge(&endpoint_id, "summary:downstream:http:GET:/api/users", EdgeType::HttpCall, Direction::Downstream);
+        graph.add_edge("summary:upstream:http:GET:/api/users", &endpoint_id, EdgeType::HttpCall, Direction::Upstream);
+        
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+        assert!(matches!(
+            graph.get_node("summary:upstream:http:GET:/api/users").unwrap().node_type,
+            NodeType::BridgeSummary { omitted: 5, .. }
+        ));
+    }
+    
+    #[test]
+    fn test_every_node_type_renders_and_round_trips() {
+        let node_types = vec![
+            NodeType::Method { qualified_name: "A::a".to_string() },
+            NodeType::HttpEndpoint { path: "/api".to_string(), method: "GET".to_string() },
+            NodeType::KafkaTopic { name: "topic".to_string() },
+            NodeType::DatabaseTable { name: "orders".to_string() },
+            NodeType::RedisPrefix { prefix: "order:".to_string() },
+            NodeType::RpcMethod { interface: "com.example.Api".to_string(), method: "call".to_string() },
+            NodeType::ThriftMethod { service: "OrderService".to_string(), method: "getOrder".to_string() },
+            NodeType::GraphQLField { type_name: "Query".to_string(), field: "order".to_string() },
+            NodeType::BridgeSummary { endpoint: "http:GET:/api".to_string(), omitted: 3 },
+            NodeType::BatchJob { name: "importJob".to_string() },
+        ];
+        let edge_types = vec![
+            EdgeType::MethodCall,
+            EdgeType::HttpCall,
+            EdgeType::KafkaProduceConsume,
+            EdgeType::DatabaseReadWrite,
+            EdgeType::RedisReadWrite,
+            EdgeType::RpcCall,
+            EdgeType::ThriftCall,
+            EdgeType::GraphQLResolve,
+            EdgeType::BatchLaunch,
+        ];
+        
+        let mut graph = ImpactGraph::new();
+        for (i, node_type) in node_types.iter().enumerate() {
+            graph.add_node(ImpactNode {
+                id: format!("n{}", i),
+                node_type: node_type.clone(),
+                metadata: NodeMetadata {
+                    label: format!("node {}", i),
+                    properties: HashMap::new(),
+                },
+            });
+        }
+        for (i, edge_type) in edge_types.iter().enumerate() {
+            graph.add_edge(&format!("n{}", i), &format!("n{}", i + 1), edge_type.clone(), Direction::Downstream);
+        }
+        
+        let dot = graph.to_dot();
+        for node_type in &node_types {
+            assert!(dot.contains(&format!("shape=\"{}\" type=\"{}\"", node_type.dot_shape(), node_type.type_tag())));
+        }
+        for edge_type in &edge_types {
+            assert!(dot.contains(&format!("label=\"{}\"", edge_type.type_tag())));
+        }
+        
+        let json = graph.to_json().unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["nodes"][7]["type"]["kind"], "graphql_field");
+        assert_eq!(parsed["nodes"][8]["type"]["omitted"], 3);
+        assert_eq!(parsed["edges"][7]["type"], "graphql_resolve");
+        assert_eq!(parsed["nodes"][9]["type"]["kind"], "batch_job");
+        assert_eq!(parsed["edges"][8]["type"], "batch_launch");
+        assert_eq!(parsed["edges"][0]["direction"], "downstream");
+        
+        let reloaded = ImpactGraph::from_json(&json).unwrap();
+        let reloaded_types: Vec<NodeType> = reloaded.nodes().map(|node| node.node_type.clone()).collect();
+        assert_eq!(reloaded_types, node_types);
+        let reloaded_edges: Vec<EdgeType> = reloaded.edges().map(|edge| edge.edge_type.clone()).collect();
+        assert_eq!(reloaded_edges, edge_types);
+    }
 }
@@ -1,14 +1,19 @@
 use std::path::{Path, PathBuf};
 use std::fs;
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use crate::code_index::CodeIndex;
+use crate::impact_tracer::service_from_path;
 use crate::language_parser::MethodInfo;
+use crate::config_parser::ConfigData;
+use crate::types::{BatchJobDefinition, FeignClientConfig, ThriftService};
 use crate::errors::IndexError;
 
 /// 索引格式版本
-const INDEX_VERSION: &str = "1.0.0";
+const INDEX_VERSION: &str = "2.0.0";
 
 /// 索引目录名称
 const INDEX_DIR: &str = ".code-impact-analyzer";
@@ -19,6 +24,12 @@ const META_FILE: &str = "index.meta.json";
 /// 索引数据文件名
 const INDEX_FILE: &str = "index.json";
 
+/// 按服务分区保存的索引目录名，每个服务一个 `<service>.json`
+const SERVICES_DIR: &str = "services";
+
+/// 内置语言解析器和配置解析器处理的文件扩展名，编排器会按实际注册的解析器覆盖
+pub const DEFAULT_SOURCE_EXTENSIONS: &[&str] = &["cs", "java", "rs", "sql", "thrift", "xml", "yaml", "yml"];
+
 /// 索引元数据
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexMetadata {
@@ -46,13 +57,19 @@ pub struct IndexMetadata {
 
 impl IndexMetadata {
     /// 创建新的元数据
-    pub fn new(workspace_path: PathBuf, file_count: usize, method_count: usize) -> Self {
+    /// 
+    /// # Arguments
+    /// * `workspace_path` - 工作空间路径
+    /// * `file_count` - 索引的文件总数
+    /// * `method_count` - 索引的方法总数
+    /// * `extensions` - 计入校验和的源文件和配置文件扩展名
+    pub fn new(workspace_path: PathBuf, file_count: usize, method_count: usize, extensions: &[String]) -> Self {
         let now = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap()
             .as_secs();
         
-        let checksum = Self::calculate_checksum(&workspace_path);
+        let checksum = Self::calculate_checksum(&workspace_path, extensions);
         
         Self {
             version: INDEX_VERSION.to_string(),
@@ -67,15 +84,15 @@ impl IndexMetadata {
     
     /// 计算工作空间校验和
     /// 
-    /// 基于工作空间中所有源文件的修改时间计算校验和
-    fn calculate_checksum(workspace_path: &Path) -> String {
+    /// 基于工作空间中扩展名属于 `extensions` 的所有文件的修改时间计算校验和
+    fn calculate_checksum(workspace_path: &Path, extensions: &[String]) -> String {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
         
         let mut hasher = DefaultHasher::new();
         
         // 遍历工作空间，收集所有源文件的修改时间
-        if let Ok(entries) = Self::collect_file_mtimes(workspace_path) {
+        if let Ok(entries) = Self::collect_file_mtimes(workspace_path, extensions) {
             for (path, mtime) in entries {
                 path.hash(&mut hasher);
                 mtime.hash(&mut hasher);
@@ -86,7 +103,7 @@ impl IndexMetadata {
     }
     
     /// 收集文件修改时间
-    fn collect_file_mtimes(dir: &Path) -> Result<Vec<(PathBuf, u64)>, std::io::Error> {
+    fn collect_file_mtimes(dir: &Path, extensions: &[String]) -> Result<Vec<(PathBuf, u64)>, std::io::Error> {
         let mut result = Vec::new();
         
         if !dir.is_dir() {
@@ -98,39 +115,39 @@ impl IndexMetadata {
             let path = entry.path();
             
             // 跳过隐藏目录和构建目录
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.starts_with('.') || name == "target" || name == "build" || name == "node_modules" {
-                    continue;
-                }
+            if let Some(name) = path.file_name().and_then(|n| n.to_str())
+                && (name.starts_with('.') || name == "target" || name == "build" || name == "node_modules")
+            {
+                continue;
             }
             
             if path.is_dir() {
-                result.extend(Self::collect_file_mtimes(&path)?);
-            } else if Self::is_source_file(&path) {
-                if let Ok(metadata) = fs::metadata(&path) {
-                    if let Ok(modified) = metadata.modified() {
-                        if let Ok(duration) = modified.duration_since(SystemTime::UNIX_EPOCH) {
-                            result.push((path, duration.as_secs()));
-                        }
-                    }
-                }
+                result.extend(Self::collect_file_mtimes(&path, extensions)?);
+            } else if Self::is_source_file(&path, extensions)
+                && let Ok(metadata) = fs::metadata(&path)
+                && let Ok(modified) = metadata.modified()
+                && let Ok(duration) = modified.duration_since(SystemTime::UNIX_EPOCH)
+            {
+                result.push((path, duration.as_secs()));
             }
         }
         
         Ok(result)
     }
     
-    /// 判断是否是源文件
-    fn is_source_file(path: &Path) -> bool {
-        if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-            matches!(ext, "java" | "rs" | "kt" | "scala" | "go" | "py" | "js" | "ts")
-        } else {
-            false
-        }
+    /// 判断文件的扩展名是否属于 `extensions`（不区分大小写）
+    fn is_source_file(path: &Path, extensions: &[String]) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
     }
     
     /// 验证元数据是否有效
-    pub fn is_valid(&self, workspace_path: &Path) -> bool {
+    /// 
+    /// # Arguments
+    /// * `workspace_path` - 工作空间路径
+    /// * `extensions` - 计入校验和的源文件和配置文件扩展名，应与构建索引时相同
+    pub fn is_valid(&self, workspace_path: &Path, extensions: &[String]) -> bool {
         // 检查版本兼容性
         if !self.is_version_compatible() {
             log::warn!("Index version {} is not compatible with current version {}", 
@@ -146,7 +163,7 @@ impl IndexMetadata {
         }
         
         // 检查校验和
-        let current_checksum = Self::calculate_checksum(workspace_path);
+        let current_checksum = Self::calculate_checksum(workspace_path, extensions);
         if self.checksum != current_checksum {
             log::warn!("Workspace checksum mismatch: index may be outdated");
             return false;
@@ -166,7 +183,7 @@ impl IndexMetadata {
 }
 
 /// 可序列化的索引数据
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SerializableIndex {
     /// 方法信息映射
     pub methods: HashMap<String, MethodInfo>,
@@ -203,6 +220,149 @@ pub struct SerializableIndex {
     
     /// 配置关联映射
     pub config_associations: HashMap<String, Vec<String>>,
+    
+    /// Feign 客户端配置映射: 模块 -> 客户端名 -> 配置
+    #[serde(default)]
+    pub feign_client_configs: HashMap<String, HashMap<String, FeignClientConfig>>,
+    
+    /// Thrift IDL 服务定义映射
+    #[serde(default)]
+    pub thrift_services: HashMap<String, Vec<String>>,
+    
+    /// Spring Batch XML 作业映射: 步骤组件方法 -> [job_names]
+    #[serde(default)]
+    pub batch_jobs: HashMap<String, Vec<String>>,
+    
+    /// 配置文件中声明的数据库表
+    #[serde(default)]
+    pub declared_db_tables: Vec<String>,
+    
+    /// 类继承关系映射: 子类 -> 父类
+    #[serde(default)]
+    pub class_superclasses: HashMap<String, String>,
+    
+    /// 构建索引时的工作空间路径，在其他位置加载索引时用于换算源文件路径
+    #[serde(default)]
+    pub workspace_path: Option<PathBuf>,
+    
+    /// 构建索引时解析失败的源文件
+    #[serde(default)]
+    pub parse_failures: Vec<PathBuf>,
+    
+    /// 构建索引时存在语法错误的源文件中各错误节点的位置
+    #[serde(default)]
+    pub syntax_errors: HashMap<PathBuf, Vec<(usize, usize)>>,
+}
+
+/// 索引来源
+pub enum IndexSource {
+    /// 工作空间下的本地索引，不存在或已过期时重新构建并保存
+    Local(IndexStorage),
+    /// 集中构建、通过 HTTP 提供的索引，响应体与本地 `index.json` 格式相同
+    /// 
+    /// 需要启用 `remote-index` 特性
+    Remote { url: String },
+}
+
+/// 内存索引缓存条目
+struct CacheEntry {
+    /// 工作空间路径
+    workspace_path: PathBuf,
+    
+    /// 加载时索引元数据中的工作空间校验和
+    checksum: String,
+    
+    /// 放入缓存的时间
+    cached_at: Instant,
+    
+    /// 缓存的索引，命中时共享而不复制
+    index: Arc<CodeIndex>,
+}
+
+/// 内存索引缓存
+/// 
+/// 常驻进程中重复分析同一工作空间时，在 TTL 内直接返回缓存的 `Arc<CodeIndex>`，
+/// 不读取磁盘索引；TTL 过期后重新从磁盘加载并校验。
+/// 条目按 (工作空间, 校验和) 区分，查找时重新计算工作空间校验和，源文件变化后不会命中旧索引；
+/// 超出容量时淘汰最久未使用的条目。可通过 `Arc` 在多个 `IndexStorage` 之间共享
+pub struct IndexCache {
+    /// 缓存条目的有效期
+    ttl: Duration,
+    
+    /// 最多缓存的工作空间数
+    capacity: usize,
+    
+    /// 缓存条目，按最近使用顺序排列（最近使用的在末尾）
+    entries: Mutex<Vec<CacheEntry>>,
+    
+    /// 实际从磁盘加载索引的次数
+    disk_loads: AtomicUsize,
+}
+
+impl IndexCache {
+    /// 创建内存索引缓存
+    /// 
+    /// # Arguments
+    /// * `ttl` - 缓存条目的有效期
+    /// * `capacity` - 最多缓存的工作空间数，至少为 1
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity: capacity.max(1),
+            entries: Mutex::new(Vec::new()),
+            disk_loads: AtomicUsize::new(0),
+        }
+    }
+    
+    /// 实际从磁盘加载索引的次数，未命中缓存时递增
+    pub fn disk_loads(&self) -> usize {
+        self.disk_loads.load(Ordering::Relaxed)
+    }
+    
+    /// 清空缓存
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+    
+    /// 获取工作空间在 TTL 内、校验和相同的缓存索引，并将其标记为最近使用
+    fn get(&self, workspace_path: &Path, checksum: &str) -> Option<Arc<CodeIndex>> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|entry| entry.cached_at.elapsed() < self.ttl);
+        
+        let pos = entries.iter()
+            .position(|entry| entry.workspace_path == workspace_path && entry.checksum == checksum)?;
+        let entry = entries.remove(pos);
+        let index = Arc::clone(&entry.index);
+        entries.push(entry);
+        Some(index)
+    }
+    
+    /// 缓存工作空间的索引，替换该工作空间的旧条目
+    fn insert(&self, workspace_path: &Path, checksum: String, index: Arc<CodeIndex>) {
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(pos) = entries.iter().position(|entry| entry.workspace_path == workspace_path) {
+            let old = entries.remove(pos);
+            if old.checksum != checksum {
+                log::debug!("Workspace {:?} changed, replacing cached index", workspace_path);
+            }
+        }
+        
+        if entries.len() >= self.capacity {
+            entries.remove(0);
+        }
+        
+        entries.push(CacheEntry {
+            workspace_path: workspace_path.to_path_buf(),
+            checksum,
+            cached_at: Instant::now(),
+            index,
+        });
+    }
+    
+    /// 移除工作空间的缓存条目
+    fn invalidate(&self, workspace_path: &Path) {
+        self.entries.lock().unwrap().retain(|entry| entry.workspace_path != workspace_path);
+    }
 }
 
 /// 索引存储管理器
@@ -212,6 +372,15 @@ pub struct IndexStorage {
     
     /// 索引目录路径
     index_dir: PathBuf,
+    
+    /// 内存索引缓存，为空表示每次都从磁盘加载
+    cache: Option<Arc<IndexCache>>,
+    
+    /// 静默模式，启用后不输出加载和保存索引的进度日志
+    quiet: bool,
+    
+    /// 计入工作空间校验和的文件扩展名
+    source_extensions: Vec<String>,
 }
 
 impl IndexStorage {
@@ -222,9 +391,46 @@ impl IndexStorage {
         Self {
             workspace_path,
             index_dir,
+            cache: None,
+            quiet: false,
+            source_extensions: DEFAULT_SOURCE_EXTENSIONS.iter().map(|ext| ext.to_string()).collect(),
         }
     }
     
+    /// 使用指定的索引目录代替工作空间下的默认目录
+    /// 
+    /// 工作空间只读时可以从其他位置加载预先构建的索引
+    /// 
+    /// # Arguments
+    /// * `index_dir` - 存放 `index.json` 和 `index.meta.json` 的目录
+    pub fn with_index_dir(mut self, index_dir: impl Into<PathBuf>) -> Self {
+        self.index_dir = index_dir.into();
+        self
+    }
+    
+    /// 设置静默模式，启用后不输出加载和保存索引的进度日志
+    pub fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+    
+    /// 设置计入工作空间校验和的文件扩展名
+    /// 
+    /// 应为构建索引所用的语言解析器和配置解析器处理的全部扩展名，
+    /// 这些文件变化或解析器集合变化时磁盘索引失效
+    pub fn set_source_extensions(&mut self, extensions: Vec<String>) {
+        self.source_extensions = extensions;
+    }
+    
+    /// 获取计入工作空间校验和的文件扩展名
+    pub fn source_extensions(&self) -> &[String] {
+        &self.source_extensions
+    }
+    
+    /// 设置内存索引缓存，TTL 内工作空间未变化时直接返回缓存的索引
+    pub fn set_cache(&mut self, cache: Arc<IndexCache>) {
+        self.cache = Some(cache);
+    }
+    
     /// 检查索引是否存在
     pub fn index_exists(&self) -> bool {
         self.meta_file_path().exists() && self.index_file_path().exists()
@@ -232,14 +438,25 @@ impl IndexStorage {
     
     /// 加载索引
     /// 
+    /// 设置内存缓存时，返回的索引与缓存共享
+    /// 
     /// # Returns
-    /// * `Ok(Some(CodeIndex))` - 成功加载索引
+    /// * `Ok(Some(Arc<CodeIndex>))` - 成功加载索引
     /// * `Ok(None)` - 索引不存在或无效
     /// * `Err(IndexError)` - 加载失败
-    pub fn load_index(&self) -> Result<Option<CodeIndex>, IndexError> {
+    pub fn load_index(&self) -> Result<Option<Arc<CodeIndex>>, IndexError> {
+        // TTL 内工作空间未变化时命中内存缓存，不读取磁盘索引
+        if let Some(cache) = &self.cache {
+            let checksum = IndexMetadata::calculate_checksum(&self.workspace_path, &self.source_extensions);
+            if let Some(index) = cache.get(&self.workspace_path, &checksum) {
+                progress!(self.quiet, "Index loaded from memory cache");
+                return Ok(Some(index));
+            }
+        }
+        
         // 检查索引文件是否存在
         if !self.index_exists() {
-            log::info!("Index files not found, will build new index");
+            progress!(self.quiet, "Index files not found, will build new index");
             return Ok(None);
         }
         
@@ -247,27 +464,33 @@ impl IndexStorage {
         let metadata = self.load_metadata()?;
         
         // 验证元数据
-        if !metadata.is_valid(&self.workspace_path) {
-            log::info!("Index is invalid or outdated, will rebuild");
+        if !metadata.is_valid(&self.workspace_path, &self.source_extensions) {
+            progress!(self.quiet, "Index is invalid or outdated, will rebuild");
             return Ok(None);
         }
         
-        log::info!("Loading index from {:?}", self.index_dir);
+        progress!(self.quiet, "Loading index from {:?}", self.index_dir);
         
         // 加载索引数据
         let serializable = self.load_index_data()?;
         
         // 转换为 CodeIndex
-        let code_index = self.deserialize_index(serializable)?;
+        let code_index = deserialize_index(serializable, &self.workspace_path)?;
+        
+        progress!(self.quiet, "Index loaded successfully: {} methods", metadata.method_count);
         
-        log::info!("Index loaded successfully: {} methods", metadata.method_count);
+        let code_index = Arc::new(code_index);
+        if let Some(cache) = &self.cache {
+            cache.disk_loads.fetch_add(1, Ordering::Relaxed);
+            cache.insert(&self.workspace_path, metadata.checksum, Arc::clone(&code_index));
+        }
         
         Ok(Some(code_index))
     }
     
     /// 保存索引
     pub fn save_index(&self, code_index: &CodeIndex) -> Result<(), IndexError> {
-        log::info!("Saving index to {:?}", self.index_dir);
+        progress!(self.quiet, "Saving index to {:?}", self.index_dir);
         
         // 创建索引目录
         self.ensure_index_dir()?;
@@ -287,6 +510,7 @@ impl IndexStorage {
             self.workspace_path.clone(),
             file_count,
             method_count,
+            &self.source_extensions,
         );
         
         // 保存元数据
@@ -295,14 +519,97 @@ impl IndexStorage {
         // 保存索引数据
         self.save_index_data(&serializable)?;
         
-        log::info!("Index saved successfully: {} methods in {} files", 
+        // 按服务分区保存，便于只加载部分服务
+        self.save_service_partitions(&serializable)?;
+        
+        if let Some(cache) = &self.cache {
+            cache.insert(&self.workspace_path, metadata.checksum, Arc::new(code_index.clone()));
+        }
+        
+        progress!(self.quiet, "Index saved successfully: {} methods in {} files", 
                   method_count, file_count);
         
         Ok(())
     }
     
+    /// 只加载指定服务的索引
+    /// 
+    /// 从按服务分区保存的索引中读取，只反序列化这些服务的方法，适用于限定服务追溯的大型工作空间。
+    /// 其他服务的方法不在返回的索引中，跨服务的 HTTP/Kafka 等关联只保留指定服务一侧。
+    /// 结果不写入内存缓存，避免之后的完整加载命中部分索引
+    /// 
+    /// # Arguments
+    /// * `services` - 服务名（工作空间下的顶层目录名）列表
+    /// 
+    /// # Returns
+    /// * `Ok(Some(CodeIndex))` - 成功加载指定服务的索引
+    /// * `Ok(None)` - 索引不存在、无效或没有按服务分区保存
+    /// * `Err(IndexError)` - 加载失败
+    pub fn load_index_for_services(&self, services: &[String]) -> Result<Option<CodeIndex>, IndexError> {
+        if !self.index_exists() || !self.services_dir_path().is_dir() {
+            progress!(self.quiet, "Partitioned index not found, will build new index");
+            return Ok(None);
+        }
+        
+        let metadata = self.load_metadata()?;
+        if !metadata.is_valid(&self.workspace_path, &self.source_extensions) {
+            progress!(self.quiet, "Index is invalid or outdated, will rebuild");
+            return Ok(None);
+        }
+        
+        progress!(self.quiet, "Loading index for services {:?} from {:?}", services, self.index_dir);
+        
+        // 各分区的共享数据（配置、类继承关系）相同，方法按服务合并
+        let mut merged: Option<SerializableIndex> = None;
+        for service in services {
+            let path = self.service_partition_path(service);
+            if !path.exists() {
+                log::debug!("No indexed methods for service {}", service);
+                continue;
+            }
+            let partition = read_index_data(&path)?;
+            match &mut merged {
+                Some(merged) => merged.methods.extend(partition.methods),
+                None => merged = Some(partition),
+            }
+        }
+        
+        let code_index = deserialize_index(merged.unwrap_or_default(), &self.workspace_path)?;
+        progress!(self.quiet, "Index loaded successfully: {} methods", code_index.methods().count());
+        
+        Ok(Some(code_index))
+    }
+    
+    /// 加载上一次保存的索引，不检查索引是否过期
+    /// 
+    /// 用于查找已从工作空间中删除的文件原有的方法：文件删除后索引校验和变化，
+    /// `load_index` 不再返回该索引，而重建的索引中没有这些方法
+    /// 
+    /// # Returns
+    /// * `Ok(Some(CodeIndex))` - 上一次保存的索引
+    /// * `Ok(None)` - 索引不存在或版本不兼容
+    /// * `Err(IndexError)` - 加载失败
+    pub fn load_previous_index(&self) -> Result<Option<CodeIndex>, IndexError> {
+        if !self.index_exists() {
+            return Ok(None);
+        }
+        
+        let metadata = self.load_metadata()?;
+        if !metadata.is_version_compatible() {
+            progress!(self.quiet, "Previous index version {} is incompatible", metadata.version);
+            return Ok(None);
+        }
+        
+        let code_index = deserialize_index(self.load_index_data()?, &self.workspace_path)?;
+        Ok(Some(code_index))
+    }
+    
     /// 清除索引
     pub fn clear_index(&self) -> Result<(), IndexError> {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(&self.workspace_path);
+        }
+        
         if self.index_dir.exists() {
             fs::remove_dir_all(&self.index_dir)
                 .map_err(|e| IndexError::IoError {
@@ -310,7 +617,7 @@ impl IndexStorage {
                     error: e.to_string(),
                 })?;
             
-            log::info!("Index cleared");
+            progress!(self.quiet, "Index cleared");
         }
         
         Ok(())
@@ -351,6 +658,16 @@ impl IndexStorage {
         self.index_dir.join(INDEX_FILE)
     }
     
+    /// 获取按服务分区的索引目录路径
+    fn services_dir_path(&self) -> PathBuf {
+        self.index_dir.join(SERVICES_DIR)
+    }
+    
+    /// 获取服务分区文件路径
+    fn service_partition_path(&self, service: &str) -> PathBuf {
+        self.services_dir_path().join(format!("{}.json", service))
+    }
+    
     /// 加载元数据
     fn load_metadata(&self) -> Result<IndexMetadata, IndexError> {
         let path = self.meta_file_path();
@@ -383,32 +700,41 @@ impl IndexStorage {
     
     /// 加载索引数据
     fn load_index_data(&self) -> Result<SerializableIndex, IndexError> {
-        let path = self.index_file_path();
-        let content = fs::read_to_string(&path)
-            .map_err(|e| IndexError::IoError {
-                path: path.clone(),
-                error: e.to_string(),
-            })?;
-        
-        serde_json::from_str(&content)
-            .map_err(|e| IndexError::SerializationError {
-                message: format!("Failed to parse index data: {}", e),
-            })
+        read_index_data(&self.index_file_path())
     }
     
     /// 保存索引数据
     fn save_index_data(&self, data: &SerializableIndex) -> Result<(), IndexError> {
-        let path = self.index_file_path();
-        let content = serde_json::to_string_pretty(data)
-            .map_err(|e| IndexError::SerializationError {
-                message: format!("Failed to serialize index data: {}", e),
-            })?;
+        write_index_data(&self.index_file_path(), data)
+    }
+    
+    /// 按服务分区保存索引数据，先清除上次保存的分区
+    /// 
+    /// 方法按文件路径的第一级目录归入服务，不在任何服务目录下的方法不写入分区
+    fn save_service_partitions(&self, data: &SerializableIndex) -> Result<(), IndexError> {
+        let dir = self.services_dir_path();
+        let io_error = |e: std::io::Error| IndexError::IoError {
+            path: dir.clone(),
+            error: e.to_string(),
+        };
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(io_error)?;
+        }
+        fs::create_dir_all(&dir).map_err(io_error)?;
         
-        fs::write(&path, content)
-            .map_err(|e| IndexError::IoError {
-                path: path.clone(),
-                error: e.to_string(),
-            })
+        let mut members: HashMap<String, HashSet<&String>> = HashMap::new();
+        for (name, method) in &data.methods {
+            let relative = method.file_path.strip_prefix(&self.workspace_path).unwrap_or(&method.file_path);
+            if let Some(service) = service_from_path(relative) {
+                members.entry(service).or_default().insert(name);
+            }
+        }
+        
+        for (service, members) in &members {
+            write_index_data(&self.service_partition_path(service), &partition_index(data, members))?;
+        }
+        
+        Ok(())
     }
     
     /// 序列化 CodeIndex
@@ -443,7 +769,7 @@ impl IndexStorage {
         
         // 遍历所有方法，查找 HTTP 注解
         for (name, method) in &methods {
-            if let Some(http_ann) = &method.http_annotations {
+            for http_ann in method.all_http_annotations() {
                 let key = format!("{}:{}", 
                     match http_ann.method {
                         crate::types::HttpMethod::GET => "GET",
@@ -451,6 +777,7 @@ impl IndexStorage {
                         crate::types::HttpMethod::PUT => "PUT",
                         crate::types::HttpMethod::DELETE => "DELETE",
                         crate::types::HttpMethod::PATCH => "PATCH",
+                        crate::types::HttpMethod::Any => "ANY",
                     },
                     http_ann.path
                 );
@@ -532,6 +859,36 @@ impl IndexStorage {
         // 配置关联（暂时为空，需要从 CodeIndex 获取）
         let config_associations = HashMap::new();
         
+        // Feign 客户端配置
+        let mut feign_client_configs: HashMap<String, HashMap<String, FeignClientConfig>> = HashMap::new();
+        for (module, name, config) in code_index.feign_client_configs() {
+            feign_client_configs.entry(module.clone()).or_default().insert(name.clone(), config.clone());
+        }
+        
+        // Thrift IDL 服务定义
+        let thrift_services = code_index.thrift_services()
+            .map(|(name, methods)| (name.clone(), methods.clone()))
+            .collect();
+        
+        // Spring Batch XML 作业
+        let batch_jobs = code_index.batch_jobs()
+            .map(|(method, jobs)| (method.clone(), jobs.clone()))
+            .collect();
+        
+        // 声明的数据库表
+        let mut declared_db_tables: Vec<String> = code_index.declared_db_tables().cloned().collect();
+        declared_db_tables.sort();
+        
+        // 类继承关系
+        let class_superclasses = code_index.class_superclasses()
+            .map(|(class_name, superclass)| (class_name.clone(), superclass.clone()))
+            .collect();
+        
+        // 解析诊断，从磁盘加载索引时仍能报告（`--strict` 据此判断）
+        let syntax_errors = code_index.syntax_error_files().into_iter()
+            .map(|file| (file.to_path_buf(), code_index.syntax_error_locations(file).to_vec()))
+            .collect();
+        
         Ok(SerializableIndex {
             methods,
             method_calls,
@@ -545,23 +902,173 @@ impl IndexStorage {
             redis_writers,
             redis_readers,
             config_associations,
+            feign_client_configs,
+            thrift_services,
+            batch_jobs,
+            declared_db_tables,
+            class_superclasses,
+            workspace_path: Some(self.workspace_path.clone()),
+            parse_failures: code_index.parse_failures().to_vec(),
+            syntax_errors,
         })
     }
+}
+
+/// 读取索引数据文件
+fn read_index_data(path: &Path) -> Result<SerializableIndex, IndexError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| IndexError::IoError {
+            path: path.to_path_buf(),
+            error: e.to_string(),
+        })?;
     
-    /// 反序列化为 CodeIndex
-    fn deserialize_index(&self, data: SerializableIndex) -> Result<CodeIndex, IndexError> {
-        let mut code_index = CodeIndex::new();
-        
-        // 重建索引
-        for (_, method) in data.methods {
-            code_index.test_index_method(&method)
-                .map_err(|e| IndexError::SerializationError {
-                    message: format!("Failed to rebuild index: {}", e),
-                })?;
+    serde_json::from_str(&content)
+        .map_err(|e| IndexError::SerializationError {
+            message: format!("Failed to parse index data: {}", e),
+        })
+}
+
+/// 写入索引数据文件
+fn write_index_data(path: &Path, data: &SerializableIndex) -> Result<(), IndexError> {
+    let content = serde_json::to_string_pretty(data)
+        .map_err(|e| IndexError::SerializationError {
+            message: format!("Failed to serialize index data: {}", e),
+        })?;
+    
+    fs::write(path, content)
+        .map_err(|e| IndexError::IoError {
+            path: path.to_path_buf(),
+            error: e.to_string(),
+        })
+}
+
+/// 取出索引数据中属于指定方法集合的部分
+/// 
+/// 只保留集合内的方法，调用关系、HTTP/Kafka/数据库/Redis 等按方法的映射在加载时由方法重建，
+/// 不写入分区；配置、Thrift 服务定义、类继承关系和解析诊断为各服务共享，完整保留
+fn partition_index(data: &SerializableIndex, members: &HashSet<&String>) -> SerializableIndex {
+    SerializableIndex {
+        methods: data.methods.iter()
+            .filter(|(name, _)| members.contains(name))
+            .map(|(name, method)| (name.clone(), method.clone()))
+            .collect(),
+        config_associations: data.config_associations.clone(),
+        feign_client_configs: data.feign_client_configs.clone(),
+        thrift_services: data.thrift_services.clone(),
+        batch_jobs: data.batch_jobs.clone(),
+        declared_db_tables: data.declared_db_tables.clone(),
+        class_superclasses: data.class_superclasses.clone(),
+        workspace_path: data.workspace_path.clone(),
+        parse_failures: data.parse_failures.clone(),
+        syntax_errors: data.syntax_errors.clone(),
+        ..SerializableIndex::default()
+    }
+}
+
+/// 反序列化为 CodeIndex
+/// 
+/// 索引构建时的工作空间路径与 `workspace_path` 不同时，方法的源文件路径换算到 `workspace_path` 下
+fn deserialize_index(data: SerializableIndex, workspace_path: &Path) -> Result<CodeIndex, IndexError> {
+    let mut code_index = CodeIndex::new();
+    let rebase = |path: PathBuf| -> PathBuf {
+        match &data.workspace_path {
+            Some(root) if root != workspace_path => match path.strip_prefix(root) {
+                Ok(relative) => workspace_path.join(relative),
+                Err(_) => path,
+            },
+            _ => path,
         }
-        
-        Ok(code_index)
+    };
+    
+    // 重建索引
+    for (_, mut method) in data.methods {
+        method.file_path = rebase(method.file_path);
+        code_index.test_index_method(&method)
+            .map_err(|e| IndexError::SerializationError {
+                message: format!("Failed to rebuild index: {}", e),
+            })?;
+    }
+    
+    // 恢复类继承关系
+    for (class_name, superclass) in &data.class_superclasses {
+        code_index.index_superclass(class_name, superclass);
+    }
+    
+    // 恢复 Thrift 服务定义、Spring Batch XML 作业和声明的数据库表
+    code_index.associate_config_data(&ConfigData {
+        db_tables: data.declared_db_tables,
+        thrift_services: data.thrift_services.into_iter()
+            .map(|(name, methods)| ThriftService { name, methods })
+            .collect(),
+        batch_jobs: data.batch_jobs.into_iter()
+            .flat_map(|(method, jobs)| jobs.into_iter()
+                .map(move |name| BatchJobDefinition { name, methods: vec![method.clone()] }))
+            .collect(),
+        ..ConfigData::default()
+    });
+    
+    // 恢复各模块的 Feign 客户端配置
+    for (module, feign_client_configs) in data.feign_client_configs {
+        code_index.associate_module_config_data(&module, &ConfigData {
+            feign_client_configs,
+            ..ConfigData::default()
+        });
     }
+    
+    // 恢复解析诊断
+    code_index.restore_parse_diagnostics(
+        data.parse_failures.into_iter().map(rebase).collect(),
+        data.syntax_errors.into_iter().map(|(file, locations)| (rebase(file), locations)),
+    );
+    
+    Ok(code_index)
+}
+
+/// 解析序列化的索引数据（`index.json` 的内容）
+/// 
+/// # Arguments
+/// * `content` - 索引数据 JSON
+/// * `workspace_path` - 本地工作空间路径，方法的源文件路径换算到该目录下
+/// 
+/// # Returns
+/// * `Ok(CodeIndex)` - 重建的索引
+/// * `Err(IndexError)` - 数据格式错误
+pub fn parse_index_data(content: &str, workspace_path: &Path) -> Result<CodeIndex, IndexError> {
+    let data: SerializableIndex = serde_json::from_str(content)
+        .map_err(|e| IndexError::SerializationError {
+            message: format!("Failed to parse index data: {}", e),
+        })?;
+    deserialize_index(data, workspace_path)
+}
+
+/// 通过 HTTP 获取集中构建的索引
+/// 
+/// # Arguments
+/// * `url` - 索引地址，响应体为 `index.json` 格式
+/// * `workspace_path` - 本地工作空间路径，方法的源文件路径换算到该目录下
+/// 
+/// # Returns
+/// * `Ok(CodeIndex)` - 获取的索引
+/// * `Err(IndexError)` - 请求失败、响应状态码错误或数据格式错误
+#[cfg(feature = "remote-index")]
+pub fn fetch_remote_index(url: &str, workspace_path: &Path) -> Result<CodeIndex, IndexError> {
+    let content = reqwest::blocking::get(url)
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .map_err(|e| IndexError::IoError {
+            path: PathBuf::from(url),
+            error: e.to_string(),
+        })?;
+    parse_index_data(&content, workspace_path)
+}
+
+/// 通过 HTTP 获取集中构建的索引（未启用 `remote-index` 特性时总是返回错误）
+#[cfg(not(feature = "remote-index"))]
+pub fn fetch_remote_index(url: &str, _workspace_path: &Path) -> Result<CodeIndex, IndexError> {
+    Err(IndexError::IoError {
+        path: PathBuf::from(url),
+        error: "remote index support requires the `remote-index` feature".to_string(),
+    })
 }
 
 #[cfg(test)]
@@ -574,7 +1081,8 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let workspace_path = temp_dir.path().to_path_buf();
         
-        let metadata = IndexMetadata::new(workspace_path.clone(), 10, 100);
+        let extensions = vec!["java".to_string()];
+        let metadata = IndexMetadata::new(workspace_path.clone(), 10, 100, &extensions);
         
         assert_eq!(metadata.version, INDEX_VERSION);
         assert_eq!(metadata.workspace_path, workspace_path);
@@ -630,6 +1138,64 @@ mod tests {
         assert!(!storage.index_exists());
     }
     
+    /// 构造位于指定文件中的最小方法信息
+    fn method_in(file_path: PathBuf, qualified_name: &str) -> MethodInfo {
+        MethodInfo {
+            line_range: (1, 5),
+            ..MethodInfo::new(qualified_name, file_path)
+        }
+    }
+    
+    #[test]
+    fn test_load_index_for_services() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path().to_path_buf();
+        
+        let mut code_index = CodeIndex::new();
+        code_index.test_index_method(&MethodInfo {
+            calls: vec![crate::language_parser::MethodCall {
+                target: "com.example.order.OrderRepository::save".to_string(),
+                line: 3,
+                condition: None,
+                low_confidence: false,
+            }],
+            ..method_in(
+                workspace_path.join("order-service/src/OrderService.java"),
+                "com.example.order.OrderService::create",
+            )
+        }).unwrap();
+        code_index.test_index_method(&method_in(
+            workspace_path.join("user-service/src/UserService.java"),
+            "com.example.user.UserService::find",
+        )).unwrap();
+        
+        let storage = IndexStorage::new(workspace_path);
+        storage.save_index(&code_index).unwrap();
+        
+        // 分区只保存方法，按方法的映射不写入
+        let partition = read_index_data(&storage.service_partition_path("order-service")).unwrap();
+        assert_eq!(partition.methods.len(), 1);
+        assert!(partition.method_calls.is_empty());
+        assert!(partition.reverse_calls.is_empty());
+        
+        // 加载时由方法重建调用关系
+        let loaded = storage.load_index_for_services(&["order-service".to_string()]).unwrap().unwrap();
+        assert!(loaded.find_method("com.example.order.OrderService::create").is_some());
+        assert!(loaded.find_method("com.example.user.UserService::find").is_none());
+        assert_eq!(
+            loaded.find_callers("com.example.order.OrderRepository::save"),
+            vec!["com.example.order.OrderService::create"],
+        );
+        
+        // 完整加载仍包含所有服务
+        let full = storage.load_index().unwrap().unwrap();
+        assert!(full.find_method("com.example.user.UserService::find").is_some());
+        
+        // 没有方法的服务得到空索引
+        let empty = storage.load_index_for_services(&["billing-service".to_string()]).unwrap().unwrap();
+        assert_eq!(empty.methods().count(), 0);
+    }
+    
     #[test]
     fn test_get_index_info() {
         let temp_dir = TempDir::new().unwrap();
@@ -651,4 +1217,93 @@ mod tests {
         let metadata = info.unwrap();
         assert_eq!(metadata.version, INDEX_VERSION);
     }
+    
+    #[test]
+    fn test_index_cache_hits_within_ttl_and_reloads_after() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path().to_path_buf();
+        
+        // 先用无缓存的存储写入磁盘索引
+        IndexStorage::new(workspace_path.clone()).save_index(&CodeIndex::new()).unwrap();
+        
+        let cache = Arc::new(IndexCache::new(Duration::from_millis(200), 4));
+        let mut storage = IndexStorage::new(workspace_path);
+        storage.set_cache(cache.clone());
+        
+        // 首次加载读取磁盘
+        assert!(storage.load_index().unwrap().is_some());
+        assert_eq!(cache.disk_loads(), 1);
+        
+        // TTL 内再次加载命中缓存，返回同一个共享索引
+        let first = storage.load_index().unwrap().unwrap();
+        let second = storage.load_index().unwrap().unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.disk_loads(), 1);
+        
+        // TTL 过期后重新从磁盘加载
+        std::thread::sleep(Duration::from_millis(300));
+        assert!(storage.load_index().unwrap().is_some());
+        assert_eq!(cache.disk_loads(), 2);
+    }
+    
+    #[test]
+    fn test_index_cache_evicts_least_recently_used() {
+        let cache = IndexCache::new(Duration::from_secs(60), 2);
+        let a = Path::new("/workspace/a");
+        let b = Path::new("/workspace/b");
+        let c = Path::new("/workspace/c");
+        
+        cache.insert(a, "1".to_string(), Arc::new(CodeIndex::new()));
+        cache.insert(b, "2".to_string(), Arc::new(CodeIndex::new()));
+        // 访问 a 后，b 成为最久未使用的条目
+        assert!(cache.get(a, "1").is_some());
+        cache.insert(c, "3".to_string(), Arc::new(CodeIndex::new()));
+        
+        assert!(cache.get(a, "1").is_some());
+        assert!(cache.get(b, "2").is_none());
+        assert!(cache.get(c, "3").is_some());
+        
+        // 校验和不同的条目不会命中
+        assert!(cache.get(a, "2").is_none());
+    }
+    
+    #[test]
+    fn test_index_cache_misses_after_workspace_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path().to_path_buf();
+        IndexStorage::new(workspace_path.clone()).save_index(&CodeIndex::new()).unwrap();
+        
+        let cache = Arc::new(IndexCache::new(Duration::from_secs(60), 4));
+        let mut storage = IndexStorage::new(workspace_path.clone());
+        storage.set_cache(cache.clone());
+        assert!(storage.load_index().unwrap().is_some());
+        
+        // 新增源文件后校验和变化，TTL 内也不返回旧索引
+        fs::write(workspace_path.join("Foo.java"), "public class Foo {}").unwrap();
+        assert!(storage.load_index().unwrap().is_none());
+        assert_eq!(cache.disk_loads(), 1);
+    }
+    
+    #[test]
+    fn test_checksum_covers_configured_extensions() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path().to_path_buf();
+        let storage = IndexStorage::new(workspace_path.clone());
+        storage.save_index(&CodeIndex::new()).unwrap();
+        
+        // 默认扩展名包括配置解析器处理的 .thrift，新增 IDL 后索引失效
+        fs::write(workspace_path.join("order.thrift"), "service OrderService {}").unwrap();
+        assert!(storage.load_index().unwrap().is_none());
+        
+        // 不在扩展名集合中的文件不影响校验和
+        storage.save_index(&CodeIndex::new()).unwrap();
+        fs::write(workspace_path.join("notes.txt"), "todo").unwrap();
+        assert!(storage.load_index().unwrap().is_some());
+        
+        // 扩展名集合变化（如注册了新的解析器）时校验和随之变化
+        fs::write(workspace_path.join("script.foo"), "def alpha").unwrap();
+        let mut storage = storage;
+        storage.set_source_extensions(vec!["foo".to_string()]);
+        assert!(storage.load_index().unwrap().is_none());
+    }
 }
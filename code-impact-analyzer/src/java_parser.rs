@@ -1,13 +1,136 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::fs;
 use tree_sitter::Parser;
 use regex::Regex;
 use serde_yaml::Value as YamlValue;
 use crate::errors::ParseError;
-use crate::language_parser::{LanguageParser, ParsedFile, ClassInfo, MethodInfo, MethodCall};
+use crate::language_parser::{collect_syntax_errors, walk_tree, CallFilter, LanguageParser, ParsedFile, ClassInfo, MethodInfo, MethodCall, StringConstant, FunctionalRoute, Visibility, UNKNOWN_CALL_TARGET};
 use crate::types::*;
 
+/// 解析配置值中的环境变量占位符
+/// 
+/// 支持 `${VAR}` 和 `${VAR:default}` 两种形式：环境变量存在时使用其值，
+/// 否则使用默认值；没有默认值且环境变量未设置时替换为空字符串。
+/// 
+/// # Arguments
+/// * `value` - 原始配置值
+/// 
+/// # Returns
+/// * 替换占位符后的配置值
+fn resolve_placeholders(value: &str) -> String {
+    let re = Regex::new(r"\$\{([^}:]+)(?::([^}]*))?\}").unwrap();
+    re.replace_all(value, |caps: &regex::Captures| {
+        std::env::var(caps[1].trim())
+            .unwrap_or_else(|_| caps.get(2).map(|m| m.as_str().to_string()).unwrap_or_default())
+    })
+    .into_owned()
+}
+
+/// 从类型文本中取出消息值类型的简单名
+/// 
+/// 泛型包装（如 `ConsumerRecord<String, OrderEvent>`）取最后一个类型参数，全限定名只保留类名
+fn message_value_type(type_text: &str) -> Option<String> {
+    let type_text = type_text.trim();
+    let inner = match (type_text.find('<'), type_text.rfind('>')) {
+        (Some(open), Some(close)) if open < close => {
+            type_text[open + 1..close].rsplit(',').next().unwrap_or_default()
+        }
+        _ => type_text,
+    };
+    let name = inner.trim().rsplit('.').next().unwrap_or_default();
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    Some(name.to_string())
+}
+
+/// 推断 `send(topic, [key,] payload)` 调用中消息参数的类型
+/// 
+/// `rest` 为 topic 参数之后的文本；消息为 `new X(...)` 时取 `X`，
+/// 为变量时在方法文本中查找其声明类型
+/// 
+/// # Arguments
+/// * `method_text` - 方法源码
+/// * `rest` - 调用中 topic 参数之后的文本
+/// 
+/// # Returns
+/// * 消息值类型的简单名，无法推断时返回 None
+fn kafka_payload_type(method_text: &str, rest: &str) -> Option<String> {
+    let rest = rest.trim_start().strip_prefix(',')?;
+    
+    // 按顶层逗号拆分剩余参数，直到调用的右括号
+    let mut args = vec![String::new()];
+    let mut depth = 0usize;
+    for c in rest.chars() {
+        match c {
+            '(' | '<' | '[' | '{' => depth += 1,
+            ')' if depth == 0 => break,
+            ')' | '>' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                args.push(String::new());
+                continue;
+            }
+            _ => {}
+        }
+        args.last_mut()?.push(c);
+    }
+    let payload = args.last()?.trim();
+    
+    if let Some(constructed) = payload.strip_prefix("new ") {
+        let type_text = constructed.split(['(', '{']).next()?;
+        let type_text = type_text.split('<').next()?;
+        return message_value_type(type_text);
+    }
+    
+    if !payload.chars().all(|c| c.is_alphanumeric() || c == '_') || payload.is_empty() {
+        return None;
+    }
+    let declaration = Regex::new(&format!(
+        r"([A-Z][\w.]*)(?:<[^;()]*>)?\s+{}\s*[=;,)]",
+        regex::escape(payload)
+    )).ok()?;
+    let captures = declaration.captures(method_text)?;
+    message_value_type(captures.get(1)?.as_str())
+}
+
+/// 组合 HTTP 接口的完整路径：application.name/context-path/class-path/method-path
+fn full_http_path(app_config: &ApplicationConfig, class_path: Option<&str>, method_path: &str) -> String {
+    let mut full_path = String::new();
+    
+    // 添加 application.name
+    if let Some(app_name) = &app_config.application_name {
+        full_path.push_str(app_name);
+    }
+    
+    // 添加 context-path
+    if let Some(context_path) = &app_config.context_path
+        && !context_path.is_empty()
+    {
+        if !full_path.is_empty() && !full_path.ends_with('/') {
+            full_path.push('/');
+        }
+        full_path.push_str(context_path.trim_start_matches('/'));
+    }
+    
+    // 添加类级别的 RequestMapping 路径
+    if let Some(class_path) = class_path {
+        if !full_path.is_empty() && !full_path.ends_with('/') {
+            full_path.push('/');
+        }
+        full_path.push_str(class_path.trim_start_matches('/'));
+    }
+    
+    // 添加方法级别的路径
+    let method_path = method_path.trim_start_matches('/');
+    if !full_path.is_empty() && !full_path.ends_with('/') && !method_path.is_empty() {
+        full_path.push('/');
+    }
+    full_path.push_str(method_path);
+    
+    full_path
+}
+
 /// FeignClient 注解信息
 #[derive(Debug, Clone)]
 struct FeignClientInfo {
@@ -31,6 +154,10 @@ struct ApplicationConfig {
 /// 使用 tree-sitter-java 解析 Java 源代码
 pub struct JavaParser {
     parser: Mutex<Parser>,
+    /// 提取调用时使用的过滤器
+    call_filter: CallFilter,
+    /// 工作空间根目录，查找模块配置文件时不越过该目录
+    workspace_root: Option<PathBuf>,
 }
 
 impl JavaParser {
@@ -46,13 +173,44 @@ impl JavaParser {
         
         Ok(JavaParser { 
             parser: Mutex::new(parser),
+            call_filter: CallFilter::default(),
+            workspace_root: None,
         })
     }
     
-    /// 从项目根目录查找并解析 application.yml 配置文件
+    /// 设置工作空间根目录
     /// 
-    /// 查找路径：start/src/main/resources/application.yml
+    /// 查找模块的 application.yml 时只在该目录内向上查找，
+    /// 避免读取到工作空间之外无关项目的配置文件
+    pub fn with_workspace_root(mut self, workspace_root: impl Into<PathBuf>) -> Self {
+        self.workspace_root = Some(workspace_root.into());
+        self
+    }
+    
+    /// 查找并解析源文件所属模块的 application.yml 配置文件
+    /// 
+    /// 优先使用标准 Maven/Gradle 模块布局：向上找到最近的包含
+    /// `src/main/resources` 的目录，读取其中的 `application.yml`（或 `.yaml`）；
+    /// 该模块没有配置文件时回退到 `start/src/main/resources/application.yml` 布局
     fn load_application_config(&self, file_path: &Path) -> ApplicationConfig {
+        if let Some((config_path, module_root)) = find_module_application_config(file_path, self.workspace_root.as_deref()) {
+            // start 模块按旧布局以项目根目录命名
+            let project_root = match module_root.file_name() {
+                Some(name) if name == "start" => module_root.parent().unwrap_or(module_root),
+                _ => module_root,
+            };
+            if let Ok(content) = fs::read_to_string(&config_path) {
+                return self.parse_application_yml(&content, project_root);
+            }
+        }
+        
+        self.load_start_module_config(file_path)
+    }
+    
+    /// 按 `start` 模块布局查找并解析 application.yml 配置文件
+    /// 
+    /// 查找路径：start/src/main/resources/application.yml
+    fn load_start_module_config(&self, file_path: &Path) -> ApplicationConfig {
         // 尝试找到项目根目录
         let mut current = file_path;
         let mut project_root = None;
@@ -75,15 +233,15 @@ impl JavaParser {
                 if let Some(name) = parent.file_name() {
                     let name_str = name.to_string_lossy();
                     // 如果是模块目录，其父目录可能是项目根目录
-                    if name_str.contains("-adapter") || name_str.contains("-app") 
+                    if (name_str.contains("-adapter") || name_str.contains("-app") 
                         || name_str.contains("-client") || name_str.contains("-domain")
-                        || name_str.contains("-infrastructure") {
-                        if let Some(potential_root) = parent.parent() {
-                            let start_dir = potential_root.join("start");
-                            if start_dir.exists() && start_dir.is_dir() {
-                                project_root = Some(potential_root);
-                                break;
-                            }
+                        || name_str.contains("-infrastructure"))
+                        && let Some(potential_root) = parent.parent()
+                    {
+                        let start_dir = potential_root.join("start");
+                        if start_dir.exists() && start_dir.is_dir() {
+                            project_root = Some(potential_root);
+                            break;
                         }
                     }
                 }
@@ -119,33 +277,29 @@ impl JavaParser {
         // 解析 YAML
         if let Ok(yaml) = serde_yaml::from_str::<YamlValue>(content) {
             // 提取 spring.application.name
-            if let Some(spring) = yaml.get("spring") {
-                if let Some(application) = spring.get("application") {
-                    if let Some(name) = application.get("name") {
-                        if let Some(name_str) = name.as_str() {
-                            config.application_name = Some(name_str.to_string());
-                        }
-                    }
-                }
+            if let Some(spring) = yaml.get("spring")
+                && let Some(application) = spring.get("application")
+                && let Some(name) = application.get("name")
+                && let Some(name_str) = name.as_str()
+            {
+                config.application_name = Some(resolve_placeholders(name_str));
             }
             
             // 提取 server.servlet.context-path
-            if let Some(server) = yaml.get("server") {
-                if let Some(servlet) = server.get("servlet") {
-                    if let Some(context_path) = servlet.get("context-path") {
-                        if let Some(path_str) = context_path.as_str() {
-                            config.context_path = Some(path_str.to_string());
-                        }
-                    }
-                }
+            if let Some(server) = yaml.get("server")
+                && let Some(servlet) = server.get("servlet")
+                && let Some(context_path) = servlet.get("context-path")
+                && let Some(path_str) = context_path.as_str()
+            {
+                config.context_path = Some(resolve_placeholders(path_str));
             }
         }
         
         // 如果没有找到 application.name，使用项目目录名
-        if config.application_name.is_none() {
-            if let Some(dir_name) = project_root.file_name() {
-                config.application_name = Some(dir_name.to_string_lossy().to_string());
-            }
+        if config.application_name.is_none()
+            && let Some(dir_name) = project_root.file_name()
+        {
+            config.application_name = Some(dir_name.to_string_lossy().to_string());
         }
         
         // 如果没有找到 context-path，使用空字符串
@@ -157,35 +311,34 @@ impl JavaParser {
     }
     
     /// 提取类信息
-    fn extract_classes(&self, source: &str, file_path: &Path, tree: &tree_sitter::Tree) -> Vec<ClassInfo> {
+    fn extract_classes(&self, source: &str, file_path: &Path, tree: &tree_sitter::Tree, package_name: &Option<String>) -> Vec<ClassInfo> {
         let mut classes = Vec::new();
         let root_node = tree.root_node();
         
         // 加载应用配置
         let app_config = self.load_application_config(file_path);
         
-        self.walk_node_for_classes(source, file_path, root_node, &mut classes, tree, &app_config);
+        self.walk_node_for_classes(source, file_path, root_node, &mut classes, tree, package_name, &app_config);
         
         classes
     }
     
-    /// 递归遍历节点查找类声明和接口声明
-    fn walk_node_for_classes(&self, source: &str, file_path: &Path, node: tree_sitter::Node, classes: &mut Vec<ClassInfo>, tree: &tree_sitter::Tree, app_config: &ApplicationConfig) {
-        // 处理类声明和接口声明
-        if node.kind() == "class_declaration" || node.kind() == "interface_declaration" {
-            if let Some(class_info) = self.extract_class_info(source, file_path, node, tree, app_config) {
+    /// 遍历节点查找类声明和接口声明
+    #[allow(clippy::too_many_arguments)]
+    fn walk_node_for_classes(&self, source: &str, file_path: &Path, node: tree_sitter::Node, classes: &mut Vec<ClassInfo>, tree: &tree_sitter::Tree, package_name: &Option<String>, app_config: &ApplicationConfig) {
+        walk_tree(node, |node| {
+            // 处理类声明、接口声明和 record 声明
+            if matches!(node.kind(), "class_declaration" | "interface_declaration" | "record_declaration")
+                && let Some(class_info) = self.extract_class_info(source, file_path, node, tree, package_name, app_config)
+            {
                 classes.push(class_info);
             }
-        }
-        
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            self.walk_node_for_classes(source, file_path, child, classes, tree, app_config);
-        }
+            true
+        });
     }
     
     /// 从类节点提取类信息
-    fn extract_class_info(&self, source: &str, file_path: &Path, class_node: tree_sitter::Node, tree: &tree_sitter::Tree, app_config: &ApplicationConfig) -> Option<ClassInfo> {
+    fn extract_class_info(&self, source: &str, file_path: &Path, class_node: tree_sitter::Node, tree: &tree_sitter::Tree, package_name: &Option<String>, app_config: &ApplicationConfig) -> Option<ClassInfo> {
         // 判断是否是接口
         let is_interface = class_node.kind() == "interface_declaration";
         
@@ -194,18 +347,17 @@ impl JavaParser {
         let mut class_name = None;
         
         for child in class_node.children(&mut cursor) {
-            if child.kind() == "identifier" {
-                if let Some(text) = source.get(child.byte_range()) {
-                    class_name = Some(text.to_string());
-                    break;
-                }
+            if child.kind() == "identifier"
+                && let Some(text) = source.get(child.byte_range())
+            {
+                class_name = Some(text.to_string());
+                break;
             }
         }
         
         let simple_name = class_name?;
         
-        // 提取包名，构建完整的类名
-        let package_name = self.extract_package_name(source, tree);
+        // 按包名构建完整的类名
         let full_class_name = if let Some(pkg) = package_name {
             format!("{}.{}", pkg, simple_name)
         } else {
@@ -215,8 +367,9 @@ impl JavaParser {
         let line_start = class_node.start_position().row + 1;
         let line_end = class_node.end_position().row + 1;
         
-        // 提取实现的接口列表
-        let implements = self.extract_implements_interfaces(source, &class_node, tree);
+        // 提取实现的接口列表和继承的父类
+        let implements = self.extract_implements_interfaces(source, &class_node, tree, package_name);
+        let extends = self.extract_superclass(source, &class_node, tree, package_name);
         
         // 提取类级别的 FeignClient 注解
         let feign_client_info = self.extract_feign_client_annotation(source, &class_node);
@@ -225,7 +378,35 @@ impl JavaParser {
         let class_request_mapping = self.extract_class_level_request_mapping(source, &class_node);
         
         // 提取类中的方法
-        let methods = self.extract_methods_from_class(source, file_path, &class_node, &full_class_name, tree, &feign_client_info, &class_request_mapping, app_config);
+        let mut methods = self.extract_methods_from_class(source, file_path, &class_node, &full_class_name, extends.as_deref(), tree, package_name, &feign_client_info, &class_request_mapping, app_config);
+        
+        // record 的组件生成同名的访问器方法
+        if class_node.kind() == "record_declaration" {
+            let accessors = self.extract_record_accessors(source, file_path, &class_node, &full_class_name, &methods);
+            methods.extend(accessors);
+        }
+        
+        // 提取 Dubbo RPC 提供者和消费者信息
+        self.attach_dubbo_operations(source, &class_node, tree, package_name, &implements, &mut methods);
+        
+        // 提取 Thrift 服务提供者和消费者信息
+        self.attach_thrift_operations(&implements, &mut methods);
+        
+        // 类级别的 @ConditionalOnProperty 作用于未单独声明开关的所有方法
+        if let Some(class_conditional) = self.extract_conditional_property(source, &class_node) {
+            for method in methods.iter_mut().filter(|method| method.conditional.is_none()) {
+                method.conditional = Some(class_conditional.clone());
+            }
+        }
+        
+        // 提取 @Configuration 类中 @Bean 方法之间的依赖
+        if self.has_annotation(source, &class_node, &["Configuration"]) {
+            self.attach_bean_dependencies(source, &class_node, &full_class_name, &mut methods);
+            self.attach_batch_wiring(source, &class_node, tree, package_name, &mut methods);
+        }
+        
+        // 静态初始化代码中的调用归入合成的 `<clinit>` 方法，实例初始化代码中的调用归入构造器 `<init>`
+        self.attach_initializers(source, file_path, &class_node, &full_class_name, extends.as_deref(), tree, package_name, &mut methods);
         
         Some(ClassInfo {
             name: full_class_name,
@@ -233,649 +414,924 @@ impl JavaParser {
             line_range: (line_start, line_end),
             is_interface,
             implements,
+            extends,
         })
     }
     
-    /// 提取类实现的接口列表
-    fn extract_implements_interfaces(&self, source: &str, class_node: &tree_sitter::Node, tree: &tree_sitter::Tree) -> Vec<String> {
-        let mut interfaces = Vec::new();
+    /// 提取 record 组件对应的访问器方法
+    /// 
+    /// `record User(String name)` 隐式生成 `name()` 访问器，其行范围为组件声明所在行；
+    /// 记录体中显式声明的同名方法已作为普通方法提取，不再重复生成
+    fn extract_record_accessors(
+        &self,
+        source: &str,
+        file_path: &Path,
+        record_node: &tree_sitter::Node,
+        record_name: &str,
+        explicit_methods: &[MethodInfo],
+    ) -> Vec<MethodInfo> {
+        let Some(parameters) = record_node.child_by_field_name("parameters") else {
+            return Vec::new();
+        };
         
-        // 构建导入映射，用于将简单类名转换为完整类名
-        let import_map = self.build_import_map(source, tree);
-        let package_name = self.extract_package_name(source, tree);
+        let mut cursor = parameters.walk();
+        parameters.children(&mut cursor)
+            .filter(|param| param.kind() == "formal_parameter")
+            .filter_map(|param| {
+                let name = source.get(param.child_by_field_name("name")?.byte_range())?;
+                if explicit_methods.iter().any(|method| method.name == name) {
+                    return None;
+                }
+                let line = param.start_position().row + 1;
+                Some(MethodInfo {
+                    name: name.to_string(),
+                    full_qualified_name: format!("{}::{}", record_name, name),
+                    file_path: file_path.to_path_buf(),
+                    line_range: (line, line),
+                    calls: Vec::new(),
+                    http_annotations: None,
+                    kafka_operations: Vec::new(),
+                    db_operations: Vec::new(),
+                    redis_operations: Vec::new(),
+                    rpc_operations: Vec::new(),
+                    thrift_operations: Vec::new(),
+                    is_bean: false,
+                    conditional: None,
+                    graphql_resolver: None,
+                    batch_job: None,
+                    api_operation: None,
+                    scheduled: false,
+                    is_delegation: false,
+                    resilience: None,
+                    column_range: Some((param.start_position().column, param.end_position().column)),
+                    visibility: Visibility::Public,
+                    extra_line_ranges: Vec::new(),
+                    extra_http_annotations: Vec::new(),
+                })
+            })
+            .collect()
+    }
+    
+    /// 将不在任何方法内的初始化代码中的调用归入合成的初始化方法
+    /// 
+    /// - 静态初始化块（`static {}`）和静态字段的初始化表达式在类加载时执行，归入 `类名::<clinit>`
+    /// - 实例初始化块（`{}`）和实例字段的初始化表达式（`private final X x = new X();`）在每个构造器中执行，
+    ///   归入 `类名::<init>`：追加到显式声明的构造器，没有构造器时合成隐式的默认构造器
+    /// 
+    /// 每段包含调用的初始化代码单独记录行范围（第一段为 `line_range`，其余为 `extra_line_ranges`），
+    /// 夹在初始化代码之间的普通方法不会被计入；没有包含调用的初始化代码时不生成
+    #[allow(clippy::too_many_arguments)]
+    fn attach_initializers(
+        &self,
+        source: &str,
+        file_path: &Path,
+        class_node: &tree_sitter::Node,
+        class_name: &str,
+        superclass: Option<&str>,
+        tree: &tree_sitter::Tree,
+        package_name: &Option<String>,
+        methods: &mut Vec<MethodInfo>,
+    ) {
+        let mut static_init: (Vec<(usize, usize)>, Vec<MethodCall>) = (Vec::new(), Vec::new());
+        let mut instance_init: (Vec<(usize, usize)>, Vec<MethodCall>) = (Vec::new(), Vec::new());
         
-        // 查找 super_interfaces 节点（包含 implements 子句）
         let mut cursor = class_node.walk();
-        for child in class_node.children(&mut cursor) {
-            if child.kind() == "super_interfaces" {
-                // 在 super_interfaces 中查找 type_list（implements 后的接口列表）
-                let mut super_cursor = child.walk();
-                for super_child in child.children(&mut super_cursor) {
-                    if super_child.kind() == "type_list" {
-                        // 提取接口名称
-                        let mut type_cursor = super_child.walk();
-                        for type_child in super_child.children(&mut type_cursor) {
-                            if type_child.kind() == "type_identifier" {
-                                if let Some(interface_name) = source.get(type_child.byte_range()) {
-                                    // 尝试将简单类名转换为完整类名
-                                    let full_interface_name = self.resolve_full_class_name(
-                                        interface_name,
-                                        &import_map,
-                                        &package_name,
-                                    );
-                                    interfaces.push(full_interface_name);
-                                }
-                            }
-                        }
+        for body in class_node.children(&mut cursor).filter(|child| child.kind() == "class_body") {
+            let mut body_cursor = body.walk();
+            for member in body.children(&mut body_cursor) {
+                let target = match member.kind() {
+                    "static_initializer" => &mut static_init,
+                    "block" => &mut instance_init,
+                    "field_declaration" => {
+                        let mut member_cursor = member.walk();
+                        let is_static = member.children(&mut member_cursor)
+                            .find(|child| child.kind() == "modifiers")
+                            .and_then(|modifiers| source.get(modifiers.byte_range()))
+                            .is_some_and(|modifiers| modifiers.split_whitespace().any(|word| word == "static"));
+                        if is_static { &mut static_init } else { &mut instance_init }
                     }
+                    _ => continue,
+                };
+                let member_calls = self.extract_method_calls(source, &member, class_name, superclass, tree, package_name);
+                if member_calls.is_empty() {
+                    continue;
                 }
+                target.0.push((member.start_position().row + 1, member.end_position().row + 1));
+                target.1.extend(member_calls);
             }
         }
         
-        interfaces
+        let synthesize = |name: &str, (ranges, calls): (Vec<(usize, usize)>, Vec<MethodCall>)| MethodInfo {
+            name: name.to_string(),
+            full_qualified_name: format!("{}::{}", class_name, name),
+            file_path: file_path.to_path_buf(),
+            line_range: ranges[0],
+            extra_line_ranges: ranges[1..].to_vec(),
+            calls,
+            // 由 JVM 调用或随类一起可见，按可见性过滤时作为入口保留
+            visibility: Visibility::Public,
+            ..Default::default()
+        };
+        
+        if !instance_init.0.is_empty() {
+            let mut constructors = methods.iter_mut().filter(|method| method.name == "<init>").peekable();
+            if constructors.peek().is_none() {
+                methods.push(synthesize("<init>", instance_init));
+            } else {
+                for constructor in constructors {
+                    constructor.extra_line_ranges.extend(instance_init.0.iter().copied());
+                    constructor.calls.extend(instance_init.1.iter().cloned());
+                }
+            }
+        }
+        if !static_init.0.is_empty() {
+            methods.push(synthesize("<clinit>", static_init));
+        }
     }
     
-    /// 将简单类名解析为完整类名
-    fn resolve_full_class_name(
+    /// 为类中的方法附加 Dubbo RPC 操作
+    /// 
+    /// - `@DubboService` 类：每个方法作为其服务接口对应方法的提供者
+    /// - `@DubboReference` / `@Reference` 字段：通过该字段类型发起的调用作为 RPC 消费
+    fn attach_dubbo_operations(
         &self,
-        simple_name: &str,
-        import_map: &std::collections::HashMap<String, String>,
+        source: &str,
+        class_node: &tree_sitter::Node,
+        tree: &tree_sitter::Tree,
         package_name: &Option<String>,
-    ) -> String {
-        // 首先尝试从导入映射中查找
-        if let Some(full_name) = import_map.get(simple_name) {
-            return full_name.clone();
+        implements: &[String],
+        methods: &mut [MethodInfo],
+    ) {
+        // 提供者：服务接口优先取 interfaceClass 属性，否则取实现的接口；
+        // 只有公开的业务方法才能通过服务接口远程调用，构造器和初始化代码（`<init>`、`<clinit>`）除外
+        let service_interfaces = self.extract_dubbo_service_interfaces(source, class_node, tree, package_name, implements);
+        if !service_interfaces.is_empty() {
+            let provided = methods.iter_mut()
+                .filter(|method| method.visibility == Visibility::Public && !method.name.starts_with('<'));
+            for method in provided {
+                for interface in &service_interfaces {
+                    method.rpc_operations.push(RpcOperation {
+                        operation_type: RpcOpType::Provide,
+                        interface: interface.clone(),
+                        method: method.name.clone(),
+                        line: method.line_range.0,
+                    });
+                }
+            }
         }
         
-        // 如果没有找到，假设在同一个包中
-        if let Some(pkg) = package_name {
-            return format!("{}.{}", pkg, simple_name);
+        // 消费者：调用目标的类型是 Dubbo 引用字段的类型
+        let reference_types = self.extract_dubbo_reference_types(source, class_node, tree, package_name);
+        if reference_types.is_empty() {
+            return;
         }
         
-        // 否则返回简单类名
-        simple_name.to_string()
+        for method in methods.iter_mut() {
+            let mut consumed = Vec::new();
+            for call in &method.calls {
+                if let Some((class_part, method_part)) = call.target.rsplit_once("::")
+                    && reference_types.iter().any(|t| t == class_part)
+                {
+                    consumed.push(RpcOperation {
+                        operation_type: RpcOpType::Consume,
+                        interface: class_part.to_string(),
+                        method: method_part.to_string(),
+                        line: call.line,
+                    });
+                }
+            }
+            method.rpc_operations.extend(consumed);
+        }
     }
     
-    /// 提取包名
-    fn extract_package_name(&self, source: &str, tree: &tree_sitter::Tree) -> Option<String> {
-        let root_node = tree.root_node();
-        let mut cursor = root_node.walk();
+    /// 为类中的方法附加 Thrift 操作
+    /// 
+    /// - 实现生成的 `Xxx.Iface` / `Xxx.AsyncIface` 接口的类：每个方法作为服务方法的提供者
+    /// - 通过生成的 `Xxx.Client` / `Xxx.AsyncClient` 发起的调用作为消费
+    fn attach_thrift_operations(&self, implements: &[String], methods: &mut [MethodInfo]) {
+        let services: Vec<&str> = implements.iter()
+            .filter_map(|interface| {
+                interface.strip_suffix(".Iface").or_else(|| interface.strip_suffix(".AsyncIface"))
+            })
+            .collect();
         
-        for child in root_node.children(&mut cursor) {
-            if child.kind() == "package_declaration" {
-                // 在 package_declaration 中查找 scoped_identifier
-                let mut pkg_cursor = child.walk();
-                for pkg_child in child.children(&mut pkg_cursor) {
-                    if pkg_child.kind() == "scoped_identifier" {
-                        if let Some(text) = source.get(pkg_child.byte_range()) {
-                            return Some(text.to_string());
-                        }
-                    }
-                }
+        for method in methods.iter_mut() {
+            for service in &services {
+                method.thrift_operations.push(ThriftOperation {
+                    operation_type: RpcOpType::Provide,
+                    service: service.to_string(),
+                    method: method.name.clone(),
+                    line: method.line_range.0,
+                });
             }
+            
+            let consumed: Vec<ThriftOperation> = method.calls.iter()
+                .filter_map(|call| {
+                    let (class_part, method_part) = call.target.rsplit_once("::")?;
+                    let service = class_part.strip_suffix(".Client")
+                        .or_else(|| class_part.strip_suffix(".AsyncClient"))?;
+                    Some(ThriftOperation {
+                        operation_type: RpcOpType::Consume,
+                        service: service.to_string(),
+                        method: method_part.to_string(),
+                        line: call.line,
+                    })
+                })
+                .collect();
+            method.thrift_operations.extend(consumed);
         }
-        
-        None
     }
     
-    /// 提取类级别的 FeignClient 注解
-    fn extract_feign_client_annotation(&self, source: &str, class_node: &tree_sitter::Node) -> Option<FeignClientInfo> {
-        // 查找类节点的 modifiers 子节点
+    /// 提取 @DubboService 类暴露的服务接口
+    fn extract_dubbo_service_interfaces(
+        &self,
+        source: &str,
+        class_node: &tree_sitter::Node,
+        tree: &tree_sitter::Tree,
+        package_name: &Option<String>,
+        implements: &[String],
+    ) -> Vec<String> {
         let mut cursor = class_node.walk();
         for child in class_node.children(&mut cursor) {
-            if child.kind() == "modifiers" {
-                // 在 modifiers 中查找注解
-                let mut mod_cursor = child.walk();
-                for mod_child in child.children(&mut mod_cursor) {
-                    if mod_child.kind() == "marker_annotation" || mod_child.kind() == "annotation" {
-                        if let Some(feign_info) = self.parse_feign_client_annotation(source, mod_child) {
-                            return Some(feign_info);
-                        }
-                    }
+            if child.kind() != "modifiers" {
+                continue;
+            }
+            
+            let mut mod_cursor = child.walk();
+            for mod_child in child.children(&mut mod_cursor) {
+                if mod_child.kind() != "marker_annotation" && mod_child.kind() != "annotation" {
+                    continue;
+                }
+                
+                let Some((name, args)) = self.parse_annotation_name_and_args(source, mod_child) else {
+                    continue;
+                };
+                
+                if annotation_simple_name(&name) != "DubboService" {
+                    continue;
+                }
+                
+                // 显式指定 interfaceClass = Xxx.class
+                if let Some(interface) = args.as_deref().and_then(|a| self.extract_class_literal_attribute(a, "interfaceClass")) {
+                    let import_map = self.build_import_map(source, tree);
+                                return vec![self.resolve_full_class_name(&interface, &import_map, package_name)];
                 }
+                
+                return implements.to_vec();
             }
         }
         
-        None
+        Vec::new()
     }
     
-    /// 提取类级别的 RequestMapping 注解
-    fn extract_class_level_request_mapping(&self, source: &str, class_node: &tree_sitter::Node) -> Option<String> {
-        // 查找类节点的 modifiers 子节点
+    /// 提取类中通过 @DubboReference / @Reference 注入的字段类型（完整类名）
+    fn extract_dubbo_reference_types(
+        &self,
+        source: &str,
+        class_node: &tree_sitter::Node,
+        tree: &tree_sitter::Tree,
+        package_name: &Option<String>,
+    ) -> Vec<String> {
+        let mut reference_types = Vec::new();
+        let import_map = self.build_import_map(source, tree);
+        
         let mut cursor = class_node.walk();
         for child in class_node.children(&mut cursor) {
-            if child.kind() == "modifiers" {
-                // 在 modifiers 中查找注解
-                let mut mod_cursor = child.walk();
-                for mod_child in child.children(&mut mod_cursor) {
-                    if mod_child.kind() == "marker_annotation" || mod_child.kind() == "annotation" {
-                        if let Some(path) = self.parse_request_mapping_annotation(source, mod_child) {
-                            return Some(path);
-                        }
+            if child.kind() != "class_body" {
+                continue;
+            }
+            
+            let mut body_cursor = child.walk();
+            for field in child.children(&mut body_cursor) {
+                if field.kind() != "field_declaration" || !self.is_dubbo_reference_field(source, &field) {
+                    continue;
+                }
+                
+                let mut field_types = std::collections::HashMap::new();
+                self.extract_field_type_from_declaration(source, field, &mut field_types);
+                for simple_type in field_types.values() {
+                    let full_type = self.resolve_full_class_name(simple_type, &import_map, package_name);
+                    if !reference_types.contains(&full_type) {
+                        reference_types.push(full_type);
                     }
                 }
             }
         }
         
-        None
+        reference_types
     }
     
-    /// 解析 RequestMapping 注解获取路径
-    fn parse_request_mapping_annotation(&self, source: &str, annotation_node: tree_sitter::Node) -> Option<String> {
-        // 获取注解名称
-        let mut cursor = annotation_node.walk();
-        let mut annotation_name = None;
-        let mut annotation_args = None;
-        
-        for child in annotation_node.children(&mut cursor) {
-            if child.kind() == "identifier" || child.kind() == "scoped_identifier" {
-                if let Some(text) = source.get(child.byte_range()) {
-                    annotation_name = Some(text.to_string());
+    /// 判断字段是否带有 @DubboReference 或 Dubbo 旧版 @Reference 注解
+    fn is_dubbo_reference_field(&self, source: &str, field_node: &tree_sitter::Node) -> bool {
+        self.has_annotation(source, field_node, &["DubboReference", "Reference"])
+    }
+    
+    /// 判断声明（类、方法或字段）是否带有指定简单名的注解
+    fn has_annotation(&self, source: &str, node: &tree_sitter::Node, simple_names: &[&str]) -> bool {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() != "modifiers" {
+                continue;
+            }
+            
+            let mut mod_cursor = child.walk();
+            for mod_child in child.children(&mut mod_cursor) {
+                if mod_child.kind() != "marker_annotation" && mod_child.kind() != "annotation" {
+                    continue;
                 }
-            } else if child.kind() == "annotation_argument_list" {
-                if let Some(text) = source.get(child.byte_range()) {
-                    annotation_args = Some(text.to_string());
+                
+                if let Some((name, _)) = self.parse_annotation_name_and_args(source, mod_child)
+                    && simple_names.contains(&annotation_simple_name(&name))
+                {
+                    return true;
                 }
             }
         }
         
-        let name = annotation_name?;
-        
-        // 检查是否是 RequestMapping 注解
-        if !name.contains("RequestMapping") {
-            return None;
-        }
-        
-        // 提取路径
-        self.extract_path_from_args(&annotation_args)
+        false
     }
     
-    /// 解析 FeignClient 注解
-    fn parse_feign_client_annotation(&self, source: &str, annotation_node: tree_sitter::Node) -> Option<FeignClientInfo> {
-        // 获取注解名称
-        let mut cursor = annotation_node.walk();
-        let mut annotation_name = None;
-        let mut annotation_args = None;
+    /// 提取方法的可见性
+    /// 
+    /// 接口中的方法默认为 public；没有访问修饰符的方法为包级可见，`protected` 也按包级可见处理
+    fn extract_visibility(&self, method_node: &tree_sitter::Node) -> Visibility {
+        if method_node.parent().is_some_and(|parent| parent.kind() == "interface_body") {
+            return Visibility::Public;
+        }
         
-        for child in annotation_node.children(&mut cursor) {
-            if child.kind() == "identifier" || child.kind() == "scoped_identifier" {
-                if let Some(text) = source.get(child.byte_range()) {
-                    annotation_name = Some(text.to_string());
-                }
-            } else if child.kind() == "annotation_argument_list" {
-                if let Some(text) = source.get(child.byte_range()) {
-                    annotation_args = Some(text.to_string());
+        let mut cursor = method_node.walk();
+        for child in method_node.children(&mut cursor) {
+            if child.kind() != "modifiers" {
+                continue;
+            }
+            
+            let mut mod_cursor = child.walk();
+            for modifier in child.children(&mut mod_cursor) {
+                match modifier.kind() {
+                    "public" => return Visibility::Public,
+                    "protected" => return Visibility::Package,
+                    "private" => return Visibility::Private,
+                    _ => {}
                 }
             }
         }
-        
-        let name = annotation_name?;
-        
-        // 检查是否是 FeignClient 注解
-        if !name.contains("FeignClient") {
-            return None;
+        Visibility::Package
+    }
+    
+    /// 提取方法上的容错注解
+    /// 
+    /// Spring Retry 的 `@Retryable` 和 Resilience4j 的 `@Retry` 记为 `retryable`，
+    /// Resilience4j 的 `@CircuitBreaker` 记为 `circuit_breaker`；同时存在时以重试为准
+    fn extract_resilience(&self, source: &str, method_node: &tree_sitter::Node) -> Option<String> {
+        if self.has_annotation(source, method_node, &["Retryable", "Retry"]) {
+            Some("retryable".to_string())
+        } else if self.has_annotation(source, method_node, &["CircuitBreaker"]) {
+            Some("circuit_breaker".to_string())
+        } else {
+            None
         }
-        
-        let args_text = annotation_args?;
-        
-        // 提取 value 或 name 属性（服务名称）
-        let service_name = self.extract_feign_attribute(&args_text, "value")
-            .or_else(|| self.extract_feign_attribute(&args_text, "name"))?;
-        
-        // 提取 path 属性（基础路径）
-        let base_path = self.extract_feign_attribute(&args_text, "path");
-        
-        Some(FeignClientInfo {
-            service_name,
-            base_path,
-        })
     }
     
-    /// 从 FeignClient 注解参数中提取指定属性的值
-    fn extract_feign_attribute(&self, args: &str, attr_name: &str) -> Option<String> {
-        // 匹配 attr_name = "value" 格式
-        let pattern = format!(r#"{}\s*=\s*"([^"]+)""#, attr_name);
-        let re = Regex::new(&pattern).ok()?;
+    /// 提取声明（类或方法）上 `@ConditionalOnProperty` 控制的属性名
+    /// 
+    /// 支持 `@ConditionalOnProperty("a.b")`、`name`/`value` 属性（含数组形式，取第一个）
+    /// 以及 `prefix` 属性，如 `@ConditionalOnProperty(prefix = "feature", name = "x")` 返回 `feature.x`
+    fn extract_conditional_property(&self, source: &str, node: &tree_sitter::Node) -> Option<String> {
+        let property_re = Regex::new(r#"^\(\s*\{?\s*"([^"]*)"|\b(?:name|value)\s*=\s*\{?\s*"([^"]*)""#).unwrap();
         
-        if let Some(cap) = re.captures(args) {
-            return cap.get(1).map(|m| m.as_str().to_string());
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() != "modifiers" {
+                continue;
+            }
+            
+            let mut mod_cursor = child.walk();
+            for mod_child in child.children(&mut mod_cursor) {
+                if mod_child.kind() != "annotation" {
+                    continue;
+                }
+                
+                let Some((name, Some(args))) = self.parse_annotation_name_and_args(source, mod_child) else {
+                    continue;
+                };
+                if annotation_simple_name(&name) != "ConditionalOnProperty" {
+                    continue;
+                }
+                
+                let property = property_re.captures(&args)
+                    .and_then(|cap| cap.get(1).or_else(|| cap.get(2)))
+                    .map(|m| m.as_str().to_string())?;
+                
+                return Some(match self.extract_string_attribute(&args, "prefix") {
+                    Some(prefix) if !prefix.is_empty() => format!("{}.{}", prefix.trim_end_matches('.'), property),
+                    _ => property,
+                });
+            }
         }
         
         None
     }
     
-    /// 提取 Feign 方法的 HTTP 注解（组合类级别和方法级别的路径）
-    fn extract_feign_http_annotation(
+    /// 记录 @Configuration 类中 @Bean 方法之间的依赖
+    /// 
+    /// 标记 `@Bean` 方法，并将以下两种依赖记录为对被依赖 `@Bean` 方法的调用：
+    /// - 直接调用同一配置类中的另一个 `@Bean` 方法（如 `new A(b())`）
+    /// - 方法参数的类型是同一配置类中另一个 `@Bean` 方法的返回类型（参数注入）
+    fn attach_bean_dependencies(
         &self,
         source: &str,
-        method_node: &tree_sitter::Node,
-        feign_info: &FeignClientInfo,
-    ) -> Option<HttpAnnotation> {
-        // 提取方法级别的 HTTP 注解（不使用应用配置，因为这是调用其他服务）
-        let method_http = self.extract_http_annotations_raw(source, method_node)?;
-        
-        // 组合路径：service_name/base_path/method_path
-        let mut full_path = feign_info.service_name.clone();
+        class_node: &tree_sitter::Node,
+        class_name: &str,
+        methods: &mut [MethodInfo],
+    ) {
+        // @Bean 方法名 -> (返回类型, 参数类型列表)
+        let mut beans: Vec<(String, String, Vec<String>)> = Vec::new();
         
-        if let Some(base_path) = &feign_info.base_path {
-            // 确保路径正确拼接
-            if !full_path.ends_with('/') {
-                full_path.push('/');
+        let mut cursor = class_node.walk();
+        for child in class_node.children(&mut cursor) {
+            if child.kind() != "class_body" {
+                continue;
+            }
+            
+            let mut body_cursor = child.walk();
+            for method_node in child.children(&mut body_cursor) {
+                if method_node.kind() != "method_declaration" || !self.has_annotation(source, &method_node, &["Bean"]) {
+                    continue;
+                }
+                
+                let text = |field: &str| method_node.child_by_field_name(field)
+                    .and_then(|n| source.get(n.byte_range()))
+                    .map(|s| s.to_string());
+                let (Some(name), Some(return_type)) = (text("name"), text("type")) else {
+                    continue;
+                };
+                
+                let mut parameter_types = Vec::new();
+                if let Some(params) = method_node.child_by_field_name("parameters") {
+                    let mut param_cursor = params.walk();
+                    for param in params.children(&mut param_cursor) {
+                        if param.kind() == "formal_parameter"
+                            && let Some(param_type) = param.child_by_field_name("type").and_then(|n| source.get(n.byte_range()))
+                        {
+                            parameter_types.push(param_type.to_string());
+                        }
+                    }
+                }
+                
+                beans.push((name, return_type, parameter_types));
             }
-            full_path.push_str(base_path.trim_start_matches('/'));
         }
         
-        // 添加方法路径
-        let method_path = method_http.path.trim_start_matches('/');
-        if !full_path.ends_with('/') && !method_path.is_empty() {
-            full_path.push('/');
+        for method in methods.iter_mut() {
+            let Some((_, _, parameter_types)) = beans.iter().find(|(name, _, _)| *name == method.name) else {
+                continue;
+            };
+            method.is_bean = true;
+            
+            // 直接调用其他 @Bean 方法：将未限定的调用目标补全为配置类中的方法
+            for call in method.calls.iter_mut() {
+                if call.target != method.name && beans.iter().any(|(name, _, _)| *name == call.target) {
+                    call.target = format!("{}::{}", class_name, call.target);
+                }
+            }
+            
+            // 参数注入：参数类型由另一个 @Bean 方法提供
+            for parameter_type in parameter_types {
+                for (name, return_type, _) in &beans {
+                    if return_type == parameter_type && *name != method.name {
+                        method.calls.push(MethodCall {
+                            target: format!("{}::{}", class_name, name),
+                            line: method.line_range.0,
+                            condition: None,
+                            low_confidence: false,
+                        });
+                    }
+                }
+            }
         }
-        full_path.push_str(method_path);
-        
-        Some(HttpAnnotation {
-            method: method_http.method,
-            path: full_path,
-            path_params: method_http.path_params,
-            is_feign_client: true,  // Feign 调用
-        })
     }
     
-    /// 从类节点中提取方法（包括接口中的抽象方法）
-    fn extract_methods_from_class(
+    /// 记录 @Configuration 类中 Spring Batch 作业与步骤的装配
+    /// 
+    /// - 返回 `Job` 的 `@Bean` 方法标记为批处理作业，作业名取自 `get("name")` /
+    ///   `new JobBuilder("name", ...)`，缺省时使用方法名
+    /// - 返回 `Step` 的 `@Bean` 方法中 `.tasklet(x)` / `.reader(x)` / `.processor(x)` /
+    ///   `.writer(x)` 记录为对 `x` 所属类 `execute` / `read` / `process` / `write` 方法的调用，
+    ///   `x` 可以是 `new Xxx(...)`、参数或字段名、或同一配置类中的 `@Bean` 方法调用
+    /// 
+    /// 作业到步骤的依赖已由 `attach_bean_dependencies` 记录
+    fn attach_batch_wiring(
         &self,
         source: &str,
-        file_path: &Path,
         class_node: &tree_sitter::Node,
-        class_name: &str,
         tree: &tree_sitter::Tree,
-        feign_client_info: &Option<FeignClientInfo>,
-        class_request_mapping: &Option<String>,
-        app_config: &ApplicationConfig,
-    ) -> Vec<MethodInfo> {
-        let mut methods = Vec::new();
+        package_name: &Option<String>,
+        methods: &mut [MethodInfo],
+    ) {
+        let job_name_re = Regex::new(r#"(?:\bget|\bJobBuilder)\s*\(\s*"([^"]+)""#).unwrap();
+        let component_re = Regex::new(r"\.(tasklet|reader|processor|writer)\s*\(\s*(new\s+)?([\w.]+)\s*(\()?").unwrap();
         
-        // 查找类体或接口体
-        let mut cursor = class_node.walk();
-        for child in class_node.children(&mut cursor) {
-            if child.kind() == "class_body" || child.kind() == "interface_body" {
-                let mut body_cursor = child.walk();
-                for body_child in child.children(&mut body_cursor) {
-                    // 处理普通方法声明和接口方法声明
-                    if body_child.kind() == "method_declaration" {
-                        if let Some(method_info) = self.extract_method_info(source, file_path, body_child, class_name, tree, feign_client_info, class_request_mapping, app_config) {
-                            methods.push(method_info);
+        let import_map = self.build_import_map(source, tree);
+        let simple_type = |type_text: &str| type_text.split('<').next().unwrap_or(type_text).trim().to_string();
+        
+        let Some(class_body) = class_node.child_by_field_name("body") else {
+            return;
+        };
+        
+        // 字段名 -> 类型，@Bean 方法名 -> 返回类型
+        let mut field_types = std::collections::HashMap::new();
+        let mut bean_types = std::collections::HashMap::new();
+        let mut cursor = class_body.walk();
+        for member in class_body.children(&mut cursor) {
+            let Some(type_text) = member.child_by_field_name("type").and_then(|n| source.get(n.byte_range())) else {
+                continue;
+            };
+            match member.kind() {
+                "field_declaration" => {
+                    let mut declarator_cursor = member.walk();
+                    for declarator in member.children_by_field_name("declarator", &mut declarator_cursor) {
+                        if let Some(name) = declarator.child_by_field_name("name").and_then(|n| source.get(n.byte_range())) {
+                            field_types.insert(name.to_string(), simple_type(type_text));
                         }
                     }
                 }
+                "method_declaration" if self.has_annotation(source, &member, &["Bean"]) => {
+                    if let Some(name) = member.child_by_field_name("name").and_then(|n| source.get(n.byte_range())) {
+                        bean_types.insert(name.to_string(), simple_type(type_text));
+                    }
+                }
+                _ => {}
             }
         }
         
-        methods
-    }
-    
-    /// 从方法节点提取方法信息
-    fn extract_method_info(
-        &self,
-        source: &str,
-        file_path: &Path,
-        method_node: tree_sitter::Node,
-        class_name: &str,
-        tree: &tree_sitter::Tree,
-        feign_client_info: &Option<FeignClientInfo>,
-        class_request_mapping: &Option<String>,
-        app_config: &ApplicationConfig,
-    ) -> Option<MethodInfo> {
-        // 查找方法名
-        let mut cursor = method_node.walk();
-        let mut method_name = None;
-        
-        for child in method_node.children(&mut cursor) {
-            if child.kind() == "identifier" {
-                if let Some(text) = source.get(child.byte_range()) {
-                    method_name = Some(text.to_string());
-                    break;
+        let mut cursor = class_body.walk();
+        for method_node in class_body.children(&mut cursor) {
+            if method_node.kind() != "method_declaration" || !self.has_annotation(source, &method_node, &["Bean"]) {
+                continue;
+            }
+            let Some(name) = method_node.child_by_field_name("name").and_then(|n| source.get(n.byte_range())) else {
+                continue;
+            };
+            let Some(method) = methods.iter_mut().find(|method| method.name == name) else {
+                continue;
+            };
+            let Some(method_text) = source.get(method_node.byte_range()) else {
+                continue;
+            };
+            
+            match bean_types.get(name).map(String::as_str) {
+                Some("Job") => {
+                    let job_name = job_name_re.captures(method_text)
+                        .map(|cap| cap[1].to_string())
+                        .unwrap_or_else(|| name.to_string());
+                    method.batch_job = Some(job_name);
                 }
+                Some("Step") => {
+                    // 参数名 -> 类型（参数优先于同名字段）
+                    let mut local_types = field_types.clone();
+                    if let Some(params) = method_node.child_by_field_name("parameters") {
+                        let mut param_cursor = params.walk();
+                        for param in params.children(&mut param_cursor) {
+                            let text = |field: &str| param.child_by_field_name(field).and_then(|n| source.get(n.byte_range()));
+                            if param.kind() == "formal_parameter"
+                                && let (Some(param_name), Some(param_type)) = (text("name"), text("type"))
+                            {
+                                local_types.insert(param_name.to_string(), simple_type(param_type));
+                            }
+                        }
+                    }
+                    
+                    for cap in component_re.captures_iter(method_text) {
+                        let operand = &cap[3];
+                        let component_type = if cap.get(2).is_some() {
+                            Some(operand.to_string())
+                        } else if cap.get(4).is_some() {
+                            bean_types.get(operand).cloned()
+                        } else {
+                            local_types.get(operand).cloned()
+                        };
+                        
+                        // Spring Batch 接口类型无法确定实现类，跳过
+                        let Some(component_type) = component_type.filter(|t| {
+                            !matches!(t.as_str(), "Tasklet" | "ItemReader" | "ItemProcessor" | "ItemWriter")
+                        }) else {
+                            continue;
+                        };
+                        
+                        let entry_method = match &cap[1] {
+                            "tasklet" => "execute",
+                            "reader" => "read",
+                            "processor" => "process",
+                            _ => "write",
+                        };
+                        let offset = cap.get(0).map(|m| m.start()).unwrap_or_default();
+                        method.calls.push(MethodCall {
+                            target: format!(
+                                "{}::{}",
+                                self.resolve_full_class_name(&component_type, &import_map, package_name),
+                                entry_method,
+                            ),
+                            line: method_node.start_position().row + 1 + method_text[..offset].matches('\n').count(),
+                            condition: None,
+                            low_confidence: false,
+                        });
+                    }
+                }
+                _ => {}
             }
         }
+    }
+    
+    /// 提取 WebFlux 函数式路由
+    /// 
+    /// 在返回 `RouterFunction` 的方法中识别两种写法：
+    /// - 构建器：`route().GET("/orders/{id}", handler::get)`
+    /// - 请求谓词：`route(GET("/orders/{id}"), handler::get)` / `.andRoute(POST("/orders"), ...)`
+    /// 
+    /// 处理函数为 `x::method` 方法引用时，`x` 可以是 `this`、字段、参数或类名；
+    /// 为 lambda 等其他表达式时，处理方法记为声明路由的方法本身
+    fn extract_functional_routes(&self, source: &str, file_path: &Path, tree: &tree_sitter::Tree, package_name: &Option<String>) -> Vec<FunctionalRoute> {
+        if !source.contains("RouterFunction") {
+            return Vec::new();
+        }
         
-        let name = method_name?;
-        let line_start = method_node.start_position().row + 1;
-        let line_end = method_node.end_position().row + 1;
-        let full_qualified_name = format!("{}::{}", class_name, name);
+        let route_re = Regex::new(r#"\b(GET|POST|PUT|DELETE|PATCH)\s*\(\s*"([^"]*)"\s*\)?\s*,\s*(?:(\w+)\s*::\s*(\w+))?"#).unwrap();
+        let app_config = self.load_application_config(file_path);
+        let import_map = self.build_import_map(source, tree);
+        let mut routes = Vec::new();
         
-        // 提取方法调用
-        let calls = self.extract_method_calls(source, &method_node, tree);
+        walk_tree(tree.root_node(), |node| {
+            if node.kind() != "method_declaration" {
+                return true;
+            }
+            let text = |n: Option<tree_sitter::Node>| n.and_then(|n| source.get(n.byte_range()));
+            let is_router = text(node.child_by_field_name("type")).is_some_and(|t| t.starts_with("RouterFunction"));
+            let class_name = std::iter::successors(node.parent(), |n| n.parent())
+                .find(|n| n.kind() == "class_declaration")
+                .and_then(|n| text(n.child_by_field_name("name")));
+            let (true, Some(class_name), Some(method_name), Some(method_text)) =
+                (is_router, class_name, text(node.child_by_field_name("name")), text(Some(node)))
+            else {
+                return false;
+            };
+            let full_class_name = match &package_name {
+                Some(pkg) => format!("{}.{}", pkg, class_name),
+                None => class_name.to_string(),
+            };
+            
+            // 字段、本地变量和参数的类型
+            let mut local_types = self.extract_field_types(source, &node, tree, package_name);
+            if let Some(params) = node.child_by_field_name("parameters") {
+                let mut cursor = params.walk();
+                for param in params.children(&mut cursor).filter(|param| param.kind() == "formal_parameter") {
+                    if let (Some(name), Some(param_type)) = (text(param.child_by_field_name("name")), text(param.child_by_field_name("type"))) {
+                        local_types.insert(name.to_string(), self.resolve_full_class_name(param_type, &import_map, package_name));
+                    }
+                }
+            }
+            
+            for cap in route_re.captures_iter(method_text) {
+                let method = match &cap[1] {
+                    "GET" => HttpMethod::GET,
+                    "POST" => HttpMethod::POST,
+                    "PUT" => HttpMethod::PUT,
+                    "DELETE" => HttpMethod::DELETE,
+                    _ => HttpMethod::PATCH,
+                };
+                let handler = match (cap.get(3), cap.get(4)) {
+                    (Some(receiver), Some(handler_method)) => {
+                        let handler_class = match receiver.as_str() {
+                            "this" => Some(full_class_name.clone()),
+                            name => local_types.get(name).cloned().or_else(|| {
+                                name.starts_with(char::is_uppercase)
+                                    .then(|| self.resolve_full_class_name(name, &import_map, package_name))
+                            }),
+                        };
+                        let Some(handler_class) = handler_class else {
+                            continue;
+                        };
+                        format!("{}::{}", handler_class, handler_method.as_str())
+                    }
+                    _ => format!("{}::{}", full_class_name, method_name),
+                };
+                
+                routes.push(FunctionalRoute {
+                    annotation: HttpAnnotation {
+                        method,
+                        path: full_http_path(&app_config, None, &cap[2]),
+                        path_params: self.extract_path_params(&cap[2]),
+                        is_feign_client: false,
+                    },
+                    handler,
+                });
+            }
+            false
+        });
         
-        // 提取 HTTP 注解（如果是 FeignClient，需要组合类级别和方法级别的注解）
-        let http_annotations = if let Some(feign_info) = feign_client_info {
-            self.extract_feign_http_annotation(source, &method_node, feign_info)
-        } else {
-            self.extract_http_annotations(source, &method_node, class_request_mapping, app_config)
+        routes
+    }
+    
+    /// 提取 Spring `RestClient` 流式调用的 HTTP 端点
+    /// 
+    /// 识别方法中每个 `restClient.get().uri("/api/x")` 形式的调用，生成 `is_feign_client = true` 的注解，
+    /// 与 Feign 调用一样作为消费者匹配提供者。URI 中的协议和查询参数会被去掉；
+    /// 相对 URI 在源文件只配置了一个 `baseUrl(...)` 时拼接该地址
+    fn extract_rest_client_calls(&self, source: &str, method_node: &tree_sitter::Node) -> Vec<HttpAnnotation> {
+        let Some(text) = source.get(method_node.byte_range()) else {
+            return Vec::new();
         };
-        
-        // 提取 Kafka 操作
-        let kafka_operations = self.extract_kafka_operations(source, &method_node);
-        
-        // 提取数据库操作
-        let db_operations = self.extract_db_operations(source, &method_node);
-        
-        // 提取 Redis 操作
-        let redis_operations = self.extract_redis_operations(source, &method_node);
-        
-        Some(MethodInfo {
-            name,
-            full_qualified_name,
-            file_path: file_path.to_path_buf(),
-            line_range: (line_start, line_end),
-            calls,
-            http_annotations,
-            kafka_operations,
-            db_operations,
-            redis_operations,
-        })
+        let call_pattern = Regex::new(r#"\.(get|post|put|delete|patch)\s*\(\s*\)\s*\.uri\s*\(\s*"([^"]+)""#).unwrap();
+        let base_pattern = Regex::new(r#"\.baseUrl\s*\(\s*"([^"]+)""#).unwrap();
+        let base_urls: std::collections::HashSet<&str> = base_pattern.captures_iter(source)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str()))
+            .collect();
+        let base_url = (base_urls.len() == 1).then(|| base_urls.into_iter().next()).flatten();
+        
+        call_pattern.captures_iter(text)
+            .map(|cap| {
+                let method = match &cap[1] {
+                    "get" => HttpMethod::GET,
+                    "post" => HttpMethod::POST,
+                    "put" => HttpMethod::PUT,
+                    "delete" => HttpMethod::DELETE,
+                    _ => HttpMethod::PATCH,
+                };
+                
+                let mut uri = cap[2].split('?').next().unwrap_or_default().to_string();
+                if !uri.contains("://") && let Some(base_url) = base_url {
+                    uri = format!("{}/{}", base_url.trim_end_matches('/'), uri.trim_start_matches('/'));
+                }
+                let path = uri.split_once("://").map_or(uri.as_str(), |(_, rest)| rest)
+                    .trim_start_matches('/')
+                    .to_string();
+                
+                HttpAnnotation {
+                    method,
+                    path_params: self.extract_path_params(&path),
+                    path,
+                    is_feign_client: true,
+                }
+            })
+            .collect()
     }
     
-    /// 提取方法调用
-    fn extract_method_calls(&self, source: &str, method_node: &tree_sitter::Node, tree: &tree_sitter::Tree) -> Vec<MethodCall> {
-        let mut calls = Vec::new();
-        
-        // 提取导入语句，建立简单类名到完整类名的映射
-        let import_map = self.build_import_map(source, tree);
+    /// 解析注解节点的名称和参数文本
+    fn parse_annotation_name_and_args(&self, source: &str, annotation_node: tree_sitter::Node) -> Option<(String, Option<String>)> {
+        let mut cursor = annotation_node.walk();
+        let mut annotation_name = None;
+        let mut annotation_args = None;
         
-        // 提取类中的字段声明和方法内的本地变量，建立变量名到类型的映射
-        let field_types = self.extract_field_types(source, method_node, tree);
+        for child in annotation_node.children(&mut cursor) {
+            let Some(text) = source.get(child.byte_range()) else {
+                continue;
+            };
+            match child.kind() {
+                "identifier" | "scoped_identifier" => {
+                    // 全限定名中可能夹杂空白（如 `@org.foo .Bar`），统一去除
+                    annotation_name = Some(text.split_whitespace().collect::<String>());
+                }
+                "annotation_argument_list" => annotation_args = Some(text.to_string()),
+                _ => {}
+            }
+        }
         
-        self.walk_node_for_calls(source, *method_node, &mut calls, &field_types, &import_map);
-        calls
+        Some((annotation_name?, annotation_args))
     }
     
-    /// 构建导入映射：简单类名 -> 完整类名
-    fn build_import_map(&self, source: &str, tree: &tree_sitter::Tree) -> std::collections::HashMap<String, String> {
-        let mut import_map = std::collections::HashMap::new();
-        let root_node = tree.root_node();
-        
-        self.walk_node_for_import_map(source, root_node, &mut import_map);
+    /// 从注解参数中提取 `attr = Xxx.class` 形式的类名
+    fn extract_class_literal_attribute(&self, args: &str, attr_name: &str) -> Option<String> {
+        let pattern = format!(r#"{}\s*=\s*([\w.]+)\.class"#, attr_name);
+        let re = Regex::new(&pattern).ok()?;
         
-        import_map
+        re.captures(args)
+            .and_then(|cap| cap.get(1))
+            .map(|m| m.as_str().to_string())
     }
     
-    /// 递归遍历节点构建导入映射
-    fn walk_node_for_import_map(
-        &self,
-        source: &str,
-        node: tree_sitter::Node,
-        import_map: &mut std::collections::HashMap<String, String>,
-    ) {
-        if node.kind() == "import_declaration" {
-            let mut cursor = node.walk();
-            for child in node.children(&mut cursor) {
-                if child.kind() == "scoped_identifier" {
-                    if let Some(full_name) = source.get(child.byte_range()) {
-                        // 从完整类名中提取简单类名
-                        if let Some(simple_name) = full_name.split('.').last() {
-                            import_map.insert(simple_name.to_string(), full_name.to_string());
+    /// 提取类实现的接口列表
+    fn extract_implements_interfaces(&self, source: &str, class_node: &tree_sitter::Node, tree: &tree_sitter::Tree, package_name: &Option<String>) -> Vec<String> {
+        let mut interfaces = Vec::new();
+        
+        // 构建导入映射，用于将简单类名转换为完整类名
+        let import_map = self.build_import_map(source, tree);
+        
+        // 查找 super_interfaces 节点（包含 implements 子句）
+        let mut cursor = class_node.walk();
+        for child in class_node.children(&mut cursor) {
+            if child.kind() == "super_interfaces" {
+                // 在 super_interfaces 中查找 type_list（implements 后的接口列表）
+                let mut super_cursor = child.walk();
+                for super_child in child.children(&mut super_cursor) {
+                    if super_child.kind() == "type_list" {
+                        // 提取接口名称
+                        let mut type_cursor = super_child.walk();
+                        for type_child in super_child.children(&mut type_cursor) {
+                            // 嵌套接口（如 Thrift 生成的 `OrderService.Iface`）为 scoped_type_identifier
+                            if matches!(type_child.kind(), "type_identifier" | "scoped_type_identifier")
+                                && let Some(interface_name) = source.get(type_child.byte_range())
+                            {
+                                // 尝试将简单类名转换为完整类名
+                                let full_interface_name = self.resolve_full_class_name(
+                                    interface_name,
+                                    &import_map,
+                                    package_name,
+                                );
+                                interfaces.push(full_interface_name);
+                            }
                         }
                     }
                 }
             }
         }
         
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            self.walk_node_for_import_map(source, child, import_map);
-        }
+        interfaces
     }
     
-    /// 提取类中的字段类型映射（包括类字段和方法内的本地变量）
-    fn extract_field_types(&self, source: &str, method_node: &tree_sitter::Node, tree: &tree_sitter::Tree) -> std::collections::HashMap<String, String> {
-        let mut field_types = std::collections::HashMap::new();
-        
-        // 1. 向上查找到类节点，提取类字段
-        let mut current = method_node.parent();
-        while let Some(node) = current {
-            if node.kind() == "class_declaration" {
-                // 在类体中查找字段声明
-                let mut cursor = node.walk();
-                for child in node.children(&mut cursor) {
-                    if child.kind() == "class_body" {
-                        let mut body_cursor = child.walk();
-                        for body_child in child.children(&mut body_cursor) {
-                            if body_child.kind() == "field_declaration" {
-                                self.extract_field_type_from_declaration(source, body_child, &mut field_types);
-                            }
-                        }
-                    }
-                }
-                break;
-            }
-            current = node.parent();
-        }
-        
-        // 2. 提取方法内的本地变量
-        self.extract_local_variable_types(source, method_node, tree, &mut field_types);
-        
-        field_types
-    }
-    
-    /// 提取方法内的本地变量类型，并解析为完整类名
-    fn extract_local_variable_types(
-        &self,
-        source: &str,
-        method_node: &tree_sitter::Node,
-        tree: &tree_sitter::Tree,
-        field_types: &mut std::collections::HashMap<String, String>,
-    ) {
-        // 先提取本地变量的简单类型
-        self.walk_node_for_local_vars(source, *method_node, field_types);
+    /// 提取类 `extends` 的父类（完整类名），泛型父类只保留原始类型
+    fn extract_superclass(&self, source: &str, class_node: &tree_sitter::Node, tree: &tree_sitter::Tree, package_name: &Option<String>) -> Option<String> {
+        let superclass = class_node.child_by_field_name("superclass")?;
+        
+        let mut cursor = superclass.walk();
+        let type_node = superclass.named_children(&mut cursor).next()?;
+        let type_node = if type_node.kind() == "generic_type" {
+            type_node.named_child(0)?
+        } else {
+            type_node
+        };
+        let name = source.get(type_node.byte_range())?;
         
-        // 获取导入映射和包名，用于解析完整类名
         let import_map = self.build_import_map(source, tree);
-        let package_name = self.extract_package_name(source, tree);
-        
-        // 将简单类名解析为完整类名
-        let mut resolved_types = std::collections::HashMap::new();
-        for (var_name, simple_type) in field_types.iter() {
-            let full_type = self.resolve_full_class_name(simple_type, &import_map, &package_name);
-            resolved_types.insert(var_name.clone(), full_type);
-        }
-        
-        // 更新 field_types
-        *field_types = resolved_types;
+        Some(self.resolve_full_class_name(name, &import_map, package_name))
     }
     
-    /// 递归遍历节点查找本地变量声明
-    fn walk_node_for_local_vars(
+    /// 将简单类名解析为完整类名
+    /// 
+    /// 嵌套类型（如 `OrderService.Client`）按外层类名解析；已是全限定名的保持不变
+    fn resolve_full_class_name(
         &self,
-        source: &str,
-        node: tree_sitter::Node,
-        field_types: &mut std::collections::HashMap<String, String>,
-    ) {
-        if node.kind() == "local_variable_declaration" {
-            // 提取本地变量的类型和名称
-            self.extract_field_type_from_declaration(source, node, field_types);
-        }
-        
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            self.walk_node_for_local_vars(source, child, field_types);
+        simple_name: &str,
+        import_map: &std::collections::HashMap<String, String>,
+        package_name: &Option<String>,
+    ) -> String {
+        // 首先尝试从导入映射中查找
+        if let Some(full_name) = import_map.get(simple_name) {
+            return full_name.clone();
         }
-    }
-    
-    /// 从字段声明中提取字段名和类型
-    fn extract_field_type_from_declaration(
-        &self,
-        source: &str,
-        field_node: tree_sitter::Node,
-        field_types: &mut std::collections::HashMap<String, String>,
-    ) {
-        let mut field_type = None;
-        let mut field_name = None;
         
-        let mut cursor = field_node.walk();
-        for child in field_node.children(&mut cursor) {
-            match child.kind() {
-                "type_identifier" | "generic_type" => {
-                    if let Some(text) = source.get(child.byte_range()) {
-                        field_type = Some(text.to_string());
-                    }
-                }
-                "variable_declarator" => {
-                    // 在 variable_declarator 中查找 identifier
-                    let mut var_cursor = child.walk();
-                    for var_child in child.children(&mut var_cursor) {
-                        if var_child.kind() == "identifier" {
-                            if let Some(text) = source.get(var_child.byte_range()) {
-                                field_name = Some(text.to_string());
-                                break;
-                            }
-                        }
-                    }
-                }
-                _ => {}
+        if let Some((outer, nested)) = simple_name.split_once('.') {
+            if let Some(full_outer) = import_map.get(outer) {
+                return format!("{}.{}", full_outer, nested);
+            }
+            // 首字母小写的是包名，视为全限定名
+            if !outer.starts_with(char::is_uppercase) {
+                return simple_name.to_string();
             }
         }
         
-        if let (Some(name), Some(type_name)) = (field_name, field_type) {
-            field_types.insert(name, type_name);
+        // 如果没有找到，假设在同一个包中
+        if let Some(pkg) = package_name {
+            return format!("{}.{}", pkg, simple_name);
         }
+        
+        // 否则返回简单类名
+        simple_name.to_string()
     }
     
-    /// 递归遍历节点查找方法调用
-    fn walk_node_for_calls(
-        &self,
-        source: &str,
-        node: tree_sitter::Node,
-        calls: &mut Vec<MethodCall>,
-        field_types: &std::collections::HashMap<String, String>,
-        import_map: &std::collections::HashMap<String, String>,
-    ) {
-        if node.kind() == "method_invocation" {
-            // 查找方法调用的对象和方法名
-            let mut cursor = node.walk();
-            let mut identifiers = Vec::new();
-            let mut scoped_identifiers = Vec::new();
-            
-            for child in node.children(&mut cursor) {
-                if child.kind() == "identifier" {
-                    if let Some(text) = source.get(child.byte_range()) {
-                        identifiers.push(text.to_string());
-                    }
-                } else if child.kind() == "scoped_identifier" {
-                    // 处理静态方法调用，如 ClassName.staticMethod()
-                    if let Some(text) = source.get(child.byte_range()) {
-                        scoped_identifiers.push(text.to_string());
+    /// 提取包名
+    fn extract_package_name(&self, source: &str, tree: &tree_sitter::Tree) -> Option<String> {
+        let root_node = tree.root_node();
+        let mut cursor = root_node.walk();
+        
+        for child in root_node.children(&mut cursor) {
+            if child.kind() == "package_declaration" {
+                // 在 package_declaration 中查找 scoped_identifier
+                let mut pkg_cursor = child.walk();
+                for pkg_child in child.children(&mut pkg_cursor) {
+                    if pkg_child.kind() == "scoped_identifier"
+                        && let Some(text) = source.get(pkg_child.byte_range())
+                    {
+                        return Some(text.to_string());
                     }
                 }
             }
-            
-            let line = node.start_position().row + 1;
-            
-            // 处理静态方法调用：ClassName.staticMethod() 或 package.ClassName.staticMethod()
-            if !scoped_identifiers.is_empty() && !identifiers.is_empty() {
-                // scoped_identifier 包含类名（可能带包名），identifier 是方法名
-                let class_name = &scoped_identifiers[0];
-                let method_name = &identifiers[identifiers.len() - 1];
-                
-                // 尝试将简单类名转换为完整类名
-                let full_class_name = import_map.get(class_name)
-                    .unwrap_or(class_name);
-                
-                let target = format!("{}::{}", full_class_name, method_name);
-                calls.push(MethodCall {
-                    target,
-                    line,
-                });
-                return;
-            }
-            
-            // 对于 obj.method() 形式，有两个 identifier：对象名和方法名
-            // 对于 method() 形式，只有一个 identifier：方法名
-            let (object_name, method_name) = if identifiers.len() >= 2 {
-                (Some(identifiers[0].clone()), identifiers[identifiers.len() - 1].clone())
-            } else if identifiers.len() == 1 {
-                (None, identifiers[0].clone())
-            } else {
-                return;
-            };
-            
-            // 如果有对象名，尝试解析为完整的类名::方法名
-            let target = if let Some(obj) = object_name {
-                if let Some(class_type) = field_types.get(&obj) {
-                    // 尝试将简单类名转换为完整类名
-                    let full_class_name = import_map.get(class_type)
-                        .unwrap_or(class_type);
-                    format!("{}::{}", full_class_name, method_name)
-                } else {
-                    // 可能是静态方法调用，尝试从 import_map 中查找
-                    if let Some(full_class_name) = import_map.get(&obj) {
-                        format!("{}::{}", full_class_name, method_name)
-                    } else {
-                        method_name.clone()
-                    }
-                }
-            } else {
-                method_name.clone()
-            };
-            
-            calls.push(MethodCall {
-                target,
-                line,
-            });
         }
         
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            self.walk_node_for_calls(source, child, calls, field_types, import_map);
-        }
+        None
     }
     
-    /// 提取 HTTP 注解（Spring Framework）
-    fn extract_http_annotations(&self, source: &str, method_node: &tree_sitter::Node, class_request_mapping: &Option<String>, app_config: &ApplicationConfig) -> Option<HttpAnnotation> {
-        // 查找方法节点的 modifiers 子节点
-        let mut cursor = method_node.walk();
-        for child in method_node.children(&mut cursor) {
+    /// 提取类级别的 FeignClient 注解
+    fn extract_feign_client_annotation(&self, source: &str, class_node: &tree_sitter::Node) -> Option<FeignClientInfo> {
+        // 查找类节点的 modifiers 子节点
+        let mut cursor = class_node.walk();
+        for child in class_node.children(&mut cursor) {
             if child.kind() == "modifiers" {
                 // 在 modifiers 中查找注解
                 let mut mod_cursor = child.walk();
                 for mod_child in child.children(&mut mod_cursor) {
-                    if mod_child.kind() == "marker_annotation" || mod_child.kind() == "annotation" {
-                        if let Some(mut http_ann) = self.parse_http_annotation(source, mod_child) {
-                            // 组合完整路径：application.name/context-path/class-path/method-path
-                            let mut full_path = String::new();
-                            
-                            // 添加 application.name
-                            if let Some(app_name) = &app_config.application_name {
-                                full_path.push_str(app_name);
-                            }
-                            
-                            // 添加 context-path
-                            if let Some(context_path) = &app_config.context_path {
-                                if !context_path.is_empty() {
-                                    if !full_path.is_empty() && !full_path.ends_with('/') {
-                                        full_path.push('/');
-                                    }
-                                    full_path.push_str(context_path.trim_start_matches('/'));
-                                }
-                            }
-                            
-                            // 添加类级别的 RequestMapping 路径
-                            if let Some(class_path) = class_request_mapping {
-                                if !full_path.is_empty() && !full_path.ends_with('/') {
-                                    full_path.push('/');
-                                }
-                                full_path.push_str(class_path.trim_start_matches('/'));
-                            }
-                            
-                            // 添加方法级别的路径
-                            let method_path = http_ann.path.trim_start_matches('/');
-                            if !full_path.is_empty() && !full_path.ends_with('/') && !method_path.is_empty() {
-                                full_path.push('/');
-                            }
-                            full_path.push_str(method_path);
-                            
-                            http_ann.path = full_path;
-                            return Some(http_ann);
-                        }
+                    if (mod_child.kind() == "marker_annotation" || mod_child.kind() == "annotation")
+                        && let Some(feign_info) = self.parse_feign_client_annotation(source, mod_child)
+                    {
+                        return Some(feign_info);
                     }
                 }
             }
@@ -884,20 +1340,19 @@ impl JavaParser {
         None
     }
     
-    /// 提取 HTTP 注解（原始版本，不包含应用配置）
-    /// 用于 FeignClient 等场景
-    fn extract_http_annotations_raw(&self, source: &str, method_node: &tree_sitter::Node) -> Option<HttpAnnotation> {
-        // 查找方法节点的 modifiers 子节点
-        let mut cursor = method_node.walk();
-        for child in method_node.children(&mut cursor) {
+    /// 提取类级别的 RequestMapping 注解
+    fn extract_class_level_request_mapping(&self, source: &str, class_node: &tree_sitter::Node) -> Option<String> {
+        // 查找类节点的 modifiers 子节点
+        let mut cursor = class_node.walk();
+        for child in class_node.children(&mut cursor) {
             if child.kind() == "modifiers" {
                 // 在 modifiers 中查找注解
                 let mut mod_cursor = child.walk();
                 for mod_child in child.children(&mut mod_cursor) {
-                    if mod_child.kind() == "marker_annotation" || mod_child.kind() == "annotation" {
-                        if let Some(http_ann) = self.parse_http_annotation(source, mod_child) {
-                            return Some(http_ann);
-                        }
+                    if (mod_child.kind() == "marker_annotation" || mod_child.kind() == "annotation")
+                        && let Some(path) = self.parse_request_mapping_annotation(source, mod_child)
+                    {
+                        return Some(path);
                     }
                 }
             }
@@ -906,965 +1361,3689 @@ impl JavaParser {
         None
     }
     
-    /// 解析 HTTP 注解
-    fn parse_http_annotation(&self, source: &str, annotation_node: tree_sitter::Node) -> Option<HttpAnnotation> {
+    /// 解析 RequestMapping 注解获取路径
+    fn parse_request_mapping_annotation(&self, source: &str, annotation_node: tree_sitter::Node) -> Option<String> {
         // 获取注解名称
-        let mut cursor = annotation_node.walk();
-        let mut annotation_name = None;
-        let mut annotation_args = None;
+        let (name, annotation_args) = self.parse_annotation_name_and_args(source, annotation_node)?;
         
-        for child in annotation_node.children(&mut cursor) {
-            if child.kind() == "identifier" || child.kind() == "scoped_identifier" {
-                if let Some(text) = source.get(child.byte_range()) {
-                    annotation_name = Some(text.to_string());
-                }
-            } else if child.kind() == "annotation_argument_list" {
-                if let Some(text) = source.get(child.byte_range()) {
-                    annotation_args = Some(text.to_string());
-                }
-            }
+        // 检查是否是 RequestMapping 注解（支持全限定名）
+        if annotation_simple_name(&name) != "RequestMapping" {
+            return None;
         }
         
-        let name = annotation_name?;
+        // 提取路径
+        self.extract_path_from_args(&annotation_args)
+    }
+    
+    /// 解析 FeignClient 注解
+    fn parse_feign_client_annotation(&self, source: &str, annotation_node: tree_sitter::Node) -> Option<FeignClientInfo> {
+        // 获取注解名称
+        let (name, annotation_args) = self.parse_annotation_name_and_args(source, annotation_node)?;
         
-        // 检查是否是 Spring HTTP 注解
-        let (method, path) = if name.contains("GetMapping") {
-            (HttpMethod::GET, self.extract_path_from_args(&annotation_args))
-        } else if name.contains("PostMapping") {
-            (HttpMethod::POST, self.extract_path_from_args(&annotation_args))
-        } else if name.contains("PutMapping") {
-            (HttpMethod::PUT, self.extract_path_from_args(&annotation_args))
-        } else if name.contains("DeleteMapping") {
-            (HttpMethod::DELETE, self.extract_path_from_args(&annotation_args))
-        } else if name.contains("PatchMapping") {
-            (HttpMethod::PATCH, self.extract_path_from_args(&annotation_args))
-        } else if name.contains("RequestMapping") {
-            let method = self.extract_request_method_from_args(&annotation_args).unwrap_or(HttpMethod::GET);
-            let path = self.extract_path_from_args(&annotation_args);
-            (method, path)
-        } else {
+        // 检查是否是 FeignClient 注解（支持全限定名）
+        if annotation_simple_name(&name) != "FeignClient" {
             return None;
-        };
+        }
         
-        let path_str = path?;
-        let path_params = self.extract_path_params(&path_str);
+        let args_text = annotation_args?;
         
-        Some(HttpAnnotation {
-            method,
-            path: path_str,
-            path_params,
-            is_feign_client: false,  // 普通 HTTP 接口声明
+        // 提取 value 或 name 属性（服务名称）
+        let service_name = self.extract_feign_attribute(&args_text, "value")
+            .or_else(|| self.extract_feign_attribute(&args_text, "name"))?;
+        
+        // 提取 path 属性（基础路径）
+        let base_path = self.extract_feign_attribute(&args_text, "path");
+        
+        Some(FeignClientInfo {
+            service_name,
+            base_path,
         })
     }
     
-    /// 从注解参数中提取路径
-    fn extract_path_from_args(&self, args: &Option<String>) -> Option<String> {
-        if let Some(args_text) = args {
-            // 查找字符串字面量
-            let re = Regex::new(r#""([^"]+)""#).unwrap();
-            if let Some(cap) = re.captures(args_text) {
-                return cap.get(1).map(|m| m.as_str().to_string());
-            }
-        }
-        None
-    }
-    
-    /// 从 RequestMapping 参数中提取 HTTP 方法
-    fn extract_request_method_from_args(&self, args: &Option<String>) -> Option<HttpMethod> {
-        if let Some(args_text) = args {
-            if args_text.contains("RequestMethod.GET") {
-                return Some(HttpMethod::GET);
-            } else if args_text.contains("RequestMethod.POST") {
-                return Some(HttpMethod::POST);
-            } else if args_text.contains("RequestMethod.PUT") {
-                return Some(HttpMethod::PUT);
-            } else if args_text.contains("RequestMethod.DELETE") {
-                return Some(HttpMethod::DELETE);
-            } else if args_text.contains("RequestMethod.PATCH") {
-                return Some(HttpMethod::PATCH);
-            }
+    /// 从 FeignClient 注解参数中提取指定属性的值
+    fn extract_feign_attribute(&self, args: &str, attr_name: &str) -> Option<String> {
+        // 匹配 attr_name = "value" 格式
+        let pattern = format!(r#"{}\s*=\s*"([^"]+)""#, attr_name);
+        let re = Regex::new(&pattern).ok()?;
+        
+        if let Some(cap) = re.captures(args) {
+            return cap.get(1).map(|m| m.as_str().to_string());
         }
+        
         None
     }
     
-    /// 提取路径参数
-    fn extract_path_params(&self, path: &str) -> Vec<String> {
-        let re = Regex::new(r"\{([^}]+)\}").unwrap();
-        re.captures_iter(path)
-            .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
-            .collect()
+    /// 提取 Feign 方法的 HTTP 注解（组合类级别和方法级别的路径）
+    fn extract_feign_http_annotation(
+        &self,
+        source: &str,
+        method_node: &tree_sitter::Node,
+        feign_info: &FeignClientInfo,
+    ) -> Option<HttpAnnotation> {
+        // 提取方法级别的 HTTP 注解（不使用应用配置，因为这是调用其他服务）
+        let method_http = self.extract_http_annotations_raw(source, method_node)?;
+        
+        // 组合路径：service_name/base_path/method_path
+        let mut full_path = feign_info.service_name.clone();
+        
+        if let Some(base_path) = &feign_info.base_path {
+            // 确保路径正确拼接
+            if !full_path.ends_with('/') {
+                full_path.push('/');
+            }
+            full_path.push_str(base_path.trim_start_matches('/'));
+        }
+        
+        // 添加方法路径
+        let method_path = method_http.path.trim_start_matches('/');
+        if !full_path.ends_with('/') && !method_path.is_empty() {
+            full_path.push('/');
+        }
+        full_path.push_str(method_path);
+        
+        Some(HttpAnnotation {
+            method: method_http.method,
+            path: full_path,
+            path_params: method_http.path_params,
+            is_feign_client: true,  // Feign 调用
+        })
     }
     
-    /// 提取 Kafka 操作
-    fn extract_kafka_operations(&self, source: &str, method_node: &tree_sitter::Node) -> Vec<KafkaOperation> {
-        let mut operations = Vec::new();
+    /// 从类节点中提取方法（包括接口中的抽象方法和构造器）
+    /// 
+    /// `superclass` 为类声明的父类（每个类只解析一次），用于解析方法中的 `super.method()` 调用
+    #[allow(clippy::too_many_arguments)]
+    fn extract_methods_from_class(
+        &self,
+        source: &str,
+        file_path: &Path,
+        class_node: &tree_sitter::Node,
+        class_name: &str,
+        superclass: Option<&str>,
+        tree: &tree_sitter::Tree,
+        package_name: &Option<String>,
+        feign_client_info: &Option<FeignClientInfo>,
+        class_request_mapping: &Option<String>,
+        app_config: &ApplicationConfig,
+    ) -> Vec<MethodInfo> {
+        let mut methods = Vec::new();
         
-        // 查找 @KafkaListener 注解 - 只在方法自己的 modifiers 中查找
-        let mut cursor = method_node.walk();
-        for child in method_node.children(&mut cursor) {
-            if child.kind() == "modifiers" {
-                if let Some(text) = source.get(child.byte_range()) {
-                    if text.contains("@KafkaListener") {
-                        let topic_pattern = Regex::new(r#"topics\s*=\s*"([^"]+)""#).unwrap();
-                        if let Some(cap) = topic_pattern.captures(text) {
-                            if let Some(topic) = cap.get(1) {
-                                operations.push(KafkaOperation {
-                                    operation_type: KafkaOpType::Consume,
-                                    topic: topic.as_str().to_string(),
-                                    line: method_node.start_position().row + 1,
-                                });
-                            }
-                        }
+        // 查找类体或接口体
+        let mut cursor = class_node.walk();
+        for child in class_node.children(&mut cursor) {
+            if child.kind() == "class_body" || child.kind() == "interface_body" {
+                let mut body_cursor = child.walk();
+                for body_child in child.children(&mut body_cursor) {
+                    // 处理普通方法声明、接口方法声明和构造器声明（包括 record 的紧凑构造器）
+                    if matches!(body_child.kind(), "method_declaration" | "constructor_declaration" | "compact_constructor_declaration")
+                        && let Some(method_info) = self.extract_method_info(source, file_path, body_child, class_name, superclass, tree, package_name, feign_client_info, class_request_mapping, app_config)
+                    {
+                        methods.push(method_info);
                     }
                 }
             }
         }
         
-        // 查找方法体中的 send 调用
-        if let Some(text) = source.get(method_node.byte_range()) {
-            let producer_pattern = Regex::new(r#"\.send\s*\(\s*"([^"]+)""#).unwrap();
-            for cap in producer_pattern.captures_iter(text) {
-                if let Some(topic) = cap.get(1) {
-                    operations.push(KafkaOperation {
-                        operation_type: KafkaOpType::Produce,
-                        topic: topic.as_str().to_string(),
-                        line: method_node.start_position().row + 1,
-                    });
-                }
+        methods
+    }
+    
+    /// 从方法节点提取方法信息
+    /// 
+    /// 构造器的方法名记为 `<init>`，与 `new X()` 产生的调用目标对应
+    #[allow(clippy::too_many_arguments)]
+    fn extract_method_info(
+        &self,
+        source: &str,
+        file_path: &Path,
+        method_node: tree_sitter::Node,
+        class_name: &str,
+        superclass: Option<&str>,
+        tree: &tree_sitter::Tree,
+        package_name: &Option<String>,
+        feign_client_info: &Option<FeignClientInfo>,
+        class_request_mapping: &Option<String>,
+        app_config: &ApplicationConfig,
+    ) -> Option<MethodInfo> {
+        // 查找方法名
+        let mut cursor = method_node.walk();
+        let mut method_name = None;
+        
+        for child in method_node.children(&mut cursor) {
+            if child.kind() == "identifier"
+                && let Some(text) = source.get(child.byte_range())
+            {
+                method_name = Some(text.to_string());
+                break;
             }
         }
         
-        operations
+        let name = if matches!(method_node.kind(), "constructor_declaration" | "compact_constructor_declaration") {
+            "<init>".to_string()
+        } else {
+            method_name?
+        };
+        let line_start = method_node.start_position().row + 1;
+        let line_end = method_node.end_position().row + 1;
+        let full_qualified_name = format!("{}::{}", class_name, name);
+        
+        // 提取方法调用
+        let calls = self.extract_method_calls(source, &method_node, class_name, superclass, tree, package_name);
+        
+        // 提取 HTTP 注解（如果是 FeignClient，需要组合类级别和方法级别的注解）
+        // 没有注解时记录方法中的 RestClient 调用，第一个之外的调用放入 extra_http_annotations
+        let (http_annotations, extra_http_annotations) = if let Some(feign_info) = feign_client_info {
+            (self.extract_feign_http_annotation(source, &method_node, feign_info), Vec::new())
+        } else if let Some(annotation) = self.extract_http_annotations(source, &method_node, class_request_mapping, app_config) {
+            (Some(annotation), Vec::new())
+        } else {
+            let mut calls = self.extract_rest_client_calls(source, &method_node).into_iter();
+            (calls.next(), calls.collect())
+        };
+        
+        // 提取 Kafka 操作
+        let kafka_operations = self.extract_kafka_operations(source, &method_node, tree, package_name);
+        
+        // 提取数据库操作
+        let db_operations = self.extract_db_operations(source, &method_node);
+        
+        // 提取 Redis 操作
+        let redis_operations = self.extract_redis_operations(source, &method_node);
+        
+        // 提取 GraphQL 解析器
+        let graphql_resolver = self.extract_graphql_resolver(source, &method_node, &name);
+        
+        // 提取 OpenAPI/Swagger 接口文档
+        let api_operation = self.extract_api_operation(source, &method_node);
+        
+        // 提取 @ConditionalOnProperty 特性开关
+        let conditional = self.extract_conditional_property(source, &method_node);
+        
+        // 只有一次调用且方法体只有这一条语句的为纯委托方法
+        let is_delegation = calls.len() == 1 && is_single_call_body(&method_node);
+        
+        // 提取 @Retryable / @CircuitBreaker 容错注解
+        let resilience = self.extract_resilience(source, &method_node);
+        
+        Some(MethodInfo {
+            name,
+            full_qualified_name,
+            file_path: file_path.to_path_buf(),
+            line_range: (line_start, line_end),
+            calls,
+            http_annotations,
+            kafka_operations,
+            db_operations,
+            redis_operations,
+            rpc_operations: Vec::new(),
+            thrift_operations: Vec::new(),
+            is_bean: false,
+            conditional,
+            graphql_resolver,
+            batch_job: None,
+            api_operation,
+            scheduled: self.has_annotation(source, &method_node, &["Scheduled"]),
+            is_delegation,
+            resilience,
+            column_range: Some((method_node.start_position().column, method_node.end_position().column)),
+            visibility: self.extract_visibility(&method_node),
+            extra_line_ranges: Vec::new(),
+            extra_http_annotations,
+        })
     }
     
-    /// 提取数据库操作
-    fn extract_db_operations(&self, source: &str, method_node: &tree_sitter::Node) -> Vec<DbOperation> {
-        let mut operations = Vec::new();
+    /// 提取方法调用
+    /// 
+    /// `this.method()` 解析到当前类，`super.method()` 解析到声明的父类 `superclass`
+    fn extract_method_calls(&self, source: &str, method_node: &tree_sitter::Node, class_name: &str, superclass: Option<&str>, tree: &tree_sitter::Tree, package_name: &Option<String>) -> Vec<MethodCall> {
+        let mut calls = Vec::new();
         
-        if let Some(text) = source.get(method_node.byte_range()) {
-            // 查找 SQL 语句
-            let sql_patterns = vec![
-                (Regex::new(r"(?i)SELECT\s+.+?\s+FROM\s+(\w+)").unwrap(), DbOpType::Select),
-                (Regex::new(r"(?i)INSERT\s+INTO\s+(\w+)").unwrap(), DbOpType::Insert),
-                (Regex::new(r"(?i)UPDATE\s+(\w+)\s+SET").unwrap(), DbOpType::Update),
-                (Regex::new(r"(?i)DELETE\s+FROM\s+(\w+)").unwrap(), DbOpType::Delete),
-            ];
-            
-            for (pattern, op_type) in sql_patterns {
-                for cap in pattern.captures_iter(text) {
-                    if let Some(table) = cap.get(1) {
-                        operations.push(DbOperation {
-                            operation_type: op_type.clone(),
-                            table: table.as_str().to_string(),
-                            line: method_node.start_position().row + 1,
-                        });
-                    }
-                }
-            }
+        // 提取导入语句，建立简单类名到完整类名的映射
+        let import_map = self.build_import_map(source, tree);
+        
+        // 提取类中的字段声明和方法内的本地变量，建立变量名到类型的映射
+        let mut field_types = self.extract_field_types(source, method_node, tree, package_name);
+        
+        // this/super 是关键字，不会与变量名冲突，直接作为接收者类型登记
+        field_types.insert("this".to_string(), class_name.to_string());
+        if let Some(superclass) = superclass {
+            field_types.insert("super".to_string(), superclass.to_string());
         }
         
-        operations
+        self.walk_node_for_calls(source, *method_node, &mut calls, &field_types, &import_map, package_name);
+        calls.retain(|call| !self.call_filter.is_ignored(&call.target));
+        
+        // 已按字面量方法名推断出反射目标时，`Method.invoke` 调用的就是这些目标，不再视为未知
+        if calls.iter().any(|call| call.low_confidence && call.target != UNKNOWN_CALL_TARGET) {
+            calls.retain(|call| !(call.low_confidence && call.target == UNKNOWN_CALL_TARGET));
+        }
+        calls
     }
     
-    /// 提取 Redis 操作
-    fn extract_redis_operations(&self, source: &str, method_node: &tree_sitter::Node) -> Vec<RedisOperation> {
-        let mut operations = Vec::new();
+    /// 构建导入映射：简单类名 -> 完整类名
+    fn build_import_map(&self, source: &str, tree: &tree_sitter::Tree) -> std::collections::HashMap<String, String> {
+        let mut import_map = std::collections::HashMap::new();
+        let root_node = tree.root_node();
         
-        if let Some(text) = source.get(method_node.byte_range()) {
-            // 查找 RedisTemplate 操作
-            let get_pattern = Regex::new(r#"\.opsForValue\(\)\.get\s*\(\s*"([^"]+)""#).unwrap();
-            let set_pattern = Regex::new(r#"\.opsForValue\(\)\.set\s*\(\s*"([^"]+)""#).unwrap();
-            let delete_pattern = Regex::new(r#"\.delete\s*\(\s*"([^"]+)""#).unwrap();
-            
-            for cap in get_pattern.captures_iter(text) {
-                if let Some(key) = cap.get(1) {
-                    operations.push(RedisOperation {
-                        operation_type: RedisOpType::Get,
-                        key_pattern: key.as_str().to_string(),
-                        line: method_node.start_position().row + 1,
-                    });
+        self.walk_node_for_import_map(source, root_node, &mut import_map);
+        
+        import_map
+    }
+    
+    /// 遍历节点构建导入映射
+    /// 
+    /// 只记录单类型导入（`import a.b.User;`）。按 Java 的解析优先级，单类型导入
+    /// 优先于同包类，而按需导入（`import a.b.*;`）低于同包类，且无法确定简单名
+    /// 对应哪个类，因此不加入映射；静态导入引入的是成员而非类型，同样跳过。
+    /// 这样当多个包中存在同名类时，调用只会绑定到本文件显式导入的那个
+    fn walk_node_for_import_map(
+        &self,
+        source: &str,
+        node: tree_sitter::Node,
+        import_map: &mut std::collections::HashMap<String, String>,
+    ) {
+        walk_tree(node, |node| {
+            if node.kind() == "import_declaration" {
+                let mut cursor = node.walk();
+                let children: Vec<_> = node.children(&mut cursor).collect();
+                
+                let is_single_type_import = !children.iter()
+                    .any(|child| matches!(child.kind(), "asterisk" | "static"));
+                
+                if is_single_type_import {
+                    for child in children.iter().filter(|child| child.kind() == "scoped_identifier") {
+                        if let Some(full_name) = source.get(child.byte_range()) {
+                            // 从完整类名中提取简单类名
+                            if let Some(simple_name) = full_name.split('.').next_back() {
+                                import_map.insert(simple_name.to_string(), full_name.to_string());
+                            }
+                        }
+                    }
                 }
+                return false;
             }
-            
-            for cap in set_pattern.captures_iter(text) {
-                if let Some(key) = cap.get(1) {
-                    operations.push(RedisOperation {
-                        operation_type: RedisOpType::Set,
-                        key_pattern: key.as_str().to_string(),
-                        line: method_node.start_position().row + 1,
-                    });
+            true
+        });
+    }
+    
+    /// 提取类中的字段类型映射（包括类字段和方法内的本地变量）
+    fn extract_field_types(&self, source: &str, method_node: &tree_sitter::Node, tree: &tree_sitter::Tree, package_name: &Option<String>) -> std::collections::HashMap<String, String> {
+        let mut field_types = std::collections::HashMap::new();
+        
+        // 1. 向上查找到类节点，提取类字段（record 的组件同样作为字段）
+        let mut current = method_node.parent();
+        while let Some(node) = current {
+            if let Some(parameters) = node.child_by_field_name("parameters").filter(|_| node.kind() == "record_declaration") {
+                let mut cursor = parameters.walk();
+                for param in parameters.children(&mut cursor).filter(|param| param.kind() == "formal_parameter") {
+                    let type_name = param.child_by_field_name("type").and_then(|t| source.get(t.byte_range()));
+                    let name = param.child_by_field_name("name").and_then(|n| source.get(n.byte_range()));
+                    if let (Some(type_name), Some(name)) = (type_name, name) {
+                        field_types.insert(name.to_string(), type_name.to_string());
+                    }
                 }
             }
-            
-            for cap in delete_pattern.captures_iter(text) {
-                if let Some(key) = cap.get(1) {
-                    operations.push(RedisOperation {
-                        operation_type: RedisOpType::Delete,
-                        key_pattern: key.as_str().to_string(),
-                        line: method_node.start_position().row + 1,
-                    });
+            if matches!(node.kind(), "class_declaration" | "record_declaration" | "interface_declaration") {
+                // 在类体中查找字段声明，接口中为常量声明（供 default/static 方法使用）
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    if matches!(child.kind(), "class_body" | "interface_body") {
+                        let mut body_cursor = child.walk();
+                        for body_child in child.children(&mut body_cursor) {
+                            if matches!(body_child.kind(), "field_declaration" | "constant_declaration") {
+                                self.extract_field_type_from_declaration(source, body_child, &mut field_types);
+                            }
+                        }
+                    }
                 }
+                break;
             }
+            current = node.parent();
         }
         
-        operations
+        // 2. 提取方法内的本地变量
+        self.extract_local_variable_types(source, method_node, tree, package_name, &mut field_types);
+        
+        field_types
+    }
+    
+    /// 提取方法内的本地变量类型，并解析为完整类名
+    fn extract_local_variable_types(
+        &self,
+        source: &str,
+        method_node: &tree_sitter::Node,
+        tree: &tree_sitter::Tree,
+        package_name: &Option<String>,
+        field_types: &mut std::collections::HashMap<String, String>,
+    ) {
+        // 先提取本地变量的简单类型
+        self.walk_node_for_local_vars(source, *method_node, field_types);
+        
+        // 获取导入映射，用于解析完整类名
+        let import_map = self.build_import_map(source, tree);
+        
+        // 将简单类名解析为完整类名
+        let mut resolved_types = std::collections::HashMap::new();
+        for (var_name, simple_type) in field_types.iter() {
+            let full_type = self.resolve_full_class_name(simple_type, &import_map, package_name);
+            resolved_types.insert(var_name.clone(), full_type);
+        }
+        
+        // 更新 field_types
+        *field_types = resolved_types;
+    }
+    
+    /// 遍历节点查找本地变量声明
+    fn walk_node_for_local_vars(
+        &self,
+        source: &str,
+        node: tree_sitter::Node,
+        field_types: &mut std::collections::HashMap<String, String>,
+    ) {
+        walk_tree(node, |node| {
+            if node.kind() == "local_variable_declaration" {
+                // 提取本地变量的类型和名称
+                self.extract_field_type_from_declaration(source, node, field_types);
+            }
+            true
+        });
+    }
+    
+    /// 从字段声明中提取字段名和类型
+    fn extract_field_type_from_declaration(
+        &self,
+        source: &str,
+        field_node: tree_sitter::Node,
+        field_types: &mut std::collections::HashMap<String, String>,
+    ) {
+        let mut field_type = None;
+        let mut field_name = None;
+        
+        let mut cursor = field_node.walk();
+        for child in field_node.children(&mut cursor) {
+            match child.kind() {
+                "type_identifier" | "generic_type" | "scoped_type_identifier" => {
+                    if let Some(text) = source.get(child.byte_range()) {
+                        field_type = Some(text.to_string());
+                    }
+                }
+                "variable_declarator" => {
+                    // 在 variable_declarator 中查找 identifier
+                    let mut var_cursor = child.walk();
+                    for var_child in child.children(&mut var_cursor) {
+                        if var_child.kind() == "identifier"
+                            && let Some(text) = source.get(var_child.byte_range())
+                        {
+                            field_name = Some(text.to_string());
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        
+        // `var` 声明的本地变量类型由初始化表达式推断，这里无法确定
+        if let (Some(name), Some(type_name)) = (field_name, field_type) && type_name != "var" {
+            field_types.insert(name, type_name);
+        }
+    }
+    
+    /// 遍历节点查找方法调用
+    /// 
+    /// 使用显式栈迭代遍历，避免深层嵌套的表达式导致栈溢出
+    fn walk_node_for_calls(
+        &self,
+        source: &str,
+        node: tree_sitter::Node,
+        calls: &mut Vec<MethodCall>,
+        field_types: &std::collections::HashMap<String, String>,
+        import_map: &std::collections::HashMap<String, String>,
+        package_name: &Option<String>,
+    ) {
+        // 变量类型作用域：0 为方法作用域，lambda 会引入新的作用域
+        let mut scopes = vec![std::borrow::Cow::Borrowed(field_types)];
+        // 特性开关分支：记录各 `if` 分支的开关名，节点携带所在最内层分支的下标
+        let mut flags: Vec<String> = Vec::new();
+        let mut stack = vec![(node, 0usize, None::<usize>)];
+        
+        while let Some((node, scope, flag)) = stack.pop() {
+            if node.kind() == "method_invocation"
+                && !self.record_method_invocation(source, node, calls, &scopes[scope], import_map, package_name, flag.map(|i| flags[i].as_str()))
+            {
+                continue;
+            }
+            if node.kind() == "object_creation_expression" {
+                self.record_object_creation(source, node, calls, import_map, package_name, flag.map(|i| flags[i].as_str()));
+            }
+            if node.kind() == "method_reference" {
+                self.record_method_reference(source, node, calls, &scopes[scope], import_map, package_name, flag.map(|i| flags[i].as_str()));
+            }
+            
+            // 条件为特性开关检查的 `if` 语句，其分支中的调用受该开关控制
+            let condition = node.child_by_field_name("condition").filter(|_| node.kind() == "if_statement");
+            let branch_flag = match condition.and_then(|c| source.get(c.byte_range())).and_then(feature_flag_check) {
+                Some(name) => {
+                    flags.push(name);
+                    Some(flags.len() - 1)
+                }
+                None => flag,
+            };
+            
+            // lambda 体内的调用仍记录在外层方法下，但 lambda 参数会遮蔽同名的字段/变量
+            let child_scope = if node.kind() == "lambda_expression" {
+                let scoped_types = self.lambda_scope_types(source, node, &scopes[scope]);
+                scopes.push(std::borrow::Cow::Owned(scoped_types));
+                scopes.len() - 1
+            } else {
+                scope
+            };
+            
+            // 逆序入栈，保证按源码顺序访问子节点
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev().map(|child| {
+                let child_flag = if condition.is_some_and(|c| c.id() == child.id()) { flag } else { branch_flag };
+                (child, child_scope, child_flag)
+            }));
+        }
+    }
+    
+    /// 记录一次方法调用
+    /// 
+    /// `condition` 为调用所在特性开关分支的开关名
+    /// 
+    /// # Returns
+    /// * `true` - 继续遍历该调用的子节点
+    /// * `false` - 不再进入子节点
+    #[allow(clippy::too_many_arguments)]
+    fn record_method_invocation(
+        &self,
+        source: &str,
+        node: tree_sitter::Node,
+        calls: &mut Vec<MethodCall>,
+        field_types: &std::collections::HashMap<String, String>,
+        import_map: &std::collections::HashMap<String, String>,
+        package_name: &Option<String>,
+        condition: Option<&str>,
+    ) -> bool {
+        // 查找方法调用的对象和方法名
+        let mut cursor = node.walk();
+        let mut identifiers = Vec::new();
+        let mut scoped_identifiers = Vec::new();
+        
+        for child in node.children(&mut cursor) {
+            if matches!(child.kind(), "identifier" | "this" | "super") {
+                // this/super 作为对象名，通过变量类型映射解析到当前类/父类
+                if let Some(text) = source.get(child.byte_range()) {
+                    identifiers.push(text.to_string());
+                }
+            } else if child.kind() == "scoped_identifier" {
+                // 处理静态方法调用，如 ClassName.staticMethod()
+                if let Some(text) = source.get(child.byte_range()) {
+                    scoped_identifiers.push(text.to_string());
+                }
+            } else if child.kind() == "field_access" {
+                // 处理 this.field.method() 形式，按字段名解析
+                if let Some(field) = source.get(child.byte_range())
+                    .and_then(|text| text.strip_prefix("this."))
+                    .filter(|field| !field.contains('.'))
+                {
+                    identifiers.push(field.trim().to_string());
+                }
+            }
+        }
+        
+        let line = node.start_position().row + 1;
+        let condition = condition.map(|flag| flag.to_string());
+        
+        // 处理静态方法调用：ClassName.staticMethod() 或 package.ClassName.staticMethod()
+        if !scoped_identifiers.is_empty() && !identifiers.is_empty() {
+            // scoped_identifier 包含类名（可能带包名），identifier 是方法名
+            let class_name = &scoped_identifiers[0];
+            let method_name = &identifiers[identifiers.len() - 1];
+            
+            // 尝试将简单类名转换为完整类名
+            let full_class_name = import_map.get(class_name)
+                .unwrap_or(class_name);
+            
+            let target = format!("{}::{}", full_class_name, method_name);
+            calls.push(MethodCall {
+                target,
+                line,
+                condition,
+                low_confidence: false,
+            });
+            return false;
+        }
+        
+        // 对于 obj.method() 形式，有两个 identifier：对象名和方法名
+        // 对于 method() 形式，只有一个 identifier：方法名
+        let (object_name, method_name) = if identifiers.len() >= 2 {
+            (Some(identifiers[0].clone()), identifiers[identifiers.len() - 1].clone())
+        } else if identifiers.len() == 1 {
+            (None, identifiers[0].clone())
+        } else {
+            return false;
+        };
+        
+        // 如果有对象名，尝试解析为完整的类名::方法名
+        let target = if let Some(obj) = object_name {
+            if let Some(class_type) = field_types.get(&obj) {
+                // 尝试将简单类名转换为完整类名
+                let full_class_name = import_map.get(class_type)
+                    .unwrap_or(class_type);
+                format!("{}::{}", full_class_name, method_name)
+            } else {
+                // 可能是静态方法调用，尝试从 import_map 中查找
+                if let Some(full_class_name) = import_map.get(&obj) {
+                    format!("{}::{}", full_class_name, method_name)
+                } else {
+                    method_name.clone()
+                }
+            }
+        } else {
+            method_name.clone()
+        };
+        
+        let reflective_target = self.reflective_call_target(source, node, &method_name, &target, field_types, import_map, package_name);
+        
+        calls.push(MethodCall {
+            target,
+            line,
+            condition: condition.clone(),
+            low_confidence: false,
+        });
+        
+        if let Some(target) = reflective_target {
+            calls.push(MethodCall {
+                target,
+                line,
+                condition,
+                low_confidence: true,
+            });
+        }
+        
+        true
+    }
+    
+    /// 识别反射调用，推断其真实调用目标
+    /// 
+    /// - `getMethod("name")` / `getDeclaredMethod("name")`：方法名为字符串字面量时，
+    ///   目标为 `类名::name`，类名取自接收者（见 `reflective_receiver_class`），
+    ///   无法静态确定时为 `<unknown>::name`
+    /// - 方法名不是字面量的 `getMethod(...)`，以及 `Method.invoke(...)`：目标为 `<unknown>`
+    /// 
+    /// # Arguments
+    /// * `node` - method_invocation 节点
+    /// * `method_name` - 被调用的方法名
+    /// * `target` - 已解析的调用目标，用于判断接收者是否为 `Method`
+    /// * `field_types` - 变量名到类型的映射
+    /// * `import_map` - 简单类名到完整类名的映射
+    /// * `package_name` - 当前文件的包名
+    /// 
+    /// # Returns
+    /// * 非反射调用时返回 `None`
+    #[allow(clippy::too_many_arguments)]
+    fn reflective_call_target(
+        &self,
+        source: &str,
+        node: tree_sitter::Node,
+        method_name: &str,
+        target: &str,
+        field_types: &std::collections::HashMap<String, String>,
+        import_map: &std::collections::HashMap<String, String>,
+        package_name: &Option<String>,
+    ) -> Option<String> {
+        match method_name {
+            "getMethod" | "getDeclaredMethod" => {
+                let name = node.child_by_field_name("arguments")
+                    .and_then(|args| args.named_child(0))
+                    .filter(|arg| arg.kind() == "string_literal")
+                    .and_then(|arg| source.get(arg.byte_range()))
+                    .map(|text| text.trim_matches('"'))
+                    .filter(|name| !name.is_empty());
+                let class_name = node.child_by_field_name("object")
+                    .and_then(|object| self.reflective_receiver_class(source, object, field_types, import_map, package_name));
+                Some(match name {
+                    Some(name) => format!("{}::{}", class_name.as_deref().unwrap_or(UNKNOWN_CALL_TARGET), name),
+                    None => UNKNOWN_CALL_TARGET.to_string(),
+                })
+            }
+            "invoke" => {
+                let receiver_type = target.rsplit_once("::")?.0;
+                (receiver_type.rsplit('.').next() == Some("Method"))
+                    .then(|| UNKNOWN_CALL_TARGET.to_string())
+            }
+            _ => None,
+        }
+    }
+    
+    /// 推断 `getMethod` 接收者所代表的类
+    /// 
+    /// 支持 `X.class`、`Class.forName("完整类名")`、`obj.getClass()` 和 `getClass()`，
+    /// 类字面量按导入和当前包解析，变量类型按导入映射转换为完整类名
+    /// 
+    /// # Returns
+    /// * 无法静态确定时返回 `None`
+    fn reflective_receiver_class(
+        &self,
+        source: &str,
+        object: tree_sitter::Node,
+        field_types: &std::collections::HashMap<String, String>,
+        import_map: &std::collections::HashMap<String, String>,
+        package_name: &Option<String>,
+    ) -> Option<String> {
+        let qualify = |class_name: &str| import_map.get(class_name).cloned().unwrap_or_else(|| class_name.to_string());
+        match object.kind() {
+            "class_literal" => {
+                let class_name = source.get(object.named_child(0)?.byte_range())?;
+                Some(self.resolve_full_class_name(class_name, import_map, package_name))
+            }
+            "method_invocation" => {
+                let name = source.get(object.child_by_field_name("name")?.byte_range())?;
+                match name {
+                    "forName" => object.child_by_field_name("arguments")
+                        .and_then(|args| args.named_child(0))
+                        .filter(|arg| arg.kind() == "string_literal")
+                        .and_then(|arg| source.get(arg.byte_range()))
+                        .map(|text| text.trim_matches('"').to_string())
+                        .filter(|class_name| !class_name.is_empty()),
+                    "getClass" => {
+                        let variable = match object.child_by_field_name("object") {
+                            Some(receiver) => source.get(receiver.byte_range())?,
+                            None => "this",
+                        };
+                        field_types.get(variable).map(|class_name| qualify(class_name))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+    
+    /// 记录一次构造器调用 `new X(...)`
+    /// 
+    /// 调用目标为 `完整类名::<init>`，泛型参数（如 `new Box<>()`）不参与解析。
+    /// 构造参数中的调用由外层遍历继续记录
+    fn record_object_creation(
+        &self,
+        source: &str,
+        node: tree_sitter::Node,
+        calls: &mut Vec<MethodCall>,
+        import_map: &std::collections::HashMap<String, String>,
+        package_name: &Option<String>,
+        condition: Option<&str>,
+    ) {
+        let Some(type_name) = node.child_by_field_name("type").and_then(|t| source.get(t.byte_range())) else {
+            return;
+        };
+        let type_name = type_name.split('<').next().unwrap_or(type_name).trim();
+        let full_class_name = self.resolve_full_class_name(type_name, import_map, package_name);
+        
+        calls.push(MethodCall {
+            target: format!("{}::<init>", full_class_name),
+            line: node.start_position().row + 1,
+            condition: condition.map(|flag| flag.to_string()),
+            low_confidence: false,
+        });
+    }
+    
+    /// 记录一次方法引用 `X::method`
+    /// 
+    /// - `this::m` / `super::m` 以及变量（如 `service::m`）按变量类型映射解析
+    /// - 其余接收者视为类型（如 `User::getName`），按导入和当前包解析
+    /// - `X::new` 为构造器引用，目标为 `完整类名::<init>`
+    /// 
+    /// 接收者为其他表达式或类型未知的变量（小写开头的标识符，如方法参数 `list::add`）时
+    /// 类型无法确定，目标仅为方法名
+    #[allow(clippy::too_many_arguments)]
+    fn record_method_reference(
+        &self,
+        source: &str,
+        node: tree_sitter::Node,
+        calls: &mut Vec<MethodCall>,
+        field_types: &std::collections::HashMap<String, String>,
+        import_map: &std::collections::HashMap<String, String>,
+        package_name: &Option<String>,
+        condition: Option<&str>,
+    ) {
+        let (Some(receiver), Some(member)) = (node.named_child(0), node.child(node.child_count().saturating_sub(1))) else {
+            return;
+        };
+        let (Some(receiver_text), Some(member_text)) = (source.get(receiver.byte_range()), source.get(member.byte_range())) else {
+            return;
+        };
+        let method_name = if member.kind() == "new" { "<init>" } else { member_text };
+        
+        let class_name = match receiver.kind() {
+            "this" | "super" | "identifier" if field_types.contains_key(receiver_text) => {
+                let class_type = &field_types[receiver_text];
+                Some(import_map.get(class_type).unwrap_or(class_type).clone())
+            }
+            "identifier" if !receiver_text.starts_with(char::is_uppercase) => None,
+            "identifier" | "type_identifier" | "scoped_identifier" | "scoped_type_identifier" | "generic_type" => {
+                let type_name = receiver_text.split('<').next().unwrap_or(receiver_text).trim();
+                Some(self.resolve_full_class_name(type_name, import_map, package_name))
+            }
+            _ => None,
+        };
+        
+        calls.push(MethodCall {
+            target: match class_name {
+                Some(class_name) => format!("{}::{}", class_name, method_name),
+                None => method_name.to_string(),
+            },
+            line: node.start_position().row + 1,
+            condition: condition.map(|flag| flag.to_string()),
+            low_confidence: false,
+        });
+    }
+    
+    /// 计算 lambda 体内可见的变量类型映射
+    /// 
+    /// - 显式声明类型的参数（如 `(Order o) -> ...`）加入映射
+    /// - 未声明类型的参数（如 `x -> ...`）类型未知，从映射中移除以免误解析为同名字段
+    /// 
+    /// # Arguments
+    /// * `source` - 源代码
+    /// * `lambda_node` - lambda_expression 节点
+    /// * `field_types` - 外层作用域的变量类型映射
+    /// 
+    /// # Returns
+    /// * lambda 体内的变量类型映射
+    fn lambda_scope_types(
+        &self,
+        source: &str,
+        lambda_node: tree_sitter::Node,
+        field_types: &std::collections::HashMap<String, String>,
+    ) -> std::collections::HashMap<String, String> {
+        let mut scoped_types = field_types.clone();
+        
+        let Some(params) = lambda_node.child_by_field_name("parameters") else {
+            return scoped_types;
+        };
+        
+        match params.kind() {
+            "identifier" => {
+                if let Some(name) = source.get(params.byte_range()) {
+                    scoped_types.remove(name);
+                }
+            }
+            _ => {
+                let mut cursor = params.walk();
+                for param in params.children(&mut cursor) {
+                    match param.kind() {
+                        "identifier" => {
+                            if let Some(name) = source.get(param.byte_range()) {
+                                scoped_types.remove(name);
+                            }
+                        }
+                        "formal_parameter" => {
+                            let name = param.child_by_field_name("name")
+                                .and_then(|n| source.get(n.byte_range()));
+                            let type_name = param.child_by_field_name("type")
+                                .and_then(|t| source.get(t.byte_range()));
+                            if let Some(name) = name {
+                                match type_name {
+                                    Some(type_name) => {
+                                        scoped_types.insert(name.to_string(), type_name.to_string());
+                                    }
+                                    None => {
+                                        scoped_types.remove(name);
+                                    }
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        
+        scoped_types
+    }
+    
+    /// 提取 HTTP 注解（Spring Framework）
+    fn extract_http_annotations(&self, source: &str, method_node: &tree_sitter::Node, class_request_mapping: &Option<String>, app_config: &ApplicationConfig) -> Option<HttpAnnotation> {
+        // 查找方法节点的 modifiers 子节点
+        let mut cursor = method_node.walk();
+        for child in method_node.children(&mut cursor) {
+            if child.kind() == "modifiers" {
+                // 在 modifiers 中查找注解
+                let mut mod_cursor = child.walk();
+                for mod_child in child.children(&mut mod_cursor) {
+                    if (mod_child.kind() == "marker_annotation" || mod_child.kind() == "annotation")
+                        && let Some(mut http_ann) = self.parse_http_annotation(source, mod_child)
+                    {
+                        http_ann.path = full_http_path(app_config, class_request_mapping.as_deref(), &http_ann.path);
+                        return Some(http_ann);
+                    }
+                }
+            }
+        }
+        
+        None
+    }
+    
+    /// 提取 HTTP 注解（原始版本，不包含应用配置）
+    /// 用于 FeignClient 等场景
+    fn extract_http_annotations_raw(&self, source: &str, method_node: &tree_sitter::Node) -> Option<HttpAnnotation> {
+        // 查找方法节点的 modifiers 子节点
+        let mut cursor = method_node.walk();
+        for child in method_node.children(&mut cursor) {
+            if child.kind() == "modifiers" {
+                // 在 modifiers 中查找注解
+                let mut mod_cursor = child.walk();
+                for mod_child in child.children(&mut mod_cursor) {
+                    if (mod_child.kind() == "marker_annotation" || mod_child.kind() == "annotation")
+                        && let Some(http_ann) = self.parse_http_annotation(source, mod_child)
+                    {
+                        return Some(http_ann);
+                    }
+                }
+            }
+        }
+        
+        None
+    }
+    
+    /// 解析 HTTP 注解
+    fn parse_http_annotation(&self, source: &str, annotation_node: tree_sitter::Node) -> Option<HttpAnnotation> {
+        // 获取注解名称
+        let (name, annotation_args) = self.parse_annotation_name_and_args(source, annotation_node)?;
+        
+        // 检查是否是 Spring HTTP 注解（按简单名匹配，支持全限定名）
+        let (method, path) = match annotation_simple_name(&name) {
+            "GetMapping" => (HttpMethod::GET, self.extract_path_from_args(&annotation_args)),
+            "PostMapping" => (HttpMethod::POST, self.extract_path_from_args(&annotation_args)),
+            "PutMapping" => (HttpMethod::PUT, self.extract_path_from_args(&annotation_args)),
+            "DeleteMapping" => (HttpMethod::DELETE, self.extract_path_from_args(&annotation_args)),
+            "PatchMapping" => (HttpMethod::PATCH, self.extract_path_from_args(&annotation_args)),
+            "RequestMapping" => {
+                // 未指定 method 的 RequestMapping 接受任意 HTTP 方法
+                let method = self.extract_request_method_from_args(&annotation_args).unwrap_or(HttpMethod::Any);
+                let path = self.extract_path_from_args(&annotation_args);
+                (method, path)
+            }
+            _ => return None,
+        };
+        
+        // 未指定路径的映射注解（如 `@GetMapping`）映射到类级别路径本身
+        let path_str = path.unwrap_or_default();
+        let path_params = self.extract_path_params(&path_str);
+        
+        Some(HttpAnnotation {
+            method,
+            path: path_str,
+            path_params,
+            is_feign_client: false,  // 普通 HTTP 接口声明
+        })
+    }
+    
+    /// 从注解参数中提取路径
+    fn extract_path_from_args(&self, args: &Option<String>) -> Option<String> {
+        if let Some(args_text) = args {
+            // 查找字符串字面量
+            let re = Regex::new(r#""([^"]+)""#).unwrap();
+            if let Some(cap) = re.captures(args_text) {
+                return cap.get(1).map(|m| m.as_str().to_string());
+            }
+        }
+        None
+    }
+    
+    /// 从 RequestMapping 参数中提取 HTTP 方法
+    fn extract_request_method_from_args(&self, args: &Option<String>) -> Option<HttpMethod> {
+        if let Some(args_text) = args {
+            if args_text.contains("RequestMethod.GET") {
+                return Some(HttpMethod::GET);
+            } else if args_text.contains("RequestMethod.POST") {
+                return Some(HttpMethod::POST);
+            } else if args_text.contains("RequestMethod.PUT") {
+                return Some(HttpMethod::PUT);
+            } else if args_text.contains("RequestMethod.DELETE") {
+                return Some(HttpMethod::DELETE);
+            } else if args_text.contains("RequestMethod.PATCH") {
+                return Some(HttpMethod::PATCH);
+            }
+        }
+        None
+    }
+    
+    /// 提取路径参数
+    fn extract_path_params(&self, path: &str) -> Vec<String> {
+        let re = Regex::new(r"\{([^}]+)\}").unwrap();
+        re.captures_iter(path)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+            .collect()
+    }
+    
+    /// 提取 Spring GraphQL 解析器注解
+    /// 
+    /// - `@QueryMapping` / `@MutationMapping` / `@SubscriptionMapping`：类型分别为 Query / Mutation / Subscription
+    /// - `@SchemaMapping(typeName = "Book", field = "author")`：未指定 typeName 时使用类级别 `@SchemaMapping` 的 typeName
+    /// 
+    /// 字段名取注解的 `name`/`field`/`value` 属性，未指定时为方法名
+    fn extract_graphql_resolver(&self, source: &str, method_node: &tree_sitter::Node, method_name: &str) -> Option<GraphQLResolver> {
+        let mut cursor = method_node.walk();
+        for child in method_node.children(&mut cursor) {
+            if child.kind() != "modifiers" {
+                continue;
+            }
+            
+            let mut mod_cursor = child.walk();
+            for mod_child in child.children(&mut mod_cursor) {
+                if mod_child.kind() != "marker_annotation" && mod_child.kind() != "annotation" {
+                    continue;
+                }
+                
+                let Some((name, args)) = self.parse_annotation_name_and_args(source, mod_child) else {
+                    continue;
+                };
+                
+                let type_name = match annotation_simple_name(&name) {
+                    "QueryMapping" => "Query".to_string(),
+                    "MutationMapping" => "Mutation".to_string(),
+                    "SubscriptionMapping" => "Subscription".to_string(),
+                    "SchemaMapping" => {
+                        match args.as_deref().and_then(|a| self.extract_string_attribute(a, "typeName"))
+                            .or_else(|| self.extract_class_schema_type(source, method_node))
+                        {
+                            Some(type_name) => type_name,
+                            None => continue,
+                        }
+                    }
+                    _ => continue,
+                };
+                
+                let field = args.as_deref()
+                    .and_then(|a| {
+                        if a.contains('=') {
+                            self.extract_string_attribute(a, "field")
+                                .or_else(|| self.extract_string_attribute(a, "name"))
+                                .or_else(|| self.extract_string_attribute(a, "value"))
+                        } else {
+                            // 只有一个位置参数，如 @QueryMapping("books")
+                            self.extract_path_from_args(&Some(a.to_string()))
+                        }
+                    })
+                    .unwrap_or_else(|| method_name.to_string());
+                
+                return Some(GraphQLResolver { type_name, field });
+            }
+        }
+        
+        None
+    }
+    
+    /// 从注解参数中提取 `attr = "value"` 形式的字符串属性（按完整属性名匹配）
+    fn extract_string_attribute(&self, args: &str, attr_name: &str) -> Option<String> {
+        let pattern = format!(r#"\b{}\s*=\s*"([^"]*)""#, attr_name);
+        let re = Regex::new(&pattern).ok()?;
+        
+        re.captures(args)
+            .and_then(|cap| cap.get(1))
+            .map(|m| m.as_str().to_string())
+    }
+    
+    /// 提取 OpenAPI/Swagger 接口文档注解
+    /// 
+    /// - OpenAPI 3：`@Operation(summary = "...", description = "...")`
+    /// - Swagger 2：`@ApiOperation(value = "...", notes = "...")` 或 `@ApiOperation("...")`
+    fn extract_api_operation(&self, source: &str, method_node: &tree_sitter::Node) -> Option<ApiOperation> {
+        let mut cursor = method_node.walk();
+        for child in method_node.children(&mut cursor) {
+            if child.kind() != "modifiers" {
+                continue;
+            }
+            
+            let mut mod_cursor = child.walk();
+            for mod_child in child.children(&mut mod_cursor) {
+                if mod_child.kind() != "annotation" {
+                    continue;
+                }
+                
+                let Some((name, Some(args))) = self.parse_annotation_name_and_args(source, mod_child) else {
+                    continue;
+                };
+                
+                let operation = match annotation_simple_name(&name) {
+                    "Operation" => ApiOperation {
+                        summary: self.extract_string_attribute(&args, "summary"),
+                        description: self.extract_string_attribute(&args, "description"),
+                    },
+                    "ApiOperation" if !args.contains('=') => ApiOperation {
+                        summary: self.extract_path_from_args(&Some(args)),
+                        description: None,
+                    },
+                    "ApiOperation" => ApiOperation {
+                        summary: self.extract_string_attribute(&args, "value"),
+                        description: self.extract_string_attribute(&args, "notes"),
+                    },
+                    _ => continue,
+                };
+                
+                if operation.summary.is_some() || operation.description.is_some() {
+                    return Some(operation);
+                }
+            }
+        }
+        
+        None
+    }
+    
+    /// 获取方法所在类上 `@SchemaMapping(typeName = ...)` 指定的类型名
+    fn extract_class_schema_type(&self, source: &str, method_node: &tree_sitter::Node) -> Option<String> {
+        let class_node = method_node.parent()?.parent()?;
+        
+        let mut cursor = class_node.walk();
+        for child in class_node.children(&mut cursor) {
+            if child.kind() != "modifiers" {
+                continue;
+            }
+            
+            let mut mod_cursor = child.walk();
+            for mod_child in child.children(&mut mod_cursor) {
+                if mod_child.kind() != "annotation" {
+                    continue;
+                }
+                if let Some((name, Some(args))) = self.parse_annotation_name_and_args(source, mod_child)
+                    && annotation_simple_name(&name) == "SchemaMapping"
+                {
+                    return self.extract_string_attribute(&args, "typeName");
+                }
+            }
+        }
+        
+        None
+    }
+    
+    /// 提取 Kafka 操作
+    /// 
+    /// topic 可以是字符串字面量，也可以是字符串常量引用（如 `Topics.ORDER_CREATED`），
+    /// 后者记录常量的全限定名，建立索引时再解析为常量值
+    fn extract_kafka_operations(&self, source: &str, method_node: &tree_sitter::Node, tree: &tree_sitter::Tree, package_name: &Option<String>) -> Vec<KafkaOperation> {
+        let mut operations = Vec::new();
+        let constant_operation = |operation_type: KafkaOpType, reference: &str, value_type: Option<String>| KafkaOperation {
+            operation_type,
+            topic: reference.to_string(),
+            line: method_node.start_position().row + 1,
+            topic_constant: Some(self.resolve_constant_reference(source, method_node, tree, package_name, reference)),
+            value_type,
+        };
+        
+        // 查找 @KafkaListener 注解 - 只在方法自己的 modifiers 中查找（支持全限定名）
+        let topic_pattern = Regex::new(r#"topics\s*=\s*"([^"]+)""#).unwrap();
+        let constant_pattern = Regex::new(r"topics\s*=\s*\{?\s*((?:[A-Za-z_]\w*\.)*[A-Z][A-Z0-9_]*)\b").unwrap();
+        let mut cursor = method_node.walk();
+        for child in method_node.children(&mut cursor) {
+            if child.kind() == "modifiers" {
+                let mut mod_cursor = child.walk();
+                for mod_child in child.children(&mut mod_cursor) {
+                    if mod_child.kind() != "annotation" {
+                        continue;
+                    }
+                    let Some((name, Some(args))) = self.parse_annotation_name_and_args(source, mod_child) else {
+                        continue;
+                    };
+                    if annotation_simple_name(&name) != "KafkaListener" {
+                        continue;
+                    }
+                    
+                    let value_type = self.kafka_listener_value_type(source, method_node);
+                    if let Some(topic) = topic_pattern.captures(&args).and_then(|cap| cap.get(1)) {
+                        operations.push(KafkaOperation {
+                            operation_type: KafkaOpType::Consume,
+                            topic: topic.as_str().to_string(),
+                            line: method_node.start_position().row + 1,
+                            topic_constant: None,
+                            value_type,
+                        });
+                    } else if let Some(reference) = constant_pattern.captures(&args).and_then(|cap| cap.get(1)) {
+                        operations.push(constant_operation(KafkaOpType::Consume, reference.as_str(), value_type));
+                    }
+                }
+            }
+        }
+        
+        // 查找方法体中的 send 调用
+        if let Some(text) = source.get(method_node.byte_range()) {
+            let producer_pattern = Regex::new(r#"\.send\s*\(\s*"([^"]+)""#).unwrap();
+            for cap in producer_pattern.captures_iter(text) {
+                if let Some(topic) = cap.get(1) {
+                    operations.push(KafkaOperation {
+                        operation_type: KafkaOpType::Produce,
+                        topic: topic.as_str().to_string(),
+                        line: method_node.start_position().row + 1,
+                        topic_constant: None,
+                        value_type: kafka_payload_type(text, &text[topic.end() + 1..]),
+                    });
+                }
+            }
+            
+            // 常量引用：最后一段为全大写的标识符，避免误匹配局部变量
+            let constant_pattern = Regex::new(r"\.send\s*\(\s*((?:[A-Za-z_]\w*\.)*[A-Z][A-Z0-9_]*)\s*[,)]").unwrap();
+            for cap in constant_pattern.captures_iter(text) {
+                if let Some(reference) = cap.get(1) {
+                    let value_type = kafka_payload_type(text, &text[reference.end()..]);
+                    operations.push(constant_operation(KafkaOpType::Produce, reference.as_str(), value_type));
+                }
+            }
+        }
+        
+        operations
+    }
+    
+    /// 推断 `@KafkaListener` 方法接收的消息值类型
+    /// 
+    /// 优先取 `@Payload` 标注的参数，否则取第一个参数；
+    /// `ConsumerRecord<K, V>` 等泛型包装取最后一个类型参数
+    fn kafka_listener_value_type(&self, source: &str, method_node: &tree_sitter::Node) -> Option<String> {
+        let parameters = method_node.child_by_field_name("parameters")?;
+        let mut cursor = parameters.walk();
+        let params: Vec<_> = parameters.children(&mut cursor)
+            .filter(|child| child.kind() == "formal_parameter")
+            .collect();
+        let param = params.iter()
+            .find(|param| source.get(param.byte_range()).is_some_and(|text| text.contains("@Payload")))
+            .or_else(|| params.first())?;
+        let type_text = source.get(param.child_by_field_name("type")?.byte_range())?;
+        message_value_type(type_text)
+    }
+    
+    /// 将常量引用解析为常量的全限定名
+    /// 
+    /// `Topics.ORDER_CREATED` 按导入解析类名；不带类名的 `ORDER_CREATED` 视为当前类（含外层类）的常量
+    fn resolve_constant_reference(
+        &self,
+        source: &str,
+        node: &tree_sitter::Node,
+        tree: &tree_sitter::Tree,
+        package_name: &Option<String>,
+        reference: &str,
+    ) -> String {
+        match reference.rsplit_once('.') {
+            Some((class_part, field)) => {
+                let import_map = self.build_import_map(source, tree);
+                let full_class_name = self.resolve_full_class_name(class_part, &import_map, package_name);
+                format!("{}.{}", full_class_name, field)
+            }
+            None => match self.enclosing_class_name(source, node, package_name) {
+                Some(class_name) => format!("{}.{}", class_name, reference),
+                None => reference.to_string(),
+            },
+        }
+    }
+    
+    /// 获取节点所在类的全限定名，嵌套类包含外层类名（如 `com.example.Topics.Order`）
+    fn enclosing_class_name(&self, source: &str, node: &tree_sitter::Node, package_name: &Option<String>) -> Option<String> {
+        let mut names = Vec::new();
+        let mut current = Some(*node);
+        while let Some(n) = current {
+            if matches!(n.kind(), "class_declaration" | "interface_declaration" | "enum_declaration")
+                && let Some(name) = n.child_by_field_name("name").and_then(|name| source.get(name.byte_range()))
+            {
+                names.push(name);
+            }
+            current = n.parent();
+        }
+        
+        if names.is_empty() {
+            return None;
+        }
+        names.reverse();
+        
+        let class_name = names.join(".");
+        Some(match package_name {
+            Some(pkg) => format!("{}.{}", pkg, class_name),
+            None => class_name,
+        })
+    }
+    
+    /// 提取文件中的字符串常量
+    /// 
+    /// 包括类中的 `static final String` 字段和接口中的 `String` 常量（隐式 static final）
+    fn extract_string_constants(&self, source: &str, tree: &tree_sitter::Tree, package_name: &Option<String>) -> Vec<StringConstant> {
+        let mut constants = Vec::new();
+        
+        walk_tree(tree.root_node(), |node| {
+            let is_constant = match node.kind() {
+                "constant_declaration" => true,
+                "field_declaration" => {
+                    let mut cursor = node.walk();
+                    let modifiers = node.children(&mut cursor)
+                        .find(|child| child.kind() == "modifiers")
+                        .and_then(|modifiers| source.get(modifiers.byte_range()))
+                        .unwrap_or_default();
+                    let words: Vec<&str> = modifiers.split_whitespace().collect();
+                    words.contains(&"static") && words.contains(&"final")
+                }
+                _ => return true,
+            };
+            
+            let is_string = node.child_by_field_name("type")
+                .and_then(|t| source.get(t.byte_range()))
+                .is_some_and(|t| t == "String" || t == "java.lang.String");
+            if !is_constant || !is_string {
+                return false;
+            }
+            
+            let Some(class_name) = self.enclosing_class_name(source, &node, package_name) else {
+                return false;
+            };
+            
+            let mut cursor = node.walk();
+            for declarator in node.children_by_field_name("declarator", &mut cursor) {
+                let name = declarator.child_by_field_name("name").and_then(|n| source.get(n.byte_range()));
+                let value = declarator.child_by_field_name("value")
+                    .filter(|v| v.kind() == "string_literal")
+                    .and_then(|v| source.get(v.byte_range()))
+                    .and_then(|v| v.strip_prefix('"')?.strip_suffix('"'));
+                if let (Some(name), Some(value)) = (name, value) {
+                    constants.push(StringConstant {
+                        name: format!("{}.{}", class_name, name),
+                        value: value.to_string(),
+                    });
+                }
+            }
+            false
+        });
+        
+        constants
+    }
+    
+    /// 提取数据库操作
+    fn extract_db_operations(&self, source: &str, method_node: &tree_sitter::Node) -> Vec<DbOperation> {
+        let mut operations = Vec::new();
+        
+        if let Some(text) = source.get(method_node.byte_range()) {
+            // 查找 SQL 语句
+            let sql_patterns = vec![
+                (Regex::new(r"(?i)SELECT\s+.+?\s+FROM\s+(\w+)").unwrap(), DbOpType::Select),
+                (Regex::new(r"(?i)INSERT\s+INTO\s+(\w+)").unwrap(), DbOpType::Insert),
+                (Regex::new(r"(?i)UPDATE\s+(\w+)\s+SET").unwrap(), DbOpType::Update),
+                (Regex::new(r"(?i)DELETE\s+FROM\s+(\w+)").unwrap(), DbOpType::Delete),
+            ];
+            
+            for (pattern, op_type) in sql_patterns {
+                for cap in pattern.captures_iter(text) {
+                    if let Some(table) = cap.get(1) {
+                        operations.push(DbOperation {
+                            operation_type: op_type.clone(),
+                            table: table.as_str().to_string(),
+                            line: method_node.start_position().row + 1,
+                        });
+                    }
+                }
+            }
+        }
+        
+        operations
+    }
+    
+    /// 提取 Redis 操作
+    fn extract_redis_operations(&self, source: &str, method_node: &tree_sitter::Node) -> Vec<RedisOperation> {
+        let mut operations = Vec::new();
+        
+        if let Some(text) = source.get(method_node.byte_range()) {
+            // 查找 RedisTemplate 操作
+            let get_pattern = Regex::new(r#"\.opsForValue\(\)\.get\s*\(\s*"([^"]+)""#).unwrap();
+            let set_pattern = Regex::new(r#"\.opsForValue\(\)\.set\s*\(\s*"([^"]+)""#).unwrap();
+            let delete_pattern = Regex::new(r#"\.delete\s*\(\s*"([^"]+)""#).unwrap();
+            
+            for cap in get_pattern.captures_iter(text) {
+                if let Some(key) = cap.get(1) {
+                    operations.push(RedisOperation {
+                        operation_type: RedisOpType::Get,
+                        key_pattern: key.as_str().to_string(),
+                        line: method_node.start_position().row + 1,
+                    });
+                }
+            }
+            
+            for cap in set_pattern.captures_iter(text) {
+                if let Some(key) = cap.get(1) {
+                    operations.push(RedisOperation {
+                        operation_type: RedisOpType::Set,
+                        key_pattern: key.as_str().to_string(),
+                        line: method_node.start_position().row + 1,
+                    });
+                }
+            }
+            
+            for cap in delete_pattern.captures_iter(text) {
+                if let Some(key) = cap.get(1) {
+                    operations.push(RedisOperation {
+                        operation_type: RedisOpType::Delete,
+                        key_pattern: key.as_str().to_string(),
+                        line: method_node.start_position().row + 1,
+                    });
+                }
+            }
+        }
+        
+        operations
+    }
+    
+    /// 提取导入声明
+    fn extract_imports(&self, source: &str, tree: &tree_sitter::Tree) -> Vec<Import> {
+        let mut imports = Vec::new();
+        let root_node = tree.root_node();
+        
+        self.walk_node_for_imports(source, root_node, &mut imports);
+        
+        imports
+    }
+    
+    /// 遍历节点查找导入声明
+    fn walk_node_for_imports(&self, source: &str, node: tree_sitter::Node, imports: &mut Vec<Import>) {
+        walk_tree(node, |node| {
+            if node.kind() == "import_declaration" {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    if child.kind() == "scoped_identifier"
+                        && let Some(text) = source.get(child.byte_range())
+                    {
+                        imports.push(Import {
+                            module: text.to_string(),
+                            items: vec![],
+                        });
+                    }
+                }
+            }
+            true
+        });
+    }
+}
+
+/// 查找源文件所属模块的应用配置文件
+/// 
+/// 从源文件所在目录向上查找最近的包含 `src/main/resources` 的模块目录，
+/// 返回其中的 `application.yml` / `application.yaml` 路径和模块目录；
+/// 指定了 `workspace_root` 时查找不越过工作空间根目录
+fn find_module_application_config<'a>(file_path: &'a Path, workspace_root: Option<&Path>) -> Option<(PathBuf, &'a Path)> {
+    let module_root = file_path.ancestors()
+        .skip(1)
+        .take_while(|dir| workspace_root.is_none_or(|root| dir.starts_with(root)))
+        .find(|dir| dir.join("src").join("main").join("resources").is_dir())?;
+    let resources_dir = module_root.join("src").join("main").join("resources");
+    
+    ["application.yml", "application.yaml"].iter()
+        .map(|name| resources_dir.join(name))
+        .find(|path| path.is_file())
+        .map(|path| (path, module_root))
+}
+
+/// 获取注解的简单名称
+/// 
+/// `org.springframework.cloud.openfeign.FeignClient` 与 `FeignClient` 都返回 `FeignClient`，
+/// 注解匹配统一基于简单名称，避免受限定形式影响，也避免 `contains` 误匹配
+/// `FeignClientsConfiguration` 这类名称。
+fn annotation_simple_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// 判断方法体是否只有一条调用语句（`foo();` 或 `return foo();`）
+fn is_single_call_body(method_node: &tree_sitter::Node) -> bool {
+    let Some(body) = method_node.child_by_field_name("body").filter(|body| body.kind() == "block") else {
+        return false;
+    };
+    
+    let mut cursor = body.walk();
+    let statements: Vec<_> = body.named_children(&mut cursor)
+        .filter(|child| !child.kind().ends_with("comment"))
+        .collect();
+    let [statement] = statements.as_slice() else {
+        return false;
+    };
+    
+    matches!(statement.kind(), "return_statement" | "expression_statement")
+        && statement.named_child(0).is_some_and(|expr| expr.kind() == "method_invocation")
+}
+
+/// 识别特性开关检查，返回开关名
+/// 
+/// 支持 `isEnabled("x")`、`isFeatureEnabled("x")`、`isActive("x")` 和 `boolVariation("x", ...)`
+/// 这类常见特性开关 SDK 的调用形式
+fn feature_flag_check(condition: &str) -> Option<String> {
+    let flag_re = Regex::new(r#"\b(?:isEnabled|isFeatureEnabled|isActive|boolVariation)\s*\(\s*"([^"]+)""#).unwrap();
+    flag_re.captures(condition).map(|cap| cap[1].to_string())
+}
+
+impl LanguageParser for JavaParser {
+    fn language_name(&self) -> &str {
+        "java"
+    }
+    
+    fn file_extensions(&self) -> &[&str] {
+        &["java"]
+    }
+    
+    fn set_call_filter(&mut self, filter: CallFilter) {
+        self.call_filter = filter;
+    }
+    
+    fn parse_file(&self, content: &str, file_path: &Path) -> Result<ParsedFile, ParseError> {
+        let tree = self.parser.lock().unwrap().parse(content, None)
+            .ok_or_else(|| ParseError::InvalidFormat {
+                message: "Failed to parse Java file".to_string(),
+            })?;
+        let syntax_errors = collect_syntax_errors(&tree);
+        
+        // 包名对整个文件相同，只解析一次
+        let package_name = self.extract_package_name(content, &tree);
+        let classes = self.extract_classes(content, file_path, &tree, &package_name);
+        let imports = self.extract_imports(content, &tree);
+        let string_constants = self.extract_string_constants(content, &tree, &package_name);
+        let functional_routes = self.extract_functional_routes(content, file_path, &tree, &package_name);
+        
+        Ok(ParsedFile {
+            file_path: file_path.to_path_buf(),
+            language: "java".to_string(),
+            classes,
+            functions: vec![], // Java 使用类和方法，不使用顶层函数
+            imports,
+            string_constants,
+            functional_routes,
+            syntax_errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_simple_class() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            public class Example {
+                public void hello() {
+                    System.out.println("Hello");
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("Example.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].name, "Example");
+        assert_eq!(result.classes[0].methods.len(), 1);
+        assert_eq!(result.classes[0].methods[0].name, "hello");
+    }
+    
+    #[test]
+    fn test_parse_interface() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example;
+            
+            public interface ShopCopyService {
+                Response query(GetShopCopyCmd cmd);
+                Response clone(ShopCloneCmd cmd);
+                Response restore(ShopRestoreCmd cmd);
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("ShopCopyService.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].name, "com.example.ShopCopyService");
+        assert_eq!(result.classes[0].methods.len(), 3);
+        assert_eq!(result.classes[0].methods[0].name, "query");
+        assert_eq!(result.classes[0].methods[1].name, "clone");
+        assert_eq!(result.classes[0].methods[2].name, "restore");
+    }
+    
+    #[test]
+    fn test_parse_interface_with_implementation() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example;
+            
+            public interface UserService {
+                void saveUser(String name);
+            }
+            
+            public class UserServiceImpl implements UserService {
+                @Override
+                public void saveUser(String name) {
+                    // implementation
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("UserService.java")).unwrap();
+        assert_eq!(result.classes.len(), 2);
+        
+        // Check interface
+        assert_eq!(result.classes[0].name, "com.example.UserService");
+        assert_eq!(result.classes[0].methods.len(), 1);
+        assert_eq!(result.classes[0].methods[0].name, "saveUser");
+        
+        // Check implementation
+        assert_eq!(result.classes[1].name, "com.example.UserServiceImpl");
+        assert_eq!(result.classes[1].methods.len(), 1);
+        assert_eq!(result.classes[1].methods[0].name, "saveUser");
+    }
+    
+    #[test]
+    fn test_debug_tree_structure() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            @RestController
+            public class UserController {
+                @GetMapping("/users/{id}")
+                public User getUser() {
+                    return null;
+                }
+            }
+        "#;
+        
+        let tree = parser.parser.lock().unwrap().parse(source, None).unwrap();
+        let root = tree.root_node();
+        
+        fn print_tree(node: tree_sitter::Node, source: &str, indent: usize) {
+            let indent_str = "  ".repeat(indent);
+            let text = source.get(node.byte_range()).unwrap_or("");
+            let preview = if text.len() > 50 {
+                format!("{}...", &text[..50])
+            } else {
+                text.to_string()
+            };
+            eprintln!("{}{} [{}]", indent_str, node.kind(), preview.replace('\n', "\\n"));
+            
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                print_tree(child, source, indent + 1);
+            }
+        }
+        
+        print_tree(root, source, 0);
+    }
+    
+    #[test]
+    fn test_extract_http_annotation() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            @RestController
+            public class UserController {
+                @GetMapping("/users/{id}")
+                public User getUser() {
+                    return null;
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("UserController.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        
+        // Debug: print method info
+        if !result.classes[0].methods.is_empty() {
+            let method = &result.classes[0].methods[0];
+            eprintln!("Method name: {}", method.name);
+            eprintln!("HTTP annotation: {:?}", method.http_annotations);
+        }
+        
+        let method = &result.classes[0].methods[0];
+        assert!(method.http_annotations.is_some(), "HTTP annotation should be present");
+        
+        let http = method.http_annotations.as_ref().unwrap();
+        assert_eq!(http.method, HttpMethod::GET);
+        // 路径可能包含应用名称前缀，所以我们检查它是否以正确的路径结尾
+        assert!(http.path.ends_with("users/{id}"), "Path should end with users/{{id}}, got: {}", http.path);
+        assert_eq!(http.path_params, vec!["id"]);
+    }
+    
+    #[test]
+    fn test_extract_string_constants_and_topic_references() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example.order;
+            
+            import com.example.kafka.Topics;
+            
+            public class OrderProducer {
+                private static final String AUDIT_TOPIC = "order-audit";
+                private static String mutable = "not-a-constant";
+                private final String instanceName = "not-static";
+                
+                interface Keys {
+                    String DEFAULT_KEY = "default";
+                }
+                
+                public void publish(String payload) {
+                    kafkaTemplate.send(Topics.ORDER_CREATED, payload);
+                    kafkaTemplate.send(AUDIT_TOPIC, payload);
+                    kafkaTemplate.send(topic, payload);
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("OrderProducer.java")).unwrap();
+        assert_eq!(result.string_constants, vec![
+            StringConstant {
+                name: "com.example.order.OrderProducer.AUDIT_TOPIC".to_string(),
+                value: "order-audit".to_string(),
+            },
+            StringConstant {
+                name: "com.example.order.OrderProducer.Keys.DEFAULT_KEY".to_string(),
+                value: "default".to_string(),
+            },
+        ]);
+        
+        // 常量引用记录常量的全限定名，局部变量不视为 topic
+        let publish = result.classes.iter()
+            .flat_map(|c| &c.methods)
+            .find(|m| m.name == "publish")
+            .unwrap();
+        let constants: Vec<Option<&str>> = publish.kafka_operations.iter()
+            .map(|op| op.topic_constant.as_deref())
+            .collect();
+        assert_eq!(constants, vec![
+            Some("com.example.kafka.Topics.ORDER_CREATED"),
+            Some("com.example.order.OrderProducer.AUDIT_TOPIC"),
+        ]);
+    }
+    
+    #[test]
+    fn test_extract_kafka_operations() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            public class MessageService {
+                @KafkaListener(topics = "user-events")
+                public void handleMessage(String message) {
+                    // process message
+                }
+                
+                public void sendMessage() {
+                    kafkaTemplate.send("order-events", "data");
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("MessageService.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].methods.len(), 2);
+        
+        // Check consumer
+        let consumer_method = &result.classes[0].methods[0];
+        eprintln!("Consumer method kafka operations: {:?}", consumer_method.kafka_operations);
+        assert_eq!(consumer_method.kafka_operations.len(), 1);
+        assert_eq!(consumer_method.kafka_operations[0].operation_type, KafkaOpType::Consume);
+        assert_eq!(consumer_method.kafka_operations[0].topic, "user-events");
+        
+        // Check producer
+        let producer_method = &result.classes[0].methods[1];
+        eprintln!("Producer method kafka operations: {:?}", producer_method.kafka_operations);
+        assert_eq!(producer_method.kafka_operations.len(), 1);
+        assert_eq!(producer_method.kafka_operations[0].operation_type, KafkaOpType::Produce);
+        assert_eq!(producer_method.kafka_operations[0].topic, "order-events");
+    }
+    
+    #[test]
+    fn test_kafka_message_value_types() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            public class OrderMessaging {
+                @KafkaListener(topics = "order-events")
+                public void onOrder(ConsumerRecord<String, com.example.avro.OrderEvent> record) {
+                }
+                
+                @KafkaListener(topics = "refund-events")
+                public void onRefund(@Header("key") String key, @Payload RefundEvent event) {
+                }
+                
+                public void publish(Order order) {
+                    kafkaTemplate.send("order-events", order.getId(), new OrderEvent(order.getId(), order.getAmount()));
+                }
+                
+                public void publishRefund(long id) {
+                    RefundEvent event = RefundEvent.newBuilder().setId(id).build();
+                    kafkaTemplate.send(REFUND_TOPIC, event);
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("OrderMessaging.java")).unwrap();
+        let value_types: Vec<_> = result.classes[0].methods.iter()
+            .map(|method| method.kafka_operations[0].value_type.as_deref())
+            .collect();
+        assert_eq!(value_types, vec![
+            Some("OrderEvent"),
+            Some("RefundEvent"),
+            Some("OrderEvent"),
+            Some("RefundEvent"),
+        ]);
+    }
+    
+    #[test]
+    fn test_extract_db_operations() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            public class UserRepository {
+                public void saveUser() {
+                    String sql = "INSERT INTO users (name) VALUES ('John')";
+                }
+                
+                public void findUser() {
+                    String sql = "SELECT * FROM users WHERE id = 1";
+                }
+                
+                public void updateUser() {
+                    String sql = "UPDATE users SET name = 'Jane'";
+                }
+                
+                public void deleteUser() {
+                    String sql = "DELETE FROM users WHERE id = 1";
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("UserRepository.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].methods.len(), 4);
+        
+        // Check INSERT
+        let insert_method = &result.classes[0].methods[0];
+        assert_eq!(insert_method.db_operations.len(), 1);
+        assert_eq!(insert_method.db_operations[0].operation_type, DbOpType::Insert);
+        assert_eq!(insert_method.db_operations[0].table, "users");
+        
+        // Check SELECT
+        let select_method = &result.classes[0].methods[1];
+        assert_eq!(select_method.db_operations.len(), 1);
+        assert_eq!(select_method.db_operations[0].operation_type, DbOpType::Select);
+        assert_eq!(select_method.db_operations[0].table, "users");
+        
+        // Check UPDATE
+        let update_method = &result.classes[0].methods[2];
+        assert_eq!(update_method.db_operations.len(), 1);
+        assert_eq!(update_method.db_operations[0].operation_type, DbOpType::Update);
+        assert_eq!(update_method.db_operations[0].table, "users");
+        
+        // Check DELETE
+        let delete_method = &result.classes[0].methods[3];
+        assert_eq!(delete_method.db_operations.len(), 1);
+        assert_eq!(delete_method.db_operations[0].operation_type, DbOpType::Delete);
+        assert_eq!(delete_method.db_operations[0].table, "users");
+    }
+    
+    #[test]
+    fn test_extract_redis_operations() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            public class CacheService {
+                public void getFromCache() {
+                    String value = redisTemplate.opsForValue().get("user:123");
+                }
+                
+                public void setToCache() {
+                    redisTemplate.opsForValue().set("user:456", "data");
+                }
+                
+                public void deleteFromCache() {
+                    redisTemplate.delete("user:789");
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("CacheService.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].methods.len(), 3);
+        
+        // Check GET
+        let get_method = &result.classes[0].methods[0];
+        assert_eq!(get_method.redis_operations.len(), 1);
+        assert_eq!(get_method.redis_operations[0].operation_type, RedisOpType::Get);
+        assert_eq!(get_method.redis_operations[0].key_pattern, "user:123");
+        
+        // Check SET
+        let set_method = &result.classes[0].methods[1];
+        assert_eq!(set_method.redis_operations.len(), 1);
+        assert_eq!(set_method.redis_operations[0].operation_type, RedisOpType::Set);
+        assert_eq!(set_method.redis_operations[0].key_pattern, "user:456");
+        
+        // Check DELETE
+        let delete_method = &result.classes[0].methods[2];
+        assert_eq!(delete_method.redis_operations.len(), 1);
+        assert_eq!(delete_method.redis_operations[0].operation_type, RedisOpType::Delete);
+        assert_eq!(delete_method.redis_operations[0].key_pattern, "user:789");
+    }
+    
+    #[test]
+    fn test_extract_method_calls() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            public class Calculator {
+                public int add(int a, int b) {
+                    return a + b;
+                }
+                
+                public int calculate() {
+                    int result = add(5, 3);
+                    System.out.println(result);
+                    return result;
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("Calculator.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].methods.len(), 2);
+        
+        // Check method calls in calculate method
+        let calculate_method = &result.classes[0].methods[1];
+        assert!(calculate_method.calls.len() >= 2); // add and println
+        
+        let call_names: Vec<&str> = calculate_method.calls.iter()
+            .map(|c| c.target.as_str())
+            .collect();
+        assert!(call_names.contains(&"add"));
+        assert!(call_names.contains(&"println"));
+    }
+    
+    #[test]
+    fn test_this_call_resolves_to_current_class() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example.order;
+            
+            public class OrderService {
+                public void placeOrder() {
+                    this.validate();
+                    validate();
+                }
+                
+                private void validate() {
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("OrderService.java")).unwrap();
+        let targets: Vec<&str> = result.classes[0].methods[0].calls.iter()
+            .map(|c| c.target.as_str())
+            .collect();
+        
+        // this.validate() 解析到当前类，不带接收者的调用保持原样
+        assert_eq!(targets, vec!["com.example.order.OrderService::validate", "validate"]);
+        assert_eq!(result.classes[0].extends, None);
+    }
+    
+    #[test]
+    fn test_interface_default_and_static_method_calls() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+package com.example.pricing;
+
+import com.example.util.Money;
+
+public interface PriceCalculator {
+    Rounding ROUNDING = new Rounding();
+    
+    long basePrice(String sku);
+    
+    default long finalPrice(String sku) {
+        long base = basePrice(sku);
+        return this.applyDiscount(base) + Money.tax(base);
+    }
+    
+    default long applyDiscount(long price) {
+        return ROUNDING.apply(price * 9 / 10);
+    }
+    
+    static long round(long price) {
+        return Money.round(price);
+    }
+}
+"#;
+        
+        let result = parser.parse_file(source, Path::new("PriceCalculator.java")).unwrap();
+        let class = &result.classes[0];
+        assert!(class.is_interface);
+        
+        let targets = |name: &str| -> Vec<String> {
+            let method = class.methods.iter().find(|m| m.name == name).unwrap();
+            method.calls.iter().map(|c| c.target.clone()).collect()
+        };
+        
+        // 抽象方法没有方法体
+        assert!(targets("basePrice").is_empty());
+        // default 方法中 this 调用解析到接口自身
+        assert_eq!(targets("finalPrice"), vec![
+            "basePrice",
+            "com.example.pricing.PriceCalculator::applyDiscount",
+            "com.example.util.Money::tax",
+        ]);
+        // 接口常量作为接收者
+        assert_eq!(targets("applyDiscount"), vec!["com.example.pricing.Rounding::apply"]);
+        assert_eq!(targets("round"), vec!["com.example.util.Money::round"]);
+    }
+    
+    #[test]
+    fn test_detect_delegation_methods() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example;
+            
+            public class OrderFacade {
+                private OrderService orderService;
+                
+                public Order find(long id) {
+                    // 直接转发
+                    return orderService.find(id);
+                }
+                
+                public void cancel(long id) {
+                    orderService.cancel(id);
+                }
+                
+                public Order findChecked(long id) {
+                    validate(id);
+                    return orderService.find(id);
+                }
+                
+                public Order findNormalized(long id) {
+                    return orderService.find(normalize(id));
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("OrderFacade.java")).unwrap();
+        let delegation = |name: &str| result.classes[0].methods.iter()
+            .find(|m| m.name == name)
+            .unwrap()
+            .is_delegation;
+        
+        assert!(delegation("find"));
+        assert!(delegation("cancel"));
+        assert!(!delegation("findChecked"));
+        assert!(!delegation("findNormalized"));
+    }
+    
+    #[test]
+    fn test_static_initializer_and_field_initializers() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example;
+            
+            public class Registry {
+                private static final Registry INSTANCE = new Registry();
+                private final Map<String, Handler> handlers = new HashMap<>();
+                private int count = 0;
+                
+                static {
+                    INSTANCE.register("default");
+                }
+                
+                public void register(String name) {
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("Registry.java")).unwrap();
+        let class = &result.classes[0];
+        let clinit = class.methods.iter().find(|m| m.name == "<clinit>").unwrap();
+        
+        assert_eq!(clinit.full_qualified_name, "com.example.Registry::<clinit>");
+        // 各段静态初始化代码单独记录行范围，实例字段不计入
+        assert_eq!(clinit.line_ranges().collect::<Vec<_>>(), vec![(5, 5), (9, 11)]);
+        let targets: Vec<&str> = clinit.calls.iter().map(|c| c.target.as_str()).collect();
+        assert_eq!(targets, vec!["com.example.Registry::<init>", "com.example.Registry::register"]);
+        
+        // 实例字段的初始化表达式归入隐式的默认构造器
+        let init = class.methods.iter().find(|m| m.name == "<init>").unwrap();
+        assert_eq!(init.line_ranges().collect::<Vec<_>>(), vec![(6, 6)]);
+        assert!(init.calls.iter().any(|c| c.target.ends_with("HashMap::<init>")));
+        
+        // 显式声明构造器时，实例初始化代码追加到构造器
+        let source = r#"
+            package com.example;
+            
+            public class Service {
+                private final Client client = new Client();
+                
+                public Service() {
+                    start();
+                }
+                
+                private void start() {
+                }
+            }
+        "#;
+        let result = parser.parse_file(source, Path::new("Service.java")).unwrap();
+        let constructors: Vec<&MethodInfo> = result.classes[0].methods.iter().filter(|m| m.name == "<init>").collect();
+        assert_eq!(constructors.len(), 1);
+        assert_eq!(constructors[0].line_ranges().collect::<Vec<_>>(), vec![(7, 9), (5, 5)]);
+        let targets: Vec<&str> = constructors[0].calls.iter().map(|c| c.target.as_str()).collect();
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[1], "com.example.Client::<init>");
+        assert!(result.classes[0].methods.iter().all(|m| m.name != "<clinit>"));
+        
+        // 没有初始化代码的类不生成 <clinit>
+        let source = "package com.example;\n\npublic class Plain {\n    private int count = 0;\n}\n";
+        let result = parser.parse_file(source, Path::new("Plain.java")).unwrap();
+        assert!(result.classes[0].methods.is_empty());
+    }
+    
+    #[test]
+    fn test_method_visibility() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example;
+            
+            public class Service {
+                public void api() {}
+                protected void hook() {}
+                void helper() {}
+                private void check() {}
+            }
+            
+            interface Api {
+                void call();
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("Service.java")).unwrap();
+        let visibility = |name: &str| result.classes.iter()
+            .flat_map(|c| c.methods.iter())
+            .find(|m| m.name == name)
+            .unwrap()
+            .visibility;
+        
+        assert_eq!(visibility("api"), Visibility::Public);
+        assert_eq!(visibility("hook"), Visibility::Package);
+        assert_eq!(visibility("helper"), Visibility::Package);
+        assert_eq!(visibility("check"), Visibility::Private);
+        // 接口方法隐式为 public
+        assert_eq!(visibility("call"), Visibility::Public);
+    }
+    
+    #[test]
+    fn test_record_declaration_with_accessors() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example.user;
+            
+            public record User(String name, Address address) implements Named {
+                public User {
+                    Objects.requireNonNull(name);
+                }
+                
+                public String name() {
+                    return name.trim();
+                }
+                
+                public String city() {
+                    return address.getCity();
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("User.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        
+        let record = &result.classes[0];
+        assert_eq!(record.name, "com.example.user.User");
+        assert_eq!(record.implements, vec!["com.example.user.Named".to_string()]);
+        
+        let mut names: Vec<&str> = record.methods.iter()
+            .map(|m| m.full_qualified_name.as_str())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec![
+            "com.example.user.User::<init>",
+            "com.example.user.User::address",
+            "com.example.user.User::city",
+            "com.example.user.User::name",
+        ]);
+        
+        // 隐式访问器的行范围为组件声明所在行
+        let address = record.methods.iter().find(|m| m.name == "address").unwrap();
+        assert_eq!(address.line_range, (4, 4));
+        
+        // 显式声明的访问器保留方法体，组件按字段类型解析调用
+        let name = record.methods.iter().find(|m| m.name == "name").unwrap();
+        assert_eq!(name.line_range, (9, 11));
+        let city = record.methods.iter().find(|m| m.name == "city").unwrap();
+        assert_eq!(city.calls[0].target, "com.example.user.Address::getCity");
+    }
+    
+    #[test]
+    fn test_constructor_calls_and_declarations() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example.payment;
+            
+            import com.example.gateway.GatewayClient;
+            
+            public class PaymentService {
+                public void pay() {
+                    PaymentProcessor processor = new PaymentProcessor(new GatewayClient(), buildConfig());
+                    java.util.List<String> ids = new java.util.ArrayList<>();
+                }
+            }
+            
+            class PaymentProcessor {
+                PaymentProcessor(GatewayClient client, Config config) {
+                    this.validate(config);
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("PaymentService.java")).unwrap();
+        let targets: Vec<&str> = result.classes[0].methods[0].calls.iter()
+            .map(|c| c.target.as_str())
+            .collect();
+        assert_eq!(targets, vec![
+            "com.example.payment.PaymentProcessor::<init>",
+            "com.example.gateway.GatewayClient::<init>",
+            "buildConfig",
+            "java.util.ArrayList::<init>",
+        ]);
+        
+        // 构造器作为 `<init>` 方法索引
+        let constructor = &result.classes[1].methods[0];
+        assert_eq!(constructor.full_qualified_name, "com.example.payment.PaymentProcessor::<init>");
+        assert_eq!(constructor.line_range, (14, 16));
+        assert_eq!(constructor.calls[0].target, "com.example.payment.PaymentProcessor::validate");
+    }
+    
+    #[test]
+    fn test_syntax_errors_report_locations() {
+        let parser = JavaParser::new().unwrap();
+        let source = "package com.example;\n\npublic class Broken {\n    public void run() {\n        int x = ;\n    }\n}\n";
+        
+        // 错误节点的位置随解析结果返回，恢复后的语法树照常提取方法
+        let result = parser.parse_file(source, Path::new("Broken.java")).unwrap();
+        assert_eq!(result.syntax_errors, vec![(5, 15)]);
+        let Some(ParseError::SyntaxErrors { path, locations }) = result.syntax_error() else {
+            panic!("expected ParseError::SyntaxErrors");
+        };
+        assert_eq!(path, PathBuf::from("Broken.java"));
+        assert_eq!(locations, vec![(5, 15)]);
+        assert_eq!(result.classes[0].methods[0].full_qualified_name, "com.example.Broken::run");
+    }
+    
+    #[test]
+    fn test_method_references_resolve_to_call_targets() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example.user;
+            
+            import com.example.model.User;
+            
+            public class UserService {
+                private UserRepository repository;
+                
+                public void export(java.util.List<User> users) {
+                    users.stream().map(User::getName).forEach(this::process);
+                    users.forEach(repository::save);
+                    users.stream().map(UserView::new);
+                }
+                
+                private void process(String name) {}
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("UserService.java")).unwrap();
+        let targets: Vec<&str> = result.classes[0].methods[0].calls.iter()
+            .map(|c| c.target.as_str())
+            .collect();
+        assert!(targets.contains(&"com.example.model.User::getName"));
+        assert!(targets.contains(&"com.example.user.UserService::process"));
+        assert!(targets.contains(&"com.example.user.UserRepository::save"));
+        assert!(targets.contains(&"com.example.user.UserView::<init>"));
+        
+        // 方法引用在索引中形成调用边
+        let user_source = r#"
+            package com.example.model;
+            
+            public class User {
+                private String name;
+                
+                public String getName() {
+                    return name;
+                }
+            }
+        "#;
+        let mut index = crate::code_index::CodeIndex::new();
+        let parsers: Vec<Box<dyn LanguageParser>> = vec![Box::new(JavaParser::new().unwrap())];
+        index.index_sources(&[
+            (PathBuf::from("UserService.java"), source.to_string()),
+            (PathBuf::from("User.java"), user_source.to_string()),
+        ], &parsers).unwrap();
+        assert!(index.find_method("com.example.model.User::getName").is_some());
+        assert_eq!(index.find_callers("com.example.model.User::getName"), vec!["com.example.user.UserService::export"]);
+        assert_eq!(index.find_callers("com.example.user.UserService::process"), vec!["com.example.user.UserService::export"]);
+    }
+    
+    #[test]
+    fn test_method_reference_on_untyped_variable_is_not_a_type() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example.user;
+            
+            public class NameCollector {
+                public void collect(Iterable<String> names, java.util.List<String> list) {
+                    names.forEach(list::add);
+                    var seen = new java.util.HashSet<String>();
+                    names.forEach(seen::add);
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("NameCollector.java")).unwrap();
+        let targets: Vec<&str> = result.classes[0].methods[0].calls.iter()
+            .map(|c| c.target.as_str())
+            .collect();
+        // 接收者是类型未知的变量，不能按类型解析为 com.example.user.list::add
+        assert!(!targets.iter().any(|target| target.contains("list::") || target.contains("seen::")), "{:?}", targets);
+        assert_eq!(targets.iter().filter(|target| **target == "add").count(), 2, "{:?}", targets);
+    }
+    
+    #[test]
+    fn test_super_call_resolves_to_superclass() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example.order;
+            
+            import com.example.common.BaseService;
+            
+            public class OrderService extends BaseService<Order> {
+                @Override
+                public void init() {
+                    super.init();
+                }
+            }
+            
+            class AuditService extends OrderService {
+                public void init() {
+                    super.init();
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("OrderService.java")).unwrap();
+        
+        // 泛型父类只保留原始类型，通过导入解析为完整类名
+        assert_eq!(result.classes[0].extends.as_deref(), Some("com.example.common.BaseService"));
+        assert_eq!(result.classes[0].methods[0].calls[0].target, "com.example.common.BaseService::init");
+        
+        // 同包父类按当前包解析
+        assert_eq!(result.classes[1].extends.as_deref(), Some("com.example.order.OrderService"));
+        assert_eq!(result.classes[1].methods[0].calls[0].target, "com.example.order.OrderService::init");
+    }
+    
+    #[test]
+    fn test_same_simple_name_binds_to_imported_class() {
+        let parser = JavaParser::new().unwrap();
+        
+        // com.app.admin 和 com.app.model 中各有一个 User 类
+        let importing = r#"
+            package com.app.web;
+            
+            import com.app.model.User;
+            import com.app.legacy.*;
+            import static com.app.util.Users.defaultUser;
+            
+            public class ProfileController {
+                private User user;
+                
+                public void show(Object legacy, Object defaultUser) {
+                    User other = new User();
+                    user.getName();
+                    other.getEmail();
+                    legacy.migrate();
+                    defaultUser.reset();
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(importing, Path::new("ProfileController.java")).unwrap();
+        let targets: Vec<&str> = result.classes[0].methods[0].calls.iter()
+            .map(|c| c.target.as_str())
+            .collect();
+        
+        // 显式导入优先，调用绑定到 com.app.model.User
+        assert!(targets.contains(&"com.app.model.User::getName"));
+        assert!(targets.contains(&"com.app.model.User::getEmail"));
+        assert!(!targets.iter().any(|t| t.starts_with("com.app.admin.User")));
+        
+        // 按需导入和静态导入不会把变量名误解析为包名或成员名
+        assert!(targets.contains(&"migrate"));
+        assert!(targets.contains(&"reset"));
+        
+        // 未导入时，同名类解析为同包中的类
+        let same_package = r#"
+            package com.app.admin;
+            
+            import com.app.legacy.*;
+            
+            public class AdminController {
+                private User user;
+                
+                public void show() {
+                    user.getName();
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(same_package, Path::new("AdminController.java")).unwrap();
+        let targets: Vec<&str> = result.classes[0].methods[0].calls.iter()
+            .map(|c| c.target.as_str())
+            .collect();
+        assert_eq!(targets, vec!["com.app.admin.User::getName"]);
+    }
+    
+    #[test]
+    fn test_extract_calls_inside_lambdas_and_anonymous_classes() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example;
+            
+            import com.example.service.OrderService;
+            import com.example.model.Order;
+            import com.example.repo.AuditRepository;
+            
+            public class OrderJob {
+                private OrderService orderService;
+                private AuditRepository auditRepository;
+                
+                public void run(List<Order> orders) {
+                    orders.forEach(order -> orderService.process(order));
+                    orders.forEach((Order o) -> this.orderService.cancel(o.getId()));
+                    Runnable task = new Runnable() {
+                        @Override
+                        public void run() {
+                            auditRepository.save();
+                        }
+                    };
+                }
+                
+                public void shadow(List<Object> items) {
+                    items.forEach(orderService -> orderService.process());
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("OrderJob.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        // 匿名类中的方法不会作为外层类的方法
+        assert_eq!(result.classes[0].methods.len(), 2);
+        
+        let run = &result.classes[0].methods[0];
+        assert_eq!(run.full_qualified_name, "com.example.OrderJob::run");
+        let targets: Vec<&str> = run.calls.iter().map(|c| c.target.as_str()).collect();
+        
+        // lambda 中对字段的调用解析为字段类型，并记录在外层方法下
+        assert!(targets.contains(&"com.example.service.OrderService::process"));
+        assert!(targets.contains(&"com.example.service.OrderService::cancel"));
+        // 显式类型的 lambda 参数
+        assert!(targets.contains(&"com.example.model.Order::getId"));
+        // 匿名类方法体中的调用归属到外层方法
+        assert!(targets.contains(&"com.example.repo.AuditRepository::save"));
+        
+        // lambda 参数遮蔽同名字段时不应解析为字段类型
+        let shadow = &result.classes[0].methods[1];
+        let targets: Vec<&str> = shadow.calls.iter().map(|c| c.target.as_str()).collect();
+        assert!(!targets.contains(&"com.example.service.OrderService::process"));
+        assert!(targets.contains(&"process"));
+    }
+    
+    #[test]
+    fn test_deeply_nested_expression_does_not_overflow() {
+        // 生成代码中常见的超长 builder 链/嵌套调用，递归遍历会导致栈溢出
+        let depth = 20_000;
+        let nested = format!("{}1{}", "wrap(".repeat(depth), ")".repeat(depth));
+        let source = format!(
+            "package com.example;\n\npublic class Generated {{\n    public void build() {{\n        helper();\n        int x = {};\n    }}\n\n    private void helper() {{}}\n}}\n",
+            nested
+        );
+        
+        // 在较小的线程栈上解析，确保遍历不依赖调用栈深度
+        let calls = std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(move || {
+                let parser = JavaParser::new().unwrap();
+                let result = parser.parse_file(&source, Path::new("Generated.java")).unwrap();
+                assert_eq!(result.classes.len(), 1);
+                result.classes[0].methods[0].calls.clone()
+            })
+            .unwrap()
+            .join()
+            .expect("parsing deeply nested code should not overflow the stack");
+        
+        let targets: Vec<&str> = calls.iter().map(|c| c.target.as_str()).collect();
+        assert_eq!(targets[0], "helper");
+        assert_eq!(targets.iter().filter(|t| **t == "wrap").count(), depth);
+    }
+    
+    #[test]
+    fn test_call_filter_drops_accessor_calls() {
+        let source = r#"
+            package com.example;
+            
+            import com.example.model.User;
+            
+            public class UserService {
+                private User user;
+                
+                public String getName() {
+                    return user.getName();
+                }
+                
+                public void update(String name) {
+                    user.setName(name);
+                    if (user.isActive()) {
+                        notifyChange();
+                    }
+                    getName();
+                }
+                
+                private void notifyChange() {}
+            }
+        "#;
+        
+        // 默认不过滤访问器调用
+        let parser = JavaParser::new().unwrap();
+        let result = parser.parse_file(source, Path::new("UserService.java")).unwrap();
+        let update = &result.classes[0].methods[1];
+        let targets: Vec<&str> = update.calls.iter().map(|c| c.target.as_str()).collect();
+        assert!(targets.contains(&"com.example.model.User::setName"));
+        assert!(targets.contains(&"com.example.model.User::isActive"));
+        assert!(targets.contains(&"getName"));
+        
+        // 启用后丢弃访问器调用边，其余调用保留
+        let mut parser = JavaParser::new().unwrap();
+        parser.set_call_filter(CallFilter::accessors());
+        let result = parser.parse_file(source, Path::new("UserService.java")).unwrap();
+        let update = &result.classes[0].methods[1];
+        let targets: Vec<&str> = update.calls.iter().map(|c| c.target.as_str()).collect();
+        assert_eq!(targets, vec!["notifyChange"]);
+        
+        // 访问器方法本身仍被索引
+        let names: Vec<&str> = result.classes[0].methods.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["getName", "update", "notifyChange"]);
+    }
+    
+    #[test]
+    fn test_extract_field_access_method_calls() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example;
+            
+            import com.hualala.shop.equipment.EquipmentManageExe;
+            
+            public class TestController {
+                private EquipmentManageExe equipmentManageExe;
+                
+                public void testMethod() {
+                    equipmentManageExe.listExecuteSchedule("");
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("TestController.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].methods.len(), 1);
+        
+        let method = &result.classes[0].methods[0];
+        assert_eq!(method.calls.len(), 1);
+        // 应该解析为完整的类名::方法名格式
+        assert_eq!(method.calls[0].target, "com.hualala.shop.equipment.EquipmentManageExe::listExecuteSchedule");
+    }
+    
+    #[test]
+    fn test_extract_local_variable_method_calls() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example;
+            
+            public class TestLocalVariable {
+                public void go() {
+                    Foo foo = new Foo();
+                    foo.bar();
+                }
+            }
+            
+            class Foo {
+                public void bar() {
+                    System.out.println("bar called");
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("TestLocalVariable.java")).unwrap();
+        assert_eq!(result.classes.len(), 2);
+        
+        // 查找 TestLocalVariable 类的 go 方法
+        let test_class = result.classes.iter()
+            .find(|c| c.name.contains("TestLocalVariable"))
+            .expect("Should find TestLocalVariable class");
+        
+        assert_eq!(test_class.methods.len(), 1);
+        let go_method = &test_class.methods[0];
+        assert_eq!(go_method.name, "go");
+        
+        // 验证方法调用：构造器调用和 foo.bar()
+        assert_eq!(go_method.calls.len(), 2, "Should have 2 method calls");
+        assert!(go_method.calls[0].target.ends_with("Foo::<init>"));
+        
+        // 应该解析为 Foo::bar 或 com.example.Foo::bar
+        let call_target = &go_method.calls[1].target;
+        assert!(
+            call_target.contains("Foo::bar"),
+            "Should resolve to Foo::bar, got: {}",
+            call_target
+        );
+    }
+    
+    #[test]
+    fn test_extract_local_variable_with_imports() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example;
+            
+            import com.hualala.shop.equipment.EquipmentManageExe;
+            
+            public class TestController {
+                public void testMethod() {
+                    // 本地变量使用导入的类
+                    EquipmentManageExe localExe = new EquipmentManageExe();
+                    localExe.listExecuteSchedule("");
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("TestController.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].methods.len(), 1);
+        
+        let method = &result.classes[0].methods[0];
+        assert_eq!(method.calls.len(), 2);
+        assert_eq!(method.calls[0].target, "com.hualala.shop.equipment.EquipmentManageExe::<init>");
+        
+        // 应该解析为完整的导入类名::方法名格式
+        assert_eq!(
+            method.calls[1].target,
+            "com.hualala.shop.equipment.EquipmentManageExe::listExecuteSchedule"
+        );
+    }
+    
+    #[test]
+    fn test_extract_mixed_field_and_local_variable_calls() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example;
+            
+            import com.hualala.shop.equipment.EquipmentManageExe;
+            
+            public class TestController {
+                private EquipmentManageExe fieldExe;
+                
+                public void testMethod() {
+                    // 类字段调用
+                    fieldExe.listExecuteSchedule("field");
+                    
+                    // 本地变量调用
+                    EquipmentManageExe localExe = new EquipmentManageExe();
+                    localExe.listExecuteSchedule("local");
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("TestController.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].methods.len(), 1);
+        
+        let method = &result.classes[0].methods[0];
+        assert_eq!(method.calls.len(), 3, "Should have 2 method calls and 1 constructor call");
+        assert_eq!(method.calls[1].target, "com.hualala.shop.equipment.EquipmentManageExe::<init>");
+        
+        // 两个方法调用都应该解析为完整的类名::方法名格式
+        for call in method.calls.iter().filter(|call| !call.target.ends_with("::<init>")) {
+            assert_eq!(
+                call.target,
+                "com.hualala.shop.equipment.EquipmentManageExe::listExecuteSchedule"
+            );
+        }
+    }
+    
+    #[test]
+    fn test_extract_self_type_local_variable() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example;
+            
+            public class Builder {
+                private String name;
+                
+                public Builder setName(String name) {
+                    this.name = name;
+                    return this;
+                }
+                
+                public Builder build() {
+                    // 本地变量类型为当前类自身
+                    Builder builder = new Builder();
+                    builder.setName("test");
+                    return builder;
+                }
+                
+                public static Builder createBuilder() {
+                    // 静态方法中的本地变量
+                    Builder instance = new Builder();
+                    instance.setName("static");
+                    return instance;
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("Builder.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        
+        let builder_class = &result.classes[0];
+        assert_eq!(builder_class.name, "com.example.Builder");
+        
+        // 测试 build() 方法
+        let build_method = builder_class.methods.iter()
+            .find(|m| m.name == "build")
+            .expect("Should find build method");
+        
+        assert_eq!(build_method.calls.len(), 2, "build() should have 1 constructor call and 1 method call");
+        assert_eq!(build_method.calls[0].target, "com.example.Builder::<init>");
+        assert!(
+            build_method.calls[1].target.contains("com.example.Builder::setName"),
+            "Should resolve to com.example.Builder::setName, got: {}",
+            build_method.calls[1].target
+        );
+        
+        // 测试 createBuilder() 静态方法
+        let create_method = builder_class.methods.iter()
+            .find(|m| m.name == "createBuilder")
+            .expect("Should find createBuilder method");
+        
+        assert_eq!(create_method.calls.len(), 2, "createBuilder() should have 1 constructor call and 1 method call");
+        assert!(
+            create_method.calls[1].target.contains("com.example.Builder::setName"),
+            "Should resolve to com.example.Builder::setName in static method, got: {}",
+            create_method.calls[1].target
+        );
+    }
+    
+    #[test]
+    fn test_extract_various_method_call_patterns() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            public class TestService {
+                private UserService userService;
+                
+                public void testMethod() {
+                    // Direct method call
+                    localMethod();
+                    
+                    // Field access method call
+                    userService.findUser();
+                    
+                    // Chained method call
+                    userService.getRepository().save();
+                    
+                    // Static method call
+                    System.out.println("test");
+                    
+                    // Method call with multiple arguments
+                    userService.updateUser(1, "name");
+                }
+                
+                private void localMethod() {
+                }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("TestService.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].methods.len(), 2);
+        
+        let test_method = &result.classes[0].methods[0];
+        let call_names: Vec<&str> = test_method.calls.iter()
+            .map(|c| c.target.as_str())
+            .collect();
+        
+        // Verify all method calls are captured correctly
+        assert!(call_names.contains(&"localMethod"), "Should find localMethod");
+        assert!(call_names.contains(&"findUser") || call_names.contains(&"UserService::findUser"), "Should find findUser");
+        assert!(call_names.contains(&"getRepository") || call_names.contains(&"UserService::getRepository"), "Should find getRepository");
+        assert!(call_names.contains(&"save"), "Should find save");
+        assert!(call_names.contains(&"println") || call_names.contains(&"System::println"), "Should find println");
+        assert!(call_names.contains(&"updateUser") || call_names.contains(&"UserService::updateUser"), "Should find updateUser");
     }
     
-    /// 提取导入声明
-    fn extract_imports(&self, source: &str, tree: &tree_sitter::Tree) -> Vec<Import> {
-        let mut imports = Vec::new();
-        let root_node = tree.root_node();
+    #[test]
+    fn test_extract_feign_client_annotation() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.hualala.shop.domain.feign;
+            
+            import org.springframework.cloud.openfeign.FeignClient;
+            import org.springframework.web.bind.annotation.PostMapping;
+            import org.springframework.web.bind.annotation.RequestBody;
+            
+            @FeignClient(value = "hll-basic-info-api", path = "/hll-basic-info-api")
+            public interface BasicInfoFeign {
+                @PostMapping("/feign/shop/copy/info")
+                GoodsResponse getGoodsInfo(@RequestBody GoodsInfoRequest request);
+            }
+        "#;
         
-        self.walk_node_for_imports(source, root_node, &mut imports);
+        let result = parser.parse_file(source, Path::new("BasicInfoFeign.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].name, "com.hualala.shop.domain.feign.BasicInfoFeign");
+        assert_eq!(result.classes[0].methods.len(), 1);
         
-        imports
+        let method = &result.classes[0].methods[0];
+        assert_eq!(method.name, "getGoodsInfo");
+        assert!(method.http_annotations.is_some(), "HTTP annotation should be present");
+        
+        let http = method.http_annotations.as_ref().unwrap();
+        assert_eq!(http.method, HttpMethod::POST);
+        // 应该组合为：service_name/base_path/method_path
+        assert_eq!(http.path, "hll-basic-info-api/hll-basic-info-api/feign/shop/copy/info");
     }
     
-    /// 递归遍历节点查找导入声明
-    fn walk_node_for_imports(&self, source: &str, node: tree_sitter::Node, imports: &mut Vec<Import>) {
-        if node.kind() == "import_declaration" {
-            let mut cursor = node.walk();
-            for child in node.children(&mut cursor) {
-                if child.kind() == "scoped_identifier" {
-                    if let Some(text) = source.get(child.byte_range()) {
-                        imports.push(Import {
-                            module: text.to_string(),
-                            items: vec![],
-                        });
-                    }
-                }
+    #[test]
+    fn test_extract_fully_qualified_annotations() {
+        let parser = JavaParser::new().unwrap();
+        
+        // 全限定名形式的 FeignClient 和方法级注解
+        let feign_source = r#"
+            package com.example.feign;
+            
+            @org.springframework.cloud.openfeign.FeignClient(value = "user-service", path = "/users")
+            public interface UserFeign {
+                @org.springframework.web.bind.annotation.GetMapping("/profile/{id}")
+                UserProfile getProfile(Long id);
             }
-        }
+        "#;
         
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            self.walk_node_for_imports(source, child, imports);
-        }
-    }
-}
-
-impl LanguageParser for JavaParser {
-    fn language_name(&self) -> &str {
-        "java"
-    }
-    
-    fn file_extensions(&self) -> &[&str] {
-        &["java"]
-    }
-    
-    fn parse_file(&self, content: &str, file_path: &Path) -> Result<ParsedFile, ParseError> {
-        let tree = self.parser.lock().unwrap().parse(content, None)
-            .ok_or_else(|| ParseError::InvalidFormat {
-                message: "Failed to parse Java file".to_string(),
-            })?;
+        let result = parser.parse_file(feign_source, Path::new("UserFeign.java")).unwrap();
+        let method = &result.classes[0].methods[0];
+        let http = method.http_annotations.as_ref().expect("FeignClient method should have HTTP annotation");
+        assert!(http.is_feign_client);
+        assert_eq!(http.method, HttpMethod::GET);
+        assert_eq!(http.path, "user-service/users/profile/{id}");
         
-        let classes = self.extract_classes(content, file_path, &tree);
-        let imports = self.extract_imports(content, &tree);
+        // 全限定名形式的类级别 RequestMapping 和方法级 RequestMapping
+        let controller_source = r#"
+            package com.example.web;
+            
+            @org.springframework.web.bind.annotation.RequestMapping("/api/orders")
+            public class OrderController {
+                @org.springframework.web.bind.annotation.RequestMapping(value = "/{id}", method = RequestMethod.DELETE)
+                public void delete(Long id) {
+                }
+                
+                @org.springframework.kafka.annotation.KafkaListener(topics = "order-events")
+                public void onEvent(String message) {
+                }
+            }
+        "#;
         
-        Ok(ParsedFile {
-            file_path: file_path.to_path_buf(),
-            language: "java".to_string(),
-            classes,
-            functions: vec![], // Java 使用类和方法，不使用顶层函数
-            imports,
-        })
+        let result = parser.parse_file(controller_source, Path::new("OrderController.java")).unwrap();
+        let delete = &result.classes[0].methods[0];
+        let http = delete.http_annotations.as_ref().expect("RequestMapping method should have HTTP annotation");
+        assert_eq!(http.method, HttpMethod::DELETE);
+        assert!(http.path.ends_with("api/orders/{id}"), "got: {}", http.path);
+        
+        let on_event = &result.classes[0].methods[1];
+        assert_eq!(on_event.kafka_operations.len(), 1);
+        assert_eq!(on_event.kafka_operations[0].topic, "order-events");
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
     
     #[test]
-    fn test_parse_simple_class() {
+    fn test_request_mapping_without_method_accepts_any() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            public class Example {
-                public void hello() {
-                    System.out.println("Hello");
+            package com.example;
+            
+            @RestController
+            public class SyncController {
+                @RequestMapping("/api/sync")
+                public void sync() {
                 }
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("Example.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].name, "Example");
-        assert_eq!(result.classes[0].methods.len(), 1);
-        assert_eq!(result.classes[0].methods[0].name, "hello");
+        let result = parser.parse_file(source, Path::new("SyncController.java")).unwrap();
+        let http = result.classes[0].methods[0].http_annotations.as_ref().unwrap();
+        assert_eq!(http.method, HttpMethod::Any);
     }
     
     #[test]
-    fn test_parse_interface() {
+    fn test_annotation_simple_name_matching() {
+        assert_eq!(annotation_simple_name("FeignClient"), "FeignClient");
+        assert_eq!(annotation_simple_name("org.springframework.cloud.openfeign.FeignClient"), "FeignClient");
+        
+        // 名称仅包含 FeignClient 的注解不应被识别为 FeignClient
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            package com.example;
-            
-            public interface ShopCopyService {
-                Response query(GetShopCopyCmd cmd);
-                Response clone(ShopCloneCmd cmd);
-                Response restore(ShopRestoreCmd cmd);
+            @EnableFeignClients(value = "com.example")
+            public interface NotAFeign {
+                @GetMapping("/x")
+                String get();
             }
         "#;
-        
-        let result = parser.parse_file(source, Path::new("ShopCopyService.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].name, "com.example.ShopCopyService");
-        assert_eq!(result.classes[0].methods.len(), 3);
-        assert_eq!(result.classes[0].methods[0].name, "query");
-        assert_eq!(result.classes[0].methods[1].name, "clone");
-        assert_eq!(result.classes[0].methods[2].name, "restore");
+        let result = parser.parse_file(source, Path::new("NotAFeign.java")).unwrap();
+        let http = result.classes[0].methods[0].http_annotations.as_ref().unwrap();
+        assert!(!http.is_feign_client);
     }
     
     #[test]
-    fn test_parse_interface_with_implementation() {
+    fn test_extract_feign_client_without_base_path() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
             package com.example;
             
-            public interface UserService {
-                void saveUser(String name);
-            }
+            import org.springframework.cloud.openfeign.FeignClient;
+            import org.springframework.web.bind.annotation.GetMapping;
             
-            public class UserServiceImpl implements UserService {
-                @Override
-                public void saveUser(String name) {
-                    // implementation
-                }
+            @FeignClient(value = "user-service")
+            public interface UserClient {
+                @GetMapping("/api/users")
+                User getUser();
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("UserService.java")).unwrap();
-        assert_eq!(result.classes.len(), 2);
-        
-        // Check interface
-        assert_eq!(result.classes[0].name, "com.example.UserService");
+        let result = parser.parse_file(source, Path::new("UserClient.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
         assert_eq!(result.classes[0].methods.len(), 1);
-        assert_eq!(result.classes[0].methods[0].name, "saveUser");
         
-        // Check implementation
-        assert_eq!(result.classes[1].name, "com.example.UserServiceImpl");
-        assert_eq!(result.classes[1].methods.len(), 1);
-        assert_eq!(result.classes[1].methods[0].name, "saveUser");
+        let method = &result.classes[0].methods[0];
+        assert!(method.http_annotations.is_some());
+        
+        let http = method.http_annotations.as_ref().unwrap();
+        assert_eq!(http.method, HttpMethod::GET);
+        // 没有 base_path 时，应该是：service_name/method_path
+        assert_eq!(http.path, "user-service/api/users");
     }
     
     #[test]
-    fn test_debug_tree_structure() {
+    fn test_extract_static_method_calls() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            @RestController
-            public class UserController {
-                @GetMapping("/users/{id}")
-                public User getUser() {
-                    return null;
+            package com.example;
+            
+            import org.apache.commons.lang3.StringUtils;
+            import java.util.Collections;
+            
+            public class TestStaticMethod {
+                private UserService userService;
+                
+                public void testMethod() {
+                    // 静态方法调用 - 使用导入的类
+                    String result1 = StringUtils.isEmpty("test");
+                    
+                    // 静态方法调用 - 使用导入的类
+                    List<String> list = Collections.emptyList();
+                    
+                    // 实例方法调用
+                    userService.findUser();
+                    
+                    // 链式调用
+                    Collections.emptyList().stream().filter(x -> x != null);
                 }
             }
         "#;
         
-        let tree = parser.parser.lock().unwrap().parse(source, None).unwrap();
-        let root = tree.root_node();
+        let result = parser.parse_file(source, Path::new("TestStaticMethod.java")).unwrap();
+        assert_eq!(result.classes.len(), 1);
+        assert_eq!(result.classes[0].methods.len(), 1);
         
-        fn print_tree(node: tree_sitter::Node, source: &str, indent: usize) {
-            let indent_str = "  ".repeat(indent);
-            let text = source.get(node.byte_range()).unwrap_or("");
-            let preview = if text.len() > 50 {
-                format!("{}...", &text[..50])
-            } else {
-                text.to_string()
-            };
-            eprintln!("{}{} [{}]", indent_str, node.kind(), preview.replace('\n', "\\n"));
-            
-            let mut cursor = node.walk();
-            for child in node.children(&mut cursor) {
-                print_tree(child, source, indent + 1);
-            }
-        }
+        let method = &result.classes[0].methods[0];
+        let call_targets: Vec<&str> = method.calls.iter()
+            .map(|c| c.target.as_str())
+            .collect();
         
-        print_tree(root, source, 0);
+        // 验证静态方法调用被正确识别为 ClassName::methodName
+        assert!(
+            call_targets.contains(&"org.apache.commons.lang3.StringUtils::isEmpty"),
+            "Should find StringUtils::isEmpty, got: {:?}", call_targets
+        );
+        assert!(
+            call_targets.iter().any(|t| t.contains("Collections::emptyList")),
+            "Should find Collections::emptyList, got: {:?}", call_targets
+        );
+        
+        // 验证实例方法调用仍然正常工作
+        assert!(
+            call_targets.iter().any(|t| t.contains("UserService::findUser")),
+            "Should find UserService::findUser (with or without package), got: {:?}", call_targets
+        );
     }
     
     #[test]
-    fn test_extract_http_annotation() {
+    fn test_extract_feign_client_with_name_attribute() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            @RestController
-            public class UserController {
-                @GetMapping("/users/{id}")
-                public User getUser() {
-                    return null;
-                }
+            package com.example;
+            
+            import org.springframework.cloud.openfeign.FeignClient;
+            import org.springframework.web.bind.annotation.PutMapping;
+            
+            @FeignClient(name = "order-service", path = "/orders")
+            public interface OrderClient {
+                @PutMapping("/update")
+                void updateOrder();
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("UserController.java")).unwrap();
+        let result = parser.parse_file(source, Path::new("OrderClient.java")).unwrap();
         assert_eq!(result.classes.len(), 1);
-        
-        // Debug: print method info
-        if !result.classes[0].methods.is_empty() {
-            let method = &result.classes[0].methods[0];
-            eprintln!("Method name: {}", method.name);
-            eprintln!("HTTP annotation: {:?}", method.http_annotations);
-        }
+        assert_eq!(result.classes[0].methods.len(), 1);
         
         let method = &result.classes[0].methods[0];
-        assert!(method.http_annotations.is_some(), "HTTP annotation should be present");
+        assert!(method.http_annotations.is_some());
         
         let http = method.http_annotations.as_ref().unwrap();
-        assert_eq!(http.method, HttpMethod::GET);
-        // 路径可能包含应用名称前缀，所以我们检查它是否以正确的路径结尾
-        assert!(http.path.ends_with("users/{id}"), "Path should end with users/{{id}}, got: {}", http.path);
-        assert_eq!(http.path_params, vec!["id"]);
+        assert_eq!(http.method, HttpMethod::PUT);
+        // 使用 name 属性时，应该正常工作
+        assert_eq!(http.path, "order-service/orders/update");
     }
     
     #[test]
-    fn test_extract_kafka_operations() {
+    fn test_rest_client_calls_recorded_as_http_consumers() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            public class MessageService {
-                @KafkaListener(topics = "user-events")
-                public void handleMessage(String message) {
-                    // process message
-                }
-                
-                public void sendMessage() {
-                    kafkaTemplate.send("order-events", "data");
-                }
-            }
-        "#;
+package com.example.gateway;
+
+import org.springframework.web.client.RestClient;
+
+public class UserGateway {
+    private final RestClient restClient = RestClient.builder().baseUrl("http://user-service").build();
+    
+    public User getUser(long id) {
+        return restClient.get()
+            .uri("/api/users/{id}?verbose=true", id)
+            .retrieve()
+            .body(User.class);
+    }
+    
+    public void createUser(User user) {
+        restClient.post().uri("https://billing/api/accounts").body(user).retrieve().toBodilessEntity();
+    }
+}
+"#;
         
-        let result = parser.parse_file(source, Path::new("MessageService.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].methods.len(), 2);
+        let result = parser.parse_file(source, Path::new("UserGateway.java")).unwrap();
+        let methods = &result.classes[0].methods;
         
-        // Check consumer
-        let consumer_method = &result.classes[0].methods[0];
-        eprintln!("Consumer method kafka operations: {:?}", consumer_method.kafka_operations);
-        assert_eq!(consumer_method.kafka_operations.len(), 1);
-        assert_eq!(consumer_method.kafka_operations[0].operation_type, KafkaOpType::Consume);
-        assert_eq!(consumer_method.kafka_operations[0].topic, "user-events");
+        let get_user = methods[0].http_annotations.as_ref().unwrap();
+        assert!(get_user.is_feign_client);
+        assert_eq!(get_user.method, HttpMethod::GET);
+        assert_eq!(get_user.path, "user-service/api/users/{id}");
+        assert_eq!(get_user.path_params, vec!["id".to_string()]);
         
-        // Check producer
-        let producer_method = &result.classes[0].methods[1];
-        eprintln!("Producer method kafka operations: {:?}", producer_method.kafka_operations);
-        assert_eq!(producer_method.kafka_operations.len(), 1);
-        assert_eq!(producer_method.kafka_operations[0].operation_type, KafkaOpType::Produce);
-        assert_eq!(producer_method.kafka_operations[0].topic, "order-events");
+        let create_user = methods[1].http_annotations.as_ref().unwrap();
+        assert_eq!(create_user.method, HttpMethod::POST);
+        assert_eq!(create_user.path, "billing/api/accounts");
     }
     
     #[test]
-    fn test_extract_db_operations() {
+    fn test_rest_client_records_every_uri_call_in_method() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            public class UserRepository {
-                public void saveUser() {
-                    String sql = "INSERT INTO users (name) VALUES ('John')";
-                }
-                
-                public void findUser() {
-                    String sql = "SELECT * FROM users WHERE id = 1";
-                }
-                
-                public void updateUser() {
-                    String sql = "UPDATE users SET name = 'Jane'";
-                }
-                
-                public void deleteUser() {
-                    String sql = "DELETE FROM users WHERE id = 1";
-                }
-            }
-        "#;
-        
-        let result = parser.parse_file(source, Path::new("UserRepository.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].methods.len(), 4);
+package com.example.gateway;
+
+public class ProfileGateway {
+    private final UserHttpClient client;
+    
+    public Profile loadProfile(long id) {
+        User user = client.get().uri("http://user-service/api/users/{id}", id).retrieve().body(User.class);
+        client.post().uri("http://audit-service/api/audits").body(user).retrieve().toBodilessEntity();
+        return new Profile(user);
+    }
+}
+"#;
         
-        // Check INSERT
-        let insert_method = &result.classes[0].methods[0];
-        assert_eq!(insert_method.db_operations.len(), 1);
-        assert_eq!(insert_method.db_operations[0].operation_type, DbOpType::Insert);
-        assert_eq!(insert_method.db_operations[0].table, "users");
+        let result = parser.parse_file(source, Path::new("ProfileGateway.java")).unwrap();
+        let method = &result.classes[0].methods[0];
         
-        // Check SELECT
-        let select_method = &result.classes[0].methods[1];
-        assert_eq!(select_method.db_operations.len(), 1);
-        assert_eq!(select_method.db_operations[0].operation_type, DbOpType::Select);
-        assert_eq!(select_method.db_operations[0].table, "users");
+        // 源文件中没有出现 RestClient 类型名，仍按调用形式识别
+        let calls: Vec<_> = method.all_http_annotations()
+            .map(|http| (http.method.clone(), http.path.as_str(), http.is_feign_client))
+            .collect();
+        assert_eq!(calls, vec![
+            (HttpMethod::GET, "user-service/api/users/{id}", true),
+            (HttpMethod::POST, "audit-service/api/audits", true),
+        ]);
+        
+        let mut index = crate::code_index::CodeIndex::new();
+        index.index_method(method).unwrap();
+        for (method, path) in [(HttpMethod::GET, "user-service/api/users/{id}"), (HttpMethod::POST, "audit-service/api/audits")] {
+            let endpoint = HttpEndpoint { method, path_pattern: path.to_string() };
+            assert_eq!(index.find_http_consumers(&endpoint), vec!["com.example.gateway.ProfileGateway::loadProfile"]);
+        }
+    }
+    
+    #[test]
+    fn test_webflux_functional_routes_attach_to_handler() {
+        let parser = JavaParser::new().unwrap();
+        let router_source = r#"
+package com.example.web;
+
+import com.example.handler.OrderHandler;
+import org.springframework.web.reactive.function.server.RouterFunction;
+import org.springframework.web.reactive.function.server.ServerResponse;
+import static org.springframework.web.reactive.function.server.RequestPredicates.POST;
+import static org.springframework.web.reactive.function.server.RouterFunctions.route;
+
+@Configuration
+public class OrderRouter {
+    @Bean
+    public RouterFunction<ServerResponse> orderRoutes(OrderHandler handler) {
+        return route()
+            .GET("/orders/{id}", handler::getOrder)
+            .build()
+            .and(route(POST("/orders"), handler::createOrder));
+    }
+    
+    @Bean
+    public RouterFunction<ServerResponse> healthRoute() {
+        return route().GET("/health", request -> ServerResponse.ok().build()).build();
+    }
+}
+"#;
+        let handler_source = r#"
+package com.example.handler;
+
+public class OrderHandler {
+    public Mono<ServerResponse> getOrder(ServerRequest request) {
+        return ServerResponse.ok().build();
+    }
+    
+    public Mono<ServerResponse> createOrder(ServerRequest request) {
+        return ServerResponse.ok().build();
+    }
+}
+"#;
+        
+        let router = parser.parse_file(router_source, Path::new("OrderRouter.java")).unwrap();
+        let handlers: Vec<_> = router.functional_routes.iter().map(|route| route.handler.as_str()).collect();
+        assert_eq!(handlers, vec![
+            "com.example.handler.OrderHandler::getOrder",
+            "com.example.handler.OrderHandler::createOrder",
+            "com.example.web.OrderRouter::healthRoute",
+        ]);
+        
+        let handler = parser.parse_file(handler_source, Path::new("OrderHandler.java")).unwrap();
+        let mut parsed_files = vec![router, handler];
+        crate::code_index::resolve_functional_routes(&mut parsed_files);
+        
+        let methods = &parsed_files[1].classes[0].methods;
+        let get_order = methods.iter().find(|m| m.name == "getOrder").unwrap();
+        let http = get_order.http_annotations.as_ref().unwrap();
+        assert_eq!(http.method, HttpMethod::GET);
+        assert_eq!(http.path, "orders/{id}");
+        assert_eq!(http.path_params, vec!["id".to_string()]);
         
-        // Check UPDATE
-        let update_method = &result.classes[0].methods[2];
-        assert_eq!(update_method.db_operations.len(), 1);
-        assert_eq!(update_method.db_operations[0].operation_type, DbOpType::Update);
-        assert_eq!(update_method.db_operations[0].table, "users");
+        let create_order = methods.iter().find(|m| m.name == "createOrder").unwrap();
+        assert_eq!(create_order.http_annotations.as_ref().unwrap().method, HttpMethod::POST);
         
-        // Check DELETE
-        let delete_method = &result.classes[0].methods[3];
-        assert_eq!(delete_method.db_operations.len(), 1);
-        assert_eq!(delete_method.db_operations[0].operation_type, DbOpType::Delete);
-        assert_eq!(delete_method.db_operations[0].table, "users");
+        // lambda 处理函数的路由附加到声明路由的方法
+        let health = parsed_files[0].classes[0].methods.iter().find(|m| m.name == "healthRoute").unwrap();
+        assert_eq!(health.http_annotations.as_ref().unwrap().path, "health");
     }
     
     #[test]
-    fn test_extract_redis_operations() {
+    fn test_reactive_controller_return_types() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            public class CacheService {
-                public void getFromCache() {
-                    String value = redisTemplate.opsForValue().get("user:123");
-                }
-                
-                public void setToCache() {
-                    redisTemplate.opsForValue().set("user:456", "data");
-                }
-                
-                public void deleteFromCache() {
-                    redisTemplate.delete("user:789");
-                }
-            }
-        "#;
-        
-        let result = parser.parse_file(source, Path::new("CacheService.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].methods.len(), 3);
-        
-        // Check GET
-        let get_method = &result.classes[0].methods[0];
-        assert_eq!(get_method.redis_operations.len(), 1);
-        assert_eq!(get_method.redis_operations[0].operation_type, RedisOpType::Get);
-        assert_eq!(get_method.redis_operations[0].key_pattern, "user:123");
-        
-        // Check SET
-        let set_method = &result.classes[0].methods[1];
-        assert_eq!(set_method.redis_operations.len(), 1);
-        assert_eq!(set_method.redis_operations[0].operation_type, RedisOpType::Set);
-        assert_eq!(set_method.redis_operations[0].key_pattern, "user:456");
+package com.example;
+
+@RestController
+@RequestMapping("/orders")
+public class OrderController {
+    @GetMapping("/{id}")
+    public Mono<Order> getOrder(@PathVariable String id) {
+        return orderService.find(id);
+    }
+    
+    @GetMapping
+    public Flux<Order> listOrders() {
+        return orderService.findAll();
+    }
+}
+"#;
         
-        // Check DELETE
-        let delete_method = &result.classes[0].methods[2];
-        assert_eq!(delete_method.redis_operations.len(), 1);
-        assert_eq!(delete_method.redis_operations[0].operation_type, RedisOpType::Delete);
-        assert_eq!(delete_method.redis_operations[0].key_pattern, "user:789");
+        let result = parser.parse_file(source, Path::new("OrderController.java")).unwrap();
+        let methods = &result.classes[0].methods;
+        let paths: Vec<_> = methods.iter()
+            .map(|m| m.http_annotations.as_ref().map(|http| http.path.as_str()))
+            .collect();
+        assert_eq!(paths, vec![Some("orders/{id}"), Some("orders")]);
     }
     
     #[test]
-    fn test_extract_method_calls() {
+    fn test_reflective_get_method_records_low_confidence_call() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            public class Calculator {
-                public int add(int a, int b) {
-                    return a + b;
-                }
-                
-                public int calculate() {
-                    int result = add(5, 3);
-                    System.out.println(result);
-                    return result;
-                }
-            }
-        "#;
-        
-        let result = parser.parse_file(source, Path::new("Calculator.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].methods.len(), 2);
-        
-        // Check method calls in calculate method
-        let calculate_method = &result.classes[0].methods[1];
-        assert!(calculate_method.calls.len() >= 2); // add and println
-        
-        let call_names: Vec<&str> = calculate_method.calls.iter()
-            .map(|c| c.target.as_str())
+package com.example;
+
+public class JobRunner {
+    public void run(Object job) throws Exception {
+        Class.forName("com.example.Job").getMethod("doWork").invoke(job);
+    }
+    
+    public void runNamed(Object job, String name) throws Exception {
+        job.getClass().getDeclaredMethod(name).invoke(job);
+    }
+    
+    public void runLiteral(Class<?> type) throws Exception {
+        JobRunner.class.getMethod("cleanup").invoke(this);
+        getClass().getDeclaredMethod("reset").invoke(this);
+        type.getMethod("start").invoke(null);
+    }
+}
+"#;
+        
+        let result = parser.parse_file(source, Path::new("JobRunner.java")).unwrap();
+        let methods = &result.classes[0].methods;
+        
+        let run = methods.iter().find(|m| m.name == "run").unwrap();
+        let reflective: Vec<_> = run.calls.iter().filter(|call| call.low_confidence).collect();
+        assert_eq!(reflective.len(), 1);
+        assert_eq!(reflective[0].target, "com.example.Job::doWork");
+        assert_eq!(reflective[0].line, 6);
+        
+        // 方法名不是字面量时无法推断目标
+        let run_named = methods.iter().find(|m| m.name == "runNamed").unwrap();
+        assert!(run_named.calls.iter().any(|call| call.low_confidence && call.target == UNKNOWN_CALL_TARGET));
+        assert!(run_named.calls.iter().all(|call| !call.low_confidence || call.target == UNKNOWN_CALL_TARGET));
+        
+        // 类字面量和 getClass() 解析到对应的类，无法确定接收者类时类名为 `<unknown>`
+        let run_literal = methods.iter().find(|m| m.name == "runLiteral").unwrap();
+        let targets: Vec<&str> = run_literal.calls.iter()
+            .filter(|call| call.low_confidence)
+            .map(|call| call.target.as_str())
             .collect();
-        assert!(call_names.contains(&"add"));
-        assert!(call_names.contains(&"println"));
+        assert_eq!(targets, vec!["com.example.JobRunner::cleanup", "com.example.JobRunner::reset", "<unknown>::start"]);
     }
     
     #[test]
-    fn test_extract_field_access_method_calls() {
+    fn test_application_yml_placeholder_uses_default_when_env_unset() {
+        let parser = JavaParser::new().unwrap();
+        let content = r#"
+spring:
+  application:
+    name: ${CODE_IMPACT_TEST_UNSET_APP_NAME:order-service}
+server:
+  servlet:
+    context-path: ${CODE_IMPACT_TEST_UNSET_CONTEXT_PATH:/api}/v1
+"#;
+        
+        let config = parser.parse_application_yml(content, Path::new("/workspace/order"));
+        assert_eq!(config.application_name.as_deref(), Some("order-service"));
+        assert_eq!(config.context_path.as_deref(), Some("/api/v1"));
+    }
+    
+    #[test]
+    fn test_extract_dubbo_service_provider() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            package com.example;
+            package com.hualala.order.service;
             
-            import com.hualala.shop.equipment.EquipmentManageExe;
+            import org.apache.dubbo.config.annotation.DubboService;
+            import com.hualala.order.api.OrderFacade;
             
-            public class TestController {
-                private EquipmentManageExe equipmentManageExe;
-                
-                public void testMethod() {
-                    equipmentManageExe.listExecuteSchedule("");
+            @DubboService(version = "1.0.0")
+            public class OrderFacadeImpl implements OrderFacade {
+                public Order queryOrder(String orderId) {
+                    return null;
                 }
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("TestController.java")).unwrap();
+        let result = parser.parse_file(source, Path::new("OrderFacadeImpl.java")).unwrap();
         assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].methods.len(), 1);
         
         let method = &result.classes[0].methods[0];
-        assert_eq!(method.calls.len(), 1);
-        // 应该解析为完整的类名::方法名格式
-        assert_eq!(method.calls[0].target, "com.hualala.shop.equipment.EquipmentManageExe::listExecuteSchedule");
+        assert_eq!(method.rpc_operations.len(), 1);
+        
+        let rpc = &method.rpc_operations[0];
+        assert_eq!(rpc.operation_type, RpcOpType::Provide);
+        assert_eq!(rpc.interface, "com.hualala.order.api.OrderFacade");
+        assert_eq!(rpc.method, "queryOrder");
+        assert_eq!(rpc.key(), "com.hualala.order.api.OrderFacade::queryOrder");
     }
     
     #[test]
-    fn test_extract_local_variable_method_calls() {
+    fn test_extract_dubbo_service_with_interface_class() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            package com.example;
+            package com.hualala.order.service;
             
-            public class TestLocalVariable {
-                public void go() {
-                    Foo foo = new Foo();
-                    foo.bar();
-                }
-            }
+            import com.hualala.order.api.OrderFacade;
             
-            class Foo {
-                public void bar() {
-                    System.out.println("bar called");
+            @DubboService(interfaceClass = OrderFacade.class)
+            public class OrderFacadeImpl extends BaseService implements OrderFacade, Serializable {
+                public Order queryOrder(String orderId) {
+                    return null;
                 }
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("TestLocalVariable.java")).unwrap();
-        assert_eq!(result.classes.len(), 2);
-        
-        // 查找 TestLocalVariable 类的 go 方法
-        let test_class = result.classes.iter()
-            .find(|c| c.name.contains("TestLocalVariable"))
-            .expect("Should find TestLocalVariable class");
-        
-        assert_eq!(test_class.methods.len(), 1);
-        let go_method = &test_class.methods[0];
-        assert_eq!(go_method.name, "go");
-        
-        // 验证方法调用
-        assert_eq!(go_method.calls.len(), 1, "Should have 1 method call");
+        let result = parser.parse_file(source, Path::new("OrderFacadeImpl.java")).unwrap();
+        let method = &result.classes[0].methods[0];
         
-        // 应该解析为 Foo::bar 或 com.example.Foo::bar
-        let call_target = &go_method.calls[0].target;
-        assert!(
-            call_target.contains("Foo::bar"),
-            "Should resolve to Foo::bar, got: {}",
-            call_target
-        );
+        // interfaceClass 指定时只注册该接口
+        assert_eq!(method.rpc_operations.len(), 1);
+        assert_eq!(method.rpc_operations[0].interface, "com.hualala.order.api.OrderFacade");
     }
     
     #[test]
-    fn test_extract_local_variable_with_imports() {
+    fn test_dubbo_provider_registers_public_methods_only() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            package com.example;
+            package com.hualala.order.service;
             
-            import com.hualala.shop.equipment.EquipmentManageExe;
+            import com.hualala.order.api.OrderFacade;
             
-            public class TestController {
-                public void testMethod() {
-                    // 本地变量使用导入的类
-                    EquipmentManageExe localExe = new EquipmentManageExe();
-                    localExe.listExecuteSchedule("");
+            @DubboService
+            public class OrderFacadeImpl implements OrderFacade {
+                public OrderFacadeImpl() {
+                    init();
+                }
+                
+                public Order queryOrder(String orderId) {
+                    return load(orderId);
+                }
+                
+                private Order load(String orderId) {
+                    return null;
+                }
+                
+                void init() {
                 }
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("TestController.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].methods.len(), 1);
-        
-        let method = &result.classes[0].methods[0];
-        assert_eq!(method.calls.len(), 1);
+        let result = parser.parse_file(source, Path::new("OrderFacadeImpl.java")).unwrap();
+        let providers: Vec<&str> = result.classes[0].methods.iter()
+            .filter(|method| method.rpc_operations.iter().any(|op| op.operation_type == RpcOpType::Provide))
+            .map(|method| method.name.as_str())
+            .collect();
         
-        // 应该解析为完整的导入类名::方法名格式
-        assert_eq!(
-            method.calls[0].target,
-            "com.hualala.shop.equipment.EquipmentManageExe::listExecuteSchedule"
-        );
+        // 私有辅助方法、包内方法和构造器不作为 RPC 端点
+        assert_eq!(providers, vec!["queryOrder"]);
     }
     
     #[test]
-    fn test_extract_mixed_field_and_local_variable_calls() {
+    fn test_extract_dubbo_reference_consumer() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            package com.example;
+            package com.hualala.shop.service;
             
-            import com.hualala.shop.equipment.EquipmentManageExe;
+            import org.apache.dubbo.config.annotation.DubboReference;
+            import com.alibaba.dubbo.config.annotation.Reference;
+            import com.hualala.order.api.OrderFacade;
+            import com.hualala.user.api.UserFacade;
+            import com.hualala.shop.repo.ShopRepository;
             
-            public class TestController {
-                private EquipmentManageExe fieldExe;
+            public class ShopService {
+                @DubboReference(version = "1.0.0")
+                private OrderFacade orderFacade;
                 
-                public void testMethod() {
-                    // 类字段调用
-                    fieldExe.listExecuteSchedule("field");
-                    
-                    // 本地变量调用
-                    EquipmentManageExe localExe = new EquipmentManageExe();
-                    localExe.listExecuteSchedule("local");
+                @Reference
+                private UserFacade userFacade;
+                
+                @Autowired
+                private ShopRepository shopRepository;
+                
+                public void checkout(String orderId) {
+                    orderFacade.queryOrder(orderId);
+                    userFacade.getUser(orderId);
+                    shopRepository.save(orderId);
                 }
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("TestController.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].methods.len(), 1);
-        
+        let result = parser.parse_file(source, Path::new("ShopService.java")).unwrap();
         let method = &result.classes[0].methods[0];
-        assert_eq!(method.calls.len(), 2, "Should have 2 method calls");
         
-        // 两个调用都应该解析为完整的类名::方法名格式
-        for call in &method.calls {
-            assert_eq!(
-                call.target,
-                "com.hualala.shop.equipment.EquipmentManageExe::listExecuteSchedule"
-            );
-        }
+        // 只有 Dubbo 引用字段的调用被识别为 RPC 消费
+        assert_eq!(method.rpc_operations.len(), 2);
+        assert!(method.rpc_operations.iter().all(|op| op.operation_type == RpcOpType::Consume));
+        
+        let keys: Vec<String> = method.rpc_operations.iter().map(|op| op.key()).collect();
+        assert!(keys.contains(&"com.hualala.order.api.OrderFacade::queryOrder".to_string()));
+        assert!(keys.contains(&"com.hualala.user.api.UserFacade::getUser".to_string()));
     }
     
     #[test]
-    fn test_extract_self_type_local_variable() {
+    fn test_extract_bean_method_dependencies() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            package com.example;
+            package com.example.config;
             
-            public class Builder {
-                private String name;
+            import org.springframework.context.annotation.Bean;
+            import org.springframework.context.annotation.Configuration;
+            
+            @Configuration
+            public class DataConfig {
+                @Bean
+                public DataSource dataSource() {
+                    return new HikariDataSource();
+                }
                 
-                public Builder setName(String name) {
-                    this.name = name;
-                    return this;
+                @Bean
+                public JdbcTemplate jdbcTemplate(DataSource dataSource) {
+                    return new JdbcTemplate(dataSource);
                 }
                 
-                public Builder build() {
-                    // 本地变量类型为当前类自身
-                    Builder builder = new Builder();
-                    builder.setName("test");
-                    return builder;
+                @Bean
+                public TransactionManager transactionManager() {
+                    return new DataSourceTransactionManager(dataSource());
                 }
                 
-                public static Builder createBuilder() {
-                    // 静态方法中的本地变量
-                    Builder instance = new Builder();
-                    instance.setName("static");
-                    return instance;
+                public void helper() {
+                    dataSource();
                 }
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("Builder.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        
-        let builder_class = &result.classes[0];
-        assert_eq!(builder_class.name, "com.example.Builder");
-        
-        // 测试 build() 方法
-        let build_method = builder_class.methods.iter()
-            .find(|m| m.name == "build")
-            .expect("Should find build method");
+        let result = parser.parse_file(source, Path::new("DataConfig.java")).unwrap();
+        let methods = &result.classes[0].methods;
+        let method = |name: &str| methods.iter().find(|m| m.name == name).unwrap();
+        let targets = |name: &str| method(name).calls.iter().map(|c| c.target.clone()).collect::<Vec<_>>();
         
-        assert_eq!(build_method.calls.len(), 1, "build() should have 1 method call");
-        assert!(
-            build_method.calls[0].target.contains("com.example.Builder::setName"),
-            "Should resolve to com.example.Builder::setName, got: {}",
-            build_method.calls[0].target
-        );
+        assert!(method("dataSource").is_bean);
+        assert!(method("jdbcTemplate").is_bean);
+        assert!(!method("helper").is_bean);
         
-        // 测试 createBuilder() 静态方法
-        let create_method = builder_class.methods.iter()
-            .find(|m| m.name == "createBuilder")
-            .expect("Should find createBuilder method");
+        // 参数注入和直接调用都记录为对 dataSource 的调用
+        assert!(targets("jdbcTemplate").contains(&"com.example.config.DataConfig::dataSource".to_string()));
+        assert!(targets("transactionManager").contains(&"com.example.config.DataConfig::dataSource".to_string()));
         
-        assert_eq!(create_method.calls.len(), 1, "createBuilder() should have 1 method call");
-        assert!(
-            create_method.calls[0].target.contains("com.example.Builder::setName"),
-            "Should resolve to com.example.Builder::setName in static method, got: {}",
-            create_method.calls[0].target
-        );
+        // 非 @Bean 方法的调用保持不变
+        assert_eq!(targets("helper"), vec!["dataSource".to_string()]);
     }
     
     #[test]
-    fn test_extract_various_method_call_patterns() {
+    fn test_extract_conditional_property_and_feature_flag_calls() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            public class TestService {
-                private UserService userService;
+            package com.example;
+            
+            @ConditionalOnProperty(prefix = "feature", name = {"search", "legacy"}, havingValue = "true")
+            public class SearchService {
+                private Unleash unleash;
+                private Indexer indexer;
                 
-                public void testMethod() {
-                    // Direct method call
-                    localMethod();
-                    
-                    // Field access method call
-                    userService.findUser();
-                    
-                    // Chained method call
-                    userService.getRepository().save();
-                    
-                    // Static method call
-                    System.out.println("test");
-                    
-                    // Method call with multiple arguments
-                    userService.updateUser(1, "name");
+                public void search() {
+                    if (!unleash.isEnabled("fast-index")) {
+                        indexer.slowPath();
+                    } else {
+                        indexer.fastPath();
+                    }
+                    indexer.flush();
                 }
                 
-                private void localMethod() {
+                @ConditionalOnProperty(name = "search.reindex")
+                public void reindex() {
                 }
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("TestService.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].methods.len(), 2);
-        
-        let test_method = &result.classes[0].methods[0];
-        let call_names: Vec<&str> = test_method.calls.iter()
-            .map(|c| c.target.as_str())
-            .collect();
-        
-        // Verify all method calls are captured correctly
-        assert!(call_names.contains(&"localMethod"), "Should find localMethod");
-        assert!(call_names.contains(&"findUser") || call_names.contains(&"UserService::findUser"), "Should find findUser");
-        assert!(call_names.contains(&"getRepository") || call_names.contains(&"UserService::getRepository"), "Should find getRepository");
-        assert!(call_names.contains(&"save"), "Should find save");
-        assert!(call_names.contains(&"println") || call_names.contains(&"System::println"), "Should find println");
-        assert!(call_names.contains(&"updateUser") || call_names.contains(&"UserService::updateUser"), "Should find updateUser");
+        let result = parser.parse_file(source, Path::new("SearchService.java")).unwrap();
+        let method = |name: &str| result.classes[0].methods.iter().find(|m| m.name == name).unwrap();
+        
+        // 类级别开关作用于未单独声明开关的方法
+        assert_eq!(method("search").conditional.as_deref(), Some("feature.search"));
+        assert_eq!(method("reindex").conditional.as_deref(), Some("search.reindex"));
+        
+        let condition = |target: &str| method("search").calls.iter()
+            .find(|c| c.target == target)
+            .unwrap()
+            .condition
+            .clone();
+        assert_eq!(condition("com.example.Indexer::slowPath"), Some("fast-index".to_string()));
+        assert_eq!(condition("com.example.Indexer::fastPath"), Some("fast-index".to_string()));
+        assert_eq!(condition("com.example.Indexer::flush"), None);
+        assert_eq!(condition("com.example.Unleash::isEnabled"), None);
     }
     
     #[test]
-    fn test_extract_feign_client_annotation() {
+    fn test_extract_batch_job_and_step_components() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            package com.hualala.shop.domain.feign;
+            package com.example.batch;
             
-            import org.springframework.cloud.openfeign.FeignClient;
-            import org.springframework.web.bind.annotation.PostMapping;
-            import org.springframework.web.bind.annotation.RequestBody;
+            import com.example.batch.io.OrderWriter;
             
-            @FeignClient(value = "hll-basic-info-api", path = "/hll-basic-info-api")
-            public interface BasicInfoFeign {
-                @PostMapping("/feign/shop/copy/info")
-                GoodsResponse getGoodsInfo(@RequestBody GoodsInfoRequest request);
+            @Configuration
+            public class ImportJobConfig {
+                @Autowired
+                private OrderWriter orderWriter;
+                
+                @Bean
+                public Job importJob(JobRepository jobRepository, Step importStep) {
+                    return new JobBuilder("importOrders", jobRepository).start(importStep).build();
+                }
+                
+                @Bean
+                public Job cleanupJob(Step cleanupStep) {
+                    return jobs.get("cleanup").start(cleanupStep).build();
+                }
+                
+                @Bean
+                public Step importStep(ItemReader<Order> reader) {
+                    return steps.get("importStep").<Order, Order>chunk(10)
+                        .reader(reader)
+                        .processor(new OrderProcessor())
+                        .writer(orderWriter)
+                        .build();
+                }
+                
+                @Bean
+                public Step cleanupStep(CleanupTasklet tasklet) {
+                    return steps.get("cleanupStep").tasklet(tasklet).build();
+                }
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("BasicInfoFeign.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].name, "com.hualala.shop.domain.feign.BasicInfoFeign");
-        assert_eq!(result.classes[0].methods.len(), 1);
+        let result = parser.parse_file(source, Path::new("ImportJobConfig.java")).unwrap();
+        let method = |name: &str| result.classes[0].methods.iter().find(|m| m.name == name).unwrap();
+        let targets = |name: &str| method(name).calls.iter().map(|c| c.target.clone()).collect::<Vec<_>>();
         
-        let method = &result.classes[0].methods[0];
-        assert_eq!(method.name, "getGoodsInfo");
-        assert!(method.http_annotations.is_some(), "HTTP annotation should be present");
+        assert_eq!(method("importJob").batch_job.as_deref(), Some("importOrders"));
+        assert_eq!(method("cleanupJob").batch_job.as_deref(), Some("cleanup"));
+        assert_eq!(method("importStep").batch_job, None);
         
-        let http = method.http_annotations.as_ref().unwrap();
-        assert_eq!(http.method, HttpMethod::POST);
-        // 应该组合为：service_name/base_path/method_path
-        assert_eq!(http.path, "hll-basic-info-api/hll-basic-info-api/feign/shop/copy/info");
+        // 作业通过参数注入依赖步骤
+        assert!(targets("importJob").contains(&"com.example.batch.ImportJobConfig::importStep".to_string()));
+        
+        // 接口类型的 reader 无法确定实现类
+        let import_step = targets("importStep");
+        assert!(import_step.contains(&"com.example.batch.OrderProcessor::process".to_string()));
+        assert!(import_step.contains(&"com.example.batch.io.OrderWriter::write".to_string()));
+        assert!(!import_step.iter().any(|t| t.ends_with("::read")));
+        
+        let tasklet_call = method("cleanupStep").calls.iter()
+            .find(|c| c.target == "com.example.batch.CleanupTasklet::execute")
+            .unwrap();
+        assert_eq!(tasklet_call.line, 32);
     }
     
     #[test]
-    fn test_extract_feign_client_without_base_path() {
+    fn test_bean_annotations_ignored_outside_configuration() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            package com.example;
+            public class Factory {
+                @Bean
+                public Client client(Pool pool) { return new Client(pool); }
+                
+                @Bean
+                public Pool pool() { return new Pool(); }
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("Factory.java")).unwrap();
+        // 只有构造器调用，没有 Bean 依赖产生的调用
+        assert!(result.classes[0].methods.iter().all(|m| !m.is_bean && m.calls.iter().all(|c| !c.target.contains("::") || c.target.ends_with("::<init>"))));
+    }
+    
+    #[test]
+    fn test_extract_thrift_provider_and_client_calls() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            package com.example.shop;
             
-            import org.springframework.cloud.openfeign.FeignClient;
-            import org.springframework.web.bind.annotation.GetMapping;
+            import com.example.order.thrift.OrderService;
+            import com.example.user.thrift.UserService;
             
-            @FeignClient(value = "user-service")
-            public interface UserClient {
-                @GetMapping("/api/users")
-                User getUser();
+            public class ShopHandler implements UserService.Iface {
+                private OrderService.Client orderClient;
+                
+                public User getUser(long id) {
+                    orderClient.getOrder(id);
+                    return null;
+                }
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("UserClient.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].methods.len(), 1);
+        let result = parser.parse_file(source, Path::new("ShopHandler.java")).unwrap();
+        assert_eq!(result.classes[0].implements, vec!["com.example.user.thrift.UserService.Iface".to_string()]);
         
         let method = &result.classes[0].methods[0];
-        assert!(method.http_annotations.is_some());
-        
-        let http = method.http_annotations.as_ref().unwrap();
-        assert_eq!(http.method, HttpMethod::GET);
-        // 没有 base_path 时，应该是：service_name/method_path
-        assert_eq!(http.path, "user-service/api/users");
+        let operations: Vec<(RpcOpType, String)> = method.thrift_operations.iter()
+            .map(|op| (op.operation_type.clone(), op.key()))
+            .collect();
+        assert_eq!(operations, vec![
+            (RpcOpType::Provide, "com.example.user.thrift.UserService::getUser".to_string()),
+            (RpcOpType::Consume, "com.example.order.thrift.OrderService::getOrder".to_string()),
+        ]);
     }
     
     #[test]
-    fn test_extract_static_method_calls() {
+    fn test_extract_graphql_query_and_mutation_mappings() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            package com.example;
+            package com.example.graphql;
             
-            import org.apache.commons.lang3.StringUtils;
-            import java.util.Collections;
+            import org.springframework.graphql.data.method.annotation.QueryMapping;
+            import org.springframework.graphql.data.method.annotation.MutationMapping;
             
-            public class TestStaticMethod {
-                private UserService userService;
+            @Controller
+            public class BookController {
+                @QueryMapping
+                public Book bookById(@Argument String id) {
+                    return bookService.find(id);
+                }
                 
-                public void testMethod() {
-                    // 静态方法调用 - 使用导入的类
-                    String result1 = StringUtils.isEmpty("test");
-                    
-                    // 静态方法调用 - 使用导入的类
-                    List<String> list = Collections.emptyList();
-                    
-                    // 实例方法调用
-                    userService.findUser();
-                    
-                    // 链式调用
-                    Collections.emptyList().stream().filter(x -> x != null);
+                @QueryMapping(name = "allBooks")
+                public List<Book> books() {
+                    return bookService.findAll();
+                }
+                
+                @org.springframework.graphql.data.method.annotation.MutationMapping
+                public Book addBook(@Argument BookInput input) {
+                    return bookService.save(input);
+                }
+                
+                public void helper() {
                 }
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("TestStaticMethod.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].methods.len(), 1);
-        
-        let method = &result.classes[0].methods[0];
-        let call_targets: Vec<&str> = method.calls.iter()
-            .map(|c| c.target.as_str())
-            .collect();
+        let result = parser.parse_file(source, Path::new("BookController.java")).unwrap();
+        let methods = &result.classes[0].methods;
         
-        // 验证静态方法调用被正确识别为 ClassName::methodName
-        assert!(
-            call_targets.contains(&"org.apache.commons.lang3.StringUtils::isEmpty"),
-            "Should find StringUtils::isEmpty, got: {:?}", call_targets
-        );
-        assert!(
-            call_targets.iter().any(|t| t.contains("Collections::emptyList")),
-            "Should find Collections::emptyList, got: {:?}", call_targets
-        );
+        let resolver = |name: &str| methods.iter()
+            .find(|m| m.name == name)
+            .and_then(|m| m.graphql_resolver.clone());
         
-        // 验证实例方法调用仍然正常工作
-        assert!(
-            call_targets.iter().any(|t| t.contains("UserService::findUser")),
-            "Should find UserService::findUser (with or without package), got: {:?}", call_targets
+        assert_eq!(
+            resolver("bookById"),
+            Some(GraphQLResolver { type_name: "Query".to_string(), field: "bookById".to_string() })
         );
+        assert_eq!(resolver("books").unwrap().key(), "Query.allBooks");
+        assert_eq!(resolver("addBook").unwrap().key(), "Mutation.addBook");
+        assert_eq!(resolver("helper"), None);
     }
     
     #[test]
-    fn test_extract_feign_client_with_name_attribute() {
+    fn test_extract_graphql_schema_mapping() {
         let parser = JavaParser::new().unwrap();
         let source = r#"
-            package com.example;
-            
-            import org.springframework.cloud.openfeign.FeignClient;
-            import org.springframework.web.bind.annotation.PutMapping;
-            
-            @FeignClient(name = "order-service", path = "/orders")
-            public interface OrderClient {
-                @PutMapping("/update")
-                void updateOrder();
+            @Controller
+            @SchemaMapping(typeName = "Book")
+            public class BookFieldsController {
+                @SchemaMapping
+                public Author author(Book book) {
+                    return null;
+                }
+                
+                @SchemaMapping(typeName = "Author", field = "books")
+                public List<Book> authorBooks(Author author) {
+                    return null;
+                }
             }
         "#;
         
-        let result = parser.parse_file(source, Path::new("OrderClient.java")).unwrap();
-        assert_eq!(result.classes.len(), 1);
-        assert_eq!(result.classes[0].methods.len(), 1);
+        let result = parser.parse_file(source, Path::new("BookFieldsController.java")).unwrap();
+        let methods = &result.classes[0].methods;
         
-        let method = &result.classes[0].methods[0];
-        assert!(method.http_annotations.is_some());
+        // 方法未指定 typeName 时使用类级别的 typeName
+        assert_eq!(methods[0].graphql_resolver.as_ref().unwrap().key(), "Book.author");
+        assert_eq!(methods[1].graphql_resolver.as_ref().unwrap().key(), "Author.books");
+    }
+    
+    #[test]
+    fn test_extract_api_operation() {
+        let parser = JavaParser::new().unwrap();
+        let source = r#"
+            @RestController
+            public class UserController {
+                @Operation(summary = "Get user", description = "Looks up a user by id")
+                @GetMapping("/users/{id}")
+                public User getUser(Long id) {
+                    return null;
+                }
+                
+                @ApiOperation(value = "Create user", notes = "Requires admin")
+                @PostMapping("/users")
+                public User createUser(User user) {
+                    return null;
+                }
+                
+                @ApiOperation("Delete user")
+                @DeleteMapping("/users/{id}")
+                public void deleteUser(Long id) {
+                }
+                
+                @GetMapping("/health")
+                public String health() {
+                    return "ok";
+                }
+            }
+        "#;
         
-        let http = method.http_annotations.as_ref().unwrap();
-        assert_eq!(http.method, HttpMethod::PUT);
-        // 使用 name 属性时，应该正常工作
-        assert_eq!(http.path, "order-service/orders/update");
+        let result = parser.parse_file(source, Path::new("UserController.java")).unwrap();
+        let operation = |name: &str| result.classes[0].methods.iter()
+            .find(|m| m.name == name)
+            .unwrap()
+            .api_operation
+            .clone();
+        
+        assert_eq!(operation("getUser"), Some(ApiOperation {
+            summary: Some("Get user".to_string()),
+            description: Some("Looks up a user by id".to_string()),
+        }));
+        assert_eq!(operation("createUser"), Some(ApiOperation {
+            summary: Some("Create user".to_string()),
+            description: Some("Requires admin".to_string()),
+        }));
+        assert_eq!(operation("deleteUser").unwrap().summary.as_deref(), Some("Delete user"));
+        assert_eq!(operation("health"), None);
     }
 }
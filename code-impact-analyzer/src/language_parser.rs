@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 use crate::errors::ParseError;
 use crate::types::*;
 use serde::{Deserialize, Serialize};
+use regex::Regex;
 
 /// 语言解析器 trait
 /// 
@@ -15,6 +16,11 @@ pub trait LanguageParser: Send + Sync {
     
     /// 解析源文件
     fn parse_file(&self, content: &str, file_path: &Path) -> Result<ParsedFile, ParseError>;
+    
+    /// 设置提取调用时使用的过滤器
+    /// 
+    /// 默认实现忽略过滤器，不支持过滤的解析器无需覆盖
+    fn set_call_filter(&mut self, _filter: CallFilter) {}
 }
 
 /// 解析后的文件信息
@@ -25,6 +31,51 @@ pub struct ParsedFile {
     pub classes: Vec<ClassInfo>,
     pub functions: Vec<FunctionInfo>,
     pub imports: Vec<Import>,
+    /// 文件中声明的字符串常量（`static final String`），用于解析以常量引用的 Kafka topic
+    #[serde(default)]
+    pub string_constants: Vec<StringConstant>,
+    /// 文件中声明的 WebFlux 函数式路由，索引时为处理方法附加 HTTP 接口信息
+    #[serde(default)]
+    pub functional_routes: Vec<FunctionalRoute>,
+    /// 语法树中错误节点的位置（行号、列号，均从 1 开始）
+    /// 
+    /// tree-sitter 会从错误中恢复，其余部分照常提取；非空时索引会记录警告
+    #[serde(default)]
+    pub syntax_errors: Vec<(usize, usize)>,
+}
+
+impl ParsedFile {
+    /// 获取文件中的语法错误
+    /// 
+    /// # Returns
+    /// * 存在错误节点时返回携带各错误位置的 `ParseError::SyntaxErrors`，否则返回 `None`
+    pub fn syntax_error(&self) -> Option<ParseError> {
+        if self.syntax_errors.is_empty() {
+            return None;
+        }
+        Some(ParseError::SyntaxErrors {
+            path: self.file_path.clone(),
+            locations: self.syntax_errors.clone(),
+        })
+    }
+}
+
+/// 字符串常量
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StringConstant {
+    /// 全限定名，嵌套类包含外层类名，如 `com.example.Topics.Order.CREATED`
+    pub name: String,
+    /// 常量值
+    pub value: String,
+}
+
+/// WebFlux 函数式路由（如 `route().GET("/orders/{id}", handler::get)`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionalRoute {
+    /// 路由对应的 HTTP 接口，路径已包含应用名和 context-path
+    pub annotation: HttpAnnotation,
+    /// 处理方法的全限定名；处理函数为 lambda 时为声明路由的方法
+    pub handler: String,
 }
 
 /// 类信息
@@ -37,10 +88,26 @@ pub struct ClassInfo {
     pub is_interface: bool,
     /// 实现的接口列表（完整类名）
     pub implements: Vec<String>,
+    /// 继承的父类（完整类名）
+    #[serde(default)]
+    pub extends: Option<String>,
+}
+
+/// 方法的可见性，按可见范围从小到大排序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// 只在类型内可见：Java/C# `private`、Rust 没有 `pub` 的函数
+    Private,
+    /// 包或模块内可见：Java 无修饰符和 `protected`、Rust `pub(crate)`/`pub(super)`、C# `internal`/`protected`
+    Package,
+    /// 公开：Java/C# `public`、接口方法、Rust `pub` 及 trait 方法
+    #[default]
+    Public,
 }
 
 /// 方法信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MethodInfo {
     pub name: String,
     pub full_qualified_name: String,
@@ -51,10 +118,77 @@ pub struct MethodInfo {
     pub kafka_operations: Vec<KafkaOperation>,
     pub db_operations: Vec<DbOperation>,
     pub redis_operations: Vec<RedisOperation>,
+    #[serde(default)]
+    pub rpc_operations: Vec<RpcOperation>,
+    #[serde(default)]
+    pub thrift_operations: Vec<ThriftOperation>,
+    /// 是否为 Spring `@Configuration` 类中的 `@Bean` 工厂方法
+    #[serde(default)]
+    pub is_bean: bool,
+    /// 控制该方法是否生效的特性开关（如 `@ConditionalOnProperty` 的属性名）
+    #[serde(default)]
+    pub conditional: Option<String>,
+    #[serde(default)]
+    pub graphql_resolver: Option<GraphQLResolver>,
+    /// Spring Batch 作业名（返回 `Job` 的 `@Bean` 方法），作业是批处理服务的入口点
+    #[serde(default)]
+    pub batch_job: Option<String>,
+    /// OpenAPI/Swagger 接口文档注解，附加到该方法提供的 HTTP 端点节点上
+    #[serde(default)]
+    pub api_operation: Option<ApiOperation>,
+    /// 是否为 `@Scheduled` 定时任务方法（入口点）
+    #[serde(default)]
+    pub scheduled: bool,
+    /// 是否为纯委托方法：方法体只有一条调用语句（或返回一次调用的结果）
+    #[serde(default)]
+    pub is_delegation: bool,
+    /// 方法上的容错注解：`retryable`（`@Retryable`/`@Retry`）或 `circuit_breaker`（`@CircuitBreaker`）
+    #[serde(default)]
+    pub resilience: Option<String>,
+    /// 方法起始行的起始列和结束行的结束列（字节偏移，从 0 开始）
+    /// 
+    /// 与 `line_range` 一起确定方法的精确范围，用于区分写在同一行的多个方法
+    #[serde(default)]
+    pub column_range: Option<(usize, usize)>,
+    /// 方法的可见性，旧索引中没有记录时视为公开
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// 方法由多段不相邻的代码组成时 `line_range` 之外的其余各段，如 `<clinit>` 的各段静态初始化代码
+    #[serde(default)]
+    pub extra_line_ranges: Vec<(usize, usize)>,
+    /// 方法在 `http_annotations` 之外发起的其余 HTTP 客户端调用，如同一方法中的多个 `RestClient` 请求
+    #[serde(default)]
+    pub extra_http_annotations: Vec<HttpAnnotation>,
+}
+
+impl MethodInfo {
+    /// 创建方法信息，方法名取全限定名最后一个 `::` 之后的部分，其余字段为默认值
+    /// 
+    /// # Arguments
+    /// * `full_qualified_name` - 方法的全限定名，如 `com.example.UserService::getUser`
+    /// * `file_path` - 方法所在文件
+    pub fn new(full_qualified_name: &str, file_path: impl Into<PathBuf>) -> Self {
+        MethodInfo {
+            name: full_qualified_name.rsplit("::").next().unwrap_or(full_qualified_name).to_string(),
+            full_qualified_name: full_qualified_name.to_string(),
+            file_path: file_path.into(),
+            ..Default::default()
+        }
+    }
+    
+    /// 获取方法代码所在的各段行范围，`line_range` 在前
+    pub fn line_ranges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::once(self.line_range).chain(self.extra_line_ranges.iter().copied())
+    }
+    
+    /// 获取方法上的全部 HTTP 注解和客户端调用，`http_annotations` 在前
+    pub fn all_http_annotations(&self) -> impl Iterator<Item = &HttpAnnotation> + '_ {
+        self.http_annotations.iter().chain(&self.extra_http_annotations)
+    }
 }
 
 /// 函数信息（用于非面向对象语言如 Rust）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FunctionInfo {
     pub name: String,
     pub full_qualified_name: String,
@@ -65,18 +199,230 @@ pub struct FunctionInfo {
     pub kafka_operations: Vec<KafkaOperation>,
     pub db_operations: Vec<DbOperation>,
     pub redis_operations: Vec<RedisOperation>,
+    #[serde(default)]
+    pub rpc_operations: Vec<RpcOperation>,
+    #[serde(default)]
+    pub thrift_operations: Vec<ThriftOperation>,
+    #[serde(default)]
+    pub graphql_resolver: Option<GraphQLResolver>,
+    /// 函数起始行的起始列和结束行的结束列（字节偏移，从 0 开始）
+    #[serde(default)]
+    pub column_range: Option<(usize, usize)>,
+    /// 函数的可见性
+    #[serde(default)]
+    pub visibility: Visibility,
 }
 
+/// 无法静态确定的调用目标
+/// 
+/// 反射调用按字面量方法名推断时记为 `<unknown>::<方法名>`，方法名也无法确定时记为 `<unknown>`
+pub const UNKNOWN_CALL_TARGET: &str = "<unknown>";
+
 /// 方法调用信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MethodCall {
     pub target: String,
     pub line: usize,
+    /// 调用所在分支的特性开关名（如 `if (flags.isEnabled("x"))` 中的 `x`）
+    #[serde(default)]
+    pub condition: Option<String>,
+    /// 调用目标无法静态确定（如反射调用按方法名推断为 `<unknown>::<name>`）
+    #[serde(default)]
+    pub low_confidence: bool,
+}
+
+/// 调用过滤器
+/// 
+/// 在提取方法调用时丢弃方法名匹配任一正则的调用，用于抑制 getter/setter、
+/// builder 等噪声调用边。只影响调用边，被调用的方法本身仍会被索引。
+#[derive(Debug, Clone, Default)]
+pub struct CallFilter {
+    patterns: Vec<Regex>,
+}
+
+impl CallFilter {
+    /// 访问器方法名模式（getX / setX / isX）
+    pub const ACCESSOR_PATTERN: &'static str = r"^(get|set|is)[A-Z]";
+    
+    /// 根据正则表达式列表创建过滤器
+    /// 
+    /// # Arguments
+    /// * `patterns` - 匹配方法名（不含类名/路径前缀）的正则表达式
+    /// 
+    /// # Returns
+    /// * `Result<Self, regex::Error>` - 过滤器或正则编译错误
+    pub fn new<I, S>(patterns: I) -> Result<Self, regex::Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let patterns = patterns
+            .into_iter()
+            .map(|p| Regex::new(p.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { patterns })
+    }
+    
+    /// 创建忽略访问器调用的过滤器
+    pub fn accessors() -> Self {
+        Self::new([Self::ACCESSOR_PATTERN]).expect("accessor pattern is valid")
+    }
+    
+    /// 过滤器是否为空（不丢弃任何调用）
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+    
+    /// 判断调用目标是否应被丢弃
+    /// 
+    /// # Arguments
+    /// * `target` - 调用目标，如 `com.example.User::getName`、`user.get_name` 或 `getName`
+    pub fn is_ignored(&self, target: &str) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let name = target.rsplit("::").next().unwrap_or(target);
+        let name = name.rsplit('.').next().unwrap_or(name);
+        self.patterns.iter().any(|p| p.is_match(name))
+    }
+}
+
+/// 先序遍历语法树节点（含根节点自身）
+/// 
+/// 基于 tree-sitter 游标迭代实现，不占用与嵌套深度成正比的调用栈，
+/// 深层嵌套的生成代码（超长 builder 链等）也不会导致栈溢出。
+/// 
+/// # Arguments
+/// * `root` - 遍历起点
+/// * `visit` - 访问回调，返回 `false` 时不进入该节点的子节点
+pub fn walk_tree<'t>(root: tree_sitter::Node<'t>, mut visit: impl FnMut(tree_sitter::Node<'t>) -> bool) {
+    let mut cursor = root.walk();
+    let mut depth = 0usize;
+    
+    loop {
+        if visit(cursor.node()) && cursor.goto_first_child() {
+            depth += 1;
+            continue;
+        }
+        
+        // 回溯到最近一个还有后继兄弟节点的祖先
+        loop {
+            if depth == 0 {
+                return;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+            cursor.goto_parent();
+            depth -= 1;
+        }
+    }
+}
+
+/// 收集语法树中的错误节点
+/// 
+/// 收集所有 `ERROR` 节点和缺失节点（解析器为补全语法插入的节点）的起始位置，
+/// 只进入包含错误的子树
+/// 
+/// # Arguments
+/// * `tree` - 语法树
+/// 
+/// # Returns
+/// * 各错误节点的起始位置（行号、列号，均从 1 开始），没有错误时为空
+pub fn collect_syntax_errors(tree: &tree_sitter::Tree) -> Vec<(usize, usize)> {
+    let root = tree.root_node();
+    if !root.has_error() {
+        return Vec::new();
+    }
+    
+    let mut locations = Vec::new();
+    walk_tree(root, |node| {
+        if node.is_error() || node.is_missing() {
+            let position = node.start_position();
+            locations.push((position.row + 1, position.column + 1));
+            return false;
+        }
+        node.has_error()
+    });
+    locations
+}
+
+/// 按路径模式强制指定或禁用文件的语言解析器
+/// 
+/// 用于扩展名与实际语言不符的文件（如实际为 Java 的 `.txt`），或需要跳过的文件（如 vendored 的 `.rs`）。
+/// 模式为 glob：`*` 匹配除 `/` 外的任意字符，`**` 匹配任意多级目录，`?` 匹配单个字符；
+/// 模式从任意一级目录开始与路径末尾匹配，因此 `*.txt` 匹配所有 `.txt` 文件，
+/// `vendor/**/*.rs` 匹配任意 `vendor` 目录下的 `.rs` 文件
+#[derive(Debug, Clone)]
+pub struct LanguageOverride {
+    /// 路径 glob 模式
+    pub pattern: String,
+    /// 强制使用的解析器语言名，为 `None` 时跳过匹配的文件
+    pub language: Option<String>,
+    matcher: Regex,
+}
+
+impl LanguageOverride {
+    /// 创建语言覆盖规则
+    /// 
+    /// # Arguments
+    /// * `pattern` - 路径 glob 模式
+    /// * `language` - 解析器语言名（与 `LanguageParser::language_name` 相同），`None` 表示跳过
+    pub fn new(pattern: &str, language: Option<String>) -> Self {
+        let mut regex = String::from(r"(^|/)");
+        let mut chars = pattern.trim_start_matches("./").chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    // `**/` 可以匹配零级目录
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        regex.push_str("(.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        regex.push('$');
+        
+        Self {
+            pattern: pattern.to_string(),
+            language,
+            matcher: Regex::new(&regex).expect("escaped glob is a valid regex"),
+        }
+    }
+    
+    /// 判断路径是否匹配该规则的模式
+    pub fn matches(&self, file_path: &Path) -> bool {
+        self.matcher.is_match(&file_path.to_string_lossy().replace('\\', "/"))
+    }
+}
+
+impl std::str::FromStr for LanguageOverride {
+    type Err = String;
+    
+    /// 解析 `GLOB=LANGUAGE` 形式的规则，语言为 `none` 时跳过匹配的文件
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.rsplit_once('=') {
+            Some((pattern, language)) if !pattern.trim().is_empty() && !language.trim().is_empty() => {
+                let language = language.trim();
+                let language = (!language.eq_ignore_ascii_case("none")).then(|| language.to_string());
+                Ok(Self::new(pattern.trim(), language))
+            }
+            _ => Err(format!("expected GLOB=LANGUAGE, got '{}'", s)),
+        }
+    }
 }
 
 /// 语言识别器
 /// 
-/// 基于文件扩展名识别编程语言类型
+/// 基于已注册解析器声明的文件扩展名识别编程语言类型，
+/// 不维护独立的扩展名表，新增解析器后其文件会自动被识别。
 pub struct LanguageDetector;
 
 impl LanguageDetector {
@@ -84,56 +430,165 @@ impl LanguageDetector {
     /// 
     /// # Arguments
     /// * `file_path` - 文件路径
+    /// * `parsers` - 已注册的语言解析器列表
     /// 
     /// # Returns
-    /// * `Some(language_name)` - 如果识别成功
+    /// * `Some(language_name)` - 负责该文件的解析器的语言名称
     /// * `None` - 如果无法识别
-    pub fn detect_language(file_path: &Path) -> Option<&'static str> {
-        file_path
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .and_then(|ext| match ext {
-                "java" => Some("java"),
-                "rs" => Some("rust"),
-                _ => None,
-            })
+    pub fn detect_language<'a>(
+        file_path: &Path,
+        parsers: &'a [Box<dyn LanguageParser>],
+    ) -> Option<&'a str> {
+        Self::find_parser(file_path, parsers).map(|p| p.language_name())
     }
     
-    /// 检查文件是否为支持的语言
-    pub fn is_supported(file_path: &Path) -> bool {
-        Self::detect_language(file_path).is_some()
+    /// 检查文件是否有已注册的解析器支持
+    pub fn is_supported(file_path: &Path, parsers: &[Box<dyn LanguageParser>]) -> bool {
+        Self::find_parser(file_path, parsers).is_some()
+    }
+    
+    /// 根据已注册解析器声明的扩展名查找负责该文件的解析器
+    /// 
+    /// 靠前的解析器优先，因此自定义解析器可以覆盖内置解析器的扩展名。
+    /// 
+    /// # Arguments
+    /// * `file_path` - 文件路径
+    /// * `parsers` - 已注册的语言解析器列表
+    /// 
+    /// # Returns
+    /// * `Some(parser)` - 声明了该扩展名的第一个解析器
+    /// * `None` - 没有解析器支持该扩展名
+    pub fn find_parser<'a>(
+        file_path: &Path,
+        parsers: &'a [Box<dyn LanguageParser>],
+    ) -> Option<&'a dyn LanguageParser> {
+        let ext = file_path.extension()?.to_str()?;
+        parsers
+            .iter()
+            .find(|p| p.file_extensions().iter().any(|e| e.eq_ignore_ascii_case(ext)))
+            .map(|p| p.as_ref())
+    }
+    
+    /// 按语言覆盖规则查找负责该文件的解析器
+    /// 
+    /// 第一条匹配路径的规则决定结果：指定语言时使用该语言的解析器，`none` 时跳过文件；
+    /// 没有规则匹配时按扩展名查找
+    /// 
+    /// # Arguments
+    /// * `file_path` - 文件路径
+    /// * `parsers` - 已注册的语言解析器列表
+    /// * `overrides` - 语言覆盖规则
+    /// 
+    /// # Returns
+    /// * `Some(parser)` - 负责该文件的解析器
+    /// * `None` - 文件被跳过、指定的语言没有注册解析器或没有解析器支持该扩展名
+    pub fn select_parser<'a>(
+        file_path: &Path,
+        parsers: &'a [Box<dyn LanguageParser>],
+        overrides: &[LanguageOverride],
+    ) -> Option<&'a dyn LanguageParser> {
+        match overrides.iter().find(|o| o.matches(file_path)) {
+            Some(LanguageOverride { language: Some(language), .. }) => parsers
+                .iter()
+                .find(|p| p.language_name().eq_ignore_ascii_case(language))
+                .map(|p| p.as_ref()),
+            Some(LanguageOverride { language: None, .. }) => None,
+            None => Self::find_parser(file_path, parsers),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::java_parser::JavaParser;
+    use crate::rust_parser::RustParser;
+    use crate::csharp_parser::CSharpParser;
+    
+    fn builtin_parsers() -> Vec<Box<dyn LanguageParser>> {
+        vec![
+            Box::new(JavaParser::new().unwrap()),
+            Box::new(RustParser::new().unwrap()),
+            Box::new(CSharpParser::new().unwrap()),
+        ]
+    }
+    
+    #[test]
+    fn test_call_filter_matches_method_name() {
+        let filter = CallFilter::accessors();
+        assert!(filter.is_ignored("getName"));
+        assert!(filter.is_ignored("com.example.User::setName"));
+        assert!(filter.is_ignored("user.isActive"));
+        assert!(!filter.is_ignored("process"));
+        // 只匹配方法名，类名中的 get 前缀不影响
+        assert!(!filter.is_ignored("com.example.GetHandler::handle"));
+        assert!(!filter.is_ignored("getaway"));
+        
+        assert!(CallFilter::default().is_empty());
+        assert!(!CallFilter::default().is_ignored("getName"));
+        assert!(CallFilter::new(["("]).is_err());
+    }
     
     #[test]
     fn test_detect_java() {
+        let parsers = builtin_parsers();
         let path = Path::new("src/main/java/Example.java");
-        assert_eq!(LanguageDetector::detect_language(path), Some("java"));
-        assert!(LanguageDetector::is_supported(path));
+        assert_eq!(LanguageDetector::detect_language(path, &parsers), Some("java"));
+        assert!(LanguageDetector::is_supported(path, &parsers));
     }
     
     #[test]
     fn test_detect_rust() {
+        let parsers = builtin_parsers();
         let path = Path::new("src/lib.rs");
-        assert_eq!(LanguageDetector::detect_language(path), Some("rust"));
-        assert!(LanguageDetector::is_supported(path));
+        assert_eq!(LanguageDetector::detect_language(path, &parsers), Some("rust"));
+        assert!(LanguageDetector::is_supported(path, &parsers));
+    }
+    
+    #[test]
+    fn test_detect_csharp() {
+        let parsers = builtin_parsers();
+        let path = Path::new("src/Controllers/OrdersController.cs");
+        assert_eq!(LanguageDetector::detect_language(path, &parsers), Some("csharp"));
+        assert!(LanguageDetector::is_supported(path, &parsers));
     }
     
     #[test]
     fn test_detect_unsupported() {
         let path = Path::new("README.md");
-        assert_eq!(LanguageDetector::detect_language(path), None);
-        assert!(!LanguageDetector::is_supported(path));
+        let parsers = builtin_parsers();
+        assert_eq!(LanguageDetector::detect_language(path, &parsers), None);
+        assert!(!LanguageDetector::is_supported(path, &parsers));
+    }
+    
+    #[test]
+    fn test_language_override_selects_parser() {
+        let parsers = builtin_parsers();
+        let overrides: Vec<LanguageOverride> = ["legacy/*.txt=java", "vendor/**/*.rs=none"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        
+        let select = |path: &str| {
+            LanguageDetector::select_parser(Path::new(path), &parsers, &overrides).map(|p| p.language_name())
+        };
+        assert_eq!(select("/ws/order/legacy/OrderService.txt"), Some("java"));
+        assert_eq!(select("/ws/order/notes/todo.txt"), None);
+        assert_eq!(select("/ws/vendor/serde/src/lib.rs"), None);
+        assert_eq!(select("/ws/vendor/lib.rs"), None);
+        assert_eq!(select("/ws/app/src/lib.rs"), Some("rust"));
+        assert_eq!(select("C:\\ws\\vendor\\lib.rs"), None);
+        
+        assert_eq!(overrides[1].language, None);
+        assert!("*.txt".parse::<LanguageOverride>().is_err());
+        assert!("=java".parse::<LanguageOverride>().is_err());
     }
     
     #[test]
     fn test_detect_no_extension() {
         let path = Path::new("Makefile");
-        assert_eq!(LanguageDetector::detect_language(path), None);
-        assert!(!LanguageDetector::is_supported(path));
+        let parsers = builtin_parsers();
+        assert_eq!(LanguageDetector::detect_language(path, &parsers), None);
+        assert!(!LanguageDetector::is_supported(path, &parsers));
     }
 }
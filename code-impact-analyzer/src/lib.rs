@@ -1,16 +1,30 @@
+/// 输出步骤进度日志，静默模式（`--quiet`）下不输出
+macro_rules! progress {
+    ($quiet:expr, $($arg:tt)+) => {
+        if !$quiet {
+            log::info!($($arg)+);
+        }
+    };
+}
+
 pub mod types;
 pub mod errors;
 pub mod patch_parser;
 pub mod language_parser;
 pub mod java_parser;
 pub mod rust_parser;
+pub mod csharp_parser;
 pub mod config_parser;
+pub mod thrift_parser;
+pub mod sql_parser;
 pub mod code_index;
 pub mod parse_cache;
 pub mod impact_tracer;
+pub mod resource_matcher;
 pub mod orchestrator;
 pub mod cli;
 pub mod index_storage;
+pub mod git_diff;
 
 pub use types::*;
 pub use errors::*;
@@ -18,34 +32,44 @@ pub use patch_parser::*;
 pub use language_parser::*;
 pub use java_parser::*;
 pub use rust_parser::*;
+pub use csharp_parser::*;
 pub use config_parser::*;
 pub use code_index::*;
 pub use parse_cache::*;
 pub use impact_tracer::*;
+pub use resource_matcher::*;
 pub use orchestrator::*;
 pub use cli::*;
 pub use index_storage::*;
+pub use git_diff::*;
 
 /// 主分析流程
 /// 
 /// 连接所有模块，执行完整的代码影响分析流程
-pub fn run(args: CliArgs) -> Result<(), AnalysisError> {
-    // 创建索引存储管理器
-    let index_storage = IndexStorage::new(args.workspace_path.clone());
+/// 
+/// # Returns
+/// * `Ok(ExitStatus)` - 分析完成，退出状态区分成功、解析失败（严格模式）、超过影响阈值和没有匹配的变更方法
+/// * `Err(AnalysisError)` - 分析失败
+pub fn run(args: CliArgs) -> Result<ExitStatus, AnalysisError> {
+    // 创建索引存储管理器，--index-dir 指定时使用该目录
+    let index_storage = match &args.index_dir {
+        Some(index_dir) => IndexStorage::new(args.workspace_path.clone()).with_index_dir(index_dir.clone()),
+        None => IndexStorage::new(args.workspace_path.clone()),
+    };
     
     // 处理索引管理命令
     if args.clear_index {
-        log::info!("Clearing index...");
+        progress!(args.quiet, "Clearing index...");
         index_storage.clear_index()
-            .map_err(|e| AnalysisError::IndexBuildError(e))?;
+            .map_err(AnalysisError::IndexBuildError)?;
         println!("Index cleared successfully");
-        return Ok(());
+        return Ok(ExitStatus::Success);
     }
     
     if args.index_info {
-        log::info!("Retrieving index information...");
+        progress!(args.quiet, "Retrieving index information...");
         match index_storage.get_index_info()
-            .map_err(|e| AnalysisError::IndexBuildError(e))? {
+            .map_err(AnalysisError::IndexBuildError)? {
             Some(metadata) => {
                 println!("Index Information:");
                 println!("  Version: {}", metadata.version);
@@ -60,15 +84,15 @@ pub fn run(args: CliArgs) -> Result<(), AnalysisError> {
                 println!("No index found");
             }
         }
-        return Ok(());
+        return Ok(ExitStatus::Success);
     }
     
     if args.verify_index {
-        log::info!("Verifying index...");
+        progress!(args.quiet, "Verifying index...");
         match index_storage.get_index_info()
-            .map_err(|e| AnalysisError::IndexBuildError(e))? {
+            .map_err(AnalysisError::IndexBuildError)? {
             Some(metadata) => {
-                if metadata.is_valid(&args.workspace_path) {
+                if metadata.is_valid(&args.workspace_path, index_storage.source_extensions()) {
                     println!("Index is valid");
                 } else {
                     println!("Index is invalid or outdated");
@@ -78,7 +102,7 @@ pub fn run(args: CliArgs) -> Result<(), AnalysisError> {
                 println!("No index found");
             }
         }
-        return Ok(());
+        return Ok(ExitStatus::Success);
     }
     
     // 验证输入路径
@@ -91,22 +115,40 @@ pub fn run(args: CliArgs) -> Result<(), AnalysisError> {
         ));
     }
     
-    if !args.diff_path.exists() {
+    // 指定 --trace-endpoint/--trace-resource 时以端点或资源为起点，--staged/--working 从 Git 仓库读取变更，
+    // --list-endpoints/--report-unresolved/--dump-index/--dry-run 只输出索引信息，均不需要 diff
+    let git_diff_mode = args.git_diff_mode();
+    let diff_path = match (&args.trace_endpoint, &args.diff_path) {
+        (Some(_), _) => None,
+        (None, _) if args.trace_resource.is_some() => None,
+        (None, Some(diff_path)) => Some(diff_path.as_path()),
+        (None, None) if args.list_endpoints || args.report_unresolved || args.dump_index || args.dry_run || git_diff_mode.is_some() => None,
+        (None, None) => {
+            return Err(AnalysisError::IoError(
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "Either --diff, --trace-endpoint or --trace-resource is required"
+                )
+            ));
+        }
+    };
+    
+    if let Some(diff_path) = diff_path && !diff_path.exists() {
         return Err(AnalysisError::IoError(
             std::io::Error::new(
                 std::io::ErrorKind::NotFound,
-                format!("Diff file does not exist: {:?}", args.diff_path)
+                format!("Diff file does not exist: {:?}", diff_path)
             )
         ));
     }
     
     // 创建追溯配置
-    let trace_config = TraceConfig {
-        max_depth: args.max_depth,
-        trace_upstream: true,
-        trace_downstream: true,
-        trace_cross_service: true,
-    };
+    let trace_config = TraceConfig::builder()
+        .max_depth(args.max_depth)
+        .allowed_services(args.services.clone())
+        .max_bridge_fanout(args.max_bridge_fanout)
+        .build()
+        .map_err(AnalysisError::TraceError)?;
     
     // 创建分析编排器
     let mut orchestrator = AnalysisOrchestrator::new(
@@ -116,10 +158,137 @@ pub fn run(args: CliArgs) -> Result<(), AnalysisError> {
     
     // 设置是否强制重建索引
     orchestrator.set_force_rebuild(args.rebuild_index);
+    orchestrator.set_persist_index(!args.no_save_index);
+    orchestrator.set_significant_changes_only(args.significant_changes_only);
+    orchestrator.set_added_only(args.added_only);
+    orchestrator.set_normalize_paths(!args.no_normalize_paths);
+    orchestrator.set_quiet(args.quiet);
+    orchestrator.set_max_warnings(args.max_warnings);
+    orchestrator.set_annotate_blame(args.blame);
+    orchestrator.set_label_style(args.label_style.to_label_style());
+    orchestrator.set_redis_namespace_depth(args.redis_namespace_depth);
+    if !args.kafka_topic_prefix_strip.is_empty() {
+        orchestrator.set_kafka_topic_prefixes(args.kafka_topic_prefix_strip.clone());
+    }
+    orchestrator.set_min_http_segments(args.min_http_segments);
+    if !args.config_topic_paths.is_empty() {
+        orchestrator.set_config_topic_paths(args.config_topic_paths.clone());
+    }
+    orchestrator.set_table_normalization(args.table_normalization());
+    orchestrator.set_service_aliases(args.service_alias_map());
+    if !args.lang_overrides.is_empty() {
+        orchestrator.set_language_overrides(args.lang_overrides.clone());
+    }
+    if !args.extra_source_roots.is_empty() {
+        orchestrator.set_extra_source_roots(args.extra_source_roots.clone());
+    }
+    orchestrator.set_reachable_only(args.reachable_only);
+    orchestrator.set_contract_delegations(args.contract_delegations);
+    orchestrator.set_min_visibility(args.visibility.min_visibility());
+    orchestrator.set_hide_low_visibility(args.hide_low_visibility);
+    if args.index_dir.is_some() {
+        orchestrator.set_index_source(IndexSource::Local(index_storage));
+    }
+    if let Some(url) = &args.index_url {
+        orchestrator.set_index_source(IndexSource::Remote { url: url.clone() });
+    }
+    if args.ignore_accessors {
+        orchestrator.set_call_filter(CallFilter::accessors());
+    }
+    
+    // 试运行：只报告将被索引的文件
+    if args.dry_run {
+        let report = orchestrator.dry_run()?;
+        let stdout = std::io::stdout();
+        write_dry_run_report(&report, &mut stdout.lock())?;
+        return Ok(ExitStatus::Success);
+    }
+    
+    // 仅列出 HTTP 端点清单
+    if args.list_endpoints {
+        progress!(args.quiet, "Listing HTTP endpoints...");
+        let endpoints = orchestrator.list_http_endpoints()?;
+        let stdout = std::io::stdout();
+        write_http_endpoints(&endpoints, &mut stdout.lock())?;
+        return Ok(ExitStatus::Success);
+    }
+    
+    // 仅列出未解析的调用目标
+    if args.report_unresolved {
+        progress!(args.quiet, "Collecting unresolved call targets...");
+        let targets = orchestrator.list_unresolved_targets()?;
+        let stdout = std::io::stdout();
+        write_unresolved_targets(&targets, &mut stdout.lock())?;
+        return Ok(ExitStatus::Success);
+    }
+    
+    // 导出完整索引
+    if args.dump_index {
+        use std::io::Write;
+        
+        progress!(args.quiet, "Dumping code index...");
+        let json = orchestrator.dump_index()?;
+        let mut writer = open_output(&args)?;
+        writeln!(writer, "{}", json)?;
+        writer.flush()?;
+        return Ok(ExitStatus::Success);
+    }
+    
+    // 仅列出变更方法，跳过影响追溯
+    if args.list_changed {
+        let Some(diff_path) = diff_path else {
+            return Err(AnalysisError::IoError(
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "--list-changed requires --diff"
+                )
+            ));
+        };
+        progress!(args.quiet, "Listing changed methods...");
+        let changed_methods = orchestrator.list_changed_methods(diff_path)?;
+        for warning in orchestrator.warnings() {
+            log::warn!("  - {}", warning);
+        }
+        let stdout = std::io::stdout();
+        write_changed_methods(&changed_methods, &mut stdout.lock())?;
+        return Ok(ExitStatus::Success);
+    }
     
     // 执行分析
-    log::info!("Starting analysis...");
-    let result = orchestrator.analyze(&args.diff_path)?;
+    progress!(args.quiet, "Starting analysis...");
+    let mut result = match (&args.trace_endpoint, &args.trace_resource, git_diff_mode, diff_path) {
+        (Some(endpoint), _, _, _) => orchestrator.analyze_endpoint(endpoint)?,
+        (None, Some(resource), _, _) => orchestrator.analyze_resource(resource)?,
+        (None, None, Some(mode), _) => orchestrator.analyze_git_changes(mode)?,
+        (None, None, None, Some(diff_path)) => orchestrator.analyze(diff_path)?,
+        (None, None, None, None) => unreachable!("diff path is checked above"),
+    };
+    
+    // 只保留相对基线新增的影响
+    if let Some(baseline_path) = &args.baseline {
+        let baseline = load_baseline(baseline_path)?;
+        result.impact_graph = result.impact_graph.difference(&baseline);
+        progress!(args.quiet, "New impact since baseline: {} nodes, {} edges",
+                  result.impact_graph.node_count(), result.impact_graph.edge_count());
+    }
+    
+    // 按边方向和风险等级过滤（种子方法始终保留），统计信息和阈值检查都基于实际输出的图
+    let seed_ids: Vec<String> = result.changed_methods.iter()
+        .map(|m| format!("method:{}", m))
+        .collect();
+    if let Some(direction) = args.direction.to_direction() {
+        result.impact_graph = result.impact_graph.filter_by_direction(&direction, &seed_ids);
+    }
+    if let Some(risk) = args.min_risk {
+        result.impact_graph = result.impact_graph.filter_by_risk(risk.min_risk(), &seed_ids);
+    }
+    result.statistics.traced_chains = result.impact_graph.edge_count();
+    result.statistics.blast_radius = result.impact_graph.blast_radius();
+    
+    // 按改动量排序变更方法
+    if args.sort_by_churn {
+        result.sort_by_churn();
+    }
     
     // 输出警告
     if !result.warnings.is_empty() {
@@ -129,19 +298,216 @@ pub fn run(args: CliArgs) -> Result<(), AnalysisError> {
         }
     }
     
-    // 输出统计信息
-    log::info!("Analysis Statistics:");
-    log::info!("  Total files: {}", result.statistics.total_files);
-    log::info!("  Parsed files: {}", result.statistics.parsed_files);
-    log::info!("  Failed files: {}", result.statistics.failed_files);
-    log::info!("  Total methods: {}", result.statistics.total_methods);
-    log::info!("  Traced chains: {}", result.statistics.traced_chains);
-    log::info!("  Duration: {} ms", result.statistics.duration_ms);
+    // 输出统计摘要（面向人阅读，写到标准错误，不影响影响图输出）
+    log::debug!("Analysis statistics: {:?}", result.statistics);
+    if log::log_enabled!(log::Level::Info) {
+        let stderr = std::io::stderr();
+        write_summary(&result, args.color.enabled(), &mut stderr.lock())?;
+        if args.sort_by_churn {
+            write_churn_ranking(&result, &mut stderr.lock())?;
+        }
+    }
     
     // 输出影响图
     output_result(&result, &args)?;
     
-    log::info!("Analysis completed successfully");
+    // 完整结果已输出，便于审查；再按严重程度确定退出状态
+    if args.strict && result.statistics.parse_failures > 0 {
+        return Err(AnalysisError::ParseFailures { count: result.statistics.parse_failures });
+    }
+    if let Some(threshold) = args.max_impact
+        && let Err(e) = check_impact_threshold(&result.impact_graph, threshold)
+    {
+        eprintln!("Error: {}", e);
+        return Ok(ExitStatus::ImpactThresholdExceeded);
+    }
+    if result.changed_methods.is_empty() {
+        log::warn!("No changed methods matched the diff");
+        return Ok(ExitStatus::NoMethodsMatched);
+    }
+    
+    progress!(args.quiet, "Analysis completed successfully");
+    Ok(ExitStatus::Success)
+}
+
+/// 检查影响范围是否超过阈值
+/// 
+/// 影响范围以影响半径（从种子方法可达的方法节点数）计算；超过阈值时不记录日志，由调用方报告返回的错误
+/// 
+/// # Arguments
+/// * `graph` - 影响图
+/// * `threshold` - 允许的最大受影响方法数
+/// 
+/// # Returns
+/// * `Ok(())` - 未超过阈值
+/// * `Err(AnalysisError::ImpactThresholdExceeded)` - 超过阈值
+pub fn check_impact_threshold(graph: &ImpactGraph, threshold: usize) -> Result<(), AnalysisError> {
+    let impact = graph.blast_radius();
+    
+    if impact > threshold {
+        return Err(AnalysisError::ImpactThresholdExceeded { impact, threshold });
+    }
+    
+    Ok(())
+}
+
+/// 加载基线影响图
+/// 
+/// # Arguments
+/// * `path` - 之前以 JSON 格式输出的影响图文件
+pub fn load_baseline(path: &std::path::Path) -> Result<ImpactGraph, AnalysisError> {
+    let content = std::fs::read_to_string(path)?;
+    ImpactGraph::from_json(&content)
+        .map_err(|e| AnalysisError::IoError(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Invalid baseline graph {:?}: {}", path, e),
+        )))
+}
+
+/// 输出变更方法列表，每行一个全限定名
+/// 
+/// # Arguments
+/// * `methods` - 已排序的变更方法列表
+/// * `writer` - 输出目标
+pub fn write_changed_methods<W: std::io::Write>(
+    methods: &[String],
+    writer: &mut W,
+) -> std::io::Result<()> {
+    for method in methods {
+        writeln!(writer, "{}", method)?;
+    }
+    Ok(())
+}
+
+/// 输出 HTTP 端点清单，每行为制表符分隔的 HTTP 方法、路径和提供者方法
+/// 
+/// # Arguments
+/// * `endpoints` - (端点, 提供者方法) 列表
+/// * `writer` - 输出目标
+pub fn write_http_endpoints<W: std::io::Write>(
+    endpoints: &[(HttpEndpoint, String)],
+    writer: &mut W,
+) -> std::io::Result<()> {
+    for (endpoint, provider) in endpoints {
+        writeln!(writer, "{}\t{}\t{}", endpoint.method_str(), endpoint.path_pattern, provider)?;
+    }
+    Ok(())
+}
+
+/// 输出未解析的调用目标，每行为制表符分隔的调用次数和调用目标
+/// 
+/// # Arguments
+/// * `targets` - (调用目标, 调用次数) 列表
+/// * `writer` - 输出目标
+pub fn write_unresolved_targets<W: std::io::Write>(
+    targets: &[(String, usize)],
+    writer: &mut W,
+) -> std::io::Result<()> {
+    for (target, count) in targets {
+        writeln!(writer, "{}\t{}", count, target)?;
+    }
+    Ok(())
+}
+
+/// 输出试运行报告
+/// 
+/// 依次输出各语言的源文件数、配置文件数和没有匹配解析器的文件列表
+/// 
+/// # Arguments
+/// * `report` - 试运行报告
+/// * `writer` - 输出目标
+pub fn write_dry_run_report<W: std::io::Write>(
+    report: &DryRunReport,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    writeln!(writer, "Source files:")?;
+    for (language, count) in &report.language_counts {
+        writeln!(writer, "  {}: {}", language, count)?;
+    }
+    writeln!(writer, "Config files: {}", report.config_files)?;
+    writeln!(writer, "Files without a matching parser: {}", report.unsupported_files.len())?;
+    for file in &report.unsupported_files {
+        writeln!(writer, "  {}", file.display())?;
+    }
+    Ok(())
+}
+
+/// 输出运行结束时的统计摘要
+/// 
+/// 影响图非空时附加按模块汇总的受影响方法数和资源数。着色时关键数字加粗，警告为黄色，错误和失败文件为红色
+/// 
+/// # Arguments
+/// * `result` - 分析结果
+/// * `color` - 是否使用 ANSI 颜色
+/// * `writer` - 输出目标
+pub fn write_summary<W: std::io::Write>(
+    result: &AnalysisResult,
+    color: bool,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    use anstyle::{AnsiColor, Style};
+    
+    let style = |style: Style| if color { style } else { Style::new() };
+    let bold = style(Style::new().bold());
+    let highlight = |count: usize, highlight: AnsiColor| {
+        if count > 0 { style(highlight.on_default().bold()) } else { Style::new() }
+    };
+    
+    let stats = &result.statistics;
+    let failed = highlight(stats.failed_files, AnsiColor::Red);
+    let warnings = highlight(result.warnings.len(), AnsiColor::Yellow);
+    let errors = highlight(result.errors.len(), AnsiColor::Red);
+    
+    writeln!(writer, "{bold}Analysis summary{bold:#}")?;
+    writeln!(writer, "  Changed methods: {bold}{}{bold:#}", result.changed_methods.len())?;
+    writeln!(writer, "  Blast radius:    {bold}{}{bold:#}", stats.blast_radius)?;
+    writeln!(writer, "  Total methods:   {}", stats.total_methods)?;
+    writeln!(writer, "  Traced chains:   {}", stats.traced_chains)?;
+    writeln!(writer, "  Files:           {} total, {} parsed, {failed}{} failed{failed:#}",
+             stats.total_files, stats.parsed_files, stats.failed_files)?;
+    writeln!(writer, "  Warnings:        {warnings}{}{warnings:#}", result.warnings.len())?;
+    writeln!(writer, "  Errors:          {errors}{}{errors:#}", result.errors.len())?;
+    writeln!(writer, "  Duration:        {} ms", stats.duration_ms)?;
+    
+    // 影响图的结构指标
+    let metrics = result.impact_graph.metrics();
+    writeln!(writer, "{bold}Graph metrics{bold:#}")?;
+    writeln!(writer, "  Components:      {}", metrics.components)?;
+    writeln!(writer, "  Average degree:  {:.2}", metrics.average_degree)?;
+    writeln!(writer, "  Max depth:       {}", metrics.max_depth)?;
+    
+    // 按模块汇总的影响
+    let modules = result.impact_graph.module_rollup();
+    if !modules.is_empty() {
+        let width = modules.iter().map(|m| m.module.len()).max().unwrap_or_default();
+        writeln!(writer, "{bold}Impact by module{bold:#}")?;
+        for module in &modules {
+            let resources: String = module.resources.iter()
+                .map(|(kind, count)| format!(", {} {}", count, kind))
+                .collect();
+            writeln!(writer, "  {:<width$}  {bold}{}{bold:#} methods{}", module.module, module.methods, resources)?;
+        }
+    }
+    Ok(())
+}
+
+/// 输出按改动量排序的变更方法
+/// 
+/// 每行为方法名、重叠的 hunk 数和变更行数，没有改动量信息的方法不输出
+/// 
+/// # Arguments
+/// * `result` - 已按改动量排序的分析结果
+/// * `writer` - 输出目标
+pub fn write_churn_ranking<W: std::io::Write>(
+    result: &AnalysisResult,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    writeln!(writer, "Changed methods by churn")?;
+    for method in &result.changed_methods {
+        if let Some(churn) = result.churn_of(method) {
+            writeln!(writer, "  {}: {} regions, {} lines", method, churn.change_regions, churn.changed_lines)?;
+        }
+    }
     Ok(())
 }
 
@@ -166,30 +532,61 @@ fn format_timestamp(timestamp: u64) -> String {
     }
 }
 
+/// 打开 `--output` 指定的输出文件，未指定时输出到标准输出
+/// 
+/// 输出到文件时使用带缓冲的写入器，否则输出到标准输出
+fn open_output(args: &CliArgs) -> Result<Box<dyn std::io::Write>, AnalysisError> {
+    Ok(match &args.output_path {
+        Some(path) => {
+            progress!(args.quiet, "Writing output to {:?}", path);
+            Box::new(std::io::BufWriter::new(std::fs::File::create(path)?))
+        }
+        None => Box::new(std::io::BufWriter::new(std::io::stdout().lock())),
+    })
+}
+
 /// 输出分析结果
 fn output_result(
     result: &AnalysisResult,
     args: &CliArgs,
 ) -> Result<(), AnalysisError> {
+    use std::io::Write;
+    
+    let graph = &result.impact_graph;
+    let mut writer = open_output(args)?;
+    
+    if args.stream_jsonl {
+        graph.write_jsonl(&mut writer)
+            .map_err(|e| AnalysisError::IoError(std::io::Error::other(e.to_string())))?;
+        writer.flush()?;
+        return Ok(());
+    }
+    
     match args.output_format {
         OutputFormat::Dot => {
-            let dot_output = result.impact_graph.to_dot();
-            println!("{}", dot_output);
+            let dot_output = graph.to_dot();
+            writeln!(writer, "{}", dot_output)?;
         }
         OutputFormat::Json => {
-            let json_output = result.impact_graph.to_json()
-                .map_err(|e| AnalysisError::IoError(
-                    std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
-                ))?;
-            println!("{}", json_output);
+            // 流式写出 JSON，避免在内存中构建完整字符串
+            graph.write_json(&mut writer)
+                .map_err(|e| AnalysisError::IoError(std::io::Error::other(e.to_string())))?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Sarif => {
+            let sarif_output = graph.to_sarif(&result.changed_methods)
+                .map_err(|e| AnalysisError::IoError(std::io::Error::other(e.to_string())))?;
+            writeln!(writer, "{}", sarif_output)?;
         }
         OutputFormat::Mermaid => {
             // Mermaid 格式暂未实现，使用 DOT 格式代替
             log::warn!("Mermaid format not yet implemented, using DOT format instead");
-            let dot_output = result.impact_graph.to_dot();
-            println!("{}", dot_output);
+            let dot_output = graph.to_dot();
+            writeln!(writer, "{}", dot_output)?;
         }
     }
     
+    writer.flush()?;
+    
     Ok(())
 }
@@ -1,4 +1,4 @@
-use code_impact_analyzer::{CliArgs, run};
+use code_impact_analyzer::{CliArgs, run, TRACE_DEBUG_TARGET};
 use clap::Parser;
 use std::process;
 
@@ -7,22 +7,38 @@ fn main() {
     let args = CliArgs::parse();
     
     // 初始化日志系统，使用用户指定的日志级别
-    env_logger::Builder::from_env(
+    let mut logger = env_logger::Builder::from_env(
         env_logger::Env::default().default_filter_or(args.log_level.to_filter_str())
-    ).init();
+    );
+    if args.trace_debug {
+        logger.filter_module(TRACE_DEBUG_TARGET, log::LevelFilter::Trace);
+    }
+    logger.init();
     
-    log::info!("Code Impact Analyzer v0.1.0");
-    log::info!("Workspace path: {:?}", args.workspace_path);
-    log::info!("Diff path: {:?}", args.diff_path);
-    log::info!("Output format: {:?}", args.output_format);
-    log::info!("Max depth: {}", args.max_depth);
-    log::info!("Log level: {:?}", args.log_level);
+    // 静默模式下不输出启动信息
+    if !args.quiet {
+        log::info!("Code Impact Analyzer v0.1.0");
+        log::info!("Workspace path: {:?}", args.workspace_path);
+        log::info!("Diff path: {:?}", args.diff_path);
+        if let Some(endpoint) = &args.trace_endpoint {
+            log::info!("Trace endpoint: {}", endpoint);
+        }
+        if let Some(resource) = &args.trace_resource {
+            log::info!("Trace resource: {}", resource);
+        }
+        log::info!("Output format: {:?}", args.output_format);
+        log::info!("Max depth: {}", args.max_depth);
+        log::info!("Log level: {:?}", args.log_level);
+    }
     
-    // 执行分析流程
-    if let Err(e) = run(args) {
-        log::error!("Analysis failed: {}", e);
-        eprintln!("Error: {}", e);
-        process::exit(1);
+    // 执行分析流程，按结果以对应的退出码结束
+    match run(args) {
+        Ok(status) => process::exit(status.code()),
+        Err(e) => {
+            log::error!("Analysis failed: {}", e);
+            eprintln!("Error: {}", e);
+            process::exit(e.exit_code());
+        }
     }
 }
 
@@ -1,14 +1,25 @@
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Instant;
-use crate::errors::{AnalysisError, ParseError};
-use crate::patch_parser::{PatchParser, FileChange};
-use crate::code_index::CodeIndex;
-use crate::impact_tracer::{ImpactTracer, TraceConfig, ImpactGraph};
-use crate::language_parser::LanguageParser;
+use crate::errors::{AnalysisError, ParseError, TraceError};
+use crate::patch_parser::{is_trivial_line, PatchParser, FileChange, ChangeKind, ChangeType, HunkLine, LineType};
+use crate::code_index::{collect_workspace_files, CodeIndex, TableNormalization};
+use crate::impact_tracer::{service_from_path, ImpactTracer, TraceConfig, ImpactGraph, LabelStyle, NodeType, TraceCache};
+use crate::resource_matcher::ResourceMatcher;
+use crate::language_parser::{CallFilter, LanguageDetector, LanguageOverride, LanguageParser, MethodInfo, Visibility};
 use crate::java_parser::JavaParser;
 use crate::rust_parser::RustParser;
-use crate::config_parser::{ConfigParser, XmlConfigParser, YamlConfigParser};
-use crate::index_storage::IndexStorage;
+use crate::csharp_parser::CSharpParser;
+use crate::config_parser::{yaml_topics_at_paths, ConfigParser, XmlConfigParser, YamlConfigParser};
+use crate::thrift_parser::ThriftIdlParser;
+use crate::sql_parser::SqlMigrationParser;
+use crate::index_storage::{fetch_remote_index, IndexCache, IndexSource, IndexStorage};
+use crate::git_diff::{read_git_diff, BlameCache, GitDiffMode};
+use crate::types::{HttpEndpoint, ResourceId};
+
+/// 默认识别的 patch 文件扩展名
+pub const DEFAULT_PATCH_EXTENSIONS: &[&str] = &["patch", "diff"];
 
 /// 分析统计信息
 #[derive(Debug, Clone)]
@@ -19,10 +30,14 @@ pub struct AnalysisStatistics {
     pub parsed_files: usize,
     /// 解析失败的文件数
     pub failed_files: usize,
+    /// 无法解析或存在语法错误的 patch、源文件和配置文件数
+    pub parse_failures: usize,
     /// 识别的方法总数
     pub total_methods: usize,
     /// 追溯的调用链路数
     pub traced_chains: usize,
+    /// 影响半径：受影响的方法节点数（不含基础设施节点）
+    pub blast_radius: usize,
     /// 分析耗时（毫秒）
     pub duration_ms: u128,
 }
@@ -34,8 +49,10 @@ impl AnalysisStatistics {
             total_files: 0,
             parsed_files: 0,
             failed_files: 0,
+            parse_failures: 0,
             total_methods: 0,
             traced_chains: 0,
+            blast_radius: 0,
             duration_ms: 0,
         }
     }
@@ -52,6 +69,10 @@ impl Default for AnalysisStatistics {
 pub struct AnalysisResult {
     /// 影响图
     pub impact_graph: ImpactGraph,
+    /// 变更的方法（追溯的种子方法）
+    pub changed_methods: Vec<String>,
+    /// 低置信度的变更方法：变更未落在任何方法内，按最近的前一个方法归属
+    pub low_confidence_methods: Vec<String>,
     /// 统计信息
     pub statistics: AnalysisStatistics,
     /// 警告列表
@@ -60,6 +81,74 @@ pub struct AnalysisResult {
     pub errors: Vec<String>,
 }
 
+impl AnalysisResult {
+    /// 获取变更方法的改动量，取自影响图中种子节点的 `change_regions`、`changed_lines` 属性
+    /// 
+    /// # Returns
+    /// * `Some(ChangeChurn)` - 方法内有变更行
+    /// * `None` - 方法不在影响图中或没有改动量信息（如以端点为起点的分析）
+    pub fn churn_of(&self, method: &str) -> Option<ChangeChurn> {
+        let properties = &self.impact_graph.get_node(&format!("method:{}", method))?.metadata.properties;
+        Some(ChangeChurn {
+            change_regions: properties.get("change_regions")?.parse().ok()?,
+            changed_lines: properties.get("changed_lines")?.parse().ok()?,
+        })
+    }
+    
+    /// 按改动量对变更方法排序：变更区域数降序，其次变更行数降序，最后按方法名
+    /// 
+    /// 有改动量信息的种子节点同时记录从 1 开始的排名（`churn_rank` 属性），随影响图一起输出
+    pub fn sort_by_churn(&mut self) {
+        let mut ranked: Vec<(ChangeChurn, String)> = self.changed_methods.iter()
+            .map(|method| (self.churn_of(method).unwrap_or_default(), method.clone()))
+            .collect();
+        ranked.sort_by(|(a_churn, a), (b_churn, b)| b_churn.cmp(a_churn).then_with(|| a.cmp(b)));
+        self.changed_methods = ranked.into_iter().map(|(_, method)| method).collect();
+        
+        let ranks: HashMap<String, usize> = self.changed_methods.iter()
+            .filter(|method| self.churn_of(method).is_some())
+            .enumerate()
+            .map(|(rank, method)| (format!("method:{}", method), rank + 1))
+            .collect();
+        for node in self.impact_graph.nodes_mut() {
+            if let Some(rank) = ranks.get(&node.id) {
+                node.metadata.properties.insert("churn_rank".to_string(), rank.to_string());
+            }
+        }
+    }
+}
+
+/// 变更方法的改动量，用于按风险排序
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ChangeChurn {
+    /// 与方法重叠的 hunk 数（不相连的变更区域数）
+    pub change_regions: usize,
+    /// 方法内新增和删除的行数
+    pub changed_lines: usize,
+}
+
+/// 变更方法及其方法体中变更的行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedMethod {
+    /// 方法全限定名
+    pub name: String,
+    /// 方法内变更的行号（最终文件中的行号，升序去重）
+    pub changed_lines: Vec<u32>,
+    /// 方法在最终文件中的源码
+    pub snippet: String,
+}
+
+/// 试运行报告：工作空间中将被索引的文件统计
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRunReport {
+    /// 各语言的源文件数量，按语言名排序
+    pub language_counts: Vec<(String, usize)>,
+    /// 配置文件数量
+    pub config_files: usize,
+    /// 没有匹配的语言解析器或配置解析器的文件（相对工作空间的路径，已排序）
+    pub unsupported_files: Vec<PathBuf>,
+}
+
 /// 分析编排器
 /// 
 /// 协调整个分析流程：解析 patch -> 构建索引 -> 追溯影响 -> 生成图
@@ -72,14 +161,74 @@ pub struct AnalysisOrchestrator {
     parsers: Vec<Box<dyn LanguageParser>>,
     /// 配置解析器列表
     config_parsers: Vec<Box<dyn ConfigParser>>,
-    /// 索引存储管理器
-    index_storage: IndexStorage,
+    /// 是否注册了自定义语言解析器或配置解析器
+    custom_parsers: bool,
+    /// 索引来源（本地索引存储或远程索引）
+    index_source: IndexSource,
+    /// 内存索引缓存，只对本地索引生效
+    index_cache: Option<Arc<IndexCache>>,
     /// 警告列表
     warnings: Vec<String>,
     /// 错误列表
     errors: Vec<String>,
+    /// 本次分析中无法解析的文件数
+    parse_failures: usize,
     /// 是否强制重建索引
     force_rebuild: bool,
+    /// 是否将索引写入磁盘，只读工作空间中关闭后不清除也不保存索引
+    persist_index: bool,
+    /// 目录模式下识别为 patch 的文件扩展名（不含点号）
+    patch_extensions: Vec<String>,
+    /// 是否只将包含非平凡变更（非空行、非纯注释）的方法视为变更
+    significant_changes_only: bool,
+    /// 是否只将包含新增行的方法视为变更
+    added_only: bool,
+    /// 是否在比较 patch 与索引中的文件路径前规范化路径（分隔符、`./` 前缀、绝对路径）
+    normalize_paths: bool,
+    /// 提取调用时使用的过滤器
+    call_filter: CallFilter,
+    /// 最近一次提取中按最近方法归属的低置信度变更方法
+    low_confidence_methods: Vec<String>,
+    /// 最近一次提取中各变更方法的改动量
+    change_churn: HashMap<String, ChangeChurn>,
+    /// 最近一次提取中各文件计入的变更行（最终文件中的行号），键为 `file_key` 得到的文件路径
+    changed_lines: HashMap<PathBuf, Vec<usize>>,
+    /// 静默模式：不输出分析各步骤的进度日志
+    quiet: bool,
+    /// Redis 键按命名空间合并时保留的段数，为空表示不合并
+    redis_namespace_depth: Option<usize>,
+    /// 匹配 Kafka Topic 前去掉的环境前缀
+    kafka_topic_prefixes: Vec<String>,
+    /// 数据库表名的规范化规则
+    table_normalization: TableNormalization,
+    /// Feign 服务名到提供者 application.name 的别名
+    service_aliases: HashMap<String, String>,
+    /// 是否只保留从入口点可达的受影响方法
+    reachable_only: bool,
+    /// 是否在影响图中收缩纯委托方法
+    contract_delegations: bool,
+    /// 作为追溯起点的方法的最低可见性，为空表示不过滤
+    min_visibility: Option<Visibility>,
+    /// 是否同时从影响图中收缩低于最低可见性的方法
+    hide_low_visibility: bool,
+    /// 按种子方法缓存追溯子图的磁盘缓存
+    trace_cache: Option<Arc<TraceCache>>,
+    /// 启发式匹配 HTTP 客户端调用时端点路径至少需要的非参数段数
+    min_http_segments: usize,
+    /// YAML 配置中额外声明 Kafka Topic 的键路径
+    config_topic_paths: Vec<String>,
+    /// 按路径模式强制指定或禁用语言解析器的规则
+    language_overrides: Vec<LanguageOverride>,
+    /// 额外索引的源码根目录（如第三方依赖源码），优先级低于工作空间
+    extra_source_roots: Vec<PathBuf>,
+    /// 分析结果中最多保留的警告条数（合并同类警告后），为空表示不限制
+    max_warnings: Option<usize>,
+    /// 是否为方法节点附加 git blame 得到的最后修改作者和提交
+    annotate_blame: bool,
+    /// 方法节点标签的样式
+    label_style: LabelStyle,
+    /// 注册的自定义资源匹配器
+    resource_matchers: Vec<Arc<dyn ResourceMatcher>>,
 }
 
 impl AnalysisOrchestrator {
@@ -97,7 +246,7 @@ impl AnalysisOrchestrator {
         
         // 尝试创建 JavaParser
         match JavaParser::new() {
-            Ok(parser) => parsers.push(Box::new(parser)),
+            Ok(parser) => parsers.push(Box::new(parser.with_workspace_root(workspace_path.clone()))),
             Err(e) => {
                 log::warn!("Failed to initialize JavaParser: {}", e);
             }
@@ -111,32 +260,350 @@ impl AnalysisOrchestrator {
             }
         }
         
+        // 尝试创建 CSharpParser
+        match CSharpParser::new() {
+            Ok(parser) => parsers.push(Box::new(parser)),
+            Err(e) => {
+                log::warn!("Failed to initialize CSharpParser: {}", e);
+            }
+        }
+        
         // 初始化配置解析器
         let config_parsers: Vec<Box<dyn ConfigParser>> = vec![
             Box::new(XmlConfigParser),
-            Box::new(YamlConfigParser),
+            Box::new(YamlConfigParser::new()),
+            Box::new(ThriftIdlParser),
+            Box::new(SqlMigrationParser),
         ];
         
-        // 初始化索引存储管理器
-        let index_storage = IndexStorage::new(workspace_path.clone());
+        // 默认使用工作空间下的本地索引
+        let index_source = IndexSource::Local(IndexStorage::new(workspace_path.clone()));
         
         Ok(Self {
             workspace_path,
             trace_config,
             parsers,
             config_parsers,
-            index_storage,
+            custom_parsers: false,
+            index_source,
+            index_cache: None,
             warnings: Vec::new(),
             errors: Vec::new(),
+            parse_failures: 0,
             force_rebuild: false,
+            persist_index: true,
+            patch_extensions: DEFAULT_PATCH_EXTENSIONS.iter().map(|s| s.to_string()).collect(),
+            significant_changes_only: false,
+            added_only: false,
+            normalize_paths: true,
+            call_filter: CallFilter::default(),
+            low_confidence_methods: Vec::new(),
+            change_churn: HashMap::new(),
+            changed_lines: HashMap::new(),
+            quiet: false,
+            redis_namespace_depth: None,
+            kafka_topic_prefixes: Vec::new(),
+            table_normalization: TableNormalization::default(),
+            service_aliases: HashMap::new(),
+            reachable_only: false,
+            contract_delegations: false,
+            min_visibility: None,
+            hide_low_visibility: false,
+            trace_cache: None,
+            min_http_segments: 0,
+            config_topic_paths: Vec::new(),
+            language_overrides: Vec::new(),
+            extra_source_roots: Vec::new(),
+            max_warnings: None,
+            annotate_blame: false,
+            label_style: LabelStyle::Full,
+            resource_matchers: Vec::new(),
         })
     }
     
+    /// 注册语言解析器
+    /// 
+    /// 内置的 Java/Rust 解析器是默认集合。注册与已有解析器同名
+    /// （`language_name()` 相同）的解析器会替换它；新注册的解析器优先级
+    /// 最高，文件按其 `file_extensions()` 路由。
+    /// 
+    /// # Arguments
+    /// * `parser` - 自定义语言解析器
+    pub fn register_parser(&mut self, mut parser: Box<dyn LanguageParser>) {
+        parser.set_call_filter(self.call_filter.clone());
+        self.parsers.retain(|p| p.language_name() != parser.language_name());
+        self.parsers.insert(0, parser);
+        self.custom_parsers = true;
+    }
+    
+    /// 注册配置解析器
+    /// 
+    /// 新注册的解析器优先于内置的 XML/YAML 解析器。
+    /// 
+    /// # Arguments
+    /// * `parser` - 自定义配置解析器
+    pub fn register_config_parser(&mut self, parser: Box<dyn ConfigParser>) {
+        self.config_parsers.insert(0, parser);
+        self.custom_parsers = true;
+    }
+    
+    /// 注册自定义资源匹配器
+    /// 
+    /// 追溯时匹配器在内置的 Kafka、数据库和 Redis 匹配器之后执行，
+    /// 通过其识别的共享资源关联不同服务中的方法
+    /// 
+    /// # Arguments
+    /// * `matcher` - 自定义资源匹配器
+    pub fn register_resource_matcher(&mut self, matcher: Box<dyn ResourceMatcher>) {
+        self.resource_matchers.push(Arc::from(matcher));
+    }
+    
     /// 设置是否强制重建索引
     pub fn set_force_rebuild(&mut self, force: bool) {
         self.force_rebuild = force;
     }
     
+    /// 设置是否将索引写入磁盘
+    /// 
+    /// 关闭后仍会加载已有的磁盘索引，但构建的新索引只保存在内存中，强制重建时也不清除磁盘索引；
+    /// 用于工作空间以只读方式挂载的沙箱或 CI 环境
+    pub fn set_persist_index(&mut self, persist: bool) {
+        self.persist_index = persist;
+    }
+    
+    /// 设置是否只将包含非平凡变更的方法视为变更
+    /// 
+    /// 启用后，hunk 中只有空行或注释变更落在方法内时不会将该方法作为追溯起点
+    pub fn set_significant_changes_only(&mut self, enabled: bool) {
+        self.significant_changes_only = enabled;
+    }
+    
+    /// 设置是否只将包含新增行的方法视为变更
+    /// 
+    /// 启用后只按 hunk 中的 `+` 行定位变更方法，只删除了代码的方法不作为追溯起点；
+    /// 可与 `set_significant_changes_only` 同时使用
+    pub fn set_added_only(&mut self, enabled: bool) {
+        self.added_only = enabled;
+    }
+    
+    /// 设置是否规范化 patch 与索引中的文件路径后再比较，默认启用
+    /// 
+    /// 索引与 patch 在不同操作系统上生成时，路径可能使用 `\` 分隔符、带有 `./` 前缀，
+    /// 或者一方是绝对路径。启用后两侧都统一为 `/` 分隔、相对于工作空间的路径再比较
+    pub fn set_normalize_paths(&mut self, enabled: bool) {
+        self.normalize_paths = enabled;
+    }
+    
+    /// 设置是否只保留从入口点可达的受影响方法
+    /// 
+    /// 启用后，追溯完成后移除无法从任何入口点（HTTP 接口、Kafka 消费者、定时任务等）
+    /// 经方法调用到达的方法节点，例如只被死代码调用的影响；变更方法本身始终保留
+    pub fn set_reachable_only(&mut self, enabled: bool) {
+        self.reachable_only = enabled;
+    }
+    
+    /// 设置是否收缩纯委托方法
+    /// 
+    /// 启用后，追溯完成后将只转发一次调用的委托方法（如 `return delegate.foo();`）
+    /// 从影响图中移除，其调用方直接连接到被委托的方法；变更方法本身始终保留
+    pub fn set_contract_delegations(&mut self, enabled: bool) {
+        self.contract_delegations = enabled;
+    }
+    
+    /// 设置作为追溯起点的方法的最低可见性
+    /// 
+    /// 可见性更低的变更方法（如私有辅助方法）不单独作为起点，而由调用它的、满足可见性的方法代替；
+    /// 为 `None` 时不过滤
+    pub fn set_min_visibility(&mut self, min_visibility: Option<Visibility>) {
+        self.min_visibility = min_visibility;
+    }
+    
+    /// 设置是否同时从影响图中收缩低于最低可见性的方法
+    /// 
+    /// 启用后这些方法的调用方直接连接到其调用的方法；未设置最低可见性时不生效
+    pub fn set_hide_low_visibility(&mut self, enabled: bool) {
+        self.hide_low_visibility = enabled;
+    }
+    
+    /// 设置静默模式
+    /// 
+    /// 启用后不输出分析各步骤的进度日志，警告和错误仍照常输出
+    pub fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+        if let IndexSource::Local(storage) = &mut self.index_source {
+            storage.set_quiet(quiet);
+        }
+    }
+    
+    /// 设置 Redis 键按命名空间合并时保留的段数
+    /// 
+    /// 磁盘索引保存的是原始键，加载或构建索引后再按该深度合并，因此不影响缓存
+    pub fn set_redis_namespace_depth(&mut self, depth: Option<usize>) {
+        self.redis_namespace_depth = depth;
+    }
+    
+    /// 设置匹配 Kafka Topic 前去掉的环境前缀（如 `prod.`）
+    /// 
+    /// 与 Redis 命名空间相同，磁盘索引保存原始 Topic，加载或构建索引后再去掉前缀
+    pub fn set_kafka_topic_prefixes(&mut self, prefixes: Vec<String>) {
+        self.kafka_topic_prefixes = prefixes;
+    }
+    
+    /// 设置数据库表名的规范化规则
+    /// 
+    /// 与 Redis 命名空间相同，在加载或构建索引后再应用，不影响磁盘缓存
+    pub fn set_table_normalization(&mut self, normalization: TableNormalization) {
+        self.table_normalization = normalization;
+    }
+    
+    /// 设置 Feign 服务名到提供者 application.name 的别名
+    /// 
+    /// 与 Redis 命名空间相同，在加载或构建索引后再应用，不影响磁盘缓存
+    pub fn set_service_aliases(&mut self, aliases: HashMap<String, String>) {
+        self.service_aliases = aliases;
+    }
+    
+    /// 设置启发式匹配 HTTP 客户端调用时端点路径至少需要的非参数段数
+    /// 
+    /// 启发式匹配在构建索引时完成，设置了该值时与调用过滤一样不加载也不保存磁盘索引
+    pub fn set_min_http_segments(&mut self, min_segments: usize) {
+        self.min_http_segments = min_segments;
+    }
+    
+    /// 设置按路径模式强制指定或禁用语言解析器的规则
+    /// 
+    /// 影响构建索引时选择的文件和解析器，设置了规则时与调用过滤一样不加载也不保存磁盘索引；
+    /// 规则指定的语言没有注册解析器时输出警告日志，匹配的文件不参与索引
+    /// 
+    /// # Arguments
+    /// * `overrides` - 语言覆盖规则，第一条匹配路径的规则生效
+    pub fn set_language_overrides(&mut self, overrides: Vec<LanguageOverride>) {
+        for language in overrides.iter().filter_map(|o| o.language.as_deref()) {
+            if !self.parsers.iter().any(|p| p.language_name().eq_ignore_ascii_case(language)) {
+                log::warn!("No parser registered for language '{}' in --lang-override", language);
+            }
+        }
+        self.language_overrides = overrides;
+    }
+    
+    /// 设置 YAML 配置中额外声明 Kafka Topic 的键路径
+    /// 
+    /// 键路径下的 Topic 与 YAML 配置解析器的结果合并，不替换已注册的解析器。
+    /// 配置在构建索引时关联，设置了键路径时与调用过滤一样不加载也不保存磁盘索引
+    /// 
+    /// # Arguments
+    /// * `paths` - `.` 分隔的键路径（如 `app.messaging.destinations`）
+    pub fn set_config_topic_paths(&mut self, paths: Vec<String>) {
+        self.config_topic_paths = paths;
+    }
+    
+    /// 设置额外索引的源码根目录
+    /// 
+    /// 目录中的方法在工作空间之后索引，与工作空间方法同名时被忽略；目录位于工作空间内时遍历工作空间会跳过它。
+    /// 调用这些方法时不再视为外部调用而继续追溯，方法节点标记 `third_party = true`；目录不存在时构建索引失败。
+    /// 设置了目录时与调用过滤一样不加载也不保存磁盘索引
+    /// 
+    /// # Arguments
+    /// * `roots` - 源码根目录列表（如 `libs-src/`）
+    pub fn set_extra_source_roots(&mut self, roots: Vec<PathBuf>) {
+        self.extra_source_roots = roots;
+    }
+    
+    /// 设置分析结果中最多保留的警告条数
+    /// 
+    /// 同类警告（只有文件路径、行号等不同）总是合并为一条并附带次数；合并后仍超过上限时
+    /// 截断并追加一条 `... and N more warnings`
+    /// 
+    /// # Arguments
+    /// * `max_warnings` - 最多保留的条数，`None` 表示不限制
+    pub fn set_max_warnings(&mut self, max_warnings: Option<usize>) {
+        self.max_warnings = max_warnings;
+    }
+    
+    /// 设置是否为方法节点附加最后修改的作者和提交
+    /// 
+    /// 启用后对影响图中每个方法所在的文件执行一次 git blame（需要 `git` 特性），
+    /// 取方法行范围内最后一次修改的提交，记录为节点的 `last_author`、`last_commit` 属性
+    pub fn set_annotate_blame(&mut self, annotate_blame: bool) {
+        self.annotate_blame = annotate_blame;
+    }
+    
+    /// 设置方法节点标签的样式
+    /// 
+    /// 只影响节点的 `label`，节点 ID 保留完整的全限定名
+    pub fn set_label_style(&mut self, style: LabelStyle) {
+        self.label_style = style;
+    }
+    
+    /// 设置索引来源
+    /// 
+    /// 使用远程索引时不在本地构建或保存索引，patch 仍从本地读取并按本地工作空间定位文件
+    pub fn set_index_source(&mut self, source: IndexSource) {
+        self.index_source = source;
+        if let IndexSource::Local(storage) = &mut self.index_source {
+            storage.set_quiet(self.quiet);
+            if let Some(cache) = &self.index_cache {
+                storage.set_cache(cache.clone());
+            }
+        }
+    }
+    
+    /// 设置内存索引缓存
+    /// 
+    /// 常驻进程中复用同一个缓存时，TTL 内工作空间未变化的重复分析不再读取磁盘索引；
+    /// 只对本地索引生效，使用远程索引时忽略
+    pub fn set_index_cache(&mut self, cache: Arc<IndexCache>) {
+        if let IndexSource::Local(storage) = &mut self.index_source {
+            storage.set_cache(cache.clone());
+        }
+        self.index_cache = Some(cache);
+    }
+    
+    /// 设置追溯子图缓存
+    /// 
+    /// 同一索引上分析不同的 diff 时，已追溯过的变更方法直接复用缓存的子图；
+    /// 缓存项以磁盘索引的校验和为键，因此只对本地索引且未启用调用过滤或 HTTP 匹配限制时生效
+    pub fn set_trace_cache(&mut self, cache: Arc<TraceCache>) {
+        self.trace_cache = Some(cache);
+    }
+    
+    /// 设置提取调用时使用的过滤器
+    /// 
+    /// 方法名匹配过滤器的调用不会生成调用边，被调用的方法本身仍会被索引。
+    /// 过滤后的索引与缓存不一致，因此启用过滤时既不加载也不保存磁盘索引。
+    /// 
+    /// # Arguments
+    /// * `filter` - 调用过滤器
+    pub fn set_call_filter(&mut self, filter: CallFilter) {
+        for parser in &mut self.parsers {
+            parser.set_call_filter(filter.clone());
+        }
+        self.call_filter = filter;
+    }
+    
+    /// 设置目录模式下识别为 patch 的文件扩展名
+    /// 
+    /// # Arguments
+    /// * `extensions` - 扩展名列表（不含点号，大小写不敏感），例如 `["patch", "diff"]`
+    pub fn set_patch_extensions<I, S>(&mut self, extensions: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.patch_extensions = extensions
+            .into_iter()
+            .map(|ext| ext.into().trim_start_matches('.').to_lowercase())
+            .collect();
+    }
+    
+    /// 判断文件是否为可识别的 patch 文件
+    fn is_patch_file(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|s| s.to_str())
+            .map(|ext| self.patch_extensions.contains(&ext.to_lowercase()))
+            .unwrap_or(false)
+    }
+    
     /// 执行完整的分析流程
     /// 
     /// # Arguments
@@ -148,58 +615,473 @@ impl AnalysisOrchestrator {
     pub fn analyze(&mut self, patch_dir: &Path) -> Result<AnalysisResult, AnalysisError> {
         let start_time = Instant::now();
         
-        log::info!("Starting code impact analysis");
-        log::info!("Workspace: {:?}", self.workspace_path);
-        log::info!("Patch directory: {:?}", patch_dir);
+        progress!(self.quiet, "Starting code impact analysis");
+        progress!(self.quiet, "Workspace: {:?}", self.workspace_path);
+        progress!(self.quiet, "Patch directory: {:?}", patch_dir);
         
         // 清空之前的警告和错误
         self.warnings.clear();
+        self.parse_failures = 0;
         self.errors.clear();
         
         // 步骤 1: 解析 patch 目录中的所有文件
-        log::info!("Step 1: Parsing patch files from directory");
+        progress!(self.quiet, "Step 1: Parsing patch files from directory");
         let file_changes = self.parse_patches_from_directory(patch_dir)?;
-        log::info!("Found {} file changes", file_changes.len());
+        progress!(self.quiet, "Found {} file changes", file_changes.len());
+        
+        self.analyze_file_changes(&file_changes, start_time)
+    }
+    
+    /// 分析工作空间 Git 仓库中的变更，代替 patch 文件
+    /// 
+    /// 需要启用 `git` 特性，工作空间根目录必须是仓库根目录
+    /// 
+    /// # Arguments
+    /// * `mode` - 分析已暂存（`Staged`）还是未暂存（`Working`）的变更
+    /// 
+    /// # Returns
+    /// * `Ok(AnalysisResult)` - 分析结果
+    /// * `Err(AnalysisError)` - 读取仓库或分析错误
+    pub fn analyze_git_changes(&mut self, mode: GitDiffMode) -> Result<AnalysisResult, AnalysisError> {
+        let start_time = Instant::now();
+        
+        progress!(self.quiet, "Starting code impact analysis");
+        progress!(self.quiet, "Workspace: {:?}", self.workspace_path);
+        progress!(self.quiet, "Git changes: {:?}", mode);
+        
+        self.warnings.clear();
+        self.parse_failures = 0;
+        self.errors.clear();
+        
+        // 步骤 1: 读取并解析仓库中的变更
+        progress!(self.quiet, "Step 1: Reading changes from git repository");
+        let diff = read_git_diff(&self.workspace_path, mode)?;
+        let mut file_changes = PatchParser::parse_patch_str(&diff)
+            .map_err(AnalysisError::PatchParseError)?;
+        if mode == GitDiffMode::Staged {
+            // 暂存区 diff 的行号属于暂存的版本，而方法行范围从工作区文件解析，
+            // 需要经由未暂存的变更换算到工作区文件中
+            let unstaged = PatchParser::parse_patch_str(&read_git_diff(&self.workspace_path, GitDiffMode::Working)?)
+                .map_err(AnalysisError::PatchParseError)?;
+            for change in &mut file_changes {
+                if let Some(later) = unstaged.iter().find(|later| later.file_path == change.file_path) {
+                    change.rebase_onto(later);
+                }
+            }
+        }
+        progress!(self.quiet, "Found {} file changes", file_changes.len());
+        
+        self.analyze_file_changes(&file_changes, start_time)
+    }
+    
+    /// 对已解析的文件变更执行索引构建、变更方法提取和影响追溯
+    fn analyze_file_changes(
+        &mut self,
+        file_changes: &[FileChange],
+        start_time: Instant,
+    ) -> Result<AnalysisResult, AnalysisError> {
+        // 重建索引会覆盖磁盘索引，先取出被删除文件中原有的方法
+        let deleted_methods = self.deleted_file_methods(file_changes);
         
         // 步骤 2: 构建代码索引
-        log::info!("Step 2: Building code index");
+        progress!(self.quiet, "Step 2: Building code index");
         let code_index = self.build_index()?;
-        log::info!("Index built successfully");
+        self.warn_undeclared_db_tables(&code_index);
+        progress!(self.quiet, "Index built successfully");
         
-        // 步骤 3: 从 patch 中提取变更的方法
-        log::info!("Step 3: Extracting changed methods from patch");
-        let changed_methods = self.extract_changed_methods(&file_changes, &code_index)?;
-        log::info!("Found {} changed methods", changed_methods.len());
+        // 步骤 3: 从 patch 中提取变更的方法，确定追溯起点
+        let changed_methods = self.select_seed_methods(file_changes, &deleted_methods, &code_index)?;
         
         // 步骤 4: 追溯影响
-        log::info!("Step 4: Tracing impact");
-        let impact_graph = self.trace_impact(&changed_methods, &code_index)?;
-        log::info!("Impact graph generated with {} nodes and {} edges", 
+        progress!(self.quiet, "Step 4: Tracing impact");
+        let mut impact_graph = self.trace_impact(&changed_methods, &code_index)?;
+        progress!(self.quiet, "Impact graph generated with {} nodes and {} edges", 
                    impact_graph.node_count(), impact_graph.edge_count());
         
+        // 标记低置信度的变更方法节点，并为变更方法附加改动量
+        for node in impact_graph.nodes_mut() {
+            let NodeType::Method { qualified_name } = &node.node_type else {
+                continue;
+            };
+            let properties = &mut node.metadata.properties;
+            if deleted_methods.contains(qualified_name) {
+                properties.insert("deleted".to_string(), "true".to_string());
+            }
+            if self.low_confidence_methods.contains(qualified_name) {
+                properties.insert("confidence".to_string(), "low".to_string());
+            }
+            if let Some(churn) = self.change_churn.get(qualified_name) {
+                properties.insert("change_regions".to_string(), churn.change_regions.to_string());
+                properties.insert("changed_lines".to_string(), churn.changed_lines.to_string());
+            }
+        }
+        self.warn_hidden_reflective_calls(&impact_graph, &code_index);
+        
         // 步骤 5: 收集统计信息
         let duration_ms = start_time.elapsed().as_millis();
         let statistics = AnalysisStatistics {
             total_files: file_changes.len(),
             parsed_files: file_changes.len() - self.errors.len(),
             failed_files: self.errors.len(),
+            parse_failures: self.parse_failures,
             total_methods: changed_methods.len(),
             traced_chains: impact_graph.edge_count(),
+            blast_radius: impact_graph.blast_radius(),
             duration_ms,
         };
         
-        log::info!("Analysis completed in {} ms", duration_ms);
-        log::info!("Statistics: {:?}", statistics);
+        progress!(self.quiet, "Analysis completed in {} ms", duration_ms);
+        progress!(self.quiet, "Statistics: {:?}", statistics);
         
         // 返回分析结果
         Ok(AnalysisResult {
             impact_graph,
+            changed_methods,
+            low_confidence_methods: self.low_confidence_methods.clone(),
             statistics,
-            warnings: self.warnings.clone(),
+            warnings: summarize_warnings(&self.warnings, self.max_warnings),
             errors: self.errors.clone(),
         })
     }
     
+    /// 以 HTTP 端点为起点执行分析，不需要 patch
+    /// 
+    /// 通过索引查找提供该端点的方法，并以其作为追溯起点，
+    /// 用于回答"这个接口背后有哪些代码"之类的问题
+    /// 
+    /// # Arguments
+    /// * `endpoint` - HTTP 端点
+    /// 
+    /// # Returns
+    /// * `Ok(AnalysisResult)` - 分析结果，`changed_methods` 为端点的提供者方法
+    /// * `Err(AnalysisError)` - 找不到提供者或分析错误
+    pub fn analyze_endpoint(&mut self, endpoint: &HttpEndpoint) -> Result<AnalysisResult, AnalysisError> {
+        let not_found = TraceError::EndpointNotFound { endpoint: endpoint.to_string() };
+        self.analyze_from_index("endpoint", endpoint, "provider", not_found, |code_index| {
+            // 索引中的提供者路径由 application.name/context-path/类路径/方法路径 拼接而成，
+            // 不带前导斜杠，因此精确匹配失败时再按去掉前导斜杠的路径查找
+            let mut providers = code_index.find_http_providers(endpoint);
+            if providers.is_empty() && endpoint.path_pattern.starts_with('/') {
+                let relative = HttpEndpoint {
+                    method: endpoint.method.clone(),
+                    path_pattern: endpoint.path_pattern.trim_start_matches('/').to_string(),
+                };
+                providers = code_index.find_http_providers(&relative);
+            }
+            providers.into_iter().map(|p| p.to_string()).collect()
+        })
+    }
+    
+    /// 以资源（数据库表、Kafka Topic、Redis 键、HTTP 端点）为起点执行分析，不需要 patch
+    /// 
+    /// 通过索引查找读写该资源的方法（HTTP 端点为提供者和调用方），并以其作为追溯起点，
+    /// 用于回答"这张表被谁写入、下游又有谁读取"之类的问题
+    /// 
+    /// # Arguments
+    /// * `resource` - 起点资源
+    /// 
+    /// # Returns
+    /// * `Ok(AnalysisResult)` - 分析结果，`changed_methods` 为访问该资源的方法
+    /// * `Err(AnalysisError)` - 没有方法访问该资源或分析错误
+    pub fn analyze_resource(&mut self, resource: &ResourceId) -> Result<AnalysisResult, AnalysisError> {
+        let not_found = TraceError::ResourceNotFound { resource: resource.to_string() };
+        self.analyze_from_index("resource", resource, "accessor", not_found, |code_index| {
+            let mut accessors: Vec<&str> = match resource {
+                ResourceId::Database(table) => {
+                    let mut methods = code_index.find_db_writers(table);
+                    methods.extend(code_index.find_db_readers(table));
+                    methods
+                }
+                ResourceId::Kafka(topic) => {
+                    let mut methods = code_index.find_kafka_producers(topic);
+                    methods.extend(code_index.find_kafka_consumers(topic));
+                    methods
+                }
+                ResourceId::Redis(prefix) => {
+                    let mut methods = code_index.find_redis_writers(prefix);
+                    methods.extend(code_index.find_redis_readers(prefix));
+                    methods
+                }
+                ResourceId::Http(endpoint) => {
+                    // 索引中的路径不带前导斜杠，与 analyze_endpoint 相同，两种形式都查找
+                    let relative = HttpEndpoint {
+                        method: endpoint.method.clone(),
+                        path_pattern: endpoint.path_pattern.trim_start_matches('/').to_string(),
+                    };
+                    let mut methods = Vec::new();
+                    for endpoint in [endpoint, &relative] {
+                        methods.extend(code_index.find_http_providers(endpoint));
+                        methods.extend(code_index.find_http_consumers(endpoint));
+                    }
+                    methods
+                }
+            };
+            accessors.sort_unstable();
+            accessors.dedup();
+            accessors.into_iter().map(|m| m.to_string()).collect()
+        })
+    }
+    
+    /// 以索引中查找到的方法为起点执行分析，`analyze_endpoint` 和 `analyze_resource` 共用
+    /// 
+    /// 清空上次的警告和错误，构建索引后用 `lookup` 查找起点方法，追溯影响并汇总结果
+    /// 
+    /// # Arguments
+    /// * `kind` - 起点类型，用于进度日志，如 `endpoint`
+    /// * `target` - 起点，用于进度日志
+    /// * `role` - 起点方法的角色，用于进度日志，如 `provider`
+    /// * `not_found` - 没有找到起点方法时返回的错误
+    /// * `lookup` - 在索引中查找起点方法
+    /// 
+    /// # Returns
+    /// * `Ok(AnalysisResult)` - 分析结果，`changed_methods` 为起点方法
+    /// * `Err(AnalysisError)` - 没有找到起点方法或分析错误
+    fn analyze_from_index(
+        &mut self,
+        kind: &str,
+        target: &dyn std::fmt::Display,
+        role: &str,
+        not_found: TraceError,
+        lookup: impl FnOnce(&CodeIndex) -> Vec<String>,
+    ) -> Result<AnalysisResult, AnalysisError> {
+        let start_time = Instant::now();
+        
+        progress!(self.quiet, "Starting {} impact analysis", kind);
+        progress!(self.quiet, "Workspace: {:?}", self.workspace_path);
+        progress!(self.quiet, "Target {}: {}", kind, target);
+        
+        // 清空之前的警告和错误
+        self.warnings.clear();
+        self.parse_failures = 0;
+        self.errors.clear();
+        
+        progress!(self.quiet, "Step 1: Building code index");
+        let code_index = self.build_index()?;
+        self.warn_undeclared_db_tables(&code_index);
+        
+        progress!(self.quiet, "Step 2: Looking up {} methods", role);
+        let start_methods = lookup(&code_index);
+        if start_methods.is_empty() {
+            self.errors.push(not_found.to_string());
+            return Err(AnalysisError::TraceError(not_found));
+        }
+        progress!(self.quiet, "Found {} {} methods", start_methods.len(), role);
+        
+        progress!(self.quiet, "Step 3: Tracing impact");
+        let impact_graph = self.trace_impact(&start_methods, &code_index)?;
+        progress!(self.quiet, "Impact graph generated with {} nodes and {} edges", 
+                   impact_graph.node_count(), impact_graph.edge_count());
+        
+        let duration_ms = start_time.elapsed().as_millis();
+        let statistics = AnalysisStatistics {
+            total_files: 0,
+            parsed_files: 0,
+            failed_files: 0,
+            parse_failures: self.parse_failures,
+            total_methods: start_methods.len(),
+            traced_chains: impact_graph.edge_count(),
+            blast_radius: impact_graph.blast_radius(),
+            duration_ms,
+        };
+        
+        progress!(self.quiet, "Analysis completed in {} ms", duration_ms);
+        
+        Ok(AnalysisResult {
+            impact_graph,
+            changed_methods: start_methods,
+            low_confidence_methods: Vec::new(),
+            statistics,
+            warnings: summarize_warnings(&self.warnings, self.max_warnings),
+            errors: self.errors.clone(),
+        })
+    }
+    
+    /// 试运行：报告将被索引的文件，不解析文件也不构建索引
+    /// 
+    /// 遍历工作空间中的文件，为每个文件选择语言解析器或配置解析器，
+    /// 用于在首次构建索引前确认文件发现和解析器选择是否符合预期
+    /// 
+    /// # Returns
+    /// * `Ok(DryRunReport)` - 各语言文件数和无匹配解析器的文件
+    /// * `Err(AnalysisError)` - 读取工作空间失败
+    pub fn dry_run(&self) -> Result<DryRunReport, AnalysisError> {
+        let files = collect_workspace_files(&self.workspace_path)
+            .map_err(AnalysisError::IndexBuildError)?;
+        
+        let mut language_counts: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+        let mut config_files = 0;
+        let mut unsupported_files = Vec::new();
+        for file in files {
+            if let Some(parser) = LanguageDetector::select_parser(&file, &self.parsers, &self.language_overrides) {
+                *language_counts.entry(parser.language_name().to_string()).or_default() += 1;
+            } else if self.is_config_file(&file) {
+                config_files += 1;
+            } else {
+                let relative = file.strip_prefix(&self.workspace_path).unwrap_or(&file);
+                unsupported_files.push(relative.to_path_buf());
+            }
+        }
+        unsupported_files.sort();
+        
+        Ok(DryRunReport {
+            language_counts: language_counts.into_iter().collect(),
+            config_files,
+            unsupported_files,
+        })
+    }
+    
+    /// 列出工作空间提供的所有 HTTP 端点
+    /// 
+    /// 只构建索引，不需要 patch
+    /// 
+    /// # Returns
+    /// * `Ok(Vec<(HttpEndpoint, String)>)` - 按路径和 HTTP 方法排序的 (端点, 提供者方法) 列表
+    /// * `Err(AnalysisError)` - 索引构建错误
+    pub fn list_http_endpoints(&mut self) -> Result<Vec<(HttpEndpoint, String)>, AnalysisError> {
+        self.warnings.clear();
+        self.parse_failures = 0;
+        self.errors.clear();
+        
+        let code_index = self.build_index()?;
+        
+        Ok(code_index.all_http_providers()
+            .into_iter()
+            .map(|(endpoint, provider)| (endpoint, provider.to_string()))
+            .collect())
+    }
+    
+    /// 构建或加载索引并导出为 JSON
+    /// 
+    /// 只构建索引，不需要 patch
+    /// 
+    /// # Returns
+    /// * `Ok(String)` - 索引的 JSON 表示，见 [`CodeIndex::to_json`]
+    /// * `Err(AnalysisError)` - 索引构建或序列化错误
+    pub fn dump_index(&mut self) -> Result<String, AnalysisError> {
+        self.warnings.clear();
+        self.parse_failures = 0;
+        self.errors.clear();
+        
+        let code_index = self.build_index()?;
+        code_index.to_json()
+            .map_err(|e| AnalysisError::IoError(std::io::Error::other(e.to_string())))
+    }
+    
+    /// 列出无法解析到已索引方法的调用目标及其调用次数
+    /// 
+    /// 只构建索引，不需要 patch
+    /// 
+    /// # Returns
+    /// * `Ok(Vec<(String, usize)>)` - 按调用次数降序、目标名升序排序的 (调用目标, 调用次数) 列表
+    /// * `Err(AnalysisError)` - 索引构建错误
+    pub fn list_unresolved_targets(&mut self) -> Result<Vec<(String, usize)>, AnalysisError> {
+        self.warnings.clear();
+        self.parse_failures = 0;
+        self.errors.clear();
+        
+        let code_index = self.build_index()?;
+        
+        let mut targets: Vec<(String, usize)> = code_index.unresolved_targets()
+            .into_iter()
+            .map(|target| (target.to_string(), code_index.find_callers(target).len()))
+            .collect();
+        targets.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+        Ok(targets)
+    }
+    
+    /// 仅提取 patch 涉及的变更方法，不追溯影响
+    /// 
+    /// 执行解析 patch、构建索引、提取变更方法三个步骤，跳过影响追溯，
+    /// 适合只需要变更方法列表的脚本场景。设置了最低可见性时与分析一样，
+    /// 列出替换后的满足可见性的方法
+    /// 
+    /// # Arguments
+    /// * `patch_dir` - Git patch 文件目录路径
+    /// 
+    /// # Returns
+    /// * `Ok(Vec<String>)` - 按全限定名排序、去重后的变更方法列表
+    /// * `Err(AnalysisError)` - 分析错误
+    pub fn list_changed_methods(&mut self, patch_dir: &Path) -> Result<Vec<String>, AnalysisError> {
+        self.changed_methods_in_patches(patch_dir).map(|(changed_methods, _)| changed_methods)
+    }
+    
+    /// 解析 patch、构建索引并确定追溯起点（见 `select_seed_methods`）
+    /// 
+    /// # Returns
+    /// * `Ok((Vec<String>, Arc<CodeIndex>))` - 按全限定名排序、去重后的起点方法列表及构建的索引
+    /// * `Err(AnalysisError)` - 分析错误
+    fn changed_methods_in_patches(&mut self, patch_dir: &Path) -> Result<(Vec<String>, Arc<CodeIndex>), AnalysisError> {
+        // 清空之前的警告和错误
+        self.warnings.clear();
+        self.parse_failures = 0;
+        self.errors.clear();
+        
+        progress!(self.quiet, "Step 1: Parsing patch files from directory");
+        let file_changes = self.parse_patches_from_directory(patch_dir)?;
+        progress!(self.quiet, "Found {} file changes", file_changes.len());
+        
+        // 重建索引前取出被删除文件中原有的方法
+        let deleted_methods = self.deleted_file_methods(&file_changes);
+        
+        progress!(self.quiet, "Step 2: Building code index");
+        let code_index = self.build_index()?;
+        
+        let changed_methods = self.select_seed_methods(&file_changes, &deleted_methods, &code_index)?;
+        
+        Ok((changed_methods, code_index))
+    }
+    
+    /// 从文件变更中提取变更方法并确定追溯起点
+    /// 
+    /// 被删除的方法作为变更方法追溯其原有的调用者；设置了最低可见性时，
+    /// 可见性不足的方法替换为满足可见性的调用者。分析和 `--list-changed` 共用，保证两者的起点一致
+    /// 
+    /// # Arguments
+    /// * `file_changes` - 文件变更列表
+    /// * `deleted_methods` - 被删除文件中原有的方法
+    /// * `code_index` - 代码索引
+    /// 
+    /// # Returns
+    /// * `Ok(Vec<String>)` - 按全限定名排序、去重后的起点方法列表
+    /// * `Err(AnalysisError)` - 分析错误
+    fn select_seed_methods(
+        &mut self,
+        file_changes: &[FileChange],
+        deleted_methods: &[String],
+        code_index: &CodeIndex,
+    ) -> Result<Vec<String>, AnalysisError> {
+        progress!(self.quiet, "Step 3: Extracting changed methods from patch");
+        let mut changed_methods = self.extract_changed_methods(file_changes, code_index)?;
+        changed_methods.extend(deleted_methods.iter().cloned());
+        changed_methods.sort();
+        changed_methods.dedup();
+        progress!(self.quiet, "Found {} changed methods", changed_methods.len());
+        
+        if let Some(min_visibility) = self.min_visibility {
+            changed_methods = lift_to_visible_callers(&changed_methods, code_index, min_visibility);
+            progress!(self.quiet, "Using {} methods with visibility {:?} or higher as seeds",
+                      changed_methods.len(), min_visibility);
+        }
+        
+        Ok(changed_methods)
+    }
+    
+    /// 提取 patch 涉及的变更方法及其方法体中变更的行，不追溯影响
+    /// 
+    /// 与 `list_changed_methods` 得到相同的方法列表，每个方法附带方法体内的变更行号和方法源码。
+    /// 按最近方法归属的方法之外的变更不计入变更行；被删除文件中的方法没有源码
+    /// 
+    /// # Arguments
+    /// * `patch_dir` - Git patch 文件目录路径
+    /// 
+    /// # Returns
+    /// * `Ok(Vec<ChangedMethod>)` - 按全限定名排序的变更方法列表
+    /// * `Err(AnalysisError)` - 分析错误
+    pub fn list_changed_method_bodies(&mut self, patch_dir: &Path) -> Result<Vec<ChangedMethod>, AnalysisError> {
+        let (changed_methods, code_index) = self.changed_methods_in_patches(patch_dir)?;
+        Ok(self.changed_method_bodies(&changed_methods, &code_index))
+    }
+    
     /// 解析 patch 目录中的所有文件
     fn parse_patches_from_directory(&mut self, patch_dir: &Path) -> Result<Vec<FileChange>, AnalysisError> {
         // 检查路径是否存在
@@ -218,7 +1100,7 @@ impl AnalysisOrchestrator {
             return self.parse_patch(patch_dir, None);
         }
         
-        // 如果是目录，遍历所有 .patch 文件
+        // 如果是目录，遍历所有 patch 文件（默认 .patch 和 .diff）
         if !patch_dir.is_dir() {
             let error_msg = format!("Patch path is neither a file nor a directory: {:?}", patch_dir);
             self.errors.push(error_msg.clone());
@@ -238,53 +1120,61 @@ impl AnalysisOrchestrator {
         
         let mut patch_files = Vec::new();
         for entry in entries {
-            let entry = entry.map_err(|e| AnalysisError::IoError(e))?;
+            let entry = entry.map_err(AnalysisError::IoError)?;
             let path = entry.path();
             
-            // 只处理 .patch 文件
-            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("patch") {
+            // 只处理扩展名在可识别列表中的文件
+            if path.is_file() && self.is_patch_file(&path) {
                 patch_files.push(path);
             }
         }
         
+        // 按文件名排序，保证处理顺序稳定
+        patch_files.sort();
+        
         if patch_files.is_empty() {
-            let warning = format!("No .patch files found in directory: {:?}", patch_dir);
+            let warning = format!(
+                "No patch files (extensions: {}) found in directory: {:?}",
+                self.patch_extensions.join(", "),
+                patch_dir
+            );
             log::warn!("{}", warning);
             self.warnings.push(warning);
             return Ok(Vec::new());
         }
         
-        log::info!("Found {} patch files to process", patch_files.len());
+        progress!(self.quiet, "Found {} patch files to process", patch_files.len());
         
         // 解析每个 patch 文件
         for patch_file in patch_files {
-            log::info!("Processing patch file: {:?}", patch_file);
+            progress!(self.quiet, "Processing patch file: {:?}", patch_file);
             
-            // 从文件名提取项目名（去掉 .patch 扩展名）
+            // 从文件名提取项目名（去掉 .patch / .diff 扩展名）
             let project_name = patch_file
                 .file_stem()
                 .and_then(|s| s.to_str())
                 .map(|s| s.to_string());
             
             if let Some(ref name) = project_name {
-                log::info!("  - Project name: {}", name);
+                progress!(self.quiet, "  - Project name: {}", name);
             }
             
             match self.parse_patch(&patch_file, project_name) {
                 Ok(mut changes) => {
-                    log::info!("  - Parsed {} file changes from {:?}", changes.len(), patch_file.file_name().unwrap());
+                    progress!(self.quiet, "  - Parsed {} file changes from {:?}", changes.len(), patch_file.file_name().unwrap());
                     all_changes.append(&mut changes);
                 }
                 Err(e) => {
                     let warning = format!("Failed to parse patch file {:?}: {}", patch_file, e);
                     log::warn!("{}", warning);
                     self.warnings.push(warning);
+                    self.parse_failures += 1;
                     // 继续处理其他文件，不中断整个流程
                 }
             }
         }
         
-        log::info!("Total file changes from all patches: {}", all_changes.len());
+        progress!(self.quiet, "Total file changes from all patches: {}", all_changes.len());
         Ok(all_changes)
     }
     
@@ -314,25 +1204,75 @@ impl AnalysisOrchestrator {
         }
     }
     
-    /// 构建代码索引
-    fn build_index(&mut self) -> Result<CodeIndex, AnalysisError> {
+    /// 构建代码索引，并按配置合并 Redis 键命名空间、Kafka Topic 环境前缀、数据库表名和 Feign 服务名别名
+    /// 
+    /// 索引与内存缓存共享时，只有需要按配置调整才复制一份，缓存中的索引保持不变
+    fn build_index(&mut self) -> Result<Arc<CodeIndex>, AnalysisError> {
+        let mut index = self.load_or_build_index()?;
+        if self.redis_namespace_depth.is_some() {
+            Arc::make_mut(&mut index).set_redis_namespace_depth(self.redis_namespace_depth);
+        }
+        if !self.kafka_topic_prefixes.is_empty() {
+            Arc::make_mut(&mut index).set_kafka_topic_prefixes(self.kafka_topic_prefixes.clone());
+        }
+        if self.table_normalization.is_enabled() {
+            Arc::make_mut(&mut index).set_table_normalization(self.table_normalization);
+        }
+        if !self.service_aliases.is_empty() {
+            Arc::make_mut(&mut index).set_service_aliases(&self.service_aliases);
+        }
+        Ok(index)
+    }
+    
+    /// 从磁盘缓存加载代码索引，不可用时重新构建；使用远程索引时直接获取
+    fn load_or_build_index(&mut self) -> Result<Arc<CodeIndex>, AnalysisError> {
+        let source_extensions = self.source_extensions();
+        if let IndexSource::Local(storage) = &mut self.index_source {
+            storage.set_source_extensions(source_extensions);
+        }
+        let storage = match &self.index_source {
+            IndexSource::Local(storage) => storage,
+            IndexSource::Remote { url } => {
+                if self.index_cache.is_some() {
+                    log::info!("Memory index cache is not used for remote index {}", url);
+                }
+                progress!(self.quiet, "Fetching index from {}", url);
+                let index = fetch_remote_index(url, &self.workspace_path).map_err(|e| {
+                    self.errors.push(format!("Failed to fetch remote index: {}", e));
+                    AnalysisError::IndexBuildError(e)
+                })?;
+                self.record_parse_diagnostics(&index);
+                return Ok(Arc::new(index));
+            }
+        };
+        
         // 如果强制重建，清除现有索引
-        if self.force_rebuild {
-            log::info!("Force rebuild enabled, clearing existing index");
-            if let Err(e) = self.index_storage.clear_index() {
+        if self.force_rebuild && self.persist_index {
+            progress!(self.quiet, "Force rebuild enabled, clearing existing index");
+            if let Err(e) = storage.clear_index() {
                 log::warn!("Failed to clear index: {}", e);
             }
         }
         
-        // 尝试加载现有索引
-        if !self.force_rebuild {
-            match self.index_storage.load_index() {
+        // 启用调用过滤或限制 HTTP 匹配时索引内容与缓存不同，不使用磁盘缓存
+        let use_cache = self.uses_disk_index();
+        
+        // 尝试加载现有索引，限定服务时只加载这些服务的分区
+        if !self.force_rebuild && use_cache {
+            let loaded = if self.trace_config.allowed_services.is_empty() {
+                storage.load_index()
+            } else {
+                storage.load_index_for_services(&self.trace_config.allowed_services)
+                    .map(|index| index.map(Arc::new))
+            };
+            match loaded {
                 Ok(Some(index)) => {
-                    log::info!("Loaded existing index from cache");
+                    progress!(self.quiet, "Loaded existing index from cache");
+                    self.record_parse_diagnostics(&index);
                     return Ok(index);
                 }
                 Ok(None) => {
-                    log::info!("No valid index found, building new index");
+                    progress!(self.quiet, "No valid index found, building new index");
                 }
                 Err(e) => {
                     log::warn!("Failed to load index: {}, will rebuild", e);
@@ -342,21 +1282,30 @@ impl AnalysisOrchestrator {
         
         // 构建新索引
         let mut index = CodeIndex::new();
+        index.set_quiet(self.quiet);
+        index.set_min_http_segments(self.min_http_segments);
+        index.set_language_overrides(self.language_overrides.clone());
+        index.set_extra_source_roots(self.extra_source_roots.clone());
         
         match index.index_workspace(&self.workspace_path, &self.parsers) {
             Ok(_) => {
-                log::info!("Workspace indexed successfully");
+                progress!(self.quiet, "Workspace indexed successfully");
+                self.record_parse_diagnostics(&index);
                 
                 // 解析配置文件并关联到代码
                 self.parse_and_associate_configs(&mut index);
                 
                 // 保存索引到磁盘
-                if let Err(e) = self.index_storage.save_index(&index) {
+                if use_cache
+                    && self.persist_index
+                    && let IndexSource::Local(storage) = &self.index_source
+                    && let Err(e) = storage.save_index(&index)
+                {
                     log::warn!("Failed to save index: {}", e);
                     // 不中断流程，继续使用内存中的索引
                 }
                 
-                Ok(index)
+                Ok(Arc::new(index))
             }
             Err(e) => {
                 let error_msg = format!("Failed to build index: {}", e);
@@ -366,18 +1315,69 @@ impl AnalysisOrchestrator {
         }
     }
     
+    /// 将索引中记录的源文件解析失败和语法错误计入警告和解析失败数
+    /// 
+    /// 新构建的索引和从磁盘或远程加载的索引都会调用，使 `--strict` 的结果与索引来源无关
+    fn record_parse_diagnostics(&mut self, index: &CodeIndex) {
+        for file in index.parse_failures() {
+            self.warnings.push(format!("Failed to parse source file {:?}", file));
+        }
+        let syntax_errors = index.syntax_errors();
+        for error in &syntax_errors {
+            self.warnings.push(format!("{}; indexed from the recovered syntax tree", error));
+        }
+        self.parse_failures += index.parse_failures().len() + syntax_errors.len();
+    }
+    
+    /// 对影响图中调用目标无法确定的反射调用记录警告
+    /// 
+    /// 包括目标为 `<unknown>`、所属类未知的 `<unknown>::name`，以及推断出的目标不在索引中的
+    /// 反射调用；这类调用的真实目标不在影响图中，可能遗漏影响
+    fn warn_hidden_reflective_calls(&mut self, graph: &ImpactGraph, index: &CodeIndex) {
+        for node in graph.nodes() {
+            let NodeType::Method { qualified_name } = &node.node_type else {
+                continue;
+            };
+            let hidden = index.find_method(qualified_name).is_some_and(|method| {
+                method.calls.iter()
+                    .filter(|call| call.low_confidence)
+                    .any(|call| {
+                        let target = index.resolve_inherited_call(&index.resolve_interface_call(&call.target));
+                        index.find_method(&target).is_none()
+                    })
+            });
+            if hidden {
+                let warning = format!("reflective call in {} may hide impact", qualified_name);
+                log::warn!("{}", warning);
+                self.warnings.push(warning);
+            }
+        }
+    }
+    
+    /// 对代码中访问但迁移脚本中未定义的数据库表记录警告
+    /// 
+    /// 通常是表名拼写错误或缺少迁移；工作空间中没有声明任何表时不检查
+    fn warn_undeclared_db_tables(&mut self, index: &CodeIndex) {
+        for table in index.undeclared_db_tables() {
+            let warning = format!("Database table '{}' is referenced in code but not defined by any migration", table);
+            log::warn!("{}", warning);
+            self.warnings.push(warning);
+        }
+    }
+    
     /// 解析配置文件并关联到代码
     fn parse_and_associate_configs(&mut self, index: &mut CodeIndex) {
         // 查找所有配置文件
         let config_files = self.find_config_files();
         
-        log::info!("Found {} configuration files", config_files.len());
+        progress!(self.quiet, "Found {} configuration files", config_files.len());
         
         for config_file in config_files {
             if let Err(e) = self.parse_config_file(&config_file, index) {
                 let warning = format!("Failed to parse config file {:?}: {}", config_file, e);
                 log::warn!("{}", warning);
                 self.warnings.push(warning);
+                self.parse_failures += 1;
             }
         }
     }
@@ -404,10 +1404,10 @@ impl AnalysisOrchestrator {
             let path = entry.path();
             
             // 跳过隐藏目录和构建目录
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.starts_with('.') || name == "target" || name == "build" || name == "node_modules" {
-                    continue;
-                }
+            if let Some(name) = path.file_name().and_then(|n| n.to_str())
+                && (name.starts_with('.') || name == "target" || name == "build" || name == "node_modules")
+            {
+                continue;
             }
             
             if path.is_dir() {
@@ -422,18 +1422,14 @@ impl AnalysisOrchestrator {
     
     /// 判断是否是配置文件
     fn is_config_file(&self, path: &Path) -> bool {
-        if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-            matches!(ext, "xml" | "yaml" | "yml")
-        } else {
-            false
-        }
+        self.select_config_parser(path).is_some()
     }
     
     /// 解析单个配置文件
     fn parse_config_file(&mut self, config_path: &Path, index: &mut CodeIndex) -> Result<(), AnalysisError> {
         // 读取文件内容
         let content = std::fs::read_to_string(config_path)
-            .map_err(|e| AnalysisError::IoError(e))?;
+            .map_err(AnalysisError::IoError)?;
         
         // 选择合适的配置解析器
         let parser = self.select_config_parser(config_path)
@@ -445,14 +1441,30 @@ impl AnalysisOrchestrator {
             })?;
         
         // 解析配置
-        let config_data = parser.parse(&content)
+        let mut config_data = parser.parse(&content)
             .map_err(|e| AnalysisError::ConfigParseError {
                 file: config_path.to_path_buf(),
                 error: e,
             })?;
         
-        // 关联配置到代码
-        index.associate_config_data(&config_data);
+        // YAML 配置额外从指定键路径提取 Kafka Topic
+        if !self.config_topic_paths.is_empty() && parser.supports_format("yaml") {
+            let topics = yaml_topics_at_paths(&content, &self.config_topic_paths)
+                .map_err(|e| AnalysisError::ConfigParseError {
+                    file: config_path.to_path_buf(),
+                    error: e,
+                })?;
+            for topic in topics {
+                if !config_data.kafka_topics.contains(&topic) {
+                    config_data.kafka_topics.push(topic);
+                }
+            }
+        }
+        
+        // 关联配置到代码，Feign 客户端配置只对配置文件所在模块生效
+        let relative = config_path.strip_prefix(&self.workspace_path).unwrap_or(config_path);
+        let module = service_from_path(relative).unwrap_or_default();
+        index.associate_module_config_data(&module, &config_data);
         
         log::debug!("Parsed config file: {:?}", config_path);
         
@@ -460,31 +1472,53 @@ impl AnalysisOrchestrator {
     }
     
     /// 选择合适的配置解析器
-    fn select_config_parser(&self, path: &Path) -> Option<&Box<dyn ConfigParser>> {
+    fn select_config_parser(&self, path: &Path) -> Option<&dyn ConfigParser> {
         let ext = path.extension()?.to_str()?;
+        let format = match ext {
+            "yml" => "yaml",
+            other => other,
+        };
         
-        self.config_parsers.iter().find(|p| {
-            match ext {
-                "xml" => p.supports_format("xml"),
-                "yaml" | "yml" => p.supports_format("yaml"),
-                _ => false,
-            }
-        })
+        self.config_parsers.iter().find(|p| p.supports_format(format)).map(|p| p.as_ref())
     }
     
     /// 从文件变更中提取变更的方法
+    /// 
+    /// 变更范围与方法的行范围重叠时，该方法为变更方法。若某个变更范围没有
+    /// 落在任何方法内（如只修改了方法之间的空行或括号），则归属到文件中
+    /// 结束位置最接近且位于变更之前的方法，并记录为低置信度变更。
+    /// 
+    /// 多个 patch 依次修改同一文件时，先按文件合并各 patch 的变更范围：
+    /// 前面 patch 的行号经过后续 patch 的 hunk 偏移换算为最终文件中的行号
     fn extract_changed_methods(
         &mut self,
         file_changes: &[FileChange],
         code_index: &CodeIndex,
     ) -> Result<Vec<String>, AnalysisError> {
         let mut changed_methods = Vec::new();
+        let mut exact_methods = HashSet::new();
+        let mut fallback_methods = Vec::new();
+        self.change_churn.clear();
+        self.changed_lines.clear();
+        
+        // 按文件分组索引中的方法，每个方法的文件路径只规范化一次
+        let mut methods_by_file: HashMap<PathBuf, Vec<(&String, &MethodInfo)>> = HashMap::new();
+        for (method_name, method_info) in code_index.methods() {
+            methods_by_file.entry(self.file_key(&method_info.file_path))
+                .or_default()
+                .push((method_name, method_info));
+        }
         
-        for file_change in file_changes {
-            // 获取文件的完整路径
-            let file_path = self.workspace_path.join(&file_change.file_path);
+        for (file_path, changes) in self.group_changes_by_file(file_changes) {
+            let file_change = changes[0];
             
-            // 如果文件不存在（可能是删除的文件），跳过
+            // 删除的文件中原有的方法从上一次的磁盘索引中取得，这里跳过
+            if file_change.change_type == ChangeType::Deleted {
+                log::debug!("Skipping deleted file {:?}", file_path);
+                continue;
+            }
+            
+            // 如果文件不存在，跳过
             if !file_path.exists() {
                 let warning = format!("File does not exist: {:?}", file_path);
                 log::warn!("{}", warning);
@@ -502,44 +1536,262 @@ impl AnalysisOrchestrator {
                     continue;
                 }
             };
-            
-            // 从 hunk 中提取变更的行号范围
-            let mut modified_line_ranges = Vec::new();
-            for hunk in &file_change.hunks {
-                let start = hunk.new_start;
-                let end = hunk.new_start + hunk.new_lines;
-                modified_line_ranges.push((start, end));
+            
+            // 只保留非平凡变更或新增行所在的行；两个选项都未启用时保留所有变更行
+            let extension = file_path.extension().and_then(|e| e.to_str());
+            let keep = |line: &HunkLine| {
+                (!self.added_only || line.line_type == LineType::Added)
+                    && (!self.significant_changes_only || !is_trivial_line(&line.content, extension))
+            };
+            
+            // 从 hunk 中提取变更的行号范围，并换算为最终文件中的行号；
+            // 同时记录每个 hunk 的变更行，用于统计方法的改动量
+            let mut modified_line_ranges = Vec::new();
+            let mut hunk_lines: Vec<Vec<usize>> = Vec::new();
+            // 行内修改的列范围，用于区分同一行上的多个方法
+            let mut changed_columns: HashMap<usize, (usize, usize)> = HashMap::new();
+            for (i, change) in changes.iter().enumerate() {
+                let mut ranges = Vec::new();
+                if self.significant_changes_only || self.added_only {
+                    for hunk in &change.hunks {
+                        for line in hunk.lines_where(keep) {
+                            ranges.push((line, line));
+                        }
+                    }
+                } else {
+                    for hunk in &change.hunks {
+                        let start = hunk.new_start;
+                        let end = hunk.new_start + hunk.new_lines;
+                        ranges.push((start, end));
+                    }
+                }
+                
+                let mut lines: Vec<Vec<usize>> = change.hunks.iter().map(|hunk| hunk.lines_where(keep)).collect();
+                let mut columns: Vec<(usize, usize, usize)> = change.hunks.iter().flat_map(|hunk| hunk.changed_columns()).collect();
+                
+                for later in &changes[i + 1..] {
+                    for range in &mut ranges {
+                        *range = later.map_line_range(range.0, range.1);
+                    }
+                    for line in lines.iter_mut().flatten() {
+                        *line = later.map_line_range(*line, *line).0;
+                    }
+                    for (line, _, _) in &mut columns {
+                        *line = later.map_line_range(*line, *line).0;
+                    }
+                }
+                modified_line_ranges.extend(ranges);
+                hunk_lines.extend(lines);
+                // 多个 patch 修改同一行时取各列范围的并集
+                for (line, start, end) in columns {
+                    changed_columns.entry(line)
+                        .and_modify(|range| *range = (range.0.min(start), range.1.max(end)))
+                        .or_insert((start, end));
+                }
+            }
+            
+            // 查找这些行范围内的方法
+            // 取出属于当前文件的方法；由多段代码组成的方法（如 `<clinit>`）每段单独参与匹配
+            let file_key = self.file_key(&file_path);
+            let file_methods: Vec<(&String, (usize, usize))> = methods_by_file.get(&file_key)
+                .into_iter()
+                .flatten()
+                .flat_map(|(method_name, method_info)| method_info.line_ranges().map(move |range| (*method_name, range)))
+                .collect();
+            
+            self.changed_lines.insert(file_key, hunk_lines.iter().flatten().copied().collect());
+            
+            // 统计每个方法重叠的 hunk 数和变更行数
+            for (method_name, (method_start, method_end)) in &file_methods {
+                let counts: Vec<usize> = hunk_lines.iter()
+                    .map(|lines| lines.iter().filter(|line| (*method_start..=*method_end).contains(*line)).count())
+                    .filter(|count| *count > 0)
+                    .collect();
+                if !counts.is_empty() {
+                    let churn = self.change_churn.entry((*method_name).clone()).or_default();
+                    churn.change_regions += counts.len();
+                    churn.changed_lines += counts.iter().sum::<usize>();
+                }
+            }
+            
+            for (change_start, change_end) in &modified_line_ranges {
+                // 检查方法的行范围是否与变更范围重叠
+                let overlapping: Vec<&String> = file_methods.iter()
+                    .filter(|(_, (method_start, method_end))| *method_start <= *change_end && *method_end >= *change_start)
+                    .map(|(method_name, _)| *method_name)
+                    .collect();
+                
+                // 多个方法写在同一行时，按行内修改的列范围只保留实际被修改的方法；
+                // 修改不落在任何方法的列范围内（如只改了方法之间的空白）时保留全部重叠方法
+                let narrowed: Vec<&String> = overlapping.iter().copied()
+                    .filter(|method_name| code_index.find_method(method_name)
+                        .is_none_or(|method| touches_changed_columns(method, (*change_start, *change_end), &changed_columns)))
+                    .collect();
+                let overlapping = if narrowed.is_empty() { overlapping } else { narrowed };
+                
+                if !overlapping.is_empty() {
+                    for method_name in overlapping {
+                        log::debug!("Found changed method: {} in file {:?}", method_name, file_path);
+                        exact_methods.insert(method_name.clone());
+                        changed_methods.push(method_name.clone());
+                    }
+                    continue;
+                }
+                
+                // 没有重叠的方法：归属到变更之前最近的方法
+                let nearest = file_methods.iter()
+                    .filter(|(_, (_, method_end))| *method_end < *change_start)
+                    .max_by_key(|(_, (method_start, method_end))| (*method_end, *method_start));
+                if let Some((method_name, _)) = nearest {
+                    let warning = format!(
+                        "Change at lines {}-{} in {:?} is outside any method, attributed to nearest method {} (low confidence)",
+                        change_start, change_end, file_change.file_path, method_name
+                    );
+                    log::warn!("{}", warning);
+                    self.warnings.push(warning);
+                    fallback_methods.push((*method_name).clone());
+                    changed_methods.push((*method_name).clone());
+                }
+            }
+        }
+        
+        // 去重
+        changed_methods.sort();
+        changed_methods.dedup();
+        
+        // 同时被精确匹配的方法不是低置信度
+        fallback_methods.retain(|method| !exact_methods.contains(method));
+        fallback_methods.sort();
+        fallback_methods.dedup();
+        self.low_confidence_methods = fallback_methods;
+        
+        Ok(changed_methods)
+    }
+    
+    /// 查找 patch 中被整个删除的文件原有的方法
+    /// 
+    /// 删除的文件已不在工作空间中，其方法只能从上一次保存的磁盘索引中取得（不检查索引是否过期），
+    /// 必须在重建索引之前调用。使用远程索引或没有磁盘索引时无法确定，记录警告后返回空列表
+    /// 
+    /// # Returns
+    /// * 被删除文件中的方法全限定名（已排序）
+    fn deleted_file_methods(&mut self, file_changes: &[FileChange]) -> Vec<String> {
+        let deleted_files: HashSet<PathBuf> = file_changes.iter()
+            .filter(|change| change.change_type == ChangeType::Deleted)
+            .map(|change| self.file_key(&self.change_path(&change.file_path)))
+            .collect();
+        if deleted_files.is_empty() {
+            return Vec::new();
+        }
+        
+        let previous = match &self.index_source {
+            IndexSource::Local(storage) => storage.load_previous_index(),
+            IndexSource::Remote { .. } => Ok(None),
+        };
+        let previous = match previous {
+            Ok(Some(index)) => index,
+            Ok(None) => {
+                let warning = format!(
+                    "No previous index to look up methods of {} deleted file(s), their callers are not traced",
+                    deleted_files.len()
+                );
+                log::warn!("{}", warning);
+                self.warnings.push(warning);
+                return Vec::new();
+            }
+            Err(e) => {
+                let warning = format!("Failed to load previous index for deleted files: {}", e);
+                log::warn!("{}", warning);
+                self.warnings.push(warning);
+                return Vec::new();
+            }
+        };
+        
+        let mut methods: Vec<String> = previous.methods()
+            .filter(|(_, method_info)| deleted_files.contains(&self.file_key(&method_info.file_path)))
+            .map(|(method_name, _)| method_name.clone())
+            .collect();
+        methods.sort();
+        progress!(self.quiet, "Found {} methods in deleted files", methods.len());
+        methods
+    }
+    
+    /// patch 中的文件路径对应的工作空间中的路径
+    fn change_path(&self, change_path: &str) -> PathBuf {
+        if self.normalize_paths {
+            self.workspace_path.join(normalize_path(Path::new(change_path), &self.workspace_path))
+        } else {
+            self.workspace_path.join(change_path)
+        }
+    }
+    
+    /// 比较索引与 patch 中的文件路径时使用的键：启用路径规范化时为规范化后的路径，否则为原路径
+    fn file_key(&self, path: &Path) -> PathBuf {
+        if self.normalize_paths {
+            normalize_path(path, &self.workspace_path)
+        } else {
+            path.to_path_buf()
+        }
+    }
+    
+    /// 按解析后的文件路径分组，保持 patch 的处理顺序
+    /// 
+    /// 路径按 `file_key` 比较，启用路径规范化时 `./src/Foo.java` 与 `src\Foo.java` 归为同一文件
+    /// 
+    /// 二进制变更和仅权限变更没有可分析的源码内容，记录提示信息后跳过
+    fn group_changes_by_file<'a>(&mut self, file_changes: &'a [FileChange]) -> Vec<(PathBuf, Vec<&'a FileChange>)> {
+        let mut file_groups: Vec<(PathBuf, Vec<&FileChange>)> = Vec::new();
+        for file_change in file_changes {
+            let skipped = match file_change.kind {
+                ChangeKind::Text => None,
+                ChangeKind::Binary => Some("binary file change"),
+                ChangeKind::ModeOnly => Some("file mode change without content changes"),
+            };
+            if let Some(reason) = skipped {
+                let warning = format!("Skipping {}: {}", reason, file_change.file_path);
+                log::info!("{}", warning);
+                self.warnings.push(warning);
+                continue;
             }
             
-            // 查找这些行范围内的方法
-            // 遍历索引中的所有方法，检查是否属于当前文件且在变更范围内
-            for (method_name, method_info) in code_index.methods() {
-                // 首先检查方法是否属于当前文件
-                // 通过比较 file_path 来判断
-                if method_info.file_path != file_path {
-                    continue;
-                }
-                
-                // 检查方法的行范围是否与变更范围重叠
-                let method_start = method_info.line_range.0;
-                let method_end = method_info.line_range.1;
-                
-                for (change_start, change_end) in &modified_line_ranges {
-                    // 检查是否有重叠
-                    if method_start <= *change_end && method_end >= *change_start {
-                        changed_methods.push(method_name.clone());
-                        log::debug!("Found changed method: {} in file {:?}", method_name, file_path);
-                        break;
-                    }
-                }
+            let file_path = self.change_path(&file_change.file_path);
+            let key = self.file_key(&file_path);
+            match file_groups.iter_mut().find(|(path, _)| self.file_key(path) == key) {
+                Some((_, changes)) => changes.push(file_change),
+                None => file_groups.push((file_path, vec![file_change])),
             }
         }
-        
-        // 去重
-        changed_methods.sort();
-        changed_methods.dedup();
-        
-        Ok(changed_methods)
+        file_groups
+    }
+    
+    /// 为变更方法附加方法体中变更的行和方法源码
+    /// 
+    /// 变更行取自最近一次 `extract_changed_methods` 计入的变更行；不在索引中的方法
+    /// （被删除文件中原有的方法）没有变更行和源码
+    /// 
+    /// # Arguments
+    /// * `changed_methods` - 变更方法全限定名列表
+    /// * `code_index` - 代码索引
+    fn changed_method_bodies(&self, changed_methods: &[String], code_index: &CodeIndex) -> Vec<ChangedMethod> {
+        let mut contents: HashMap<&Path, String> = HashMap::new();
+        
+        changed_methods.iter()
+            .map(|name| {
+                let Some(method_info) = code_index.find_method(name) else {
+                    return ChangedMethod {
+                        name: name.clone(),
+                        changed_lines: Vec::new(),
+                        snippet: String::new(),
+                    };
+                };
+                let content = contents.entry(method_info.file_path.as_path())
+                    .or_insert_with(|| std::fs::read_to_string(&method_info.file_path).unwrap_or_default());
+                let changed_lines = self.changed_lines.get(&self.file_key(&method_info.file_path))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                changed_method_body(content, name, method_info.line_ranges(), changed_lines)
+            })
+            .collect()
     }
     
     /// 追溯影响
@@ -548,18 +1800,142 @@ impl AnalysisOrchestrator {
         changed_methods: &[String],
         code_index: &CodeIndex,
     ) -> Result<ImpactGraph, AnalysisError> {
-        let tracer = ImpactTracer::new(code_index, self.trace_config.clone());
+        let trace_cache = self.trace_cache.clone();
+        let mut tracer = ImpactTracer::new(code_index, self.trace_config.clone())
+            .with_workspace_root(self.workspace_path.clone())
+            .with_resource_matchers(self.resource_matchers.iter().cloned());
+        if let Some(cache) = &trace_cache
+            && let Some(index_key) = self.trace_cache_index_key()
+        {
+            tracer = tracer.with_trace_cache(cache, index_key);
+        }
         
-        match tracer.trace_impact(changed_methods) {
-            Ok(graph) => Ok(graph),
+        let mut graph = match tracer.trace_impact(changed_methods) {
+            Ok(graph) => graph,
             Err(e) => {
                 let error_msg = format!("Failed to trace impact: {}", e);
                 self.errors.push(error_msg.clone());
-                Err(AnalysisError::TraceError(e))
+                return Err(AnalysisError::TraceError(e));
+            }
+        };
+        
+        let seed_ids: Vec<String> = changed_methods.iter()
+            .map(|m| format!("method:{}", m))
+            .collect();
+        
+        if self.reachable_only {
+            let reachable = code_index.reachable_from_entry_points();
+            let pruned = graph.filter_methods(|method| reachable.contains(method), &seed_ids);
+            progress!(self.quiet, "Pruned {} nodes unreachable from entry points",
+                      graph.node_count() - pruned.node_count());
+            graph = pruned;
+        }
+        
+        if self.contract_delegations {
+            let contracted = graph.contract_methods(
+                |method| code_index.find_method(method).is_some_and(|info| info.is_delegation),
+                &seed_ids,
+            );
+            progress!(self.quiet, "Contracted {} delegation methods",
+                      graph.node_count() - contracted.node_count());
+            graph = contracted;
+        }
+        
+        if self.hide_low_visibility && let Some(min_visibility) = self.min_visibility {
+            let contracted = graph.contract_methods(
+                |method| code_index.find_method(method).is_some_and(|info| info.visibility < min_visibility),
+                &seed_ids,
+            );
+            progress!(self.quiet, "Contracted {} methods below visibility {:?}",
+                      graph.node_count() - contracted.node_count(), min_visibility);
+            graph = contracted;
+        }
+        
+        if self.annotate_blame {
+            self.annotate_last_commits(&mut graph, code_index);
+        }
+        
+        if self.label_style != LabelStyle::Full {
+            graph.set_label_style(self.label_style);
+        }
+        
+        Ok(graph)
+    }
+    
+    /// 为影响图中的方法节点附加 git blame 得到的最后修改作者和提交
+    fn annotate_last_commits(&mut self, graph: &mut ImpactGraph, code_index: &CodeIndex) {
+        let mut blame = match BlameCache::open(&self.workspace_path) {
+            Ok(blame) => blame,
+            Err(e) => {
+                let warning = format!("Failed to annotate last authors: {}", e);
+                log::warn!("{}", warning);
+                self.warnings.push(warning);
+                return;
+            }
+        };
+        
+        for node in graph.nodes_mut() {
+            let NodeType::Method { qualified_name } = &node.node_type else {
+                continue;
+            };
+            let Some(method) = code_index.find_method(qualified_name) else {
+                continue;
+            };
+            if let Some(last) = blame.last_commit(&self.workspace_path.join(&method.file_path), method.line_range) {
+                node.metadata.properties.insert("last_author".to_string(), last.author);
+                node.metadata.properties.insert("last_commit".to_string(), last.commit);
             }
         }
     }
     
+    /// 索引内容是否与磁盘索引一致：启用调用过滤、限制 HTTP 匹配或注册了自定义解析器时构建的索引不同于磁盘索引
+    fn uses_disk_index(&self) -> bool {
+        self.call_filter.is_empty() && self.min_http_segments == 0 && self.config_topic_paths.is_empty()
+            && self.language_overrides.is_empty() && self.extra_source_roots.is_empty()
+            && !self.custom_parsers
+    }
+    
+    /// 语言解析器和配置解析器处理的全部文件扩展名（小写、排序去重），这些文件计入磁盘索引的校验和
+    fn source_extensions(&self) -> Vec<String> {
+        let mut extensions: Vec<String> = self.parsers.iter()
+            .flat_map(|p| p.file_extensions())
+            .chain(self.config_parsers.iter().flat_map(|p| p.file_extensions()))
+            .map(|ext| ext.to_ascii_lowercase())
+            .collect();
+        extensions.sort_unstable();
+        extensions.dedup();
+        extensions
+    }
+    
+    /// 追溯缓存使用的索引标识：磁盘索引的校验和加上加载索引后改变索引内容的设置
+    /// 
+    /// 远程索引或索引内容不对应磁盘索引时返回 None，不使用追溯缓存
+    fn trace_cache_index_key(&self) -> Option<String> {
+        let IndexSource::Local(storage) = &self.index_source else {
+            return None;
+        };
+        if !self.uses_disk_index() {
+            return None;
+        }
+        let checksum = storage.get_index_info().ok().flatten()?.checksum;
+        
+        let mut service_aliases: Vec<String> = self.service_aliases.iter()
+            .map(|(service, application)| format!("{}={}", service, application))
+            .collect();
+        service_aliases.sort_unstable();
+        let table = self.table_normalization;
+        Some(format!(
+            "checksum={};redis_namespace_depth={};kafka_topic_prefixes={};table_normalization={},{},{};service_aliases={}",
+            checksum,
+            self.redis_namespace_depth.map(|depth| depth.to_string()).unwrap_or_default(),
+            self.kafka_topic_prefixes.join(","),
+            table.strip_prefix,
+            table.singularize,
+            table.lowercase,
+            service_aliases.join(","),
+        ))
+    }
+    
     /// 获取警告列表
     pub fn warnings(&self) -> &[String] {
         &self.warnings
@@ -571,6 +1947,207 @@ impl AnalysisOrchestrator {
     }
 }
 
+/// 将可见性低于 `min_visibility` 的方法替换为调用它们的、满足可见性的方法
+/// 
+/// 沿调用者逐层向上查找，直到遇到满足可见性的方法；找不到这样的调用者的方法不再作为起点。
+/// 不在索引中的方法（如被删除文件中的方法）可见性未知，原样保留
+/// 
+/// # Arguments
+/// * `methods` - 变更方法
+/// * `code_index` - 代码索引
+/// * `min_visibility` - 最低可见性
+/// 
+/// # Returns
+/// * 满足可见性的起点方法（已排序、去重）
+fn lift_to_visible_callers<'a>(
+    methods: &'a [String],
+    code_index: &'a CodeIndex,
+    min_visibility: Visibility,
+) -> Vec<String> {
+    let is_visible = |method: &str| {
+        code_index.find_method(method).is_none_or(|info| info.visibility >= min_visibility)
+    };
+    
+    let mut seeds = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue: VecDeque<&str> = methods.iter().map(String::as_str).collect();
+    while let Some(method) = queue.pop_front() {
+        if !visited.insert(method) {
+            continue;
+        }
+        if is_visible(method) {
+            seeds.push(method.to_string());
+        } else {
+            queue.extend(code_index.find_callers(method));
+        }
+    }
+    
+    seeds.sort();
+    seeds.dedup();
+    seeds
+}
+
+/// 规范化文件路径以便跨平台比较
+/// 
+/// 统一使用 `/` 分隔符并去掉 `.` 段（包括开头的 `./`）；位于工作空间内的绝对路径
+/// 转换为相对于工作空间的路径，其他绝对路径保留开头的 `/`
+/// 
+/// # Arguments
+/// * `path` - 文件路径
+/// * `workspace` - 工作空间根目录
+fn normalize_path(path: &Path, workspace: &Path) -> PathBuf {
+    let text = path.to_string_lossy().replace('\\', "/");
+    let workspace_text = workspace.to_string_lossy().replace('\\', "/");
+    let workspace_text = workspace_text.trim_end_matches('/');
+    
+    let (root, rest) = match text.strip_prefix(workspace_text) {
+        Some(rest) if !workspace_text.is_empty() && (rest.is_empty() || rest.starts_with('/')) => ("", rest),
+        _ if text.starts_with('/') => ("/", text.as_str()),
+        _ => ("", text.as_str()),
+    };
+    let segments: Vec<&str> = rest.split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect();
+    PathBuf::from(format!("{}{}", root, segments.join("/")))
+}
+
+/// 合并同类警告并按上限截断
+/// 
+/// 将引号内的内容（文件路径、表名等）和数字替换为占位符后作为警告的模板，模板相同的警告
+/// 合并为第一条，出现多次时附带次数；顺序按各模板第一次出现的位置
+/// 
+/// # Arguments
+/// * `warnings` - 原始警告列表
+/// * `max_warnings` - 合并后最多保留的条数，`None` 表示不限制
+fn summarize_warnings(warnings: &[String], max_warnings: Option<usize>) -> Vec<String> {
+    let mut groups: Vec<(&String, usize)> = Vec::new();
+    let mut group_of_template: HashMap<String, usize> = HashMap::new();
+    for warning in warnings {
+        let group = *group_of_template.entry(warning_template(warning)).or_insert_with(|| {
+            groups.push((warning, 0));
+            groups.len() - 1
+        });
+        groups[group].1 += 1;
+    }
+    
+    let mut summarized: Vec<String> = groups.into_iter()
+        .map(|(warning, count)| match count {
+            1 => warning.clone(),
+            _ => format!("{} ({} occurrences)", warning, count),
+        })
+        .collect();
+    if let Some(max) = max_warnings
+        && summarized.len() > max
+    {
+        let more = summarized.len() - max;
+        summarized.truncate(max);
+        summarized.push(format!("... and {} more warnings", more));
+    }
+    summarized
+}
+
+/// 计算警告的模板：引号内的内容替换为 `"…"`，连续数字替换为 `N`
+fn warning_template(warning: &str) -> String {
+    let mut template = String::with_capacity(warning.len());
+    let mut chars = warning.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                // 跳过到匹配的引号，`Debug` 格式的路径中引号以反斜杠转义
+                while let Some(inner) = chars.next() {
+                    match inner {
+                        '\\' => { chars.next(); }
+                        _ if inner == c => break,
+                        _ => {}
+                    }
+                }
+                template.push(c);
+                template.push('…');
+                template.push(c);
+            }
+            _ if c.is_ascii_digit() => {
+                while chars.next_if(char::is_ascii_digit).is_some() {}
+                template.push('N');
+            }
+            _ => template.push(c),
+        }
+    }
+    template
+}
+
+/// 判断方法是否与变更范围内的行内修改相交
+/// 
+/// 方法与变更范围重叠的行中，只要有一行没有列信息（新增、删除或上下文行），就视为相交；
+/// 所有重叠行都有列信息时，方法在其中某一行上的列范围与修改的列范围相交才视为相交。
+/// 方法没有列信息时总是视为相交
+/// 
+/// # Arguments
+/// * `method` - 与变更范围重叠的方法
+/// * `change` - 变更的行范围（1 起始，闭区间）
+/// * `changed_columns` - 行号到该行修改的列范围（字节偏移，结束列不包含在范围内）
+fn touches_changed_columns(
+    method: &MethodInfo,
+    change: (usize, usize),
+    changed_columns: &HashMap<usize, (usize, usize)>,
+) -> bool {
+    let Some((method_start_column, method_end_column)) = method.column_range else {
+        return true;
+    };
+    let (method_start, method_end) = method.line_range;
+    // 列范围只描述 `line_range`，与其余各段（如构造器中的实例初始化代码）的重叠不按列判断
+    if change.0 > method_end || change.1 < method_start {
+        return true;
+    }
+    
+    (change.0.max(method_start)..=change.1.min(method_end)).any(|line| {
+        let Some(&(start, end)) = changed_columns.get(&line) else {
+            return true;
+        };
+        let from = if line == method_start { method_start_column } else { 0 };
+        let to = if line == method_end { method_end_column } else { usize::MAX };
+        // 纯插入的列范围为空，按插入位置处的一个字符计算
+        from < end.max(start + 1) && start < to
+    })
+}
+
+/// 构造变更方法的变更行号和源码
+/// 
+/// 由多段代码组成的方法（如 `<clinit>`）各段源码按行范围顺序拼接
+/// 
+/// # Arguments
+/// * `content` - 最终文件内容
+/// * `name` - 方法全限定名
+/// * `ranges` - 方法的行范围（1 起始，闭区间）
+/// * `changed_lines` - 最终文件中变更的行号，可以无序、重复
+fn changed_method_body(
+    content: &str,
+    name: &str,
+    ranges: impl Iterator<Item = (usize, usize)>,
+    changed_lines: &[usize],
+) -> ChangedMethod {
+    let source_lines: Vec<&str> = content.lines().collect();
+    let mut lines = Vec::new();
+    let mut snippets = Vec::new();
+    
+    for (start, end) in ranges {
+        lines.extend(changed_lines.iter()
+            .filter(|line| (start..=end).contains(*line))
+            .map(|line| *line as u32));
+        snippets.push(source_lines
+            .get(start.saturating_sub(1)..end.min(source_lines.len()))
+            .unwrap_or_default()
+            .join("\n"));
+    }
+    lines.sort_unstable();
+    lines.dedup();
+    
+    ChangedMethod {
+        name: name.to_string(),
+        changed_lines: lines,
+        snippet: snippets.join("\n"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -586,6 +2163,7 @@ mod tests {
         assert_eq!(stats.failed_files, 0);
         assert_eq!(stats.total_methods, 0);
         assert_eq!(stats.traced_chains, 0);
+        assert_eq!(stats.blast_radius, 0);
         assert_eq!(stats.duration_ms, 0);
     }
     
@@ -612,10 +2190,211 @@ mod tests {
         assert!(orchestrator.is_config_file(Path::new("config.xml")));
         assert!(orchestrator.is_config_file(Path::new("config.yaml")));
         assert!(orchestrator.is_config_file(Path::new("config.yml")));
+        assert!(orchestrator.is_config_file(Path::new("order.thrift")));
         assert!(!orchestrator.is_config_file(Path::new("config.txt")));
         assert!(!orchestrator.is_config_file(Path::new("config.rs")));
     }
     
+    #[test]
+    fn test_register_config_parser() {
+        struct PropertiesParser;
+        
+        impl ConfigParser for PropertiesParser {
+            fn parse(&self, _content: &str) -> Result<crate::config_parser::ConfigData, ParseError> {
+                Ok(crate::config_parser::ConfigData::default())
+            }
+            
+            fn supports_format(&self, format: &str) -> bool {
+                format == "properties"
+            }
+        }
+        
+        let temp_dir = TempDir::new().unwrap();
+        let mut orchestrator = AnalysisOrchestrator::new(temp_dir.path().to_path_buf(), TraceConfig::default()).unwrap();
+        assert!(!orchestrator.is_config_file(Path::new("application.properties")));
+        
+        orchestrator.register_config_parser(Box::new(PropertiesParser));
+        assert!(orchestrator.is_config_file(Path::new("application.properties")));
+        // 内置格式仍然可用
+        assert!(orchestrator.is_config_file(Path::new("config.yml")));
+        // 注册自定义解析器后构建的索引不同于磁盘索引
+        assert!(!orchestrator.uses_disk_index());
+    }
+    
+    #[test]
+    fn test_source_extensions_match_builtin_parsers() {
+        let temp_dir = TempDir::new().unwrap();
+        let orchestrator = AnalysisOrchestrator::new(temp_dir.path().to_path_buf(), TraceConfig::default()).unwrap();
+        
+        // 索引存储默认的校验和扩展名与内置的语言解析器和配置解析器一致
+        assert_eq!(orchestrator.source_extensions(), crate::index_storage::DEFAULT_SOURCE_EXTENSIONS);
+        assert!(orchestrator.uses_disk_index());
+    }
+    
+    #[test]
+    fn test_config_topic_paths_keep_registered_yaml_parser() {
+        struct RecordingYamlParser(Arc<std::sync::Mutex<Vec<String>>>);
+        
+        impl ConfigParser for RecordingYamlParser {
+            fn parse(&self, content: &str) -> Result<crate::config_parser::ConfigData, ParseError> {
+                self.0.lock().unwrap().push(content.to_string());
+                Ok(crate::config_parser::ConfigData::default())
+            }
+            
+            fn supports_format(&self, format: &str) -> bool {
+                format == "yaml"
+            }
+        }
+        
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("application.yml"), "app:\n  destinations: [order-events]\n").unwrap();
+        
+        let parsed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut orchestrator = AnalysisOrchestrator::new(temp_dir.path().to_path_buf(), TraceConfig::default()).unwrap();
+        orchestrator.register_config_parser(Box::new(RecordingYamlParser(parsed.clone())));
+        orchestrator.set_config_topic_paths(vec!["app.destinations".to_string()]);
+        
+        // 设置键路径后，已注册的 YAML 解析器仍然负责解析
+        let mut index = CodeIndex::new();
+        orchestrator.parse_config_file(&temp_dir.path().join("application.yml"), &mut index).unwrap();
+        assert_eq!(parsed.lock().unwrap().len(), 1);
+    }
+    
+    #[test]
+    fn test_index_cache_shared_across_orchestrators() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path().to_path_buf();
+        fs::write(temp_dir.path().join("Foo.java"), "public class Foo { void bar() {} }").unwrap();
+        
+        // 首次运行构建并保存磁盘索引
+        let mut orchestrator = AnalysisOrchestrator::new(workspace_path.clone(), TraceConfig::default()).unwrap();
+        orchestrator.load_or_build_index().unwrap();
+        
+        let cache = Arc::new(IndexCache::new(std::time::Duration::from_secs(60), 4));
+        for _ in 0..2 {
+            let mut orchestrator = AnalysisOrchestrator::new(workspace_path.clone(), TraceConfig::default()).unwrap();
+            orchestrator.set_index_cache(cache.clone());
+            let index = orchestrator.load_or_build_index().unwrap();
+            assert!(index.find_method("Foo::bar").is_some());
+        }
+        
+        // 只有第一次加载读取了磁盘
+        assert_eq!(cache.disk_loads(), 1);
+    }
+    
+    #[test]
+    fn test_trace_cache_reused_across_analyses() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path().join("workspace");
+        fs::create_dir(&workspace_path).unwrap();
+        fs::write(workspace_path.join("Foo.java"), "public class Foo { void bar() {} }").unwrap();
+        
+        let cache = Arc::new(TraceCache::new(temp_dir.path().join("trace-cache")));
+        let mut orchestrator = AnalysisOrchestrator::new(workspace_path, TraceConfig::default()).unwrap();
+        orchestrator.set_trace_cache(cache.clone());
+        let index = orchestrator.load_or_build_index().unwrap();
+        
+        orchestrator.trace_impact(&["Foo::bar".to_string()], &index).unwrap();
+        let graph = orchestrator.trace_impact(&["Foo::bar".to_string()], &index).unwrap();
+        
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        assert!(graph.get_node("method:Foo::bar").is_some());
+        
+        // 加载索引后改变索引内容的设置参与缓存键
+        orchestrator.set_kafka_topic_prefixes(vec!["prod.".to_string()]);
+        orchestrator.trace_impact(&["Foo::bar".to_string()], &index).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+        orchestrator.set_service_aliases(HashMap::from([("users".to_string(), "user-service".to_string())]));
+        orchestrator.trace_impact(&["Foo::bar".to_string()], &index).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 3));
+        orchestrator.trace_impact(&["Foo::bar".to_string()], &index).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (2, 3));
+    }
+    
+    #[test]
+    fn test_repeated_warnings_are_aggregated() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path().to_path_buf();
+        let mut orchestrator = AnalysisOrchestrator::new(workspace_path, TraceConfig::default()).unwrap();
+        
+        // patch 修改了 50 个工作空间中不存在的文件
+        let patch: String = (0..50)
+            .map(|i| format!(
+                "diff --git a/Missing{i}.java b/Missing{i}.java\nindex 0000000..1111111 100644\n--- a/Missing{i}.java\n+++ b/Missing{i}.java\n@@ -1,1 +1,1 @@\n-old\n+new\n"
+            ))
+            .collect();
+        let patch_path = temp_dir.path().join("missing.patch");
+        fs::write(&patch_path, patch).unwrap();
+        
+        let result = orchestrator.analyze(&patch_path).unwrap();
+        let missing: Vec<&String> = result.warnings.iter()
+            .filter(|warning| warning.starts_with("File does not exist"))
+            .collect();
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].ends_with("(50 occurrences)"), "{}", missing[0]);
+        assert!(missing[0].contains("Missing0.java"));
+        
+        // 原始警告不受影响
+        assert!(orchestrator.warnings().len() >= 50);
+    }
+    
+    #[test]
+    fn test_normalize_path() {
+        let workspace = Path::new("/work/space");
+        let expected = PathBuf::from("src/main/Foo.java");
+        
+        assert_eq!(normalize_path(Path::new("src\\main\\Foo.java"), workspace), expected);
+        assert_eq!(normalize_path(Path::new("./src/./main/Foo.java"), workspace), expected);
+        assert_eq!(normalize_path(Path::new(".\\src\\main\\Foo.java"), workspace), expected);
+        assert_eq!(normalize_path(Path::new("/work/space/src/main/Foo.java"), workspace), expected);
+        assert_eq!(normalize_path(Path::new("/work/space//src/main/Foo.java"), Path::new("/work/space/")), expected);
+        
+        // 工作空间外的绝对路径保持绝对路径，同名前缀的目录不视为在工作空间内
+        assert_eq!(normalize_path(Path::new("/other/Foo.java"), workspace), PathBuf::from("/other/Foo.java"));
+        assert_eq!(normalize_path(Path::new("/work/spaces/Foo.java"), workspace), PathBuf::from("/work/spaces/Foo.java"));
+    }
+    
+    #[test]
+    fn test_group_changes_by_normalized_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut orchestrator = AnalysisOrchestrator::new(temp_dir.path().to_path_buf(), TraceConfig::default()).unwrap();
+        let change = |file_path: &str| FileChange {
+            file_path: file_path.to_string(),
+            change_type: ChangeType::Modified,
+            kind: ChangeKind::Text,
+            hunks: Vec::new(),
+        };
+        let changes = vec![change("./src/Foo.java"), change("src\\Foo.java"), change("src/Bar.java")];
+        
+        // 不同写法的同一文件归为一组，保持 patch 中的顺序
+        let groups = orchestrator.group_changes_by_file(&changes);
+        let sizes: Vec<usize> = groups.iter().map(|(_, changes)| changes.len()).collect();
+        assert_eq!(sizes, vec![2, 1]);
+        assert_eq!(orchestrator.file_key(&groups[0].0), PathBuf::from("src/Foo.java"));
+    }
+    
+    #[test]
+    fn test_summarize_warnings_with_cap() {
+        let warnings = vec![
+            "Failed to read file \"a.java\": denied".to_string(),
+            "Database table 'orders' is referenced in code but not defined by any migration".to_string(),
+            "Failed to read file \"b.java\": denied".to_string(),
+            "Database table 'users' is referenced in code but not defined by any migration".to_string(),
+            "Change at lines 3-5 in \"c.java\" is outside any method".to_string(),
+        ];
+        
+        assert_eq!(summarize_warnings(&warnings, None), vec![
+            "Failed to read file \"a.java\": denied (2 occurrences)",
+            "Database table 'orders' is referenced in code but not defined by any migration (2 occurrences)",
+            "Change at lines 3-5 in \"c.java\" is outside any method",
+        ]);
+        assert_eq!(summarize_warnings(&warnings, Some(1)), vec![
+            "Failed to read file \"a.java\": denied (2 occurrences)",
+            "... and 2 more warnings",
+        ]);
+        assert_eq!(summarize_warnings(&warnings, Some(3)).len(), 3);
+    }
+    
     #[test]
     fn test_parse_patch_with_invalid_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -720,6 +2499,87 @@ index 1234567..abcdefg 100644
         assert_eq!(changes.len(), 1);
     }
     
+    #[test]
+    fn test_parse_patches_from_directory_with_diff_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path().to_path_buf();
+        let trace_config = TraceConfig::default();
+        let mut orchestrator = AnalysisOrchestrator::new(workspace_path, trace_config).unwrap();
+        
+        // 创建 patches 目录
+        let patches_dir = temp_dir.path().join("patches");
+        fs::create_dir(&patches_dir).unwrap();
+        
+        // 创建一个 .diff 文件和一个 .patch 文件
+        let diff_content = r#"diff --git a/file1.txt b/file1.txt
+index 1234567..abcdefg 100644
+--- a/file1.txt
++++ b/file1.txt
+@@ -1,2 +1,2 @@
+ line 1
+-line 2
++line 2 modified
+"#;
+        
+        let patch_content = r#"diff --git a/file2.txt b/file2.txt
+index 2345678..bcdefgh 100644
+--- a/file2.txt
++++ b/file2.txt
+@@ -1,2 +1,3 @@
+ line 1
+ line 2
++line 3
+"#;
+        
+        let mut diff_file = fs::File::create(patches_dir.join("project_a.diff")).unwrap();
+        diff_file.write_all(diff_content.as_bytes()).unwrap();
+        
+        let mut patch_file = fs::File::create(patches_dir.join("project_b.patch")).unwrap();
+        patch_file.write_all(patch_content.as_bytes()).unwrap();
+        
+        // 解析目录
+        let result = orchestrator.parse_patches_from_directory(&patches_dir);
+        
+        // 两种扩展名都应该被解析，且项目名取自文件名
+        assert!(result.is_ok());
+        let changes = result.unwrap();
+        assert_eq!(changes.len(), 2);
+        let paths: Vec<&str> = changes.iter().map(|c| c.file_path.as_str()).collect();
+        assert!(paths.contains(&"project_a/file1.txt"));
+        assert!(paths.contains(&"project_b/file2.txt"));
+    }
+    
+    #[test]
+    fn test_parse_patches_from_directory_with_custom_extensions() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path().to_path_buf();
+        let trace_config = TraceConfig::default();
+        let mut orchestrator = AnalysisOrchestrator::new(workspace_path, trace_config).unwrap();
+        orchestrator.set_patch_extensions([".DIFF"]);
+        
+        // 创建 patches 目录
+        let patches_dir = temp_dir.path().join("patches");
+        fs::create_dir(&patches_dir).unwrap();
+        
+        let content = r#"diff --git a/file1.txt b/file1.txt
+index 1234567..abcdefg 100644
+--- a/file1.txt
++++ b/file1.txt
+@@ -1,2 +1,2 @@
+ line 1
+-line 2
++line 2 modified
+"#;
+        
+        fs::write(patches_dir.join("project_a.diff"), content).unwrap();
+        fs::write(patches_dir.join("project_b.patch"), content).unwrap();
+        
+        // 只应解析 .diff 文件
+        let changes = orchestrator.parse_patches_from_directory(&patches_dir).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].file_path, "project_a/file1.txt");
+    }
+    
     #[test]
     fn test_parse_patches_from_directory_empty() {
         let temp_dir = TempDir::new().unwrap();
@@ -741,6 +2601,30 @@ index 1234567..abcdefg 100644
         assert_eq!(orchestrator.warnings.len(), 1);
     }
     
+    #[test]
+    fn test_non_text_changes_skipped_with_warning() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path().to_path_buf();
+        let trace_config = TraceConfig::default();
+        let mut orchestrator = AnalysisOrchestrator::new(workspace_path, trace_config).unwrap();
+        
+        let content = "diff --git a/logo.png b/logo.png
+index 1111111..2222222 100644
+Binary files a/logo.png and b/logo.png differ
+diff --git a/run.sh b/run.sh
+old mode 100644
+new mode 100755
+";
+        let changes = PatchParser::parse_patch_str(content).unwrap();
+        
+        let changed = orchestrator.extract_changed_methods(&changes, &CodeIndex::new()).unwrap();
+        assert!(changed.is_empty());
+        assert_eq!(orchestrator.warnings, vec![
+            "Skipping binary file change: logo.png".to_string(),
+            "Skipping file mode change without content changes: run.sh".to_string(),
+        ]);
+    }
+    
     #[test]
     fn test_parse_patches_from_single_file_backward_compatibility() {
         let temp_dir = TempDir::new().unwrap();
@@ -900,6 +2784,35 @@ index 2345678..bcdefgh 100644
         assert_eq!(config_files.len(), 1);
     }
     
+    #[test]
+    fn test_dry_run_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_path = temp_dir.path().to_path_buf();
+        
+        let src_dir = temp_dir.path().join("service").join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("OrderService.java"), "class OrderService {}").unwrap();
+        fs::write(src_dir.join("OrderRepository.java"), "class OrderRepository {}").unwrap();
+        fs::write(src_dir.join("lib.rs"), "fn main() {}").unwrap();
+        fs::write(src_dir.join("order.kt"), "class Order").unwrap();
+        fs::write(temp_dir.path().join("service").join("application.yml"), "server:\n  port: 8080\n").unwrap();
+        
+        // 构建目录中的文件不会被索引
+        let target_dir = temp_dir.path().join("target");
+        fs::create_dir(&target_dir).unwrap();
+        fs::write(target_dir.join("Generated.java"), "class Generated {}").unwrap();
+        
+        let orchestrator = AnalysisOrchestrator::new(workspace_path, TraceConfig::default()).unwrap();
+        let report = orchestrator.dry_run().unwrap();
+        
+        assert_eq!(report.language_counts, vec![
+            ("java".to_string(), 2),
+            ("rust".to_string(), 1),
+        ]);
+        assert_eq!(report.config_files, 1);
+        assert_eq!(report.unsupported_files, vec![PathBuf::from("service/src/order.kt")]);
+    }
+    
     #[test]
     fn test_warnings_and_errors_accessors() {
         let temp_dir = TempDir::new().unwrap();
@@ -915,4 +2828,27 @@ index 2345678..bcdefgh 100644
         assert_eq!(orchestrator.warnings()[0], "Test warning");
         assert_eq!(orchestrator.errors()[0], "Test error");
     }
+    
+    #[test]
+    fn test_changed_method_body() {
+        let content = "class A {\n    void a() {\n        x();\n    }\n    void b() {\n        y();\n    }\n}\n";
+        
+        // 第 1 行在方法之外，第 3 行重复出现
+        let changed = changed_method_body(content, "A::a", [(2, 4)].into_iter(), &[3, 1, 3]);
+        assert_eq!(changed, ChangedMethod {
+            name: "A::a".to_string(),
+            changed_lines: vec![3],
+            snippet: "    void a() {\n        x();\n    }".to_string(),
+        });
+        
+        // 按最近方法归属的方法没有方法体内的变更行
+        let changed = changed_method_body(content, "A::b", [(5, 7)].into_iter(), &[8]);
+        assert!(changed.changed_lines.is_empty());
+        assert_eq!(changed.snippet, "    void b() {\n        y();\n    }");
+        
+        // 多段代码的各段源码依次拼接
+        let changed = changed_method_body(content, "A::<clinit>", [(3, 3), (6, 6)].into_iter(), &[6, 2]);
+        assert_eq!(changed.changed_lines, vec![6]);
+        assert_eq!(changed.snippet, "        x();\n        y();");
+    }
 }
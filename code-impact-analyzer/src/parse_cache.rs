@@ -98,20 +98,20 @@ mod tests {
                             full_qualified_name: "com.example.TestClass::testMethod".to_string(),
                             file_path: file_path.to_path_buf(),
                             line_range: (10, 20),
-                            calls: vec![],
-                            http_annotations: None,
-                            kafka_operations: vec![],
-                            db_operations: vec![],
-                            redis_operations: vec![],
+                            ..Default::default()
                         },
                     ],
                     line_range: (5, 25),
                     is_interface: false,
                     implements: vec![],
+                    extends: None,
                 },
             ],
             functions: vec![],
             imports: vec![],
+            string_constants: vec![],
+            functional_routes: vec![],
+            syntax_errors: Vec::new(),
         }
     }
     
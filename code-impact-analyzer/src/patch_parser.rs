@@ -11,6 +11,17 @@ pub enum ChangeType {
     Deleted,
 }
 
+/// 文件变更的内容类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// 文本内容变更，带有 hunk
+    Text,
+    /// 二进制文件变更（`GIT binary patch` 或 `Binary files ... differ`）
+    Binary,
+    /// 仅文件权限变更（`old mode` / `new mode`），没有内容变更
+    ModeOnly,
+}
+
 /// Hunk 中的单行信息
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HunkLine {
@@ -35,14 +46,226 @@ pub struct Hunk {
     pub lines: Vec<HunkLine>,
 }
 
+impl Hunk {
+    /// 获取 hunk 中非平凡变更所在的新文件行号
+    /// 
+    /// 新增行取其在新文件中的行号；删除行取删除位置在新文件中对应的行号。
+    /// 空行和纯注释行视为平凡变更，不计入结果。
+    /// 
+    /// # 参数
+    /// * `extension` - 源文件扩展名，用于选择注释语法
+    /// 
+    /// # 返回
+    /// * 非平凡变更的新文件行号列表（升序，可能重复）
+    pub fn significant_lines(&self, extension: Option<&str>) -> Vec<usize> {
+        self.lines_where(|line| !is_trivial_line(&line.content, extension))
+    }
+    
+    /// 获取 hunk 中新增行在新文件中的行号
+    /// 
+    /// 不包含删除行，只关心新代码时使用
+    /// 
+    /// # 返回
+    /// * 新增行的新文件行号列表（升序）
+    pub fn added_lines(&self) -> Vec<usize> {
+        self.lines_where(|line| line.line_type == LineType::Added)
+    }
+    
+    /// 获取 hunk 中所有变更所在的新文件行号
+    /// 
+    /// 与 `significant_lines` 相同，但不过滤空行和注释行
+    /// 
+    /// # 返回
+    /// * 变更的新文件行号列表（升序，可能重复）
+    pub fn changed_lines(&self) -> Vec<usize> {
+        self.lines_where(|_| true)
+    }
+    
+    /// 收集满足 `keep` 的新增/删除行在新文件中的行号
+    /// 
+    /// 删除行取删除位置在新文件中对应的行号
+    /// 
+    /// # 参数
+    /// * `keep` - 判断新增/删除行是否计入结果
+    pub fn lines_where(&self, keep: impl Fn(&HunkLine) -> bool) -> Vec<usize> {
+        let mut lines = Vec::new();
+        let mut new_line = self.new_start;
+        
+        for line in &self.lines {
+            match line.line_type {
+                LineType::Context => new_line += 1,
+                LineType::Added => {
+                    if keep(line) {
+                        lines.push(new_line);
+                    }
+                    new_line += 1;
+                }
+                LineType::Removed => {
+                    if keep(line) {
+                        lines.push(new_line);
+                    }
+                }
+            }
+        }
+        
+        lines
+    }
+    
+    /// 获取行内修改的列范围
+    /// 
+    /// 连续的删除行后紧跟相同数量的新增行时，逐行配对视为行内修改，
+    /// 去掉两行的公共前缀和公共后缀后剩下的部分即为新行中变更的列范围。
+    /// 删除行与新增行数量不同时无法确定对应关系，不返回列信息。
+    /// 
+    /// # 返回
+    /// * (新文件行号, 起始列, 结束列) 列表，列为字节偏移，结束列不包含在范围内
+    pub fn changed_columns(&self) -> Vec<(usize, usize, usize)> {
+        let mut columns = Vec::new();
+        let mut new_line = self.new_start;
+        let mut i = 0;
+        
+        while i < self.lines.len() {
+            if self.lines[i].line_type == LineType::Context {
+                new_line += 1;
+                i += 1;
+                continue;
+            }
+            
+            let removed_start = i;
+            while i < self.lines.len() && self.lines[i].line_type == LineType::Removed {
+                i += 1;
+            }
+            let added_start = i;
+            while i < self.lines.len() && self.lines[i].line_type == LineType::Added {
+                i += 1;
+            }
+            let removed = &self.lines[removed_start..added_start];
+            let added = &self.lines[added_start..i];
+            
+            if removed.len() == added.len() {
+                for (offset, (old, new)) in removed.iter().zip(added).enumerate() {
+                    let (old, new) = (old.content.as_bytes(), new.content.as_bytes());
+                    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+                    let max_suffix = old.len().min(new.len()) - prefix;
+                    let suffix = old.iter().rev().zip(new.iter().rev())
+                        .take(max_suffix)
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    columns.push((new_line + offset, prefix, new.len() - suffix));
+                }
+            }
+            new_line += added.len();
+        }
+        
+        columns
+    }
+}
+
+/// 判断一行代码是否为平凡内容（空行或纯注释行）
+/// 
+/// 轻量级的逐行判断，不跟踪跨行的块注释状态：
+/// - Java/Rust 等类 C 语言：`//`、`/*`、`*/`、`* ` 开头的行或只有 `*` 的行
+/// - Python/Shell/YAML 等：`#` 开头的行
+/// 
+/// # 参数
+/// * `content` - 行内容（不含 diff 前缀）
+/// * `extension` - 源文件扩展名
+/// 
+/// # 返回
+/// * `true` - 空行或纯注释行
+pub fn is_trivial_line(content: &str, extension: Option<&str>) -> bool {
+    let trimmed = content.trim();
+    if trimmed.is_empty() {
+        return true;
+    }
+    
+    match extension {
+        Some("py" | "sh" | "rb" | "yaml" | "yml" | "properties") => trimmed.starts_with('#'),
+        _ => {
+            // 块注释的续行以 `* ` 开头，`*count += 1;` 之类的解引用不是注释
+            trimmed.starts_with("//")
+                || trimmed.starts_with("/*")
+                || trimmed.starts_with("*/")
+                || trimmed.starts_with("* ")
+                || trimmed == "*"
+        }
+    }
+}
+
 /// 文件变更信息
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FileChange {
     pub file_path: String,
     pub change_type: ChangeType,
+    pub kind: ChangeKind,
     pub hunks: Vec<Hunk>,
 }
 
+impl FileChange {
+    /// 将应用本变更之前的文件行范围映射到应用之后的文件
+    /// 
+    /// 位于 hunk 之后的行按 hunk 增删的行数偏移；落在 hunk 修改区域内的端点
+    /// 扩展为该 hunk 在新文件中的整个范围。用于多个 patch 依次修改同一文件时，
+    /// 将前面 patch 的变更行号换算为最终文件中的行号。
+    /// 
+    /// # 参数
+    /// * `start` - 起始行号（旧文件）
+    /// * `end` - 结束行号（旧文件，包含）
+    /// 
+    /// # 返回
+    /// * 新文件中的 (起始行号, 结束行号)
+    pub fn map_line_range(&self, start: usize, end: usize) -> (usize, usize) {
+        let (mapped_start, _) = self.map_line(start);
+        let (_, mapped_end) = self.map_line(end);
+        (mapped_start, mapped_end.max(mapped_start))
+    }
+    
+    /// 将各 hunk 的新文件行号换算为再应用 `later` 之后的文件中的行号
+    /// 
+    /// 用于本变更的新文件不是磁盘上的文件时（如暂存区中的版本之后还有未暂存的修改），
+    /// 使 hunk 的行号与从最终文件解析出的方法行范围一致
+    /// 
+    /// # 参数
+    /// * `later` - 在本变更之后应用于同一文件的变更
+    pub fn rebase_onto(&mut self, later: &FileChange) {
+        for hunk in &mut self.hunks {
+            // 从文件开头删除的 hunk 没有新文件行号，无需换算
+            if hunk.new_start > 0 {
+                hunk.new_start = later.map_line_range(hunk.new_start, hunk.new_start).0;
+            }
+        }
+    }
+    
+    /// 映射单行，返回其在新文件中的行范围（未被修改的行起止相同）
+    fn map_line(&self, line: usize) -> (usize, usize) {
+        let mut offset: isize = 0;
+        
+        for hunk in &self.hunks {
+            // 纯新增的 hunk（old_lines 为 0）插入在 old_start 行之后
+            let before = if hunk.old_lines == 0 {
+                line <= hunk.old_start
+            } else {
+                line < hunk.old_start
+            };
+            if before {
+                break;
+            }
+            
+            if line >= hunk.old_start + hunk.old_lines {
+                offset += hunk.new_lines as isize - hunk.old_lines as isize;
+                continue;
+            }
+            
+            // 行位于 hunk 修改的区域内
+            let new_end = hunk.new_start + hunk.new_lines.saturating_sub(1);
+            return (hunk.new_start, new_end);
+        }
+        
+        let mapped = (line as isize + offset).max(1) as usize;
+        (mapped, mapped)
+    }
+}
+
 /// Patch 解析器
 pub struct PatchParser;
 
@@ -61,13 +284,36 @@ impl PatchParser {
             message: format!("Failed to read patch file: {}", e),
         })?;
 
+        Self::parse_patch_str(&content)
+    }
+
+    /// 解析内存中的 Git patch 内容
+    /// 
+    /// 会先去除开头的 UTF-8 BOM 并将 `\r\n` / `\r` 统一为 `\n`，
+    /// 因此 Windows 上生成的 patch 与 LF 版本解析结果一致。
+    /// 
+    /// # 参数
+    /// * `content` - patch 文本内容
+    /// 
+    /// # 返回
+    /// * `Ok(Vec<FileChange>)` - 成功解析的文件变更列表
+    /// * `Err(ParseError)` - 解析失败错误
+    pub fn parse_patch_str(content: &str) -> Result<Vec<FileChange>, ParseError> {
+        let content = Self::normalize_patch_content(content);
+
         // 预处理 patch 内容：移除 "-- " 分隔符后面的所有内容
         // git format-patch 生成的文件会在最后添加 "-- " 和版本号等信息
         // 这些内容会导致 gitpatch crate panic
         let cleaned_content = Self::remove_trailing_content(&content);
 
+        // 二进制变更和仅权限变更没有 hunk，gitpatch 无法解析，先单独提取
+        let (text_content, special_changes) = Self::split_non_text_sections(&cleaned_content);
+        if text_content.trim().is_empty() && !special_changes.is_empty() {
+            return Ok(special_changes);
+        }
+
         // 使用 gitpatch crate 解析多个 patch
-        let patches = gitpatch::Patch::from_multiple(&cleaned_content).map_err(|e| ParseError::InvalidFormat {
+        let patches = gitpatch::Patch::from_multiple(&text_content).map_err(|e| ParseError::InvalidFormat {
             message: format!("Failed to parse patch: {}", e),
         })?;
 
@@ -131,13 +377,110 @@ impl PatchParser {
             file_changes.push(FileChange {
                 file_path,
                 change_type,
+                kind: ChangeKind::Text,
                 hunks,
             });
         }
 
+        file_changes.extend(special_changes);
         Ok(file_changes)
     }
 
+    /// 从 patch 内容中分离出二进制变更和仅权限变更的 `diff --git` 段
+    /// 
+    /// 包含 `GIT binary patch` 或 `Binary files ... differ` 的段标记为
+    /// `ChangeKind::Binary`；包含 `old mode` / `new mode` 但没有 hunk 的段
+    /// 标记为 `ChangeKind::ModeOnly`。其余段原样保留，交给 gitpatch 解析。
+    /// 
+    /// # 参数
+    /// * `content` - 清理后的 patch 内容
+    /// 
+    /// # 返回
+    /// * (文本变更部分的 patch 内容, 非文本变更列表)
+    fn split_non_text_sections(content: &str) -> (String, Vec<FileChange>) {
+        let mut text_content = String::new();
+        let mut special_changes = Vec::new();
+        
+        let mut sections: Vec<Vec<&str>> = vec![Vec::new()];
+        for line in content.lines() {
+            if line.starts_with("diff --git ") {
+                sections.push(Vec::new());
+            }
+            sections.last_mut().unwrap().push(line);
+        }
+        
+        for section in sections {
+            let kind = Self::classify_section(&section);
+            match (kind, section.first().and_then(|header| Self::parse_git_header(header))) {
+                (ChangeKind::Binary | ChangeKind::ModeOnly, Some((old_path, new_path))) => {
+                    let change_type = if section.iter().any(|l| l.starts_with("new file mode")) {
+                        ChangeType::Added
+                    } else if section.iter().any(|l| l.starts_with("deleted file mode")) {
+                        ChangeType::Deleted
+                    } else {
+                        ChangeType::Modified
+                    };
+                    let file_path = if change_type == ChangeType::Deleted { old_path } else { new_path };
+                    special_changes.push(FileChange {
+                        file_path,
+                        change_type,
+                        kind,
+                        hunks: Vec::new(),
+                    });
+                }
+                _ => {
+                    for line in section {
+                        text_content.push_str(line);
+                        text_content.push('\n');
+                    }
+                }
+            }
+        }
+        
+        (text_content, special_changes)
+    }
+
+    /// 判断一个 `diff --git` 段的变更类别
+    fn classify_section(section: &[&str]) -> ChangeKind {
+        if section.iter().any(|l| *l == "GIT binary patch" || (l.starts_with("Binary files ") && l.ends_with(" differ"))) {
+            return ChangeKind::Binary;
+        }
+        
+        let has_mode_change = section.iter().any(|l| l.starts_with("old mode ") || l.starts_with("new mode "));
+        let has_hunks = section.iter().any(|l| l.starts_with("@@ "));
+        if has_mode_change && !has_hunks {
+            ChangeKind::ModeOnly
+        } else {
+            ChangeKind::Text
+        }
+    }
+
+    /// 从 `diff --git a/<old> b/<new>` 头部提取旧路径和新路径（不含 a/、b/ 前缀）
+    fn parse_git_header(header: &str) -> Option<(String, String)> {
+        let paths = header.strip_prefix("diff --git ")?;
+        let (old_path, new_path) = paths.split_once(" b/")?;
+        let old_path = old_path.strip_prefix("a/").unwrap_or(old_path);
+        Some((old_path.to_string(), new_path.to_string()))
+    }
+
+    /// 规范化 patch 内容：去除开头的 UTF-8 BOM，并将 `\r\n` 换行符统一为 `\n`
+    /// 
+    /// 只替换换行符本身，不增删行，因此 hunk 的行数统计保持不变。
+    /// 单独的 `\r` 可能是源文件行内容的一部分，保持不变，否则会把一行拆成两行。
+    /// 
+    /// # 参数
+    /// * `content` - 原始 patch 内容
+    /// 
+    /// # 返回
+    /// * 规范化后的 patch 内容
+    fn normalize_patch_content(content: &str) -> String {
+        let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+        if !content.contains('\r') {
+            return content.to_string();
+        }
+        content.replace("\r\n", "\n")
+    }
+
     /// 移除 patch 内容中 "-- " 分隔符后面的所有内容
     /// 
     /// git format-patch 生成的文件会在最后添加 "-- " 分隔符和版本号等信息，
@@ -282,6 +625,57 @@ index 2345678..bcdefgh 100644
         assert_eq!(changes[1].file_path, "file2.txt");
     }
 
+    #[test]
+    fn test_parse_combined_three_file_patch() {
+        // 每个 `diff --git` 行结束上一个文件的变更，新增、删除和修改的文件各自成为一个 FileChange
+        let patch_content = "diff --git a/src/A.java b/src/A.java
+index 1234567..abcdefg 100644
+--- a/src/A.java
++++ b/src/A.java
+@@ -1,2 +1,2 @@
+ class A {
+-    int a;
++    long a;
+@@ -10,1 +10,2 @@
+ }
++// end
+diff --git a/src/B.java b/src/B.java
+new file mode 100644
+index 0000000..2345678
+--- /dev/null
++++ b/src/B.java
+@@ -0,0 +1,2 @@
++class B {
++}
+diff --git a/src/C.java b/src/C.java
+deleted file mode 100644
+index 3456789..0000000
+--- a/src/C.java
++++ /dev/null
+@@ -1 +0,0 @@
+-class C {}
+";
+
+        let changes = PatchParser::parse_patch_str(patch_content).unwrap();
+        assert_eq!(changes.len(), 3);
+
+        assert_eq!(changes[0].file_path, "src/A.java");
+        assert_eq!(changes[0].change_type, ChangeType::Modified);
+        let ranges: Vec<(usize, usize)> = changes[0].hunks.iter().map(|h| (h.new_start, h.new_lines)).collect();
+        assert_eq!(ranges, vec![(1, 2), (10, 2)]);
+        assert_eq!(changes[0].hunks[1].lines.len(), 2);
+
+        assert_eq!(changes[1].file_path, "src/B.java");
+        assert_eq!(changes[1].change_type, ChangeType::Added);
+        assert_eq!(changes[1].hunks.len(), 1);
+        assert_eq!(changes[1].hunks[0].added_lines(), vec![1, 2]);
+
+        assert_eq!(changes[2].file_path, "src/C.java");
+        assert_eq!(changes[2].change_type, ChangeType::Deleted);
+        assert_eq!(changes[2].hunks.len(), 1);
+        assert_eq!(changes[2].hunks[0].lines.len(), 1);
+    }
+
     #[test]
     fn test_parse_added_file() {
         let patch_content = r#"diff --git a/new_file.txt b/new_file.txt
@@ -347,6 +741,7 @@ index 1234567..0000000
         let file_change = FileChange {
             file_path: "test.rs".to_string(),
             change_type: ChangeType::Modified,
+            kind: ChangeKind::Text,
             hunks: vec![
                 Hunk {
                     old_start: 10,
@@ -485,6 +880,125 @@ Another line
         assert_eq!(changes[0].file_path, "test.txt");
     }
 
+    #[test]
+    fn test_parse_crlf_patch_with_bom() {
+        // Windows 上生成的 patch：CRLF 换行 + UTF-8 BOM
+        let lf_content = "diff --git a/src/Foo.java b/src/Foo.java\n\
+                          index 1234567..abcdefg 100644\n\
+                          --- a/src/Foo.java\n\
+                          +++ b/src/Foo.java\n\
+                          @@ -10,4 +10,5 @@ public class Foo {\n\
+                          \x20    int a = 1;\n\
+                          -    int b = 2;\n\
+                          +    int b = 3;\n\
+                          +    int c = 4;\n\
+                          \x20    return;\n\
+                          \x20}\n";
+        let crlf_content = format!("\u{feff}{}", lf_content.replace('\n', "\r\n"));
+
+        let expected = PatchParser::parse_patch_str(lf_content).unwrap();
+        assert_eq!(expected.len(), 1);
+        assert_eq!(expected[0].hunks[0].old_lines, 4);
+        assert_eq!(expected[0].hunks[0].new_lines, 5);
+
+        let changes = PatchParser::parse_patch_str(&crlf_content).unwrap();
+        assert_eq!(changes, expected);
+
+        // 通过文件读取时结果也应一致
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(crlf_content.as_bytes()).unwrap();
+        let changes = PatchParser::parse_patch_file(temp_file.path()).unwrap();
+        assert_eq!(changes, expected);
+        assert!(changes[0].hunks[0].lines.iter().all(|l| !l.content.contains('\r')));
+    }
+
+    #[test]
+    fn test_normalize_patch_content() {
+        assert_eq!(PatchParser::normalize_patch_content("\u{feff}a\r\nb\r\n"), "a\nb\n");
+        // 行内的单独 \r 保持不变
+        assert_eq!(PatchParser::normalize_patch_content("a\rb\r\n"), "a\rb\n");
+        assert_eq!(PatchParser::normalize_patch_content("a\nb\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn test_hunk_significant_lines() {
+        let patch_content = r#"diff --git a/Foo.java b/Foo.java
+index 1234567..abcdefg 100644
+--- a/Foo.java
++++ b/Foo.java
+@@ -10,6 +10,7 @@
+     void a() {
+-        // old comment
++        // new comment
++
+         int x = 1;
+-        int y = 2;
++        int y = 3;
+     }
+"#;
+        
+        let changes = PatchParser::parse_patch_str(patch_content).unwrap();
+        let hunk = &changes[0].hunks[0];
+        
+        // 只有 `int y` 的删除与新增是非平凡变更，均位于新文件第 14 行
+        assert_eq!(hunk.significant_lines(Some("java")), vec![14, 14]);
+        
+        // 不过滤时注释行和空行也计入
+        assert_eq!(hunk.changed_lines(), vec![11, 11, 12, 14, 14]);
+        
+        // 只取新增行时不含删除位置
+        assert_eq!(hunk.added_lines(), vec![11, 12, 14]);
+    }
+    
+    #[test]
+    fn test_file_change_map_line_range() {
+        let hunk = |old_start, old_lines, new_start, new_lines| Hunk {
+            old_start,
+            old_lines,
+            new_start,
+            new_lines,
+            lines: vec![],
+        };
+        let change = FileChange {
+            file_path: "Test.java".to_string(),
+            change_type: ChangeType::Modified,
+            kind: ChangeKind::Text,
+            hunks: vec![
+                // 第 2 行之后插入 3 行
+                hunk(2, 0, 3, 3),
+                // 第 10-11 行替换为 1 行
+                hunk(10, 2, 13, 1),
+            ],
+        };
+        
+        // hunk 之前的行不变
+        assert_eq!(change.map_line_range(1, 2), (1, 2));
+        // 插入之后的行偏移 3 行
+        assert_eq!(change.map_line_range(5, 6), (8, 9));
+        // 落在修改区域内的端点扩展为新文件中的 hunk 范围
+        assert_eq!(change.map_line_range(8, 11), (11, 13));
+        // 两个 hunk 之后的行偏移 3 - 1 = 2 行
+        assert_eq!(change.map_line_range(20, 20), (22, 22));
+    }
+    
+    #[test]
+    fn test_is_trivial_line() {
+        assert!(is_trivial_line("", Some("java")));
+        assert!(is_trivial_line("    ", Some("rs")));
+        assert!(is_trivial_line("  // comment", Some("java")));
+        assert!(is_trivial_line(" * javadoc", Some("java")));
+        assert!(is_trivial_line("/* block */", Some("rs")));
+        assert!(is_trivial_line("# comment", Some("py")));
+        
+        assert!(!is_trivial_line("int x = 1; // trailing", Some("java")));
+        assert!(!is_trivial_line("let x = a * b;", Some("rs")));
+        assert!(!is_trivial_line("    *count += 1;", Some("rs")));
+        assert!(!is_trivial_line("*ptr = value;", Some("c")));
+        assert!(is_trivial_line(" */", Some("java")));
+        assert!(is_trivial_line(" *", Some("java")));
+        assert!(!is_trivial_line("// not a comment in python", Some("py")));
+    }
+    
     #[test]
     fn test_remove_trailing_content() {
         // 测试移除尾部内容的功能
@@ -530,4 +1044,99 @@ Another line
         let cleaned = PatchParser::remove_trailing_content(content);
         assert_eq!(cleaned, format!("{}\n", content.trim_end()), "Should keep content unchanged");
     }
+    
+    #[test]
+    fn test_parse_binary_patch() {
+        let patch_content = "diff --git a/src/A.java b/src/A.java
+index 1111111..2222222 100644
+--- a/src/A.java
++++ b/src/A.java
+@@ -1,1 +1,1 @@
+-a
++b
+diff --git a/assets/logo.png b/assets/logo.png
+index 3333333..4444444 100644
+GIT binary patch
+literal 10
+RcmZ?wbhEHbRA6LE002XM0ssI2
+
+literal 5
+McmZ?wbhEHb00Ecrk^lez
+
+diff --git a/assets/icon.png b/assets/icon.png
+new file mode 100644
+index 0000000..5555555
+Binary files /dev/null and b/assets/icon.png differ
+";
+        
+        let changes = PatchParser::parse_patch_str(patch_content).unwrap();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].file_path, "src/A.java");
+        assert_eq!(changes[0].kind, ChangeKind::Text);
+        
+        assert_eq!(changes[1].file_path, "assets/logo.png");
+        assert_eq!(changes[1].kind, ChangeKind::Binary);
+        assert_eq!(changes[1].change_type, ChangeType::Modified);
+        assert!(changes[1].hunks.is_empty());
+        
+        assert_eq!(changes[2].file_path, "assets/icon.png");
+        assert_eq!(changes[2].kind, ChangeKind::Binary);
+        assert_eq!(changes[2].change_type, ChangeType::Added);
+    }
+    
+    #[test]
+    fn test_parse_mode_only_patch() {
+        let patch_content = "diff --git a/bin/run.sh b/bin/run.sh
+old mode 100644
+new mode 100755
+";
+        
+        let changes = PatchParser::parse_patch_str(patch_content).unwrap();
+        assert_eq!(changes, vec![FileChange {
+            file_path: "bin/run.sh".to_string(),
+            change_type: ChangeType::Modified,
+            kind: ChangeKind::ModeOnly,
+            hunks: vec![],
+        }]);
+    }
+    
+    #[test]
+    fn test_mode_change_with_content_is_text() {
+        let patch_content = "diff --git a/bin/run.sh b/bin/run.sh
+old mode 100644
+new mode 100755
+index 1111111..2222222
+--- a/bin/run.sh
++++ b/bin/run.sh
+@@ -1,1 +1,1 @@
+-echo a
++echo b
+";
+        
+        let changes = PatchParser::parse_patch_str(patch_content).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, ChangeKind::Text);
+        assert_eq!(changes[0].hunks.len(), 1);
+    }
+    
+    #[test]
+    fn test_changed_columns_of_modified_lines() {
+        let patch_content = "diff --git a/A.java b/A.java
+--- a/A.java
++++ b/A.java
+@@ -1,5 +1,5 @@
+ class A {
+-    int x(){return x;} int y(){return y;}
++    int x(){return x;} int y(){return y + 1;}
+-    int a;
++    long a;
++    int b;
+ }
+";
+        
+        let changes = PatchParser::parse_patch_str(patch_content).unwrap();
+        let columns = changes[0].hunks[0].changed_columns();
+        // 只有数量相同的删除行和新增行能逐行配对
+        assert_eq!(columns, vec![(2, 39, 43)]);
+    }
 }
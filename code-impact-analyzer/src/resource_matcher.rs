@@ -0,0 +1,140 @@
+use crate::code_index::CodeIndex;
+use crate::impact_tracer::{EdgeType, ImpactNode};
+use crate::language_parser::MethodInfo;
+use crate::types::{DbOpType, KafkaOpType, RedisOpType};
+
+/// 方法对共享资源的访问方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceAccess {
+    /// 读取（消费）资源：资源的写入者是该方法的上游
+    Read,
+    /// 写入（生产）资源：资源的读取者是该方法的下游
+    Write,
+}
+
+/// 方法对一个共享资源的操作
+#[derive(Debug, Clone)]
+pub struct ResourceOp {
+    /// 资源节点，节点属性会补充到影响图中已存在的同一资源节点上
+    pub node: ImpactNode,
+    /// 在资源另一端查找方法时使用的键（如 Topic 名、原始表名）
+    pub key: String,
+    /// 访问方式
+    pub access: ResourceAccess,
+    /// 方法与资源之间、资源与另一端方法之间的边类型
+    pub edge_type: EdgeType,
+}
+
+/// 跨服务资源匹配器
+///
+/// 通过共享资源（消息队列、存储等）关联不同服务中的方法。追溯到一个方法时，
+/// 先用 `extract` 取出方法访问的资源，再用 `bridge` 找到资源另一端的方法：
+/// 写入资源时沿读取者继续向下游追溯，读取资源时沿写入者继续向上游追溯。
+/// 内置的 Kafka、数据库和 Redis 追溯都实现为匹配器，自定义匹配器可以通过
+/// `AnalysisOrchestrator::register_resource_matcher` 注册
+pub trait ResourceMatcher: Send + Sync {
+    /// 提取方法访问的资源
+    ///
+    /// # Arguments
+    /// * `method` - 方法信息
+    /// * `index` - 代码索引，用于读取资源名的规范化设置
+    fn extract(&self, method: &MethodInfo, index: &CodeIndex) -> Vec<ResourceOp>;
+
+    /// 查找资源另一端的方法
+    ///
+    /// # Arguments
+    /// * `op` - `extract` 返回的资源操作
+    /// * `index` - 代码索引
+    ///
+    /// # Returns
+    /// * `op` 为写入时返回读取该资源的方法，为读取时返回写入该资源的方法（全限定名）
+    fn bridge(&self, op: &ResourceOp, index: &CodeIndex) -> Vec<String>;
+}
+
+/// Kafka Topic 匹配器：生产者写入、消费者读取 Topic，Topic 按索引的环境前缀合并
+pub struct KafkaMatcher;
+
+impl ResourceMatcher for KafkaMatcher {
+    fn extract(&self, method: &MethodInfo, index: &CodeIndex) -> Vec<ResourceOp> {
+        method.kafka_operations.iter()
+            .map(|kafka_op| {
+                let mut node = ImpactNode::kafka_topic(index.normalize_kafka_topic(&kafka_op.topic));
+                // 消息值类型（schema）挂在 Topic 节点上
+                if let Some(value_type) = &kafka_op.value_type {
+                    node.metadata.properties.insert("value_type".to_string(), value_type.clone());
+                }
+                ResourceOp {
+                    node,
+                    key: kafka_op.topic.clone(),
+                    access: match kafka_op.operation_type {
+                        KafkaOpType::Produce => ResourceAccess::Write,
+                        KafkaOpType::Consume => ResourceAccess::Read,
+                    },
+                    edge_type: EdgeType::KafkaProduceConsume,
+                }
+            })
+            .collect()
+    }
+
+    fn bridge(&self, op: &ResourceOp, index: &CodeIndex) -> Vec<String> {
+        let methods = match op.access {
+            ResourceAccess::Write => index.find_kafka_consumers(&op.key),
+            ResourceAccess::Read => index.find_kafka_producers(&op.key),
+        };
+        methods.into_iter().map(str::to_string).collect()
+    }
+}
+
+/// 数据库表匹配器：表名按索引的规范化规则合并
+pub struct DatabaseMatcher;
+
+impl ResourceMatcher for DatabaseMatcher {
+    fn extract(&self, method: &MethodInfo, index: &CodeIndex) -> Vec<ResourceOp> {
+        method.db_operations.iter()
+            .map(|db_op| ResourceOp {
+                node: ImpactNode::database_table(index.table_normalization().normalize(&db_op.table)),
+                key: db_op.table.clone(),
+                access: match db_op.operation_type {
+                    DbOpType::Select => ResourceAccess::Read,
+                    DbOpType::Insert | DbOpType::Update | DbOpType::Delete => ResourceAccess::Write,
+                },
+                edge_type: EdgeType::DatabaseReadWrite,
+            })
+            .collect()
+    }
+
+    fn bridge(&self, op: &ResourceOp, index: &CodeIndex) -> Vec<String> {
+        let methods = match op.access {
+            ResourceAccess::Write => index.find_db_readers(&op.key),
+            ResourceAccess::Read => index.find_db_writers(&op.key),
+        };
+        methods.into_iter().map(str::to_string).collect()
+    }
+}
+
+/// Redis 键匹配器：键按索引的命名空间深度合并
+pub struct RedisMatcher;
+
+impl ResourceMatcher for RedisMatcher {
+    fn extract(&self, method: &MethodInfo, index: &CodeIndex) -> Vec<ResourceOp> {
+        method.redis_operations.iter()
+            .map(|redis_op| ResourceOp {
+                node: ImpactNode::redis_namespace(redis_op.key_pattern.clone(), index.redis_namespace_depth()),
+                key: redis_op.key_pattern.clone(),
+                access: match redis_op.operation_type {
+                    RedisOpType::Get => ResourceAccess::Read,
+                    RedisOpType::Set | RedisOpType::Delete => ResourceAccess::Write,
+                },
+                edge_type: EdgeType::RedisReadWrite,
+            })
+            .collect()
+    }
+
+    fn bridge(&self, op: &ResourceOp, index: &CodeIndex) -> Vec<String> {
+        let methods = match op.access {
+            ResourceAccess::Write => index.find_redis_readers(&op.key),
+            ResourceAccess::Read => index.find_redis_writers(&op.key),
+        };
+        methods.into_iter().map(str::to_string).collect()
+    }
+}
@@ -1,9 +1,10 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Mutex;
 use tree_sitter::Parser;
 use regex::Regex;
 use crate::errors::ParseError;
-use crate::language_parser::{LanguageParser, ParsedFile, FunctionInfo, MethodCall};
+use crate::language_parser::{collect_syntax_errors, walk_tree, CallFilter, LanguageParser, ParsedFile, FunctionInfo, MethodCall, Visibility};
 use crate::types::*;
 
 /// Rust 语言解析器
@@ -11,6 +12,8 @@ use crate::types::*;
 /// 使用 tree-sitter-rust 解析 Rust 源代码
 pub struct RustParser {
     parser: Mutex<Parser>,
+    /// 提取调用时使用的过滤器
+    call_filter: CallFilter,
 }
 
 impl RustParser {
@@ -26,6 +29,7 @@ impl RustParser {
         
         Ok(RustParser { 
             parser: Mutex::new(parser),
+            call_filter: CallFilter::default(),
         })
     }
     
@@ -39,7 +43,9 @@ impl RustParser {
         functions
     }
     
-    /// 递归遍历节点查找函数声明
+    /// 遍历节点查找函数声明
+    /// 
+    /// 使用显式栈迭代遍历，避免深层嵌套的表达式导致栈溢出
     fn walk_node_for_functions(
         &self,
         source: &str,
@@ -48,37 +54,45 @@ impl RustParser {
         functions: &mut Vec<FunctionInfo>,
         module_path: Option<&str>,
     ) {
-        if node.kind() == "function_item" {
-            if let Some(func_info) = self.extract_function_info(source, file_path, node, module_path) {
-                functions.push(func_info);
-            }
-        } else if node.kind() == "mod_item" {
-            // 提取模块名并递归处理模块内容
-            if let Some(mod_name) = self.extract_module_name(source, node) {
-                let new_path = if let Some(parent) = module_path {
-                    format!("{}::{}", parent, mod_name)
-                } else {
-                    mod_name
-                };
-                
-                // 查找模块体
-                let mut cursor = node.walk();
-                for child in node.children(&mut cursor) {
-                    if child.kind() == "declaration_list" {
-                        let mut body_cursor = child.walk();
-                        for body_child in child.children(&mut body_cursor) {
-                            self.walk_node_for_functions(source, file_path, body_child, functions, Some(&new_path));
-                        }
+        // 模块路径表，栈中的节点通过下标引用所在模块
+        let mut module_paths: Vec<String> = module_path.into_iter().map(str::to_string).collect();
+        let mut stack = vec![(node, module_paths.len().checked_sub(1))];
+        
+        while let Some((node, module)) = stack.pop() {
+            let module_path = module.map(|i| module_paths[i].as_str());
+            
+            if node.kind() == "function_item" {
+                if let Some(func_info) = self.extract_function_info(source, file_path, node, module_path) {
+                    functions.push(func_info);
+                }
+            } else if node.kind() == "mod_item" {
+                // 提取模块名，只处理模块体内的内容
+                if let Some(mod_name) = self.extract_module_name(source, node) {
+                    let new_path = if let Some(parent) = module_path {
+                        format!("{}::{}", parent, mod_name)
+                    } else {
+                        mod_name
+                    };
+                    module_paths.push(new_path);
+                    let new_module = Some(module_paths.len() - 1);
+                    
+                    let mut cursor = node.walk();
+                    let bodies: Vec<_> = node.children(&mut cursor)
+                        .filter(|child| child.kind() == "declaration_list")
+                        .collect();
+                    for body in bodies.into_iter().rev() {
+                        let mut body_cursor = body.walk();
+                        let items: Vec<_> = body.children(&mut body_cursor).collect();
+                        stack.extend(items.into_iter().rev().map(|item| (item, new_module)));
                     }
+                    continue;
                 }
-                // Don't continue walking children after processing mod_item
-                return;
             }
-        }
-        
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            self.walk_node_for_functions(source, file_path, child, functions, module_path);
+            
+            // 逆序入栈，保证按源码顺序访问子节点
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev().map(|child| (child, module)));
         }
     }
     
@@ -86,10 +100,10 @@ impl RustParser {
     fn extract_module_name(&self, source: &str, mod_node: tree_sitter::Node) -> Option<String> {
         let mut cursor = mod_node.walk();
         for child in mod_node.children(&mut cursor) {
-            if child.kind() == "identifier" {
-                if let Some(text) = source.get(child.byte_range()) {
-                    return Some(text.to_string());
-                }
+            if child.kind() == "identifier"
+                && let Some(text) = source.get(child.byte_range())
+            {
+                return Some(text.to_string());
             }
         }
         None
@@ -108,11 +122,11 @@ impl RustParser {
         let mut func_name = None;
         
         for child in func_node.children(&mut cursor) {
-            if child.kind() == "identifier" {
-                if let Some(text) = source.get(child.byte_range()) {
-                    func_name = Some(text.to_string());
-                    break;
-                }
+            if child.kind() == "identifier"
+                && let Some(text) = source.get(child.byte_range())
+            {
+                func_name = Some(text.to_string());
+                break;
             }
         }
         
@@ -129,8 +143,8 @@ impl RustParser {
         // 提取函数调用
         let calls = self.extract_function_calls(source, &func_node);
         
-        // 提取 Axum 路由宏
-        let http_annotations = self.extract_axum_routes(source, &func_node);
+        // 提取函数上的路由属性宏（actix-web/rocket），Axum 路由在整个文件解析后按函数名关联
+        let http_annotations = self.extract_route_attribute(source, &func_node);
         
         // 提取 Kafka 操作
         let kafka_operations = self.extract_kafka_operations(source, &func_node);
@@ -151,6 +165,11 @@ impl RustParser {
             kafka_operations,
             db_operations,
             redis_operations,
+            rpc_operations: Vec::new(),
+            thrift_operations: Vec::new(),
+            graphql_resolver: None,
+            column_range: Some((func_node.start_position().column, func_node.end_position().column)),
+            visibility: function_visibility(source, &func_node),
         })
     }
     
@@ -158,90 +177,188 @@ impl RustParser {
     fn extract_function_calls(&self, source: &str, func_node: &tree_sitter::Node) -> Vec<MethodCall> {
         let mut calls = Vec::new();
         self.walk_node_for_calls(source, *func_node, &mut calls);
+        calls.retain(|call| !self.call_filter.is_ignored(&call.target));
         calls
     }
     
-    /// 递归遍历节点查找函数调用
+    /// 遍历节点查找函数调用
     fn walk_node_for_calls(&self, source: &str, node: tree_sitter::Node, calls: &mut Vec<MethodCall>) {
-        if node.kind() == "call_expression" {
-            // 查找被调用的函数 - 第一个子节点通常是被调用的表达式
-            if let Some(first_child) = node.child(0) {
-                if let Some(text) = source.get(first_child.byte_range()) {
+        walk_tree(node, |node| {
+            if node.kind() == "call_expression" {
+                // 查找被调用的函数 - 第一个子节点通常是被调用的表达式
+                if let Some(first_child) = node.child(0)
+                    && let Some(text) = source.get(first_child.byte_range())
+                {
                     let line = node.start_position().row + 1;
                     calls.push(MethodCall {
                         target: text.to_string(),
                         line,
+                        condition: None,
+                        low_confidence: false,
                     });
                 }
-            }
-        } else if node.kind() == "macro_invocation" {
-            // 处理宏调用（如 println!）
-            let mut cursor = node.walk();
-            for child in node.children(&mut cursor) {
-                if child.kind() == "identifier" {
-                    if let Some(text) = source.get(child.byte_range()) {
+            } else if node.kind() == "macro_invocation" {
+                // 处理宏调用（如 println!）
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    if child.kind() == "identifier"
+                        && let Some(text) = source.get(child.byte_range())
+                    {
                         let line = node.start_position().row + 1;
                         calls.push(MethodCall {
                             target: format!("{}!", text),
                             line,
+                            condition: None,
+                            low_confidence: false,
                         });
                         break;
                     }
                 }
             }
-        }
-        
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            self.walk_node_for_calls(source, child, calls);
-        }
+            true
+        });
     }
     
-    /// 提取 Axum 路由宏
-    fn extract_axum_routes(&self, source: &str, func_node: &tree_sitter::Node) -> Option<HttpAnnotation> {
-        // 在函数所在的上下文中查找 Router::route 调用
-        // 这需要在更大的上下文中查找，暂时通过正则表达式在函数附近查找
-        
-        // 获取函数的起始位置，向前查找一些行
-        let func_start = func_node.start_byte();
-        let search_start = if func_start > 500 { func_start - 500 } else { 0 };
-        
-        if let Some(context) = source.get(search_start..func_node.end_byte()) {
-            // 查找 .route("/path", get(function_name)) 模式
-            let route_pattern = Regex::new(r#"\.route\s*\(\s*"([^"]+)"\s*,\s*(get|post|put|delete|patch)\s*\("#).unwrap();
+    /// 提取 Axum 路由表：处理函数名 -> HTTP 注解
+    /// 
+    /// 在 AST 中查找 `Router::new().route("/path", get(handler).post(other))` 链上的每个
+    /// `.route(...)` 调用，按 `get(...)`/`post(...)` 中的处理函数标识符建立映射，
+    /// 同一链上注册的多个路由各自对应到正确的处理函数
+    fn extract_axum_routes(&self, source: &str, tree: &tree_sitter::Tree) -> HashMap<String, HttpAnnotation> {
+        let mut routes = HashMap::new();
+        
+        walk_tree(tree.root_node(), |node| {
+            if node.kind() != "call_expression" {
+                return true;
+            }
             
-            if let Some(cap) = route_pattern.captures(context) {
-                let path = cap.get(1)?.as_str().to_string();
-                let method_str = cap.get(2)?.as_str();
-                
-                let method = match method_str {
-                    "get" => HttpMethod::GET,
-                    "post" => HttpMethod::POST,
-                    "put" => HttpMethod::PUT,
-                    "delete" => HttpMethod::DELETE,
-                    "patch" => HttpMethod::PATCH,
-                    _ => return None,
-                };
-                
-                let path_params = self.extract_path_params(&path);
-                
-                return Some(HttpAnnotation {
+            let is_route_call = node.child_by_field_name("function")
+                .filter(|function| function.kind() == "field_expression")
+                .and_then(|function| function.child_by_field_name("field"))
+                .and_then(|field| source.get(field.byte_range()))
+                == Some("route");
+            if !is_route_call {
+                return true;
+            }
+            
+            let Some(arguments) = node.child_by_field_name("arguments") else {
+                return true;
+            };
+            let mut cursor = arguments.walk();
+            let args: Vec<_> = arguments.named_children(&mut cursor).collect();
+            let (Some(path_node), Some(method_router)) = (args.first(), args.get(1)) else {
+                return true;
+            };
+            if path_node.kind() != "string_literal" {
+                return true;
+            }
+            let Some(path) = source.get(path_node.byte_range()).map(|text| text.trim_matches('"')) else {
+                return true;
+            };
+            
+            for (method, handler) in self.method_router_handlers(source, *method_router) {
+                routes.entry(handler).or_insert_with(|| HttpAnnotation {
                     method,
-                    path,
-                    path_params,
+                    path: path.to_string(),
+                    path_params: self.extract_path_params(path),
                     is_feign_client: false,  // Rust 不使用 Feign
                 });
             }
+            true
+        });
+        
+        routes
+    }
+    
+    /// 解析 Axum 方法路由（如 `get(list).post(create)`），返回 (HTTP 方法, 处理函数名) 列表
+    /// 
+    /// 处理函数可以带模块路径（如 `handlers::get_user`），只保留函数名
+    fn method_router_handlers(&self, source: &str, node: tree_sitter::Node) -> Vec<(HttpMethod, String)> {
+        let mut handlers = Vec::new();
+        let mut current = Some(node);
+        
+        while let Some(call) = current.filter(|n| n.kind() == "call_expression") {
+            current = None;
+            let Some(function) = call.child_by_field_name("function") else {
+                break;
+            };
+            
+            // `get(handler)` / `routing::get(handler)` 或链式的 `.post(handler)`
+            let method_name = match function.kind() {
+                "identifier" | "scoped_identifier" => source.get(function.byte_range())
+                    .map(|text| text.rsplit("::").next().unwrap_or(text)),
+                "field_expression" => {
+                    current = function.child_by_field_name("value");
+                    function.child_by_field_name("field")
+                        .and_then(|field| source.get(field.byte_range()))
+                }
+                _ => None,
+            };
+            
+            let handler = call.child_by_field_name("arguments")
+                .and_then(|arguments| arguments.named_child(0))
+                .filter(|arg| matches!(arg.kind(), "identifier" | "scoped_identifier"))
+                .and_then(|arg| source.get(arg.byte_range()))
+                .map(|text| text.rsplit("::").next().unwrap_or(text).to_string());
+            
+            if let (Some(method), Some(handler)) = (method_name.and_then(http_method_from_name), handler) {
+                handlers.push((method, handler));
+            }
+        }
+        
+        // 链是从右向左解析的，恢复源码顺序
+        handlers.reverse();
+        handlers
+    }
+    
+    /// 提取处理函数上的路由属性宏
+    /// 
+    /// 支持 actix-web 和 rocket 的 `#[get("/path")]`、`#[post("/path", data = "<form>")]`
+    /// 等形式（可带 `actix_web::` 等路径前缀）。属性宏直接附着在函数上，不依赖位置推断
+    fn extract_route_attribute(&self, source: &str, func_node: &tree_sitter::Node) -> Option<HttpAnnotation> {
+        let attribute_pattern = Regex::new(
+            r#"^#\[\s*(?:\w+::)*(get|post|put|delete|patch)\s*\(\s*"([^"]+)""#
+        ).unwrap();
+        
+        // 属性是函数节点前面的兄弟节点，中间可能夹有注释
+        let mut sibling = func_node.prev_sibling();
+        while let Some(node) = sibling {
+            match node.kind() {
+                "attribute_item" => {
+                    if let Some(cap) = source.get(node.byte_range())
+                        .and_then(|text| attribute_pattern.captures(text))
+                    {
+                        let method = http_method_from_name(&cap[1])?;
+                        let path = cap[2].to_string();
+                        let path_params = self.extract_path_params(&path);
+                        
+                        return Some(HttpAnnotation {
+                            method,
+                            path,
+                            path_params,
+                            is_feign_client: false,
+                        });
+                    }
+                }
+                "line_comment" | "block_comment" => {}
+                _ => break,
+            }
+            sibling = node.prev_sibling();
         }
         
         None
     }
     
     /// 提取路径参数
+    /// 
+    /// 支持 Axum 的 `:id`、actix-web 的 `{id}`（可带正则约束）和 rocket 的 `<id>` / `<path..>`
     fn extract_path_params(&self, path: &str) -> Vec<String> {
-        let re = Regex::new(r":(\w+)").unwrap();
+        let re = Regex::new(r"(?::(\w+))|(?:\{(\w+)[^}]*\})|(?:<(\w+)(?:\.\.)?>)").unwrap();
         re.captures_iter(path)
-            .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+            .filter_map(|cap| {
+                cap.get(1).or_else(|| cap.get(2)).or_else(|| cap.get(3))
+                    .map(|m| m.as_str().to_string())
+            })
             .collect()
     }
     
@@ -258,6 +375,8 @@ impl RustParser {
                         operation_type: KafkaOpType::Produce,
                         topic: topic.as_str().to_string(),
                         line: func_node.start_position().row + 1,
+                        topic_constant: None,
+                        value_type: None,
                     });
                 }
             }
@@ -273,6 +392,8 @@ impl RustParser {
                             operation_type: KafkaOpType::Consume,
                             topic: topic.as_str().to_string(),
                             line: func_node.start_position().row + 1,
+                            topic_constant: None,
+                            value_type: None,
                         });
                     }
                 }
@@ -336,26 +457,26 @@ impl RustParser {
             }
             
             for cap in set_pattern.captures_iter(text) {
-                if let Some(key) = cap.get(1) {
-                    if text.contains("redis") || text.contains("Commands") {
-                        operations.push(RedisOperation {
-                            operation_type: RedisOpType::Set,
-                            key_pattern: key.as_str().to_string(),
-                            line: func_node.start_position().row + 1,
-                        });
-                    }
+                if let Some(key) = cap.get(1)
+                    && (text.contains("redis") || text.contains("Commands"))
+                {
+                    operations.push(RedisOperation {
+                        operation_type: RedisOpType::Set,
+                        key_pattern: key.as_str().to_string(),
+                        line: func_node.start_position().row + 1,
+                    });
                 }
             }
             
             for cap in del_pattern.captures_iter(text) {
-                if let Some(key) = cap.get(1) {
-                    if text.contains("redis") || text.contains("Commands") {
-                        operations.push(RedisOperation {
-                            operation_type: RedisOpType::Delete,
-                            key_pattern: key.as_str().to_string(),
-                            line: func_node.start_position().row + 1,
-                        });
-                    }
+                if let Some(key) = cap.get(1)
+                    && (text.contains("redis") || text.contains("Commands"))
+                {
+                    operations.push(RedisOperation {
+                        operation_type: RedisOpType::Delete,
+                        key_pattern: key.as_str().to_string(),
+                        line: func_node.start_position().row + 1,
+                    });
                 }
             }
         }
@@ -373,10 +494,12 @@ impl RustParser {
         imports
     }
     
-    /// 递归遍历节点查找导入声明
+    /// 遍历节点查找导入声明
     fn walk_node_for_imports(&self, source: &str, node: tree_sitter::Node, imports: &mut Vec<Import>) {
-        if node.kind() == "use_declaration" {
-            if let Some(text) = source.get(node.byte_range()) {
+        walk_tree(node, |node| {
+            if node.kind() == "use_declaration"
+                && let Some(text) = source.get(node.byte_range())
+            {
                 // 简单提取 use 语句
                 let use_text = text.trim_start_matches("use").trim_end_matches(';').trim();
                 
@@ -403,12 +526,43 @@ impl RustParser {
                     }
                 }
             }
-        }
-        
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            self.walk_node_for_imports(source, child, imports);
-        }
+            true
+        });
+    }
+}
+
+/// 取函数的可见性
+/// 
+/// trait 定义和 trait 实现中的函数与 trait 一样可见，按公开处理；`pub(crate)`、`pub(super)`、
+/// `pub(in path)` 按包级可见处理，没有可见性修饰符的函数为私有
+fn function_visibility(source: &str, func_node: &tree_sitter::Node) -> Visibility {
+    let container = func_node.parent().and_then(|body| body.parent());
+    if container.is_some_and(|item| {
+        item.kind() == "trait_item" || (item.kind() == "impl_item" && item.child_by_field_name("trait").is_some())
+    }) {
+        return Visibility::Public;
+    }
+    
+    let mut cursor = func_node.walk();
+    let modifier = func_node.children(&mut cursor)
+        .find(|child| child.kind() == "visibility_modifier")
+        .and_then(|child| source.get(child.byte_range()));
+    match modifier {
+        Some("pub") => Visibility::Public,
+        Some(_) => Visibility::Package,
+        None => Visibility::Private,
+    }
+}
+
+/// 将小写的 HTTP 方法名（路由宏名）转换为 `HttpMethod`
+fn http_method_from_name(name: &str) -> Option<HttpMethod> {
+    match name {
+        "get" => Some(HttpMethod::GET),
+        "post" => Some(HttpMethod::POST),
+        "put" => Some(HttpMethod::PUT),
+        "delete" => Some(HttpMethod::DELETE),
+        "patch" => Some(HttpMethod::PATCH),
+        _ => None,
     }
 }
 
@@ -421,13 +575,27 @@ impl LanguageParser for RustParser {
         &["rs"]
     }
     
+    fn set_call_filter(&mut self, filter: CallFilter) {
+        self.call_filter = filter;
+    }
+    
     fn parse_file(&self, content: &str, file_path: &Path) -> Result<ParsedFile, ParseError> {
         let tree = self.parser.lock().unwrap().parse(content, None)
             .ok_or_else(|| ParseError::InvalidFormat {
                 message: "Failed to parse Rust file".to_string(),
             })?;
+        let syntax_errors = collect_syntax_errors(&tree);
+        
+        let mut functions = self.extract_functions(content, file_path, &tree);
+        
+        // 将 Axum 路由关联到同名处理函数，路由属性宏优先
+        let routes = self.extract_axum_routes(content, &tree);
+        for function in &mut functions {
+            if function.http_annotations.is_none() {
+                function.http_annotations = routes.get(&function.name).cloned();
+            }
+        }
         
-        let functions = self.extract_functions(content, file_path, &tree);
         let imports = self.extract_imports(content, &tree);
         
         Ok(ParsedFile {
@@ -436,6 +604,9 @@ impl LanguageParser for RustParser {
             classes: vec![], // Rust 不使用类
             functions,
             imports,
+            string_constants: vec![],
+            functional_routes: vec![],
+            syntax_errors,
         })
     }
 }
@@ -508,6 +679,55 @@ mod tests {
         assert!(call_names.contains(&"println!"));
     }
     
+    #[test]
+    fn test_deeply_nested_expression_does_not_overflow() {
+        let depth = 20_000;
+        let nested = format!("{}1{}", "wrap(".repeat(depth), ")".repeat(depth));
+        let source = format!("mod generated {{\n    fn build() {{\n        helper();\n        let x = {};\n    }}\n}}\n", nested);
+        
+        // 在较小的线程栈上解析，确保遍历不依赖调用栈深度
+        let functions = std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(move || {
+                let parser = RustParser::new().unwrap();
+                parser.parse_file(&source, Path::new("generated.rs")).unwrap().functions
+            })
+            .unwrap()
+            .join()
+            .expect("parsing deeply nested code should not overflow the stack");
+        
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].full_qualified_name, "generated::build");
+        assert_eq!(functions[0].calls[0].target, "helper");
+        assert_eq!(functions[0].calls.len(), depth + 1);
+    }
+    
+    #[test]
+    fn test_call_filter_drops_matching_calls() {
+        let mut parser = RustParser::new().unwrap();
+        parser.set_call_filter(CallFilter::new([r"^get_", r"^println!$"]).unwrap());
+        let source = r#"
+            fn get_total() -> i32 {
+                1
+            }
+            
+            fn report() {
+                let total = get_total();
+                save(total);
+                println!("{}", total);
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("report.rs")).unwrap();
+        assert_eq!(result.functions.len(), 2);
+        assert_eq!(result.functions[0].name, "get_total");
+        
+        let call_names: Vec<&str> = result.functions[1].calls.iter()
+            .map(|c| c.target.as_str())
+            .collect();
+        assert_eq!(call_names, vec!["save"]);
+    }
+    
     #[test]
     fn test_extract_axum_routes() {
         let parser = RustParser::new().unwrap();
@@ -542,6 +762,104 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_axum_routes_in_one_chain_map_to_their_handlers() {
+        let parser = RustParser::new().unwrap();
+        let source = r#"
+            use axum::{Router, routing::{get, post, delete}};
+            
+            async fn list_orders() -> String { String::new() }
+            async fn create_order() -> String { String::new() }
+            async fn get_order() -> String { String::new() }
+            async fn cancel_order() -> String { String::new() }
+            async fn health() -> String { String::new() }
+            
+            fn app() -> Router {
+                Router::new()
+                    .route("/orders", get(list_orders).post(create_order))
+                    .route("/orders/:id", axum::routing::get(handlers::get_order))
+                    .route("/orders/:id/cancel", delete(cancel_order))
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("routes.rs")).unwrap();
+        let http_of = |name: &str| result.functions.iter()
+            .find(|f| f.name == name)
+            .and_then(|f| f.http_annotations.clone())
+            .map(|http| (http.method, http.path, http.path_params));
+        
+        assert_eq!(http_of("list_orders"), Some((HttpMethod::GET, "/orders".to_string(), vec![])));
+        assert_eq!(http_of("create_order"), Some((HttpMethod::POST, "/orders".to_string(), vec![])));
+        assert_eq!(http_of("get_order"), Some((HttpMethod::GET, "/orders/:id".to_string(), vec!["id".to_string()])));
+        assert_eq!(http_of("cancel_order"), Some((HttpMethod::DELETE, "/orders/:id/cancel".to_string(), vec!["id".to_string()])));
+        
+        // 未注册路由的函数以及构建路由的函数本身都不是处理函数
+        assert_eq!(http_of("health"), None);
+        assert_eq!(http_of("app"), None);
+    }
+    
+    #[test]
+    fn test_extract_actix_route_attribute() {
+        let parser = RustParser::new().unwrap();
+        let source = r#"
+            use actix_web::{get, web, HttpResponse};
+            
+            #[get("/users/{id}/orders/{order_id:\d+}")]
+            // 查询用户订单
+            async fn get_user_order(path: web::Path<(u64, u64)>) -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
+            
+            #[actix_web::delete("/users/{id}")]
+            async fn delete_user() -> HttpResponse {
+                HttpResponse::NoContent().finish()
+            }
+            
+            async fn health() -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("handlers.rs")).unwrap();
+        let http_of = |name: &str| result.functions.iter()
+            .find(|f| f.name == name)
+            .and_then(|f| f.http_annotations.clone());
+        
+        let http = http_of("get_user_order").unwrap();
+        assert_eq!(http.method, HttpMethod::GET);
+        assert_eq!(http.path, "/users/{id}/orders/{order_id:\\d+}");
+        assert_eq!(http.path_params, vec!["id", "order_id"]);
+        assert!(!http.is_feign_client);
+        
+        let http = http_of("delete_user").unwrap();
+        assert_eq!(http.method, HttpMethod::DELETE);
+        assert_eq!(http.path, "/users/{id}");
+        
+        // 没有路由属性、附近也没有 Axum 路由的函数不是 HTTP 处理函数
+        assert!(http_of("health").is_none());
+    }
+    
+    #[test]
+    fn test_extract_rocket_route_attribute() {
+        let parser = RustParser::new().unwrap();
+        let source = r#"
+            #[macro_use] extern crate rocket;
+            
+            #[post("/orders/<id>/items/<path..>", data = "<item>")]
+            fn add_item(id: u64, path: PathBuf, item: Json<Item>) -> Status {
+                Status::Created
+            }
+        "#;
+        
+        let result = parser.parse_file(source, Path::new("main.rs")).unwrap();
+        let func = result.functions.iter().find(|f| f.name == "add_item").unwrap();
+        let http = func.http_annotations.as_ref().unwrap();
+        
+        assert_eq!(http.method, HttpMethod::POST);
+        assert_eq!(http.path, "/orders/<id>/items/<path..>");
+        assert_eq!(http.path_params, vec!["id", "path"]);
+    }
+    
     #[test]
     fn test_extract_kafka_operations() {
         let parser = RustParser::new().unwrap();
@@ -0,0 +1,100 @@
+use crate::config_parser::{ConfigData, ConfigParser};
+use crate::errors::ParseError;
+use regex::Regex;
+
+/// SQL 迁移文件解析器
+///
+/// 从 Flyway/Liquibase 等迁移脚本（`.sql` 文件）的 `CREATE TABLE` 语句中提取表名，
+/// 作为数据库表的权威清单。schema 限定名（如 `public.orders`）只保留表名，
+/// 引号（`"`、`` ` ``、`[]`）会被去除
+pub struct SqlMigrationParser;
+
+impl ConfigParser for SqlMigrationParser {
+    fn parse(&self, content: &str) -> Result<ConfigData, ParseError> {
+        let source = strip_comments(content);
+        
+        let create_table_re = Regex::new(
+            r#"(?i)\bCREATE\s+(?:OR\s+REPLACE\s+)?(?:(?:GLOBAL|LOCAL)\s+)?(?:TEMP(?:ORARY)?\s+)?(?:UNLOGGED\s+)?TABLE\s+(?:IF\s+NOT\s+EXISTS\s+)?((?:[`"\[]?\w+[`"\]]?\s*\.\s*)*[`"\[]?\w+[`"\]]?)"#,
+        ).unwrap();
+        
+        let mut tables: Vec<String> = Vec::new();
+        for cap in create_table_re.captures_iter(&source) {
+            let Some(table) = cap[1].rsplit('.').next().map(unquote_identifier) else {
+                continue;
+            };
+            if !table.is_empty() && !tables.contains(&table) {
+                tables.push(table);
+            }
+        }
+        
+        Ok(ConfigData {
+            db_tables: tables,
+            ..ConfigData::default()
+        })
+    }
+    
+    fn supports_format(&self, format: &str) -> bool {
+        format == "sql"
+    }
+    
+    fn file_extensions(&self) -> &[&str] {
+        &["sql"]
+    }
+}
+
+/// 去除 `--` 单行注释和 `/* */` 块注释
+fn strip_comments(content: &str) -> String {
+    let block_comment = Regex::new(r"(?s)/\*.*?\*/").unwrap();
+    let line_comment = Regex::new(r"--[^\n]*").unwrap();
+    let without_blocks = block_comment.replace_all(content, " ");
+    line_comment.replace_all(&without_blocks, "").into_owned()
+}
+
+/// 去除标识符两侧的空白和引号
+fn unquote_identifier(identifier: &str) -> String {
+    identifier.trim()
+        .trim_matches(|c| matches!(c, '`' | '"' | '[' | ']'))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_create_table_migration() {
+        let sql = r#"
+            -- V1__create_orders.sql
+            CREATE TABLE orders (
+                id BIGINT PRIMARY KEY,
+                status VARCHAR(32) NOT NULL
+            );
+            
+            create table if not exists `order_items` (id bigint);
+            CREATE TABLE public."payments" (id BIGINT);
+            /* CREATE TABLE legacy_orders (id BIGINT); */
+            CREATE INDEX idx_orders_status ON orders (status);
+            CREATE TABLE orders_archive AS SELECT * FROM orders;
+        "#;
+        
+        let config = SqlMigrationParser.parse(sql).unwrap();
+        assert_eq!(config.db_tables, vec![
+            "orders".to_string(),
+            "order_items".to_string(),
+            "payments".to_string(),
+            "orders_archive".to_string(),
+        ]);
+    }
+    
+    #[test]
+    fn test_parse_sql_without_tables() {
+        let config = SqlMigrationParser.parse("ALTER TABLE orders ADD COLUMN note TEXT;").unwrap();
+        assert!(config.db_tables.is_empty());
+    }
+    
+    #[test]
+    fn test_supports_sql_format() {
+        assert!(SqlMigrationParser.supports_format("sql"));
+        assert!(!SqlMigrationParser.supports_format("thrift"));
+    }
+}
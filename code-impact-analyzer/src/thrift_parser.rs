@@ -0,0 +1,239 @@
+use crate::config_parser::{ConfigData, ConfigParser};
+use crate::errors::ParseError;
+use crate::types::ThriftService;
+use regex::Regex;
+
+/// Thrift IDL 解析器
+///
+/// 从 `.thrift` 文件中提取 `service` 定义及其方法。服务名使用
+/// `namespace java` 声明的包名限定，与生成的 Java 类 `<namespace>.<Service>` 一致，
+/// 以便与 Java 代码中 `Xxx.Iface` 实现和 `Xxx.Client` 调用对应
+pub struct ThriftIdlParser;
+
+impl ConfigParser for ThriftIdlParser {
+    fn parse(&self, content: &str) -> Result<ConfigData, ParseError> {
+        let source = strip_comments(content);
+        let namespace = extract_java_namespace(&source);
+        
+        let service_re = Regex::new(r"\bservice\s+(\w+)(?:\s+extends\s+[\w.]+)?\s*\{").unwrap();
+        
+        let mut services = Vec::new();
+        for cap in service_re.captures_iter(&source) {
+            let name = &cap[1];
+            let body_start = cap.get(0).map(|m| m.end()).unwrap_or_default();
+            let body_end = source[body_start..].find('}')
+                .map(|offset| body_start + offset)
+                .ok_or_else(|| ParseError::InvalidFormat {
+                    message: format!("Unterminated thrift service: {}", name),
+                })?;
+            
+            services.push(ThriftService {
+                name: match &namespace {
+                    Some(ns) => format!("{}.{}", ns, name),
+                    None => name.to_string(),
+                },
+                methods: extract_service_methods(&source[body_start..body_end]),
+            });
+        }
+        
+        Ok(ConfigData {
+            thrift_services: services,
+            ..ConfigData::default()
+        })
+    }
+    
+    fn supports_format(&self, format: &str) -> bool {
+        format == "thrift"
+    }
+    
+    fn file_extensions(&self) -> &[&str] {
+        &["thrift"]
+    }
+}
+
+/// 去除 `//`、`#` 单行注释和 `/* */` 块注释
+/// 
+/// 字符串字面量（`"..."` 或 `'...'`）中的注释符号（如 URL 中的 `//`、`#`）原样保留
+fn strip_comments(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut quote: Option<char> = None;
+    
+    while let Some(c) = chars.next() {
+        if let Some(open) = quote {
+            result.push(c);
+            if c == '\\' {
+                result.extend(chars.next());
+            } else if c == open {
+                quote = None;
+            }
+            continue;
+        }
+        
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                result.push(c);
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                result.push(' ');
+            }
+            '#' => skip_to_line_end(&mut chars),
+            '/' if chars.peek() == Some(&'/') => skip_to_line_end(&mut chars),
+            _ => result.push(c),
+        }
+    }
+    
+    result
+}
+
+/// 跳过当前行剩余的字符，保留换行符
+fn skip_to_line_end(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    while chars.next_if(|&c| c != '\n').is_some() {}
+}
+
+/// 提取 Java 命名空间，未声明时回退到 `namespace *`
+fn extract_java_namespace(source: &str) -> Option<String> {
+    let namespace_re = Regex::new(r"(?m)^\s*namespace\s+(java|\*)\s+([\w.]+)").unwrap();
+    
+    let mut fallback = None;
+    for cap in namespace_re.captures_iter(source) {
+        if &cap[1] == "java" {
+            return Some(cap[2].to_string());
+        }
+        fallback = Some(cap[2].to_string());
+    }
+    fallback
+}
+
+/// 提取服务体中的方法名
+///
+/// 方法定义形如 `[oneway] ReturnType name(1: T arg, ...) [throws (...)]`，
+/// 在括号外紧跟 `(` 的标识符即为方法名（`throws` 除外）
+fn extract_service_methods(body: &str) -> Vec<String> {
+    let mut methods = Vec::new();
+    let mut depth = 0usize;
+    let mut token = String::new();
+    let mut last_token = String::new();
+    
+    for c in body.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            token.push(c);
+            continue;
+        }
+        
+        if !token.is_empty() {
+            last_token = std::mem::take(&mut token);
+        }
+        
+        match c {
+            '(' => {
+                if depth == 0 && !last_token.is_empty() && last_token != "throws" {
+                    methods.push(last_token.clone());
+                }
+                depth += 1;
+                last_token.clear();
+            }
+            ')' => depth = depth.saturating_sub(1),
+            c if !c.is_whitespace() => last_token.clear(),
+            _ => {}
+        }
+    }
+    
+    methods
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_thrift_service() {
+        let idl = r#"
+            namespace java com.example.order.thrift
+            namespace py order
+            
+            include "common.thrift"
+            
+            struct Order {
+                1: required i64 id,
+                2: optional string status
+            }
+            
+            exception OrderNotFound {
+                1: string message
+            }
+            
+            /* 订单服务 */
+            service OrderService extends common.BaseService {
+                // 查询订单
+                Order getOrder(1: i64 id) throws (1: OrderNotFound e),
+                list<Order> listOrders(1: map<string, string> filters);
+                # 异步通知
+                oneway void notifyShipped(1: i64 id)
+            }
+        "#;
+        
+        let config = ThriftIdlParser.parse(idl).unwrap();
+        assert_eq!(config.thrift_services, vec![ThriftService {
+            name: "com.example.order.thrift.OrderService".to_string(),
+            methods: vec![
+                "getOrder".to_string(),
+                "listOrders".to_string(),
+                "notifyShipped".to_string(),
+            ],
+        }]);
+    }
+    
+    #[test]
+    fn test_parse_thrift_without_java_namespace() {
+        let idl = r#"
+            namespace * com.example.common
+            service Ping { void ping() }
+            service Health { bool check(1: string name) }
+        "#;
+        
+        let config = ThriftIdlParser.parse(idl).unwrap();
+        let names: Vec<&str> = config.thrift_services.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["com.example.common.Ping", "com.example.common.Health"]);
+        assert_eq!(config.thrift_services[1].methods, vec!["check".to_string()]);
+    }
+    
+    #[test]
+    fn test_comment_markers_inside_string_literals() {
+        let idl = r##"
+            namespace java com.example.order
+            const string DOCS = "https://docs.example.com/#orders" // 文档地址
+            const string ORDER_PATHS = "/orders/*"
+            
+            service OrderService {
+                list<Order> findByTag(1: string tag = "#hot")
+                /** 取消订单 */
+                void cancel(1: i64 id)
+            }
+        "##;
+        
+        let config = ThriftIdlParser.parse(idl).unwrap();
+        assert_eq!(config.thrift_services[0].methods, vec!["findByTag".to_string(), "cancel".to_string()]);
+        
+        let stripped = strip_comments(idl);
+        assert!(stripped.contains("\"https://docs.example.com/#orders\""));
+        assert!(stripped.contains("\"/orders/*\""));
+        assert!(!stripped.contains("文档地址"));
+        assert!(!stripped.contains("取消订单"));
+    }
+    
+    #[test]
+    fn test_supports_thrift_format() {
+        assert!(ThriftIdlParser.supports_format("thrift"));
+        assert!(!ThriftIdlParser.supports_format("yaml"));
+    }
+}
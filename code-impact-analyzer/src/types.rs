@@ -1,5 +1,8 @@
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 use serde::{Deserialize, Serialize};
+use crate::errors::ParseError;
 
 /// 方法定位信息
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -18,6 +21,23 @@ pub enum HttpMethod {
     PUT,
     DELETE,
     PATCH,
+    /// 未限定方法（如不指定 `method` 的 `@RequestMapping`），与同一路径上任意方法的端点匹配
+    #[serde(rename = "ANY")]
+    Any,
+}
+
+impl HttpMethod {
+    /// 获取 HTTP 方法的字符串表示（大写，未限定方法为 `ANY`）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::GET => "GET",
+            HttpMethod::POST => "POST",
+            HttpMethod::PUT => "PUT",
+            HttpMethod::DELETE => "DELETE",
+            HttpMethod::PATCH => "PATCH",
+            HttpMethod::Any => "ANY",
+        }
+    }
 }
 
 /// HTTP 注解信息
@@ -43,6 +63,16 @@ pub struct KafkaOperation {
     pub operation_type: KafkaOpType,
     pub topic: String,
     pub line: usize,
+    /// topic 引用的字符串常量全限定名（如 `com.example.Topics.ORDER_CREATED`）
+    /// 
+    /// 建立索引时会被解析为常量的值；解析前 `topic` 为源码中的引用文本
+    #[serde(default)]
+    pub topic_constant: Option<String>,
+    /// 消息值的类型简单名（如 `OrderEvent`），通常对应 Avro/Protobuf schema
+    /// 
+    /// 生产者取自 `send` 调用的消息参数，消费者取自 `@KafkaListener` 方法的消息参数
+    #[serde(default)]
+    pub value_type: Option<String>,
 }
 
 /// 数据库操作类型
@@ -78,6 +108,155 @@ pub struct RedisOperation {
     pub line: usize,
 }
 
+/// RPC 操作类型
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RpcOpType {
+    /// 服务提供者（如 @DubboService 实现类的方法）
+    Provide,
+    /// 服务消费者（如通过 @DubboReference 注入的接口调用）
+    Consume,
+}
+
+/// RPC 操作信息（Dubbo 等基于接口的 RPC 框架）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcOperation {
+    pub operation_type: RpcOpType,
+    /// RPC 接口的完整类名
+    pub interface: String,
+    /// 接口方法名
+    pub method: String,
+    pub line: usize,
+}
+
+impl RpcOperation {
+    /// 获取 RPC 方法的唯一标识：接口名::方法名
+    pub fn key(&self) -> String {
+        format!("{}::{}", self.interface, self.method)
+    }
+}
+
+/// Thrift 操作信息
+/// 
+/// 提供者是实现生成的 `Xxx.Iface` 接口的类的方法，消费者是通过生成的
+/// `Xxx.Client` 发起的调用
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThriftOperation {
+    pub operation_type: RpcOpType,
+    /// Thrift 服务的完整类名（IDL 中 `namespace java` 加服务名）
+    pub service: String,
+    /// 服务方法名
+    pub method: String,
+    pub line: usize,
+}
+
+impl ThriftOperation {
+    /// 获取 Thrift 方法的唯一标识：服务名::方法名
+    pub fn key(&self) -> String {
+        format!("{}::{}", self.service, self.method)
+    }
+}
+
+/// Thrift IDL 中定义的服务
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThriftService {
+    /// 服务的完整类名（`namespace java` 加服务名，未声明命名空间时为服务名）
+    pub name: String,
+    /// IDL 中声明的方法名
+    pub methods: Vec<String>,
+}
+
+/// Spring Batch XML（`<batch:job>`）中定义的作业
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchJobDefinition {
+    /// 作业 id
+    pub name: String,
+    /// 作业各步骤的 tasklet/reader/processor/writer 方法全限定名
+    pub methods: Vec<String>,
+}
+
+/// GraphQL 解析器（Spring GraphQL 的 `@QueryMapping` 等）
+/// 
+/// 解析器是服务的入口点，作为 `type_name.field` 字段的提供者
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphQLResolver {
+    /// GraphQL 类型名：Query、Mutation、Subscription 或自定义类型
+    pub type_name: String,
+    /// 字段名
+    pub field: String,
+}
+
+impl GraphQLResolver {
+    /// 获取 GraphQL 字段的唯一标识：类型名.字段名
+    pub fn key(&self) -> String {
+        format!("{}.{}", self.type_name, self.field)
+    }
+}
+
+/// OpenAPI/Swagger 接口文档注解（`@Operation` / `@ApiOperation`）
+/// 
+/// 为 HTTP 端点提供面向人的说明，附加到端点节点的元数据上
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiOperation {
+    /// 接口摘要：`@Operation(summary)` 或 `@ApiOperation(value)`
+    pub summary: Option<String>,
+    /// 接口描述：`@Operation(description)` 或 `@ApiOperation(notes)`
+    pub description: Option<String>,
+}
+
+impl ApiOperation {
+    /// 转换为节点元数据，只包含已设置的字段
+    pub fn to_properties(&self) -> Vec<(String, String)> {
+        let mut properties = Vec::new();
+        if let Some(summary) = &self.summary {
+            properties.push(("summary".to_string(), summary.clone()));
+        }
+        if let Some(description) = &self.description {
+            properties.push(("description".to_string(), description.clone()));
+        }
+        properties
+    }
+}
+
+/// Feign 客户端的超时与重试配置
+/// 
+/// 来自 `feign.client.config.<name>` 配置项，`<name>` 为 `@FeignClient` 的服务名，
+/// `default` 为所有客户端的默认配置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeignClientConfig {
+    /// 连接超时（毫秒）
+    pub connect_timeout: Option<u64>,
+    /// 读取超时（毫秒）
+    pub read_timeout: Option<u64>,
+    /// 重试器类名
+    pub retryer: Option<String>,
+}
+
+impl FeignClientConfig {
+    /// 用 `fallback` 补全未设置的配置项
+    pub fn or(self, fallback: &FeignClientConfig) -> Self {
+        Self {
+            connect_timeout: self.connect_timeout.or(fallback.connect_timeout),
+            read_timeout: self.read_timeout.or(fallback.read_timeout),
+            retryer: self.retryer.or_else(|| fallback.retryer.clone()),
+        }
+    }
+    
+    /// 转换为节点元数据，只包含已设置的配置项
+    pub fn to_properties(&self) -> Vec<(String, String)> {
+        let mut properties = Vec::new();
+        if let Some(timeout) = self.connect_timeout {
+            properties.push(("connect_timeout".to_string(), timeout.to_string()));
+        }
+        if let Some(timeout) = self.read_timeout {
+            properties.push(("read_timeout".to_string(), timeout.to_string()));
+        }
+        if let Some(retryer) = &self.retryer {
+            properties.push(("retryer".to_string(), retryer.clone()));
+        }
+        properties
+    }
+}
+
 /// 导入声明
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Import {
@@ -95,12 +274,103 @@ pub struct HttpEndpoint {
 impl HttpEndpoint {
     /// 获取 HTTP 方法的字符串表示
     pub fn method_str(&self) -> &str {
-        match self.method {
-            HttpMethod::GET => "GET",
-            HttpMethod::POST => "POST",
-            HttpMethod::PUT => "PUT",
-            HttpMethod::DELETE => "DELETE",
-            HttpMethod::PATCH => "PATCH",
+        self.method.as_str()
+    }
+    
+    /// 判断两个端点是否匹配：路径相同，且 HTTP 方法相同或任一方为 `Any`
+    pub fn matches(&self, other: &HttpEndpoint) -> bool {
+        self.path_pattern == other.path_pattern
+            && (self.method == other.method || self.method == HttpMethod::Any || other.method == HttpMethod::Any)
+    }
+}
+
+impl fmt::Display for HttpEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.method_str(), self.path_pattern)
+    }
+}
+
+impl FromStr for HttpEndpoint {
+    type Err = ParseError;
+    
+    /// 解析 `METHOD:PATH` 形式的端点字符串，如 `GET:/api/orders/{id}`
+    /// 
+    /// HTTP 方法不区分大小写
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |message: String| ParseError::InvalidFormat { message };
+        
+        let (method, path) = s.trim().split_once(':')
+            .ok_or_else(|| invalid(format!("expected METHOD:PATH, got '{}'", s)))?;
+        
+        let method = match method.trim().to_uppercase().as_str() {
+            "GET" => HttpMethod::GET,
+            "POST" => HttpMethod::POST,
+            "PUT" => HttpMethod::PUT,
+            "DELETE" => HttpMethod::DELETE,
+            "PATCH" => HttpMethod::PATCH,
+            "ANY" => HttpMethod::Any,
+            other => return Err(invalid(format!("unknown HTTP method '{}'", other))),
+        };
+        
+        let path = path.trim();
+        if path.is_empty() {
+            return Err(invalid(format!("missing path in '{}'", s)));
+        }
+        
+        Ok(HttpEndpoint {
+            method,
+            path_pattern: path.to_string(),
+        })
+    }
+}
+
+/// 追溯起点资源
+/// 
+/// 字符串形式与影响图中的资源节点 ID 相同，如 `db:orders`、`kafka:order-created`、
+/// `redis:user:`、`http:GET:/api/orders/{id}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceId {
+    /// 数据库表
+    Database(String),
+    /// Kafka Topic
+    Kafka(String),
+    /// Redis 键前缀
+    Redis(String),
+    /// HTTP 端点
+    Http(HttpEndpoint),
+}
+
+impl fmt::Display for ResourceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceId::Database(table) => write!(f, "db:{}", table),
+            ResourceId::Kafka(topic) => write!(f, "kafka:{}", topic),
+            ResourceId::Redis(prefix) => write!(f, "redis:{}", prefix),
+            ResourceId::Http(endpoint) => write!(f, "http:{}", endpoint),
+        }
+    }
+}
+
+impl FromStr for ResourceId {
+    type Err = ParseError;
+    
+    /// 解析 `KIND:NAME` 形式的资源字符串，KIND 为 db、kafka、redis 或 http
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |message: String| ParseError::InvalidFormat { message };
+        
+        let (kind, name) = s.trim().split_once(':')
+            .ok_or_else(|| invalid(format!("expected KIND:NAME, got '{}'", s)))?;
+        if name.trim().is_empty() {
+            return Err(invalid(format!("missing resource name in '{}'", s)));
+        }
+        
+        let name = name.trim().to_string();
+        match kind.trim().to_lowercase().as_str() {
+            "db" => Ok(ResourceId::Database(name)),
+            "kafka" => Ok(ResourceId::Kafka(name)),
+            "redis" => Ok(ResourceId::Redis(name)),
+            "http" => Ok(ResourceId::Http(name.parse()?)),
+            other => Err(invalid(format!("unknown resource kind '{}' (expected db, kafka, redis or http)", other))),
         }
     }
 }
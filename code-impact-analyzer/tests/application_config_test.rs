@@ -47,9 +47,9 @@ fn test_http_endpoint_with_application_config() {
     let controller_path = adapter_dir.join("FeignShopCopyController.java");
     let mut controller_file = fs::File::create(&controller_path).unwrap();
     writeln!(controller_file, "package com.hll.basic.api.adapter.feign;").unwrap();
-    writeln!(controller_file, "").unwrap();
+    writeln!(controller_file).unwrap();
     writeln!(controller_file, "import org.springframework.web.bind.annotation.*;").unwrap();
-    writeln!(controller_file, "").unwrap();
+    writeln!(controller_file).unwrap();
     writeln!(controller_file, "@RestController").unwrap();
     writeln!(controller_file, "@RequestMapping(\"feign/shop/copy\")").unwrap();
     writeln!(controller_file, "public class FeignShopCopyController {{").unwrap();
@@ -122,9 +122,9 @@ fn test_http_endpoint_without_context_path() {
     let controller_path = adapter_dir.join("TestController.java");
     let mut controller_file = fs::File::create(&controller_path).unwrap();
     writeln!(controller_file, "package com.example;").unwrap();
-    writeln!(controller_file, "").unwrap();
+    writeln!(controller_file).unwrap();
     writeln!(controller_file, "import org.springframework.web.bind.annotation.*;").unwrap();
-    writeln!(controller_file, "").unwrap();
+    writeln!(controller_file).unwrap();
     writeln!(controller_file, "@RestController").unwrap();
     writeln!(controller_file, "@RequestMapping(\"/api\")").unwrap();
     writeln!(controller_file, "public class TestController {{").unwrap();
@@ -197,9 +197,9 @@ fn test_http_endpoint_without_class_mapping() {
     let controller_path = adapter_dir.join("SimpleController.java");
     let mut controller_file = fs::File::create(&controller_path).unwrap();
     writeln!(controller_file, "package com.example;").unwrap();
-    writeln!(controller_file, "").unwrap();
+    writeln!(controller_file).unwrap();
     writeln!(controller_file, "import org.springframework.web.bind.annotation.*;").unwrap();
-    writeln!(controller_file, "").unwrap();
+    writeln!(controller_file).unwrap();
     writeln!(controller_file, "@RestController").unwrap();
     writeln!(controller_file, "public class SimpleController {{").unwrap();
     writeln!(controller_file, "    @PostMapping(\"/users\")").unwrap();
@@ -232,3 +232,107 @@ fn test_http_endpoint_without_class_mapping() {
         "HTTP path should be: application.name/context-path/method-path"
     );
 }
+
+/// 创建模块中的 Java 控制器文件，返回文件路径
+fn write_controller(module_dir: &std::path::Path, class_name: &str, mapping: &str) -> std::path::PathBuf {
+    let source_dir = module_dir
+        .join("src")
+        .join("main")
+        .join("java")
+        .join("com")
+        .join("example");
+    fs::create_dir_all(&source_dir).unwrap();
+    
+    let controller_path = source_dir.join(format!("{}.java", class_name));
+    let mut controller_file = fs::File::create(&controller_path).unwrap();
+    writeln!(controller_file, "package com.example;").unwrap();
+    writeln!(controller_file).unwrap();
+    writeln!(controller_file, "@RestController").unwrap();
+    writeln!(controller_file, "public class {} {{", class_name).unwrap();
+    writeln!(controller_file, "    @GetMapping(\"{}\")", mapping).unwrap();
+    writeln!(controller_file, "    public String get() {{").unwrap();
+    writeln!(controller_file, "        return null;").unwrap();
+    writeln!(controller_file, "    }}").unwrap();
+    writeln!(controller_file, "}}").unwrap();
+    
+    controller_path
+}
+
+/// 解析控制器文件，返回唯一方法的 HTTP 路径
+fn parse_http_path(controller_path: &std::path::Path) -> String {
+    let parser = JavaParser::new().unwrap();
+    let content = fs::read_to_string(controller_path).unwrap();
+    let result = parser.parse_file(&content, controller_path).unwrap();
+    result.classes[0].methods[0].http_annotations.as_ref().unwrap().path.clone()
+}
+
+#[test]
+fn test_http_endpoint_with_maven_module_config() {
+    // 标准 Maven 多模块布局：每个模块有自己的 src/main/resources/application.yml
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+    
+    let order_module = project_root.join("order-service");
+    let order_resources = order_module.join("src").join("main").join("resources");
+    fs::create_dir_all(&order_resources).unwrap();
+    fs::write(
+        order_resources.join("application.yml"),
+        "server:\n  servlet:\n    context-path: /orders\nspring:\n  application:\n    name: order-service\n",
+    ).unwrap();
+    
+    let user_module = project_root.join("user-service");
+    let user_resources = user_module.join("src").join("main").join("resources");
+    fs::create_dir_all(&user_resources).unwrap();
+    fs::write(
+        user_resources.join("application.yaml"),
+        "spring:\n  application:\n    name: user-service\n",
+    ).unwrap();
+    
+    // 每个模块的端点使用各自模块的配置
+    let order_controller = write_controller(&order_module, "OrderController", "/list");
+    assert_eq!(parse_http_path(&order_controller), "order-service/orders/list");
+    
+    let user_controller = write_controller(&user_module, "UserController", "/profile");
+    assert_eq!(parse_http_path(&user_controller), "user-service/profile");
+}
+
+#[test]
+fn test_maven_module_config_without_application_name() {
+    // 未配置 spring.application.name 时使用模块目录名
+    let temp_dir = TempDir::new().unwrap();
+    
+    let module = temp_dir.path().join("inventory");
+    let resources = module.join("src").join("main").join("resources");
+    fs::create_dir_all(&resources).unwrap();
+    fs::write(resources.join("application.yml"), "server:\n  port: 8080\n").unwrap();
+    
+    let controller = write_controller(&module, "StockController", "/stock");
+    assert_eq!(parse_http_path(&controller), "inventory/stock");
+}
+
+#[test]
+fn test_module_config_lookup_stops_at_workspace_root() {
+    // 工作空间之外的 application.yml 不应被当作模块配置
+    let temp_dir = TempDir::new().unwrap();
+    let outer_resources = temp_dir.path().join("src").join("main").join("resources");
+    fs::create_dir_all(&outer_resources).unwrap();
+    fs::write(
+        outer_resources.join("application.yml"),
+        "spring:\n  application:\n    name: unrelated\n",
+    ).unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    let controller = write_controller(&workspace, "OrderController", "/list");
+    let content = fs::read_to_string(&controller).unwrap();
+    
+    // 未限定工作空间时会向上找到外层配置
+    let unbounded = JavaParser::new().unwrap();
+    let result = unbounded.parse_file(&content, &controller).unwrap();
+    let path = &result.classes[0].methods[0].http_annotations.as_ref().unwrap().path;
+    assert!(path.starts_with("unrelated"));
+    
+    let parser = JavaParser::new().unwrap().with_workspace_root(&workspace);
+    let result = parser.parse_file(&content, &controller).unwrap();
+    let path = &result.classes[0].methods[0].http_annotations.as_ref().unwrap().path;
+    assert!(!path.contains("unrelated"), "unexpected path {}", path);
+}
@@ -17,16 +17,13 @@ fn test_http_endpoint_config_association() {
         name: "getUserById".to_string(),
         full_qualified_name: "com.example.api.UserController::getUserById".to_string(),
         file_path: std::path::PathBuf::from("test.java"),        line_range: (10, 25),
-        calls: vec![],
         http_annotations: Some(HttpAnnotation {
             method: HttpMethod::GET,
             path: "/api/v1/users/{id}".to_string(),
             path_params: vec!["id".to_string()],
             is_feign_client: false,
         }),
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 添加 HTTP 消费者（调用该接口的客户端代码）
@@ -38,12 +35,11 @@ fn test_http_endpoint_config_association() {
             MethodCall {
                 target: "RestTemplate.getForObject(/api/v1/users)".to_string(),
                 line: 35,
+                condition: None,
+                low_confidence: false,
             },
         ],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     index.test_index_method(&provider).unwrap();
@@ -92,17 +88,16 @@ fn test_kafka_topic_config_association() {
         name: "publishUserEvent".to_string(),
         full_qualified_name: "com.example.events.UserEventPublisher::publishUserEvent".to_string(),
         file_path: std::path::PathBuf::from("test.java"),        line_range: (10, 25),
-        calls: vec![],
-        http_annotations: None,
         kafka_operations: vec![
             KafkaOperation {
                 operation_type: KafkaOpType::Produce,
                 topic: "user-lifecycle-events".to_string(),
                 line: 15,
+                topic_constant: None,
+                value_type: None,
             },
         ],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 添加 Kafka 消费者
@@ -110,17 +105,16 @@ fn test_kafka_topic_config_association() {
         name: "handleUserEvent".to_string(),
         full_qualified_name: "com.example.handlers.UserEventHandler::handleUserEvent".to_string(),
         file_path: std::path::PathBuf::from("test.java"),        line_range: (30, 50),
-        calls: vec![],
-        http_annotations: None,
         kafka_operations: vec![
             KafkaOperation {
                 operation_type: KafkaOpType::Consume,
                 topic: "user-lifecycle-events".to_string(),
                 line: 35,
+                topic_constant: None,
+                value_type: None,
             },
         ],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     index.test_index_method(&producer).unwrap();
@@ -135,7 +129,7 @@ fn test_kafka_topic_config_association() {
           bootstrap-servers: localhost:9092
     "#;
     
-    let parser = YamlConfigParser;
+    let parser = YamlConfigParser::new();
     let config_data = parser.parse(yaml_config).unwrap();
     
     // 关联配置到代码
@@ -158,9 +152,6 @@ fn test_database_table_config_association() {
         name: "findUserById".to_string(),
         full_qualified_name: "com.example.repository.UserRepository::findUserById".to_string(),
         file_path: std::path::PathBuf::from("test.java"),        line_range: (10, 20),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
         db_operations: vec![
             DbOperation {
                 operation_type: DbOpType::Select,
@@ -168,7 +159,7 @@ fn test_database_table_config_association() {
                 line: 15,
             },
         ],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 添加数据库写入者
@@ -176,9 +167,6 @@ fn test_database_table_config_association() {
         name: "saveUser".to_string(),
         full_qualified_name: "com.example.repository.UserRepository::saveUser".to_string(),
         file_path: std::path::PathBuf::from("test.java"),        line_range: (25, 35),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
         db_operations: vec![
             DbOperation {
                 operation_type: DbOpType::Insert,
@@ -186,16 +174,13 @@ fn test_database_table_config_association() {
                 line: 30,
             },
         ],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     let updater = MethodInfo {
         name: "updateUser".to_string(),
         full_qualified_name: "com.example.repository.UserRepository::updateUser".to_string(),
         file_path: std::path::PathBuf::from("test.java"),        line_range: (40, 50),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
         db_operations: vec![
             DbOperation {
                 operation_type: DbOpType::Update,
@@ -203,7 +188,7 @@ fn test_database_table_config_association() {
                 line: 45,
             },
         ],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     index.test_index_method(&reader).unwrap();
@@ -244,10 +229,6 @@ fn test_redis_prefix_config_association() {
         name: "getUserFromCache".to_string(),
         full_qualified_name: "com.example.cache.UserCache::getUserFromCache".to_string(),
         file_path: std::path::PathBuf::from("test.java"),        line_range: (10, 20),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
         redis_operations: vec![
             RedisOperation {
                 operation_type: RedisOpType::Get,
@@ -255,6 +236,7 @@ fn test_redis_prefix_config_association() {
                 line: 15,
             },
         ],
+        ..Default::default()
     };
     
     // 添加 Redis 写入者
@@ -262,10 +244,6 @@ fn test_redis_prefix_config_association() {
         name: "cacheUser".to_string(),
         full_qualified_name: "com.example.cache.UserCache::cacheUser".to_string(),
         file_path: std::path::PathBuf::from("test.java"),        line_range: (25, 35),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
         redis_operations: vec![
             RedisOperation {
                 operation_type: RedisOpType::Set,
@@ -273,6 +251,7 @@ fn test_redis_prefix_config_association() {
                 line: 30,
             },
         ],
+        ..Default::default()
     };
     
     index.test_index_method(&reader).unwrap();
@@ -286,7 +265,7 @@ fn test_redis_prefix_config_association() {
             session-key: "session:*"
     "#;
     
-    let parser = YamlConfigParser;
+    let parser = YamlConfigParser::new();
     let config_data = parser.parse(yaml_config).unwrap();
     
     // 关联配置到代码
@@ -309,13 +288,13 @@ fn test_mixed_config_association() {
         name: "processUserRegistration".to_string(),
         full_qualified_name: "com.example.service.UserService::processUserRegistration".to_string(),
         file_path: std::path::PathBuf::from("test.java"),        line_range: (10, 50),
-        calls: vec![],
-        http_annotations: None,
         kafka_operations: vec![
             KafkaOperation {
                 operation_type: KafkaOpType::Produce,
                 topic: "user-registered".to_string(),
                 line: 20,
+                topic_constant: None,
+                value_type: None,
             },
         ],
         db_operations: vec![
@@ -332,6 +311,7 @@ fn test_mixed_config_association() {
                 line: 30,
             },
         ],
+        ..Default::default()
     };
     
     index.test_index_method(&service_method).unwrap();
@@ -352,7 +332,7 @@ fn test_mixed_config_association() {
             - "user:profile:*"
     "#;
     
-    let parser = YamlConfigParser;
+    let parser = YamlConfigParser::new();
     let config_data = parser.parse(yaml_config).unwrap();
     
     // 关联配置到代码
@@ -384,34 +364,32 @@ fn test_config_association_deduplication() {
         name: "sendEvent1".to_string(),
         full_qualified_name: "com.example.Producer1::sendEvent1".to_string(),
         file_path: std::path::PathBuf::from("test.java"),        line_range: (10, 20),
-        calls: vec![],
-        http_annotations: None,
         kafka_operations: vec![
             KafkaOperation {
                 operation_type: KafkaOpType::Produce,
                 topic: "events".to_string(),
                 line: 15,
+                topic_constant: None,
+                value_type: None,
             },
         ],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     let producer2 = MethodInfo {
         name: "sendEvent2".to_string(),
         full_qualified_name: "com.example.Producer2::sendEvent2".to_string(),
         file_path: std::path::PathBuf::from("test.java"),        line_range: (30, 40),
-        calls: vec![],
-        http_annotations: None,
         kafka_operations: vec![
             KafkaOperation {
                 operation_type: KafkaOpType::Produce,
                 topic: "events".to_string(),
                 line: 35,
+                topic_constant: None,
+                value_type: None,
             },
         ],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     index.test_index_method(&producer1).unwrap();
@@ -1,9 +1,9 @@
-use code_impact_analyzer::code_index::CodeIndex;
-use code_impact_analyzer::impact_tracer::{ImpactTracer, TraceConfig, NodeType, EdgeType};
+use code_impact_analyzer::code_index::{CodeIndex, TableNormalization};
+use code_impact_analyzer::impact_tracer::{ImpactTracer, ImpactGraph, TraceConfig, NodeType, EdgeType, Risk};
 use code_impact_analyzer::language_parser::{MethodInfo, MethodCall};
 use code_impact_analyzer::types::{
     HttpAnnotation, HttpMethod, KafkaOperation, KafkaOpType,
-    DbOperation, DbOpType, RedisOperation, RedisOpType,
+    DbOperation, DbOpType, RedisOperation, RedisOpType, RpcOperation, RpcOpType, GraphQLResolver,
 };
 
 /// 测试 HTTP 接口双向追溯
@@ -17,16 +17,13 @@ fn test_http_bidirectional_tracing() {
         full_qualified_name: "com.example.UserController::getUser".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (10, 20),
-        calls: vec![],
         http_annotations: Some(HttpAnnotation {
             method: HttpMethod::GET,
             path: "/api/users/{id}".to_string(),
             path_params: vec!["id".to_string()],
             is_feign_client: false,
         }),
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 索引提供者
@@ -52,10 +49,10 @@ fn test_http_bidirectional_tracing() {
     let http_node = graph.get_node("http:GET:/api/users/{id}").unwrap();
     assert!(matches!(http_node.node_type, NodeType::HttpEndpoint { .. }));
     
-    // 验证边的存在
+    // 验证边的存在：HTTP 接口是提供者方法的上游，边为 endpoint -> provider
     let has_http_edge = graph.edges().any(|edge| {
-        edge.from == "method:com.example.UserController::getUser"
-            && edge.to == "http:GET:/api/users/{id}"
+        edge.from == "http:GET:/api/users/{id}"
+            && edge.to == "method:com.example.UserController::getUser"
             && edge.edge_type == EdgeType::HttpCall
     });
     assert!(has_http_edge);
@@ -72,15 +69,14 @@ fn test_kafka_producer_to_consumer_tracing() {
         full_qualified_name: "com.example.EventProducer::sendEvent".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (10, 20),
-        calls: vec![],
-        http_annotations: None,
         kafka_operations: vec![KafkaOperation {
             operation_type: KafkaOpType::Produce,
             topic: "user-events".to_string(),
             line: 15,
+            topic_constant: None,
+            value_type: None,
         }],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 创建 Kafka 消费者方法
@@ -89,15 +85,14 @@ fn test_kafka_producer_to_consumer_tracing() {
         full_qualified_name: "com.example.EventConsumer::handleEvent".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (30, 40),
-        calls: vec![],
-        http_annotations: None,
         kafka_operations: vec![KafkaOperation {
             operation_type: KafkaOpType::Consume,
             topic: "user-events".to_string(),
             line: 35,
+            topic_constant: None,
+            value_type: None,
         }],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 索引生产者和消费者
@@ -155,15 +150,14 @@ fn test_kafka_consumer_to_producer_tracing() {
         full_qualified_name: "com.example.EventProducer::sendEvent".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (10, 20),
-        calls: vec![],
-        http_annotations: None,
         kafka_operations: vec![KafkaOperation {
             operation_type: KafkaOpType::Produce,
             topic: "order-events".to_string(),
             line: 15,
+            topic_constant: None,
+            value_type: None,
         }],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 创建 Kafka 消费者方法
@@ -172,15 +166,14 @@ fn test_kafka_consumer_to_producer_tracing() {
         full_qualified_name: "com.example.OrderProcessor::processOrder".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (30, 40),
-        calls: vec![],
-        http_annotations: None,
         kafka_operations: vec![KafkaOperation {
             operation_type: KafkaOpType::Consume,
             topic: "order-events".to_string(),
             line: 35,
+            topic_constant: None,
+            value_type: None,
         }],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 索引生产者和消费者
@@ -218,15 +211,12 @@ fn test_database_writer_to_reader_tracing() {
         full_qualified_name: "com.example.UserRepository::saveUser".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (10, 20),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
         db_operations: vec![DbOperation {
             operation_type: DbOpType::Insert,
             table: "users".to_string(),
             line: 15,
         }],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 创建数据库读取者方法
@@ -235,15 +225,12 @@ fn test_database_writer_to_reader_tracing() {
         full_qualified_name: "com.example.UserRepository::findUser".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (30, 40),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
         db_operations: vec![DbOperation {
             operation_type: DbOpType::Select,
             table: "users".to_string(),
             line: 35,
         }],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 索引写入者和读取者
@@ -290,6 +277,48 @@ fn test_database_writer_to_reader_tracing() {
     assert!(has_reader_edge);
 }
 
+/// 测试数据库边的风险等级：写入为高风险，读取为低风险
+#[test]
+fn test_database_edge_risk() {
+    let mut index = CodeIndex::new();
+    
+    let db_method = |qualified_name: &str, operation_type: DbOpType| MethodInfo {
+        line_range: (10, 20),
+        db_operations: vec![DbOperation {
+            operation_type,
+            table: "orders".to_string(),
+            line: 15,
+        }],
+        ..MethodInfo::new(qualified_name, "test.java")
+    };
+    index.test_index_method(&db_method("com.example.OrderRepository::updateStatus", DbOpType::Update)).unwrap();
+    index.test_index_method(&db_method("com.example.ReportService::loadOrders", DbOpType::Select)).unwrap();
+    
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.OrderRepository::updateStatus".to_string()]).unwrap();
+    
+    let risk_of = |from: &str, to: &str| {
+        graph.edges()
+            .find(|edge| edge.from == from && edge.to == to)
+            .and_then(|edge| edge.risk)
+    };
+    assert_eq!(risk_of("method:com.example.OrderRepository::updateStatus", "db:orders"), Some(Risk::High));
+    assert_eq!(risk_of("db:orders", "method:com.example.ReportService::loadOrders"), Some(Risk::Low));
+    
+    // DOT 中写入边以红色粗线表示
+    assert!(graph.to_dot().contains("color=\"red\" style=\"bold\" risk=\"high\""));
+    
+    // 只保留高风险边时，读取者因孤立被移除
+    let seeds = vec!["method:com.example.OrderRepository::updateStatus".to_string()];
+    let filtered = graph.filter_by_risk(Risk::High, &seeds);
+    assert!(filtered.get_node("db:orders").is_some());
+    assert!(filtered.get_node("method:com.example.ReportService::loadOrders").is_none());
+    
+    // 风险等级在 JSON 往返后保留
+    let reloaded = ImpactGraph::from_json(&graph.to_json().unwrap()).unwrap();
+    assert!(reloaded.edges().any(|edge| edge.to == "db:orders" && edge.risk == Some(Risk::High)));
+}
+
 /// 测试数据库表双向追溯 - 读取者到写入者
 #[test]
 fn test_database_reader_to_writer_tracing() {
@@ -301,15 +330,12 @@ fn test_database_reader_to_writer_tracing() {
         full_qualified_name: "com.example.OrderRepository::updateOrder".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (10, 20),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
         db_operations: vec![DbOperation {
             operation_type: DbOpType::Update,
             table: "orders".to_string(),
             line: 15,
         }],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 创建数据库读取者方法
@@ -318,15 +344,12 @@ fn test_database_reader_to_writer_tracing() {
         full_qualified_name: "com.example.OrderRepository::getOrder".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (30, 40),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
         db_operations: vec![DbOperation {
             operation_type: DbOpType::Select,
             table: "orders".to_string(),
             line: 35,
         }],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 索引写入者和读取者
@@ -364,15 +387,12 @@ fn test_redis_writer_to_reader_tracing() {
         full_qualified_name: "com.example.SessionCache::cacheSession".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (10, 20),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
         redis_operations: vec![RedisOperation {
             operation_type: RedisOpType::Set,
             key_pattern: "session:*".to_string(),
             line: 15,
         }],
+        ..Default::default()
     };
     
     // 创建 Redis 读取者方法
@@ -381,15 +401,12 @@ fn test_redis_writer_to_reader_tracing() {
         full_qualified_name: "com.example.SessionCache::getSession".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (30, 40),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
         redis_operations: vec![RedisOperation {
             operation_type: RedisOpType::Get,
             key_pattern: "session:*".to_string(),
             line: 35,
         }],
+        ..Default::default()
     };
     
     // 索引写入者和读取者
@@ -447,15 +464,12 @@ fn test_redis_reader_to_writer_tracing() {
         full_qualified_name: "com.example.UserCache::cacheUser".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (10, 20),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
         redis_operations: vec![RedisOperation {
             operation_type: RedisOpType::Set,
             key_pattern: "user:*".to_string(),
             line: 15,
         }],
+        ..Default::default()
     };
     
     // 创建 Redis 读取者方法
@@ -464,15 +478,12 @@ fn test_redis_reader_to_writer_tracing() {
         full_qualified_name: "com.example.UserCache::getUser".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (30, 40),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
         redis_operations: vec![RedisOperation {
             operation_type: RedisOpType::Get,
             key_pattern: "user:*".to_string(),
             line: 35,
         }],
+        ..Default::default()
     };
     
     // 索引写入者和读取者
@@ -515,6 +526,8 @@ fn test_complex_cross_service_tracing() {
         calls: vec![MethodCall {
             target: "com.example.Service::processRequest".to_string(),
             line: 15,
+            condition: None,
+            low_confidence: false,
         }],
         http_annotations: Some(HttpAnnotation {
             method: HttpMethod::POST,
@@ -522,9 +535,7 @@ fn test_complex_cross_service_tracing() {
             path_params: vec![],
             is_feign_client: false,
         }),
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     let service_method = MethodInfo {
@@ -532,15 +543,14 @@ fn test_complex_cross_service_tracing() {
         full_qualified_name: "com.example.Service::processRequest".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (30, 40),
-        calls: vec![],
-        http_annotations: None,
         kafka_operations: vec![KafkaOperation {
             operation_type: KafkaOpType::Produce,
             topic: "process-events".to_string(),
             line: 35,
+            topic_constant: None,
+            value_type: None,
         }],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     let kafka_consumer = MethodInfo {
@@ -548,19 +558,19 @@ fn test_complex_cross_service_tracing() {
         full_qualified_name: "com.example.EventHandler::handleEvent".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (50, 60),
-        calls: vec![],
-        http_annotations: None,
         kafka_operations: vec![KafkaOperation {
             operation_type: KafkaOpType::Consume,
             topic: "process-events".to_string(),
             line: 55,
+            topic_constant: None,
+            value_type: None,
         }],
         db_operations: vec![DbOperation {
             operation_type: DbOpType::Insert,
             table: "events".to_string(),
             line: 58,
         }],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     let db_reader = MethodInfo {
@@ -568,9 +578,6 @@ fn test_complex_cross_service_tracing() {
         full_qualified_name: "com.example.EventQuery::queryEvents".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (70, 80),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
         db_operations: vec![DbOperation {
             operation_type: DbOpType::Select,
             table: "events".to_string(),
@@ -581,6 +588,7 @@ fn test_complex_cross_service_tracing() {
             key_pattern: "event:*".to_string(),
             line: 78,
         }],
+        ..Default::default()
     };
     
     let redis_reader = MethodInfo {
@@ -588,15 +596,12 @@ fn test_complex_cross_service_tracing() {
         full_qualified_name: "com.example.EventCache::getCachedEvent".to_string(),
         file_path: std::path::PathBuf::from("test.java"),
         line_range: (90, 100),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
         redis_operations: vec![RedisOperation {
             operation_type: RedisOpType::Get,
             key_pattern: "event:*".to_string(),
             line: 95,
         }],
+        ..Default::default()
     };
     
     // 索引所有方法
@@ -640,3 +645,500 @@ fn test_complex_cross_service_tracing() {
     assert!(has_db);
     assert!(has_redis);
 }
+
+/// 测试 Redis 占位符键归并 - `{id}` 与 `*` 指向同一个节点
+#[test]
+fn test_redis_placeholder_keys_collapse_to_one_node() {
+    let mut index = CodeIndex::new();
+    
+    // 写入者使用 {id} 占位符
+    let writer = MethodInfo {
+        name: "putProfile".to_string(),
+        full_qualified_name: "com.example.ProfileCache::putProfile".to_string(),
+        file_path: std::path::PathBuf::from("test.java"),
+        line_range: (10, 20),
+        redis_operations: vec![RedisOperation {
+            operation_type: RedisOpType::Set,
+            key_pattern: "user:{id}:profile".to_string(),
+            line: 15,
+        }],
+        ..Default::default()
+    };
+    
+    // 读取者使用 * 通配符
+    let reader = MethodInfo {
+        name: "getProfile".to_string(),
+        full_qualified_name: "com.example.ProfileQuery::getProfile".to_string(),
+        file_path: std::path::PathBuf::from("test.java"),
+        line_range: (30, 40),
+        redis_operations: vec![RedisOperation {
+            operation_type: RedisOpType::Get,
+            key_pattern: "user:*:profile".to_string(),
+            line: 35,
+        }],
+        ..Default::default()
+    };
+    
+    index.test_index_method(&writer).unwrap();
+    index.test_index_method(&reader).unwrap();
+    
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.ProfileCache::putProfile".to_string()]).unwrap();
+    
+    // 只有一个规范化后的 Redis 节点
+    let redis_nodes: Vec<_> = graph.nodes()
+        .filter(|n| matches!(n.node_type, NodeType::RedisPrefix { .. }))
+        .collect();
+    assert_eq!(redis_nodes.len(), 1);
+    assert_eq!(redis_nodes[0].id, "redis:user:*:profile");
+    
+    // 读取者通过同一个节点被关联
+    assert!(graph.edges().any(|edge| {
+        edge.from == "redis:user:*:profile"
+            && edge.to == "method:com.example.ProfileQuery::getProfile"
+            && edge.edge_type == EdgeType::RedisReadWrite
+    }));
+}
+
+/// 测试表名规范化后 `t_users` 与 `user` 合并为同一个表节点
+#[test]
+fn test_table_normalization_collapses_table_nodes() {
+    let db_method = |qualified_name: &str, operation_type: DbOpType, table: &str| MethodInfo {
+        line_range: (10, 20),
+        db_operations: vec![DbOperation {
+            operation_type,
+            table: table.to_string(),
+            line: 15,
+        }],
+        ..MethodInfo::new(qualified_name, "test.java")
+    };
+    
+    let mut index = CodeIndex::new();
+    // MyBatis SQL 写入 t_users，JPA 实体读取 user
+    index.test_index_method(&db_method("com.example.UserMapper::insertUser", DbOpType::Insert, "t_users")).unwrap();
+    index.test_index_method(&db_method("com.example.UserRepository::findUser", DbOpType::Select, "user")).unwrap();
+    index.set_table_normalization(TableNormalization {
+        strip_prefix: true,
+        singularize: true,
+        lowercase: true,
+    });
+    
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.UserMapper::insertUser".to_string()]).unwrap();
+    
+    let table_nodes: Vec<_> = graph.nodes()
+        .filter(|n| matches!(n.node_type, NodeType::DatabaseTable { .. }))
+        .collect();
+    assert_eq!(table_nodes.len(), 1);
+    assert_eq!(table_nodes[0].id, "db:user");
+    
+    assert!(graph.edges().any(|edge| {
+        edge.from == "db:user"
+            && edge.to == "method:com.example.UserRepository::findUser"
+            && edge.edge_type == EdgeType::DatabaseReadWrite
+    }));
+}
+
+/// 创建 Dubbo RPC 的提供者和消费者方法
+fn rpc_provider_and_consumer() -> (MethodInfo, MethodInfo) {
+    let provider = MethodInfo {
+        name: "queryOrder".to_string(),
+        full_qualified_name: "com.example.order.OrderFacadeImpl::queryOrder".to_string(),
+        file_path: std::path::PathBuf::from("order/OrderFacadeImpl.java"),
+        line_range: (10, 20),
+        rpc_operations: vec![RpcOperation {
+            operation_type: RpcOpType::Provide,
+            interface: "com.example.api.OrderFacade".to_string(),
+            method: "queryOrder".to_string(),
+            line: 10,
+        }],
+        ..Default::default()
+    };
+    
+    let consumer = MethodInfo {
+        name: "checkout".to_string(),
+        full_qualified_name: "com.example.shop.ShopService::checkout".to_string(),
+        file_path: std::path::PathBuf::from("shop/ShopService.java"),
+        line_range: (30, 40),
+        calls: vec![MethodCall {
+            target: "com.example.api.OrderFacade::queryOrder".to_string(),
+            line: 35,
+            condition: None,
+            low_confidence: false,
+        }],
+        rpc_operations: vec![RpcOperation {
+            operation_type: RpcOpType::Consume,
+            interface: "com.example.api.OrderFacade".to_string(),
+            method: "queryOrder".to_string(),
+            line: 35,
+        }],
+        ..Default::default()
+    };
+    
+    (provider, consumer)
+}
+
+/// 测试 Dubbo RPC 追溯 - 消费者到提供者
+#[test]
+fn test_rpc_consumer_to_provider_tracing() {
+    let mut index = CodeIndex::new();
+    let (provider, consumer) = rpc_provider_and_consumer();
+    index.test_index_method(&provider).unwrap();
+    index.test_index_method(&consumer).unwrap();
+    
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.shop.ShopService::checkout".to_string()]).unwrap();
+    
+    // 验证 RPC 节点
+    let rpc_id = "rpc:com.example.api.OrderFacade::queryOrder";
+    let rpc_node = graph.get_node(rpc_id).unwrap();
+    assert!(matches!(rpc_node.node_type, NodeType::RpcMethod { .. }));
+    
+    // 验证跨服务边：consumer -> rpc -> provider
+    assert!(graph.edges().any(|e| {
+        e.from == "method:com.example.shop.ShopService::checkout"
+            && e.to == rpc_id
+            && e.edge_type == EdgeType::RpcCall
+    }));
+    assert!(graph.edges().any(|e| {
+        e.from == rpc_id
+            && e.to == "method:com.example.order.OrderFacadeImpl::queryOrder"
+            && e.edge_type == EdgeType::RpcCall
+    }));
+}
+
+/// 测试 Dubbo RPC 追溯 - 提供者到消费者
+#[test]
+fn test_rpc_provider_to_consumer_tracing() {
+    let mut index = CodeIndex::new();
+    let (provider, consumer) = rpc_provider_and_consumer();
+    index.test_index_method(&provider).unwrap();
+    index.test_index_method(&consumer).unwrap();
+    
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.order.OrderFacadeImpl::queryOrder".to_string()]).unwrap();
+    
+    let rpc_id = "rpc:com.example.api.OrderFacade::queryOrder";
+    
+    // 修改提供者会影响到消费者：consumer -> rpc -> provider
+    assert!(graph.get_node("method:com.example.shop.ShopService::checkout").is_some());
+    assert!(graph.edges().any(|e| {
+        e.from == rpc_id
+            && e.to == "method:com.example.order.OrderFacadeImpl::queryOrder"
+            && e.edge_type == EdgeType::RpcCall
+    }));
+    assert!(graph.edges().any(|e| {
+        e.from == "method:com.example.shop.ShopService::checkout"
+            && e.to == rpc_id
+            && e.edge_type == EdgeType::RpcCall
+    }));
+}
+
+/// 在指定服务目录下创建方法
+fn service_method(service: &str, qualified_name: &str, calls: Vec<&str>, kafka_operations: Vec<KafkaOperation>) -> MethodInfo {
+    MethodInfo {
+        line_range: (10, 20),
+        calls: calls.into_iter()
+            .map(|target| MethodCall { target: target.to_string(), line: 15, condition: None, low_confidence: false })
+            .collect(),
+        kafka_operations,
+        ..MethodInfo::new(qualified_name, format!("/workspace/{}/src/main/java/App.java", service))
+    }
+}
+
+/// 测试服务白名单：追溯不进入未列出的服务
+#[test]
+fn test_service_allowlist_restricts_tracing() {
+    let mut index = CodeIndex::new();
+    
+    let produce = |topic: &str| vec![KafkaOperation {
+        operation_type: KafkaOpType::Produce,
+        topic: topic.to_string(),
+        line: 15,
+        topic_constant: None,
+        value_type: None,
+    }];
+    let consume = |topic: &str| vec![KafkaOperation {
+        operation_type: KafkaOpType::Consume,
+        topic: topic.to_string(),
+        line: 15,
+        topic_constant: None,
+        value_type: None,
+    }];
+    
+    // order-service 发送事件，并直接调用 audit-service 中的方法
+    index.test_index_method(&service_method(
+        "order-service", "com.order.OrderService::placeOrder",
+        vec!["com.order.OrderRepo::save", "com.audit.AuditLog::record"], produce("order-events"),
+    )).unwrap();
+    index.test_index_method(&service_method("order-service", "com.order.OrderRepo::save", vec![], vec![])).unwrap();
+    
+    // billing-service 和 audit-service 都消费该事件
+    index.test_index_method(&service_method(
+        "billing-service", "com.billing.BillingListener::onOrder",
+        vec!["com.billing.Invoice::create"], consume("order-events"),
+    )).unwrap();
+    index.test_index_method(&service_method("billing-service", "com.billing.Invoice::create", vec![], vec![])).unwrap();
+    index.test_index_method(&service_method(
+        "audit-service", "com.audit.AuditListener::onOrder",
+        vec!["com.audit.AuditLog::record"], consume("order-events"),
+    )).unwrap();
+    index.test_index_method(&service_method("audit-service", "com.audit.AuditLog::record", vec![], vec![])).unwrap();
+    
+    let config = TraceConfig {
+        allowed_services: vec!["order-service".to_string(), "billing-service".to_string()],
+        max_bridge_fanout: None,
+        diagnostic_filter: None,
+        ..TraceConfig::default()
+    };
+    let tracer = ImpactTracer::new(&index, config)
+        .with_workspace_root(std::path::PathBuf::from("/workspace"));
+    
+    assert_eq!(tracer.service_of("com.audit.AuditLog::record").as_deref(), Some("audit-service"));
+    
+    let graph = tracer.trace_impact(&["com.order.OrderService::placeOrder".to_string()]).unwrap();
+    
+    // 白名单内的服务正常追溯
+    assert!(graph.get_node("method:com.order.OrderRepo::save").is_some());
+    assert!(graph.get_node("method:com.billing.BillingListener::onOrder").is_some());
+    assert!(graph.get_node("method:com.billing.Invoice::create").is_some());
+    
+    // Kafka 节点保留为叶子，audit-service 的方法全部被排除
+    assert!(graph.get_node("kafka:order-events").is_some());
+    assert!(graph.nodes().all(|node| !node.id.contains("com.audit.")));
+    
+    // 不限制时 audit-service 会出现在结果中
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.order.OrderService::placeOrder".to_string()]).unwrap();
+    assert!(graph.get_node("method:com.audit.AuditListener::onOrder").is_some());
+    assert!(graph.get_node("method:com.audit.AuditLog::record").is_some());
+}
+
+/// 测试 GraphQL 解析器作为入口点出现在影响图中
+#[test]
+fn test_graphql_resolver_entry_point() {
+    let mut index = CodeIndex::new();
+    
+    let mut resolver = service_method("book-service", "com.example.BookController::bookById", vec!["com.example.BookService::find"], vec![]);
+    resolver.graphql_resolver = Some(GraphQLResolver {
+        type_name: "Query".to_string(),
+        field: "bookById".to_string(),
+    });
+    index.test_index_method(&resolver).unwrap();
+    index.test_index_method(&service_method("book-service", "com.example.BookService::find", vec![], vec![])).unwrap();
+    
+    assert_eq!(index.find_graphql_resolvers("Query.bookById"), vec!["com.example.BookController::bookById"]);
+    
+    // 修改被解析器调用的服务方法，上游应追溯到 GraphQL 入口
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.BookService::find".to_string()]).unwrap();
+    
+    let field_node = graph.get_node("graphql:Query.bookById").expect("GraphQL field node should exist");
+    assert!(matches!(field_node.node_type, NodeType::GraphQLField { .. }));
+    assert_eq!(field_node.metadata.properties.get("entry_point").map(String::as_str), Some("true"));
+    
+    assert!(graph.edges().any(|edge| {
+        edge.from == "graphql:Query.bookById"
+            && edge.to == "method:com.example.BookController::bookById"
+            && edge.edge_type == EdgeType::GraphQLResolve
+    }));
+    
+    // GraphQL 节点不计入影响半径
+    assert_eq!(graph.blast_radius(), 2);
+}
+
+/// 测试 Feign 客户端的超时/重试配置附加到对应的 HTTP 端点节点
+#[test]
+fn test_feign_client_config_attached_to_endpoint_node() {
+    use code_impact_analyzer::config_parser::{ConfigParser, YamlConfigParser};
+    
+    let mut index = CodeIndex::new();
+    
+    let feign_method = |qualified_name: &str, path: &str| MethodInfo {
+        line_range: (10, 12),
+        http_annotations: Some(HttpAnnotation {
+            method: HttpMethod::GET,
+            path: path.to_string(),
+            path_params: vec![],
+            is_feign_client: true,
+        }),
+        ..MethodInfo::new(qualified_name, "order-service/src/UserFeign.java")
+    };
+    index.test_index_method(&feign_method("com.example.UserFeign::getUser", "user-service/users/{id}")).unwrap();
+    index.test_index_method(&feign_method("com.example.StockFeign::getStock", "stock-service/stocks/{id}")).unwrap();
+    
+    let yaml = r#"
+        feign:
+          client:
+            config:
+              user-service:
+                connectTimeout: 5000
+                readTimeout: 8000
+    "#;
+    let config_data = YamlConfigParser::new().parse(yaml).unwrap();
+    index.associate_module_config_data("order-service", &config_data);
+    
+    // 其他服务对同名客户端的配置不影响 order-service 中的调用
+    let other_yaml = r#"
+        feign:
+          client:
+            config:
+              user-service:
+                connectTimeout: 1000
+    "#;
+    let other_config_data = YamlConfigParser::new().parse(other_yaml).unwrap();
+    index.associate_module_config_data("payment-service", &other_config_data);
+    
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&[
+        "com.example.UserFeign::getUser".to_string(),
+        "com.example.StockFeign::getStock".to_string(),
+    ]).unwrap();
+    
+    // user-service 的超时配置出现在对应 Feign 端点节点的元数据中
+    let user_node = graph.get_node("http:GET:user-service/users/{id}").unwrap();
+    let properties = &user_node.metadata.properties;
+    assert_eq!(properties.get("connect_timeout").map(String::as_str), Some("5000"));
+    assert_eq!(properties.get("read_timeout").map(String::as_str), Some("8000"));
+    assert!(!properties.contains_key("retryer"));
+    
+    // 没有配置的客户端不附加元数据
+    let stock_node = graph.get_node("http:GET:stock-service/stocks/{id}").unwrap();
+    assert!(stock_node.metadata.properties.is_empty());
+}
+
+/// 测试 Thrift 追溯：IDL 中的服务方法连接 Java 客户端调用与服务实现
+#[test]
+fn test_thrift_client_linked_to_provider() {
+    use code_impact_analyzer::config_parser::ConfigParser;
+    use code_impact_analyzer::java_parser::JavaParser;
+    use code_impact_analyzer::language_parser::LanguageParser;
+    use code_impact_analyzer::thrift_parser::ThriftIdlParser;
+    use std::path::Path;
+    
+    let idl = r#"
+        namespace java com.example.order.thrift
+        
+        service OrderService {
+            Order getOrder(1: i64 id)
+        }
+    "#;
+    
+    let provider = r#"
+        package com.example.order;
+        
+        import com.example.order.thrift.OrderService;
+        
+        public class OrderServiceImpl implements OrderService.Iface {
+            public Order getOrder(long id) {
+                return null;
+            }
+        }
+    "#;
+    
+    let consumer = r#"
+        package com.example.checkout;
+        
+        import com.example.order.thrift.OrderService;
+        
+        public class CheckoutService {
+            private OrderService.Client orderClient;
+            
+            public void checkout(long orderId) {
+                orderClient.getOrder(orderId);
+                orderClient.send_getOrder(orderId);
+            }
+        }
+    "#;
+    
+    let parser = JavaParser::new().unwrap();
+    let mut index = CodeIndex::new();
+    index.test_index_parsed_file(parser.parse_file(provider, Path::new("order-service/OrderServiceImpl.java")).unwrap()).unwrap();
+    index.test_index_parsed_file(parser.parse_file(consumer, Path::new("checkout-service/CheckoutService.java")).unwrap()).unwrap();
+    index.associate_config_data(&ThriftIdlParser.parse(idl).unwrap());
+    
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    
+    // 修改服务实现，影响通过客户端调用它的消费者
+    let graph = tracer.trace_impact(&["com.example.order.OrderServiceImpl::getOrder".to_string()]).unwrap();
+    let thrift_id = "thrift:com.example.order.thrift.OrderService::getOrder";
+    assert!(matches!(
+        graph.get_node(thrift_id).map(|n| &n.node_type),
+        Some(NodeType::ThriftMethod { service, method })
+            if service == "com.example.order.thrift.OrderService" && method == "getOrder"
+    ));
+    assert!(graph.get_node("method:com.example.checkout.CheckoutService::checkout").is_some());
+    assert!(graph.edges().any(|e| {
+        e.from == "method:com.example.checkout.CheckoutService::checkout"
+            && e.to == thrift_id
+            && e.edge_type == EdgeType::ThriftCall
+    }));
+    
+    // 从消费者出发同样追溯到服务实现；IDL 中未声明的客户端辅助方法不产生 Thrift 节点
+    let graph = tracer.trace_impact(&["com.example.checkout.CheckoutService::checkout".to_string()]).unwrap();
+    assert!(graph.edges().any(|e| {
+        e.from == thrift_id
+            && e.to == "method:com.example.order.OrderServiceImpl::getOrder"
+            && e.edge_type == EdgeType::ThriftCall
+    }));
+    assert!(graph.get_node("thrift:com.example.order.thrift.OrderService::send_getOrder").is_none());
+}
+
+/// 测试 HTTP 端点桥接的展开上限：超出部分合并为汇总节点
+#[test]
+fn test_http_bridge_fanout_cap() {
+    let mut index = CodeIndex::new();
+    
+    let http_method = |qualified_name: &str, is_feign_client: bool| MethodInfo {
+        line_range: (10, 20),
+        http_annotations: Some(HttpAnnotation {
+            method: HttpMethod::GET,
+            path: "/api/users/{id}".to_string(),
+            path_params: vec!["id".to_string()],
+            is_feign_client,
+        }),
+        ..MethodInfo::new(qualified_name, "test.java")
+    };
+    index.test_index_method(&http_method("com.example.UserController::getUser", false)).unwrap();
+    // 倒序索引，验证展开结果与索引顺序无关
+    for i in (0..12).rev() {
+        let consumer = http_method(&format!("com.example.UserClient{:02}::getUser", i), true);
+        index.test_index_method(&consumer).unwrap();
+    }
+    
+    let changed = ["com.example.UserController::getUser".to_string()];
+    let consumer_ids = |graph: &code_impact_analyzer::impact_tracer::ImpactGraph| {
+        let mut ids: Vec<String> = graph.nodes()
+            .map(|node| node.id.clone())
+            .filter(|id| id.starts_with("method:com.example.UserClient"))
+            .collect();
+        ids.sort();
+        ids
+    };
+    
+    // 不限制时展开全部消费者
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&changed).unwrap();
+    assert_eq!(consumer_ids(&graph).len(), 12);
+    assert!(graph.get_node("summary:upstream:http:GET:/api/users/{id}").is_none());
+    
+    // 限制为 5 个时按方法名取前 5 个，其余 7 个合并为汇总节点
+    let config = TraceConfig::builder().max_bridge_fanout(Some(5)).build().unwrap();
+    let tracer = ImpactTracer::new(&index, config);
+    let graph = tracer.trace_impact(&changed).unwrap();
+    let expected: Vec<String> = (0..5)
+        .map(|i| format!("method:com.example.UserClient{:02}::getUser", i))
+        .collect();
+    assert_eq!(consumer_ids(&graph), expected);
+    
+    let summary = graph.get_node("summary:upstream:http:GET:/api/users/{id}").unwrap();
+    assert_eq!(summary.node_type, NodeType::BridgeSummary {
+        endpoint: "http:GET:/api/users/{id}".to_string(),
+        omitted: 7,
+    });
+    assert!(graph.edges().any(|edge| {
+        edge.from == "summary:upstream:http:GET:/api/users/{id}"
+            && edge.to == "http:GET:/api/users/{id}"
+            && edge.edge_type == EdgeType::HttpCall
+    }));
+}
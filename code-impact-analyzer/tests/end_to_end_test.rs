@@ -25,10 +25,11 @@ fn test_end_to_end_simple_analysis() {
     // 创建 CLI 参数
     let args = CliArgs {
         workspace_path: workspace,
-        diff_path: patch_path,
+        diff_path: Some(patch_path),
         output_format: OutputFormat::Json,
-        max_depth: 10,
         log_level: LogLevel::Error,
+        color: ColorMode::Never,
+        ..Default::default()
     };
     
     // 运行分析
@@ -60,10 +61,11 @@ fn test_end_to_end_with_statistics() {
     // 创建 CLI 参数
     let args = CliArgs {
         workspace_path: workspace,
-        diff_path: patch_path,
-        output_format: OutputFormat::Dot,
+        diff_path: Some(patch_path),
         max_depth: 5,
         log_level: LogLevel::Error,
+        color: ColorMode::Never,
+        ..Default::default()
     };
     
     // 运行分析
@@ -73,6 +75,1368 @@ fn test_end_to_end_with_statistics() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_end_to_end_list_changed_methods() {
+    // 创建临时目录
+    let temp_dir = TempDir::new().unwrap();
+    
+    // 创建 workspace
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    // 创建一个包含三个方法的 Java 源文件
+    let java_file = workspace.join("Test.java");
+    fs::write(
+        &java_file,
+        "public class Test {\n    public void method1() {\n        System.out.println(\"Method 1\");\n    }\n    public void method2() {\n        method1();\n    }\n    public void method3() {\n        method2();\n    }\n}\n",
+    ).unwrap();
+    
+    // patch 只修改 method1 和 method3 的行
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Test.java b/Test.java\nindex 0000000..1111111 100644\n--- a/Test.java\n+++ b/Test.java\n@@ -2,2 +2,2 @@\n     public void method1() {\n-        System.out.println(\"Old\");\n+        System.out.println(\"Method 1\");\n@@ -9,1 +9,1 @@\n-        method1();\n+        method2();\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    let changed = orchestrator.list_changed_methods(&patch_path).unwrap();
+    
+    // 输出列表应恰好包含与 hunk 重叠的方法，并按名称排序
+    let mut output = Vec::new();
+    write_changed_methods(&changed, &mut output).unwrap();
+    let printed = String::from_utf8(output).unwrap();
+    assert_eq!(printed, "Test::method1\nTest::method3\n");
+    
+    // 通过 CLI 入口运行也应成功
+    let args = CliArgs {
+        workspace_path: workspace,
+        diff_path: Some(patch_path),
+        log_level: LogLevel::Error,
+        color: ColorMode::Never,
+        list_changed: true,
+        ..Default::default()
+    };
+    assert!(run(args).is_ok());
+}
+
+#[test]
+fn test_change_between_methods_attributed_to_nearest_method() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    // method1 位于第 2-4 行，method2 位于第 8-10 行，中间是空行
+    fs::write(
+        workspace.join("Test.java"),
+        "public class Test {\n    public void method1() {\n        System.out.println(\"Method 1\");\n    }\n\n\n\n    public void method2() {\n        method1();\n    }\n}\n",
+    ).unwrap();
+    
+    // patch 只修改两个方法之间的空行
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Test.java b/Test.java\nindex 0000000..1111111 100644\n--- a/Test.java\n+++ b/Test.java\n@@ -6,1 +6,1 @@\n- \n+\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    
+    // 变更归属到之前最近的方法，并标记为低置信度
+    assert_eq!(result.changed_methods, vec!["Test::method1".to_string()]);
+    assert_eq!(result.low_confidence_methods, vec!["Test::method1".to_string()]);
+    assert!(result.warnings.iter().any(|w| w.contains("low confidence")));
+    
+    let node = result.impact_graph.get_node("method:Test::method1").unwrap();
+    assert_eq!(node.metadata.properties.get("confidence").map(String::as_str), Some("low"));
+}
+
+#[test]
+fn test_successive_patches_to_same_file_are_merged() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    // 最终文件：method1 第 2-7 行，method2 第 9-11 行，method3 第 13-15 行
+    fs::write(
+        workspace.join("Test.java"),
+        "public class Test {\n    public void method1() {\n        int a = 1;\n        int b = 2;\n        int c = 3;\n        System.out.println(\"Method 1\");\n    }\n\n    public void method2() {\n        System.out.println(\"Method 2\");\n    }\n\n    public void method3() {\n        System.out.println(\"Method 3\");\n    }\n}\n",
+    ).unwrap();
+    
+    // 同一个 patch 文件中依次包含两次提交对 Test.java 的修改（git format-patch --stdout）：
+    // 第一次修改 method3（当时位于第 11 行），第二次在 method1 中插入 3 行并再次修改同一行
+    let patch_path = temp_dir.path().join("series.patch");
+    fs::write(
+        &patch_path,
+        concat!(
+            "diff --git a/Test.java b/Test.java\n",
+            "index 0000000..1111111 100644\n",
+            "--- a/Test.java\n",
+            "+++ b/Test.java\n",
+            "@@ -11,1 +11,1 @@\n",
+            "-        System.out.println(\"Method three\");\n",
+            "+        System.out.println(\"Method 3!\");\n",
+            "diff --git a/Test.java b/Test.java\n",
+            "index 1111111..2222222 100644\n",
+            "--- a/Test.java\n",
+            "+++ b/Test.java\n",
+            "@@ -2,0 +3,3 @@\n",
+            "+        int a = 1;\n",
+            "+        int b = 2;\n",
+            "+        int c = 3;\n",
+            "@@ -11,1 +14,1 @@\n",
+            "-        System.out.println(\"Method 3!\");\n",
+            "+        System.out.println(\"Method 3\");\n",
+        ),
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let changed = orchestrator.list_changed_methods(&patch_path).unwrap();
+    
+    // 第一次修改的第 11 行换算到最终文件的第 14 行（method3），而不是 method2；
+    // 两次都修改了 method3，只作为一个起点
+    assert_eq!(changed, vec!["Test::method1".to_string(), "Test::method3".to_string()]);
+    assert!(orchestrator.warnings().iter().all(|w| !w.contains("low confidence")));
+}
+
+#[test]
+fn test_end_to_end_list_changed_method_bodies() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("Test.java"),
+        "public class Test {\n    public void method1() {\n        System.out.println(\"Method 1\");\n    }\n    public void method2() {\n        method1();\n    }\n    public void method3() {\n        method2();\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Test.java b/Test.java\nindex 0000000..1111111 100644\n--- a/Test.java\n+++ b/Test.java\n@@ -2,2 +2,2 @@\n     public void method1() {\n-        System.out.println(\"Old\");\n+        System.out.println(\"Method 1\");\n@@ -9,1 +9,1 @@\n-        method1();\n+        method2();\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let changed = orchestrator.list_changed_method_bodies(&patch_path).unwrap();
+    
+    let names: Vec<&str> = changed.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, vec!["Test::method1", "Test::method3"]);
+    
+    // 变更行与 hunk 中的新增/删除行一致，且位于方法的行范围内
+    assert_eq!(changed[0].changed_lines, vec![3]);
+    assert_eq!(changed[1].changed_lines, vec![9]);
+    assert!(changed.iter().all(|m| m.changed_lines.iter().all(|line| (2..=10).contains(line))));
+    
+    assert!(changed[0].snippet.contains("System.out.println(\"Method 1\");"));
+    assert_eq!(changed[1].snippet, "    public void method3() {\n        method2();\n    }");
+    
+    // 方法之外的变更与 list_changed_methods 一样归属到最近的方法，但不计入方法体内的变更行
+    fs::write(
+        &patch_path,
+        "diff --git a/Test.java b/Test.java\nindex 0000000..1111111 100644\n--- a/Test.java\n+++ b/Test.java\n@@ -11,1 +11,1 @@\n-} \n+}\n",
+    ).unwrap();
+    let changed = orchestrator.list_changed_method_bodies(&patch_path).unwrap();
+    let names: Vec<&str> = changed.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, orchestrator.list_changed_methods(&patch_path).unwrap());
+    assert_eq!(names, vec!["Test::method3"]);
+    assert!(changed[0].changed_lines.is_empty());
+}
+
+#[test]
+fn test_reachable_only_prunes_dead_code_callers() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("Helper.java"),
+        "package com.example;\n\npublic class Helper {\n    public String format() {\n        return \"x\";\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("UserController.java"),
+        "package com.example;\n\npublic class UserController {\n    private Helper helper;\n\n    @GetMapping(\"/users\")\n    public String list() {\n        return helper.format();\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("Legacy.java"),
+        "package com.example;\n\npublic class Legacy {\n    private Helper helper;\n\n    public String unused() {\n        return helper.format();\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Helper.java b/Helper.java\nindex 0000000..1111111 100644\n--- a/Helper.java\n+++ b/Helper.java\n@@ -5,1 +5,1 @@\n-        return \"y\";\n+        return \"x\";\n",
+    ).unwrap();
+    
+    // 不过滤时两个调用方都在影响图中
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    assert!(result.impact_graph.get_node("method:com.example.Legacy::unused").is_some());
+    assert!(result.impact_graph.get_node("method:com.example.UserController::list").is_some());
+    
+    // 只保留从入口点可达的影响：死代码调用方被剔除，HTTP 端点背后的调用方保留
+    orchestrator.set_reachable_only(true);
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    let graph = &result.impact_graph;
+    assert!(graph.get_node("method:com.example.Helper::format").is_some());
+    assert!(graph.get_node("method:com.example.UserController::list").is_some());
+    assert!(graph.get_node("method:com.example.Legacy::unused").is_none());
+    assert!(graph.edges().all(|edge| !edge.from.contains("Legacy") && !edge.to.contains("Legacy")));
+    assert!(graph.nodes().any(|node| matches!(node.node_type, NodeType::HttpEndpoint { .. })));
+}
+
+#[test]
+fn test_constructor_change_reaches_instantiating_caller() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("PaymentProcessor.java"),
+        "package com.example;\n\npublic class PaymentProcessor {\n    private final int retries;\n\n    public PaymentProcessor() {\n        this.retries = 3;\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("CheckoutService.java"),
+        "package com.example;\n\npublic class CheckoutService {\n    public void checkout() {\n        PaymentProcessor processor = new PaymentProcessor();\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/PaymentProcessor.java b/PaymentProcessor.java\nindex 0000000..1111111 100644\n--- a/PaymentProcessor.java\n+++ b/PaymentProcessor.java\n@@ -7,1 +7,1 @@\n-        this.retries = 1;\n+        this.retries = 3;\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    
+    // 构造器变更影响到 `new PaymentProcessor()` 的调用方
+    let graph = &result.impact_graph;
+    assert!(graph.get_node("method:com.example.PaymentProcessor::<init>").is_some());
+    assert!(graph.edges().any(|edge| {
+        edge.from == "method:com.example.CheckoutService::checkout"
+            && edge.to == "method:com.example.PaymentProcessor::<init>"
+    }));
+}
+
+#[test]
+fn test_contract_delegations_removes_delegation_hop() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("OrderService.java"),
+        "package com.example;\n\npublic class OrderService {\n    public String find(long id) {\n        return \"order-\" + id;\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("OrderFacade.java"),
+        "package com.example;\n\npublic class OrderFacade {\n    private OrderService orderService;\n\n    public String find(long id) {\n        return orderService.find(id);\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("OrderController.java"),
+        "package com.example;\n\npublic class OrderController {\n    private OrderFacade orderFacade;\n\n    public String show(long id) {\n        return orderFacade.find(id).trim();\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/OrderService.java b/OrderService.java\nindex 0000000..1111111 100644\n--- a/OrderService.java\n+++ b/OrderService.java\n@@ -5,1 +5,1 @@\n-        return \"o-\" + id;\n+        return \"order-\" + id;\n",
+    ).unwrap();
+    
+    // 默认保留委托方法这一跳
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    assert!(result.impact_graph.get_node("method:com.example.OrderFacade::find").is_some());
+    
+    // 收缩后调用方直接连接到被委托的变更方法
+    orchestrator.set_contract_delegations(true);
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    let graph = &result.impact_graph;
+    assert!(graph.get_node("method:com.example.OrderFacade::find").is_none());
+    assert!(graph.edges().any(|edge| {
+        edge.from == "method:com.example.OrderController::show"
+            && edge.to == "method:com.example.OrderService::find"
+    }));
+}
+
+#[test]
+fn test_unresolved_reflective_call_warns() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("JobRunner.java"),
+        "package com.example;\n\nimport java.lang.reflect.Method;\n\npublic class JobRunner {\n    public void run(Object job, String name) throws Exception {\n        Method method = job.getClass().getMethod(name);\n        method.invoke(job);\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/JobRunner.java b/JobRunner.java\nindex 0000000..1111111 100644\n--- a/JobRunner.java\n+++ b/JobRunner.java\n@@ -8,1 +8,1 @@\n-        method.invoke(null);\n+        method.invoke(job);\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    assert!(result.warnings.iter().any(|warning| {
+        warning == "reflective call in com.example.JobRunner::run may hide impact"
+    }));
+}
+
+#[test]
+fn test_reflective_call_with_literal_name_resolves_or_warns() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("Job.java"),
+        "package com.example;\n\npublic class Job {\n    public void doWork() {\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("JobRunner.java"),
+        "package com.example;\n\npublic class JobRunner {\n    public void run(Object job) throws Exception {\n        Job.class.getMethod(\"doWork\").invoke(job);\n    }\n\n    public void start(Class<?> type) throws Exception {\n        type.getMethod(\"doWork\").invoke(null);\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Job.java b/Job.java\nindex 0000000..1111111 100644\n--- a/Job.java\n+++ b/Job.java\n@@ -4,1 +4,1 @@\n-    public void doWork() {\n+    public void doWork() {\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    
+    // 接收者为类字面量时解析到该类的方法，调用方出现在影响图中且不产生警告
+    assert!(result.impact_graph.edges().any(|edge| {
+        edge.from == "method:com.example.JobRunner::run" && edge.to == "method:com.example.Job::doWork"
+    }));
+    assert!(!result.warnings.iter().any(|warning| warning.contains("JobRunner::run")));
+    
+    // 所属类无法确定的 `<unknown>::doWork` 调用不会出现在影响图中，对所在方法给出警告
+    assert!(result.impact_graph.get_node("method:com.example.JobRunner::start").is_none());
+    let start_patch = temp_dir.path().join("start.patch");
+    fs::write(
+        &start_patch,
+        "diff --git a/JobRunner.java b/JobRunner.java\nindex 0000000..1111111 100644\n--- a/JobRunner.java\n+++ b/JobRunner.java\n@@ -9,1 +9,1 @@\n-        type.getMethod(\"doWork\").invoke(type);\n+        type.getMethod(\"doWork\").invoke(null);\n",
+    ).unwrap();
+    let result = orchestrator.analyze(&start_patch).unwrap();
+    assert!(result.warnings.iter().any(|warning| {
+        warning == "reflective call in com.example.JobRunner::start may hide impact"
+    }));
+}
+
+#[test]
+fn test_no_stray_stdout_for_send_coupon_method() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("CouponService.java"),
+        "package com.example;\n\npublic class CouponService {\n    public void sendCoupon(long userId) {\n        log(userId);\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("OrderService.java"),
+        "package com.example;\n\npublic class OrderService {\n    private CouponService couponService;\n\n    public void complete(long userId) {\n        couponService.sendCoupon(userId);\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/CouponService.java b/CouponService.java\nindex 0000000..1111111 100644\n--- a/CouponService.java\n+++ b/CouponService.java\n@@ -5,1 +5,1 @@\n-        log(0);\n+        log(userId);\n",
+    ).unwrap();
+    
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_code_impact_analyzer"))
+        .arg("-w").arg(&workspace)
+        .arg("-d").arg(&patch_path)
+        .args(["-o", "json", "-q", "--log-level", "error"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    
+    // 标准输出只有影响图 JSON
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let graph: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(graph["nodes"].as_array().unwrap().iter().any(|node| {
+        node["id"] == "method:com.example.OrderService::complete"
+    }));
+}
+
+#[test]
+fn test_kafka_topic_referenced_by_constant() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    // topic 常量定义在另一个文件的嵌套类中
+    fs::write(
+        workspace.join("Topics.java"),
+        "package com.example.kafka;\n\npublic final class Topics {\n    public static final class Order {\n        public static final String CREATED = \"order-created\";\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("OrderProducer.java"),
+        "package com.example.order;\n\nimport com.example.kafka.Topics;\n\npublic class OrderProducer {\n    public void publish(String payload) {\n        kafkaTemplate.send(Topics.Order.CREATED, payload);\n    }\n}\n",
+    ).unwrap();
+    // 消费者引用本类中的常量
+    fs::write(
+        workspace.join("OrderListener.java"),
+        "package com.example.notify;\n\npublic class OrderListener {\n    private static final String TOPIC = \"order-created\";\n\n    @KafkaListener(topics = TOPIC)\n    public void onCreated(String payload) {\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/OrderProducer.java b/OrderProducer.java\nindex 0000000..1111111 100644\n--- a/OrderProducer.java\n+++ b/OrderProducer.java\n@@ -7,1 +7,1 @@\n-        kafkaTemplate.send(Topics.Order.UPDATED, payload);\n+        kafkaTemplate.send(Topics.Order.CREATED, payload);\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    
+    // 两端的常量引用都解析为常量值，通过 topic 连接到消费者
+    let graph = &result.impact_graph;
+    assert!(graph.get_node("kafka:order-created").is_some());
+    assert!(graph.get_node("method:com.example.notify.OrderListener::onCreated").is_some());
+}
+
+#[test]
+fn test_rest_client_consumer_matches_provider() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("UserController.java"),
+        "package com.example.user;\n\n@RestController\npublic class UserController {\n    @GetMapping(\"/api/users/{id}\")\n    public User getUser(long id) {\n        return load(id);\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("UserGateway.java"),
+        "package com.example.order;\n\nimport org.springframework.web.client.RestClient;\n\npublic class UserGateway {\n    private RestClient restClient;\n\n    public User fetch(long id) {\n        return restClient.get().uri(\"/api/users/{id}\", id).retrieve().body(User.class);\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/UserController.java b/UserController.java\nindex 0000000..1111111 100644\n--- a/UserController.java\n+++ b/UserController.java\n@@ -7,1 +7,1 @@\n-        return null;\n+        return load(id);\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    
+    // 提供者的变更通过 HTTP 端点追溯到 RestClient 调用方
+    let graph = &result.impact_graph;
+    assert!(graph.get_node("http:GET:api/users/{id}").is_some());
+    assert!(graph.get_node("method:com.example.order.UserGateway::fetch").is_some());
+}
+
+#[test]
+fn test_service_alias_matches_feign_client_to_provider() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    let user_resources = workspace.join("user").join("src").join("main").join("resources");
+    let user_java = workspace.join("user").join("src").join("main").join("java");
+    let order_java = workspace.join("order").join("src").join("main").join("java");
+    fs::create_dir_all(&user_resources).unwrap();
+    fs::create_dir_all(&user_java).unwrap();
+    fs::create_dir_all(&order_java).unwrap();
+    
+    // 提供者的 application.name 与 Feign 客户端使用的服务名不一致
+    fs::write(
+        user_resources.join("application.yml"),
+        "spring:\n  application:\n    name: hll-user-app\n",
+    ).unwrap();
+    fs::write(
+        user_java.join("UserController.java"),
+        "package com.example.user;\n\n@RestController\npublic class UserController {\n    @GetMapping(\"/api/users/{id}\")\n    public User getUser(long id) {\n        return load(id);\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        order_java.join("UserClient.java"),
+        "package com.example.order;\n\nimport org.springframework.cloud.openfeign.FeignClient;\n\n@FeignClient(value = \"user-service\")\npublic interface UserClient {\n    @GetMapping(\"/api/users/{id}\")\n    User getUser(long id);\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/user/src/main/java/UserController.java b/user/src/main/java/UserController.java\nindex 0000000..1111111 100644\n--- a/user/src/main/java/UserController.java\n+++ b/user/src/main/java/UserController.java\n@@ -7,1 +7,1 @@\n-        return null;\n+        return load(id);\n",
+    ).unwrap();
+    
+    let feign_method = "method:com.example.order.UserClient::getUser";
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    assert!(result.impact_graph.get_node(feign_method).is_none());
+    
+    // 配置别名后 Feign 调用匹配到提供者端点
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    orchestrator.set_service_aliases(
+        [("user-service".to_string(), "hll-user-app".to_string())].into_iter().collect()
+    );
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    let graph = &result.impact_graph;
+    assert!(graph.get_node("http:GET:hll-user-app/api/users/{id}").is_some());
+    assert!(graph.get_node(feign_method).is_some());
+}
+
+/// 按方法名识别内部任务队列的匹配器：`enqueueJob` 写入、`handleJob` 读取 `jobs` 队列
+struct JobQueueMatcher;
+
+impl ResourceMatcher for JobQueueMatcher {
+    fn extract(&self, method: &MethodInfo, _index: &CodeIndex) -> Vec<ResourceOp> {
+        let access = match method.name.as_str() {
+            "enqueueJob" => ResourceAccess::Write,
+            "handleJob" => ResourceAccess::Read,
+            _ => return vec![],
+        };
+        vec![ResourceOp {
+            node: ImpactNode::resource("queue".to_string(), "jobs".to_string()),
+            key: "jobs".to_string(),
+            access,
+            edge_type: EdgeType::ResourceAccess,
+        }]
+    }
+    
+    fn bridge(&self, op: &ResourceOp, index: &CodeIndex) -> Vec<String> {
+        index.methods()
+            .filter(|(_, method)| self.extract(method, index).iter().any(|other| other.key == op.key && other.access != op.access))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+#[test]
+fn test_custom_resource_matcher_links_methods() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    let producer_java = workspace.join("producer").join("src").join("main").join("java");
+    let worker_java = workspace.join("worker").join("src").join("main").join("java");
+    fs::create_dir_all(&producer_java).unwrap();
+    fs::create_dir_all(&worker_java).unwrap();
+    
+    fs::write(
+        producer_java.join("JobProducer.java"),
+        "package com.example.producer;\n\npublic class JobProducer {\n    public void enqueueJob() {\n        queue.push(\"job\");\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        worker_java.join("JobWorker.java"),
+        "package com.example.worker;\n\npublic class JobWorker {\n    public void handleJob() {\n        this.run();\n    }\n\n    void run() {\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/producer/src/main/java/JobProducer.java b/producer/src/main/java/JobProducer.java\nindex 0000000..1111111 100644\n--- a/producer/src/main/java/JobProducer.java\n+++ b/producer/src/main/java/JobProducer.java\n@@ -5,1 +5,1 @@\n-        queue.push(\"old\");\n+        queue.push(\"job\");\n",
+    ).unwrap();
+    
+    let handler = "method:com.example.worker.JobWorker::handleJob";
+    
+    // 内置匹配器无法识别内部队列
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    assert!(result.impact_graph.get_node(handler).is_none());
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    orchestrator.register_resource_matcher(Box::new(JobQueueMatcher));
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    let graph = &result.impact_graph;
+    
+    let queue = graph.get_node("resource:queue:jobs").unwrap();
+    assert_eq!(queue.metadata.label, "queue: jobs");
+    assert!(graph.get_node(handler).is_some());
+    assert!(graph.get_node("method:com.example.worker.JobWorker::run").is_some());
+    assert!(graph.edges().any(|edge| edge.from == "resource:queue:jobs" && edge.to == handler && edge.edge_type == EdgeType::ResourceAccess));
+}
+
+#[test]
+fn test_deleted_file_traces_former_callers() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("Helper.java"),
+        "package com.example;\n\npublic class Helper {\n    public void help() {\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("Caller.java"),
+        "package com.example;\n\npublic class Caller {\n    private Helper helper;\n\n    public void call() {\n        helper.help();\n    }\n}\n",
+    ).unwrap();
+    
+    // 删除前构建并保存索引
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    orchestrator.list_http_endpoints().unwrap();
+    
+    fs::remove_file(workspace.join("Helper.java")).unwrap();
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Helper.java b/Helper.java\ndeleted file mode 100644\nindex 1111111..0000000\n--- a/Helper.java\n+++ /dev/null\n@@ -1,6 +0,0 @@\n-package com.example;\n-\n-public class Helper {\n-    public void help() {\n-    }\n-}\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    let graph = &result.impact_graph;
+    
+    assert_eq!(result.changed_methods, vec!["com.example.Helper::help".to_string()]);
+    let deleted = graph.get_node("method:com.example.Helper::help").unwrap();
+    assert_eq!(deleted.metadata.properties.get("deleted").map(String::as_str), Some("true"));
+    assert!(graph.get_node("method:com.example.Caller::call").is_some());
+    assert!(graph.edges().any(|edge| edge.from == "method:com.example.Caller::call" && edge.to == "method:com.example.Helper::help"));
+}
+
+#[test]
+fn test_windows_style_patch_paths_normalized_by_default() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir_all(workspace.join("src/main")).unwrap();
+    fs::write(
+        workspace.join("src/main/Foo.java"),
+        "package com.example;\n\npublic class Foo {\n    public void bar() {\n        System.out.println(\"bar\");\n    }\n}\n",
+    ).unwrap();
+    
+    // Windows 上生成的 patch 使用反斜杠分隔符，并带有 ./ 前缀
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/.\\src\\main\\Foo.java b/.\\src\\main\\Foo.java\nindex 0000000..1111111 100644\n--- a/.\\src\\main\\Foo.java\n+++ b/.\\src\\main\\Foo.java\n@@ -5,1 +5,1 @@\n-        System.out.println(\"foo\");\n+        System.out.println(\"bar\");\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    assert_eq!(result.changed_methods, vec!["com.example.Foo::bar".to_string()]);
+    
+    // 关闭规范化后按原样比较，匹配不到索引中的文件
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    orchestrator.set_normalize_paths(false);
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    assert!(result.changed_methods.is_empty());
+}
+
+#[test]
+fn test_static_initializer_traced_as_clinit() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    fs::write(
+        workspace.join("Registry.java"),
+        "package com.example;\n\npublic class Registry {\n    public void register(String name) {\n        System.out.println(name);\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("Plugins.java"),
+        "package com.example;\n\npublic class Plugins {\n    private static final Registry REGISTRY = new Registry();\n\n    static {\n        REGISTRY.register(\"default\");\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Registry.java b/Registry.java\nindex 0000000..1111111 100644\n--- a/Registry.java\n+++ b/Registry.java\n@@ -5,1 +5,1 @@\n-        System.out.print(name);\n+        System.out.println(name);\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    
+    // 静态初始化块中的调用作为 <clinit> 的上游边
+    assert!(result.impact_graph.edges().any(|edge| {
+        edge.from == "method:com.example.Plugins::<clinit>" && edge.to == "method:com.example.Registry::register"
+    }));
+}
+
+#[test]
+fn test_visibility_public_excludes_private_seeds() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    fs::write(
+        workspace.join("OrderService.java"),
+        "package com.example;\n\npublic class OrderService {\n    public void place() {\n        this.validate();\n    }\n\n    private void validate() {\n        this.check(1);\n    }\n\n    void check(int n) {\n    }\n}\n",
+    ).unwrap();
+    
+    // 只修改了私有方法 validate
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/OrderService.java b/OrderService.java\nindex 0000000..1111111 100644\n--- a/OrderService.java\n+++ b/OrderService.java\n@@ -9,1 +9,1 @@\n-        this.check(0);\n+        this.check(1);\n",
+    ).unwrap();
+    
+    let place = "com.example.OrderService::place";
+    let validate = "com.example.OrderService::validate";
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    assert_eq!(result.changed_methods, vec![validate.to_string()]);
+    
+    // 私有方法不作为起点，由调用它的公开方法代替
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    orchestrator.set_min_visibility(Some(Visibility::Public));
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    assert_eq!(result.changed_methods, vec![place.to_string()]);
+    assert!(result.impact_graph.get_node(&format!("method:{}", validate)).is_some());
+    
+    // 只列出变更方法时使用与分析相同的起点
+    assert_eq!(orchestrator.list_changed_methods(&patch_path).unwrap(), vec![place.to_string()]);
+    
+    // 同时从影响图中收缩私有方法
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    orchestrator.set_min_visibility(Some(Visibility::Public));
+    orchestrator.set_hide_low_visibility(true);
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    let graph = &result.impact_graph;
+    assert!(graph.get_node(&format!("method:{}", validate)).is_none());
+    assert!(graph.edges().any(|edge| edge.from == format!("method:{}", place) && edge.to == "method:com.example.OrderService::check"));
+}
+
+#[test]
+fn test_kafka_topic_carries_value_type() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("OrderProducer.java"),
+        "package com.example.order;\n\npublic class OrderProducer {\n    public void publish(long id) {\n        OrderEvent event = new OrderEvent(id);\n        kafkaTemplate.send(\"order-created\", event);\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/OrderProducer.java b/OrderProducer.java\nindex 0000000..1111111 100644\n--- a/OrderProducer.java\n+++ b/OrderProducer.java\n@@ -5,1 +5,1 @@\n-        OrderEvent event = new OrderEvent(0);\n+        OrderEvent event = new OrderEvent(id);\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    
+    let topic = result.impact_graph.get_node("kafka:order-created").unwrap();
+    assert_eq!(topic.metadata.properties.get("value_type").map(String::as_str), Some("OrderEvent"));
+}
+
+#[test]
+fn test_sql_migration_registers_tables() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    let migrations = workspace.join("src").join("main").join("resources").join("db").join("migration");
+    fs::create_dir_all(&migrations).unwrap();
+    fs::write(
+        migrations.join("V1__create_orders.sql"),
+        "CREATE TABLE orders (\n    id BIGINT PRIMARY KEY,\n    status VARCHAR(32)\n);\n",
+    ).unwrap();
+    
+    // 代码中的 ordr 是 orders 的拼写错误
+    fs::write(
+        workspace.join("OrderRepository.java"),
+        "public class OrderRepository {\n    public void save() {\n        jdbcTemplate.update(\"INSERT INTO orders (id) VALUES (?)\");\n    }\n    public void find() {\n        jdbcTemplate.query(\"SELECT id FROM ordr WHERE id = ?\");\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/OrderRepository.java b/OrderRepository.java\nindex 0000000..1111111 100644\n--- a/OrderRepository.java\n+++ b/OrderRepository.java\n@@ -3,1 +3,1 @@\n-        jdbcTemplate.update(\"INSERT INTO orders (id, status) VALUES (?, ?)\");\n+        jdbcTemplate.update(\"INSERT INTO orders (id) VALUES (?)\");\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    
+    // 迁移中定义的 orders 已注册，未定义的 ordr 给出警告
+    assert!(result.impact_graph.get_node("db:orders").is_some());
+    assert!(result.warnings.iter().any(|w| w.contains("'ordr'")));
+    assert!(!result.warnings.iter().any(|w| w.contains("'orders'")));
+}
+
+#[test]
+fn test_significant_changes_only_skips_comment_edits() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("Test.java"),
+        "public class Test {\n    public void method1() {\n        // explain method1\n        System.out.println(\"Method 1\");\n    }\n    public void method2() {\n        method1();\n    }\n}\n",
+    ).unwrap();
+    
+    // method1 中只改了注释，method2 中改了代码
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Test.java b/Test.java\nindex 0000000..1111111 100644\n--- a/Test.java\n+++ b/Test.java\n@@ -3,1 +3,1 @@\n-        // old explanation\n+        // explain method1\n@@ -7,1 +7,1 @@\n-        method3();\n+        method1();\n",
+    ).unwrap();
+    
+    // 默认情况下两个方法都被视为变更
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    let changed = orchestrator.list_changed_methods(&patch_path).unwrap();
+    assert_eq!(changed, vec!["Test::method1".to_string(), "Test::method2".to_string()]);
+    
+    // 启用后只有包含代码变更的方法作为追溯起点
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    orchestrator.set_significant_changes_only(true);
+    let changed = orchestrator.list_changed_methods(&patch_path).unwrap();
+    assert_eq!(changed, vec!["Test::method2".to_string()]);
+}
+
+#[test]
+fn test_same_line_methods_disambiguated_by_columns() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("Point.java"),
+        "public class Point {\n    int x(){return x;} int y(){return y + 1;}\n}\n",
+    ).unwrap();
+    
+    // 两个方法写在同一行，只修改了 y()
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Point.java b/Point.java\nindex 0000000..1111111 100644\n--- a/Point.java\n+++ b/Point.java\n@@ -1,3 +1,3 @@\n public class Point {\n-    int x(){return x;} int y(){return y;}\n+    int x(){return x;} int y(){return y + 1;}\n }\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    let changed = orchestrator.list_changed_methods(&patch_path).unwrap();
+    assert_eq!(changed, vec!["Point::y".to_string()]);
+    
+    // 只改了两个方法之间的空白时无法区分，两个方法都作为变更
+    fs::write(
+        workspace.join("Point.java"),
+        "public class Point {\n    int x(){return x;}  int y(){return y;}\n}\n",
+    ).unwrap();
+    fs::write(
+        &patch_path,
+        "diff --git a/Point.java b/Point.java\nindex 0000000..1111111 100644\n--- a/Point.java\n+++ b/Point.java\n@@ -1,3 +1,3 @@\n public class Point {\n-    int x(){return x;} int y(){return y;}\n+    int x(){return x;}  int y(){return y;}\n }\n",
+    ).unwrap();
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    orchestrator.set_force_rebuild(true);
+    let changed = orchestrator.list_changed_methods(&patch_path).unwrap();
+    assert_eq!(changed, vec!["Point::x".to_string(), "Point::y".to_string()]);
+}
+
+#[test]
+fn test_added_only_skips_methods_with_only_removed_lines() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("Test.java"),
+        "public class Test {\n    public void method1() {\n        System.out.println(\"Method 1\");\n    }\n    public void method2() {\n        method1();\n        log();\n    }\n}\n",
+    ).unwrap();
+    
+    // method1 中只删除了一行，method2 中新增了一行
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Test.java b/Test.java\nindex 0000000..1111111 100644\n--- a/Test.java\n+++ b/Test.java\n@@ -3,2 +3,1 @@\n-        cleanup();\n         System.out.println(\"Method 1\");\n@@ -7,1 +6,2 @@\n         method1();\n+        log();\n",
+    ).unwrap();
+    
+    // 默认情况下两个方法都被视为变更
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    let changed = orchestrator.list_changed_methods(&patch_path).unwrap();
+    assert_eq!(changed, vec!["Test::method1".to_string(), "Test::method2".to_string()]);
+    
+    // 启用后只删除了代码的 method1 不再作为追溯起点
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    orchestrator.set_added_only(true);
+    let changed = orchestrator.list_changed_methods(&patch_path).unwrap();
+    assert_eq!(changed, vec!["Test::method2".to_string()]);
+}
+
+#[test]
+fn test_change_churn_counts_hunks_per_method() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("Test.java"),
+        "public class Test {\n    public void method1() {\n        validate();\n        int a = 1;\n        int b = 2;\n        int c = 3;\n        int d = 4;\n        int e = 5;\n        int f = 6;\n        int g = 7;\n        save();\n    }\n    public void method2() {\n        method1();\n    }\n}\n",
+    ).unwrap();
+    
+    // method1 开头和结尾各有一个 hunk，method2 只有一个 hunk
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Test.java b/Test.java\nindex 0000000..1111111 100644\n--- a/Test.java\n+++ b/Test.java\n@@ -3,1 +3,1 @@\n-        check();\n+        validate();\n@@ -11,1 +11,1 @@\n-        store();\n+        save();\n@@ -14,1 +14,1 @@\n-        method0();\n+        method1();\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let mut result = orchestrator.analyze(&patch_path).unwrap();
+    
+    let method1 = result.impact_graph.get_node("method:Test::method1").unwrap();
+    assert_eq!(method1.metadata.properties.get("change_regions").map(String::as_str), Some("2"));
+    assert_eq!(method1.metadata.properties.get("changed_lines").map(String::as_str), Some("4"));
+    assert_eq!(result.churn_of("Test::method2"), Some(ChangeChurn { change_regions: 1, changed_lines: 2 }));
+    
+    // 按改动量排序后 method1 排在前面
+    result.changed_methods = vec!["Test::method2".to_string(), "Test::method1".to_string()];
+    result.sort_by_churn();
+    assert_eq!(result.changed_methods, vec!["Test::method1".to_string(), "Test::method2".to_string()]);
+    
+    // 排名随影响图写入 JSON 和 DOT 输出
+    let json: serde_json::Value = serde_json::from_str(&result.impact_graph.to_json().unwrap()).unwrap();
+    let rank_of = |id: &str| json["nodes"].as_array().unwrap().iter()
+        .find(|node| node["id"] == id)
+        .map(|node| node["properties"]["churn_rank"].clone());
+    assert_eq!(rank_of("method:Test::method1"), Some(serde_json::json!("1")));
+    assert_eq!(rank_of("method:Test::method2"), Some(serde_json::json!("2")));
+    assert!(result.impact_graph.to_dot().contains("churn_rank=\"1\""));
+    
+    let mut ranking = Vec::new();
+    write_churn_ranking(&result, &mut ranking).unwrap();
+    assert_eq!(
+        String::from_utf8(ranking).unwrap(),
+        "Changed methods by churn\n  Test::method1: 2 regions, 4 lines\n  Test::method2: 1 regions, 2 lines\n",
+    );
+}
+
+#[test]
+fn test_no_save_index_leaves_workspace_untouched() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    fs::write(workspace.join("main.rs"), "fn main() {\n    helper();\n}\n\nfn helper() {\n}\n").unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/main.rs b/main.rs\nindex 0000000..1111111 100644\n--- a/main.rs\n+++ b/main.rs\n@@ -5,2 +5,3 @@\n fn helper() {\n+    println!(\"helper\");\n }\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    orchestrator.set_persist_index(false);
+    orchestrator.set_force_rebuild(true);
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    assert!(result.impact_graph.get_node("method:main").is_some());
+    
+    // 没有创建任何索引文件
+    assert!(!workspace.join(".code-impact-analyzer").exists());
+    
+    // 默认会保存索引
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    orchestrator.analyze(&patch_path).unwrap();
+    assert!(workspace.join(".code-impact-analyzer").exists());
+}
+
+#[test]
+fn test_no_save_index_loads_from_explicit_index_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    fs::write(workspace.join("main.rs"), "fn main() {\n    helper();\n}\n\nfn helper() {\n}\n").unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/main.rs b/main.rs\nindex 0000000..1111111 100644\n--- a/main.rs\n+++ b/main.rs\n@@ -5,2 +5,3 @@\n fn helper() {\n+    println!(\"helper\");\n }\n",
+    ).unwrap();
+    
+    // 预先把索引构建到工作空间之外的目录
+    let index_dir = temp_dir.path().join("prebuilt-index");
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    orchestrator.set_index_source(IndexSource::Local(IndexStorage::new(workspace.clone()).with_index_dir(&index_dir)));
+    orchestrator.analyze(&patch_path).unwrap();
+    assert!(index_dir.join("index.json").exists());
+    assert!(!workspace.join(".code-impact-analyzer").exists());
+    
+    // 只读运行从该目录加载索引，不向任何位置写入
+    let storage = IndexStorage::new(workspace.clone()).with_index_dir(&index_dir);
+    assert!(storage.load_index().unwrap().is_some());
+    let modified = fs::metadata(index_dir.join("index.json")).unwrap().modified().unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    orchestrator.set_persist_index(false);
+    orchestrator.set_index_source(IndexSource::Local(storage));
+    let result = orchestrator.analyze(&patch_path).unwrap();
+    assert!(result.impact_graph.get_node("method:main").is_some());
+    assert!(!workspace.join(".code-impact-analyzer").exists());
+    assert_eq!(fs::metadata(index_dir.join("index.json")).unwrap().modified().unwrap(), modified);
+}
+
+#[test]
+fn test_quiet_mode_still_writes_output() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("Test.java"),
+        "public class Test {\n    public void method1() {\n        System.out.println(\"Method 1\");\n    }\n    public void method2() {\n        method1();\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Test.java b/Test.java\nindex 0000000..1111111 100644\n--- a/Test.java\n+++ b/Test.java\n@@ -3,1 +3,1 @@\n-        System.out.println(\"Old\");\n+        System.out.println(\"Method 1\");\n",
+    ).unwrap();
+    
+    let output_path = temp_dir.path().join("impact.json");
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_code_impact_analyzer"))
+        .arg("-w").arg(&workspace)
+        .arg("-d").arg(&patch_path)
+        .arg("--output").arg(&output_path)
+        .args(["-o", "json", "--quiet", "--color", "never"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    
+    // 影响图写入文件，标准输出为空；标准错误只有警告和最终摘要，没有任何进度日志
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Analysis summary"), "unexpected stderr: {}", stderr);
+    assert!(!stderr.contains(" INFO "), "unexpected stderr: {}", stderr);
+    
+    // 最终结果照常写入输出文件
+    let content = fs::read_to_string(&output_path).unwrap();
+    let graph: serde_json::Value = serde_json::from_str(&content).unwrap();
+    let nodes = graph["nodes"].as_array().unwrap();
+    assert!(nodes.iter().any(|node| node["id"] == "method:Test::method1"));
+    assert!(graph["edges"].is_array());
+}
+
+#[test]
+fn test_dump_index_writes_to_output_file() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    fs::write(
+        workspace.join("Test.java"),
+        "public class Test {\n    public void method1() {\n        method2();\n    }\n    public void method2() {\n    }\n}\n",
+    ).unwrap();
+    
+    let output_path = temp_dir.path().join("index.json");
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_code_impact_analyzer"))
+        .arg("-w").arg(&workspace)
+        .arg("--dump-index")
+        .arg("--output").arg(&output_path)
+        .args(["--quiet", "--color", "never"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    
+    // 索引写入 --output 指定的文件，标准输出为空
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+    let content = fs::read_to_string(&output_path).unwrap();
+    let index: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert!(index["methods"]["Test::method1"].is_object(), "unexpected index: {}", index);
+    assert!(index["method_calls"]["Test::method1"].is_array(), "unexpected index: {}", index);
+}
+
+#[test]
+fn test_trace_from_http_endpoint() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("OrderController.java"),
+        "package com.example.web;\n\nimport com.example.service.OrderService;\n\npublic class OrderController {\n    private OrderService orderService;\n\n    @GetMapping(\"/api/orders/{id}\")\n    public Order get(Long id) {\n        return orderService.find(id);\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("OrderService.java"),
+        "package com.example.service;\n\npublic class OrderService {\n    public Order find(Long id) {\n        return null;\n    }\n}\n",
+    ).unwrap();
+    
+    let endpoint: HttpEndpoint = "GET:/api/orders/{id}".parse().unwrap();
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze_endpoint(&endpoint).unwrap();
+    
+    // 以端点的提供者为起点，追溯结果包含提供者及其下游
+    assert_eq!(result.changed_methods, vec!["com.example.web.OrderController::get".to_string()]);
+    let graph = &result.impact_graph;
+    assert!(graph.get_node("method:com.example.web.OrderController::get").is_some());
+    assert!(graph.get_node("method:com.example.service.OrderService::find").is_some());
+    assert!(graph.get_node("http:GET:api/orders/{id}").is_some());
+    
+    // 没有提供者的端点返回错误
+    let missing: HttpEndpoint = "POST:/api/orders".parse().unwrap();
+    let err = orchestrator.analyze_endpoint(&missing).unwrap_err();
+    assert!(matches!(err, AnalysisError::TraceError(TraceError::EndpointNotFound { .. })));
+    
+    // 通过 CLI 入口运行时不需要 diff
+    let args = CliArgs {
+        workspace_path: workspace,
+        output_format: OutputFormat::Json,
+        output_path: Some(temp_dir.path().join("impact.json")),
+        log_level: LogLevel::Error,
+        color: ColorMode::Never,
+        trace_endpoint: Some(endpoint),
+        ..Default::default()
+    };
+    assert!(run(args).is_ok());
+    let output = fs::read_to_string(temp_dir.path().join("impact.json")).unwrap();
+    assert!(output.contains("com.example.service.OrderService::find"));
+}
+
+#[test]
+fn test_trace_from_database_table() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("OrderRepository.java"),
+        "package com.example.order;\n\npublic class OrderRepository {\n    public void save() {\n        jdbcTemplate.update(\"INSERT INTO orders (id) VALUES (?)\");\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("ReportService.java"),
+        "package com.example.report;\n\npublic class ReportService {\n    public void summarize() {\n        jdbcTemplate.query(\"SELECT id FROM orders\");\n    }\n}\n",
+    ).unwrap();
+    
+    let resource: ResourceId = "db:orders".parse().unwrap();
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze_resource(&resource).unwrap();
+    
+    // 写入者和读取者都作为起点
+    assert_eq!(result.changed_methods, vec![
+        "com.example.order.OrderRepository::save".to_string(),
+        "com.example.report.ReportService::summarize".to_string(),
+    ]);
+    let graph = &result.impact_graph;
+    assert!(graph.get_node("db:orders").is_some());
+    assert!(graph.get_node("method:com.example.order.OrderRepository::save").is_some());
+    assert!(graph.get_node("method:com.example.report.ReportService::summarize").is_some());
+    
+    // 没有方法访问的资源返回错误
+    let missing: ResourceId = "kafka:unknown-topic".parse().unwrap();
+    let err = orchestrator.analyze_resource(&missing).unwrap_err();
+    assert!(matches!(err, AnalysisError::TraceError(TraceError::ResourceNotFound { .. })));
+}
+
+#[test]
+fn test_list_http_endpoints() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    fs::write(
+        workspace.join("OrderController.java"),
+        "package com.example.web;\n\npublic class OrderController {\n    @GetMapping(\"/api/orders/{id}\")\n    public Order get(Long id) {\n        return null;\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        workspace.join("UserController.java"),
+        "package com.example.web;\n\npublic class UserController {\n    @PostMapping(\"/api/users\")\n    public User create(User user) {\n        return user;\n    }\n}\n",
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let endpoints = orchestrator.list_http_endpoints().unwrap();
+    
+    let listed: Vec<(&str, &str, &str)> = endpoints.iter()
+        .map(|(endpoint, provider)| (endpoint.method_str(), endpoint.path_pattern.as_str(), provider.as_str()))
+        .collect();
+    assert_eq!(listed, vec![
+        ("GET", "api/orders/{id}", "com.example.web.OrderController::get"),
+        ("POST", "api/users", "com.example.web.UserController::create"),
+    ]);
+    
+    let mut output = Vec::new();
+    write_http_endpoints(&endpoints, &mut output).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "GET\tapi/orders/{id}\tcom.example.web.OrderController::get\nPOST\tapi/users\tcom.example.web.UserController::create\n",
+    );
+}
+
+#[test]
+fn test_summary_color_modes() {
+    let result = AnalysisResult {
+        impact_graph: ImpactGraph::new(),
+        changed_methods: vec!["com.example.Foo::bar".to_string()],
+        low_confidence_methods: vec![],
+        statistics: AnalysisStatistics {
+            failed_files: 1,
+            blast_radius: 3,
+            ..AnalysisStatistics::default()
+        },
+        warnings: vec!["Failed to parse Broken.java".to_string()],
+        errors: vec![],
+    };
+    
+    // never：不输出任何 ANSI 转义序列
+    let mut plain = Vec::new();
+    write_summary(&result, ColorMode::Never.enabled(), &mut plain).unwrap();
+    let plain = String::from_utf8(plain).unwrap();
+    assert!(!plain.contains('\x1b'));
+    assert!(plain.contains("Changed methods: 1"));
+    assert!(plain.contains("Blast radius:    3"));
+    assert!(plain.contains("Warnings:        1"));
+    // 空影响图不输出模块汇总
+    assert!(!plain.contains("Impact by module"));
+    assert!(plain.contains("Components:      0"));
+    assert!(plain.contains("Average degree:  0.00"));
+    
+    // always：警告为黄色、失败文件为红色
+    let mut colored = Vec::new();
+    write_summary(&result, ColorMode::Always.enabled(), &mut colored).unwrap();
+    let colored = String::from_utf8(colored).unwrap();
+    assert!(colored.contains("\x1b[1m"));
+    assert!(colored.contains("\x1b[33m"));
+    assert!(colored.contains("\x1b[31m"));
+}
+
+#[test]
+fn test_check_impact_threshold() {
+    let mut graph = ImpactGraph::new();
+    graph.add_node(ImpactNode::method("A".to_string()));
+    graph.add_node(ImpactNode::method("B".to_string()));
+    graph.add_node(ImpactNode::method("C".to_string()));
+    graph.add_node(ImpactNode::kafka_topic("events".to_string()));
+    
+    // 只统计方法节点：3 个方法不超过阈值 3
+    assert!(check_impact_threshold(&graph, 3).is_ok());
+    
+    // 超过阈值时返回错误
+    let err = check_impact_threshold(&graph, 2).unwrap_err();
+    assert!(matches!(err, AnalysisError::ImpactThresholdExceeded { impact: 3, threshold: 2 }));
+    assert_eq!(err.to_string(), "impact of 3 exceeds threshold 2");
+}
+
+#[test]
+fn test_end_to_end_max_impact_exceeded() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    
+    // method2 调用 method1，修改 method1 会影响 2 个方法
+    fs::write(
+        workspace.join("Test.java"),
+        "public class Test {\n    public void method1() {\n        System.out.println(\"Method 1\");\n    }\n    public void method2() {\n        method1();\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Test.java b/Test.java\nindex 0000000..1111111 100644\n--- a/Test.java\n+++ b/Test.java\n@@ -3,1 +3,1 @@\n-        System.out.println(\"Old\");\n+        System.out.println(\"Method 1\");\n",
+    ).unwrap();
+    
+    let output_path = temp_dir.path().join("impact.json");
+    let args = CliArgs {
+        workspace_path: workspace,
+        diff_path: Some(patch_path),
+        output_format: OutputFormat::Json,
+        output_path: Some(output_path.clone()),
+        log_level: LogLevel::Error,
+        color: ColorMode::Never,
+        max_impact: Some(0),
+        ..Default::default()
+    };
+    
+    // 超过阈值时返回对应的退出状态（main 据此以退出码 3 结束）
+    let status = run(args).unwrap();
+    assert_eq!(status, ExitStatus::ImpactThresholdExceeded);
+    assert_eq!(status.code(), 3);
+    
+    // 完整结果仍然被输出
+    let output = fs::read_to_string(&output_path).unwrap();
+    assert!(output.contains("Test::method1"));
+}
+
+#[test]
+fn test_strict_mode_reports_parse_failures_from_cached_index() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    fs::write(
+        workspace.join("Test.java"),
+        "public class Test {\n    public void method1() {\n        System.out.println(\"Method 1\");\n    }\n}\n",
+    ).unwrap();
+    // 不是合法 UTF-8 的源文件无法解析
+    fs::write(workspace.join("Broken.java"), b"public class Broken { \xff\xfe }\n").unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Test.java b/Test.java\nindex 0000000..1111111 100644\n--- a/Test.java\n+++ b/Test.java\n@@ -3,1 +3,1 @@\n-        System.out.println(\"Old\");\n+        System.out.println(\"Method 1\");\n",
+    ).unwrap();
+    
+    let output_path = temp_dir.path().join("impact.json");
+    let args = || CliArgs {
+        workspace_path: workspace.clone(),
+        diff_path: Some(patch_path.clone()),
+        output_format: OutputFormat::Json,
+        output_path: Some(output_path.clone()),
+        log_level: LogLevel::Error,
+        color: ColorMode::Never,
+        strict: true,
+        ..Default::default()
+    };
+    
+    // 第一次运行构建并保存索引，第二次从磁盘加载索引，两次都应报告解析失败
+    let index_file = workspace.join(".code-impact-analyzer").join("index.json");
+    let mut saved = None;
+    for _ in 0..2 {
+        let error = run(args()).unwrap_err();
+        assert!(matches!(error, AnalysisError::ParseFailures { count: 1 }), "unexpected error: {}", error);
+        assert_eq!(error.exit_code(), ExitStatus::ParseErrors.code());
+        // 完整结果仍然被输出
+        assert!(fs::read_to_string(&output_path).unwrap().contains("Test::method1"));
+        
+        let modified = fs::metadata(&index_file).unwrap().modified().unwrap();
+        assert_eq!(*saved.get_or_insert(modified), modified, "second run should load the saved index");
+    }
+}
+
+#[test]
+fn test_strict_mode_fails_on_syntax_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    fs::write(
+        workspace.join("Test.java"),
+        "public class Test {\n    public void method1() {\n        int x = ;\n        System.out.println(\"Method 1\");\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/Test.java b/Test.java\nindex 0000000..1111111 100644\n--- a/Test.java\n+++ b/Test.java\n@@ -4,1 +4,1 @@\n-        System.out.println(\"Old\");\n+        System.out.println(\"Method 1\");\n",
+    ).unwrap();
+    
+    let output_path = temp_dir.path().join("impact.json");
+    let args = |strict| CliArgs {
+        workspace_path: workspace.clone(),
+        diff_path: Some(patch_path.clone()),
+        output_format: OutputFormat::Json,
+        output_path: Some(output_path.clone()),
+        log_level: LogLevel::Error,
+        color: ColorMode::Never,
+        strict,
+        ..Default::default()
+    };
+    
+    // 存在语法错误的文件仍从恢复后的语法树索引，只有严格模式下失败
+    assert_eq!(run(args(false)).unwrap(), ExitStatus::Success);
+    let error = run(args(true)).unwrap_err();
+    assert!(matches!(error, AnalysisError::ParseFailures { count: 1 }), "unexpected error: {}", error);
+    assert!(fs::read_to_string(&output_path).unwrap().contains("Test::method1"));
+}
+
 #[test]
 fn test_end_to_end_error_handling() {
     // 测试各种错误情况
@@ -81,10 +1445,10 @@ fn test_end_to_end_error_handling() {
     // 测试 1: 不存在的 workspace
     let args = CliArgs {
         workspace_path: temp_dir.path().join("nonexistent"),
-        diff_path: temp_dir.path().join("test.patch"),
-        output_format: OutputFormat::Dot,
-        max_depth: 10,
+        diff_path: Some(temp_dir.path().join("test.patch")),
         log_level: LogLevel::Error,
+        color: ColorMode::Never,
+        ..Default::default()
     };
     assert!(run(args).is_err());
     
@@ -94,10 +1458,71 @@ fn test_end_to_end_error_handling() {
     
     let args = CliArgs {
         workspace_path: workspace,
-        diff_path: temp_dir.path().join("nonexistent.patch"),
-        output_format: OutputFormat::Dot,
-        max_depth: 10,
+        diff_path: Some(temp_dir.path().join("nonexistent.patch")),
         log_level: LogLevel::Error,
+        color: ColorMode::Never,
+        ..Default::default()
+    };
+    assert!(run(args).is_err());
+}
+
+#[test]
+fn test_extra_source_root_nested_in_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let workspace = temp_dir.path().join("workspace");
+    let libs_src = workspace.join("libs-src");
+    fs::create_dir_all(libs_src.join("org/thirdparty")).unwrap();
+    
+    fs::write(
+        workspace.join("OrderService.java"),
+        "package com.example;\n\nimport org.thirdparty.Mailer;\n\npublic class OrderService {\n    private Mailer mailer;\n\n    public void place() {\n        mailer.send();\n    }\n}\n",
+    ).unwrap();
+    fs::write(
+        libs_src.join("org/thirdparty/Mailer.java"),
+        "package org.thirdparty;\n\npublic class Mailer {\n    public void send() {\n    }\n}\n",
+    ).unwrap();
+    
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/OrderService.java b/OrderService.java\nindex 0000000..1111111 100644\n--- a/OrderService.java\n+++ b/OrderService.java\n@@ -9,1 +9,1 @@\n-        mailer.close();\n+        mailer.send();\n",
+    ).unwrap();
+    
+    let output_path = temp_dir.path().join("impact.json");
+    let args = CliArgs {
+        workspace_path: workspace.clone(),
+        diff_path: Some(patch_path.clone()),
+        output_format: OutputFormat::Json,
+        output_path: Some(output_path.clone()),
+        color: ColorMode::Never,
+        quiet: true,
+        extra_source_roots: vec![libs_src],
+        ..Default::default()
+    };
+    assert!(run(args).is_ok());
+    
+    // 工作空间内的 libs-src 只按额外源码根目录索引，调用继续进入其中的方法并标记为第三方
+    let graph: serde_json::Value = serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+    let send = graph["nodes"].as_array().unwrap().iter()
+        .find(|node| node["id"] == "method:org.thirdparty.Mailer::send")
+        .expect("third-party method should be traced");
+    assert_eq!(send["properties"]["third_party"], "true");
+    let place = graph["nodes"].as_array().unwrap().iter()
+        .find(|node| node["id"] == "method:com.example.OrderService::place")
+        .unwrap();
+    assert!(place["properties"].get("third_party").is_none());
+    
+    // 拼错的目录导致运行失败
+    let args = CliArgs {
+        workspace_path: workspace.clone(),
+        diff_path: Some(patch_path),
+        output_format: OutputFormat::Json,
+        output_path: Some(output_path),
+        color: ColorMode::Never,
+        quiet: true,
+        extra_source_roots: vec![workspace.join("lib-src")],
+        ..Default::default()
     };
     assert!(run(args).is_err());
 }
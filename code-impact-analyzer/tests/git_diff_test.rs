@@ -0,0 +1,111 @@
+//! 从 Git 仓库读取已暂存/未暂存变更的集成测试（需要启用 git 特性）
+#![cfg(feature = "git")]
+
+use code_impact_analyzer::{AnalysisOrchestrator, BlameCache, GitDiffMode, TraceConfig};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+const ORIGINAL: &str = "public class Test {\n    public void staged() {\n        System.out.println(\"a\");\n    }\n    public void unstaged() {\n        System.out.println(\"b\");\n    }\n    public void untouched() {\n        System.out.println(\"c\");\n    }\n}\n";
+
+fn git(repo: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(repo)
+        .status()
+        .expect("git should be installed");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn test_staged_and_working_changes_are_analyzed_separately() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo = temp_dir.path();
+    let file = repo.join("Test.java");
+    
+    fs::write(&file, ORIGINAL).unwrap();
+    git(repo, &["init", "-q"]);
+    git(repo, &["add", "Test.java"]);
+    git(repo, &["commit", "-q", "-m", "init"]);
+    
+    // 暂存 staged() 的修改，再在工作区修改 unstaged()
+    fs::write(&file, ORIGINAL.replace("\"a\"", "\"a2\"")).unwrap();
+    git(repo, &["add", "Test.java"]);
+    fs::write(&file, ORIGINAL.replace("\"a\"", "\"a2\"").replace("\"b\"", "\"b2\"")).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(repo.to_path_buf(), TraceConfig::default()).unwrap();
+    
+    let staged = orchestrator.analyze_git_changes(GitDiffMode::Staged).unwrap();
+    assert_eq!(staged.changed_methods, vec!["Test::staged".to_string()]);
+    
+    let working = orchestrator.analyze_git_changes(GitDiffMode::Working).unwrap();
+    assert_eq!(working.changed_methods, vec!["Test::unstaged".to_string()]);
+}
+
+#[test]
+fn test_staged_changes_are_mapped_through_unstaged_edits() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo = temp_dir.path();
+    let file = repo.join("Test.java");
+    
+    fs::write(&file, ORIGINAL).unwrap();
+    git(repo, &["init", "-q"]);
+    git(repo, &["add", "Test.java"]);
+    git(repo, &["commit", "-q", "-m", "init"]);
+    
+    // 暂存 untouched() 的修改，再在工作区的类开头插入一个方法，使其后的行整体下移
+    let staged = ORIGINAL.replace("\"c\"", "\"c2\"");
+    fs::write(&file, &staged).unwrap();
+    git(repo, &["add", "Test.java"]);
+    let inserted = "public class Test {\n    public void inserted() {\n        System.out.println(\"d\");\n    }\n";
+    fs::write(&file, staged.replacen("public class Test {\n", inserted, 1)).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(repo.to_path_buf(), TraceConfig::default()).unwrap();
+    let result = orchestrator.analyze_git_changes(GitDiffMode::Staged).unwrap();
+    assert_eq!(result.changed_methods, vec!["Test::untouched".to_string()]);
+}
+
+#[test]
+fn test_method_nodes_carry_last_author() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo = temp_dir.path();
+    let file = repo.join("Test.java");
+    
+    fs::write(&file, ORIGINAL).unwrap();
+    git(repo, &["init", "-q"]);
+    git(repo, &["add", "Test.java"]);
+    git(repo, &["commit", "-q", "-m", "init"]);
+    
+    // 由另一位作者修改 unstaged()，再在工作区修改 staged() 作为待分析的变更
+    fs::write(&file, ORIGINAL.replace("\"b\"", "\"b2\"")).unwrap();
+    let status = Command::new("git")
+        .args(["-c", "user.name=alice", "-c", "user.email=alice@example.com", "commit", "-q", "-am", "update"])
+        .current_dir(repo)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    fs::write(&file, ORIGINAL.replace("\"b\"", "\"b2\"").replace("\"a\"", "\"a2\"")).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(repo.to_path_buf(), TraceConfig::default()).unwrap();
+    orchestrator.set_annotate_blame(true);
+    
+    let result = orchestrator.analyze_git_changes(GitDiffMode::Working).unwrap();
+    let properties = &result.impact_graph.get_node("method:Test::staged").unwrap().metadata.properties;
+    // 工作区中尚未提交的行不计入，取方法内最后一次提交的作者
+    assert_eq!(properties.get("last_author").map(String::as_str), Some("test"));
+    assert_eq!(properties.get("last_commit").map(String::len), Some(40));
+    
+    let mut blame = BlameCache::open(repo).unwrap();
+    let last = blame.last_commit(&file, (5, 7)).unwrap();
+    assert_eq!(last.author, "alice");
+    assert_ne!(last.commit, properties["last_commit"]);
+}
+
+#[test]
+fn test_git_changes_outside_repository_fail() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut orchestrator = AnalysisOrchestrator::new(temp_dir.path().to_path_buf(), TraceConfig::default()).unwrap();
+    assert!(orchestrator.analyze_git_changes(GitDiffMode::Staged).is_err());
+}
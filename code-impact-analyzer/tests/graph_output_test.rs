@@ -1,5 +1,7 @@
-use code_impact_analyzer::impact_tracer::{ImpactGraph, ImpactNode, EdgeType, Direction};
-use code_impact_analyzer::types::HttpMethod;
+use code_impact_analyzer::code_index::CodeIndex;
+use code_impact_analyzer::impact_tracer::{ImpactGraph, ImpactNode, ImpactTracer, TraceConfig, EdgeType, Direction};
+use code_impact_analyzer::language_parser::{MethodCall, MethodInfo};
+use code_impact_analyzer::types::{DbOpType, DbOperation, HttpMethod};
 
 #[test]
 fn test_graph_output_formats_integration() {
@@ -114,3 +116,135 @@ fn test_cycle_detection_integration() {
     assert_eq!(parsed["node_count"], 3);
     assert_eq!(parsed["edge_count"], 3);
 }
+
+#[test]
+fn test_sarif_output() {
+    let mut index = CodeIndex::new();
+    
+    let method = |name: &str, file: &str, line_range: (usize, usize), calls: Vec<&str>, db_operations: Vec<DbOperation>| MethodInfo {
+        line_range,
+        calls: calls.into_iter().map(|target| MethodCall { target: target.to_string(), line: line_range.0 + 1, condition: None, low_confidence: false }).collect(),
+        db_operations,
+        ..MethodInfo::new(name, file)
+    };
+    index.test_index_method(&method(
+        "com.example.OrderService::create",
+        "/ws/order-service/src/OrderService.java",
+        (12, 20),
+        vec!["com.example.OrderDao::insert"],
+        vec![],
+    )).unwrap();
+    index.test_index_method(&method(
+        "com.example.OrderDao::insert",
+        "/ws/order-service/src/OrderDao.java",
+        (5, 9),
+        vec![],
+        vec![DbOperation { operation_type: DbOpType::Insert, table: "orders".to_string(), line: 6 }],
+    )).unwrap();
+    
+    let seeds = vec!["com.example.OrderService::create".to_string()];
+    let tracer = ImpactTracer::new(&index, TraceConfig::default())
+        .with_workspace_root(std::path::PathBuf::from("/ws"));
+    let graph = tracer.trace_impact(&seeds).unwrap();
+    
+    // 方法节点携带源码位置
+    let seed_node = graph.get_node("method:com.example.OrderService::create").unwrap();
+    assert_eq!(seed_node.metadata.properties["file"], "order-service/src/OrderService.java");
+    assert_eq!(seed_node.metadata.properties["line_start"], "12");
+    
+    let sarif: serde_json::Value = serde_json::from_str(&graph.to_sarif(&seeds).unwrap())
+        .expect("SARIF output should be valid JSON");
+    
+    // 基本 SARIF 结构
+    assert_eq!(sarif["version"], "2.1.0");
+    let runs = sarif["runs"].as_array().expect("runs should be an array");
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0]["tool"]["driver"]["name"], "code-impact-analyzer");
+    
+    // 每个受影响资源一条结果，位置取自种子方法
+    let results = runs[0]["results"].as_array().expect("results should be an array");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["ruleId"], "impact/database-table");
+    assert_eq!(results[0]["message"]["text"], "change impacts table orders");
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "order-service/src/OrderService.java");
+    assert_eq!(location["region"]["startLine"], 12);
+    assert_eq!(location["region"]["endLine"], 20);
+}
+
+#[test]
+fn test_sarif_rules_cover_every_result() {
+    let mut graph = ImpactGraph::new();
+    let seed = ImpactNode::method("com.example.JobService::submit".to_string());
+    let resources = vec![
+        ImpactNode::http_endpoint(HttpMethod::POST, "/api/jobs".to_string()),
+        ImpactNode::kafka_topic("job-events".to_string()),
+        ImpactNode::database_table("jobs".to_string()),
+        ImpactNode::redis_prefix("job:*".to_string()),
+        ImpactNode::resource("queue".to_string(), "job-queue".to_string()),
+    ];
+    let seed_id = seed.id.clone();
+    graph.add_node(seed);
+    for resource in resources {
+        let resource_id = resource.id.clone();
+        graph.add_node(resource);
+        graph.add_edge(&seed_id, &resource_id, EdgeType::ResourceAccess, Direction::Downstream);
+    }
+    
+    let sarif: serde_json::Value = serde_json::from_str(
+        &graph.to_sarif(&["com.example.JobService::submit".to_string()]).unwrap(),
+    ).unwrap();
+    let run = &sarif["runs"][0];
+    let rule_ids: Vec<&str> = run["tool"]["driver"]["rules"].as_array().unwrap().iter()
+        .map(|rule| rule["id"].as_str().unwrap())
+        .collect();
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 5);
+    
+    // 每条结果的 ruleId 都在 tool.driver.rules 中声明，包括自定义资源
+    for result in results {
+        let rule_id = result["ruleId"].as_str().unwrap();
+        assert!(rule_ids.contains(&rule_id), "rule {} is not declared", rule_id);
+    }
+    assert!(results.iter().any(|result| result["ruleId"] == "impact/resource"));
+}
+
+#[test]
+fn test_baseline_difference_keeps_only_new_impact() {
+    // 基线：A -> B
+    let mut baseline = ImpactGraph::new();
+    baseline.add_node(ImpactNode::method("com.example.A::run".to_string()));
+    baseline.add_node(ImpactNode::method("com.example.B::run".to_string()));
+    baseline.add_edge("method:com.example.A::run", "method:com.example.B::run", EdgeType::MethodCall, Direction::Downstream);
+    
+    // 基线经 JSON 输出后重新加载
+    let reloaded = ImpactGraph::from_json(&baseline.to_json().unwrap()).unwrap();
+    assert_eq!(reloaded.node_count(), 2);
+    assert_eq!(reloaded.edge_count(), 1);
+    
+    // 当前图是基线的超集：新增 B -> C 和 C -> orders 表
+    let mut current = ImpactGraph::new();
+    current.add_node(ImpactNode::method("com.example.A::run".to_string()));
+    current.add_node(ImpactNode::method("com.example.B::run".to_string()));
+    current.add_node(ImpactNode::method("com.example.C::run".to_string()));
+    current.add_node(ImpactNode::database_table("orders".to_string()));
+    current.add_edge("method:com.example.A::run", "method:com.example.B::run", EdgeType::MethodCall, Direction::Downstream);
+    current.add_edge("method:com.example.B::run", "method:com.example.C::run", EdgeType::MethodCall, Direction::Downstream);
+    current.add_edge("method:com.example.C::run", "db:orders", EdgeType::DatabaseReadWrite, Direction::Downstream);
+    
+    let delta = current.difference(&reloaded);
+    
+    let node_ids: Vec<&str> = delta.nodes().map(|n| n.id.as_str()).collect();
+    assert_eq!(node_ids, vec!["method:com.example.B::run", "method:com.example.C::run", "db:orders"]);
+    
+    let edges: Vec<(&str, &str)> = delta.edges().map(|e| (e.from.as_str(), e.to.as_str())).collect();
+    assert_eq!(edges, vec![
+        ("method:com.example.B::run", "method:com.example.C::run"),
+        ("method:com.example.C::run", "db:orders"),
+    ]);
+    
+    // 与自身比较没有新增影响
+    let empty = current.difference(&current);
+    assert_eq!(empty.node_count(), 0);
+    assert_eq!(empty.edge_count(), 0);
+}
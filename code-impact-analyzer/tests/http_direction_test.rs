@@ -13,17 +13,13 @@ fn test_http_interface_provider_direction() {
         full_qualified_name: "com.example.UserController::getUser".to_string(),
         file_path: PathBuf::from("UserController.java"),
         line_range: (10, 15),
-        calls: vec![],
         http_annotations: Some(HttpAnnotation {
             method: HttpMethod::GET,
             path: "md-user-service/api/users/{id}".to_string(),
             path_params: vec!["id".to_string()],
-            is_feign_client: false,
             is_feign_client: false,  // 普通 HTTP 接口
         }),
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     index.index_method(&provider).unwrap();
@@ -53,17 +49,13 @@ fn test_feign_client_consumer_direction() {
         full_qualified_name: "com.example.UserClient::fetchUser".to_string(),
         file_path: PathBuf::from("UserClient.java"),
         line_range: (10, 15),
-        calls: vec![],
         http_annotations: Some(HttpAnnotation {
             method: HttpMethod::GET,
             path: "user-service/api/users".to_string(),
             path_params: vec![],
-            is_feign_client: false,
             is_feign_client: true,  // Feign 调用
         }),
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     index.index_method(&consumer).unwrap();
@@ -93,16 +85,13 @@ fn test_feign_client_with_base_path() {
         full_qualified_name: "com.hualala.shop.domain.feign.BasicInfoFeign::getGoodsInfo".to_string(),
         file_path: PathBuf::from("BasicInfoFeign.java"),
         line_range: (10, 15),
-        calls: vec![],
         http_annotations: Some(HttpAnnotation {
             method: HttpMethod::POST,
             path: "hll-basic-info-api/hll-basic-info-api/feign/shop/copy/info".to_string(),
             path_params: vec![],
             is_feign_client: true,  // Feign 调用
         }),
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     index.index_method(&consumer).unwrap();
@@ -128,17 +117,13 @@ fn test_http_provider_and_consumer_different_endpoints() {
         full_qualified_name: "com.example.UserController::createUser".to_string(),
         file_path: PathBuf::from("UserController.java"),
         line_range: (10, 15),
-        calls: vec![],
         http_annotations: Some(HttpAnnotation {
             method: HttpMethod::POST,
             path: "md-user-service/api/users".to_string(),
             path_params: vec![],
-            is_feign_client: false,
             is_feign_client: false,  // 普通 HTTP 接口
         }),
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 创建一个 Feign 客户端调用
@@ -147,16 +132,13 @@ fn test_http_provider_and_consumer_different_endpoints() {
         full_qualified_name: "com.example.OrderClient::callOrderService".to_string(),
         file_path: PathBuf::from("OrderClient.java"),
         line_range: (20, 25),
-        calls: vec![],
         http_annotations: Some(HttpAnnotation {
             method: HttpMethod::GET,
             path: "order-service/api/orders".to_string(),
             path_params: vec![],
             is_feign_client: true,  // Feign 调用
         }),
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     index.index_method(&provider).unwrap();
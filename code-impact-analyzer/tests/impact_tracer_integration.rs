@@ -31,6 +31,9 @@ fn test_trace_with_custom_config() {
         trace_upstream: true,
         trace_downstream: false,
         trace_cross_service: false,
+        allowed_services: Vec::new(),
+        max_bridge_fanout: None,
+        diagnostic_filter: None,
     };
     
     let tracer = ImpactTracer::new(&index, config);
@@ -68,6 +71,9 @@ fn test_trace_with_zero_depth() {
         trace_upstream: true,
         trace_downstream: true,
         trace_cross_service: false,
+        allowed_services: Vec::new(),
+        max_bridge_fanout: None,
+        diagnostic_filter: None,
     };
     
     let tracer = ImpactTracer::new(&index, config);
@@ -80,3 +86,420 @@ fn test_trace_with_zero_depth() {
     assert_eq!(graph.node_count(), 1);
     assert_eq!(graph.edge_count(), 0);
 }
+
+#[test]
+fn test_bean_dependency_edge_and_marker() {
+    use code_impact_analyzer::java_parser::JavaParser;
+    use code_impact_analyzer::language_parser::LanguageParser;
+    use std::path::Path;
+    
+    let source = r#"
+        package com.example.config;
+        
+        @Configuration
+        public class CacheConfig {
+            @Bean
+            public RedisConnectionFactory connectionFactory() {
+                return new LettuceConnectionFactory();
+            }
+            
+            @Bean
+            public RedisTemplate redisTemplate(RedisConnectionFactory factory) {
+                return new RedisTemplate(factory);
+            }
+        }
+    "#;
+    
+    let parser = JavaParser::new().unwrap();
+    let mut index = CodeIndex::new();
+    index.test_index_parsed_file(parser.parse_file(source, Path::new("CacheConfig.java")).unwrap()).unwrap();
+    
+    // 修改被依赖的 bean，影响依赖它的 bean
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.config.CacheConfig::connectionFactory".to_string()]).unwrap();
+    
+    assert!(graph.edges().any(|e| {
+        e.from == "method:com.example.config.CacheConfig::redisTemplate"
+            && e.to == "method:com.example.config.CacheConfig::connectionFactory"
+    }));
+    
+    for id in ["method:com.example.config.CacheConfig::connectionFactory", "method:com.example.config.CacheConfig::redisTemplate"] {
+        let node = graph.get_node(id).unwrap();
+        assert_eq!(node.metadata.properties.get("bean").map(String::as_str), Some("true"));
+    }
+}
+
+#[test]
+fn test_method_call_edge_records_call_site_line() {
+    use code_impact_analyzer::java_parser::JavaParser;
+    use code_impact_analyzer::language_parser::LanguageParser;
+    use std::path::Path;
+    
+    let order_source = r#"package com.example;
+
+public class OrderService {
+    private PaymentService paymentService;
+
+    public void placeOrder() {
+        log();
+        paymentService.charge();
+    }
+}
+"#;
+    let payment_source = r#"package com.example;
+
+public class PaymentService {
+    public void charge() {
+    }
+}
+"#;
+    
+    let parser = JavaParser::new().unwrap();
+    let mut index = CodeIndex::new();
+    index.test_index_parsed_file(parser.parse_file(order_source, Path::new("OrderService.java")).unwrap()).unwrap();
+    index.test_index_parsed_file(parser.parse_file(payment_source, Path::new("PaymentService.java")).unwrap()).unwrap();
+    
+    // 下游：placeOrder 第 8 行调用 charge
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.OrderService::placeOrder".to_string()]).unwrap();
+    let edge = graph.edges()
+        .find(|e| e.to == "method:com.example.PaymentService::charge")
+        .unwrap();
+    assert_eq!(edge.from, "method:com.example.OrderService::placeOrder");
+    assert_eq!(edge.source_line, Some(8));
+    
+    // 上游：从 charge 追溯到调用方时同样记录调用方中的行号
+    let graph = tracer.trace_impact(&["com.example.PaymentService::charge".to_string()]).unwrap();
+    let edge = graph.edges()
+        .find(|e| e.from == "method:com.example.OrderService::placeOrder")
+        .unwrap();
+    assert_eq!(edge.source_line, Some(8));
+    
+    // JSON 输出包含调用行号，重新加载后保持不变
+    let json = graph.to_json().unwrap();
+    assert!(json.contains("\"source_line\": 8"));
+    let reloaded = code_impact_analyzer::ImpactGraph::from_json(&json).unwrap();
+    assert_eq!(reloaded.edges().next().unwrap().source_line, Some(8));
+}
+
+#[test]
+fn test_inherited_method_call_resolves_to_superclass() {
+    use code_impact_analyzer::java_parser::JavaParser;
+    use code_impact_analyzer::language_parser::LanguageParser;
+    use std::path::Path;
+    
+    let base_source = r#"package com.example;
+
+public class BaseRepository {
+    public void save() {
+    }
+}
+"#;
+    let order_source = r#"package com.example;
+
+public class OrderRepository extends BaseRepository {
+    public void findOrder() {
+    }
+}
+"#;
+    let service_source = r#"package com.example;
+
+public class OrderService {
+    private OrderRepository orderRepository;
+
+    public void placeOrder() {
+        orderRepository.save();
+    }
+}
+"#;
+    
+    let parser = JavaParser::new().unwrap();
+    let mut index = CodeIndex::new();
+    for (source, file) in [
+        (base_source, "BaseRepository.java"),
+        (order_source, "OrderRepository.java"),
+        (service_source, "OrderService.java"),
+    ] {
+        index.test_index_parsed_file(parser.parse_file(source, Path::new(file)).unwrap()).unwrap();
+    }
+    
+    // 下游：通过子类调用未重写的方法，解析到父类的定义
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.OrderService::placeOrder".to_string()]).unwrap();
+    let edge = graph.edges()
+        .find(|e| e.from == "method:com.example.OrderService::placeOrder")
+        .unwrap();
+    assert_eq!(edge.to, "method:com.example.BaseRepository::save");
+    assert_eq!(edge.source_line, Some(7));
+    assert!(graph.get_node("method:com.example.OrderRepository::save").is_none());
+    
+    // 上游：父类方法的变更影响通过子类调用它的方法
+    let graph = tracer.trace_impact(&["com.example.BaseRepository::save".to_string()]).unwrap();
+    let edge = graph.edges()
+        .find(|e| e.to == "method:com.example.BaseRepository::save")
+        .unwrap();
+    assert_eq!(edge.from, "method:com.example.OrderService::placeOrder");
+    assert_eq!(edge.source_line, Some(7));
+}
+
+#[test]
+fn test_feature_flagged_code_is_marked_conditional() {
+    use code_impact_analyzer::java_parser::JavaParser;
+    use code_impact_analyzer::language_parser::LanguageParser;
+    use std::path::Path;
+    
+    let source = r#"package com.example;
+
+public class CheckoutService {
+    private FeatureFlags featureFlags;
+    private CouponService couponService;
+
+    @ConditionalOnProperty("feature.x")
+    public void applyCoupon() {
+    }
+
+    public void checkout() {
+        if (featureFlags.isEnabled("new-pricing")) {
+            couponService.reprice();
+            couponService.validate();
+        }
+        this.applyCoupon();
+        couponService.validate();
+    }
+}
+"#;
+    
+    let coupon_source = r#"package com.example;
+
+public class CouponService {
+    public void reprice() {
+    }
+
+    public void validate() {
+    }
+}
+"#;
+    
+    let parser = JavaParser::new().unwrap();
+    let mut index = CodeIndex::new();
+    for (source, file) in [(source, "CheckoutService.java"), (coupon_source, "CouponService.java")] {
+        index.test_index_parsed_file(parser.parse_file(source, Path::new(file)).unwrap()).unwrap();
+    }
+    
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.CheckoutService::checkout".to_string()]).unwrap();
+    
+    // @ConditionalOnProperty 方法节点携带开关名
+    let node = graph.get_node("method:com.example.CheckoutService::applyCoupon").unwrap();
+    assert_eq!(node.metadata.properties.get("conditional").map(String::as_str), Some("feature.x"));
+    let node = graph.get_node("method:com.example.CheckoutService::checkout").unwrap();
+    assert!(!node.metadata.properties.contains_key("conditional"));
+    
+    // 特性开关分支中的调用边携带开关名，其余调用边不受影响
+    let conditional = |to: &str| graph.edges()
+        .find(|e| e.to == to)
+        .map(|e| e.conditional.clone())
+        .unwrap();
+    assert_eq!(conditional("method:com.example.CouponService::reprice"), Some("new-pricing".to_string()));
+    assert_eq!(conditional("method:com.example.CheckoutService::applyCoupon"), None);
+    
+    // 同一方法在分支外也被调用时，调用总会发生，边不标记为条件调用
+    assert_eq!(conditional("method:com.example.CouponService::validate"), None);
+    
+    let json = graph.to_json().unwrap();
+    assert!(json.contains("\"conditional\": \"new-pricing\""));
+    assert!(graph.to_dot().contains("style=\"dashed\""));
+}
+
+#[test]
+fn test_resilience_annotations_are_marked() {
+    use code_impact_analyzer::java_parser::JavaParser;
+    use code_impact_analyzer::language_parser::LanguageParser;
+    use std::path::Path;
+    
+    let source = r#"package com.example;
+
+public class PaymentService {
+    private PaymentGateway gateway;
+
+    public void pay() {
+        gateway.charge();
+        gateway.refund();
+        gateway.audit();
+    }
+}
+"#;
+    
+    let gateway_source = r#"package com.example;
+
+public class PaymentGateway {
+    @Retryable(maxAttempts = 3)
+    public void charge() {
+    }
+
+    @CircuitBreaker(name = "refund", fallbackMethod = "refundFallback")
+    public void refund() {
+    }
+
+    public void audit() {
+    }
+}
+"#;
+    
+    let parser = JavaParser::new().unwrap();
+    let mut index = CodeIndex::new();
+    for (source, file) in [(source, "PaymentService.java"), (gateway_source, "PaymentGateway.java")] {
+        index.test_index_parsed_file(parser.parse_file(source, Path::new(file)).unwrap()).unwrap();
+    }
+    
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.PaymentService::pay".to_string()]).unwrap();
+    
+    let resilience = |id: &str| graph.get_node(id).unwrap().metadata.properties.get("resilience").cloned();
+    assert_eq!(resilience("method:com.example.PaymentGateway::charge").as_deref(), Some("retryable"));
+    assert_eq!(resilience("method:com.example.PaymentGateway::refund").as_deref(), Some("circuit_breaker"));
+    assert_eq!(resilience("method:com.example.PaymentGateway::audit"), None);
+    
+    // 指向容错方法的边在 DOT 中单独着色
+    let dot = graph.to_dot();
+    assert!(dot.contains("resilience=\"retryable\""));
+    assert!(dot.contains("resilience=\"circuit_breaker\""));
+}
+
+#[test]
+fn test_batch_tasklet_reachable_from_job_entry() {
+    use code_impact_analyzer::java_parser::JavaParser;
+    use code_impact_analyzer::language_parser::LanguageParser;
+    use code_impact_analyzer::impact_tracer::{EdgeType, NodeType};
+    use std::path::Path;
+    
+    let config_source = r#"package com.example.batch;
+
+@Configuration
+public class ReportJobConfig {
+    @Bean
+    public Job reportJob(Step reportStep) {
+        return jobs.get("dailyReport").start(reportStep).build();
+    }
+
+    @Bean
+    public Step reportStep(ReportTasklet reportTasklet) {
+        return steps.get("reportStep").tasklet(reportTasklet).build();
+    }
+}
+"#;
+    
+    let tasklet_source = r#"package com.example.batch;
+
+public class ReportTasklet implements Tasklet {
+    public RepeatStatus execute(StepContribution contribution, ChunkContext context) {
+        return RepeatStatus.FINISHED;
+    }
+}
+"#;
+    
+    let parser = JavaParser::new().unwrap();
+    let mut index = CodeIndex::new();
+    for (source, file) in [(config_source, "ReportJobConfig.java"), (tasklet_source, "ReportTasklet.java")] {
+        index.test_index_parsed_file(parser.parse_file(source, Path::new(file)).unwrap()).unwrap();
+    }
+    
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.batch.ReportTasklet::execute".to_string()]).unwrap();
+    
+    // 作业节点是入口点
+    let job = graph.get_node("batch:dailyReport").unwrap();
+    assert_eq!(job.node_type, NodeType::BatchJob { name: "dailyReport".to_string() });
+    assert_eq!(job.metadata.properties.get("entry_point").map(String::as_str), Some("true"));
+    
+    // 作业 -> 作业方法 -> 步骤方法 -> tasklet
+    let has_edge = |from: &str, to: &str| graph.edges().any(|e| e.from == from && e.to == to);
+    assert!(graph.edges().any(|e| e.from == "batch:dailyReport"
+        && e.to == "method:com.example.batch.ReportJobConfig::reportJob"
+        && e.edge_type == EdgeType::BatchLaunch));
+    assert!(has_edge(
+        "method:com.example.batch.ReportJobConfig::reportJob",
+        "method:com.example.batch.ReportJobConfig::reportStep",
+    ));
+    assert!(has_edge(
+        "method:com.example.batch.ReportJobConfig::reportStep",
+        "method:com.example.batch.ReportTasklet::execute",
+    ));
+}
+
+#[test]
+fn test_xml_batch_job_links_to_step_components() {
+    use code_impact_analyzer::config_parser::{ConfigParser, XmlConfigParser};
+    use code_impact_analyzer::java_parser::JavaParser;
+    use code_impact_analyzer::language_parser::LanguageParser;
+    use code_impact_analyzer::impact_tracer::EdgeType;
+    use std::path::Path;
+    
+    let job_xml = r#"<beans xmlns:batch="http://www.springframework.org/schema/batch">
+    <batch:job id="nightlyCleanup">
+        <batch:step id="cleanupStep">
+            <batch:tasklet ref="cleanupTasklet"/>
+        </batch:step>
+    </batch:job>
+    <bean id="cleanupTasklet" class="com.example.batch.CleanupTasklet"/>
+</beans>
+"#;
+    
+    let tasklet_source = r#"package com.example.batch;
+
+public class CleanupTasklet implements Tasklet {
+    public RepeatStatus execute(StepContribution contribution, ChunkContext context) {
+        return RepeatStatus.FINISHED;
+    }
+}
+"#;
+    
+    let parser = JavaParser::new().unwrap();
+    let mut index = CodeIndex::new();
+    index.test_index_parsed_file(parser.parse_file(tasklet_source, Path::new("CleanupTasklet.java")).unwrap()).unwrap();
+    index.associate_config_data(&XmlConfigParser.parse(job_xml).unwrap());
+    
+    // XML 作业的步骤组件是入口点
+    assert_eq!(index.entry_points(), vec!["com.example.batch.CleanupTasklet::execute"]);
+    
+    // 作业直接连接到 tasklet 方法
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.batch.CleanupTasklet::execute".to_string()]).unwrap();
+    assert!(graph.get_node("batch:nightlyCleanup").is_some());
+    assert!(graph.edges().any(|e| e.from == "batch:nightlyCleanup"
+        && e.to == "method:com.example.batch.CleanupTasklet::execute"
+        && e.edge_type == EdgeType::BatchLaunch));
+}
+
+#[test]
+fn test_openapi_summary_attached_to_endpoint_node() {
+    use code_impact_analyzer::java_parser::JavaParser;
+    use code_impact_analyzer::language_parser::LanguageParser;
+    use code_impact_analyzer::impact_tracer::NodeType;
+    use std::path::Path;
+    
+    let source = r#"package com.example;
+
+@RestController
+public class UserController {
+    @Operation(summary = "Get user")
+    @GetMapping("/users/{id}")
+    public User getUser(Long id) {
+        return null;
+    }
+}
+"#;
+    
+    let parser = JavaParser::new().unwrap();
+    let mut index = CodeIndex::new();
+    index.test_index_parsed_file(parser.parse_file(source, Path::new("UserController.java")).unwrap()).unwrap();
+    
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    let graph = tracer.trace_impact(&["com.example.UserController::getUser".to_string()]).unwrap();
+    
+    let endpoint = graph.nodes()
+        .find(|node| matches!(node.node_type, NodeType::HttpEndpoint { .. }))
+        .expect("HTTP endpoint node should exist");
+    assert_eq!(endpoint.metadata.properties.get("summary").map(String::as_str), Some("Get user"));
+    assert!(!endpoint.metadata.properties.contains_key("description"));
+}
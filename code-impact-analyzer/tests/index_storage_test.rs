@@ -24,11 +24,7 @@ fn test_index_lifecycle() {
         full_qualified_name: "com.example.Test::testMethod".to_string(),
         file_path: PathBuf::from("Test.java"),
         line_range: (10, 20),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     code_index.test_index_method(&method).unwrap();
@@ -75,11 +71,11 @@ fn test_index_validation() {
     
     // 验证索引有效
     let metadata = storage.get_index_info().unwrap().unwrap();
-    assert!(metadata.is_valid(&workspace_path));
+    assert!(metadata.is_valid(&workspace_path, storage.source_extensions()));
     
     // 使用不同的工作空间路径验证
     let other_path = PathBuf::from("/other/path");
-    assert!(!metadata.is_valid(&other_path));
+    assert!(!metadata.is_valid(&other_path, storage.source_extensions()));
 }
 
 #[test]
@@ -100,11 +96,7 @@ fn test_index_reload_after_save() {
             full_qualified_name: format!("com.example.Test::method{}", i),
             file_path: PathBuf::from("Test.java"),
             line_range: (i * 10, i * 10 + 10),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         code_index.test_index_method(&method).unwrap();
@@ -144,11 +136,7 @@ fn test_multiple_save_and_load_cycles() {
             full_qualified_name: format!("com.example.Test::method_cycle_{}", cycle),
             file_path: PathBuf::from("Test.java"),
             line_range: (cycle * 10, cycle * 10 + 10),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         code_index.test_index_method(&method).unwrap();
@@ -182,11 +170,7 @@ fn test_index_info_after_save() {
             full_qualified_name: format!("com.example.Test::method{}", i),
             file_path: PathBuf::from("Test.java"),
             line_range: (i * 10, i * 10 + 10),
-            calls: vec![],
-            http_annotations: None,
-            kafka_operations: vec![],
-            db_operations: vec![],
-            redis_operations: vec![],
+            ..Default::default()
         };
         
         code_index.test_index_method(&method).unwrap();
@@ -201,6 +185,82 @@ fn test_index_info_after_save() {
     // 验证信息
     assert_eq!(info.method_count, 5);
     assert_eq!(info.file_count, 1); // 所有方法在同一个文件中
-    assert_eq!(info.version, "1.0.0");
+    assert_eq!(info.version, "2.0.0");
     assert_eq!(info.workspace_path, workspace_path);
 }
+
+/// 简易 HTTP 服务器：对收到的第一个请求返回给定的 JSON 响应体
+fn serve_once(body: String) -> String {
+    use std::io::{Read, Write};
+    
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0u8; 4096];
+        let _ = stream.read(&mut request);
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        ).unwrap();
+    });
+    format!("http://{}/index.json", addr)
+}
+
+/// 远程索引测试在本地启动 HTTP 服务器，不依赖外部网络，默认构建下也会运行
+#[test]
+fn test_trace_against_remote_index() {
+    use code_impact_analyzer::{AnalysisOrchestrator, IndexSource, TraceConfig};
+    use std::fs;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let source = "public class Test {\n    public void caller() {\n        this.callee();\n    }\n\n    public void callee() {\n        System.out.println(\"old\");\n    }\n}\n";
+    
+    // 集中构建索引的工作空间
+    let server_workspace = temp_dir.path().join("server");
+    fs::create_dir(&server_workspace).unwrap();
+    fs::write(server_workspace.join("Test.java"), source).unwrap();
+    AnalysisOrchestrator::new(server_workspace.clone(), TraceConfig::default()).unwrap()
+        .list_http_endpoints()
+        .unwrap();
+    let index_json = fs::read_to_string(server_workspace.join(".code-impact-analyzer/index.json")).unwrap();
+    let url = serve_once(index_json);
+    
+    // 客户端在另一个位置检出同样的代码，只读取本地 patch
+    let client_workspace = temp_dir.path().join("client");
+    fs::create_dir(&client_workspace).unwrap();
+    fs::write(client_workspace.join("Test.java"), source).unwrap();
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        concat!(
+            "diff --git a/Test.java b/Test.java\n",
+            "index 0000000..1111111 100644\n",
+            "--- a/Test.java\n",
+            "+++ b/Test.java\n",
+            "@@ -7,1 +7,1 @@\n",
+            "-        System.out.println(\"older\");\n",
+            "+        System.out.println(\"old\");\n",
+        ),
+    ).unwrap();
+    
+    let mut orchestrator = AnalysisOrchestrator::new(client_workspace.clone(), TraceConfig::default()).unwrap();
+    orchestrator.set_index_source(IndexSource::Remote { url });
+    let result = orchestrator.analyze(&patch_path);
+    
+    // 未启用 remote-index 特性时明确报错，而不是悄悄在本地构建索引
+    if !cfg!(feature = "remote-index") {
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("remote-index"), "unexpected error: {}", error);
+        assert!(!client_workspace.join(".code-impact-analyzer").exists());
+        return;
+    }
+    let result = result.unwrap();
+    
+    // 远程索引中的源文件路径换算到客户端工作空间，变更方法和上游调用者都能追溯到
+    assert!(result.impact_graph.get_node("method:Test::callee").is_some());
+    assert!(result.impact_graph.get_node("method:Test::caller").is_some());
+    assert!(!client_workspace.join(".code-impact-analyzer").exists());
+}
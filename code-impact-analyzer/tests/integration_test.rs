@@ -16,10 +16,10 @@ fn test_main_run_with_invalid_workspace_path() {
     // 使用不存在的 workspace 路径
     let args = CliArgs {
         workspace_path: temp_dir.path().join("nonexistent"),
-        diff_path: patch_path,
-        output_format: OutputFormat::Dot,
-        max_depth: 10,
+        diff_path: Some(patch_path),
         log_level: LogLevel::Error,
+        color: ColorMode::Never,
+        ..Default::default()
     };
     
     // 运行应该失败
@@ -35,10 +35,10 @@ fn test_main_run_with_invalid_diff_path() {
     // 使用不存在的 diff 文件
     let args = CliArgs {
         workspace_path: temp_dir.path().to_path_buf(),
-        diff_path: temp_dir.path().join("nonexistent.patch"),
-        output_format: OutputFormat::Dot,
-        max_depth: 10,
+        diff_path: Some(temp_dir.path().join("nonexistent.patch")),
         log_level: LogLevel::Error,
+        color: ColorMode::Never,
+        ..Default::default()
     };
     
     // 运行应该失败
@@ -69,10 +69,11 @@ fn test_output_format_selection() {
         
         let args = CliArgs {
             workspace_path: workspace,
-            diff_path: patch_path,
+            diff_path: Some(patch_path),
             output_format: format,
-            max_depth: 10,
             log_level: LogLevel::Error,
+            color: ColorMode::Never,
+            ..Default::default()
         };
         
         // 运行分析（可能会失败，但不应该 panic）
@@ -1,6 +1,5 @@
 use code_impact_analyzer::code_index::CodeIndex;
 use code_impact_analyzer::language_parser::MethodInfo;
-use code_impact_analyzer::impact_tracer::{ImpactTracer, TraceConfig};
 use std::path::PathBuf;
 
 #[test]
@@ -13,11 +12,7 @@ fn test_interface_with_single_implementation() {
         full_qualified_name: "com.example.ShopCopyService::query".to_string(),
         file_path: PathBuf::from("ShopCopyService.java"),
         line_range: (10, 12),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 创建实现类方法
@@ -26,11 +21,7 @@ fn test_interface_with_single_implementation() {
         full_qualified_name: "com.example.ShopCopyServiceImpl::query".to_string(),
         file_path: PathBuf::from("ShopCopyServiceImpl.java"),
         line_range: (20, 30),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 索引方法
@@ -109,7 +100,7 @@ fn test_interface_resolution_with_java_parser() {
 #[test]
 fn test_interface_resolution_in_call_chain() {
     use code_impact_analyzer::java_parser::JavaParser;
-    use code_impact_analyzer::language_parser::{LanguageParser, MethodCall};
+    use code_impact_analyzer::language_parser::LanguageParser;
     
     let parser = JavaParser::new().unwrap();
     
@@ -19,11 +19,7 @@ fn test_interface_upstream_tracing() {
         full_qualified_name: "com.example.Service::execute".to_string(),
         file_path: PathBuf::from("Service.java"),
         line_range: (10, 12),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 定义实现类方法
@@ -32,11 +28,7 @@ fn test_interface_upstream_tracing() {
         full_qualified_name: "com.example.ServiceImpl::execute".to_string(),
         file_path: PathBuf::from("ServiceImpl.java"),
         line_range: (20, 30),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 定义 Controller 方法，调用接口方法
@@ -49,12 +41,11 @@ fn test_interface_upstream_tracing() {
             MethodCall {
                 target: "com.example.Service::execute".to_string(),
                 line: 18,
+                condition: None,
+                low_confidence: false,
             }
         ],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 创建接口类
@@ -64,6 +55,7 @@ fn test_interface_upstream_tracing() {
         methods: vec![interface_method.clone()],
         is_interface: true,
         implements: vec![],
+        extends: None,
     };
     
     // 创建实现类
@@ -73,6 +65,7 @@ fn test_interface_upstream_tracing() {
         methods: vec![impl_method.clone()],
         is_interface: false,
         implements: vec!["com.example.Service".to_string()],
+        extends: None,
     };
     
     // 创建 Controller 类
@@ -82,6 +75,7 @@ fn test_interface_upstream_tracing() {
         methods: vec![controller_method.clone()],
         is_interface: false,
         implements: vec![],
+        extends: None,
     };
     
     // 索引所有类
@@ -91,6 +85,9 @@ fn test_interface_upstream_tracing() {
         classes: vec![interface_class],
         functions: vec![],
         imports: vec![],
+        string_constants: vec![],
+        functional_routes: vec![],
+        syntax_errors: Vec::new(),
     };
     
     let impl_file = ParsedFile {
@@ -99,6 +96,9 @@ fn test_interface_upstream_tracing() {
         classes: vec![impl_class],
         functions: vec![],
         imports: vec![],
+        string_constants: vec![],
+        functional_routes: vec![],
+        syntax_errors: Vec::new(),
     };
     
     let controller_file = ParsedFile {
@@ -107,6 +107,9 @@ fn test_interface_upstream_tracing() {
         classes: vec![controller_class],
         functions: vec![],
         imports: vec![],
+        string_constants: vec![],
+        functional_routes: vec![],
+        syntax_errors: Vec::new(),
     };
     
     index.test_index_parsed_file(interface_file).unwrap();
@@ -133,6 +136,9 @@ fn test_interface_upstream_tracing() {
         trace_upstream: true,
         trace_downstream: false,
         trace_cross_service: false,
+        allowed_services: Vec::new(),
+        max_bridge_fanout: None,
+        diagnostic_filter: None,
     };
     
     let tracer = ImpactTracer::new(&index, config);
@@ -162,11 +168,7 @@ fn test_multiple_interfaces_upstream_tracing() {
         full_qualified_name: "com.example.MultiImpl::process".to_string(),
         file_path: PathBuf::from("MultiImpl.java"),
         line_range: (20, 30),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 接口1方法
@@ -175,11 +177,7 @@ fn test_multiple_interfaces_upstream_tracing() {
         full_qualified_name: "com.example.Interface1::process".to_string(),
         file_path: PathBuf::from("Interface1.java"),
         line_range: (10, 12),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 接口2方法
@@ -188,11 +186,7 @@ fn test_multiple_interfaces_upstream_tracing() {
         full_qualified_name: "com.example.Interface2::process".to_string(),
         file_path: PathBuf::from("Interface2.java"),
         line_range: (10, 12),
-        calls: vec![],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // Caller1 调用 Interface1::process
@@ -205,12 +199,11 @@ fn test_multiple_interfaces_upstream_tracing() {
             MethodCall {
                 target: "com.example.Interface1::process".to_string(),
                 line: 18,
+                condition: None,
+                low_confidence: false,
             }
         ],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // Caller2 调用 Interface2::process
@@ -223,12 +216,11 @@ fn test_multiple_interfaces_upstream_tracing() {
             MethodCall {
                 target: "com.example.Interface2::process".to_string(),
                 line: 18,
+                condition: None,
+                low_confidence: false,
             }
         ],
-        http_annotations: None,
-        kafka_operations: vec![],
-        db_operations: vec![],
-        redis_operations: vec![],
+        ..Default::default()
     };
     
     // 创建类
@@ -241,6 +233,7 @@ fn test_multiple_interfaces_upstream_tracing() {
             "com.example.Interface1".to_string(),
             "com.example.Interface2".to_string(),
         ],
+        extends: None,
     };
     
     let interface1_class = ClassInfo {
@@ -249,6 +242,7 @@ fn test_multiple_interfaces_upstream_tracing() {
         methods: vec![interface1_method.clone()],
         is_interface: true,
         implements: vec![],
+        extends: None,
     };
     
     let interface2_class = ClassInfo {
@@ -257,6 +251,7 @@ fn test_multiple_interfaces_upstream_tracing() {
         methods: vec![interface2_method.clone()],
         is_interface: true,
         implements: vec![],
+        extends: None,
     };
     
     let caller1_class = ClassInfo {
@@ -265,6 +260,7 @@ fn test_multiple_interfaces_upstream_tracing() {
         methods: vec![caller1_method.clone()],
         is_interface: false,
         implements: vec![],
+        extends: None,
     };
     
     let caller2_class = ClassInfo {
@@ -273,6 +269,7 @@ fn test_multiple_interfaces_upstream_tracing() {
         methods: vec![caller2_method.clone()],
         is_interface: false,
         implements: vec![],
+        extends: None,
     };
     
     // 索引所有类
@@ -282,6 +279,9 @@ fn test_multiple_interfaces_upstream_tracing() {
         classes: vec![impl_class],
         functions: vec![],
         imports: vec![],
+        string_constants: vec![],
+        functional_routes: vec![],
+        syntax_errors: Vec::new(),
     }).unwrap();
     
     index.test_index_parsed_file(ParsedFile {
@@ -290,6 +290,9 @@ fn test_multiple_interfaces_upstream_tracing() {
         classes: vec![interface1_class],
         functions: vec![],
         imports: vec![],
+        string_constants: vec![],
+        functional_routes: vec![],
+        syntax_errors: Vec::new(),
     }).unwrap();
     
     index.test_index_parsed_file(ParsedFile {
@@ -298,6 +301,9 @@ fn test_multiple_interfaces_upstream_tracing() {
         classes: vec![interface2_class],
         functions: vec![],
         imports: vec![],
+        string_constants: vec![],
+        functional_routes: vec![],
+        syntax_errors: Vec::new(),
     }).unwrap();
     
     index.test_index_parsed_file(ParsedFile {
@@ -306,6 +312,9 @@ fn test_multiple_interfaces_upstream_tracing() {
         classes: vec![caller1_class],
         functions: vec![],
         imports: vec![],
+        string_constants: vec![],
+        functional_routes: vec![],
+        syntax_errors: Vec::new(),
     }).unwrap();
     
     index.test_index_parsed_file(ParsedFile {
@@ -314,6 +323,9 @@ fn test_multiple_interfaces_upstream_tracing() {
         classes: vec![caller2_class],
         functions: vec![],
         imports: vec![],
+        string_constants: vec![],
+        functional_routes: vec![],
+        syntax_errors: Vec::new(),
     }).unwrap();
     
     // 验证接口实现关系
@@ -328,6 +340,9 @@ fn test_multiple_interfaces_upstream_tracing() {
         trace_upstream: true,
         trace_downstream: false,
         trace_cross_service: false,
+        allowed_services: Vec::new(),
+        max_bridge_fanout: None,
+        diagnostic_filter: None,
     };
     
     let tracer = ImpactTracer::new(&index, config);
@@ -1,6 +1,6 @@
-/// 并行处理集成测试
-/// 
-/// 验证 rayon 并行处理功能正常工作
+//! 并行处理集成测试
+//!
+//! 验证 rayon 并行处理功能正常工作
 
 use code_impact_analyzer::code_index::CodeIndex;
 use code_impact_analyzer::java_parser::JavaParser;
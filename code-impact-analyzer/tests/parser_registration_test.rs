@@ -0,0 +1,144 @@
+use code_impact_analyzer::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// 测试用的自定义解析器：把 `.foo` 文件中每个 `def name` 行识别为一个函数
+struct FooParser;
+
+impl LanguageParser for FooParser {
+    fn language_name(&self) -> &str {
+        "foo"
+    }
+
+    fn file_extensions(&self) -> &[&str] {
+        &["foo"]
+    }
+
+    fn parse_file(&self, content: &str, file_path: &Path) -> Result<ParsedFile, ParseError> {
+        let functions = content
+            .lines()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let name = line.trim().strip_prefix("def ")?;
+                Some(FunctionInfo {
+                    name: name.to_string(),
+                    full_qualified_name: format!("foo::{}", name),
+                    file_path: file_path.to_path_buf(),
+                    line_range: (i + 1, i + 2),
+                    ..Default::default()
+                })
+            })
+            .collect();
+
+        Ok(ParsedFile {
+            file_path: file_path.to_path_buf(),
+            language: "foo".to_string(),
+            classes: vec![],
+            functions,
+            imports: vec![],
+            string_constants: vec![],
+            functional_routes: vec![],
+            syntax_errors: Vec::new(),
+        })
+    }
+}
+
+#[test]
+fn test_registered_parser_receives_custom_extension() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+
+    fs::write(workspace.join("script.foo"), "def alpha\n  body\ndef beta\n  body\n").unwrap();
+
+    // 修改 beta 的函数体
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/script.foo b/script.foo\nindex 0000000..1111111 100644\n--- a/script.foo\n+++ b/script.foo\n@@ -4,1 +4,1 @@\n-  old\n+  body\n",
+    ).unwrap();
+
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    orchestrator.register_parser(Box::new(FooParser));
+
+    let changed = orchestrator.list_changed_methods(&patch_path).unwrap();
+    assert_eq!(changed, vec!["foo::beta".to_string()]);
+}
+
+#[test]
+fn test_registered_parser_bypasses_saved_index() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+
+    fs::write(workspace.join("Main.java"), "public class Main {\n    public void run() {}\n}\n").unwrap();
+    fs::write(workspace.join("script.foo"), "def alpha\n  body\n").unwrap();
+
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/script.foo b/script.foo\nindex 0000000..1111111 100644\n--- a/script.foo\n+++ b/script.foo\n@@ -2,1 +2,1 @@\n-  old\n+  body\n",
+    ).unwrap();
+
+    // 只用内置解析器构建并保存磁盘索引，其中没有 .foo 文件的方法
+    let mut orchestrator = AnalysisOrchestrator::new(workspace.clone(), TraceConfig::default()).unwrap();
+    assert!(orchestrator.list_changed_methods(&patch_path).unwrap().is_empty());
+    assert!(IndexStorage::new(workspace.clone()).index_exists());
+
+    // 注册解析器后不强制重建也能看到 .foo 文件的方法
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    orchestrator.register_parser(Box::new(FooParser));
+    let changed = orchestrator.list_changed_methods(&patch_path).unwrap();
+    assert_eq!(changed, vec!["foo::alpha".to_string()]);
+}
+
+#[test]
+fn test_unregistered_extension_is_ignored() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+
+    fs::write(workspace.join("script.foo"), "def alpha\n  body\n").unwrap();
+
+    let patch_path = temp_dir.path().join("test.patch");
+    fs::write(
+        &patch_path,
+        "diff --git a/script.foo b/script.foo\nindex 0000000..1111111 100644\n--- a/script.foo\n+++ b/script.foo\n@@ -2,1 +2,1 @@\n-  old\n+  body\n",
+    ).unwrap();
+
+    // 未注册自定义解析器时，.foo 文件不会被索引
+    let mut orchestrator = AnalysisOrchestrator::new(workspace, TraceConfig::default()).unwrap();
+    let changed = orchestrator.list_changed_methods(&patch_path).unwrap();
+    assert!(changed.is_empty());
+}
+
+#[test]
+fn test_detect_registered_parser_extension() {
+    let parsers: Vec<Box<dyn LanguageParser>> = vec![Box::new(FooParser)];
+
+    assert_eq!(LanguageDetector::detect_language(Path::new("a/b.foo"), &parsers), Some("foo"));
+    assert_eq!(LanguageDetector::detect_language(Path::new("a/b.FOO"), &parsers), Some("foo"));
+    // 未注册 Java 解析器时不识别 .java
+    assert_eq!(LanguageDetector::detect_language(Path::new("A.java"), &parsers), None);
+    assert_eq!(LanguageDetector::detect_language(Path::new("Makefile"), &parsers), None);
+}
+
+#[test]
+fn test_index_workspace_uses_registered_parser_extensions() {
+    let temp_dir = TempDir::new().unwrap();
+    let nested = temp_dir.path().join("nested");
+    fs::create_dir(&nested).unwrap();
+    fs::write(temp_dir.path().join("a.foo"), "def alpha\n").unwrap();
+    fs::write(nested.join("b.foo"), "def beta\n").unwrap();
+    fs::write(temp_dir.path().join("Main.java"), "public class Main {\n    public void run() {}\n}\n").unwrap();
+
+    let parsers: Vec<Box<dyn LanguageParser>> = vec![Box::new(FooParser)];
+    let mut index = CodeIndex::new();
+    index.index_workspace(temp_dir.path(), &parsers).unwrap();
+
+    // 只索引已注册解析器声明的扩展名，包括子目录中的文件
+    let mut methods: Vec<&str> = index.methods().map(|(name, _)| name.as_str()).collect();
+    methods.sort();
+    assert_eq!(methods, vec!["foo::alpha", "foo::beta"]);
+}
@@ -0,0 +1,122 @@
+//! 追溯决策日志（`--trace-debug`）测试
+//!
+//! 测试需要安装全局日志器并打开 trace 级别，单独放在一个测试二进制中，
+//! 避免影响其他测试的日志输出
+
+use code_impact_analyzer::{CodeIndex, ImpactTracer, JavaParser, LanguageParser, TraceConfig, TRACE_DEBUG_TARGET};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// 收集追溯决策日志的测试日志器
+struct TraceDebugCapture;
+
+static TRACE_DEBUG_LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+impl log::Log for TraceDebugCapture {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target() == TRACE_DEBUG_TARGET
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            TRACE_DEBUG_LINES.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: TraceDebugCapture = TraceDebugCapture;
+
+/// 安装测试日志器并打开 trace 级别，重复调用时沿用已安装的日志器
+fn install_logger() {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(log::LevelFilter::Trace);
+}
+
+/// 返回目前收集到的追溯决策日志
+fn captured_lines() -> Vec<String> {
+    TRACE_DEBUG_LINES.lock().unwrap().clone()
+}
+
+/// 解析源码并按方法建立索引
+fn index_source(source: &str, file_name: &str) -> CodeIndex {
+    let parser = JavaParser::new().unwrap();
+    let parsed = parser.parse_file(source, Path::new(file_name)).unwrap();
+    let mut index = CodeIndex::new();
+    for method in parsed.classes.iter().flat_map(|class| &class.methods) {
+        index.index_method(method).unwrap();
+    }
+    index
+}
+
+#[test]
+fn test_trace_debug_records_skipped_external_callee() {
+    install_logger();
+    let index = index_source(r#"
+package com.example;
+
+import org.thirdparty.Mailer;
+
+public class OrderService {
+    private Mailer mailer;
+    
+    public void place() {
+        mailer.send();
+    }
+}
+"#, "OrderService.java");
+
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    tracer.trace_impact(&["com.example.OrderService::place".to_string()]).unwrap();
+
+    let lines = captured_lines();
+    assert!(lines.iter().any(|line| line.contains("downstream com.example.OrderService::place")));
+    assert!(lines.iter().any(|line| line.contains("skipped external callee org.thirdparty.Mailer::send")));
+}
+
+#[test]
+fn test_diagnostic_filter_limits_trace_debug_to_matching_methods() {
+    install_logger();
+    let index = index_source(r#"
+package com.example;
+
+import org.thirdparty.Audit;
+
+public class BillingService {
+    private Calculator calculator;
+    
+    public void charge() {
+        calculator.compute();
+    }
+}
+
+class Calculator {
+    private Audit audit;
+    
+    public void compute() {
+        audit.record();
+    }
+}
+"#, "BillingService.java");
+
+    let config = TraceConfig::builder()
+        .diagnostic_filter(Some("BillingService::charge".to_string()))
+        .build()
+        .unwrap();
+    let tracer = ImpactTracer::new(&index, config);
+    tracer.trace_impact(&["com.example.BillingService::charge".to_string()]).unwrap();
+
+    // 只记录匹配过滤器的方法的决策，compute 的决策被过滤
+    let lines = captured_lines();
+    assert!(lines.iter().any(|line| line.contains("downstream com.example.BillingService::charge")));
+    assert!(!lines.iter().any(|line| line.contains("downstream com.example.Calculator::compute")));
+    assert!(!lines.iter().any(|line| line.contains("org.thirdparty.Audit::record")));
+
+    // 未设置过滤器时记录所有方法
+    let tracer = ImpactTracer::new(&index, TraceConfig::default());
+    tracer.trace_impact(&["com.example.BillingService::charge".to_string()]).unwrap();
+    let lines = captured_lines();
+    assert!(lines.iter().any(|line| line.contains("downstream com.example.Calculator::compute")));
+    assert!(lines.iter().any(|line| line.contains("skipped external callee org.thirdparty.Audit::record")));
+}